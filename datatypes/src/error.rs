@@ -348,6 +348,18 @@ pub enum Error {
         expected: usize,
         found: usize,
     },
+
+    #[snafu(display(
+        "Tile {}/{}/{} is not part of the WebMercatorQuad tile matrix set",
+        z,
+        x,
+        y
+    ))]
+    InvalidTileIndex {
+        z: u8,
+        x: u32,
+        y: u32,
+    },
 }
 
 impl From<arrow::error::ArrowError> for Error {
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use image::RgbaImage;
+
+use crate::error;
+use crate::operations::image::font::{draw_text, text_width, GLYPH_HEIGHT};
+use crate::operations::image::to_png::encode_image;
+use crate::operations::image::{Colorizer, ImageOutputFormat, RgbaColor};
+use crate::util::Result;
+
+const MARGIN: u32 = 4;
+const TITLE_SPACING: u32 = 6;
+const RAMP_WIDTH: u32 = 20;
+const MIN_RAMP_HEIGHT: u32 = 150;
+const RAMP_HEIGHT_PER_BREAKPOINT: u32 = 12;
+const TICK_LENGTH: u32 = 4;
+const LABEL_SPACING: u32 = 4;
+const SWATCH_HEIGHT: u32 = 10;
+const SWATCH_SPACING: u32 = 4;
+
+/// Settings for rendering the legend of a `Colorizer`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegendOptions {
+    /// A caption above the legend, e.g., the measurement and its unit
+    pub title: Option<String>,
+    /// Labels for palette values, e.g., the classes of a classification measurement
+    pub class_labels: HashMap<u8, String>,
+}
+
+pub trait ToLegendPng {
+    /// Outputs png bytes of a legend image that explains the mapping of values to colors
+    fn to_legend_png(&self, options: &LegendOptions) -> Result<Vec<u8>>;
+}
+
+impl ToLegendPng for Colorizer {
+    fn to_legend_png(&self, options: &LegendOptions) -> Result<Vec<u8>> {
+        let image = LegendLayout::try_new(self, options)?.render(self);
+
+        encode_image(image, ImageOutputFormat::Png)
+    }
+}
+
+/// The contents of a legend, positioned from top to bottom
+struct LegendLayout {
+    title: Option<String>,
+    /// labels of the breakpoints with their relative position on the ramp (0 = bottom, 1 = top)
+    ramp_labels: Vec<(f64, String)>,
+    swatches: Vec<(RgbaColor, String)>,
+}
+
+impl LegendLayout {
+    fn try_new(colorizer: &Colorizer, options: &LegendOptions) -> Result<Self> {
+        let (ramp_labels, swatches) = match colorizer {
+            Colorizer::LinearGradient {
+                breakpoints,
+                no_data_color,
+                over_color,
+                under_color,
+            }
+            | Colorizer::LogarithmicGradient {
+                breakpoints,
+                no_data_color,
+                over_color,
+                under_color,
+            } => {
+                let ramp_labels = breakpoints
+                    .iter()
+                    .map(|breakpoint| {
+                        (
+                            ramp_position(colorizer, *breakpoint.value),
+                            format_value(*breakpoint.value),
+                        )
+                    })
+                    .collect();
+
+                let swatches = vec![
+                    (*over_color, "over".to_string()),
+                    (*under_color, "under".to_string()),
+                    (*no_data_color, "no data".to_string()),
+                ];
+
+                (ramp_labels, swatches)
+            }
+            Colorizer::Palette {
+                colors,
+                no_data_color,
+                default_color,
+            } => {
+                let mut classes: Vec<_> = colors.inner().iter().collect();
+                classes.sort_unstable_by_key(|(value, _)| **value);
+
+                let mut swatches: Vec<(RgbaColor, String)> = classes
+                    .into_iter()
+                    .map(|(value, color)| {
+                        (
+                            *color,
+                            class_label(value.into_inner(), &options.class_labels),
+                        )
+                    })
+                    .collect();
+
+                swatches.push((*default_color, "other".to_string()));
+                swatches.push((*no_data_color, "no data".to_string()));
+
+                (Vec::new(), swatches)
+            }
+            Colorizer::Rgba => {
+                return Err(error::Error::Colorizer {
+                    details: "Cannot create a legend for an RGBA colorizer".to_string(),
+                })
+            }
+        };
+
+        Ok(Self {
+            title: options.title.clone(),
+            ramp_labels,
+            swatches,
+        })
+    }
+
+    fn title_height(&self) -> u32 {
+        if self.title.is_some() {
+            GLYPH_HEIGHT + TITLE_SPACING
+        } else {
+            0
+        }
+    }
+
+    fn ramp_height(&self) -> u32 {
+        if self.ramp_labels.is_empty() {
+            return 0;
+        }
+
+        u32::max(
+            MIN_RAMP_HEIGHT,
+            self.ramp_labels.len() as u32 * RAMP_HEIGHT_PER_BREAKPOINT,
+        )
+    }
+
+    /// The space that the ramp occupies including the overhang of its top and bottom labels
+    fn ramp_block_height(&self) -> u32 {
+        if self.ramp_labels.is_empty() {
+            return 0;
+        }
+
+        GLYPH_HEIGHT / 2 + self.ramp_height() + GLYPH_HEIGHT / 2 + SWATCH_SPACING
+    }
+
+    fn width(&self) -> u32 {
+        let title_width = self.title.as_deref().map_or(0, text_width);
+
+        let label_width = self
+            .ramp_labels
+            .iter()
+            .map(|(_, label)| label)
+            .chain(self.swatches.iter().map(|(_, label)| label))
+            .map(|label| text_width(label))
+            .max()
+            .unwrap_or_default();
+
+        let entries_width = RAMP_WIDTH + TICK_LENGTH + LABEL_SPACING + label_width;
+
+        MARGIN + u32::max(title_width, entries_width) + MARGIN
+    }
+
+    fn height(&self) -> u32 {
+        MARGIN
+            + self.title_height()
+            + self.ramp_block_height()
+            + self.swatches.len() as u32 * (SWATCH_HEIGHT + SWATCH_SPACING)
+            + MARGIN
+    }
+
+    fn render(&self, colorizer: &Colorizer) -> RgbaImage {
+        let mut image = RgbaImage::from_pixel(self.width(), self.height(), WHITE);
+        let label_x = MARGIN + RAMP_WIDTH + TICK_LENGTH + LABEL_SPACING;
+
+        let mut y = MARGIN;
+
+        if let Some(title) = &self.title {
+            draw_text(&mut image, BLACK, MARGIN, y, title);
+            y += self.title_height();
+        }
+
+        if !self.ramp_labels.is_empty() {
+            let ramp_top = y + GLYPH_HEIGHT / 2;
+            let ramp_height = self.ramp_height();
+
+            draw_ramp(&mut image, colorizer, MARGIN, ramp_top, ramp_height);
+
+            for (position, label) in &self.ramp_labels {
+                let tick_y =
+                    ramp_top + f64::round((1. - position) * f64::from(ramp_height - 1)) as u32;
+
+                for tick_x in MARGIN + RAMP_WIDTH..MARGIN + RAMP_WIDTH + TICK_LENGTH {
+                    image.put_pixel(tick_x, tick_y, BLACK);
+                }
+
+                draw_text(&mut image, BLACK, label_x, tick_y - GLYPH_HEIGHT / 2, label);
+            }
+
+            y += self.ramp_block_height();
+        }
+
+        for (color, label) in &self.swatches {
+            draw_swatch(&mut image, MARGIN, y, *color);
+            draw_text(
+                &mut image,
+                BLACK,
+                label_x,
+                y + (SWATCH_HEIGHT - GLYPH_HEIGHT) / 2,
+                label,
+            );
+
+            y += SWATCH_HEIGHT + SWATCH_SPACING;
+        }
+
+        image
+    }
+}
+
+const WHITE: image::Rgba<u8> = image::Rgba([255, 255, 255, 255]);
+const BLACK: image::Rgba<u8> = image::Rgba([0, 0, 0, 255]);
+
+/// Computes the relative position of `value` on the ramp of a gradient colorizer.
+/// Logarithmic gradients position their values in log space.
+fn ramp_position(colorizer: &Colorizer, value: f64) -> f64 {
+    let (min, max) = (colorizer.min_value(), colorizer.max_value());
+
+    let position = match colorizer {
+        Colorizer::LogarithmicGradient { .. } => {
+            (value.log10() - min.log10()) / (max.log10() - min.log10())
+        }
+        _ => (value - min) / (max - min),
+    };
+
+    position.clamp(0., 1.)
+}
+
+/// Inverse of `ramp_position`
+fn ramp_value(colorizer: &Colorizer, position: f64) -> f64 {
+    let (min, max) = (colorizer.min_value(), colorizer.max_value());
+
+    let value = match colorizer {
+        Colorizer::LogarithmicGradient { .. } => {
+            10_f64.powf(min.log10() + position * (max.log10() - min.log10()))
+        }
+        _ => min + position * (max - min),
+    };
+
+    value.clamp(min, max)
+}
+
+fn draw_ramp(image: &mut RgbaImage, colorizer: &Colorizer, x: u32, y: u32, height: u32) {
+    let color_mapper = colorizer.create_color_mapper();
+
+    for row in 0..height {
+        let position = 1. - f64::from(row) / f64::from(u32::max(height - 1, 1));
+        let color = blend_on_white(color_mapper.call(ramp_value(colorizer, position)));
+
+        for column in 0..RAMP_WIDTH {
+            image.put_pixel(x + column, y + row, color);
+        }
+    }
+
+    draw_border(image, x, y, RAMP_WIDTH, height);
+}
+
+fn draw_swatch(image: &mut RgbaImage, x: u32, y: u32, color: RgbaColor) {
+    let color = blend_on_white(color);
+
+    for row in 0..SWATCH_HEIGHT {
+        for column in 0..RAMP_WIDTH {
+            image.put_pixel(x + column, y + row, color);
+        }
+    }
+
+    draw_border(image, x, y, RAMP_WIDTH, SWATCH_HEIGHT);
+}
+
+fn draw_border(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32) {
+    for column in x..x + width {
+        image.put_pixel(column, y, BLACK);
+        image.put_pixel(column, y + height - 1, BLACK);
+    }
+    for row in y..y + height {
+        image.put_pixel(x, row, BLACK);
+        image.put_pixel(x + width - 1, row, BLACK);
+    }
+}
+
+/// Composes a (semi-)transparent color onto the white legend background
+fn blend_on_white(color: RgbaColor) -> image::Rgba<u8> {
+    let [r, g, b, a] = color.into_inner();
+    let alpha = f64::from(a) / 255.;
+
+    let blend = |channel: u8| (f64::from(channel) * alpha + 255. * (1. - alpha)).round() as u8;
+
+    image::Rgba([blend(r), blend(g), blend(b), 255])
+}
+
+fn class_label(value: f64, class_labels: &HashMap<u8, String>) -> String {
+    if value.fract() == 0. && (0. ..=f64::from(u8::MAX)).contains(&value) {
+        if let Some(label) = class_labels.get(&(value as u8)) {
+            return label.clone();
+        }
+    }
+
+    format_value(value)
+}
+
+/// Formats a value with at most three decimals or in scientific notation for very large and small values
+fn format_value(value: f64) -> String {
+    let magnitude = value.abs();
+
+    if magnitude != 0. && !(1e-3..1e6).contains(&magnitude) {
+        return format!("{value:e}");
+    }
+
+    let formatted = format!("{value:.3}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    if formatted == "-0" {
+        "0".to_string()
+    } else {
+        formatted.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryInto;
+
+    fn linear_colorizer() -> Colorizer {
+        Colorizer::linear_gradient(
+            vec![
+                (0.0, RgbaColor::black()).try_into().unwrap(),
+                (0.5, RgbaColor::red()).try_into().unwrap(),
+                (1.0, RgbaColor::white()).try_into().unwrap(),
+            ],
+            RgbaColor::transparent(),
+            RgbaColor::blue(),
+            RgbaColor::pink(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_formats_values() {
+        assert_eq!(format_value(0.), "0");
+        assert_eq!(format_value(-0.), "0");
+        assert_eq!(format_value(1.), "1");
+        assert_eq!(format_value(0.25), "0.25");
+        assert_eq!(format_value(1.0 / 3.0), "0.333");
+        assert_eq!(format_value(-42.5), "-42.5");
+        assert_eq!(format_value(1e7), "1e7");
+        assert_eq!(format_value(0.0001), "1e-4");
+    }
+
+    #[test]
+    fn it_positions_logarithmic_breakpoints() {
+        let colorizer = Colorizer::logarithmic_gradient(
+            vec![
+                (1.0, RgbaColor::black()).try_into().unwrap(),
+                (10.0, RgbaColor::white()).try_into().unwrap(),
+                (100.0, RgbaColor::white()).try_into().unwrap(),
+            ],
+            RgbaColor::transparent(),
+            RgbaColor::transparent(),
+            RgbaColor::transparent(),
+        )
+        .unwrap();
+
+        assert!((ramp_position(&colorizer, 10.) - 0.5).abs() < 1e-9);
+        assert!((ramp_value(&colorizer, 0.5) - 10.).abs() < 1e-9);
+
+        assert!((ramp_position(&linear_colorizer(), 0.25) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn it_creates_gradient_legends() {
+        let options = LegendOptions {
+            title: Some("NDVI".to_string()),
+            class_labels: HashMap::new(),
+        };
+
+        let layout = LegendLayout::try_new(&linear_colorizer(), &options).unwrap();
+
+        assert_eq!(
+            layout.ramp_labels,
+            vec![
+                (0.0, "0".to_string()),
+                (0.5, "0.5".to_string()),
+                (1.0, "1".to_string())
+            ]
+        );
+        assert_eq!(
+            layout.swatches,
+            vec![
+                (RgbaColor::blue(), "over".to_string()),
+                (RgbaColor::pink(), "under".to_string()),
+                (RgbaColor::transparent(), "no data".to_string()),
+            ]
+        );
+
+        let image = layout.render(&linear_colorizer());
+
+        assert_eq!(image.width(), layout.width());
+        assert_eq!(image.height(), layout.height());
+
+        // the top of the ramp shows the maximum value, i.e., white, and the bottom the minimum value, i.e., black
+        let ramp_top = MARGIN + layout.title_height() + GLYPH_HEIGHT / 2;
+        let ramp_bottom = ramp_top + layout.ramp_height() - 1;
+        let [r, g, b, _] = image.get_pixel(MARGIN + RAMP_WIDTH / 2, ramp_top + 1).0;
+        assert!(r > 240 && g > 240 && b > 240);
+        let [r, g, b, _] = image.get_pixel(MARGIN + RAMP_WIDTH / 2, ramp_bottom - 1).0;
+        assert!(r < 15 && g < 15 && b < 15);
+
+        let png = linear_colorizer().to_legend_png(&options).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+
+    #[test]
+    fn it_creates_palette_legends() {
+        let colorizer = Colorizer::palette(
+            [
+                (2.0.try_into().unwrap(), RgbaColor::red()),
+                (1.0.try_into().unwrap(), RgbaColor::blue()),
+                (3.5.try_into().unwrap(), RgbaColor::black()),
+            ]
+            .into_iter()
+            .collect(),
+            RgbaColor::transparent(),
+            RgbaColor::white(),
+        )
+        .unwrap();
+
+        let options = LegendOptions {
+            title: None,
+            class_labels: [(1, "water".to_string()), (2, "forest".to_string())]
+                .into_iter()
+                .collect(),
+        };
+
+        let layout = LegendLayout::try_new(&colorizer, &options).unwrap();
+
+        assert!(layout.ramp_labels.is_empty());
+        assert_eq!(
+            layout.swatches,
+            vec![
+                (RgbaColor::blue(), "water".to_string()),
+                (RgbaColor::red(), "forest".to_string()),
+                (RgbaColor::black(), "3.5".to_string()),
+                (RgbaColor::white(), "other".to_string()),
+                (RgbaColor::transparent(), "no data".to_string()),
+            ]
+        );
+
+        assert!(colorizer.to_legend_png(&options).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_rgba_legends() {
+        assert!(Colorizer::rgba()
+            .to_legend_png(&LegendOptions::default())
+            .is_err());
+    }
+}
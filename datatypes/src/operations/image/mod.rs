@@ -1,9 +1,12 @@
 mod colorizer;
+mod font;
 mod into_lossy;
+mod legend;
 mod rgba_transmutable;
 mod to_png;
 
 pub use colorizer::{Breakpoint, Breakpoints, Colorizer, Palette, RasterColorizer, RgbaColor};
 pub use into_lossy::LossyInto;
+pub use legend::{LegendOptions, ToLegendPng};
 pub use rgba_transmutable::RgbaTransmutable;
-pub use to_png::ToPng;
+pub use to_png::{message_to_image, ImageOutputFormat, ToImage, ToPng};
@@ -6,33 +6,177 @@ use crate::raster::{
 use crate::util::Result;
 use crate::{error, raster::EmptyGrid2D};
 use crate::{
-    operations::image::{Colorizer, RgbaTransmutable},
+    operations::image::{
+        font::{draw_text, wrap_text, GLYPH_HEIGHT},
+        Colorizer, RgbaColor, RgbaTransmutable,
+    },
     raster::GridOrEmpty,
 };
-use image::{DynamicImage, ImageBuffer, ImageFormat, RgbaImage};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::{WebPEncoder, WebPQuality};
+use image::{ColorType, DynamicImage, ImageBuffer, ImageFormat, RgbImage, RgbaImage};
+
+/// The encodings for colorized raster images
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageOutputFormat {
+    Png,
+    /// JPEG has no alpha channel, so (semi-)transparent pixels are composed onto the `background` color.
+    /// The `quality` ranges from 1 to 100.
+    Jpeg {
+        quality: u8,
+        background: RgbaColor,
+    },
+    /// WebP is encoded lossless if there is no `quality`.
+    /// Otherwise, it is encoded lossy with a `quality` from 1 to 100.
+    WebP {
+        quality: Option<u8>,
+    },
+}
+
+impl ImageOutputFormat {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageOutputFormat::Png => "image/png",
+            ImageOutputFormat::Jpeg { .. } => "image/jpeg",
+            ImageOutputFormat::WebP { .. } => "image/webp",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ImageOutputFormat::Png => "PNG",
+            ImageOutputFormat::Jpeg { .. } => "JPEG",
+            ImageOutputFormat::WebP { .. } => "WebP",
+        }
+    }
+}
+
+pub trait ToImage {
+    /// Outputs the bytes of an image of size width x height in the given format
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>>;
+}
 
 pub trait ToPng {
     /// Outputs png bytes of an image of size width x height
     fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>>;
 }
 
-fn image_buffer_to_png_bytes(
+impl<T> ToPng for T
+where
+    T: ToImage,
+{
+    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
+        self.to_image(width, height, colorizer, ImageOutputFormat::Png)
+    }
+}
+
+pub(crate) fn encode_image(
     image_buffer: ImageBuffer<image::Rgba<u8>, Vec<u8>>,
+    format: ImageOutputFormat,
 ) -> Result<Vec<u8>> {
     let mut buffer = Cursor::new(Vec::new());
-    DynamicImage::ImageRgba8(image_buffer)
-        .write_to(&mut buffer, ImageFormat::Png)
-        .map_err(|error| error::Error::Colorizer {
-            details: format!("encoding PNG failed: {error}"),
-        })?;
+
+    let result = match format {
+        ImageOutputFormat::Png => {
+            DynamicImage::ImageRgba8(image_buffer).write_to(&mut buffer, ImageFormat::Png)
+        }
+        ImageOutputFormat::Jpeg {
+            quality,
+            background,
+        } => JpegEncoder::new_with_quality(&mut buffer, quality.clamp(1, 100))
+            .encode_image(&compose_onto_background(&image_buffer, background)),
+        ImageOutputFormat::WebP { quality } => {
+            let encoder = match quality {
+                Some(quality) => WebPEncoder::new_with_quality(
+                    &mut buffer,
+                    WebPQuality::lossy(quality.clamp(1, 100)),
+                ),
+                None => WebPEncoder::new_lossless(&mut buffer),
+            };
+
+            encoder.encode(
+                image_buffer.as_raw(),
+                image_buffer.width(),
+                image_buffer.height(),
+                ColorType::Rgba8,
+            )
+        }
+    };
+
+    result.map_err(|error| error::Error::Colorizer {
+        details: format!("encoding {} failed: {error}", format.name()),
+    })?;
+
     Ok(buffer.into_inner())
 }
 
-impl<P> ToPng for Grid2D<P>
+/// Removes the alpha channel by blending all pixels onto an opaque background color
+fn compose_onto_background(image_buffer: &RgbaImage, background: RgbaColor) -> RgbImage {
+    let [background_r, background_g, background_b, _] = background.into_inner();
+
+    RgbImage::from_fn(image_buffer.width(), image_buffer.height(), |x, y| {
+        let [r, g, b, a] = image_buffer.get_pixel(x, y).0;
+        let alpha = f64::from(a) / 255.;
+
+        let blend = |channel: u8, background_channel: u8| {
+            (f64::from(channel) * alpha + f64::from(background_channel) * (1. - alpha)).round()
+                as u8
+        };
+
+        image::Rgb([
+            blend(r, background_r),
+            blend(g, background_g),
+            blend(b, background_b),
+        ])
+    })
+}
+
+/// Outputs an image of size width x height that shows the `message` on the `background` color,
+/// e.g., for reporting errors inside of a map. An empty message results in a blank image.
+pub fn message_to_image(
+    width: u32,
+    height: u32,
+    message: &str,
+    background: RgbaColor,
+    format: ImageOutputFormat,
+) -> Result<Vec<u8>> {
+    const MARGIN: u32 = 4;
+    const LINE_HEIGHT: u32 = GLYPH_HEIGHT + 3;
+
+    let mut image_buffer = RgbaImage::from_pixel(width, height, background.into());
+
+    let lines = wrap_text(message, width.saturating_sub(2 * MARGIN));
+
+    for (line_index, line) in lines.iter().enumerate() {
+        draw_text(
+            &mut image_buffer,
+            RgbaColor::black().into(),
+            MARGIN,
+            MARGIN + line_index as u32 * LINE_HEIGHT,
+            line,
+        );
+    }
+
+    encode_image(image_buffer, format)
+}
+
+impl<P> ToImage for Grid2D<P>
 where
     P: Pixel + RgbaTransmutable,
 {
-    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>> {
         // TODO: use PNG color palette once it is available
 
         let [.., raster_y_size, raster_x_size] = self.shape.shape_array;
@@ -42,15 +186,21 @@ where
         let image_buffer =
             create_rgba_image_from_grid(self, width, height, colorizer, scale_x, scale_y);
 
-        image_buffer_to_png_bytes(image_buffer)
+        encode_image(image_buffer, format)
     }
 }
 
-impl<P> ToPng for MaskedGrid2D<P>
+impl<P> ToImage for MaskedGrid2D<P>
 where
     P: Pixel + RgbaTransmutable,
 {
-    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>> {
         // TODO: use PNG color palette once it is available
 
         let [.., raster_y_size, raster_x_size] = self.shape().shape_array;
@@ -60,33 +210,45 @@ where
         let image_buffer =
             create_rgba_image_from_masked_grid(self, width, height, colorizer, scale_x, scale_y);
 
-        image_buffer_to_png_bytes(image_buffer)
+        encode_image(image_buffer, format)
     }
 }
 
-impl<P> ToPng for EmptyGrid2D<P>
+impl<P> ToImage for EmptyGrid2D<P>
 where
     P: Pixel + RgbaTransmutable,
 {
-    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>> {
         // TODO: use PNG color palette once it is available
 
         let no_data_color: image::Rgba<u8> = colorizer.no_data_color().into();
 
         let image_buffer = ImageBuffer::from_pixel(width, height, no_data_color);
 
-        image_buffer_to_png_bytes(image_buffer)
+        encode_image(image_buffer, format)
     }
 }
 
-impl<P> ToPng for GridOrEmpty2D<P>
+impl<P> ToImage for GridOrEmpty2D<P>
 where
     P: Pixel + RgbaTransmutable,
 {
-    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>> {
         match self {
-            GridOrEmpty::Grid(g) => g.to_png(width, height, colorizer),
-            GridOrEmpty::Empty(n) => n.to_png(width, height, colorizer),
+            GridOrEmpty::Grid(g) => g.to_image(width, height, colorizer, format),
+            GridOrEmpty::Empty(n) => n.to_image(width, height, colorizer, format),
         }
     }
 }
@@ -133,25 +295,37 @@ fn create_rgba_image_from_masked_grid<P: Pixel + RgbaTransmutable>(
     })
 }
 
-impl<T: Pixel> ToPng for RasterTile2D<T> {
-    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
-        self.grid_array.to_png(width, height, colorizer)
+impl<T: Pixel> ToImage for RasterTile2D<T> {
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>> {
+        self.grid_array.to_image(width, height, colorizer, format)
     }
 }
 
-impl ToPng for TypedRasterTile2D {
-    fn to_png(&self, width: u32, height: u32, colorizer: &Colorizer) -> Result<Vec<u8>> {
+impl ToImage for TypedRasterTile2D {
+    fn to_image(
+        &self,
+        width: u32,
+        height: u32,
+        colorizer: &Colorizer,
+        format: ImageOutputFormat,
+    ) -> Result<Vec<u8>> {
         match self {
-            TypedRasterTile2D::U8(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::U16(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::U32(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::U64(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::I8(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::I16(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::I32(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::I64(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::F32(r) => r.to_png(width, height, colorizer),
-            TypedRasterTile2D::F64(r) => r.to_png(width, height, colorizer),
+            TypedRasterTile2D::U8(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::U16(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::U32(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::U64(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::I8(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::I16(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::I32(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::I64(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::F32(r) => r.to_image(width, height, colorizer, format),
+            TypedRasterTile2D::F64(r) => r.to_image(width, height, colorizer, format),
         }
     }
 }
@@ -352,4 +526,150 @@ mod tests {
             image_bytes.as_slice()
         );
     }
+
+    #[test]
+    fn jpeg() {
+        let raster = MaskedGrid2D::new(
+            Grid2D::new([2, 2].into(), vec![0, 100, 200, 255]).unwrap(),
+            Grid2D::new([2, 2].into(), vec![false, true, true, true]).unwrap(),
+        )
+        .unwrap();
+
+        let colorizer = Colorizer::linear_gradient(
+            vec![
+                (0.0, RgbaColor::new(0, 0, 0, 255)).try_into().unwrap(),
+                (255.0, RgbaColor::new(255, 255, 255, 255))
+                    .try_into()
+                    .unwrap(),
+            ],
+            RgbaColor::transparent(),
+            RgbaColor::white(),
+            RgbaColor::black(),
+        )
+        .unwrap();
+
+        let image_bytes = raster
+            .to_image(
+                100,
+                100,
+                &colorizer,
+                ImageOutputFormat::Jpeg {
+                    quality: 90,
+                    background: RgbaColor::red(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            image::guess_format(&image_bytes).unwrap(),
+            ImageFormat::Jpeg
+        );
+
+        let image = image::load_from_memory(&image_bytes).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), (100, 100));
+
+        // the no data pixel is transparent and thus shows the background color
+        let [r, g, b] = image.get_pixel(10, 10).0;
+        assert!(r > 240 && g < 15 && b < 15);
+    }
+
+    #[test]
+    fn webp() {
+        let mut raster = Grid2D::new([2, 2].into(), vec![0x0000_00FF_u32; 4]).unwrap();
+
+        raster.set_at_grid_index([0, 0], 0xFF00_00FF_u32).unwrap();
+        raster.set_at_grid_index([1, 0], 0x00FF_00FF_u32).unwrap();
+
+        let colorizer = Colorizer::rgba();
+
+        let image_bytes = raster
+            .to_image(
+                100,
+                100,
+                &colorizer,
+                ImageOutputFormat::WebP { quality: None },
+            )
+            .unwrap();
+
+        assert_eq!(
+            image::guess_format(&image_bytes).unwrap(),
+            ImageFormat::WebP
+        );
+
+        // lossless encoding yields the same pixels as the PNG
+        let webp = image::load_from_memory(&image_bytes).unwrap().to_rgba8();
+        let png = image::load_from_memory(&raster.to_png(100, 100, &colorizer).unwrap())
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(webp, png);
+    }
+
+    #[test]
+    fn lossy_webp() {
+        // noise does not compress well, so the quality determines the size
+        let mut seed = 42_u32;
+        let noise = (0..64 * 64)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (seed & 0xFFFF_FF00) | 0xFF
+            })
+            .collect();
+        let raster = Grid2D::new([64, 64].into(), noise).unwrap();
+
+        let colorizer = Colorizer::rgba();
+
+        let encode = |quality| {
+            raster
+                .to_image(64, 64, &colorizer, ImageOutputFormat::WebP { quality })
+                .unwrap()
+        };
+
+        let lossless = encode(None);
+        let high_quality = encode(Some(90));
+        let low_quality = encode(Some(10));
+
+        assert_eq!(
+            image::guess_format(&low_quality).unwrap(),
+            ImageFormat::WebP
+        );
+
+        assert!(low_quality.len() < high_quality.len());
+        assert!(high_quality.len() < lossless.len());
+
+        // lossy encoding changes the pixels
+        let lossy = image::load_from_memory(&low_quality).unwrap().to_rgba8();
+        let png = image::load_from_memory(&raster.to_png(64, 64, &colorizer).unwrap())
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(lossy.dimensions(), png.dimensions());
+        assert_ne!(lossy, png);
+    }
+
+    #[test]
+    fn message_image() {
+        let image_bytes = message_to_image(
+            100,
+            50,
+            "Something went wrong",
+            RgbaColor::white(),
+            ImageOutputFormat::Png,
+        )
+        .unwrap();
+
+        let image = image::load_from_memory(&image_bytes).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (100, 50));
+        assert!(image.pixels().any(|pixel| pixel.0 == [0, 0, 0, 255]));
+
+        let blank_bytes = message_to_image(
+            100,
+            50,
+            "",
+            RgbaColor::transparent(),
+            ImageOutputFormat::Png,
+        )
+        .unwrap();
+
+        let blank = image::load_from_memory(&blank_bytes).unwrap().to_rgba8();
+        assert!(blank.pixels().all(|pixel| pixel.0 == [0, 0, 0, 0]));
+    }
 }
@@ -1,4 +1,5 @@
 pub mod image;
+pub mod mvt;
 pub mod reproject;
 mod spatial_relation;
 
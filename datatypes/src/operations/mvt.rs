@@ -0,0 +1,813 @@
+//! Encoding of feature collections as [Mapbox Vector Tiles](https://github.com/mapbox/vector-tile-spec/tree/master/2.1)
+//! in the `WebMercatorQuad` tile matrix set.
+
+use crate::collections::{FeatureCollection, FeatureCollectionInfos, IntoGeometryIterator};
+use crate::error;
+use crate::primitives::{
+    BoundingBox2D, Coordinate2D, FeatureDataValue, Geometry, MultiLineStringAccess,
+    MultiLineStringRef, MultiPointAccess, MultiPointRef, MultiPolygonAccess, MultiPolygonRef,
+    SpatialResolution,
+};
+use crate::util::arrow::ArrowTyped;
+use crate::util::Result;
+use geo::Simplify;
+use snafu::ensure;
+use std::collections::HashMap;
+
+/// The number of units of a tile along each axis
+pub const MVT_EXTENT: u32 = 4096;
+/// The number of units that geometries may exceed the tile to avoid clipping artifacts at the tile borders
+pub const MVT_BUFFER: u32 = 64;
+/// The maximum distance (in tile units) that simplified lines and rings may deviate from the original ones
+const MVT_SIMPLIFY_TOLERANCE: f64 = 4.0;
+/// The size of a tile in pixels, used for deriving the spatial resolution of tile queries
+const TILE_SIZE_IN_PIXELS: f64 = 256.;
+
+/// The maximum extent of the `WebMercatorQuad` in EPSG:3857
+const WEB_MERCATOR_MAX: f64 = 20_037_508.342_789_244;
+
+pub const MAX_ZOOM_LEVEL: u8 = 24;
+
+/// A tile of the `WebMercatorQuad` tile matrix set, addressed by zoom level, column and row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebMercatorTile {
+    z: u8,
+    x: u32,
+    y: u32,
+}
+
+impl WebMercatorTile {
+    pub fn new(z: u8, x: u32, y: u32) -> Result<Self> {
+        ensure!(
+            z <= MAX_ZOOM_LEVEL && x < (1 << z) && y < (1 << z),
+            error::InvalidTileIndex { z, x, y }
+        );
+
+        Ok(Self { z, x, y })
+    }
+
+    /// The size of the tile in EPSG:3857 units
+    fn size(self) -> f64 {
+        2. * WEB_MERCATOR_MAX / f64::from(1u32 << self.z)
+    }
+
+    /// The bounds of the tile in EPSG:3857
+    pub fn bounds(self) -> BoundingBox2D {
+        let size = self.size();
+        let min_x = -WEB_MERCATOR_MAX + f64::from(self.x) * size;
+        let max_y = WEB_MERCATOR_MAX - f64::from(self.y) * size;
+
+        BoundingBox2D::new_unchecked(
+            Coordinate2D::new(min_x, max_y - size),
+            Coordinate2D::new(min_x + size, max_y),
+        )
+    }
+
+    /// The bounds of the tile in EPSG:3857 including the buffer around the tile
+    pub fn buffered_bounds(self) -> BoundingBox2D {
+        let size = self.size();
+        let buffer = size * f64::from(MVT_BUFFER) / f64::from(MVT_EXTENT);
+        let bounds = self.bounds();
+
+        BoundingBox2D::new_unchecked(
+            Coordinate2D::new(
+                (bounds.lower_left().x - buffer).max(-WEB_MERCATOR_MAX),
+                (bounds.lower_left().y - buffer).max(-WEB_MERCATOR_MAX),
+            ),
+            Coordinate2D::new(
+                (bounds.upper_right().x + buffer).min(WEB_MERCATOR_MAX),
+                (bounds.upper_right().y + buffer).min(WEB_MERCATOR_MAX),
+            ),
+        )
+    }
+
+    /// The resolution of a 256x256 pixel rendering of the tile
+    pub fn spatial_resolution(self) -> SpatialResolution {
+        let resolution = self.size() / TILE_SIZE_IN_PIXELS;
+        SpatialResolution::new_unchecked(resolution, resolution)
+    }
+
+    /// Transforms a coordinate in EPSG:3857 into tile units with the origin in the upper left corner
+    fn to_tile_units(self, coordinate: Coordinate2D) -> geo::Coord<f64> {
+        let size = self.size();
+        let bounds = self.bounds();
+        let extent = f64::from(MVT_EXTENT);
+
+        geo::Coord {
+            x: (coordinate.x - bounds.lower_left().x) / size * extent,
+            y: (bounds.upper_right().y - coordinate.y) / size * extent,
+        }
+    }
+}
+
+/// The rectangle in tile units that geometries are clipped to
+fn clip_rect() -> geo::Rect<f64> {
+    let min = -f64::from(MVT_BUFFER);
+    let max = f64::from(MVT_EXTENT + MVT_BUFFER);
+    geo::Rect::new(geo::Coord { x: min, y: min }, geo::Coord { x: max, y: max })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MvtGeometryType {
+    Point = 1,
+    LineString = 2,
+    Polygon = 3,
+}
+
+/// A geometry encoded as MVT commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MvtGeometry {
+    pub geometry_type: MvtGeometryType,
+    pub commands: Vec<u32>,
+}
+
+/// Conversion of geometries in EPSG:3857 into clipped and simplified MVT geometries of a tile
+pub trait ToMvtGeometry {
+    /// Returns `None` if nothing of the geometry remains inside the tile
+    fn to_mvt_geometry(&self, tile: WebMercatorTile) -> Option<MvtGeometry>;
+}
+
+impl<'g> ToMvtGeometry for MultiPointRef<'g> {
+    fn to_mvt_geometry(&self, tile: WebMercatorTile) -> Option<MvtGeometry> {
+        let rect = clip_rect();
+
+        let points: Vec<(i64, i64)> = self
+            .points()
+            .iter()
+            .map(|&point| tile.to_tile_units(point))
+            .filter(|point| {
+                (rect.min().x..=rect.max().x).contains(&point.x)
+                    && (rect.min().y..=rect.max().y).contains(&point.y)
+            })
+            .map(quantize)
+            .collect();
+
+        if points.is_empty() {
+            return None;
+        }
+
+        let mut encoder = GeometryEncoder::default();
+        encoder.move_to(&points);
+
+        Some(MvtGeometry {
+            geometry_type: MvtGeometryType::Point,
+            commands: encoder.commands,
+        })
+    }
+}
+
+impl<'g> ToMvtGeometry for MultiLineStringRef<'g> {
+    fn to_mvt_geometry(&self, tile: WebMercatorTile) -> Option<MvtGeometry> {
+        let rect = clip_rect();
+        let mut encoder = GeometryEncoder::default();
+
+        for line in self.lines() {
+            let line: Vec<geo::Coord<f64>> = line.iter().map(|&c| tile.to_tile_units(c)).collect();
+
+            for part in clip_line(&line, rect) {
+                let part = geo::LineString(part).simplify(&MVT_SIMPLIFY_TOLERANCE);
+                let part = quantize_path(&part.0);
+
+                if let [first, rest @ ..] = part.as_slice() {
+                    if !rest.is_empty() {
+                        encoder.move_to(&[*first]);
+                        encoder.line_to(rest);
+                    }
+                }
+            }
+        }
+
+        if encoder.commands.is_empty() {
+            return None;
+        }
+
+        Some(MvtGeometry {
+            geometry_type: MvtGeometryType::LineString,
+            commands: encoder.commands,
+        })
+    }
+}
+
+impl<'g> ToMvtGeometry for MultiPolygonRef<'g> {
+    fn to_mvt_geometry(&self, tile: WebMercatorTile) -> Option<MvtGeometry> {
+        let rect = clip_rect();
+        let mut encoder = GeometryEncoder::default();
+
+        for polygon in self.polygons() {
+            for (ring_index, ring) in polygon.as_ref().iter().enumerate() {
+                let ring: Vec<geo::Coord<f64>> = ring
+                    .as_ref()
+                    .iter()
+                    .map(|&c| tile.to_tile_units(c))
+                    .collect();
+
+                let ring = clip_ring(&ring, rect);
+                let ring = geo::LineString(ring).simplify(&MVT_SIMPLIFY_TOLERANCE);
+                let mut ring = quantize_path(&ring.0);
+
+                // the closing coordinate is implied by the `ClosePath` command
+                if ring.len() > 1 && ring.first() == ring.last() {
+                    ring.pop();
+                }
+
+                let area = signed_area(&ring);
+
+                if ring.len() < 3 || area == 0 {
+                    if ring_index == 0 {
+                        // the exterior ring vanished, so do the holes
+                        break;
+                    }
+                    continue;
+                }
+
+                // exterior rings must have a positive area and interior rings a negative area in tile units
+                let is_exterior = ring_index == 0;
+                if (area > 0) != is_exterior {
+                    ring.reverse();
+                }
+
+                encoder.move_to(&ring[..1]);
+                encoder.line_to(&ring[1..]);
+                encoder.close_path();
+            }
+        }
+
+        if encoder.commands.is_empty() {
+            return None;
+        }
+
+        Some(MvtGeometry {
+            geometry_type: MvtGeometryType::Polygon,
+            commands: encoder.commands,
+        })
+    }
+}
+
+fn quantize(coordinate: geo::Coord<f64>) -> (i64, i64) {
+    (coordinate.x.round() as i64, coordinate.y.round() as i64)
+}
+
+/// Quantizes the coordinates and removes consecutive duplicates
+fn quantize_path(path: &[geo::Coord<f64>]) -> Vec<(i64, i64)> {
+    let mut quantized: Vec<(i64, i64)> = path.iter().map(|&c| quantize(c)).collect();
+    quantized.dedup();
+    quantized
+}
+
+/// Twice the signed area of a ring that is computed using the surveyor's formula
+fn signed_area(ring: &[(i64, i64)]) -> i64 {
+    ring.iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| a.0 * b.1 - b.0 * a.1)
+        .sum()
+}
+
+/// Clips the segment from `a` to `b` to the rectangle using the Liang–Barsky algorithm
+fn clip_segment(
+    a: geo::Coord<f64>,
+    b: geo::Coord<f64>,
+    rect: geo::Rect<f64>,
+) -> Option<(geo::Coord<f64>, geo::Coord<f64>)> {
+    let d = b - a;
+    let (mut t0, mut t1) = (0., 1.);
+
+    for (p, q) in [
+        (-d.x, a.x - rect.min().x),
+        (d.x, rect.max().x - a.x),
+        (-d.y, a.y - rect.min().y),
+        (d.y, rect.max().y - a.y),
+    ] {
+        if p == 0. {
+            if q < 0. {
+                return None;
+            }
+            continue;
+        }
+
+        let r = q / p;
+        if p < 0. {
+            if r > t1 {
+                return None;
+            }
+            t0 = f64::max(t0, r);
+        } else {
+            if r < t0 {
+                return None;
+            }
+            t1 = f64::min(t1, r);
+        }
+    }
+
+    let start = if t0 > 0. { a + d * t0 } else { a };
+    let end = if t1 < 1. { a + d * t1 } else { b };
+
+    Some((start, end))
+}
+
+/// Clips a line to the rectangle. The result consists of the parts of the line inside the rectangle.
+fn clip_line(line: &[geo::Coord<f64>], rect: geo::Rect<f64>) -> Vec<Vec<geo::Coord<f64>>> {
+    let mut parts = Vec::new();
+    let mut current: Vec<geo::Coord<f64>> = Vec::new();
+
+    for segment in line.windows(2) {
+        let Some((start, end)) = clip_segment(segment[0], segment[1], rect) else {
+            if current.len() > 1 {
+                parts.push(std::mem::take(&mut current));
+            }
+            current.clear();
+            continue;
+        };
+
+        if current.last() != Some(&start) {
+            if current.len() > 1 {
+                parts.push(std::mem::take(&mut current));
+            }
+            current.clear();
+            current.push(start);
+        }
+        current.push(end);
+    }
+
+    if current.len() > 1 {
+        parts.push(current);
+    }
+
+    parts
+}
+
+/// Clips a closed ring to the rectangle using the Sutherland–Hodgman algorithm
+fn clip_ring(ring: &[geo::Coord<f64>], rect: geo::Rect<f64>) -> Vec<geo::Coord<f64>> {
+    let (min, max) = (rect.min(), rect.max());
+
+    let edges: [(fn(geo::Coord<f64>, f64) -> bool, usize, f64); 4] = [
+        (|c, v| c.x >= v, 0, min.x),
+        (|c, v| c.x <= v, 0, max.x),
+        (|c, v| c.y >= v, 1, min.y),
+        (|c, v| c.y <= v, 1, max.y),
+    ];
+
+    let mut output = ring.to_vec();
+
+    for (inside, axis, value) in edges {
+        let input = std::mem::take(&mut output);
+
+        let intersect = |a: geo::Coord<f64>, b: geo::Coord<f64>| {
+            let (a_v, b_v) = if axis == 0 { (a.x, b.x) } else { (a.y, b.y) };
+            let t = (value - a_v) / (b_v - a_v);
+            a + (b - a) * t
+        };
+
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+
+            match (inside(previous, value), inside(current, value)) {
+                (true, true) => output.push(current),
+                (true, false) => output.push(intersect(previous, current)),
+                (false, true) => {
+                    output.push(intersect(previous, current));
+                    output.push(current);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+
+    if let Some(&first) = output.first() {
+        if output.last() != Some(&first) {
+            output.push(first);
+        }
+    }
+
+    output
+}
+
+/// Encodes coordinates as MVT commands with delta-encoded parameters
+#[derive(Default)]
+struct GeometryEncoder {
+    commands: Vec<u32>,
+    cursor: (i64, i64),
+}
+
+impl GeometryEncoder {
+    const MOVE_TO: u32 = 1;
+    const LINE_TO: u32 = 2;
+    const CLOSE_PATH: u32 = 7;
+
+    fn command(id: u32, count: usize) -> u32 {
+        (id & 0x7) | ((count as u32) << 3)
+    }
+
+    fn move_to(&mut self, points: &[(i64, i64)]) {
+        self.commands
+            .push(Self::command(Self::MOVE_TO, points.len()));
+        self.push_parameters(points);
+    }
+
+    fn line_to(&mut self, points: &[(i64, i64)]) {
+        self.commands
+            .push(Self::command(Self::LINE_TO, points.len()));
+        self.push_parameters(points);
+    }
+
+    fn close_path(&mut self) {
+        self.commands.push(Self::command(Self::CLOSE_PATH, 1));
+    }
+
+    fn push_parameters(&mut self, points: &[(i64, i64)]) {
+        for &(x, y) in points {
+            self.commands.push(zigzag(x - self.cursor.0) as u32);
+            self.commands.push(zigzag(y - self.cursor.1) as u32);
+            self.cursor = (x, y);
+        }
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// A property value of a feature
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MvtValue {
+    String(String),
+    /// The bits of an `f64`
+    Double(u64),
+    Uint(u64),
+    Sint(i64),
+    Bool(bool),
+}
+
+impl MvtValue {
+    fn from_feature_data_value(value: FeatureDataValue) -> Option<Self> {
+        Some(match value {
+            FeatureDataValue::Category(v) | FeatureDataValue::NullableCategory(Some(v)) => {
+                Self::Uint(v.into())
+            }
+            FeatureDataValue::Int(v) | FeatureDataValue::NullableInt(Some(v)) => Self::Sint(v),
+            FeatureDataValue::Float(v) | FeatureDataValue::NullableFloat(Some(v)) => {
+                Self::Double(v.to_bits())
+            }
+            FeatureDataValue::Text(v) | FeatureDataValue::NullableText(Some(v)) => Self::String(v),
+            FeatureDataValue::Bool(v) | FeatureDataValue::NullableBool(Some(v)) => Self::Bool(v),
+            FeatureDataValue::DateTime(v) | FeatureDataValue::NullableDateTime(Some(v)) => {
+                Self::String(v.as_datetime_string())
+            }
+            FeatureDataValue::NullableCategory(None)
+            | FeatureDataValue::NullableInt(None)
+            | FeatureDataValue::NullableFloat(None)
+            | FeatureDataValue::NullableText(None)
+            | FeatureDataValue::NullableBool(None)
+            | FeatureDataValue::NullableDateTime(None) => return None,
+        })
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut writer = ProtobufWriter::default();
+        match self {
+            Self::String(v) => writer.bytes(1, v.as_bytes()),
+            Self::Double(v) => writer.double(3, f64::from_bits(*v)),
+            Self::Uint(v) => writer.varint(5, *v),
+            Self::Sint(v) => writer.varint(6, zigzag(*v)),
+            Self::Bool(v) => writer.varint(7, u64::from(*v)),
+        }
+        writer.buffer
+    }
+}
+
+/// Builds a single layer of a vector tile from feature collections.
+/// The feature collections must be in EPSG:3857.
+pub struct MvtLayerBuilder {
+    name: String,
+    tile: WebMercatorTile,
+    keys: Vec<String>,
+    key_indices: HashMap<String, u32>,
+    values: Vec<MvtValue>,
+    value_indices: HashMap<MvtValue, u32>,
+    features: Vec<Vec<u8>>,
+}
+
+impl MvtLayerBuilder {
+    pub fn new(name: String, tile: WebMercatorTile) -> Self {
+        Self {
+            name,
+            tile,
+            keys: Vec::new(),
+            key_indices: HashMap::new(),
+            values: Vec::new(),
+            value_indices: HashMap::new(),
+            features: Vec::new(),
+        }
+    }
+
+    pub fn num_features(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Adds the features of the collection that intersect the tile. Their columns become the feature properties.
+    pub fn add_collection<'c, G>(&mut self, collection: &'c FeatureCollection<G>) -> Result<()>
+    where
+        G: Geometry + ArrowTyped,
+        FeatureCollection<G>: IntoGeometryIterator<'c>,
+        <FeatureCollection<G> as IntoGeometryIterator<'c>>::GeometryType: ToMvtGeometry,
+    {
+        let mut column_names: Vec<&String> = collection.column_names().collect();
+        column_names.sort();
+
+        let columns = column_names
+            .into_iter()
+            .map(|name| Ok((name, collection.data(name)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        for (row, geometry) in collection.geometries().enumerate() {
+            let Some(geometry) = geometry.to_mvt_geometry(self.tile) else {
+                continue;
+            };
+
+            let mut tags = Vec::with_capacity(columns.len() * 2);
+            for (name, data) in &columns {
+                let Some(value) = MvtValue::from_feature_data_value(data.get_unchecked(row)) else {
+                    continue;
+                };
+
+                tags.push(self.key_index(name));
+                tags.push(self.value_index(value));
+            }
+
+            let mut writer = ProtobufWriter::default();
+            if !tags.is_empty() {
+                writer.packed(2, &tags);
+            }
+            writer.varint(3, geometry.geometry_type as u64);
+            writer.packed(4, &geometry.commands);
+
+            self.features.push(writer.buffer);
+        }
+
+        Ok(())
+    }
+
+    fn key_index(&mut self, key: &str) -> u32 {
+        if let Some(&index) = self.key_indices.get(key) {
+            return index;
+        }
+
+        let index = self.keys.len() as u32;
+        self.keys.push(key.to_string());
+        self.key_indices.insert(key.to_string(), index);
+        index
+    }
+
+    fn value_index(&mut self, value: MvtValue) -> u32 {
+        if let Some(&index) = self.value_indices.get(&value) {
+            return index;
+        }
+
+        let index = self.values.len() as u32;
+        self.values.push(value.clone());
+        self.value_indices.insert(value, index);
+        index
+    }
+
+    /// Encodes a vector tile that consists of this layer
+    pub fn into_tile_bytes(self) -> Vec<u8> {
+        let mut layer = ProtobufWriter::default();
+        layer.varint(15, 2); // version
+        layer.bytes(1, self.name.as_bytes());
+        for feature in &self.features {
+            layer.bytes(2, feature);
+        }
+        for key in &self.keys {
+            layer.bytes(3, key.as_bytes());
+        }
+        for value in &self.values {
+            layer.bytes(4, &value.encode());
+        }
+        layer.varint(5, MVT_EXTENT.into());
+
+        let mut tile = ProtobufWriter::default();
+        tile.bytes(3, &layer.buffer);
+        tile.buffer
+    }
+}
+
+/// A minimal writer for the protocol buffer messages of the vector tile format
+#[derive(Default)]
+struct ProtobufWriter {
+    buffer: Vec<u8>,
+}
+
+impl ProtobufWriter {
+    const WIRE_TYPE_VARINT: u32 = 0;
+    const WIRE_TYPE_64_BIT: u32 = 1;
+    const WIRE_TYPE_LENGTH_DELIMITED: u32 = 2;
+
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buffer.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buffer.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u32) {
+        self.raw_varint(u64::from((field << 3) | wire_type));
+    }
+
+    fn varint(&mut self, field: u32, value: u64) {
+        self.key(field, Self::WIRE_TYPE_VARINT);
+        self.raw_varint(value);
+    }
+
+    fn double(&mut self, field: u32, value: f64) {
+        self.key(field, Self::WIRE_TYPE_64_BIT);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.key(field, Self::WIRE_TYPE_LENGTH_DELIMITED);
+        self.raw_varint(bytes.len() as u64);
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    fn packed(&mut self, field: u32, values: &[u32]) {
+        let mut packed = ProtobufWriter::default();
+        for &value in values {
+            packed.raw_varint(value.into());
+        }
+        self.bytes(field, &packed.buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::{MultiLineStringCollection, MultiPointCollection};
+    use crate::primitives::{
+        AxisAlignedRectangle, CacheHint, FeatureData, MultiLineString, MultiPoint, MultiPolygon,
+        TimeInterval,
+    };
+
+    #[test]
+    fn it_computes_tile_bounds() {
+        let tile = WebMercatorTile::new(1, 1, 0).unwrap();
+
+        assert_eq!(
+            tile.bounds(),
+            BoundingBox2D::new_unchecked(
+                (0., 0.).into(),
+                (WEB_MERCATOR_MAX, WEB_MERCATOR_MAX).into()
+            )
+        );
+        assert_eq!(
+            tile.buffered_bounds().lower_left(),
+            Coordinate2D::new(-WEB_MERCATOR_MAX / 64., -WEB_MERCATOR_MAX / 64.)
+        );
+        assert_eq!(
+            tile.buffered_bounds().upper_right(),
+            Coordinate2D::new(WEB_MERCATOR_MAX, WEB_MERCATOR_MAX)
+        );
+
+        assert!(WebMercatorTile::new(1, 2, 0).is_err());
+        assert!(WebMercatorTile::new(MAX_ZOOM_LEVEL + 1, 0, 0).is_err());
+    }
+
+    #[test]
+    fn it_encodes_points() {
+        let tile = WebMercatorTile::new(0, 0, 0).unwrap();
+
+        let points =
+            MultiPoint::new(vec![(0., 0.).into(), (WEB_MERCATOR_MAX / 2., 0.).into()]).unwrap();
+        let points = MultiPointRef::from(&points);
+
+        assert_eq!(
+            points.to_mvt_geometry(tile),
+            Some(MvtGeometry {
+                geometry_type: MvtGeometryType::Point,
+                // MoveTo(2), (2048, 2048), (+1024, 0)
+                commands: vec![17, 4096, 4096, 2048, 0],
+            })
+        );
+    }
+
+    #[test]
+    fn it_clips_lines() {
+        let tile = WebMercatorTile::new(1, 1, 0).unwrap();
+
+        // from the center of the world to the east, leaving the tile to the south
+        let lines = MultiLineString::new(vec![vec![
+            (WEB_MERCATOR_MAX / 2., WEB_MERCATOR_MAX / 2.).into(),
+            (WEB_MERCATOR_MAX / 2., -WEB_MERCATOR_MAX / 2.).into(),
+        ]])
+        .unwrap();
+        let lines = MultiLineStringRef::from(&lines);
+
+        assert_eq!(
+            lines.to_mvt_geometry(tile),
+            Some(MvtGeometry {
+                geometry_type: MvtGeometryType::LineString,
+                // MoveTo(1), (2048, 2048), LineTo(1), (0, +2112)
+                commands: vec![9, 4096, 4096, 10, 0, 4224],
+            })
+        );
+    }
+
+    #[test]
+    fn it_clips_and_orients_polygons() {
+        let tile = WebMercatorTile::new(1, 1, 0).unwrap();
+
+        // a counter-clockwise square around the tile origin
+        let d = WEB_MERCATOR_MAX / 2.;
+        let polygon = MultiPolygon::new(vec![vec![vec![
+            (-d, -d).into(),
+            (d, -d).into(),
+            (d, d).into(),
+            (-d, d).into(),
+            (-d, -d).into(),
+        ]]])
+        .unwrap();
+        let polygon = MultiPolygonRef::from(&polygon);
+
+        let geometry = polygon.to_mvt_geometry(tile).unwrap();
+
+        assert_eq!(geometry.geometry_type, MvtGeometryType::Polygon);
+        // MoveTo(1), LineTo(3), ClosePath(1)
+        assert_eq!(geometry.commands.len(), 1 + 2 + 1 + 3 * 2 + 1);
+        assert_eq!(geometry.commands[0], 9);
+        assert_eq!(geometry.commands[3], 26);
+        assert_eq!(geometry.commands[10], 15);
+
+        // decode the ring and check that it covers the clipped area with a positive area
+        let mut cursor = (0, 0);
+        let mut ring = Vec::new();
+        for parameters in [&geometry.commands[1..3], &geometry.commands[4..10]] {
+            for delta in parameters.chunks(2) {
+                let unzigzag = |v: u32| i64::from(v >> 1) ^ -i64::from(v & 1);
+                cursor = (cursor.0 + unzigzag(delta[0]), cursor.1 + unzigzag(delta[1]));
+                ring.push(cursor);
+            }
+        }
+
+        assert!(signed_area(&ring) > 0);
+        assert_eq!(signed_area(&ring), 2 * (2048 + 64) * (2048 + 64));
+    }
+
+    #[test]
+    fn it_encodes_a_layer() {
+        let tile = WebMercatorTile::new(0, 0, 0).unwrap();
+
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![(0., 0.), (1., 1.)]).unwrap(),
+            vec![TimeInterval::default(); 2],
+            [(
+                "name".to_string(),
+                FeatureData::NullableText(vec![Some("a".to_string()), None]),
+            )]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let mut builder = MvtLayerBuilder::new("points".to_string(), tile);
+        builder.add_collection(&collection).unwrap();
+
+        assert_eq!(builder.num_features(), 2);
+
+        assert_eq!(
+            builder.into_tile_bytes(),
+            vec![
+                0x1A, 50, // layer
+                0x78, 2, // version
+                0x0A, 6, b'p', b'o', b'i', b'n', b't', b's', // name
+                0x12, 13, 0x12, 2, 0, 0, 0x18, 1, 0x22, 5, 9, 128, 32, 128,
+                32, // feature with tags
+                0x12, 9, 0x18, 1, 0x22, 5, 9, 128, 32, 128, 32, // feature without tags
+                0x1A, 4, b'n', b'a', b'm', b'e', // key
+                0x22, 3, 0x0A, 1, b'a', // value
+                0x28, 128, 32, // extent
+            ]
+        );
+    }
+
+    #[test]
+    fn it_skips_features_outside_of_the_tile() {
+        let tile = WebMercatorTile::new(1, 0, 0).unwrap();
+
+        let collection = MultiLineStringCollection::from_data(
+            vec![MultiLineString::new(vec![vec![
+                (1_000_000., -1_000_000.).into(),
+                (2_000_000., -2_000_000.).into(),
+            ]])
+            .unwrap()],
+            vec![TimeInterval::default()],
+            Default::default(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let mut builder = MvtLayerBuilder::new("lines".to_string(), tile);
+        builder.add_collection(&collection).unwrap();
+
+        assert_eq!(builder.num_features(), 0);
+    }
+}
@@ -20,8 +20,9 @@ pub use self::grid_traits::{
 pub use self::grid_typed::{TypedGrid, TypedGrid2D, TypedGrid3D};
 pub use self::operations::{
     blit::Blit, convert_data_type::ConvertDataType, convert_data_type::ConvertDataTypeParallel,
-    grid_blit::GridBlit, interpolation::Bilinear, interpolation::InterpolationAlgorithm,
-    interpolation::NearestNeighbor,
+    grid_blit::GridBlit, interpolation::interpolate_by_sampling, interpolation::Average,
+    interpolation::BiCubic, interpolation::Bilinear, interpolation::InterpolationAlgorithm,
+    interpolation::Lanczos, interpolation::Mode, interpolation::NearestNeighbor,
 };
 pub use self::raster_tile::{
     display_raster_tile_2d, BaseTile, MaterializedRasterTile, MaterializedRasterTile2D,
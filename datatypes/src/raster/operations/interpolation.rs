@@ -5,16 +5,154 @@ use crate::raster::{
     TileInformation,
 };
 use crate::util::Result;
+use std::f64::consts::PI;
+use std::ops::Range;
 
 pub trait InterpolationAlgorithm<P: Pixel>: Send + Sync + Clone + 'static {
+    /// The number of input pixels (before, after) that are required around the output area on each axis
+    const INPUT_MARGIN: (usize, usize) = (0, 1);
+
+    /// Whether the algorithm aggregates all input pixels that are covered by an output pixel.
+    /// Such algorithms are meant for downsampling, i.e., the output may have a coarser resolution than the input.
+    const AGGREGATES: bool = false;
+
     /// interpolate the given input tile into the output tile
-    /// the output must be fully contained in the input tile and have an additional row and column in order
+    /// the output must be fully contained in the input tile and have `INPUT_MARGIN` additional rows and columns in order
     /// to have all the required neighbor pixels.
-    /// Also the output must have a finer resolution than the input
+    /// Also the output must have a finer resolution than the input, unless the algorithm `AGGREGATES`.
     fn interpolate(
         input: &RasterTile2D<P>,
         output_tile_info: &TileInformation,
-    ) -> Result<RasterTile2D<P>>;
+    ) -> Result<RasterTile2D<P>> {
+        interpolate_by_sampling::<P, Self>(input, output_tile_info)
+    }
+
+    /// Sample the input at the fractional grid `position` (`[y, x]` in input pixels relative to the upper left of the input).
+    /// The `footprint` is the size of an output pixel in input pixels (`[y, x]`).
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], footprint: [f64; 2]) -> Option<P>;
+}
+
+/// Interpolate the input tile into the output tile by sampling the input at the upper left coordinate of each output pixel
+pub fn interpolate_by_sampling<P, I>(
+    input: &RasterTile2D<P>,
+    info_out: &TileInformation,
+) -> Result<RasterTile2D<P>>
+where
+    P: Pixel,
+    I: InterpolationAlgorithm<P> + ?Sized,
+{
+    if input.is_empty() {
+        return Ok(RasterTile2D::new_with_tile_info(
+            input.time,
+            *info_out,
+            input.band,
+            EmptyGrid::new(info_out.tile_size_in_pixels).into(),
+            input.cache_hint.clone_with_current_datetime(),
+        ));
+    }
+
+    let info_in = input.tile_information();
+    let in_upper_left = info_in.spatial_partition().upper_left();
+    let in_x_size = info_in.global_geo_transform.x_pixel_size();
+    let in_y_size = info_in.global_geo_transform.y_pixel_size();
+
+    let out_upper_left = info_out.spatial_partition().upper_left();
+    let out_x_size = info_out.global_geo_transform.x_pixel_size();
+    let out_y_size = info_out.global_geo_transform.y_pixel_size();
+
+    let footprint = [out_y_size / in_y_size, out_x_size / in_x_size];
+
+    let map_fn = |gidx: GridIdx2D| {
+        let GridIdx([y, x]) = gidx;
+        let out_y_coord = out_upper_left.y + y as f64 * out_y_size;
+        let out_x_coord = out_upper_left.x + x as f64 * out_x_size;
+        let position = [
+            (out_y_coord - in_upper_left.y) / in_y_size,
+            (out_x_coord - in_upper_left.x) / in_x_size,
+        ];
+        I::sample(input, position, footprint)
+    };
+
+    let out_data = GridOrEmpty::from_index_fn_parallel(&info_out.tile_size_in_pixels, map_fn);
+
+    Ok(RasterTile2D::new(
+        input.time,
+        info_out.global_tile_position,
+        input.band,
+        info_out.global_geo_transform,
+        out_data,
+        input.cache_hint.clone_with_current_datetime(),
+    ))
+}
+
+/// Sample the input with a separable `kernel` that uses the `2 * radius` pixels around the `position` on each axis.
+/// Returns `None` if any of the required pixels is missing.
+fn sample_with_kernel<P, K>(
+    input: &RasterTile2D<P>,
+    position: [f64; 2],
+    radius: isize,
+    kernel: K,
+) -> Option<P>
+where
+    P: Pixel,
+    K: Fn(f64) -> f64,
+{
+    let [y, x] = position;
+    let (y_floor, x_floor) = (y.floor(), x.floor());
+
+    let mut sum = 0.;
+    let mut weight_sum = 0.;
+
+    for dy in (1 - radius)..=radius {
+        let weight_y = kernel(y - (y_floor + dy as f64));
+
+        for dx in (1 - radius)..=radius {
+            let weight_x = kernel(x - (x_floor + dx as f64));
+
+            let value: f64 = input
+                .get_at_grid_index([y_floor as isize + dy, x_floor as isize + dx])
+                .ok()
+                .flatten()?
+                .as_();
+
+            sum += weight_y * weight_x * value;
+            weight_sum += weight_y * weight_x;
+        }
+    }
+
+    if weight_sum == 0. {
+        return None;
+    }
+
+    Some(P::from_(sum / weight_sum))
+}
+
+/// The input pixels whose centers are covered by an output pixel starting at `position` with size `footprint`.
+/// If no center is covered, the pixel containing the `position` is used.
+fn covered_pixels(position: f64, footprint: f64) -> Range<isize> {
+    let start = (position - 0.5).ceil() as isize;
+    let end = (position + footprint - 0.5).ceil() as isize;
+
+    if end > start {
+        start..end
+    } else {
+        let pixel = position.floor() as isize;
+        pixel..pixel + 1
+    }
+}
+
+/// All valid input pixel values that are covered by an output pixel
+fn covered_values<P: Pixel>(
+    input: &RasterTile2D<P>,
+    position: [f64; 2],
+    footprint: [f64; 2],
+) -> Vec<P> {
+    let x_range = covered_pixels(position[1], footprint[1]);
+
+    covered_pixels(position[0], footprint[0])
+        .flat_map(|y| x_range.clone().map(move |x| [y, x]))
+        .filter_map(|idx| input.get_at_grid_index(idx).ok().flatten())
+        .collect()
 }
 
 #[derive(Clone, Debug)]
@@ -66,6 +204,14 @@ where
 
         Ok(out_tile)
     }
+
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], _footprint: [f64; 2]) -> Option<P> {
+        let [y, x] = position;
+        input
+            .get_at_grid_index([y.round() as isize, x.round() as isize])
+            .ok()
+            .flatten()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -178,6 +324,127 @@ where
 
         Ok(out_tile)
     }
+
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], _footprint: [f64; 2]) -> Option<P> {
+        sample_with_kernel(input, position, 1, |t| 1. - t.abs())
+    }
+}
+
+/// Bicubic convolution using the Keys kernel with `a = -0.5` on the 4x4 neighborhood
+#[derive(Clone, Debug)]
+pub struct BiCubic {}
+
+impl BiCubic {
+    const A: f64 = -0.5;
+
+    pub fn kernel(t: f64) -> f64 {
+        let t = t.abs();
+        if t <= 1. {
+            (Self::A + 2.) * t.powi(3) - (Self::A + 3.) * t.powi(2) + 1.
+        } else if t < 2. {
+            Self::A * t.powi(3) - 5. * Self::A * t.powi(2) + 8. * Self::A * t - 4. * Self::A
+        } else {
+            0.
+        }
+    }
+}
+
+impl<P> InterpolationAlgorithm<P> for BiCubic
+where
+    P: Pixel,
+{
+    const INPUT_MARGIN: (usize, usize) = (1, 2);
+
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], _footprint: [f64; 2]) -> Option<P> {
+        sample_with_kernel(input, position, 2, Self::kernel)
+    }
+}
+
+/// Lanczos resampling with `a = 3` on the 6x6 neighborhood
+#[derive(Clone, Debug)]
+pub struct Lanczos {}
+
+impl Lanczos {
+    const A: f64 = 3.;
+
+    pub fn kernel(t: f64) -> f64 {
+        if t == 0. {
+            1.
+        } else if t.abs() < Self::A {
+            Self::A * (PI * t).sin() * (PI * t / Self::A).sin() / (PI * PI * t * t)
+        } else {
+            0.
+        }
+    }
+}
+
+impl<P> InterpolationAlgorithm<P> for Lanczos
+where
+    P: Pixel,
+{
+    const INPUT_MARGIN: (usize, usize) = (2, 3);
+
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], _footprint: [f64; 2]) -> Option<P> {
+        sample_with_kernel(input, position, 3, Self::kernel)
+    }
+}
+
+/// The mean of all valid input pixels that are covered by an output pixel
+#[derive(Clone, Debug)]
+pub struct Average {}
+
+impl<P> InterpolationAlgorithm<P> for Average
+where
+    P: Pixel,
+{
+    const AGGREGATES: bool = true;
+
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], footprint: [f64; 2]) -> Option<P> {
+        let values = covered_values(input, position, footprint);
+
+        if values.is_empty() {
+            return None;
+        }
+
+        let sum: f64 = values.iter().map(|&v| -> f64 { v.as_() }).sum();
+
+        Some(P::from_(sum / values.len() as f64))
+    }
+}
+
+/// The most frequent of all valid input pixels that are covered by an output pixel.
+/// Ties are resolved by choosing the smallest value.
+#[derive(Clone, Debug)]
+pub struct Mode {}
+
+impl<P> InterpolationAlgorithm<P> for Mode
+where
+    P: Pixel,
+{
+    const AGGREGATES: bool = true;
+
+    fn sample(input: &RasterTile2D<P>, position: [f64; 2], footprint: [f64; 2]) -> Option<P> {
+        let mut values = covered_values(input, position, footprint);
+        values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut mode = None;
+        let mut mode_count = 0;
+
+        let mut i = 0;
+        while i < values.len() {
+            let value = values[i];
+            let count = values[i..].iter().take_while(|&&v| v == value).count();
+
+            if count > mode_count {
+                mode = Some(value);
+                mode_count = count;
+            }
+
+            i += count;
+        }
+
+        mode
+    }
 }
 
 #[cfg(test)]
@@ -319,4 +586,229 @@ mod tests {
             ]
         );
     }
+
+    fn ramp_input() -> RasterTile2D<f64> {
+        RasterTile2D::new_with_tile_info(
+            Default::default(),
+            TileInformation {
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [6, 6].into(),
+                global_geo_transform: GeoTransform::new((0.0, 6.0).into(), 1.0, -1.0),
+            },
+            0,
+            GridOrEmpty::Grid(MaskedGrid::from(
+                Grid2D::new([6, 6].into(), (0..36).map(f64::from).collect()).unwrap(),
+            )),
+            CacheHint::default(),
+        )
+    }
+
+    #[test]
+    fn bicubic_reproduces_linear_functions() {
+        let input = ramp_input();
+
+        let output_info = TileInformation {
+            global_tile_position: [0, 0].into(),
+            tile_size_in_pixels: [4, 4].into(),
+            global_geo_transform: GeoTransform::new((1.0, 5.0).into(), 0.5, -0.5),
+        };
+
+        let pool = ThreadPoolBuilder::new().num_threads(0).build().unwrap();
+
+        let output = pool
+            .install(|| BiCubic::interpolate(&input, &output_info))
+            .unwrap();
+
+        let output_data = output.grid_array.as_masked_grid().unwrap();
+
+        let expected = (0..4).flat_map(|y| {
+            (0..4).map(move |x| 6. * (1. + 0.5 * f64::from(y)) + 1. + 0.5 * f64::from(x))
+        });
+
+        for (value, expected) in output_data.masked_element_deref_iterator().zip(expected) {
+            assert!(float_cmp::approx_eq!(
+                f64,
+                value.unwrap(),
+                expected,
+                epsilon = 1e-9
+            ));
+        }
+    }
+
+    #[test]
+    fn bicubic_requires_all_neighbors() {
+        let input = ramp_input();
+
+        assert!(BiCubic::sample(&input, [1.5, 1.5], [1., 1.]).is_some());
+        assert!(BiCubic::sample(&input, [0.5, 1.5], [1., 1.]).is_none());
+        assert!(BiCubic::sample(&input, [1.5, 4.5], [1., 1.]).is_none());
+    }
+
+    #[test]
+    fn lanczos() {
+        let input = RasterTile2D::new_with_tile_info(
+            Default::default(),
+            TileInformation {
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [6, 6].into(),
+                global_geo_transform: GeoTransform::new((0.0, 6.0).into(), 1.0, -1.0),
+            },
+            0,
+            GridOrEmpty::Grid(MaskedGrid::from(Grid2D::new_filled([6, 6].into(), 7.))),
+            CacheHint::default(),
+        );
+
+        let output_info = TileInformation {
+            global_tile_position: [0, 0].into(),
+            tile_size_in_pixels: [2, 2].into(),
+            global_geo_transform: GeoTransform::new((2.0, 4.0).into(), 0.5, -0.5),
+        };
+
+        let pool = ThreadPoolBuilder::new().num_threads(0).build().unwrap();
+
+        let output = pool
+            .install(|| Lanczos::interpolate(&input, &output_info))
+            .unwrap();
+
+        for value in output
+            .grid_array
+            .as_masked_grid()
+            .unwrap()
+            .masked_element_deref_iterator()
+        {
+            assert!(float_cmp::approx_eq!(
+                f64,
+                value.unwrap(),
+                7.,
+                epsilon = 1e-9
+            ));
+        }
+
+        assert!(float_cmp::approx_eq!(f64, Lanczos::kernel(0.), 1.));
+        assert!(float_cmp::approx_eq!(
+            f64,
+            Lanczos::kernel(1.),
+            0.,
+            epsilon = 1e-12
+        ));
+        assert!(float_cmp::approx_eq!(f64, Lanczos::kernel(3.5), 0.));
+    }
+
+    #[test]
+    fn average() {
+        let input = RasterTile2D::new_with_tile_info(
+            Default::default(),
+            TileInformation {
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [4, 4].into(),
+                global_geo_transform: GeoTransform::new((0.0, 4.0).into(), 1.0, -1.0),
+            },
+            0,
+            GridOrEmpty::Grid(
+                MaskedGrid::new(
+                    Grid2D::new(
+                        [4, 4].into(),
+                        vec![
+                            1., 2., 3., 4., //
+                            5., 6., 7., 8., //
+                            9., 10., 11., 12., //
+                            13., 14., 15., 16.,
+                        ],
+                    )
+                    .unwrap(),
+                    Grid2D::new(
+                        [4, 4].into(),
+                        vec![
+                            true, true, true, true, //
+                            true, true, true, true, //
+                            true, true, false, false, //
+                            true, true, false, false,
+                        ],
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            CacheHint::default(),
+        );
+
+        let output_info = TileInformation {
+            global_tile_position: [0, 0].into(),
+            tile_size_in_pixels: [2, 2].into(),
+            global_geo_transform: GeoTransform::new((0.0, 4.0).into(), 2.0, -2.0),
+        };
+
+        let pool = ThreadPoolBuilder::new().num_threads(0).build().unwrap();
+
+        let output = pool
+            .install(|| Average::interpolate(&input, &output_info))
+            .unwrap();
+
+        assert_eq!(
+            output
+                .grid_array
+                .as_masked_grid()
+                .unwrap()
+                .masked_element_deref_iterator()
+                .collect::<Vec<_>>(),
+            vec![Some(3.5), Some(5.5), Some(11.5), None]
+        );
+    }
+
+    #[test]
+    fn mode() {
+        let input = RasterTile2D::new_with_tile_info(
+            Default::default(),
+            TileInformation {
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [4, 4].into(),
+                global_geo_transform: GeoTransform::new((0.0, 4.0).into(), 1.0, -1.0),
+            },
+            0,
+            GridOrEmpty::Grid(MaskedGrid::from(
+                Grid2D::new(
+                    [4, 4].into(),
+                    vec![
+                        1, 1, 3, 4, //
+                        1, 2, 4, 3, //
+                        5, 6, 7, 7, //
+                        6, 5, 7, 8,
+                    ],
+                )
+                .unwrap(),
+            )),
+            CacheHint::default(),
+        );
+
+        let output_info = TileInformation {
+            global_tile_position: [0, 0].into(),
+            tile_size_in_pixels: [2, 2].into(),
+            global_geo_transform: GeoTransform::new((0.0, 4.0).into(), 2.0, -2.0),
+        };
+
+        let pool = ThreadPoolBuilder::new().num_threads(0).build().unwrap();
+
+        let output = pool
+            .install(|| Mode::interpolate(&input, &output_info))
+            .unwrap();
+
+        assert_eq!(
+            output
+                .grid_array
+                .as_masked_grid()
+                .unwrap()
+                .masked_element_deref_iterator()
+                .collect::<Vec<_>>(),
+            vec![Some(1), Some(3), Some(5), Some(7)]
+        );
+    }
+
+    #[test]
+    fn covered_pixels_of_footprint() {
+        assert_eq!(covered_pixels(0., 1.), 0..1);
+        assert_eq!(covered_pixels(0., 2.), 0..2);
+        assert_eq!(covered_pixels(2., 2.), 2..4);
+        assert_eq!(covered_pixels(1.25, 0.5), 1..2);
+        assert_eq!(covered_pixels(0.1, 0.2), 0..1);
+    }
 }
@@ -1,4 +1,10 @@
-use geo::{Area, Centroid};
+use geo::{
+    Area, BoundingRect, Centroid, ConvexHull, CoordsIter, EuclideanDistance, EuclideanLength,
+    Simplify,
+};
+
+/// The [`geo`] geometry that all geometry types convert to for binary operations
+pub use geo::Geometry as GeoGeometry;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MultiPoint(geo::MultiPoint);
@@ -39,8 +45,50 @@ pub trait GeoOptionOperations {
     fn area(&self) -> Option<f64>;
 
     fn centroid(&self) -> Option<MultiPoint>;
+
+    /// The length of lines, which is `0` for points and polygons
+    fn length(&self) -> Option<f64>;
+
+    /// The length of the polygon rings, which is `0` for points and lines
+    fn perimeter(&self) -> Option<f64>;
+
+    fn num_points(&self) -> Option<f64>;
+
+    fn convex_hull(&self) -> Option<MultiPolygon>;
+
+    /// The bounding box as a polygon
+    fn envelope(&self) -> Option<MultiPolygon>;
+
+    /// The area within `distance` of the geometry.
+    /// Only positive distances are supported.
+    /// Returns `None` if the buffer cannot be computed, e.g., for degenerated geometries.
+    fn buffer(&self, distance: f64) -> Option<MultiPolygon>;
+
+    /// The x coordinate of a single point
+    fn x(&self) -> Option<f64>;
+
+    /// The y coordinate of a single point
+    fn y(&self) -> Option<f64>;
+
+    fn to_geo(&self) -> Option<GeoGeometry>;
+
+    /// The minimum euclidean distance between two geometries
+    fn distance<G: GeoOptionOperations>(&self, other: &G) -> Option<f64> {
+        Some(self.to_geo()?.euclidean_distance(&other.to_geo()?))
+    }
 }
 
+/// Simplifies geometries while keeping their type.
+pub trait GeoOptionSimplify {
+    type Output;
+
+    /// Simplifies the geometry with the Ramer–Douglas–Peucker algorithm
+    fn simplify(&self, tolerance: f64) -> Option<Self::Output>;
+}
+
+/// Number of segments that approximate a quarter circle in buffers
+const BUFFER_QUADRANT_SEGMENTS: usize = 8;
+
 impl GeoOptionOperations for MultiPoint {
     fn area(&self) -> Option<f64> {
         Some(self.0.unsigned_area())
@@ -49,6 +97,58 @@ impl GeoOptionOperations for MultiPoint {
     fn centroid(&self) -> Option<MultiPoint> {
         Some(MultiPoint(self.0.centroid()?.into()))
     }
+
+    fn length(&self) -> Option<f64> {
+        Some(0.)
+    }
+
+    fn perimeter(&self) -> Option<f64> {
+        Some(0.)
+    }
+
+    fn num_points(&self) -> Option<f64> {
+        Some(self.0.coords_count() as f64)
+    }
+
+    fn convex_hull(&self) -> Option<MultiPolygon> {
+        Some(MultiPolygon(self.0.convex_hull().into()))
+    }
+
+    fn envelope(&self) -> Option<MultiPolygon> {
+        Some(MultiPolygon(self.0.bounding_rect()?.to_polygon().into()))
+    }
+
+    fn buffer(&self, distance: f64) -> Option<MultiPolygon> {
+        if distance.is_nan() || distance <= 0. {
+            return None;
+        }
+
+        let circles = self
+            .0
+            .iter()
+            .map(|point| buffer::circle(point.0, distance, BUFFER_QUADRANT_SEGMENTS).into())
+            .collect();
+
+        buffer::union_all(circles).map(MultiPolygon)
+    }
+
+    fn x(&self) -> Option<f64> {
+        match self.0 .0.as_slice() {
+            [point] => Some(point.x()),
+            _ => None,
+        }
+    }
+
+    fn y(&self) -> Option<f64> {
+        match self.0 .0.as_slice() {
+            [point] => Some(point.y()),
+            _ => None,
+        }
+    }
+
+    fn to_geo(&self) -> Option<GeoGeometry> {
+        Some(self.0.clone().into())
+    }
 }
 
 impl GeoOptionOperations for MultiLineString {
@@ -59,6 +159,53 @@ impl GeoOptionOperations for MultiLineString {
     fn centroid(&self) -> Option<MultiPoint> {
         Some(MultiPoint(self.0.centroid()?.into()))
     }
+
+    fn length(&self) -> Option<f64> {
+        Some(self.0.euclidean_length())
+    }
+
+    fn perimeter(&self) -> Option<f64> {
+        Some(0.)
+    }
+
+    fn num_points(&self) -> Option<f64> {
+        Some(self.0.coords_count() as f64)
+    }
+
+    fn convex_hull(&self) -> Option<MultiPolygon> {
+        Some(MultiPolygon(self.0.convex_hull().into()))
+    }
+
+    fn envelope(&self) -> Option<MultiPolygon> {
+        Some(MultiPolygon(self.0.bounding_rect()?.to_polygon().into()))
+    }
+
+    fn buffer(&self, distance: f64) -> Option<MultiPolygon> {
+        if distance.is_nan() || distance <= 0. {
+            return None;
+        }
+
+        let parts = self
+            .0
+            .iter()
+            .flat_map(|line| buffer::line_buffer_parts(line, distance, BUFFER_QUADRANT_SEGMENTS))
+            .map(Into::into)
+            .collect();
+
+        buffer::union_all(parts).map(MultiPolygon)
+    }
+
+    fn x(&self) -> Option<f64> {
+        None
+    }
+
+    fn y(&self) -> Option<f64> {
+        None
+    }
+
+    fn to_geo(&self) -> Option<GeoGeometry> {
+        Some(self.0.clone().into())
+    }
 }
 
 impl GeoOptionOperations for MultiPolygon {
@@ -69,6 +216,67 @@ impl GeoOptionOperations for MultiPolygon {
     fn centroid(&self) -> Option<MultiPoint> {
         Some(MultiPoint(self.0.centroid()?.into()))
     }
+
+    fn length(&self) -> Option<f64> {
+        Some(0.)
+    }
+
+    fn perimeter(&self) -> Option<f64> {
+        Some(
+            self.0
+                .iter()
+                .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+                .map(EuclideanLength::euclidean_length)
+                .sum(),
+        )
+    }
+
+    fn num_points(&self) -> Option<f64> {
+        Some(self.0.coords_count() as f64)
+    }
+
+    fn convex_hull(&self) -> Option<MultiPolygon> {
+        Some(MultiPolygon(self.0.convex_hull().into()))
+    }
+
+    fn envelope(&self) -> Option<MultiPolygon> {
+        Some(MultiPolygon(self.0.bounding_rect()?.to_polygon().into()))
+    }
+
+    fn buffer(&self, distance: f64) -> Option<MultiPolygon> {
+        if distance.is_nan() || distance <= 0. {
+            return None;
+        }
+
+        let parts = self
+            .0
+            .iter()
+            .flat_map(|polygon| {
+                std::iter::once(polygon.clone()).chain(
+                    std::iter::once(polygon.exterior())
+                        .chain(polygon.interiors())
+                        .flat_map(|ring| {
+                            buffer::line_buffer_parts(ring, distance, BUFFER_QUADRANT_SEGMENTS)
+                        }),
+                )
+            })
+            .map(Into::into)
+            .collect();
+
+        buffer::union_all(parts).map(MultiPolygon)
+    }
+
+    fn x(&self) -> Option<f64> {
+        None
+    }
+
+    fn y(&self) -> Option<f64> {
+        None
+    }
+
+    fn to_geo(&self) -> Option<GeoGeometry> {
+        Some(self.0.clone().into())
+    }
 }
 
 impl<T> GeoOptionOperations for Option<T>
@@ -82,4 +290,386 @@ where
     fn centroid(&self) -> Option<MultiPoint> {
         self.as_ref()?.centroid()
     }
+
+    fn length(&self) -> Option<f64> {
+        self.as_ref()?.length()
+    }
+
+    fn perimeter(&self) -> Option<f64> {
+        self.as_ref()?.perimeter()
+    }
+
+    fn num_points(&self) -> Option<f64> {
+        self.as_ref()?.num_points()
+    }
+
+    fn convex_hull(&self) -> Option<MultiPolygon> {
+        self.as_ref()?.convex_hull()
+    }
+
+    fn envelope(&self) -> Option<MultiPolygon> {
+        self.as_ref()?.envelope()
+    }
+
+    fn buffer(&self, distance: f64) -> Option<MultiPolygon> {
+        self.as_ref()?.buffer(distance)
+    }
+
+    fn x(&self) -> Option<f64> {
+        self.as_ref()?.x()
+    }
+
+    fn y(&self) -> Option<f64> {
+        self.as_ref()?.y()
+    }
+
+    fn to_geo(&self) -> Option<GeoGeometry> {
+        self.as_ref()?.to_geo()
+    }
+}
+
+impl GeoOptionSimplify for MultiPoint {
+    type Output = MultiPoint;
+
+    fn simplify(&self, _tolerance: f64) -> Option<Self::Output> {
+        Some(self.clone())
+    }
+}
+
+impl GeoOptionSimplify for MultiLineString {
+    type Output = MultiLineString;
+
+    fn simplify(&self, tolerance: f64) -> Option<Self::Output> {
+        Some(MultiLineString(self.0.simplify(&tolerance)))
+    }
+}
+
+impl GeoOptionSimplify for MultiPolygon {
+    type Output = MultiPolygon;
+
+    fn simplify(&self, tolerance: f64) -> Option<Self::Output> {
+        Some(MultiPolygon(self.0.simplify(&tolerance)))
+    }
+}
+
+impl<T> GeoOptionSimplify for Option<T>
+where
+    T: GeoOptionSimplify,
+{
+    type Output = T::Output;
+
+    fn simplify(&self, tolerance: f64) -> Option<Self::Output> {
+        self.as_ref()?.simplify(tolerance)
+    }
+}
+
+/// Building blocks for buffering geometries, shared with the vector operators
+pub mod buffer {
+    use geo::BooleanOps;
+    use std::cell::Cell;
+    use std::panic::UnwindSafe;
+    use std::sync::Once;
+
+    thread_local! {
+        /// Whether the panic hook stays silent for panics of the current thread
+        static SILENT_PANICS: Cell<bool> = const { Cell::new(false) };
+    }
+
+    static SILENT_PANIC_HOOK: Once = Once::new();
+
+    /// Runs `f` and returns `None` if it panics.
+    ///
+    /// The panic is expected, so it is not reported by the panic hook.
+    /// Panics of other threads are reported as usual.
+    pub fn catch_silent_panic<R>(f: impl FnOnce() -> R + UnwindSafe) -> Option<R> {
+        SILENT_PANIC_HOOK.call_once(|| {
+            let hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |info| {
+                if !SILENT_PANICS.with(Cell::get) {
+                    hook(info);
+                }
+            }));
+        });
+
+        let was_silent = SILENT_PANICS.with(|silent| silent.replace(true));
+        let result = std::panic::catch_unwind(f);
+        SILENT_PANICS.with(|silent| silent.set(was_silent));
+
+        result.ok()
+    }
+
+    /// A polygon that approximates the circle around `center` with `4 * quadrant_segments` segments
+    pub fn circle(center: geo::Coord, radius: f64, quadrant_segments: usize) -> geo::Polygon {
+        let num_segments = 4 * quadrant_segments;
+
+        let ring: Vec<geo::Coord> = (0..num_segments)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * (i as f64) / (num_segments as f64);
+                geo::coord! {
+                    x: center.x + radius * angle.cos(),
+                    y: center.y + radius * angle.sin(),
+                }
+            })
+            .collect();
+
+        geo::Polygon::new(ring.into(), vec![])
+    }
+
+    /// The polygons whose union is the buffer of a line, i.e.,
+    /// circles around the vertices and rectangles around the segments.
+    pub fn line_buffer_parts(
+        line: &geo::LineString,
+        distance: f64,
+        quadrant_segments: usize,
+    ) -> Vec<geo::Polygon> {
+        let circles = line
+            .coords()
+            .map(|coord| circle(*coord, distance, quadrant_segments));
+
+        let rectangles = line.lines().filter_map(|segment| {
+            let length = segment.dx().hypot(segment.dy());
+            if length == 0. {
+                return None;
+            }
+
+            // the normal of the segment with the length of the distance
+            let normal = geo::coord! {
+                x: -segment.dy() / length * distance,
+                y: segment.dx() / length * distance,
+            };
+
+            Some(geo::Polygon::new(
+                vec![
+                    segment.start + normal,
+                    segment.end + normal,
+                    segment.end - normal,
+                    segment.start - normal,
+                ]
+                .into(),
+                vec![],
+            ))
+        });
+
+        circles.chain(rectangles).collect()
+    }
+
+    /// Unions the geometries pairwise, which is faster than adding them one by one.
+    ///
+    /// Returns `None` if the union fails.
+    /// The boolean operations of [`geo`] panic for some inputs, e.g., nearly collinear edges,
+    /// so the panic is caught instead of aborting the whole computation.
+    pub fn union_all(parts: Vec<geo::MultiPolygon>) -> Option<geo::MultiPolygon> {
+        catch_silent_panic(move || {
+            let mut parts = parts;
+
+            while parts.len() > 1 {
+                parts = parts
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [a, b] => a.union(b),
+                        _ => pair[0].clone(),
+                    })
+                    .collect();
+            }
+
+            parts
+                .pop()
+                .unwrap_or_else(|| geo::MultiPolygon::new(vec![]))
+        })
+    }
+}
+
+/// Date functions on time instances, i.e., milliseconds since the Unix epoch (UTC).
+pub mod time {
+    const MILLIS_PER_DAY: f64 = 86_400_000.;
+    const MILLIS_PER_HOUR: f64 = 3_600_000.;
+    const MILLIS_PER_MINUTE: f64 = 60_000.;
+    const MILLIS_PER_SECOND: f64 = 1_000.;
+
+    /// The number of days since the Unix epoch.
+    fn days(millis: f64) -> i64 {
+        (millis / MILLIS_PER_DAY).floor() as i64
+    }
+
+    /// Converts days since the Unix epoch to `(year, month, day)` in the proleptic Gregorian calendar.
+    ///
+    /// cf. <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    fn civil_from_days(days: i64) -> (i64, i64, i64) {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        (year, month, day)
+    }
+
+    /// Converts a date in the proleptic Gregorian calendar to days since the Unix epoch.
+    ///
+    /// cf. <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let year = year - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month_from_march = (month + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn year(millis: f64) -> f64 {
+        civil_from_days(days(millis)).0 as f64
+    }
+
+    pub fn month(millis: f64) -> f64 {
+        civil_from_days(days(millis)).1 as f64
+    }
+
+    pub fn day(millis: f64) -> f64 {
+        civil_from_days(days(millis)).2 as f64
+    }
+
+    /// The day of the year, starting with `1` for January 1st.
+    pub fn day_of_year(millis: f64) -> f64 {
+        let days = days(millis);
+        let (year, _, _) = civil_from_days(days);
+
+        (days - days_from_civil(year, 1, 1) + 1) as f64
+    }
+
+    /// The milliseconds since midnight.
+    fn millis_of_day(millis: f64) -> f64 {
+        millis.rem_euclid(MILLIS_PER_DAY)
+    }
+
+    pub fn hour(millis: f64) -> f64 {
+        (millis_of_day(millis) / MILLIS_PER_HOUR).floor()
+    }
+
+    pub fn minute(millis: f64) -> f64 {
+        (millis_of_day(millis) % MILLIS_PER_HOUR / MILLIS_PER_MINUTE).floor()
+    }
+
+    pub fn second(millis: f64) -> f64 {
+        (millis_of_day(millis) % MILLIS_PER_MINUTE / MILLIS_PER_SECOND).floor()
+    }
+
+    /// The time instance at midnight of the given date.
+    /// Months and days that exceed their range overflow into the next month or year.
+    pub fn date(year: f64, month: f64, day: f64) -> Option<f64> {
+        if !(year.is_finite() && month.is_finite() && day.is_finite()) {
+            return None;
+        }
+
+        let months = (year as i64) * 12 + (month as i64) - 1;
+        let (year, month) = (months.div_euclid(12), months.rem_euclid(12) + 1);
+        let days = days_from_civil(year, month, 1) + (day as i64) - 1;
+
+        Some(days as f64 * MILLIS_PER_DAY)
+    }
+
+    pub fn add_days(millis: f64, days: f64) -> f64 {
+        millis + days * MILLIS_PER_DAY
+    }
+
+    pub fn add_hours(millis: f64, hours: f64) -> f64 {
+        millis + hours * MILLIS_PER_HOUR
+    }
+
+    pub fn add_minutes(millis: f64, minutes: f64) -> f64 {
+        millis + minutes * MILLIS_PER_MINUTE
+    }
+
+    pub fn add_seconds(millis: f64, seconds: f64) -> f64 {
+        millis + seconds * MILLIS_PER_SECOND
+    }
+
+    /// The (fractional) number of days from `start` to `end`.
+    pub fn days_between(start: f64, end: f64) -> f64 {
+        (end - start) / MILLIS_PER_DAY
+    }
+
+    /// Formats the time instance according to ISO 8601, e.g., `2024-03-01T12:00:00.000Z`.
+    pub fn to_iso_string(millis: f64) -> Option<String> {
+        if !millis.is_finite() {
+            return None;
+        }
+
+        let (year, month, day) = civil_from_days(days(millis));
+        let millis_of_day = millis_of_day(millis);
+
+        Some(format!(
+            "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{millis:03}Z",
+            hour = hour(millis),
+            minute = minute(millis),
+            second = second(millis),
+            millis = (millis_of_day % MILLIS_PER_SECOND).floor(),
+        ))
+    }
+}
+
+/// Functions on texts. Positions and lengths refer to characters and not to bytes.
+pub mod text {
+    use regex::Regex;
+    use std::cell::RefCell;
+
+    thread_local! {
+        /// The last compiled pattern, since an expression usually uses the same pattern for all its inputs
+        static LAST_REGEX: RefCell<Option<(String, Option<Regex>)>> = const { RefCell::new(None) };
+    }
+
+    pub fn concat(a: &str, b: &str) -> String {
+        let mut result = String::with_capacity(a.len() + b.len());
+        result.push_str(a);
+        result.push_str(b);
+        result
+    }
+
+    /// The part of `text` that starts at the character index `start` and has at most `length` characters.
+    pub fn substring(text: &str, start: f64, length: f64) -> Option<String> {
+        if !(start >= 0. && length >= 0.) {
+            return None;
+        }
+
+        Some(
+            text.chars()
+                .skip(start as usize)
+                .take(length as usize)
+                .collect(),
+        )
+    }
+
+    pub fn upper(text: &str) -> String {
+        text.to_uppercase()
+    }
+
+    pub fn lower(text: &str) -> String {
+        text.to_lowercase()
+    }
+
+    /// Checks whether the regular expression `pattern` matches any part of `text`.
+    /// Returns `None` if the pattern is invalid.
+    pub fn matches(text: &str, pattern: &str) -> Option<bool> {
+        LAST_REGEX.with(|last_regex| {
+            let mut last_regex = last_regex.borrow_mut();
+
+            if !matches!(&*last_regex, Some((last_pattern, _)) if last_pattern == pattern) {
+                *last_regex = Some((pattern.to_string(), Regex::new(pattern).ok()));
+            }
+
+            let (_, regex) = last_regex.as_ref()?;
+
+            Some(regex.as_ref()?.is_match(text))
+        })
+    }
 }
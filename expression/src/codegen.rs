@@ -59,6 +59,31 @@ impl ExpressionAst {
     pub fn name(&self) -> &str {
         self.name.as_ref()
     }
+
+    /// If the expression just returns a variable, e.g., `let a = B * 2; a`, this is its name.
+    pub fn output_variable(&self) -> Option<&str> {
+        let mut node = &self.root;
+        loop {
+            match node {
+                AstNode::Variable { name, .. } => return Some(name.as_ref()),
+                AstNode::AssignmentsAndExpression { expression, .. } => node = expression,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Returns whether the expression refers to the parameter `parameter`.
+    pub fn uses_parameter(&self, parameter: &str) -> bool {
+        self.root.uses_variable(&parameter.into())
+    }
+
+    pub(crate) fn root(&self) -> &AstNode {
+        &self.root
+    }
+
+    pub(crate) fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
 }
 
 impl ToTokens for ExpressionAst {
@@ -113,6 +138,8 @@ impl ToTokens for Prelude {
 #[derive(Debug, Clone)]
 pub enum AstNode {
     Constant(f64),
+    TextConstant(String),
+    BoolConstant(bool),
     NoData,
     Variable {
         name: Identifier,
@@ -135,27 +162,70 @@ pub enum AstNode {
 impl AstNode {
     pub fn data_type(&self) -> DataType {
         match self {
-            // - only support number constants
-            // - no data is a number for now
+            // no data is a number for now, but it can be used in place of any type
             Self::Constant(_) | Self::NoData => DataType::Number,
+            Self::TextConstant(_) => DataType::Text,
+            Self::BoolConstant(_) => DataType::Bool,
 
             Self::Variable { data_type, .. } => *data_type,
 
             Self::Function { function, .. } => function.output_type(),
 
-            // we have to check beforehand that all branches have the same type
-            Self::Branch { else_branch, .. } => else_branch.data_type(),
+            // we have to check beforehand that all branches have the same type or no data
+            Self::Branch {
+                condition_branches,
+                else_branch,
+            } => condition_branches
+                .iter()
+                .map(|branch| &branch.body)
+                .chain(std::iter::once(else_branch.as_ref()))
+                .find(|body| !matches!(body, Self::NoData))
+                .map_or(DataType::Number, Self::data_type),
 
             Self::AssignmentsAndExpression { expression, .. } => expression.data_type(),
         }
     }
+
+    fn uses_variable(&self, variable: &Identifier) -> bool {
+        match self {
+            Self::Constant(_) | Self::TextConstant(_) | Self::BoolConstant(_) | Self::NoData => {
+                false
+            }
+            Self::Variable { name, .. } => name == variable,
+            Self::Function { args, .. } => args.iter().any(|arg| arg.uses_variable(variable)),
+            Self::Branch {
+                condition_branches,
+                else_branch,
+            } => {
+                condition_branches.iter().any(|branch| {
+                    branch.condition.uses_variable(variable) || branch.body.uses_variable(variable)
+                }) || else_branch.uses_variable(variable)
+            }
+            Self::AssignmentsAndExpression {
+                assignments,
+                expression,
+            } => {
+                assignments
+                    .iter()
+                    .any(|assignment| assignment.expression.uses_variable(variable))
+                    || expression.uses_variable(variable)
+            }
+        }
+    }
 }
 
 impl ToTokens for AstNode {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let new_tokens = match self {
             Self::Constant(n) => quote! { Some(#n) },
+            Self::TextConstant(text) => quote! { Some(String::from(#text)) },
+            Self::BoolConstant(b) => quote! { Some(#b) },
             Self::NoData => quote! { None },
+            // texts are not `Copy`, so we clone them to be able to use a variable multiple times
+            Self::Variable {
+                name,
+                data_type: DataType::Text,
+            } => quote! { #name.clone() },
             Self::Variable { name, .. } => quote! { #name },
             Self::Function { function, args } => {
                 let fn_name = function.name();
@@ -260,6 +330,8 @@ pub struct Branch {
 #[derive(Debug, Clone)]
 pub enum BooleanExpression {
     Constant(bool),
+    /// An expression that outputs a [`DataType::Bool`], where no data is `false`
+    Value(Box<AstNode>),
     Comparison {
         left: Box<AstNode>,
         op: BooleanComparator,
@@ -272,10 +344,26 @@ pub enum BooleanExpression {
     },
 }
 
+impl BooleanExpression {
+    fn uses_variable(&self, variable: &Identifier) -> bool {
+        match self {
+            Self::Constant(_) => false,
+            Self::Value(value) => value.uses_variable(variable),
+            Self::Comparison { left, right, .. } => {
+                left.uses_variable(variable) || right.uses_variable(variable)
+            }
+            Self::Operation { left, right, .. } => {
+                left.uses_variable(variable) || right.uses_variable(variable)
+            }
+        }
+    }
+}
+
 impl ToTokens for BooleanExpression {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let new_tokens = match self {
             Self::Constant(b) => quote! { #b },
+            Self::Value(value) => quote! { (#value).unwrap_or(false) },
             Self::Comparison { left, op, right } => quote! { ((#left) #op (#right)) },
             Self::Operation { left, op, right } => quote! { ( (#left) #op (#right) ) },
         };
@@ -352,6 +440,9 @@ pub enum Parameter {
     MultiPoint(Identifier),
     MultiLineString(Identifier),
     MultiPolygon(Identifier),
+    Text(Identifier),
+    Bool(Identifier),
+    DateTime(Identifier),
 }
 
 impl AsRef<str> for Parameter {
@@ -360,7 +451,10 @@ impl AsRef<str> for Parameter {
             Self::Number(identifier)
             | Self::MultiPoint(identifier)
             | Self::MultiLineString(identifier)
-            | Self::MultiPolygon(identifier) => identifier.as_ref(),
+            | Self::MultiPolygon(identifier)
+            | Self::Text(identifier)
+            | Self::Bool(identifier)
+            | Self::DateTime(identifier) => identifier.as_ref(),
         }
     }
 }
@@ -371,7 +465,10 @@ impl Parameter {
             Self::Number(identifier)
             | Self::MultiPoint(identifier)
             | Self::MultiLineString(identifier)
-            | Self::MultiPolygon(identifier) => identifier,
+            | Self::MultiPolygon(identifier)
+            | Self::Text(identifier)
+            | Self::Bool(identifier)
+            | Self::DateTime(identifier) => identifier,
         }
     }
 
@@ -381,6 +478,9 @@ impl Parameter {
             Self::MultiPoint(_) => DataType::MultiPoint,
             Self::MultiLineString(_) => DataType::MultiLineString,
             Self::MultiPolygon(_) => DataType::MultiPolygon,
+            Self::Text(_) => DataType::Text,
+            Self::Bool(_) => DataType::Bool,
+            Self::DateTime(_) => DataType::DateTime,
         }
     }
 }
@@ -391,6 +491,10 @@ pub enum DataType {
     MultiPoint,
     MultiLineString,
     MultiPolygon,
+    Text,
+    Bool,
+    /// A time instance in milliseconds since the Unix epoch
+    DateTime,
 }
 
 impl std::fmt::Display for DataType {
@@ -400,6 +504,9 @@ impl std::fmt::Display for DataType {
             Self::MultiPoint => "geometry (multipoint)",
             Self::MultiLineString => "geometry (multilinestring)",
             Self::MultiPolygon => "geometry (multipolygon)",
+            Self::Text => "text",
+            Self::Bool => "bool",
+            Self::DateTime => "datetime",
         };
 
         write!(f, "{s}")
@@ -409,7 +516,8 @@ impl std::fmt::Display for DataType {
 impl ToTokens for DataType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         tokens.extend(match self {
-            Self::Number => quote! { f64 },
+            // date times are numbers in the generated code, s.t. they can use the number operations
+            Self::Number | Self::DateTime => quote! { f64 },
             Self::MultiPoint => {
                 quote! { MultiPoint }
             }
@@ -419,6 +527,8 @@ impl ToTokens for DataType {
             Self::MultiPolygon => {
                 quote! { MultiPolygon }
             }
+            Self::Text => quote! { String },
+            Self::Bool => quote! { bool },
         });
     }
 }
@@ -428,6 +538,9 @@ impl DataType {
         match self {
             Self::Number => "number",
             Self::MultiPoint | Self::MultiLineString | Self::MultiPolygon => "geometry",
+            Self::Text => "text",
+            Self::Bool => "bool",
+            Self::DateTime => "datetime",
         }
     }
 
@@ -438,8 +551,18 @@ impl DataType {
             Self::MultiPoint => 'p',
             Self::MultiLineString => 'l',
             Self::MultiPolygon => 'q',
+            Self::Text => 't',
+            Self::Bool => 'b',
+            Self::DateTime => 'd',
         }
     }
+
+    pub fn is_geometry(self) -> bool {
+        matches!(
+            self,
+            Self::MultiPoint | Self::MultiLineString | Self::MultiPolygon
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -171,7 +171,7 @@ mod tests {
     use crate::{DataType, ExpressionParser, Parameter};
 
     use super::*;
-    use geoengine_expression_deps::{MultiPoint, MultiPolygon};
+    use geoengine_expression_deps::{GeoOptionOperations, MultiPoint, MultiPolygon};
     use quote::quote;
 
     #[test]
@@ -259,4 +259,71 @@ mod tests {
             Some(MultiPoint::from(point!(x: 2.5, y: 3.0)))
         );
     }
+
+    #[test]
+    fn it_compiles_geometry_functions() {
+        use geo::polygon;
+
+        let dependencies = ExpressionDependencies::new().unwrap();
+
+        let ast = ExpressionParser::new(
+            &[Parameter::MultiPolygon("geom".into())],
+            DataType::MultiPolygon,
+        )
+        .unwrap()
+        .parse("expression", "buffer(simplify(geom, 0.1), 1)")
+        .unwrap();
+
+        let linked_expression = LinkedExpression::from_ast(&ast, &dependencies).unwrap();
+
+        let buffer = unsafe {
+            linked_expression
+                .function_nary::<fn(Option<MultiPolygon>) -> Option<MultiPolygon>>()
+                .unwrap()
+        }(Some(MultiPolygon::from(polygon![
+            (x: 0., y: 0.),
+            (x: 4., y: 0.),
+            (x: 4., y: 4.),
+            (x: 0., y: 4.),
+            (x: 0., y: 0.),
+        ])));
+
+        // the square plus four rectangles and an approximated unit circle at the corners
+        let area = buffer.area().unwrap();
+        assert!((area - (16. + 16. + std::f64::consts::PI)).abs() < 0.05);
+    }
+
+    #[test]
+    fn it_compiles_text_and_date_functions() {
+        let dependencies = ExpressionDependencies::new().unwrap();
+
+        let ast = ExpressionParser::new(
+            &[
+                Parameter::Text("name".into()),
+                Parameter::DateTime("time".into()),
+            ],
+            DataType::Text,
+        )
+        .unwrap()
+        .parse(
+            "expression",
+            r#"if matches(name, "^\w+$") { concat(upper(name), to_text(add_days(time, 1))) } else { NODATA }"#,
+        )
+        .unwrap();
+
+        let linked_expression = LinkedExpression::from_ast(&ast, &dependencies).unwrap();
+
+        let label = unsafe {
+            linked_expression
+                .function_nary::<fn(Option<String>, Option<f64>) -> Option<String>>()
+                .unwrap()
+        };
+
+        assert_eq!(
+            label(Some("abc".into()), Some(0.)),
+            Some("ABC1970-01-02T00:00:00.000Z".into())
+        );
+        assert_eq!(label(Some("a b".into()), Some(0.)), None);
+        assert_eq!(label(Some("abc".into()), None), None);
+    }
 }
@@ -40,6 +40,9 @@ pub enum ExpressionExecutionError {
         source: libloading::Error,
         name: String,
     },
+
+    #[snafu(display("Cannot resolve variable in expression: {name}"))]
+    UnresolvedVariable { name: String },
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -207,8 +210,18 @@ pub enum ExpressionSemanticError {
     #[snafu(display("All branches of an if-then-else expression must output the same type"))]
     AllBranchesMustOutputSameType,
 
-    #[snafu(display("Comparisons can only be used with numbers"))]
-    ComparisonsMustBeUsedWithNumbers,
+    #[snafu(display("Comparisons cannot be used with geometries"))]
+    ComparisonsCannotBeUsedWithGeometries,
+
+    #[snafu(display("Comparisons can only be used with values of the same type"))]
+    ComparisonsMustBeUsedWithSameTypes,
+
+    #[snafu(display(
+        "A condition must be a comparison or output `bool`, but it outputs `{actual}`"
+    ))]
+    ConditionMustBeBool {
+        actual: DataType,
+    },
 
     #[snafu(display("Operators can only be used with numbers"))]
     OperatorsMustBeUsedWithNumbers,
@@ -1,7 +1,9 @@
 use crate::{
     codegen::{DataType, Identifier},
     error::ExpressionSemanticError,
+    interpreter::Value,
 };
+use geoengine_expression_deps::{text, time, GeoOptionOperations, GeoOptionSimplify};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use std::{collections::HashMap, hash::Hash, sync::OnceLock};
@@ -41,6 +43,9 @@ pub struct Function {
 
     /// Write the function to a token stream
     token_fn: fn(&Self, &mut TokenStream) -> (),
+
+    /// Evaluate the function on interpreter [`Value`]s
+    eval_fn: fn(&[Value]) -> Value,
 }
 
 impl Function {
@@ -51,6 +56,10 @@ impl Function {
     pub fn output_type(&self) -> DataType {
         self.output_type
     }
+
+    pub fn eval_fn(&self) -> fn(&[Value]) -> Value {
+        self.eval_fn
+    }
 }
 
 impl ToTokens for Function {
@@ -92,7 +101,7 @@ pub static FUNCTIONS: OnceLock<HashMap<&'static str, FunctionGenerator>> = OnceL
 
 /// Add a function generator for a function that returns a [`DataType::Number`] constant.
 macro_rules! add_const_num {
-    ( $name:literal, $functions:expr, $fn:ty ) => {{
+    ( $name:literal, $functions:expr, $fn:path ) => {{
         let name = $name;
         $functions.insert(
             name,
@@ -112,6 +121,7 @@ macro_rules! add_const_num {
                                 }
                             });
                         },
+                        eval_fn: |_args| Value::Number(Some($fn)),
                     }),
                     _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
                         name: name.into(),
@@ -126,7 +136,7 @@ macro_rules! add_const_num {
 
 /// Add a function generator for a function with 1 [`DataType::Number`] that returns a [`DataType::Number`].
 macro_rules! add_1_num {
-    ( $name:literal, $functions:expr, $fn:ty ) => {{
+    ( $name:literal, $functions:expr, $fn:path ) => {{
         let name = $name;
         $functions.insert(
             name,
@@ -146,6 +156,10 @@ macro_rules! add_1_num {
                                 }
                             });
                         },
+                        eval_fn: |args| match args {
+                            [Value::Number(a)] => Value::Number(a.map($fn)),
+                            _ => Value::Number(None),
+                        },
                     }),
                     _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
                         name: name.into(),
@@ -160,7 +174,7 @@ macro_rules! add_1_num {
 
 /// Add a function generator for a function with 2 [`DataType::Number`]s that returns a [`DataType::Number`].
 macro_rules! add_2_num {
-    ( $name:literal, $functions:expr, $fn:ty ) => {{
+    ( $name:literal, $functions:expr, $fn:path ) => {{
         let name = $name;
         $functions.insert(
             name,
@@ -183,6 +197,12 @@ macro_rules! add_2_num {
                                 }
                             });
                         },
+                        eval_fn: |args| match args {
+                            [Value::Number(Some(a)), Value::Number(Some(b))] => {
+                                Value::Number(Some($fn(*a, *b)))
+                            }
+                            _ => Value::Number(None),
+                        },
                     }),
                     _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
                         name: name.into(),
@@ -199,6 +219,185 @@ macro_rules! add_2_num {
     }};
 }
 
+/// Add a function generator for a function with 1 geometry of any type that returns a value of the given [`DataType`].
+/// The function calls the given method of [`GeoOptionOperations`].
+macro_rules! add_1_geom {
+    ( $name:literal, $functions:expr, $output_type:ident, $method:ident ) => {{
+        let name = $name;
+        $functions.insert(
+            name,
+            FunctionGenerator {
+                name,
+                generate_fn: |name, args| match args {
+                    [dtype @ (DataType::MultiPoint
+                    | DataType::MultiLineString
+                    | DataType::MultiPolygon)] => Ok(Function {
+                        name: unique_name(name, args),
+                        signature: vec![*dtype],
+                        output_type: DataType::$output_type,
+                        token_fn: |fn_, tokens| {
+                            let name = &fn_.name;
+                            let dtype = fn_.signature[0];
+                            let output_type = fn_.output_type;
+
+                            tokens.extend(quote! {
+                                fn #name(geom: Option<#dtype>) -> Option<#output_type> {
+                                    geom.$method()
+                                }
+                            });
+                        },
+                        eval_fn: |args| match args {
+                            [geom] => Value::$output_type(geom.$method()),
+                            _ => Value::$output_type(None),
+                        },
+                    }),
+                    _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
+                        name: name.into(),
+                        expected: vec![DataType::MultiPoint.group_name().to_string()],
+                        actual: args.into(),
+                    }),
+                },
+            },
+        );
+    }};
+}
+
+/// Add a function generator for a function with 1 [`DataType::MultiPoint`] that returns a [`DataType::Number`].
+/// The function calls the given method of [`GeoOptionOperations`].
+macro_rules! add_1_point {
+    ( $name:literal, $functions:expr, $method:ident ) => {{
+        let name = $name;
+        $functions.insert(
+            name,
+            FunctionGenerator {
+                name,
+                generate_fn: |name, args| match args {
+                    [DataType::MultiPoint] => Ok(Function {
+                        name: unique_name(name, args),
+                        signature: vec![DataType::MultiPoint],
+                        output_type: DataType::Number,
+                        token_fn: |fn_, tokens| {
+                            let name = &fn_.name;
+                            let dtype = DataType::MultiPoint;
+                            let output_type = DataType::Number;
+
+                            tokens.extend(quote! {
+                                fn #name(geom: Option<#dtype>) -> Option<#output_type> {
+                                    geom.$method()
+                                }
+                            });
+                        },
+                        eval_fn: |args| match args {
+                            [point @ Value::MultiPoint(_)] => Value::Number(point.$method()),
+                            _ => Value::Number(None),
+                        },
+                    }),
+                    _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
+                        name: name.into(),
+                        expected: vec![DataType::MultiPoint.group_name().to_string()],
+                        actual: args.into(),
+                    }),
+                },
+            },
+        );
+    }};
+}
+
+/// Add a function generator for a function with 1 [`DataType::Number`] or [`DataType::DateTime`] that returns a [`DataType::Number`].
+/// Both are milliseconds since the Unix epoch in the generated code.
+macro_rules! add_1_time {
+    ( $name:literal, $functions:expr, $fn:path ) => {{
+        let name = $name;
+        $functions.insert(
+            name,
+            FunctionGenerator {
+                name,
+                generate_fn: |name, args| match args {
+                    [dtype @ (DataType::Number | DataType::DateTime)] => Ok(Function {
+                        name: unique_name(name, args),
+                        signature: vec![*dtype],
+                        output_type: DataType::Number,
+                        token_fn: |fn_, tokens| {
+                            let name = &fn_.name;
+                            let dtype = fn_.signature[0];
+                            let output_type = fn_.output_type;
+
+                            tokens.extend(quote! {
+                                fn #name(a: Option<#dtype>) -> Option<#output_type> {
+                                    a.map($fn)
+                                }
+                            });
+                        },
+                        eval_fn: |args| match args {
+                            [Value::Number(a) | Value::DateTime(a)] => Value::Number(a.map($fn)),
+                            _ => Value::Number(None),
+                        },
+                    }),
+                    _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
+                        name: name.into(),
+                        expected: vec![DataType::DateTime.group_name().to_string()],
+                        actual: args.into(),
+                    }),
+                },
+            },
+        );
+    }};
+}
+
+/// Add a function generator for a function with a fixed signature of non-geometry types.
+///
+/// The `body` is used for the generated code and for the interpreter.
+/// It sees the arguments without no data and must return an [`Option`] of the output type.
+/// If any argument is no data, the output is no data.
+macro_rules! add_fixed {
+    ( $name:literal, $functions:expr, ( $( $arg:ident: $dtype:ident ),+ ) -> $output_type:ident, $body:expr ) => {{
+        let name = $name;
+        $functions.insert(
+            name,
+            FunctionGenerator {
+                name,
+                generate_fn: |name, args| match args {
+                    [ $( DataType::$dtype ),+ ] => Ok(Function {
+                        name: unique_name(name, args),
+                        signature: vec![ $( DataType::$dtype ),+ ],
+                        output_type: DataType::$output_type,
+                        token_fn: |fn_, tokens| {
+                            let name = &fn_.name;
+                            let params = [ $( (quote! { $arg }, DataType::$dtype) ),+ ]
+                                .into_iter()
+                                .map(|(arg, dtype)| quote! { #arg: Option<#dtype> });
+                            let output_type = fn_.output_type;
+
+                            tokens.extend(quote! {
+                                fn #name( #(#params),* ) -> Option<#output_type> {
+                                    $( let $arg = $arg?; )+
+                                    $body
+                                }
+                            });
+                        },
+                        eval_fn: |args| match args {
+                            [ $( Value::$dtype(Some($arg)) ),+ ] => {
+                                $( let $arg = ToOwned::to_owned($arg); )+
+                                Value::$output_type($body)
+                            }
+                            _ => Value::$output_type(None),
+                        },
+                    }),
+                    _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
+                        name: name.into(),
+                        expected: [ $( DataType::$dtype ),+ ]
+                            .iter()
+                            .map(DataType::group_name)
+                            .map(ToString::to_string)
+                            .collect(),
+                        actual: args.into(),
+                    }),
+                },
+            },
+        );
+    }};
+}
+
 // TODO: change to [`std::sync::LazyLock'] once stable
 #[allow(clippy::too_many_lines)]
 pub fn init_functions() -> HashMap<&'static str, FunctionGenerator> {
@@ -210,10 +409,10 @@ pub fn init_functions() -> HashMap<&'static str, FunctionGenerator> {
     add_2_num!("div", functions, std::ops::Div::div);
     add_2_num!("min", functions, f64::min);
     add_2_num!("max", functions, f64::max);
-    add_2_num!("abs", functions, f64::abs);
     add_2_num!("pow", functions, f64::powf);
     add_2_num!("mod", functions, std::ops::Rem::rem);
 
+    add_1_num!("abs", functions, f64::abs);
     add_1_num!("sqrt", functions, f64::sqrt);
     add_1_num!("cos", functions, f64::cos);
     add_1_num!("sin", functions, f64::sin);
@@ -232,46 +431,141 @@ pub fn init_functions() -> HashMap<&'static str, FunctionGenerator> {
     add_const_num!("pi", functions, std::f64::consts::PI);
     add_const_num!("e", functions, std::f64::consts::E);
 
-    // [`geo`] functions
-
-    let name = "centroid";
+    // date functions on time instances in milliseconds since the Unix epoch
+
+    add_1_time!("year", functions, time::year);
+    add_1_time!("month", functions, time::month);
+    add_1_time!("day", functions, time::day);
+    add_1_time!("day_of_year", functions, time::day_of_year);
+    add_1_time!("hour", functions, time::hour);
+    add_1_time!("minute", functions, time::minute);
+    add_1_time!("second", functions, time::second);
+
+    add_fixed!("datetime", functions, (millis: Number) -> DateTime, Some(millis));
+    add_fixed!("millis", functions, (datetime: DateTime) -> Number, Some(datetime));
+    add_fixed!("date", functions, (year: Number, month: Number, day: Number) -> DateTime, time::date(year, month, day));
+    add_fixed!("add_days", functions, (datetime: DateTime, days: Number) -> DateTime, Some(time::add_days(datetime, days)));
+    add_fixed!("add_hours", functions, (datetime: DateTime, hours: Number) -> DateTime, Some(time::add_hours(datetime, hours)));
+    add_fixed!("add_minutes", functions, (datetime: DateTime, minutes: Number) -> DateTime, Some(time::add_minutes(datetime, minutes)));
+    add_fixed!("add_seconds", functions, (datetime: DateTime, seconds: Number) -> DateTime, Some(time::add_seconds(datetime, seconds)));
+    add_fixed!("days_between", functions, (start: DateTime, end: DateTime) -> Number, Some(time::days_between(start, end)));
+
+    // text functions
+
+    add_fixed!("concat", functions, (a: Text, b: Text) -> Text, Some(text::concat(&a, &b)));
+    add_fixed!("substring", functions, (text: Text, start: Number, length: Number) -> Text, text::substring(&text, start, length));
+    add_fixed!("upper", functions, (text: Text) -> Text, Some(text::upper(&text)));
+    add_fixed!("lower", functions, (text: Text) -> Text, Some(text::lower(&text)));
+    add_fixed!("matches", functions, (text: Text, pattern: Text) -> Bool, text::matches(&text, &pattern));
+    add_fixed!("to_number", functions, (text: Text) -> Number, text.trim().parse().ok());
+
+    let name = "to_text";
     functions.insert(
         name,
         FunctionGenerator {
             name,
             generate_fn: |name, args| match args {
-                [dtype @ (DataType::MultiPoint
-                | DataType::MultiLineString
-                | DataType::MultiPolygon)] => Ok(Function {
+                [dtype @ (DataType::Number
+                | DataType::Bool
+                | DataType::DateTime
+                | DataType::Text)] => Ok(Function {
                     name: unique_name(name, args),
                     signature: vec![*dtype],
-                    output_type: DataType::MultiPoint,
-                    token_fn: move |fn_, tokens| {
+                    output_type: DataType::Text,
+                    token_fn: |fn_, tokens| {
                         let name = &fn_.name;
                         let dtype = fn_.signature[0];
 
                         let inner_operation = match dtype {
-                            DataType::MultiPoint
-                            | DataType::MultiLineString
-                            | DataType::MultiPolygon => quote! {
-                                geom.centroid()
+                            DataType::DateTime => quote! {
+                                value.and_then(time::to_iso_string)
+                            },
+                            DataType::Text => quote! {
+                                value
+                            },
+                            _ => quote! {
+                                value.map(|value| value.to_string())
                             },
-                            // should never happen
-                            DataType::Number => quote! {},
                         };
 
-                        let output_type = &fn_.output_type;
+                        let output_type = fn_.output_type;
 
                         tokens.extend(quote! {
-                            fn #name(geom: Option<#dtype>) -> Option<#output_type> {
+                            fn #name(value: Option<#dtype>) -> Option<#output_type> {
                                 #inner_operation
                             }
                         });
                     },
+                    eval_fn: |args| match args {
+                        [Value::Number(value)] => Value::Text(value.map(|value| value.to_string())),
+                        [Value::Bool(value)] => Value::Text(value.map(|value| value.to_string())),
+                        [Value::DateTime(value)] => {
+                            Value::Text(value.and_then(time::to_iso_string))
+                        }
+                        [Value::Text(value)] => Value::Text(value.clone()),
+                        _ => Value::Text(None),
+                    },
                 }),
                 _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
                     name: name.into(),
-                    expected: [DataType::MultiPoint]
+                    expected: vec![DataType::Number.group_name().to_string()],
+                    actual: args.into(),
+                }),
+            },
+        },
+    );
+
+    // bool functions
+
+    add_fixed!("not", functions, (value: Bool) -> Bool, Some(!value));
+
+    // [`geo`] functions
+
+    add_1_geom!("centroid", functions, MultiPoint, centroid);
+    add_1_geom!("area", functions, Number, area);
+    add_1_geom!("length", functions, Number, length);
+    add_1_geom!("perimeter", functions, Number, perimeter);
+    add_1_geom!("num_points", functions, Number, num_points);
+    add_1_geom!("convex_hull", functions, MultiPolygon, convex_hull);
+    add_1_geom!("envelope", functions, MultiPolygon, envelope);
+
+    add_1_point!("x", functions, x);
+    add_1_point!("y", functions, y);
+
+    let name = "buffer";
+    functions.insert(
+        name,
+        FunctionGenerator {
+            name,
+            generate_fn: |name, args| match args {
+                [dtype @ (DataType::MultiPoint
+                | DataType::MultiLineString
+                | DataType::MultiPolygon), DataType::Number] => Ok(Function {
+                    name: unique_name(name, args),
+                    signature: vec![*dtype, DataType::Number],
+                    output_type: DataType::MultiPolygon,
+                    token_fn: |fn_, tokens| {
+                        let name = &fn_.name;
+                        let dtype = fn_.signature[0];
+                        let number = DataType::Number;
+                        let output_type = fn_.output_type;
+
+                        tokens.extend(quote! {
+                            fn #name(geom: Option<#dtype>, distance: Option<#number>) -> Option<#output_type> {
+                                geom.buffer(distance?)
+                            }
+                        });
+                    },
+                    eval_fn: |args| match args {
+                        [geom, Value::Number(Some(distance))] => {
+                            Value::MultiPolygon(geom.buffer(*distance))
+                        }
+                        _ => Value::MultiPolygon(None),
+                    },
+                }),
+                _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
+                    name: name.into(),
+                    expected: [DataType::MultiPolygon, DataType::Number]
                         .iter()
                         .map(DataType::group_name)
                         .map(ToString::to_string)
@@ -282,7 +576,7 @@ pub fn init_functions() -> HashMap<&'static str, FunctionGenerator> {
         },
     );
 
-    let name = "area";
+    let name = "simplify";
     functions.insert(
         name,
         FunctionGenerator {
@@ -290,36 +584,82 @@ pub fn init_functions() -> HashMap<&'static str, FunctionGenerator> {
             generate_fn: |name, args| match args {
                 [dtype @ (DataType::MultiPoint
                 | DataType::MultiLineString
-                | DataType::MultiPolygon)] => Ok(Function {
+                | DataType::MultiPolygon), DataType::Number] => Ok(Function {
                     name: unique_name(name, args),
-                    signature: vec![*dtype],
-                    output_type: DataType::Number,
-                    token_fn: move |fn_, tokens| {
+                    signature: vec![*dtype, DataType::Number],
+                    output_type: *dtype,
+                    token_fn: |fn_, tokens| {
                         let name = &fn_.name;
                         let dtype = fn_.signature[0];
+                        let number = DataType::Number;
 
-                        let inner_operation = match dtype {
-                            DataType::MultiPoint
-                            | DataType::MultiLineString
-                            | DataType::MultiPolygon => quote! {
-                                geom.area()
-                            },
-                            // should never happen
-                            DataType::Number => quote! {},
-                        };
+                        tokens.extend(quote! {
+                            fn #name(geom: Option<#dtype>, tolerance: Option<#number>) -> Option<#dtype> {
+                                geom.simplify(tolerance?)
+                            }
+                        });
+                    },
+                    eval_fn: |args| match args {
+                        [Value::MultiPoint(geom), Value::Number(tolerance)] => {
+                            Value::MultiPoint(tolerance.and_then(|t| geom.simplify(t)))
+                        }
+                        [Value::MultiLineString(geom), Value::Number(tolerance)] => {
+                            Value::MultiLineString(tolerance.and_then(|t| geom.simplify(t)))
+                        }
+                        [Value::MultiPolygon(geom), Value::Number(tolerance)] => {
+                            Value::MultiPolygon(tolerance.and_then(|t| geom.simplify(t)))
+                        }
+                        // should never happen
+                        _ => Value::Number(None),
+                    },
+                }),
+                _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
+                    name: name.into(),
+                    expected: [DataType::MultiPolygon, DataType::Number]
+                        .iter()
+                        .map(DataType::group_name)
+                        .map(ToString::to_string)
+                        .collect(),
+                    actual: args.into(),
+                }),
+            },
+        },
+    );
 
-                        let output_type = &fn_.output_type;
+    let name = "distance";
+    functions.insert(
+        name,
+        FunctionGenerator {
+            name,
+            generate_fn: |name, args| match args {
+                [a @ (DataType::MultiPoint
+                | DataType::MultiLineString
+                | DataType::MultiPolygon), b @ (DataType::MultiPoint
+                | DataType::MultiLineString
+                | DataType::MultiPolygon)] => Ok(Function {
+                    name: unique_name(name, args),
+                    signature: vec![*a, *b],
+                    output_type: DataType::Number,
+                    token_fn: |fn_, tokens| {
+                        let name = &fn_.name;
+                        let a_type = fn_.signature[0];
+                        let b_type = fn_.signature[1];
+                        let output_type = fn_.output_type;
 
                         tokens.extend(quote! {
-                            fn #name(geom: Option<#dtype>) -> Option<#output_type> {
-                                #inner_operation
+                            fn #name(a: Option<#a_type>, b: Option<#b_type>) -> Option<#output_type> {
+                                a.distance(&b)
                             }
                         });
                     },
+                    eval_fn: |args| match args {
+                        [a, b] => Value::Number(a.distance(b)),
+                        _ => Value::Number(None),
+                    },
                 }),
                 _ => Err(ExpressionSemanticError::InvalidFunctionArguments {
                     name: name.into(),
-                    expected: [DataType::MultiPoint]
+                    expected: [DataType::MultiPolygon, DataType::MultiPolygon]
                         .iter()
                         .map(DataType::group_name)
                         .map(ToString::to_string)
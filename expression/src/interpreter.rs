@@ -0,0 +1,775 @@
+use crate::{
+    codegen::{AstNode, BooleanComparator, BooleanExpression, BooleanOperator, Identifier},
+    error::{self, ExpressionExecutionError},
+    ExpressionAst,
+};
+use geoengine_expression_deps::{
+    GeoGeometry, GeoOptionOperations, MultiLineString, MultiPoint, MultiPolygon,
+};
+use snafu::OptionExt;
+use std::collections::HashMap;
+
+pub type Result<T, E = ExpressionExecutionError> = std::result::Result<T, E>;
+
+/// A value that is passed into or returned from an [`InterpretedExpression`].
+/// `None` represents no data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(Option<f64>),
+    MultiPoint(Option<MultiPoint>),
+    MultiLineString(Option<MultiLineString>),
+    MultiPolygon(Option<MultiPolygon>),
+    Text(Option<String>),
+    Bool(Option<bool>),
+    /// A time instance in milliseconds since the Unix epoch
+    DateTime(Option<f64>),
+}
+
+impl Value {
+    /// Returns the number or `None` if the value is no data or not a number
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => *number,
+            Self::MultiPoint(_)
+            | Self::MultiLineString(_)
+            | Self::MultiPolygon(_)
+            | Self::Text(_)
+            | Self::Bool(_)
+            | Self::DateTime(_) => None,
+        }
+    }
+
+    /// Returns the key for comparing the value with another value of the same type or `None` if the value is no data.
+    /// Date times compare like numbers and geometries are not comparable.
+    fn comparison_key(&self) -> Option<ComparisonKey<'_>> {
+        match self {
+            Self::Number(number) | Self::DateTime(number) => number.map(ComparisonKey::Number),
+            Self::Text(text) => text.as_deref().map(ComparisonKey::Text),
+            Self::Bool(value) => value.map(ComparisonKey::Bool),
+            Self::MultiPoint(_) | Self::MultiLineString(_) | Self::MultiPolygon(_) => None,
+        }
+    }
+}
+
+/// A non-geometry [`Value`] without no data
+#[derive(Debug, PartialEq, PartialOrd)]
+enum ComparisonKey<'v> {
+    Number(f64),
+    Text(&'v str),
+    Bool(bool),
+}
+
+/// Dispatches a geometry operation to the geometry inside a [`Value`].
+/// Other values have no geometry, so the result is `None`.
+macro_rules! dispatch_geometry {
+    ( $value:expr, $geom:ident => $operation:expr ) => {
+        match $value {
+            Value::Number(_) | Value::Text(_) | Value::Bool(_) | Value::DateTime(_) => None,
+            Value::MultiPoint($geom) => $operation,
+            Value::MultiLineString($geom) => $operation,
+            Value::MultiPolygon($geom) => $operation,
+        }
+    };
+}
+
+impl GeoOptionOperations for Value {
+    fn area(&self) -> Option<f64> {
+        dispatch_geometry!(self, geom => geom.area())
+    }
+
+    fn centroid(&self) -> Option<MultiPoint> {
+        dispatch_geometry!(self, geom => geom.centroid())
+    }
+
+    fn length(&self) -> Option<f64> {
+        dispatch_geometry!(self, geom => geom.length())
+    }
+
+    fn perimeter(&self) -> Option<f64> {
+        dispatch_geometry!(self, geom => geom.perimeter())
+    }
+
+    fn num_points(&self) -> Option<f64> {
+        dispatch_geometry!(self, geom => geom.num_points())
+    }
+
+    fn convex_hull(&self) -> Option<MultiPolygon> {
+        dispatch_geometry!(self, geom => geom.convex_hull())
+    }
+
+    fn envelope(&self) -> Option<MultiPolygon> {
+        dispatch_geometry!(self, geom => geom.envelope())
+    }
+
+    fn buffer(&self, distance: f64) -> Option<MultiPolygon> {
+        dispatch_geometry!(self, geom => geom.buffer(distance))
+    }
+
+    fn x(&self) -> Option<f64> {
+        dispatch_geometry!(self, geom => geom.x())
+    }
+
+    fn y(&self) -> Option<f64> {
+        dispatch_geometry!(self, geom => geom.y())
+    }
+
+    fn to_geo(&self) -> Option<GeoGeometry> {
+        dispatch_geometry!(self, geom => geom.to_geo())
+    }
+}
+
+/// Converts the types of expression inputs and outputs to and from [`Value`]s.
+pub trait ValueType: Sized {
+    fn into_value(value: Option<Self>) -> Value;
+
+    /// Returns `None` if the value is no data or of another type
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+/// Numbers are also used for date times, which are milliseconds since the Unix epoch
+impl ValueType for f64 {
+    fn into_value(value: Option<Self>) -> Value {
+        Value::Number(value)
+    }
+
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Number(value) | Value::DateTime(value) => value,
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_value_type {
+    ( $type:ty, $variant:ident ) => {
+        impl ValueType for $type {
+            fn into_value(value: Option<Self>) -> Value {
+                Value::$variant(value)
+            }
+
+            fn from_value(value: Value) -> Option<Self> {
+                match value {
+                    Value::$variant(value) => value,
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_value_type!(String, Text);
+impl_value_type!(bool, Bool);
+impl_value_type!(MultiPoint, MultiPoint);
+impl_value_type!(MultiLineString, MultiLineString);
+impl_value_type!(MultiPolygon, MultiPolygon);
+
+/// Evaluates an expression by walking its syntax tree instead of compiling it.
+/// Thus, it does not need a Rust toolchain at runtime.
+///
+/// It has the same semantics as the [`LinkedExpression`](crate::LinkedExpression),
+/// e.g., functions output no data if any of their inputs is no data.
+#[derive(Debug, Clone)]
+pub struct InterpretedExpression {
+    root: Node,
+    num_parameters: usize,
+    num_slots: usize,
+}
+
+impl InterpretedExpression {
+    pub fn from_ast(ast: &ExpressionAst) -> Result<Self> {
+        let mut slots = Slots::default();
+        for parameter in ast.parameters() {
+            slots.assign(parameter.identifier().clone());
+        }
+        let num_parameters = slots.len;
+
+        let root = Node::resolve(ast.root(), &mut slots)?;
+
+        Ok(Self {
+            root,
+            num_parameters,
+            num_slots: slots.len,
+        })
+    }
+
+    /// Evaluates the expression for `inputs` in the order of the expression's parameters.
+    /// Missing inputs are treated as no data.
+    pub fn evaluate<I>(&self, inputs: I) -> Value
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let mut stack = Vec::with_capacity(self.num_slots);
+        stack.extend(inputs.into_iter().take(self.num_parameters));
+        // reserve the slots for the assignments
+        stack.resize(self.num_slots, Value::Number(None));
+
+        self.root.evaluate(&mut stack)
+    }
+}
+
+/// Maps variables to their position on the evaluation stack
+#[derive(Debug, Default)]
+struct Slots {
+    indices: HashMap<Identifier, usize>,
+    len: usize,
+}
+
+impl Slots {
+    fn assign(&mut self, identifier: Identifier) -> usize {
+        let slot = self.len;
+        self.indices.insert(identifier, slot);
+        self.len += 1;
+        slot
+    }
+
+    fn get(&self, identifier: &Identifier) -> Result<usize> {
+        self.indices
+            .get(identifier)
+            .copied()
+            .context(error::UnresolvedVariable {
+                name: identifier.to_string(),
+            })
+    }
+}
+
+/// An [`AstNode`] with variables resolved to stack slots
+#[derive(Debug, Clone)]
+enum Node {
+    Constant(Value),
+    NoData,
+    Slot(usize),
+    Function {
+        eval_fn: fn(&[Value]) -> Value,
+        args: Vec<Node>,
+    },
+    Branch {
+        condition_branches: Vec<(Condition, Node)>,
+        else_branch: Box<Node>,
+    },
+    AssignmentsAndExpression {
+        assignments: Vec<(usize, Node)>,
+        expression: Box<Node>,
+    },
+}
+
+impl Node {
+    fn resolve(node: &AstNode, slots: &mut Slots) -> Result<Self> {
+        Ok(match node {
+            AstNode::Constant(number) => Self::Constant(Value::Number(Some(*number))),
+            AstNode::TextConstant(text) => Self::Constant(Value::Text(Some(text.clone()))),
+            AstNode::BoolConstant(value) => Self::Constant(Value::Bool(Some(*value))),
+            AstNode::NoData => Self::NoData,
+            AstNode::Variable { name, .. } => Self::Slot(slots.get(name)?),
+            AstNode::Function { function, args } => Self::Function {
+                eval_fn: function.eval_fn(),
+                args: args
+                    .iter()
+                    .map(|arg| Self::resolve(arg, slots))
+                    .collect::<Result<_>>()?,
+            },
+            AstNode::Branch {
+                condition_branches,
+                else_branch,
+            } => Self::Branch {
+                condition_branches: condition_branches
+                    .iter()
+                    .map(|branch| {
+                        Ok((
+                            Condition::resolve(&branch.condition, slots)?,
+                            Self::resolve(&branch.body, slots)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+                else_branch: Box::new(Self::resolve(else_branch, slots)?),
+            },
+            AstNode::AssignmentsAndExpression {
+                assignments,
+                expression,
+            } => {
+                let mut resolved_assignments = Vec::with_capacity(assignments.len());
+                for assignment in assignments {
+                    // the assigned variable is only visible after the assignment
+                    let expression = Self::resolve(&assignment.expression, slots)?;
+                    let slot = slots.assign(assignment.identifier.clone());
+                    resolved_assignments.push((slot, expression));
+                }
+
+                Self::AssignmentsAndExpression {
+                    assignments: resolved_assignments,
+                    expression: Box::new(Self::resolve(expression, slots)?),
+                }
+            }
+        })
+    }
+
+    /// Evaluates the node. Function arguments are pushed on top of the variable slots.
+    fn evaluate(&self, stack: &mut Vec<Value>) -> Value {
+        match self {
+            Self::Constant(value) => value.clone(),
+            Self::NoData => Value::Number(None),
+            Self::Slot(slot) => stack[*slot].clone(),
+            Self::Function { eval_fn, args } => {
+                let base = stack.len();
+                for arg in args {
+                    let value = arg.evaluate(stack);
+                    stack.push(value);
+                }
+
+                let result = eval_fn(&stack[base..]);
+                stack.truncate(base);
+
+                result
+            }
+            Self::Branch {
+                condition_branches,
+                else_branch,
+            } => {
+                for (condition, body) in condition_branches {
+                    if condition.evaluate(stack) {
+                        return body.evaluate(stack);
+                    }
+                }
+
+                else_branch.evaluate(stack)
+            }
+            Self::AssignmentsAndExpression {
+                assignments,
+                expression,
+            } => {
+                for (slot, assignment) in assignments {
+                    let value = assignment.evaluate(stack);
+                    stack[*slot] = value;
+                }
+
+                expression.evaluate(stack)
+            }
+        }
+    }
+}
+
+/// A [`BooleanExpression`] with variables resolved to stack slots
+#[derive(Debug, Clone)]
+enum Condition {
+    Constant(bool),
+    Value(Node),
+    Comparison {
+        left: Node,
+        op: BooleanComparator,
+        right: Node,
+    },
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+}
+
+impl Condition {
+    fn resolve(expression: &BooleanExpression, slots: &mut Slots) -> Result<Self> {
+        Ok(match expression {
+            BooleanExpression::Constant(value) => Self::Constant(*value),
+            BooleanExpression::Value(value) => Self::Value(Node::resolve(value, slots)?),
+            BooleanExpression::Comparison { left, op, right } => Self::Comparison {
+                left: Node::resolve(left, slots)?,
+                op: op.clone(),
+                right: Node::resolve(right, slots)?,
+            },
+            BooleanExpression::Operation { left, op, right } => {
+                let left = Box::new(Self::resolve(left, slots)?);
+                let right = Box::new(Self::resolve(right, slots)?);
+                match op {
+                    BooleanOperator::And => Self::And(left, right),
+                    BooleanOperator::Or => Self::Or(left, right),
+                }
+            }
+        })
+    }
+
+    /// Compares like `Option`s in the generated code, i.e., no data is smaller than any value
+    fn evaluate(&self, stack: &mut Vec<Value>) -> bool {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Value(value) => matches!(value.evaluate(stack), Value::Bool(Some(true))),
+            Self::Comparison { left, op, right } => {
+                let left = left.evaluate(stack);
+                let right = right.evaluate(stack);
+                let left = left.comparison_key();
+                let right = right.comparison_key();
+
+                match op {
+                    BooleanComparator::Equal => left == right,
+                    BooleanComparator::NotEqual => left != right,
+                    BooleanComparator::LessThan => left < right,
+                    BooleanComparator::LessThanOrEqual => left <= right,
+                    BooleanComparator::GreaterThan => left > right,
+                    BooleanComparator::GreaterThanOrEqual => left >= right,
+                }
+            }
+            Self::And(left, right) => left.evaluate(stack) && right.evaluate(stack),
+            Self::Or(left, right) => left.evaluate(stack) || right.evaluate(stack),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DataType, ExpressionDependencies, ExpressionParser, LinkedExpression, Parameter};
+    use geo::{line_string, point, polygon};
+
+    fn interpret(parameters: &[&str], input: &str) -> InterpretedExpression {
+        let parameters: Vec<Parameter> = parameters
+            .iter()
+            .map(|&p| Parameter::Number(p.into()))
+            .collect();
+
+        let ast = ExpressionParser::new(&parameters, DataType::Number)
+            .unwrap()
+            .parse("expression", input)
+            .unwrap();
+
+        InterpretedExpression::from_ast(&ast).unwrap()
+    }
+
+    fn evaluate(expression: &InterpretedExpression, inputs: &[Option<f64>]) -> Option<f64> {
+        expression
+            .evaluate(inputs.iter().copied().map(Value::Number))
+            .as_number()
+    }
+
+    #[test]
+    fn it_evaluates_arithmetics() {
+        let expression = interpret(&["A", "B"], "A + B * 2 - 1 / 4 ** 0.5");
+
+        assert_eq!(evaluate(&expression, &[Some(1.), Some(2.)]), Some(4.5));
+        assert_eq!(evaluate(&expression, &[None, Some(2.)]), None);
+        assert_eq!(evaluate(&expression, &[Some(1.), None]), None);
+    }
+
+    #[test]
+    fn it_evaluates_functions() {
+        let expression = interpret(&["A", "B"], "max(abs(A), sqrt(B)) + round(pi())");
+
+        assert_eq!(evaluate(&expression, &[Some(-5.), Some(16.)]), Some(8.));
+        assert_eq!(evaluate(&expression, &[Some(-2.), Some(16.)]), Some(7.));
+        assert_eq!(evaluate(&expression, &[Some(-2.), None]), None);
+
+        let expression = interpret(&[], "e()");
+
+        assert_eq!(evaluate(&expression, &[]), Some(std::f64::consts::E));
+    }
+
+    #[test]
+    fn it_evaluates_date_functions() {
+        // 2024-03-01T12:00:00Z
+        let t = Some(1_709_294_400_000.);
+        // 1969-12-31T23:59:59.999Z
+        let before_epoch = Some(-1.);
+
+        for (function, expected, expected_before_epoch) in [
+            ("year", 2024., 1969.),
+            ("month", 3., 12.),
+            ("day", 1., 31.),
+            ("day_of_year", 61., 365.),
+            ("hour", 12., 23.),
+            ("minute", 0., 59.),
+            ("second", 0., 59.),
+        ] {
+            let expression = interpret(&["t"], &format!("{function}(t)"));
+
+            assert_eq!(evaluate(&expression, &[t]), Some(expected));
+            assert_eq!(
+                evaluate(&expression, &[before_epoch]),
+                Some(expected_before_epoch)
+            );
+            assert_eq!(evaluate(&expression, &[None]), None);
+        }
+    }
+
+    #[test]
+    fn it_evaluates_text_bool_and_datetime_functions() {
+        let parameters = [
+            Parameter::Text("name".into()),
+            Parameter::Bool("valid".into()),
+            Parameter::DateTime("time".into()),
+        ];
+        let evaluate = |output_type: DataType, input: &str, inputs: [Value; 3]| {
+            let ast = ExpressionParser::new(&parameters, output_type)
+                .unwrap()
+                .parse("expression", input)
+                .unwrap();
+
+            InterpretedExpression::from_ast(&ast)
+                .unwrap()
+                .evaluate(inputs)
+        };
+        let inputs = || {
+            [
+                Value::Text(Some("Geo Engine".into())),
+                Value::Bool(Some(true)),
+                // 2024-03-01T12:00:00Z
+                Value::DateTime(Some(1_709_294_400_000.)),
+            ]
+        };
+
+        assert_eq!(
+            evaluate(
+                DataType::Text,
+                r#"concat(lower(substring(name, 4, 6)), "!")"#,
+                inputs()
+            ),
+            Value::Text(Some("engine!".into()))
+        );
+        assert_eq!(
+            evaluate(DataType::Bool, r#"matches(name, "^Geo\s")"#, inputs()),
+            Value::Bool(Some(true))
+        );
+        assert_eq!(
+            evaluate(
+                DataType::Number,
+                r#"if valid && name == "Geo Engine" { 1 } else { 0 }"#,
+                inputs()
+            ),
+            Value::Number(Some(1.))
+        );
+        assert_eq!(
+            evaluate(
+                DataType::Number,
+                "if not(valid) { 1 } else { 0 }",
+                [Value::Text(None), Value::Bool(None), Value::DateTime(None)]
+            ),
+            Value::Number(Some(0.))
+        );
+        assert_eq!(
+            evaluate(
+                DataType::Number,
+                "days_between(date(2024, 1, 1), add_hours(time, 12))",
+                inputs()
+            ),
+            Value::Number(Some(61.))
+        );
+        assert_eq!(
+            evaluate(DataType::Text, "to_text(add_days(time, -1))", inputs()),
+            Value::Text(Some("2024-02-29T12:00:00.000Z".into()))
+        );
+        assert_eq!(
+            evaluate(DataType::Number, r#"to_number(" 4.5") * 2"#, inputs()),
+            Value::Number(Some(9.))
+        );
+        assert_eq!(
+            evaluate(
+                DataType::Text,
+                "upper(name)",
+                [Value::Text(None), Value::Bool(None), Value::DateTime(None)]
+            ),
+            Value::Text(None)
+        );
+    }
+
+    #[test]
+    fn it_evaluates_branches() {
+        let expression = interpret(
+            &["A"],
+            "if A IS NODATA { 0 } else if A > 5 && A < 10 { 1 } else if A == 3 || false { NODATA } else { A }",
+        );
+
+        assert_eq!(evaluate(&expression, &[None]), Some(0.));
+        assert_eq!(evaluate(&expression, &[Some(7.)]), Some(1.));
+        assert_eq!(evaluate(&expression, &[Some(3.)]), None);
+        assert_eq!(evaluate(&expression, &[Some(42.)]), Some(42.));
+    }
+
+    #[test]
+    fn it_compares_nodata_like_the_compiled_expression() {
+        let expression = interpret(
+            &["A", "B"],
+            "if A < B { 1 } else if A == B { 0 } else { -1 }",
+        );
+
+        assert_eq!(evaluate(&expression, &[None, Some(-1.)]), Some(1.));
+        assert_eq!(evaluate(&expression, &[Some(-1.), None]), Some(-1.));
+        assert_eq!(evaluate(&expression, &[None, None]), Some(0.));
+    }
+
+    #[test]
+    fn it_evaluates_assignments() {
+        let expression = interpret(&["A"], "let x = A * 2; let y = x + 1; y * x");
+
+        assert_eq!(evaluate(&expression, &[Some(2.)]), Some(20.));
+        assert_eq!(evaluate(&expression, &[None]), None);
+    }
+
+    #[test]
+    fn it_treats_missing_inputs_as_nodata() {
+        let expression = interpret(&["A", "B"], "if B IS NODATA { A } else { B }");
+
+        assert_eq!(evaluate(&expression, &[Some(1.)]), Some(1.));
+    }
+
+    #[test]
+    fn it_evaluates_geometries() {
+        let ast = ExpressionParser::new(
+            &[Parameter::MultiPolygon("geom".into())],
+            DataType::MultiPoint,
+        )
+        .unwrap()
+        .parse("expression", "centroid(geom)")
+        .unwrap();
+        let expression = InterpretedExpression::from_ast(&ast).unwrap();
+
+        let polygon = MultiPolygon::from(polygon![
+            (x: 0., y: 0.),
+            (x: 5., y: 0.),
+            (x: 5., y: 6.),
+            (x: 0., y: 6.),
+            (x: 0., y: 0.),
+        ]);
+
+        assert_eq!(
+            MultiPoint::from_value(
+                expression.evaluate([MultiPolygon::into_value(Some(polygon.clone()))])
+            ),
+            Some(MultiPoint::from(point!(x: 2.5, y: 3.0)))
+        );
+        assert_eq!(
+            MultiPoint::from_value(expression.evaluate([MultiPolygon::into_value(None)])),
+            None
+        );
+
+        let ast =
+            ExpressionParser::new(&[Parameter::MultiPolygon("geom".into())], DataType::Number)
+                .unwrap()
+                .parse("expression", "area(geom) / 2")
+                .unwrap();
+        let expression = InterpretedExpression::from_ast(&ast).unwrap();
+
+        assert_eq!(
+            f64::from_value(expression.evaluate([MultiPolygon::into_value(Some(polygon))])),
+            Some(15.)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn it_evaluates_geometry_functions() {
+        let parameters = [
+            Parameter::MultiLineString("line".into()),
+            Parameter::MultiPoint("point".into()),
+        ];
+        let evaluate = |input: &str| {
+            let ast = ExpressionParser::new(&parameters, DataType::Number)
+                .unwrap()
+                .parse("expression", input)
+                .unwrap();
+            let expression = InterpretedExpression::from_ast(&ast).unwrap();
+
+            f64::from_value(expression.evaluate([
+                MultiLineString::into_value(Some(MultiLineString::from(line_string![
+                    (x: 0., y: 0.),
+                    (x: 3., y: 0.),
+                    (x: 3., y: 4.),
+                ]))),
+                MultiPoint::into_value(Some(MultiPoint::from(point!(x: 1., y: 2.)))),
+            ]))
+        };
+
+        assert_eq!(evaluate("length(line)"), Some(7.));
+        assert_eq!(evaluate("perimeter(line)"), Some(0.));
+        assert_eq!(evaluate("num_points(line)"), Some(3.));
+        assert_eq!(evaluate("area(convex_hull(line))"), Some(6.));
+        assert_eq!(evaluate("area(envelope(line))"), Some(12.));
+        assert_eq!(evaluate("perimeter(envelope(line))"), Some(14.));
+        assert_eq!(evaluate("x(point) + 10 * y(point)"), Some(21.));
+        assert_eq!(evaluate("distance(line, point)"), Some(2.));
+        assert_eq!(evaluate("num_points(simplify(line, 5))"), Some(2.));
+        assert_eq!(evaluate("num_points(buffer(point, 0))"), None);
+
+        let buffer_area = evaluate("area(buffer(point, 1))").unwrap();
+        assert!((buffer_area - std::f64::consts::PI).abs() < 0.05);
+    }
+
+    #[test]
+    fn it_buffers_nearly_collinear_segments() {
+        let ast = ExpressionParser::new(
+            &[Parameter::MultiLineString("line".into())],
+            DataType::Number,
+        )
+        .unwrap()
+        .parse("expression", "area(buffer(line, 1))")
+        .unwrap();
+        let expression = InterpretedExpression::from_ast(&ast).unwrap();
+
+        // a zig-zag line whose segments are collinear up to rounding errors
+        let coordinates: Vec<geo::Coord> = (0..1000)
+            .map(|i| {
+                geo::coord! {
+                    x: f64::from(i) * 0.1,
+                    y: if i % 2 == 0 { 0. } else { 1e-12 },
+                }
+            })
+            .collect();
+        let line = MultiLineString::from(geo::LineString::from(coordinates));
+
+        let area = f64::from_value(expression.evaluate([MultiLineString::into_value(Some(line))]))
+            .unwrap();
+
+        // a rectangle around the line and a circle, which is approximated by 32 segments, at its ends
+        let expected_area = 2. * 99.9 + 16. * (std::f64::consts::PI / 16.).sin();
+        assert!((area - expected_area).abs() < 1e-9);
+    }
+
+    #[test]
+    fn it_returns_nodata_if_the_buffer_fails() {
+        let ast =
+            ExpressionParser::new(&[Parameter::MultiPolygon("geom".into())], DataType::Number)
+                .unwrap()
+                .parse("expression", "area(buffer(geom, 1))")
+                .unwrap();
+        let expression = InterpretedExpression::from_ast(&ast).unwrap();
+
+        // a sliver whose edges are collinear up to rounding errors, which makes the union of `geo` panic
+        let polygon = MultiPolygon::from(polygon![
+            (x: 3., y: 0.),
+            (x: 2., y: 1.),
+            (x: 1e-15, y: 3.),
+        ]);
+
+        assert_eq!(
+            f64::from_value(expression.evaluate([MultiPolygon::into_value(Some(polygon))])),
+            None
+        );
+    }
+
+    #[test]
+    fn it_matches_the_compiled_expression() {
+        let dependencies = ExpressionDependencies::new().unwrap();
+
+        let ast = ExpressionParser::new(
+            &[Parameter::Number("A".into()), Parameter::Number("B".into())],
+            DataType::Number,
+        )
+        .unwrap()
+        .parse(
+            "expression",
+            "let c = mod(A, 3); if A IS NODATA { B } else if c >= B { pow(c, 2) } else { floor(A / B) }",
+        )
+        .unwrap();
+
+        let linked_expression = LinkedExpression::from_ast(&ast, &dependencies).unwrap();
+        let compiled = unsafe {
+            linked_expression
+                .function_2::<Option<f64>, Option<f64>>()
+                .unwrap()
+        };
+
+        let interpreted = InterpretedExpression::from_ast(&ast).unwrap();
+
+        let values = [None, Some(-4.), Some(0.), Some(1.5), Some(2.), Some(7.)];
+        for a in values {
+            for b in values {
+                assert_eq!(
+                    evaluate(&interpreted, &[a, b]),
+                    compiled(a, b),
+                    "A = {a:?}, B = {b:?}"
+                );
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ mod compiled;
 mod dependencies;
 pub mod error;
 mod functions;
+mod interpreter;
 mod parser;
 mod util;
 
@@ -10,6 +11,7 @@ pub use codegen::{DataType, ExpressionAst, Parameter};
 pub use compiled::LinkedExpression;
 pub use dependencies::ExpressionDependencies;
 pub use functions::FUNCTION_PREFIX;
+pub use interpreter::{InterpretedExpression, Value, ValueType};
 pub use parser::ExpressionParser;
 
 pub use geoengine_expression_deps::*;
@@ -36,6 +36,7 @@ pub struct ExpressionParser {
     parameters: Vec<Parameter>,
     out_type: DataType,
     functions: Rc<RefCell<BTreeSet<AstFunction>>>,
+    aliases: HashMap<Identifier, Identifier>,
 }
 
 static EXPRESSION_PARSER: OnceLock<PrattParser<Rule>> = OnceLock::new();
@@ -77,9 +78,40 @@ impl ExpressionParser {
             parameters: parameters.to_vec(),
             out_type,
             functions: Rc::new(RefCell::new(Default::default())),
+            aliases: HashMap::new(),
         })
     }
 
+    /// Adds an alternative name that refers to the parameter `parameter`.
+    pub fn add_alias(&mut self, alias: &str, parameter: &str) -> Result<()> {
+        let alias: Identifier = alias.into();
+        let parameter: Identifier = parameter.into();
+
+        if alias.as_ref().is_empty() {
+            return Err(ExpressionSemanticError::EmptyParameterName.into_definition_parser_error());
+        }
+
+        if self.aliases.contains_key(&alias)
+            || self.parameters.iter().any(|p| p.identifier() == &alias)
+        {
+            return Err(ExpressionSemanticError::DuplicateParameterName {
+                parameter: alias.to_string(),
+            }
+            .into_definition_parser_error());
+        }
+
+        if !self.parameters.iter().any(|p| p.identifier() == &parameter) {
+            return Err(ExpressionSemanticError::UnknownVariable {
+                variable: parameter.to_string(),
+            }
+            .into_definition_parser_error());
+        }
+
+        self.aliases.insert(alias, parameter);
+
+        Ok(())
+    }
+
     pub fn parse(self, name: &str, input: &str) -> Result<ExpressionAst> {
         if name.is_empty() {
             return Err(ExpressionSemanticError::EmptyExpressionName.into_definition_parser_error());
@@ -88,14 +120,90 @@ impl ExpressionParser {
         let pairs = _ExpressionParser::parse(Rule::main, input)
             .map_err(ExpressionParserError::from_syntactic_error)?;
 
-        let variables = self
+        let variables = self.variables();
+
+        let root = self.build_ast(pairs, &variables)?;
+
+        self.check_output_type(&root)?;
+
+        ExpressionAst::new(
+            name.to_string().into(),
+            self.parameters,
+            self.out_type,
+            self.functions.borrow_mut().clone(),
+            root,
+        )
+    }
+
+    /// Parses an expression that either has a single output or
+    /// a list of outputs, e.g., `[A + B, A - B]`.
+    /// Assignments in front of the list are shared by all outputs.
+    ///
+    /// Returns one expression per output.
+    /// If there are multiple outputs, they are named `{name}_{index}`.
+    pub fn parse_multi_output(self, name: &str, input: &str) -> Result<Vec<ExpressionAst>> {
+        if name.is_empty() {
+            return Err(ExpressionSemanticError::EmptyExpressionName.into_definition_parser_error());
+        }
+
+        let pairs = _ExpressionParser::parse(Rule::main_multi_output, input)
+            .map_err(ExpressionParserError::from_syntactic_error)?;
+
+        let variables = self.variables();
+
+        let outputs = if let Some(pair) = pairs
+            .clone()
+            .find(|pair| pair.as_rule() == Rule::assignments_and_outputs)
+        {
+            self.resolve_assignments_and_outputs(pair, &variables)?
+        } else {
+            let root = self.build_ast(pairs, &variables)?;
+            vec![(root, self.functions.borrow().clone())]
+        };
+
+        let number_of_outputs = outputs.len();
+
+        outputs
+            .into_iter()
+            .enumerate()
+            .map(|(i, (root, functions))| {
+                self.check_output_type(&root)?;
+
+                let name = if number_of_outputs == 1 {
+                    name.to_string()
+                } else {
+                    format!("{name}_{i}")
+                };
+
+                ExpressionAst::new(
+                    name.into(),
+                    self.parameters.clone(),
+                    self.out_type,
+                    functions,
+                    root,
+                )
+            })
+            .collect()
+    }
+
+    fn variables(&self) -> HashMap<Identifier, DataType> {
+        let mut variables: HashMap<Identifier, DataType> = self
             .parameters
             .iter()
             .map(|param| (param.identifier().clone(), param.data_type()))
             .collect();
 
-        let root = self.build_ast(pairs, &variables)?;
+        // aliases are variables as well, s.t. assignments cannot shadow them
+        for (alias, parameter) in &self.aliases {
+            if let Some(&data_type) = variables.get(parameter) {
+                variables.insert(alias.clone(), data_type);
+            }
+        }
+
+        variables
+    }
 
+    fn check_output_type(&self, root: &AstNode) -> Result<()> {
         if root.data_type() != self.out_type {
             return Err(ExpressionSemanticError::WrongOutputType {
                 expected: self.out_type,
@@ -104,13 +212,27 @@ impl ExpressionParser {
             .into_definition_parser_error());
         }
 
-        ExpressionAst::new(
-            name.to_string().into(),
-            self.parameters,
-            self.out_type,
-            self.functions.borrow_mut().clone(),
-            root,
-        )
+        Ok(())
+    }
+
+    /// Resolves a variable name to its node.
+    /// Aliases are replaced by the parameter they refer to.
+    fn resolve_variable(
+        &self,
+        identifier: Identifier,
+        span: pest::Span<'_>,
+        variables: &HashMap<Identifier, DataType>,
+    ) -> Result<AstNode> {
+        let data_type = *variables
+            .get(&identifier)
+            .context(error::UnknownVariable {
+                variable: identifier.to_string(),
+            })
+            .map_err(|e| e.into_parser_error(span))?;
+
+        let name = self.aliases.get(&identifier).cloned().unwrap_or(identifier);
+
+        Ok(AstNode::Variable { name, data_type })
     }
 
     fn build_ast(
@@ -142,19 +264,12 @@ impl ExpressionParser {
                     })
                     .map_err(|e| e.into_parser_error(span))?,
             )),
-            Rule::identifier => {
-                let identifier = pair.as_str().into();
-                let data_type = *variables
-                    .get(&identifier)
-                    .context(error::UnknownVariable {
-                        variable: identifier.to_string(),
-                    })
-                    .map_err(|e| e.into_parser_error(span))?;
-                Ok(AstNode::Variable {
-                    name: identifier,
-                    data_type,
-                })
-            }
+            Rule::text => Ok(AstNode::TextConstant(unescape_text(
+                pair.into_inner().as_str(),
+            ))),
+            Rule::boolean_true => Ok(AstNode::BoolConstant(true)),
+            Rule::boolean_false => Ok(AstNode::BoolConstant(false)),
+            Rule::identifier => self.resolve_variable(pair.as_str().into(), span, variables),
             Rule::nodata => Ok(AstNode::NoData),
             Rule::function => self.resolve_function(pair.into_inner(), span, variables),
             Rule::branch => self.resolve_branch(pair, span, variables),
@@ -165,43 +280,7 @@ impl ExpressionParser {
 
                 for pair in pair.into_inner() {
                     if matches!(pair.as_rule(), Rule::assignment) {
-                        let mut pairs = pair.into_inner();
-
-                        let first_pair = pairs.next().ok_or(
-                            ExpressionSemanticError::AssignmentNeedsTwoParts
-                                .into_parser_error(span),
-                        )?;
-                        let second_pair = pairs.next().ok_or(
-                            ExpressionSemanticError::AssignmentNeedsTwoParts
-                                .into_parser_error(span),
-                        )?;
-
-                        let identifier: Identifier = first_pair.as_str().into();
-
-                        let expression = self.build_ast(second_pair.into_inner(), &variables)?;
-                        let expression_data_type = expression.data_type();
-
-                        assignments.push(Assignment {
-                            identifier: identifier.clone(),
-                            expression,
-                        });
-
-                        // having an assignment allows more variables,
-                        // but only in the next assignments or expression
-                        match variables.entry(identifier) {
-                            hash_map::Entry::Vacant(entry) => {
-                                entry.insert(expression_data_type);
-                            }
-                            hash_map::Entry::Occupied(entry) => {
-                                // we do not allow shadowing for now
-
-                                let identifier: &Identifier = entry.key();
-                                return Err(ExpressionSemanticError::VariableShadowing {
-                                    variable: identifier.to_string(),
-                                }
-                                .into_parser_error(span));
-                            }
-                        };
+                        self.resolve_assignment(pair, span, &mut variables, &mut assignments)?;
                     } else {
                         let expression = self.build_ast(pair.into_inner(), &variables)?;
 
@@ -221,6 +300,94 @@ impl ExpressionParser {
         }
     }
 
+    fn resolve_assignment(
+        &self,
+        pair: Pair<Rule>,
+        span: pest::Span<'_>,
+        variables: &mut HashMap<Identifier, DataType>,
+        assignments: &mut Vec<Assignment>,
+    ) -> Result<()> {
+        let mut pairs = pair.into_inner();
+
+        let first_pair = pairs
+            .next()
+            .ok_or(ExpressionSemanticError::AssignmentNeedsTwoParts.into_parser_error(span))?;
+        let second_pair = pairs
+            .next()
+            .ok_or(ExpressionSemanticError::AssignmentNeedsTwoParts.into_parser_error(span))?;
+
+        let identifier: Identifier = first_pair.as_str().into();
+
+        let expression = self.build_ast(second_pair.into_inner(), variables)?;
+        let expression_data_type = expression.data_type();
+
+        assignments.push(Assignment {
+            identifier: identifier.clone(),
+            expression,
+        });
+
+        // having an assignment allows more variables,
+        // but only in the next assignments or expression
+        match variables.entry(identifier) {
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(expression_data_type);
+            }
+            hash_map::Entry::Occupied(entry) => {
+                // we do not allow shadowing for now
+
+                let identifier: &Identifier = entry.key();
+                return Err(ExpressionSemanticError::VariableShadowing {
+                    variable: identifier.to_string(),
+                }
+                .into_parser_error(span));
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Resolves the shared assignments and the list of outputs.
+    /// Returns one root node per output together with the functions it uses.
+    fn resolve_assignments_and_outputs(
+        &self,
+        pair: Pair<Rule>,
+        variables: &HashMap<Identifier, DataType>,
+    ) -> Result<Vec<(AstNode, BTreeSet<AstFunction>)>> {
+        let span = pair.as_span();
+
+        let mut assignments: Vec<Assignment> = vec![];
+
+        let mut variables = variables.clone();
+
+        for pair in pair.into_inner() {
+            if matches!(pair.as_rule(), Rule::assignment) {
+                self.resolve_assignment(pair, span, &mut variables, &mut assignments)?;
+                continue;
+            }
+
+            // only keep the functions that are used by the assignments and the respective output
+            let assignment_functions = self.functions.borrow().clone();
+
+            return pair
+                .into_inner()
+                .map(|output| {
+                    *self.functions.borrow_mut() = assignment_functions.clone();
+
+                    let expression = self.build_ast(output.into_inner(), &variables)?;
+
+                    let root = AstNode::AssignmentsAndExpression {
+                        assignments: assignments.clone(),
+                        expression: Box::new(expression),
+                    };
+
+                    Ok((root, self.functions.borrow().clone()))
+                })
+                .collect();
+        }
+
+        Err(ExpressionSemanticError::DoesNotEndWithExpression.into_parser_error(span))
+    }
+
     fn resolve_branch(
         &self,
         pair: Pair<Rule>,
@@ -244,25 +411,13 @@ impl ExpressionParser {
                     .ok_or(ExpressionSemanticError::MissingBranch.into_parser_error(span))?;
                 let body = self.build_ast(next_pair.into_inner(), variables)?;
 
-                if let Some(data_type) = data_type {
-                    if data_type != body.data_type() {
-                        return Err(ExpressionSemanticError::AllBranchesMustOutputSameType
-                            .into_parser_error(span));
-                    }
-                } else {
-                    data_type = Some(body.data_type());
-                }
+                check_branch_data_type(&mut data_type, &body, span)?;
 
                 condition_branches.push(Branch { condition, body });
             } else {
                 let expression = self.build_ast(pair.into_inner(), variables)?;
 
-                if let Some(data_type) = data_type {
-                    if data_type != expression.data_type() {
-                        return Err(ExpressionSemanticError::AllBranchesMustOutputSameType
-                            .into_parser_error(span));
-                    }
-                }
+                check_branch_data_type(&mut data_type, &expression, span)?;
 
                 return Ok(AstNode::Branch {
                     condition_branches,
@@ -396,23 +551,15 @@ impl ExpressionParser {
                     .as_str()
                     .into();
 
-                let data_type = *variables
-                    .get(&identifier)
-                    .context(error::UnknownVariable {
-                        variable: identifier.to_string(),
-                    })
-                    .map_err(|e| e.into_parser_error(span))?;
+                let left = self.resolve_variable(identifier, span, variables)?;
 
-                if data_type != DataType::Number {
-                    return Err(ExpressionSemanticError::ComparisonsMustBeUsedWithNumbers
-                        .into_parser_error(span));
+                if left.data_type().is_geometry() {
+                    return Err(
+                        ExpressionSemanticError::ComparisonsCannotBeUsedWithGeometries
+                            .into_parser_error(span),
+                    );
                 }
 
-                let left = AstNode::Variable {
-                    name: identifier,
-                    data_type,
-                };
-
                 Ok(BooleanExpression::Comparison {
                     left: Box::new(left),
                     op: BooleanComparator::Equal,
@@ -451,12 +598,8 @@ impl ExpressionParser {
                 };
                 let right_expression = self.build_ast(third_pair.into_inner(), variables)?;
 
-                if left_expression.data_type() != DataType::Number
-                    || right_expression.data_type() != DataType::Number
-                {
-                    return Err(ExpressionSemanticError::ComparisonsMustBeUsedWithNumbers
-                        .into_parser_error(span));
-                }
+                check_comparison_data_types(&left_expression, &right_expression)
+                    .map_err(|e| e.into_parser_error(span))?;
 
                 Ok(BooleanExpression::Comparison {
                     left: Box::new(left_expression),
@@ -465,6 +608,18 @@ impl ExpressionParser {
                 })
             }
             Rule::boolean_expression => self.build_boolean_expression(pair.into_inner(), variables),
+            Rule::boolean_value => {
+                let value = self.build_ast(pair.into_inner(), variables)?;
+
+                if value.data_type() != DataType::Bool {
+                    return Err(ExpressionSemanticError::ConditionMustBeBool {
+                        actual: value.data_type(),
+                    }
+                    .into_parser_error(span));
+                }
+
+                Ok(BooleanExpression::Value(Box::new(value)))
+            }
             _ => Err(ExpressionSemanticError::UnexpectedBooleanRule {
                 rule: format!("{:?}", pair.as_rule()),
             }
@@ -498,6 +653,74 @@ impl ExpressionParser {
     }
 }
 
+/// Checks that a branch outputs the same type as the previous branches.
+/// No data fits any type.
+fn check_branch_data_type(
+    data_type: &mut Option<DataType>,
+    body: &AstNode,
+    span: pest::Span<'_>,
+) -> Result<()> {
+    if matches!(body, AstNode::NoData) {
+        return Ok(());
+    }
+
+    match data_type {
+        Some(data_type) if *data_type != body.data_type() => {
+            Err(ExpressionSemanticError::AllBranchesMustOutputSameType.into_parser_error(span))
+        }
+        Some(_) => Ok(()),
+        None => {
+            *data_type = Some(body.data_type());
+            Ok(())
+        }
+    }
+}
+
+/// Values of the same type can be compared, except for geometries.
+/// No data can be compared to any type.
+fn check_comparison_data_types(
+    left: &AstNode,
+    right: &AstNode,
+) -> Result<(), ExpressionSemanticError> {
+    if left.data_type().is_geometry() || right.data_type().is_geometry() {
+        return Err(ExpressionSemanticError::ComparisonsCannotBeUsedWithGeometries);
+    }
+
+    if left.data_type() != right.data_type()
+        && !matches!(left, AstNode::NoData)
+        && !matches!(right, AstNode::NoData)
+    {
+        return Err(ExpressionSemanticError::ComparisonsMustBeUsedWithSameTypes);
+    }
+
+    Ok(())
+}
+
+/// Replaces the escape sequences `\"` and `\\` in a text.
+/// Other backslashes are kept, e.g., for regular expressions like `\d+`.
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some(escaped @ ('"' | '\\')) => unescaped.push(escaped),
+            Some(other) => {
+                unescaped.push(c);
+                unescaped.push(other);
+            }
+            None => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1199,8 +1422,8 @@ mod tests {
             )
             .unwrap_err()
             .to_string(),
-            " --> 1:4\n  |\n1 | if B IS NODATA { A } else { A }\n  |    ^---------^\n  |\n  = Comparisons can only be used with numbers",
-            "cannot use non-numeric comparison"
+            " --> 1:4\n  |\n1 | if B IS NODATA { A } else { A }\n  |    ^---------^\n  |\n  = Comparisons cannot be used with geometries",
+            "cannot compare geometries"
         );
 
         assert_eq!(
@@ -1267,4 +1490,190 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn it_works_with_texts_and_bools() {
+        assert_eq_pretty!(
+            parse2(
+                "expression",
+                &[
+                    Parameter::Text("name".into()),
+                    Parameter::Bool("valid".into())
+                ],
+                DataType::Text,
+                r#"if valid && name != "a \"b\"" { upper(name) } else { NODATA }"#,
+            ),
+            quote! {
+                #Prelude
+
+                #[inline]
+                fn expression_fn_upper__t(text: Option<String>) -> Option<String> {
+                    let text = text?;
+                    Some(text::upper(&text))
+                }
+
+                #[no_mangle]
+                pub extern "Rust" fn expression(
+                    name: Option<String>,
+                    valid: Option<bool>
+                ) -> Option<String> {
+                    if (((valid).unwrap_or(false))
+                        && (((name.clone()) != (Some(String::from("a \"b\""))))))
+                    {
+                        expression_fn_upper__t(name.clone())
+                    } else {
+                        None
+                    }
+                }
+            }
+            .to_string()
+        );
+
+        assert_eq!(
+            try_parse(
+                "expression",
+                &[Parameter::Text("name".into())],
+                DataType::Number,
+                "if name { 1 } else { 2 }",
+            )
+            .unwrap_err()
+            .to_string(),
+            " --> 1:4\n  |\n1 | if name { 1 } else { 2 }\n  |    ^---^\n  |\n  = A condition must be a comparison or output `bool`, but it outputs `text`",
+            "cannot use text as condition"
+        );
+
+        assert_eq!(
+            try_parse(
+                "expression",
+                &[Parameter::Text("name".into())],
+                DataType::Number,
+                "if name == 1 { 1 } else { 2 }",
+            )
+            .unwrap_err()
+            .to_string(),
+            " --> 1:4\n  |\n1 | if name == 1 { 1 } else { 2 }\n  |    ^--------^\n  |\n  = Comparisons can only be used with values of the same type",
+            "cannot compare text and number"
+        );
+    }
+
+    #[test]
+    fn it_parses_multiple_outputs() {
+        let parser = ExpressionParser::new(
+            &[Parameter::Number("A".into()), Parameter::Number("B".into())],
+            DataType::Number,
+        )
+        .unwrap();
+
+        let asts = parser
+            .parse_multi_output(
+                "expression",
+                "let sum = A + B;
+                [sum, A * B]",
+            )
+            .unwrap();
+
+        assert_eq!(asts.len(), 2);
+        assert_eq!(asts[0].name(), "expression_0");
+        assert_eq!(asts[0].output_variable(), Some("sum"));
+        assert_eq!(asts[1].name(), "expression_1");
+        assert_eq!(asts[1].output_variable(), None);
+
+        assert_eq_pretty!(
+            asts[0].to_token_stream().to_string(),
+            quote! {
+                #Prelude
+
+                #ADD_FN
+
+                #[no_mangle]
+                pub extern "Rust" fn expression_0(A: Option<f64>, B: Option<f64>) -> Option<f64> {
+                    let sum = expression_fn_add__n_n(A, B);
+                    sum
+                }
+            }
+            .to_string()
+        );
+
+        assert_eq_pretty!(
+            asts[1].to_token_stream().to_string(),
+            quote! {
+                #Prelude
+
+                #ADD_FN
+                #MUL_FN
+
+                #[no_mangle]
+                pub extern "Rust" fn expression_1(A: Option<f64>, B: Option<f64>) -> Option<f64> {
+                    let sum = expression_fn_add__n_n(A, B);
+                    expression_fn_mul__n_n(A, B)
+                }
+            }
+            .to_string()
+        );
+
+        let parser =
+            ExpressionParser::new(&[Parameter::Number("A".into())], DataType::Number).unwrap();
+        let asts = parser.parse_multi_output("single", "A + 1").unwrap();
+
+        assert_eq!(asts.len(), 1);
+        assert_eq!(asts[0].name(), "single");
+    }
+
+    #[test]
+    fn it_resolves_aliases() {
+        let mut parser = ExpressionParser::new(
+            &[Parameter::Number("A".into()), Parameter::Number("B".into())],
+            DataType::Number,
+        )
+        .unwrap();
+        parser.add_alias("nir", "A").unwrap();
+        parser.add_alias("red", "B").unwrap();
+
+        assert_eq_pretty!(
+            parser
+                .parse(
+                    "ndvi",
+                    "if red IS NODATA { 0 } else { (nir - red) / (nir + red) }"
+                )
+                .unwrap()
+                .into_token_stream()
+                .to_string(),
+            quote! {
+                #Prelude
+
+                #ADD_FN
+                #DIV_FN
+                #SUB_FN
+
+                #[no_mangle]
+                pub extern "Rust" fn ndvi(A: Option<f64>, B: Option<f64>) -> Option<f64> {
+                    if ((B) == (None)) {
+                        Some(0f64)
+                    } else {
+                        expression_fn_div__n_n(
+                            expression_fn_sub__n_n(A, B),
+                            expression_fn_add__n_n(A, B),
+                        )
+                    }
+                }
+            }
+            .to_string()
+        );
+
+        let mut parser =
+            ExpressionParser::new(&[Parameter::Number("A".into())], DataType::Number).unwrap();
+        parser.add_alias("nir", "A").unwrap();
+
+        assert!(parser.add_alias("nir", "A").is_err(), "duplicate alias");
+        assert!(
+            parser.add_alias("A", "A").is_err(),
+            "alias shadows parameter"
+        );
+        assert!(parser.add_alias("red", "B").is_err(), "unknown parameter");
+
+        assert!(
+            parser.parse("expression", "let nir = 1; nir").is_err(),
+            "assignments cannot shadow aliases"
+        );
+    }
 }
@@ -34,6 +34,7 @@ fn expression_on_sources(
             expression: "(A - B) / (A + B)".to_string(),
             output_type: RasterDataType::F64,
             output_band: None,
+            output_bands: vec![],
             map_no_data: false,
         },
         sources: SingleRasterSource {
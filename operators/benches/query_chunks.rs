@@ -123,6 +123,7 @@ fn setup_benchmarks(exe_ctx: &mut StatisticsWrappingMockExecutionContext) -> Vec
                     names: ColumnNames::Names(vec!["ndvi".to_string()]),
                     feature_aggregation: FeatureAggregationMethod::Mean,
                     feature_aggregation_ignore_no_data: true,
+                    feature_aggregation_area_weighted: false,
                     temporal_aggregation: TemporalAggregationMethod::Mean,
                     temporal_aggregation_ignore_no_data: true,
                 },
@@ -27,8 +27,8 @@ use geoengine_operators::engine::{
 };
 use geoengine_operators::mock::{MockRasterSource, MockRasterSourceParams};
 use geoengine_operators::processing::{
-    Expression, ExpressionParams, RasterStacker, RasterStackerParams, Reprojection,
-    ReprojectionParams,
+    Expression, ExpressionParams, InterpolationMethod, RasterStacker, RasterStackerParams,
+    Reprojection, ReprojectionParams,
 };
 use geoengine_operators::source::GdalSource;
 use geoengine_operators::{
@@ -407,6 +407,7 @@ fn bench_mock_source_operator_with_expression(bench_collector: &mut BenchmarkCol
                 expression: "A+B".to_string(),
                 output_type: RasterDataType::U8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -503,6 +504,7 @@ fn bench_mock_source_operator_with_identity_reprojection(bench_collector: &mut B
         Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference: SpatialReference::epsg_4326(),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource::from(mock_raster_operator.boxed()),
         }
@@ -586,6 +588,7 @@ fn bench_mock_source_operator_with_4326_to_3857_reprojection(
         Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference: SpatialReference::epsg_4326(),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource::from(mock_raster_operator.boxed()),
         }
@@ -726,6 +729,7 @@ fn bench_gdal_source_operator_with_expression_tile_size(bench_collector: &mut Be
             expression: "A+B".to_string(),
             output_type: RasterDataType::U8,
             output_band: None,
+            output_bands: vec![],
             map_no_data: false,
         },
         sources: SingleRasterSource {
@@ -796,6 +800,7 @@ fn bench_gdal_source_operator_with_identity_reprojection(bench_collector: &mut B
     let projection_operator = Reprojection {
         params: ReprojectionParams {
             target_spatial_reference: SpatialReference::epsg_4326(),
+            interpolation: InterpolationMethod::NearestNeighbor,
         },
         sources: SingleRasterOrVectorSource::from(gdal_operator.boxed()),
     }
@@ -863,6 +868,7 @@ fn bench_gdal_source_operator_with_4326_to_3857_reprojection(
                 geoengine_datatypes::spatial_reference::SpatialReferenceAuthority::Epsg,
                 3857,
             ),
+            interpolation: InterpolationMethod::NearestNeighbor,
         },
         sources: SingleRasterOrVectorSource::from(gdal_operator.boxed()),
     }
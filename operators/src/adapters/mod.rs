@@ -9,8 +9,9 @@ mod sparse_tiles_fill_adapter;
 pub use feature_collection_merger::FeatureCollectionChunkMerger;
 pub use raster_stacker::{RasterStackerAdapter, RasterStackerSource};
 pub use raster_subquery::{
-    fold_by_coordinate_lookup_future, FoldTileAccu, FoldTileAccuMut, RasterSubQueryAdapter,
-    SubQueryTileAggregator, TileReprojectionSubQuery,
+    fold_by_coordinate_lookup_future, fold_by_interpolation_future, FoldTileAccu, FoldTileAccuMut,
+    RasterSubQueryAdapter, SubQueryTileAggregator, TileInterpolationReprojectionSubQuery,
+    TileReprojectionSubQuery,
 };
 pub use raster_time::{QueryWrapper, Queryable, RasterArrayTimeAdapter, RasterTimeAdapter};
 pub use simple_raster_stacker::{
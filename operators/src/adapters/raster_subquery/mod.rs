@@ -6,5 +6,6 @@ pub use raster_subquery_adapter::{
 };
 
 pub use raster_subquery_reprojection::{
-    fold_by_coordinate_lookup_future, TileReprojectionSubQuery,
+    fold_by_coordinate_lookup_future, fold_by_interpolation_future,
+    TileInterpolationReprojectionSubQuery, TileReprojectionSubQuery,
 };
@@ -9,10 +9,11 @@ use futures::{Future, FutureExt, TryFuture, TryFutureExt};
 use geoengine_datatypes::operations::reproject::Reproject;
 use geoengine_datatypes::primitives::CacheHint;
 use geoengine_datatypes::primitives::{
-    RasterQueryRectangle, SpatialPartition2D, SpatialPartitioned,
+    AxisAlignedRectangle, RasterQueryRectangle, SpatialPartition2D, SpatialPartitioned,
 };
 use geoengine_datatypes::raster::{
-    Grid2D, GridIndexAccess, GridSize, UpdateIndexedElementsParallel,
+    Blit, EmptyGrid2D, FromIndexFnParallel, GeoTransform, Grid2D, GridIndexAccess, GridOrEmpty,
+    GridSize, InterpolationAlgorithm, TilingSpecification, UpdateIndexedElementsParallel,
 };
 use geoengine_datatypes::{
     operations::reproject::{CoordinateProjection, CoordinateProjector},
@@ -86,31 +87,21 @@ where
         start_time: TimeInstance,
         band_idx: u32,
     ) -> Result<Option<RasterQueryRectangle>> {
-        // this is the spatial partition we are interested in
-        let valid_spatial_bounds = self
-            .valid_bounds_out
-            .intersection(&tile_info.spatial_partition())
-            .and_then(|vo| vo.intersection(&query_rect.spatial_partition()));
-        if let Some(bounds) = valid_spatial_bounds {
-            let proj = CoordinateProjector::from_known_srs(self.out_srs, self.in_srs)?;
-            let projected_bounds = bounds.reproject(&proj);
-
-            match projected_bounds {
-                Ok(pb) => Ok(Some(RasterQueryRectangle {
-                    spatial_bounds: pb,
-                    time_interval: TimeInterval::new_instant(start_time)?,
-                    spatial_resolution: self.in_spatial_res,
-                    attributes: band_idx.into(),
-                })),
-                // In some strange cases the reprojection can return an empty box.
-                // We ignore it since it contains no pixels.
-                Err(geoengine_datatypes::error::Error::OutputBboxEmpty { bbox: _ }) => Ok(None),
-                Err(e) => Err(e.into()),
-            }
-        } else {
-            // output query rectangle is not valid in source projection => produce empty tile
-            Ok(None)
-        }
+        let projected_bounds = projected_tile_bounds(
+            tile_info,
+            &query_rect,
+            self.valid_bounds_out,
+            self.out_srs,
+            self.in_srs,
+        )?;
+        let time_interval = TimeInterval::new_instant(start_time)?;
+
+        Ok(projected_bounds.map(|pb| RasterQueryRectangle {
+            spatial_bounds: pb,
+            time_interval,
+            spatial_resolution: self.in_spatial_res,
+            attributes: band_idx.into(),
+        }))
     }
 
     fn fold_method(&self) -> Self::FoldMethod {
@@ -118,6 +109,35 @@ where
     }
 }
 
+/// The bounds of the output tile that are valid and queried, projected into the input spatial reference
+fn projected_tile_bounds(
+    tile_info: TileInformation,
+    query_rect: &RasterQueryRectangle,
+    valid_bounds_out: SpatialPartition2D,
+    out_srs: SpatialReference,
+    in_srs: SpatialReference,
+) -> Result<Option<SpatialPartition2D>> {
+    // this is the spatial partition we are interested in
+    let valid_spatial_bounds = valid_bounds_out
+        .intersection(&tile_info.spatial_partition())
+        .and_then(|vo| vo.intersection(&query_rect.spatial_partition()));
+    if let Some(bounds) = valid_spatial_bounds {
+        let proj = CoordinateProjector::from_known_srs(out_srs, in_srs)?;
+        let projected_bounds = bounds.reproject(&proj);
+
+        match projected_bounds {
+            Ok(pb) => Ok(Some(pb)),
+            // In some strange cases the reprojection can return an empty box.
+            // We ignore it since it contains no pixels.
+            Err(geoengine_datatypes::error::Error::OutputBboxEmpty { bbox: _ }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    } else {
+        // output query rectangle is not valid in source projection => produce empty tile
+        Ok(None)
+    }
+}
+
 fn build_accu<T: Pixel>(
     query_rect: &RasterQueryRectangle,
     pool: Arc<ThreadPool>,
@@ -356,6 +376,282 @@ impl<T: Pixel> FoldTileAccuMut for TileWithProjectionCoordinates<T> {
     }
 }
 
+/// A sub query that reprojects tiles by sampling the input with an `InterpolationAlgorithm`.
+/// In contrast to the `TileReprojectionSubQuery`, all input tiles are collected before sampling
+/// since the algorithms require neighbor pixels that may belong to different input tiles.
+#[derive(Debug)]
+pub struct TileInterpolationReprojectionSubQuery<T, I, F> {
+    pub in_srs: SpatialReference,
+    pub out_srs: SpatialReference,
+    pub fold_fn: F,
+    pub in_spatial_res: SpatialResolution,
+    pub valid_bounds_in: SpatialPartition2D,
+    pub valid_bounds_out: SpatialPartition2D,
+    /// the size of an output pixel in input pixels (`[y, x]`)
+    pub footprint: [f64; 2],
+    pub tiling_specification: TilingSpecification,
+    pub _phantom_data: PhantomData<(T, I)>,
+}
+
+impl<'a, T, I, FoldM, FoldF> SubQueryTileAggregator<'a, T>
+    for TileInterpolationReprojectionSubQuery<T, I, FoldM>
+where
+    T: Pixel,
+    I: InterpolationAlgorithm<T>,
+    FoldM: Send
+        + Sync
+        + 'static
+        + Clone
+        + Fn(TileWithProjectionCoordinatesAndInput<T, I>, RasterTile2D<T>) -> FoldF,
+    FoldF: Send + TryFuture<Ok = TileWithProjectionCoordinatesAndInput<T, I>, Error = error::Error>,
+{
+    type FoldFuture = FoldF;
+
+    type FoldMethod = FoldM;
+
+    type TileAccu = TileWithProjectionCoordinatesAndInput<T, I>;
+    type TileAccuFuture = BoxFuture<'a, Result<Self::TileAccu>>;
+
+    fn new_fold_accu(
+        &self,
+        tile_info: TileInformation,
+        query_rect: RasterQueryRectangle,
+        pool: &Arc<ThreadPool>,
+    ) -> Self::TileAccuFuture {
+        build_interpolation_accu(
+            query_rect,
+            pool.clone(),
+            tile_info,
+            self.valid_bounds_out,
+            self.out_srs,
+            self.in_srs,
+            self.footprint,
+            self.tiling_specification,
+        )
+        .boxed()
+    }
+
+    fn tile_query_rectangle(
+        &self,
+        tile_info: TileInformation,
+        query_rect: RasterQueryRectangle,
+        start_time: TimeInstance,
+        band_idx: u32,
+    ) -> Result<Option<RasterQueryRectangle>> {
+        let Some(projected_bounds) = projected_tile_bounds(
+            tile_info,
+            &query_rect,
+            self.valid_bounds_out,
+            self.out_srs,
+            self.in_srs,
+        )?
+        else {
+            return Ok(None);
+        };
+
+        // enlarge the spatial bounds in order to have the neighbor pixels for the interpolation
+        let (margin_before, margin_after) = I::INPUT_MARGIN;
+        let pixel: Coordinate2D = (self.in_spatial_res.x, -self.in_spatial_res.y).into();
+        let spatial_bounds = SpatialPartition2D::new(
+            projected_bounds.upper_left() - pixel * margin_before as f64,
+            projected_bounds.lower_right() + pixel * margin_after as f64,
+        )?;
+
+        Ok(Some(RasterQueryRectangle {
+            spatial_bounds,
+            time_interval: TimeInterval::new_instant(start_time)?,
+            spatial_resolution: self.in_spatial_res,
+            attributes: band_idx.into(),
+        }))
+    }
+
+    fn fold_method(&self) -> Self::FoldMethod {
+        self.fold_fn.clone()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_interpolation_accu<T: Pixel, I: InterpolationAlgorithm<T>>(
+    query_rect: RasterQueryRectangle,
+    pool: Arc<ThreadPool>,
+    tile_info: TileInformation,
+    valid_bounds_out: SpatialPartition2D,
+    out_srs: SpatialReference,
+    in_srs: SpatialReference,
+    footprint: [f64; 2],
+    tiling_specification: TilingSpecification,
+) -> impl Future<Output = Result<TileWithProjectionCoordinatesAndInput<T, I>>> {
+    crate::util::spawn_blocking(move || {
+        let projected_coords = projected_coordinate_grid_parallel(
+            &pool,
+            tile_info,
+            out_srs,
+            in_srs,
+            &valid_bounds_out,
+        )?;
+
+        // create a single input tile that fits all the input tiles of the query
+        let spatial_bounds = query_rect.spatial_bounds;
+        let spatial_resolution = query_rect.spatial_resolution;
+        let tiling = tiling_specification.strategy(spatial_resolution.x, -spatial_resolution.y);
+
+        let origin_coordinate = tiling
+            .tile_information_iterator(spatial_bounds)
+            .next()
+            .expect("a query contains at least one tile")
+            .spatial_partition()
+            .upper_left();
+
+        let geo_transform = GeoTransform::new(
+            origin_coordinate,
+            spatial_resolution.x,
+            -spatial_resolution.y,
+        );
+
+        let bbox = tiling.tile_grid_box(spatial_bounds);
+
+        let shape = [
+            bbox.axis_size_y() * tiling.tile_size_in_pixels.axis_size_y(),
+            bbox.axis_size_x() * tiling.tile_size_in_pixels.axis_size_x(),
+        ];
+
+        let input_tile = RasterTile2D::new(
+            query_rect.time_interval,
+            [0, 0].into(),
+            0,
+            geo_transform,
+            EmptyGrid2D::new(shape.into()).into(),
+            CacheHint::max_duration(),
+        );
+
+        Ok(TileWithProjectionCoordinatesAndInput {
+            accu_tile: RasterTile2D::new_with_tile_info(
+                query_rect.time_interval,
+                tile_info,
+                0,
+                EmptyGrid::new(tile_info.tile_size_in_pixels).into(),
+                CacheHint::max_duration(),
+            ),
+            input_tile,
+            coords: projected_coords,
+            footprint,
+            pool,
+            _phantom_data: PhantomData,
+        })
+    })
+    .map_err(From::from)
+    .and_then(|x| async { x }) // flatten Ok(Ok())
+}
+
+pub fn fold_by_interpolation_future<T, I>(
+    accu: TileWithProjectionCoordinatesAndInput<T, I>,
+    tile: RasterTile2D<T>,
+) -> impl TryFuture<Ok = TileWithProjectionCoordinatesAndInput<T, I>, Error = error::Error>
+where
+    T: Pixel,
+    I: InterpolationAlgorithm<T>,
+{
+    crate::util::spawn_blocking(|| fold_by_interpolation_impl(accu, tile)).then(|x| async move {
+        match x {
+            Ok(r) => r,
+            Err(e) => Err(e.into()),
+        }
+    })
+}
+
+pub fn fold_by_interpolation_impl<T, I>(
+    mut accu: TileWithProjectionCoordinatesAndInput<T, I>,
+    tile: RasterTile2D<T>,
+) -> Result<TileWithProjectionCoordinatesAndInput<T, I>>
+where
+    T: Pixel,
+    I: InterpolationAlgorithm<T>,
+{
+    accu.accu_tile.time = accu.accu_tile.time.union(&tile.time)?;
+    accu.accu_tile.cache_hint.merge_with(&tile.cache_hint);
+
+    if tile.grid_array.is_empty() {
+        return Ok(accu);
+    }
+
+    // copy all input tiles into the accu to have all data for the interpolation
+    let mut input_tile = accu.input_tile.into_materialized_tile();
+    input_tile.blit(tile)?;
+    accu.input_tile = input_tile.into();
+
+    Ok(accu)
+}
+
+#[derive(Debug, Clone)]
+pub struct TileWithProjectionCoordinatesAndInput<T: Pixel, I: InterpolationAlgorithm<T>> {
+    accu_tile: RasterTile2D<T>,
+    input_tile: RasterTile2D<T>,
+    coords: Grid2D<Option<Coordinate2D>>,
+    footprint: [f64; 2],
+    pool: Arc<ThreadPool>,
+    _phantom_data: PhantomData<I>,
+}
+
+#[async_trait]
+impl<T: Pixel, I: InterpolationAlgorithm<T>> FoldTileAccu
+    for TileWithProjectionCoordinatesAndInput<T, I>
+{
+    type RasterType = T;
+
+    async fn into_tile(self) -> Result<RasterTile2D<Self::RasterType>> {
+        if self.input_tile.is_empty() {
+            return Ok(self.accu_tile);
+        }
+
+        // now that we collected all the input pixels we sample them at the projected coordinates
+        let output_tile = crate::util::spawn_blocking_with_thread_pool(self.pool, move || {
+            let info_in = self.input_tile.tile_information();
+            let in_upper_left = info_in.spatial_partition().upper_left();
+            let in_x_size = info_in.global_geo_transform.x_pixel_size();
+            let in_y_size = info_in.global_geo_transform.y_pixel_size();
+
+            let map_fn = |grid_idx: GridIdx2D| {
+                self.coords
+                    .get_at_grid_index_unchecked(grid_idx)
+                    .and_then(|coord| {
+                        let position = [
+                            (coord.y - in_upper_left.y) / in_y_size,
+                            (coord.x - in_upper_left.x) / in_x_size,
+                        ];
+                        I::sample(&self.input_tile, position, self.footprint)
+                    })
+            };
+
+            let accu_info = self.accu_tile.tile_information();
+            let out_data =
+                GridOrEmpty::from_index_fn_parallel(&accu_info.tile_size_in_pixels, map_fn);
+
+            RasterTile2D::new_with_tile_info(
+                self.accu_tile.time,
+                accu_info,
+                self.accu_tile.band,
+                out_data,
+                self.accu_tile.cache_hint,
+            )
+        })
+        .await?;
+
+        Ok(output_tile)
+    }
+
+    fn thread_pool(&self) -> &Arc<ThreadPool> {
+        &self.pool
+    }
+}
+
+impl<T: Pixel, I: InterpolationAlgorithm<T>> FoldTileAccuMut
+    for TileWithProjectionCoordinatesAndInput<T, I>
+{
+    fn tile_mut(&mut self) -> &mut RasterTile2D<Self::RasterType> {
+        &mut self.accu_tile
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::StreamExt;
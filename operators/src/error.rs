@@ -82,6 +82,31 @@ pub enum Error {
         found: usize,
     },
 
+    #[snafu(display("Percentile {} must be between 0 and 100", percentile))]
+    InvalidPercentile {
+        percentile: u8,
+    },
+
+    #[snafu(display(
+        "The classes of a class fraction must be non-empty and unique, but found {:?}",
+        classes
+    ))]
+    InvalidClassFractionClasses {
+        classes: Vec<i64>,
+    },
+
+    #[snafu(display(
+        "The query requires a raster mosaic of {}x{} pixels, which exceeds the maximum of {} pixels. Reduce the size of the query's bounds or its resolution.",
+        width,
+        height,
+        max_pixels
+    ))]
+    RasterMosaicTooLarge {
+        width: usize,
+        height: usize,
+        max_pixels: usize,
+    },
+
     #[snafu(display("Column {} does not exist", column))]
     ColumnDoesNotExist {
         column: String,
@@ -105,11 +130,21 @@ pub enum Error {
     InvalidExpression,
 
     #[snafu(display(
-        "The expression operator only supports inputs with up to 8 bands. Found {found} bands.",
+        "The compiled expression backend supports inputs with 1 to {max} bands. Found {found} bands.",
     ))]
     InvalidNumberOfExpressionInputBands {
         found: usize,
+        max: usize,
+    },
+
+    #[snafu(display(
+        "The expression has {expected} outputs, but {found} output bands were specified.",
+    ))]
+    InvalidNumberOfExpressionOutputBands {
+        expected: usize,
+        found: usize,
     },
+
     InvalidNumberOfRasterStackerInputs,
 
     InvalidNoDataValueValueForOutputDataType,
@@ -358,6 +393,31 @@ pub enum Error {
         source: crate::processing::NeighborhoodAggregateError,
     },
 
+    #[snafu(context(false))]
+    TerrainAnalysis {
+        source: crate::processing::TerrainAnalysisError,
+    },
+
+    #[snafu(context(false))]
+    Reclassify {
+        source: crate::processing::ReclassifyError,
+    },
+
+    #[snafu(context(false))]
+    Polygonize {
+        source: crate::processing::PolygonizeError,
+    },
+
+    #[snafu(context(false))]
+    Contour {
+        source: crate::processing::ContourError,
+    },
+
+    #[snafu(context(false))]
+    VectorOverlay {
+        source: crate::processing::VectorOverlayError,
+    },
+
     #[snafu(context(false))]
     GdalSource {
         source: crate::source::GdalSourceError,
@@ -367,6 +427,14 @@ pub enum Error {
 
     AbortTriggerAlreadyUsed,
 
+    #[snafu(display(
+        "The vector file format {} cannot store features without geometries",
+        format
+    ))]
+    OgrVectorFileFormatRequiresGeometry {
+        format: String,
+    },
+
     SubPathMustNotEscapeBasePath {
         base: PathBuf,
         sub_path: PathBuf,
@@ -412,6 +480,16 @@ pub enum Error {
         found: usize,
     },
 
+    #[snafu(display(
+        "InvalidNumberOfBandNames: expected \"{}\" found \"{}\"",
+        expected,
+        found
+    ))]
+    InvalidNumberOfBandNames {
+        expected: usize,
+        found: usize,
+    },
+
     QueryingProcessorFailed {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
@@ -539,6 +539,7 @@ mod tests {
                             expression: "2 * A".to_string(),
                             output_type: RasterDataType::U8,
                             output_band: None,
+                            output_bands: vec![],
                             map_no_data: false,
                         },
                         sources: SingleRasterSource {
@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+
+/// A position in the grid of pixel centers, i.e., (column, row)
+pub type Point = (f64, f64);
+
+/// The bit representation of a `Point`.
+/// Crossings on an edge shared by two triangles are computed identically, so they can be matched exactly.
+type PointKey = (u64, u64);
+
+fn key((x, y): Point) -> PointKey {
+    (x.to_bits(), y.to_bits())
+}
+
+/// A grid of values in row-major order that is split into triangles between the pixel centers.
+/// Within each triangle, the values are interpolated linearly.
+pub struct TriangleGrid<'g> {
+    values: &'g [Option<f64>],
+    width: usize,
+    height: usize,
+}
+
+impl<'g> TriangleGrid<'g> {
+    pub fn new(values: &'g [Option<f64>], width: usize, height: usize) -> Self {
+        debug_assert_eq!(values.len(), width * height);
+
+        Self {
+            values,
+            width,
+            height,
+        }
+    }
+
+    /// The minimum and maximum of all values, or `None` if there are no values
+    pub fn value_range(&self) -> Option<(f64, f64)> {
+        self.values.iter().flatten().fold(None, |range, &value| {
+            let (min, max) = range.unwrap_or((value, value));
+            Some((min.min(value), max.max(value)))
+        })
+    }
+
+    /// All triangles with values at their three corners.
+    /// The corners are ordered clockwise, with rows pointing downwards.
+    fn triangles(&self) -> impl Iterator<Item = [(usize, f64); 3]> + '_ {
+        let width = self.width;
+        let cells_y = self.height.saturating_sub(1);
+        let cells_x = width.saturating_sub(1);
+
+        (0..cells_y)
+            .flat_map(move |row| (0..cells_x).map(move |col| row * width + col))
+            .flat_map(move |upper_left| {
+                let upper_right = upper_left + 1;
+                let lower_left = upper_left + width;
+                let lower_right = lower_left + 1;
+
+                [
+                    [upper_left, upper_right, lower_right],
+                    [upper_left, lower_right, lower_left],
+                ]
+            })
+            .filter_map(|[a, b, c]| {
+                Some([
+                    (a, self.values[a]?),
+                    (b, self.values[b]?),
+                    (c, self.values[c]?),
+                ])
+            })
+    }
+
+    fn point(&self, idx: usize) -> Point {
+        ((idx % self.width) as f64, (idx / self.width) as f64)
+    }
+
+    /// The point on the edge between two corners where the interpolated value equals the `level`
+    fn crossing(&self, a: (usize, f64), b: (usize, f64), level: f64) -> Point {
+        // always interpolate in the same direction to get identical results for neighboring triangles
+        let ((a_idx, a_value), (b_idx, b_value)) = if a.0 < b.0 { (a, b) } else { (b, a) };
+
+        let t = (level - a_value) / (b_value - a_value);
+        let (a_x, a_y) = self.point(a_idx);
+        let (b_x, b_y) = self.point(b_idx);
+
+        (a_x + t * (b_x - a_x), a_y + t * (b_y - a_y))
+    }
+
+    /// Compute the lines where the interpolated values equal the `level`.
+    ///
+    /// Closed lines end with their first point.
+    pub fn isolines(&self, level: f64) -> Vec<Vec<Point>> {
+        let mut segments = Vec::new();
+
+        for corners in self.triangles() {
+            let crossings = (0..3)
+                .map(|i| (corners[i], corners[(i + 1) % 3]))
+                .filter(|(a, b)| (a.1 >= level) != (b.1 >= level))
+                .map(|(a, b)| self.crossing(a, b, level))
+                .collect::<Vec<_>>();
+
+            if let [start, end] = crossings[..] {
+                if key(start) != key(end) {
+                    segments.push((start, end));
+                }
+            }
+        }
+
+        stitch_segments(&segments)
+    }
+
+    /// Compute the polygons where the interpolated values are at least `min` and less than `max`.
+    ///
+    /// Each polygon consists of an exterior ring and its holes. All rings are closed.
+    pub fn isobands(&self, min: f64, max: f64) -> Vec<Vec<Vec<Point>>> {
+        let in_band = |value: f64| value >= min && value < max;
+
+        // the pieces of neighboring triangles share edges in opposite directions, so only the outline remains
+        let mut outline = HashSet::new();
+        let mut points = HashMap::new();
+
+        for corners in self.triangles() {
+            let mut piece: Vec<Point> = Vec::with_capacity(9);
+
+            for i in 0..3 {
+                let (a, b) = (corners[i], corners[(i + 1) % 3]);
+
+                if in_band(a.1) {
+                    piece.push(self.point(a.0));
+                }
+
+                let levels = if a.1 < b.1 { [min, max] } else { [max, min] };
+                for level in levels {
+                    if (a.1 >= level) != (b.1 >= level) {
+                        piece.push(self.crossing(a, b, level));
+                    }
+                }
+            }
+
+            piece.dedup_by_key(|point| key(*point));
+            if piece.len() > 1 && key(piece[0]) == key(piece[piece.len() - 1]) {
+                piece.pop();
+            }
+            if piece.len() < 3 {
+                continue;
+            }
+
+            for i in 0..piece.len() {
+                let (from, to) = (piece[i], piece[(i + 1) % piece.len()]);
+                points.insert(key(from), from);
+
+                if !outline.remove(&(key(to), key(from))) {
+                    outline.insert((key(from), key(to)));
+                }
+            }
+        }
+
+        let rings = trace_rings(outline, &points);
+
+        assemble_polygons(rings)
+    }
+}
+
+/// Connect segments with common end points to lines.
+/// Lines that end at the border of the grid are traced first, then all closed lines.
+fn stitch_segments(segments: &[(Point, Point)]) -> Vec<Vec<Point>> {
+    let mut segments_at_point: HashMap<PointKey, Vec<usize>> = HashMap::new();
+    for (idx, (start, end)) in segments.iter().enumerate() {
+        segments_at_point.entry(key(*start)).or_default().push(idx);
+        segments_at_point.entry(key(*end)).or_default().push(idx);
+    }
+
+    let is_line_end = |point: Point| segments_at_point[&key(point)].len() == 1;
+
+    let mut used = vec![false; segments.len()];
+    let mut lines = Vec::new();
+
+    for only_line_ends in [true, false] {
+        for (idx, &(start, end)) in segments.iter().enumerate() {
+            if used[idx] {
+                continue;
+            }
+
+            let (first, mut current) = if !only_line_ends || is_line_end(start) {
+                (start, end)
+            } else if is_line_end(end) {
+                (end, start)
+            } else {
+                continue;
+            };
+
+            used[idx] = true;
+            let mut line = vec![first, current];
+
+            while let Some(&next_idx) = segments_at_point[&key(current)]
+                .iter()
+                .find(|&&next_idx| !used[next_idx])
+            {
+                used[next_idx] = true;
+
+                let (next_start, next_end) = segments[next_idx];
+                current = if key(next_start) == key(current) {
+                    next_end
+                } else {
+                    next_start
+                };
+                line.push(current);
+            }
+
+            lines.push(line);
+        }
+    }
+
+    lines
+}
+
+/// Link directed edges to closed rings and drop vertices on straight lines
+fn trace_rings(
+    edges: HashSet<(PointKey, PointKey)>,
+    points: &HashMap<PointKey, Point>,
+) -> Vec<Vec<Point>> {
+    let mut edges = edges.into_iter().collect::<Vec<_>>();
+    edges.sort_unstable();
+
+    let mut outgoing: HashMap<PointKey, Vec<PointKey>> = HashMap::new();
+    for &(from, to) in &edges {
+        outgoing.entry(from).or_default().push(to);
+    }
+
+    let mut rings = Vec::new();
+
+    for (start, _) in edges {
+        let mut ring = vec![start];
+        let mut current = start;
+
+        while let Some(next) = outgoing.get_mut(&current).and_then(Vec::pop) {
+            ring.push(next);
+            current = next;
+
+            if current == start {
+                break;
+            }
+        }
+
+        if ring.len() < 4 || ring[0] != ring[ring.len() - 1] {
+            continue;
+        }
+
+        let ring = ring.into_iter().map(|point| points[&point]).collect();
+        rings.push(remove_collinear_points(ring));
+    }
+
+    rings
+}
+
+fn remove_collinear_points(mut ring: Vec<Point>) -> Vec<Point> {
+    // work on the open ring and close it afterwards
+    ring.pop();
+
+    let mut i = 0;
+    while ring.len() > 3 && i < ring.len() {
+        let previous = ring[(i + ring.len() - 1) % ring.len()];
+        let point = ring[i];
+        let next = ring[(i + 1) % ring.len()];
+
+        let cross = (point.0 - previous.0) * (next.1 - point.1)
+            - (point.1 - previous.1) * (next.0 - point.0);
+
+        if cross == 0. {
+            ring.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+
+    ring.push(ring[0]);
+    ring
+}
+
+/// Twice the signed area of a closed ring. Rings that are oriented like the triangles are positive.
+fn signed_area_twice(ring: &[Point]) -> f64 {
+    ring.windows(2)
+        .map(|w| w[0].0 * w[1].1 - w[1].0 * w[0].1)
+        .sum()
+}
+
+fn ring_contains(ring: &[Point], (x, y): Point) -> bool {
+    let mut inside = false;
+
+    for w in ring.windows(2) {
+        let ((x_a, y_a), (x_b, y_b)) = (w[0], w[1]);
+
+        if (y_a > y) != (y_b > y) && x < x_a + (y - y_a) / (y_b - y_a) * (x_b - x_a) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Assign each hole to the smallest exterior ring that contains it
+fn assemble_polygons(rings: Vec<Vec<Point>>) -> Vec<Vec<Vec<Point>>> {
+    let (exteriors, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .map(|ring| (signed_area_twice(&ring), ring))
+        .partition(|(area, _)| *area > 0.);
+
+    let mut polygons = exteriors
+        .iter()
+        .map(|(_, exterior)| vec![exterior.clone()])
+        .collect::<Vec<_>>();
+
+    for (_, hole) in holes {
+        let (x_a, y_a) = hole[0];
+        let (x_b, y_b) = hole[1];
+        let probe = ((x_a + x_b) / 2., (y_a + y_b) / 2.);
+
+        let container = exteriors
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, exterior))| ring_contains(exterior, probe))
+            .min_by(|(_, (a, _)), (_, (b, _))| a.total_cmp(b))
+            .map(|(idx, _)| idx);
+
+        if let Some(idx) = container {
+            polygons[idx].push(hole);
+        }
+    }
+
+    polygons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_computes_isolines() {
+        #[rustfmt::skip]
+        let values = [
+            Some(0.), Some(0.), Some(0.),
+            Some(0.), Some(2.), Some(0.),
+            Some(0.), Some(0.), Some(0.),
+        ];
+        let grid = TriangleGrid::new(&values, 3, 3);
+
+        assert_eq!(grid.value_range(), Some((0., 2.)));
+
+        let lines = grid.isolines(1.);
+        assert_eq!(lines.len(), 1);
+
+        let line = &lines[0];
+        assert_eq!(line.first(), line.last());
+
+        let mut points = line[1..].to_vec();
+        points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            points,
+            vec![
+                (0.5, 0.5),
+                (0.5, 1.),
+                (1., 0.5),
+                (1., 1.5),
+                (1.5, 1.),
+                (1.5, 1.5)
+            ]
+        );
+
+        assert!(grid.isolines(3.).is_empty());
+    }
+
+    #[test]
+    fn it_stitches_open_isolines() {
+        #[rustfmt::skip]
+        let values = [
+            Some(0.), Some(2.), Some(2.),
+            Some(0.), Some(2.), Some(2.),
+        ];
+        let grid = TriangleGrid::new(&values, 3, 2);
+
+        assert_eq!(
+            grid.isolines(1.),
+            vec![vec![(0.5, 0.), (0.5, 0.5), (0.5, 1.)]]
+        );
+    }
+
+    #[test]
+    fn it_computes_isobands_with_holes() {
+        #[rustfmt::skip]
+        let values = [
+            Some(0.), Some(0.), Some(0.),
+            Some(0.), Some(2.), Some(0.),
+            Some(0.), Some(0.), Some(0.),
+        ];
+        let grid = TriangleGrid::new(&values, 3, 3);
+
+        let outer = grid.isobands(-1., 1.);
+        assert_eq!(outer.len(), 1);
+        assert_eq!(outer[0].len(), 2);
+        float_cmp::assert_approx_eq!(f64, signed_area_twice(&outer[0][0]), 8.);
+        float_cmp::assert_approx_eq!(f64, signed_area_twice(&outer[0][1]), -1.5);
+
+        let inner = grid.isobands(1., 3.);
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].len(), 1);
+        float_cmp::assert_approx_eq!(f64, signed_area_twice(&inner[0][0]), 1.5);
+    }
+}
@@ -0,0 +1,772 @@
+mod marching_triangles;
+
+use crate::engine::{
+    BoxRasterQueryProcessor, CanonicOperatorName, ExecutionContext, InitializedRasterOperator,
+    InitializedSources, InitializedVectorOperator, Operator, OperatorName, QueryContext,
+    QueryProcessor, SingleRasterSource, TypedVectorQueryProcessor, VectorColumnInfo,
+    VectorOperator, VectorQueryProcessor, VectorResultDescriptor, WorkflowOperatorPath,
+};
+use crate::util::raster_mosaic::{mosaic_raster_stream, RasterMosaic};
+use crate::util::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use geoengine_datatypes::collections::{FeatureCollection, VectorDataType};
+use geoengine_datatypes::primitives::{
+    AxisAlignedRectangle, BandSelection, BoundingBox2D, ColumnSelection, Coordinate2D, FeatureData,
+    FeatureDataType, Geometry, MultiLineString, MultiPolygon, RasterQueryRectangle,
+    VectorQueryRectangle,
+};
+use geoengine_datatypes::raster::{GeoTransform, Pixel};
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use num_traits::AsPrimitive;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, Snafu};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use self::marching_triangles::{Point, TriangleGrid};
+
+/// An operator that derives contour lines or filled contour bands from a raster.
+///
+/// The raster values are interpolated linearly between the pixel centers.
+/// Contours are connected across tile borders within the query rectangle.
+/// Thus, the query rectangle must not exceed [`MAX_MOSAIC_PIXELS`](crate::util::raster_mosaic::MAX_MOSAIC_PIXELS) pixels.
+/// Each feature contains all contours of one level and is valid for the time of the raster.
+pub type Contour = Operator<ContourParams, SingleRasterSource>;
+
+impl OperatorName for Contour {
+    const TYPE_NAME: &'static str = "Contour";
+}
+
+/// The maximum number of levels that are computed for one raster
+const MAX_NUMBER_OF_LEVELS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContourParams {
+    /// The values at which the contours are computed
+    pub levels: ContourLevels,
+
+    /// Whether the output are lines or bands.
+    /// The default is [`ContourOutput::Isolines`].
+    #[serde(default)]
+    pub output: ContourOutput,
+
+    /// The name of the column that stores the level of a contour.
+    /// For isobands, this is the lower level of the band.
+    /// The default is `level`.
+    #[serde(default = "default_level_column")]
+    pub level_column: String,
+}
+
+fn default_level_column() -> String {
+    "level".into()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum ContourLevels {
+    /// All multiples of the `interval`, shifted by the `offset`, within the range of the raster values
+    Interval {
+        interval: f64,
+        #[serde(default)]
+        offset: f64,
+    },
+    /// The given levels in ascending order.
+    /// For isobands, the bands lie between consecutive levels.
+    Levels { levels: Vec<f64> },
+}
+
+impl ContourLevels {
+    fn validate(&self, output: ContourOutput) -> Result<(), ContourError> {
+        match self {
+            ContourLevels::Interval { interval, offset } => {
+                ensure!(
+                    interval.is_finite() && *interval > 0.,
+                    error::InvalidInterval {
+                        interval: *interval
+                    }
+                );
+                ensure!(offset.is_finite(), error::InvalidOffset { offset: *offset });
+            }
+            ContourLevels::Levels { levels } => {
+                let min_number_of_levels = match output {
+                    ContourOutput::Isolines => 1,
+                    ContourOutput::Isobands => 2,
+                };
+                ensure!(
+                    levels.len() >= min_number_of_levels,
+                    error::TooFewLevels {
+                        min_number_of_levels
+                    }
+                );
+                ensure!(
+                    levels.len() <= MAX_NUMBER_OF_LEVELS,
+                    error::TooManyLevels {
+                        number_of_levels: levels.len(),
+                        max_number_of_levels: MAX_NUMBER_OF_LEVELS,
+                    }
+                );
+                ensure!(
+                    levels.iter().all(|level| level.is_finite())
+                        && levels.windows(2).all(|w| w[0] < w[1]),
+                    error::UnsortedLevels
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The multiples `k` of the interval s.t. `offset + k * interval` lies within `[min, max]` after rounding with `round_min`
+    fn interval_steps(
+        interval: f64,
+        offset: f64,
+        (min, max): (f64, f64),
+        round_min: fn(f64) -> f64,
+    ) -> Result<impl Iterator<Item = f64>, ContourError> {
+        let first = round_min((min - offset) / interval);
+        let last = ((max - offset) / interval).floor();
+
+        let number_of_levels = (last - first + 1.).max(0.) as usize;
+        ensure!(
+            number_of_levels <= MAX_NUMBER_OF_LEVELS,
+            error::TooManyLevels {
+                number_of_levels,
+                max_number_of_levels: MAX_NUMBER_OF_LEVELS,
+            }
+        );
+
+        Ok((0..number_of_levels).map(move |i| first + i as f64))
+    }
+
+    /// The levels of isolines for values within `value_range`
+    fn isoline_levels(&self, value_range: (f64, f64)) -> Result<Vec<f64>, ContourError> {
+        match self {
+            ContourLevels::Interval { interval, offset } => {
+                Ok(
+                    Self::interval_steps(*interval, *offset, value_range, f64::ceil)?
+                        .map(|step| offset + step * interval)
+                        .collect(),
+                )
+            }
+            ContourLevels::Levels { levels } => Ok(levels
+                .iter()
+                .copied()
+                .filter(|level| (value_range.0..=value_range.1).contains(level))
+                .collect()),
+        }
+    }
+
+    /// The lower and upper levels of isobands that contain values within `value_range`
+    fn isoband_levels(&self, value_range: (f64, f64)) -> Result<Vec<(f64, f64)>, ContourError> {
+        match self {
+            ContourLevels::Interval { interval, offset } => {
+                Ok(
+                    Self::interval_steps(*interval, *offset, value_range, f64::floor)?
+                        .map(|step| (offset + step * interval, offset + (step + 1.) * interval))
+                        .collect(),
+                )
+            }
+            ContourLevels::Levels { levels } => Ok(levels
+                .windows(2)
+                .map(|w| (w[0], w[1]))
+                .filter(|(lower, upper)| *lower <= value_range.1 && *upper > value_range.0)
+                .collect()),
+        }
+    }
+}
+
+/// The kind of contours to compute
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContourOutput {
+    /// Lines of equal value as `MultiLineString`s
+    #[default]
+    Isolines,
+    /// Areas of values between two levels as `MultiPolygon`s
+    Isobands,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)), module(error))]
+pub enum ContourError {
+    #[snafu(display("The interval must be a positive number, but is {}", interval))]
+    InvalidInterval { interval: f64 },
+
+    #[snafu(display("The offset must be a finite number, but is {}", offset))]
+    InvalidOffset { offset: f64 },
+
+    #[snafu(display("There must be at least {} levels", min_number_of_levels))]
+    TooFewLevels { min_number_of_levels: usize },
+
+    #[snafu(display(
+        "The number of levels must not exceed {}, but is {}",
+        max_number_of_levels,
+        number_of_levels
+    ))]
+    TooManyLevels {
+        number_of_levels: usize,
+        max_number_of_levels: usize,
+    },
+
+    #[snafu(display("The levels must be finite numbers in strictly ascending order"))]
+    UnsortedLevels,
+
+    #[snafu(display("The name of the level column must not be empty"))]
+    EmptyLevelColumn,
+}
+
+#[typetag::serde]
+#[async_trait]
+impl VectorOperator for Contour {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedVectorOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        self.params.levels.validate(self.params.output)?;
+        ensure!(
+            !self.params.level_column.is_empty(),
+            error::EmptyLevelColumn
+        );
+
+        let initialized_sources = self.sources.initialize_sources(path, context).await?;
+        let raster_source = initialized_sources.raster;
+
+        let in_descriptor = raster_source.result_descriptor();
+
+        ensure!(
+            in_descriptor.bands.len() == 1,
+            crate::error::OperatorDoesNotSupportMultiBandsSources {
+                operator: Contour::TYPE_NAME
+            }
+        );
+
+        let level_column_info = VectorColumnInfo {
+            data_type: FeatureDataType::Float,
+            measurement: in_descriptor.bands.bands()[0].measurement.clone(),
+        };
+
+        let result_descriptor = VectorResultDescriptor {
+            data_type: match self.params.output {
+                ContourOutput::Isolines => VectorDataType::MultiLineString,
+                ContourOutput::Isobands => VectorDataType::MultiPolygon,
+            },
+            spatial_reference: in_descriptor.spatial_reference,
+            columns: [(self.params.level_column.clone(), level_column_info)]
+                .into_iter()
+                .collect(),
+            time: in_descriptor.time,
+            bbox: in_descriptor.bbox.map(|bbox| bbox.as_bbox()),
+        };
+
+        let initialized_operator = InitializedContour {
+            name,
+            result_descriptor,
+            raster_source,
+            levels: self.params.levels,
+            output: self.params.output,
+            level_column: self.params.level_column,
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(Contour);
+}
+
+pub struct InitializedContour {
+    name: CanonicOperatorName,
+    result_descriptor: VectorResultDescriptor,
+    raster_source: Box<dyn InitializedRasterOperator>,
+    levels: ContourLevels,
+    output: ContourOutput,
+    level_column: String,
+}
+
+impl InitializedContour {
+    fn processor<G: ContourGeometry>(
+        &self,
+    ) -> Result<Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>> {
+        let source_processor = self.raster_source.query_processor()?;
+
+        Ok(
+            call_on_generic_raster_processor!(source_processor, source => {
+                ContourProcessor::<_, G> {
+                    source,
+                    result_descriptor: self.result_descriptor.clone(),
+                    levels: self.levels.clone(),
+                    level_column: self.level_column.clone(),
+                    geometry: PhantomData,
+                }
+                .boxed()
+            }),
+        )
+    }
+}
+
+impl InitializedVectorOperator for InitializedContour {
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn query_processor(&self) -> Result<TypedVectorQueryProcessor> {
+        Ok(match self.output {
+            ContourOutput::Isolines => {
+                TypedVectorQueryProcessor::MultiLineString(self.processor::<MultiLineString>()?)
+            }
+            ContourOutput::Isobands => {
+                TypedVectorQueryProcessor::MultiPolygon(self.processor::<MultiPolygon>()?)
+            }
+        })
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+/// A geometry type that contours can be computed for
+trait ContourGeometry: Geometry + ArrowTyped + Sized + Send + Sync + 'static {
+    /// Compute the contours of the `grid`, one geometry per level.
+    /// The `geo_transform` maps the upper left corners of the pixels.
+    fn contours(
+        grid: &TriangleGrid,
+        levels: &ContourLevels,
+        geo_transform: GeoTransform,
+    ) -> Result<Vec<(f64, Self)>>;
+}
+
+/// Transform a position in the grid of pixel centers into a coordinate
+fn pixel_center_coordinate(geo_transform: GeoTransform, (x, y): Point) -> Coordinate2D {
+    let origin = geo_transform.origin_coordinate;
+
+    Coordinate2D::new(
+        origin.x + (x + 0.5) * geo_transform.x_pixel_size(),
+        origin.y + (y + 0.5) * geo_transform.y_pixel_size(),
+    )
+}
+
+impl ContourGeometry for MultiLineString {
+    fn contours(
+        grid: &TriangleGrid,
+        levels: &ContourLevels,
+        geo_transform: GeoTransform,
+    ) -> Result<Vec<(f64, Self)>> {
+        let Some(value_range) = grid.value_range() else {
+            return Ok(vec![]);
+        };
+
+        let mut contours = Vec::new();
+
+        for level in levels.isoline_levels(value_range)? {
+            let lines = grid
+                .isolines(level)
+                .into_iter()
+                .map(|line| {
+                    line.into_iter()
+                        .map(|point| pixel_center_coordinate(geo_transform, point))
+                        .collect()
+                })
+                .collect::<Vec<_>>();
+
+            if lines.is_empty() {
+                continue;
+            }
+
+            contours.push((level, MultiLineString::new(lines)?));
+        }
+
+        Ok(contours)
+    }
+}
+
+impl ContourGeometry for MultiPolygon {
+    fn contours(
+        grid: &TriangleGrid,
+        levels: &ContourLevels,
+        geo_transform: GeoTransform,
+    ) -> Result<Vec<(f64, Self)>> {
+        let Some(value_range) = grid.value_range() else {
+            return Ok(vec![]);
+        };
+
+        let mut contours = Vec::new();
+
+        for (lower, upper) in levels.isoband_levels(value_range)? {
+            let polygons = grid
+                .isobands(lower, upper)
+                .into_iter()
+                .map(|polygon| {
+                    polygon
+                        .into_iter()
+                        .map(|ring| {
+                            ring.into_iter()
+                                .map(|point| pixel_center_coordinate(geo_transform, point))
+                                .collect()
+                        })
+                        .collect()
+                })
+                .collect::<Vec<_>>();
+
+            if polygons.is_empty() {
+                continue;
+            }
+
+            contours.push((lower, MultiPolygon::new(polygons)?));
+        }
+
+        Ok(contours)
+    }
+}
+
+struct ContourProcessor<P: Pixel, G> {
+    source: BoxRasterQueryProcessor<P>,
+    result_descriptor: VectorResultDescriptor,
+    levels: ContourLevels,
+    level_column: String,
+    geometry: PhantomData<G>,
+}
+
+#[async_trait]
+impl<P, G> QueryProcessor for ContourProcessor<P, G>
+where
+    P: Pixel,
+    G: ContourGeometry,
+{
+    type Output = FeatureCollection<G>;
+    type SpatialBounds = BoundingBox2D;
+    type Selection = ColumnSelection;
+    type ResultDescription = VectorResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: VectorQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        let raster_query =
+            RasterQueryRectangle::from_qrect_and_bands(&query, BandSelection::first());
+        let spatial_bounds = raster_query.spatial_bounds;
+
+        let tiles = self.source.raster_query(raster_query, ctx).await?;
+
+        let collections =
+            mosaic_raster_stream(tiles, spatial_bounds).and_then(move |mosaic| async move {
+                let levels = self.levels.clone();
+                let level_column = self.level_column.clone();
+
+                crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
+                    contour_mosaic::<P, G>(mosaic, &levels, &level_column)
+                })
+                .await?
+            });
+
+        Ok(collections.boxed())
+    }
+
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+fn contour_mosaic<P: Pixel, G: ContourGeometry>(
+    mosaic: RasterMosaic<P>,
+    levels: &ContourLevels,
+    level_column: &str,
+) -> Result<FeatureCollection<G>> {
+    let values = mosaic
+        .pixels
+        .into_iter()
+        .map(|pixel| {
+            pixel
+                .map(AsPrimitive::<f64>::as_)
+                .filter(|value| !value.is_nan())
+        })
+        .collect::<Vec<_>>();
+
+    let grid = TriangleGrid::new(&values, mosaic.width, mosaic.height);
+
+    let (levels, geometries): (Vec<f64>, Vec<G>) =
+        G::contours(&grid, levels, mosaic.geo_transform)?
+            .into_iter()
+            .unzip();
+
+    FeatureCollection::<G>::from_data(
+        geometries,
+        vec![mosaic.time; levels.len()],
+        HashMap::from([(level_column.to_string(), FeatureData::Float(levels))]),
+        mosaic.cache_hint,
+    )
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{
+        MockExecutionContext, MockQueryContext, RasterBandDescriptors, RasterOperator,
+        RasterResultDescriptor,
+    };
+    use crate::mock::{MockRasterSource, MockRasterSourceParams};
+    use geoengine_datatypes::collections::{
+        FeatureCollectionInfos, IntoGeometryIterator, MultiLineStringCollection,
+        MultiPolygonCollection,
+    };
+    use geoengine_datatypes::primitives::{
+        CacheHint, MultiLineStringAccess, MultiPolygonAccess, SpatialResolution, TimeInterval,
+    };
+    use geoengine_datatypes::raster::{
+        Grid2D, GridOrEmpty, RasterDataType, RasterTile2D, TileInformation, TilingSpecification,
+    };
+    use geoengine_datatypes::spatial_reference::SpatialReference;
+    use geoengine_datatypes::util::test::TestDefault;
+
+    /// A 4x4 raster of two tiles with a peak of 4 in the center, split by the tile border
+    fn make_raster() -> Box<dyn RasterOperator> {
+        let tile = |tile_x: isize, values: Vec<f32>| {
+            RasterTile2D::new_with_tile_info(
+                TimeInterval::new_unchecked(0, 10),
+                TileInformation {
+                    global_tile_position: [-1, tile_x].into(),
+                    tile_size_in_pixels: [4, 2].into(),
+                    global_geo_transform: TestDefault::test_default(),
+                },
+                0,
+                GridOrEmpty::from(Grid2D::new([4, 2].into(), values).unwrap()),
+                CacheHint::default(),
+            )
+        };
+
+        MockRasterSource {
+            params: MockRasterSourceParams {
+                data: vec![
+                    tile(0, vec![0., 0., 0., 4., 0., 4., 0., 0.]),
+                    tile(1, vec![0., 0., 4., 0., 4., 0., 0., 0.]),
+                ],
+                result_descriptor: RasterResultDescriptor {
+                    data_type: RasterDataType::F32,
+                    spatial_reference: SpatialReference::epsg_4326().into(),
+                    time: None,
+                    bbox: None,
+                    resolution: None,
+                    bands: RasterBandDescriptors::new_single_band(),
+                },
+            },
+        }
+        .boxed()
+    }
+
+    async fn initialize(params: ContourParams) -> Result<Box<dyn InitializedVectorOperator>> {
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [4, 2].into(),
+        ));
+
+        Contour {
+            params,
+            sources: make_raster().into(),
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await
+    }
+
+    fn query_rectangle() -> VectorQueryRectangle {
+        VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new_unchecked((0., 0.).into(), (4., 4.).into()),
+            time_interval: TimeInterval::new_unchecked(0, 10),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: ColumnSelection::all(),
+        }
+    }
+
+    #[test]
+    fn it_deserializes_the_params() {
+        let params: ContourParams = serde_json::from_value(serde_json::json!({
+            "levels": { "type": "interval", "interval": 10 },
+            "output": "isobands",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            ContourParams {
+                levels: ContourLevels::Interval {
+                    interval: 10.,
+                    offset: 0.,
+                },
+                output: ContourOutput::Isobands,
+                level_column: "level".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn it_computes_levels() {
+        let levels = ContourLevels::Interval {
+            interval: 10.,
+            offset: 5.,
+        };
+
+        assert_eq!(
+            levels.isoline_levels((0., 30.)).unwrap(),
+            vec![5., 15., 25.]
+        );
+        assert_eq!(
+            levels.isoband_levels((0., 30.)).unwrap(),
+            vec![(-5., 5.), (5., 15.), (15., 25.), (25., 35.)]
+        );
+        assert!(levels.isoline_levels((0., 1e6)).is_err());
+
+        let levels = ContourLevels::Levels {
+            levels: vec![0., 10., 100.],
+        };
+
+        assert_eq!(levels.isoline_levels((5., 50.)).unwrap(), vec![10.]);
+        assert_eq!(
+            levels.isoband_levels((5., 50.)).unwrap(),
+            vec![(0., 10.), (10., 100.)]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_validates_the_params() {
+        assert!(initialize(ContourParams {
+            levels: ContourLevels::Interval {
+                interval: 0.,
+                offset: 0.,
+            },
+            output: ContourOutput::Isolines,
+            level_column: "level".to_string(),
+        })
+        .await
+        .is_err());
+
+        assert!(initialize(ContourParams {
+            levels: ContourLevels::Levels {
+                levels: vec![2., 1.],
+            },
+            output: ContourOutput::Isolines,
+            level_column: "level".to_string(),
+        })
+        .await
+        .is_err());
+
+        assert!(initialize(ContourParams {
+            levels: ContourLevels::Levels { levels: vec![1.] },
+            output: ContourOutput::Isobands,
+            level_column: "level".to_string(),
+        })
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn it_stitches_isolines_across_tiles() {
+        let operator = initialize(ContourParams {
+            levels: ContourLevels::Levels {
+                levels: vec![2., 10.],
+            },
+            output: ContourOutput::Isolines,
+            level_column: "height".to_string(),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            operator.result_descriptor().data_type,
+            VectorDataType::MultiLineString
+        );
+
+        let processor = operator
+            .query_processor()
+            .unwrap()
+            .multi_line_string()
+            .unwrap();
+
+        let query_ctx = MockQueryContext::test_default();
+        let collections: Vec<MultiLineStringCollection> = processor
+            .query(query_rectangle(), &query_ctx)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(collections.len(), 1);
+
+        let collection = &collections[0];
+        assert_eq!(collection.len(), 1);
+        assert_eq!(
+            collection
+                .data("height")
+                .unwrap()
+                .float_options_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(2.)]
+        );
+        assert_eq!(
+            collection.time_intervals(),
+            &[TimeInterval::new_unchecked(0, 10)]
+        );
+
+        // a single closed line around the peak, although it crosses the tile border
+        let geometry = collection.geometries().next().unwrap();
+        let lines = geometry.lines();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].first(), lines[0].last());
+        assert!(lines[0].len() > 4);
+    }
+
+    #[tokio::test]
+    async fn it_computes_isobands() {
+        let operator = initialize(ContourParams {
+            levels: ContourLevels::Interval {
+                interval: 2.,
+                offset: 0.,
+            },
+            output: ContourOutput::Isobands,
+            level_column: "level".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let processor = operator.query_processor().unwrap().multi_polygon().unwrap();
+
+        let query_ctx = MockQueryContext::test_default();
+        let collections: Vec<MultiPolygonCollection> = processor
+            .query(query_rectangle(), &query_ctx)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(collections.len(), 1);
+
+        let collection = &collections[0];
+        assert_eq!(
+            collection
+                .data("level")
+                .unwrap()
+                .float_options_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(0.), Some(2.), Some(4.)]
+        );
+
+        let polygons = collection
+            .geometries()
+            .map(|geometry| geometry.polygons().to_vec())
+            .collect::<Vec<_>>();
+
+        // the lowest band surrounds the higher ones
+        assert_eq!(polygons[0].len(), 1);
+        assert_eq!(polygons[0][0].len(), 2);
+        assert_eq!(polygons[1].len(), 1);
+        assert_eq!(polygons[1][0].len(), 2);
+    }
+}
@@ -31,9 +31,6 @@ pub enum VectorExpressionError {
     #[snafu(display("Input column `{name}` does not exist."))]
     InputColumnNotExisting { name: String },
 
-    #[snafu(display("Input column `{name}` is not numeric."))]
-    InputColumnNotNumeric { name: String },
-
     #[snafu(display("Found {found} columns, but only up to {max} are allowed."))]
     TooManyInputColumns { max: usize, found: usize },
 
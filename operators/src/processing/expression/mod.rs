@@ -1,4 +1,5 @@
 mod error;
+mod raster_context;
 mod raster_operator;
 mod raster_query_processor;
 mod vector_operator;
@@ -13,9 +14,44 @@ use geoengine_datatypes::primitives::{
     AsGeoOption, MultiLineString, MultiLineStringRef, MultiPoint, MultiPointRef, MultiPolygon,
     MultiPolygonRef, NoGeometry,
 };
-use geoengine_expression::{error::ExpressionExecutionError, ExpressionDependencies};
+use geoengine_expression::{
+    error::ExpressionExecutionError, ExpressionDependencies, InterpretedExpression,
+    LinkedExpression, ValueType,
+};
+use serde::{Deserialize, Serialize};
 use std::sync::{Arc, OnceLock};
 
+/// The backend that evaluates expressions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExpressionBackend {
+    /// Generates Rust code and compiles it, which requires a Rust toolchain at runtime
+    #[default]
+    Compiled,
+    /// Evaluates the syntax tree directly
+    Interpreter,
+}
+
+/// The backend is set once at startup and then used for all expression operators.
+static EXPRESSION_BACKEND: OnceLock<ExpressionBackend> = OnceLock::new();
+
+/// Sets the backend for all expression operators.
+/// Only the first call has an effect, so this has to happen before the first expression is initialized.
+pub fn set_expression_backend(backend: ExpressionBackend) {
+    // if set returns an error, it was set before and the backend must not change anymore
+    let _ = EXPRESSION_BACKEND.set(backend);
+}
+
+fn get_expression_backend() -> ExpressionBackend {
+    EXPRESSION_BACKEND.get().copied().unwrap_or_default()
+}
+
+/// An expression that is ready for evaluation by one of the [`ExpressionBackend`]s
+enum ExpressionProgram {
+    Compiled(LinkedExpression),
+    Interpreted(InterpretedExpression),
+}
+
 /// The expression dependencies are initialized once and then reused for all expression evaluations.
 static EXPRESSION_DEPENDENCIES: OnceLock<
     Result<ExpressionDependencies, Arc<ExpressionExecutionError>>,
@@ -53,14 +89,14 @@ fn get_expression_dependencies(
 
 /// Convenience trait for converting [`geoengine_datatypes`] types to [`geoengine_expression`] types.
 trait AsExpressionGeo: AsGeoOption {
-    type ExpressionGeometryType: Send;
+    type ExpressionGeometryType: Send + ValueType;
 
     fn as_expression_geo(&self) -> Option<Self::ExpressionGeometryType>;
 }
 
 /// Convenience trait for converting [`geoengine_expression`] types to [`geoengine_datatypes`] types.
 trait FromExpressionGeo: Sized {
-    type ExpressionGeometryType: Send;
+    type ExpressionGeometryType: Send + ValueType;
 
     fn from_expression_geo(geom: Self::ExpressionGeometryType) -> Option<Self>;
 }
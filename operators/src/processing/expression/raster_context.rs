@@ -0,0 +1,175 @@
+use geoengine_datatypes::{
+    operations::reproject::{
+        project_coordinates_fail_tolerant, CoordinateProjection, CoordinateProjector,
+    },
+    primitives::{Coordinate2D, TimeInterval},
+    raster::{GridIdx2D, GridSize, TileInformation},
+    spatial_reference::{SpatialReference, SpatialReferenceOption},
+};
+
+pub const NUM_CONTEXT_VARIABLES: usize = 8;
+
+/// Built-in variables that describe the pixel that is computed.
+/// They are passed to the expression after the input bands.
+///
+/// * `x` and `y` are the pixel center coordinates in the spatial reference of the raster
+/// * `lon` and `lat` are the pixel center coordinates in WGS 84
+/// * `row` and `col` are the pixel indices in the global raster grid
+/// * `time_start` and `time_end` are the bounds of the tile's time interval in milliseconds since the Unix epoch
+pub const CONTEXT_VARIABLES: [&str; NUM_CONTEXT_VARIABLES] = [
+    "x",
+    "y",
+    "lon",
+    "lat",
+    "row",
+    "col",
+    "time_start",
+    "time_end",
+];
+
+/// The values of the [`CONTEXT_VARIABLES`] for a single pixel
+pub type ContextValues = [Option<f64>; NUM_CONTEXT_VARIABLES];
+
+/// Computes the [`ContextValues`] for the pixels of a tile.
+pub struct TileContext {
+    tile_information: TileInformation,
+    time_start: Option<f64>,
+    time_end: Option<f64>,
+    /// The pixel centers in WGS 84, which are only computed if the expression uses them
+    lon_lat: Option<Vec<Option<Coordinate2D>>>,
+}
+
+impl TileContext {
+    pub fn new(
+        tile_information: TileInformation,
+        time: TimeInterval,
+        spatial_reference: SpatialReferenceOption,
+        with_lon_lat: bool,
+    ) -> Self {
+        let lon_lat = with_lon_lat.then(|| lon_lat(&tile_information, spatial_reference));
+
+        Self {
+            tile_information,
+            time_start: Some(time.start().inner() as f64),
+            time_end: Some(time.end().inner() as f64),
+            lon_lat,
+        }
+    }
+
+    /// Computes the context values for the pixel with the linear index `lin_idx` inside the tile.
+    pub fn values(&self, lin_idx: usize) -> ContextValues {
+        let global_pixel_idx = global_pixel_idx(&self.tile_information, lin_idx);
+        let [row, col] = *global_pixel_idx.inner();
+
+        let coordinate = self
+            .tile_information
+            .global_geo_transform
+            .grid_idx_to_pixel_center_coordinate_2d(global_pixel_idx);
+
+        let lon_lat = self
+            .lon_lat
+            .as_ref()
+            .and_then(|coordinates| coordinates[lin_idx]);
+
+        [
+            Some(coordinate.x),
+            Some(coordinate.y),
+            lon_lat.map(|c| c.x),
+            lon_lat.map(|c| c.y),
+            Some(row as f64),
+            Some(col as f64),
+            self.time_start,
+            self.time_end,
+        ]
+    }
+}
+
+fn global_pixel_idx(tile_information: &TileInformation, lin_idx: usize) -> GridIdx2D {
+    let tile_width = tile_information.tile_size_in_pixels.axis_size_x();
+
+    tile_information.global_upper_left_pixel_idx()
+        + [
+            (lin_idx / tile_width) as isize,
+            (lin_idx % tile_width) as isize,
+        ]
+}
+
+/// Projects all pixel centers of the tile to WGS 84.
+/// Pixels that cannot be projected have no coordinate.
+fn lon_lat(
+    tile_information: &TileInformation,
+    spatial_reference: SpatialReferenceOption,
+) -> Vec<Option<Coordinate2D>> {
+    let num_pixels = tile_information.tile_size_in_pixels.number_of_elements();
+
+    let Some(spatial_reference): Option<SpatialReference> = spatial_reference.into() else {
+        return vec![None; num_pixels];
+    };
+
+    let pixel_centers: Vec<Coordinate2D> = (0..num_pixels)
+        .map(|lin_idx| {
+            tile_information
+                .global_geo_transform
+                .grid_idx_to_pixel_center_coordinate_2d(global_pixel_idx(tile_information, lin_idx))
+        })
+        .collect();
+
+    if spatial_reference == SpatialReference::epsg_4326() {
+        return pixel_centers.into_iter().map(Some).collect();
+    }
+
+    match CoordinateProjector::from_known_srs(spatial_reference, SpatialReference::epsg_4326()) {
+        Ok(projector) => project_coordinates_fail_tolerant(&pixel_centers, &projector),
+        Err(_) => vec![None; num_pixels],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geoengine_datatypes::{primitives::TimeInstance, util::test::TestDefault};
+
+    #[test]
+    fn it_computes_the_context_values() {
+        let tile_information = TileInformation {
+            global_tile_position: [-1, 1].into(),
+            tile_size_in_pixels: [3, 2].into(),
+            global_geo_transform: TestDefault::test_default(),
+        };
+
+        let context = TileContext::new(
+            tile_information,
+            TimeInterval::new_unchecked(
+                TimeInstance::from_millis_unchecked(0),
+                TimeInstance::from_millis_unchecked(1000),
+            ),
+            SpatialReference::epsg_4326().into(),
+            true,
+        );
+
+        // second row, first column
+        assert_eq!(
+            context.values(2),
+            [
+                Some(2.5),
+                Some(1.5),
+                Some(2.5),
+                Some(1.5),
+                Some(-2.),
+                Some(2.),
+                Some(0.),
+                Some(1000.),
+            ]
+        );
+
+        let context = TileContext::new(
+            tile_information,
+            TimeInterval::default(),
+            SpatialReferenceOption::Unreferenced,
+            true,
+        );
+
+        let [_, _, lon, lat, ..] = context.values(0);
+        assert_eq!((lon, lat), (None, None));
+    }
+}
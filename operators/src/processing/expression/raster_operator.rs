@@ -1,7 +1,10 @@
 use super::{
-    get_expression_dependencies,
-    raster_query_processor::{ExpressionInput, ExpressionQueryProcessor},
-    RasterExpressionError,
+    get_expression_backend, get_expression_dependencies,
+    raster_context::CONTEXT_VARIABLES,
+    raster_query_processor::{
+        DynamicExpressionInput, ExpressionInput, ExpressionQueryProcessor, MAX_COMPILED_INPUT_BANDS,
+    },
+    ExpressionBackend, ExpressionProgram, RasterExpressionError,
 };
 use crate::{
     engine::{
@@ -10,29 +13,42 @@ use crate::{
         RasterResultDescriptor, SingleRasterSource, TypedRasterQueryProcessor,
         WorkflowOperatorPath,
     },
-    error::InvalidNumberOfExpressionInputBands,
+    error::{InvalidNumberOfExpressionInputBands, InvalidNumberOfExpressionOutputBands},
     util::Result,
 };
 use async_trait::async_trait;
 use geoengine_datatypes::raster::RasterDataType;
 use geoengine_expression::{
-    DataType, ExpressionAst, ExpressionParser, LinkedExpression, Parameter,
+    DataType, ExpressionAst, ExpressionParser, InterpretedExpression, LinkedExpression, Parameter,
 };
 use serde::{Deserialize, Serialize};
 use snafu::ensure;
+use std::collections::HashSet;
 
 /// Parameters for the `Expression` operator.
 /// * The `expression` must only contain simple arithmetic
 ///     calculations.
+///     It can either produce a single output band or a list of output bands, e.g., `[A + B, A - B]`.
+///     Input bands are referred to by `A`, `B`, `C`, … or by their band names,
+///     where characters that are not allowed in variables are replaced by `_`.
+///     After `Z`, the bands are referred to by `AA`, `AB`, ….
+///     The compiled backend supports at most 8 input bands.
+///     Moreover, there are the read-only variables `x`, `y`, `lon`, `lat`, `row`, `col`,
+///     `time_start` and `time_end` that describe the pixel's location and time.
 /// * `output_type` is the data type of the produced raster tiles.
-/// * `output_no_data_value` is the no data value of the output raster
-/// * `output_measurement` is the measurement description of the output
+/// * `output_band` is the band descriptor of a single output band.
+///     It must not be set for multiple outputs.
+/// * `output_bands` are the band descriptors for a list of output bands.
+///     If they are omitted, the bands are named after the variables they output.
+/// * `map_no_data` specifies whether no data values are passed to the expression.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ExpressionParams {
     pub expression: String,
     pub output_type: RasterDataType,
     pub output_band: Option<RasterBandDescriptor>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_bands: Vec<RasterBandDescriptor>,
     pub map_no_data: bool,
 }
 /// The `Expression` operator calculates an expression for all pixels of the input rasters bands and
@@ -40,19 +56,64 @@ pub struct ExpressionParams {
 pub type Expression = Operator<ExpressionParams, SingleRasterSource>;
 
 /// Create a parameter name from an index.
-/// Starts with `A`.
-///
-/// ## Note
-///
-/// This function only makes sense for indices between 0 and 25.
-///
+/// Starts with `A`, …, `Z` and continues with `AA`, `AB`, … like spreadsheet columns.
 fn index_to_parameter(index: usize) -> String {
-    let index = index as u32;
-    let start_index = 'A' as u32;
+    const LETTERS: &[u8; 26] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut parameter = String::new();
+    let mut remainder = index + 1;
 
-    let parameter = char::from_u32(start_index + index).unwrap_or_default();
+    while remainder > 0 {
+        remainder -= 1;
+        parameter.insert(0, char::from(LETTERS[remainder % LETTERS.len()]));
+        remainder /= LETTERS.len();
+    }
 
-    parameter.to_string()
+    parameter
+}
+
+/// The parameters of an expression with `num_bands` input bands.
+/// The input bands are followed by the context variables.
+fn expression_parameters(num_bands: usize) -> Vec<Parameter> {
+    (0..num_bands)
+        .map(index_to_parameter)
+        .chain(CONTEXT_VARIABLES.iter().map(ToString::to_string))
+        .map(|parameter| Parameter::Number(parameter.into()))
+        .collect()
+}
+
+/// Turns a band name into a variable name by replacing all characters
+/// that are not allowed in variables with `_`, e.g., `near infrared` to `near_infrared`.
+///
+/// Returns `None` if the band name cannot be used as a variable.
+fn band_name_to_variable(band_name: &str) -> Option<String> {
+    let variable: String = band_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    variable
+        .starts_with(|c: char| c.is_ascii_alphabetic())
+        .then_some(variable)
+}
+
+/// Derives the name of an output band from its expression.
+/// If the expression outputs an input band, its name is used.
+/// If it outputs an assigned variable, the variable name is used.
+fn output_band_name(
+    expression: &ExpressionAst,
+    index: usize,
+    in_bands: &RasterBandDescriptors,
+) -> String {
+    let Some(variable) = expression.output_variable() else {
+        return format!("expression_{index}");
+    };
+
+    in_bands
+        .iter()
+        .enumerate()
+        .find(|(i, _)| index_to_parameter(*i) == variable)
+        .map_or_else(|| variable.to_string(), |(_, band)| band.name.clone())
 }
 
 #[typetag::serde]
@@ -69,24 +130,44 @@ impl RasterOperator for Expression {
 
         let in_descriptor = source.result_descriptor();
 
+        let backend = get_expression_backend();
+
+        // the interpreter evaluates expressions for any number of bands
         ensure!(
-            !in_descriptor.bands.is_empty() && in_descriptor.bands.len() <= 8,
+            !in_descriptor.bands.is_empty()
+                && (backend == ExpressionBackend::Interpreter
+                    || in_descriptor.bands.len() <= MAX_COMPILED_INPUT_BANDS),
             InvalidNumberOfExpressionInputBands {
-                found: in_descriptor.bands.len()
+                found: in_descriptor.bands.len(),
+                max: MAX_COMPILED_INPUT_BANDS,
             }
         );
 
         // we refer to raster bands by A, B, C, …
-        let parameters = (0..in_descriptor.bands.len())
-            .map(|i| {
-                let parameter = index_to_parameter(i);
-                Parameter::Number(parameter.into())
-            })
-            .collect::<Vec<_>>();
+        let parameters = expression_parameters(in_descriptor.bands.len());
+
+        let mut parser = ExpressionParser::new(&parameters, DataType::Number)
+            .map_err(RasterExpressionError::from)?;
+
+        // additionally, we refer to raster bands by their names if they are valid variables
+        let mut variables: HashSet<String> = parameters
+            .iter()
+            .map(|parameter| parameter.identifier().to_string())
+            .collect();
+        for (i, band) in in_descriptor.bands.iter().enumerate() {
+            let Some(variable) = band_name_to_variable(&band.name) else {
+                continue;
+            };
+
+            if variables.insert(variable.clone()) {
+                parser
+                    .add_alias(&variable, &index_to_parameter(i))
+                    .map_err(RasterExpressionError::from)?;
+            }
+        }
 
-        let expression = ExpressionParser::new(&parameters, DataType::Number)
-            .map_err(RasterExpressionError::from)?
-            .parse(
+        let expressions = parser
+            .parse_multi_output(
                 self.params
                     .output_band
                     .as_ref()
@@ -95,24 +176,60 @@ impl RasterOperator for Expression {
             )
             .map_err(RasterExpressionError::from)?;
 
+        let output_bands = if !self.params.output_bands.is_empty() {
+            ensure!(
+                self.params.output_bands.len() == expressions.len(),
+                InvalidNumberOfExpressionOutputBands {
+                    expected: expressions.len(),
+                    found: self.params.output_bands.len(),
+                }
+            );
+
+            self.params.output_bands
+        } else if expressions.len() == 1 {
+            vec![self
+                .params
+                .output_band
+                .unwrap_or(RasterBandDescriptor::new_unitless("expression".into()))]
+        } else {
+            // a single output band cannot describe multiple outputs
+            ensure!(
+                self.params.output_band.is_none(),
+                InvalidNumberOfExpressionOutputBands {
+                    expected: expressions.len(),
+                    found: 1_usize,
+                }
+            );
+
+            expressions
+                .iter()
+                .enumerate()
+                .map(|(i, expression)| {
+                    RasterBandDescriptor::new_unitless(output_band_name(
+                        expression,
+                        i,
+                        &in_descriptor.bands,
+                    ))
+                })
+                .collect()
+        };
+
         let result_descriptor = RasterResultDescriptor {
             data_type: self.params.output_type,
             spatial_reference: in_descriptor.spatial_reference,
             time: in_descriptor.time,
             bbox: in_descriptor.bbox,
             resolution: in_descriptor.resolution,
-            bands: RasterBandDescriptors::new(vec![self
-                .params
-                .output_band
-                .unwrap_or(RasterBandDescriptor::new_unitless("expression".into()))])?,
+            bands: RasterBandDescriptors::new(output_bands)?,
         };
 
         let initialized_operator = InitializedExpression {
             name,
             result_descriptor,
             source,
-            expression,
+            expressions,
             map_no_data: self.params.map_no_data,
+            backend,
         };
 
         Ok(initialized_operator.boxed())
@@ -129,29 +246,46 @@ pub struct InitializedExpression {
     name: CanonicOperatorName,
     result_descriptor: RasterResultDescriptor,
     source: Box<dyn InitializedRasterOperator>,
-    expression: ExpressionAst,
+    /// One expression per output band
+    expressions: Vec<ExpressionAst>,
     map_no_data: bool,
+    backend: ExpressionBackend,
 }
 
 /// Macro for generating the match cases for number of bands to the `ExpressionInput` struct.
 macro_rules! generate_match_cases {
-    ($num_bands:expr, $output_type:expr, $expression:expr, $source_processor:expr, $result_descriptor:expr, $map_no_data:expr, $($x:expr),*) => {
+    ($num_bands:expr, $output_type:expr, $programs:expr, $source_processor:expr, $result_descriptor:expr, $map_no_data:expr, $with_lon_lat:expr, $($x:expr),*) => {
         match $num_bands {
             $(
                 $x => call_generic_raster_processor!(
                     $output_type,
                     ExpressionQueryProcessor::new(
-                        $expression,
+                        $programs,
                         ExpressionInput::<$x> {
                             raster: $source_processor,
                         },
                         $result_descriptor,
                         $map_no_data.clone(),
+                        $with_lon_lat,
                     )
                     .boxed()
                 ),
             )*
-            _ => unreachable!("number of bands was checked to be between 1 and 8"),
+            // only the interpreter gets here, since the compiled backend was limited to 8 bands
+            num_bands => call_generic_raster_processor!(
+                $output_type,
+                ExpressionQueryProcessor::new(
+                    $programs,
+                    DynamicExpressionInput {
+                        raster: $source_processor,
+                        num_bands: num_bands as u32,
+                    },
+                    $result_descriptor,
+                    $map_no_data.clone(),
+                    $with_lon_lat,
+                )
+                .boxed()
+            ),
         }
     };
 }
@@ -160,25 +294,46 @@ impl InitializedRasterOperator for InitializedExpression {
     fn query_processor(&self) -> Result<TypedRasterQueryProcessor> {
         let output_type = self.result_descriptor().data_type;
 
-        // TODO: spawn a blocking task for the compilation process
-        let expression_dependencies = get_expression_dependencies()
-            .map_err(|source| RasterExpressionError::Dependencies { source })?;
-        let expression = LinkedExpression::new(
-            self.expression.name(),
-            &self.expression.code(),
-            expression_dependencies,
-        )
-        .map_err(RasterExpressionError::from)?;
+        let programs = self
+            .expressions
+            .iter()
+            .map(|expression| match self.backend {
+                ExpressionBackend::Compiled => {
+                    // TODO: spawn a blocking task for the compilation process
+                    let expression_dependencies = get_expression_dependencies()
+                        .map_err(|source| RasterExpressionError::Dependencies { source })?;
+                    Ok(ExpressionProgram::Compiled(
+                        LinkedExpression::new(
+                            expression.name(),
+                            &expression.code(),
+                            expression_dependencies,
+                        )
+                        .map_err(RasterExpressionError::from)?,
+                    ))
+                }
+                ExpressionBackend::Interpreter => Ok(ExpressionProgram::Interpreted(
+                    InterpretedExpression::from_ast(expression)
+                        .map_err(RasterExpressionError::from)?,
+                )),
+            })
+            .collect::<Result<Vec<_>, RasterExpressionError>>()?;
 
         let source_processor = self.source.query_processor()?.into_f64();
 
+        // projecting the pixel coordinates is expensive, so we only do it if necessary
+        let with_lon_lat = self
+            .expressions
+            .iter()
+            .any(|expression| expression.uses_parameter("lon") || expression.uses_parameter("lat"));
+
         Ok(generate_match_cases!(
             self.source.result_descriptor().bands.len(),
             output_type,
-            expression,
+            programs,
             source_processor,
             self.result_descriptor.clone(),
             self.map_no_data,
+            with_lon_lat,
             1,
             2,
             3,
@@ -230,6 +385,7 @@ mod tests {
                 expression: "1*A".to_owned(),
                 output_type: RasterDataType::F64,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             }
         );
@@ -245,6 +401,7 @@ mod tests {
                 expression: "1*A".to_owned(),
                 output_type: RasterDataType::F64,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             })
             .unwrap()
@@ -261,6 +418,7 @@ mod tests {
                 expression: "1*A".to_owned(),
                 output_type: RasterDataType::F64,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             })
             .unwrap()
@@ -284,6 +442,7 @@ mod tests {
                 expression: "2 * A".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource { raster: raster_a },
@@ -345,6 +504,7 @@ mod tests {
                 expression: "2 * A".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: true,
             },
             sources: SingleRasterSource { raster: raster_a },
@@ -407,6 +567,7 @@ mod tests {
                 expression: "A+B".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -482,6 +643,7 @@ mod tests {
                 .to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: true,
             },
             sources: SingleRasterSource {
@@ -558,6 +720,7 @@ mod tests {
                 expression: "A+B+C".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -644,6 +807,7 @@ mod tests {
                 expression: "A+B+C+D+E+F+G+H".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -715,6 +879,7 @@ mod tests {
                 expression: "min(A * pi(), 10)".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -765,6 +930,484 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn it_evaluates_with_the_interpreter() {
+        let tile_size_in_pixels = [3, 2].into();
+        let tiling_specification = TilingSpecification {
+            origin_coordinate: [0.0, 0.0].into(),
+            tile_size_in_pixels,
+        };
+
+        let ectx = MockExecutionContext::new_with_tiling_spec(tiling_specification);
+
+        let source = RasterStacker {
+            params: RasterStackerParams {
+                rename_bands: RenameBands::Default,
+            },
+            sources: MultipleRasterSources {
+                rasters: vec![make_raster(Some(3)), make_raster(None), make_raster(None)],
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await
+        .unwrap();
+
+        let expression = ExpressionParser::new(&expression_parameters(3), DataType::Number)
+            .unwrap()
+            .parse("expression", "if A IS NODATA { B * C } else { A * 2 }")
+            .unwrap();
+
+        let o = InitializedExpression {
+            name: CanonicOperatorName::new_unchecked(&"expression"),
+            result_descriptor: RasterResultDescriptor {
+                data_type: RasterDataType::I8,
+                spatial_reference: SpatialReference::epsg_4326().into(),
+                time: None,
+                bbox: None,
+                resolution: None,
+                bands: RasterBandDescriptors::new_single_band(),
+            },
+            source,
+            expressions: vec![expression],
+            map_no_data: true,
+            backend: ExpressionBackend::Interpreter,
+        };
+
+        let processor = o.query_processor().unwrap().get_i8().unwrap();
+
+        let ctx = MockQueryContext::new(1.into());
+        let result_stream = processor
+            .query(
+                RasterQueryRectangle {
+                    spatial_bounds: SpatialPartition2D::new_unchecked(
+                        (0., 3.).into(),
+                        (2., 0.).into(),
+                    ),
+                    time_interval: Default::default(),
+                    spatial_resolution: SpatialResolution::one(),
+                    attributes: BandSelection::first(),
+                },
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        let result: Vec<Result<RasterTile2D<i8>>> = result_stream.collect().await;
+
+        assert_eq!(result.len(), 1);
+
+        assert_eq!(
+            result[0].as_ref().unwrap().grid_array,
+            Grid2D::new([3, 2].into(), vec![2, 4, 9, 8, 10, 12],)
+                .unwrap()
+                .into()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_evaluates_more_than_8_bands_with_the_interpreter() {
+        let tile_size_in_pixels = [3, 2].into();
+        let tiling_specification = TilingSpecification {
+            origin_coordinate: [0.0, 0.0].into(),
+            tile_size_in_pixels,
+        };
+
+        let ectx = MockExecutionContext::new_with_tiling_spec(tiling_specification);
+
+        let source = RasterStacker {
+            params: RasterStackerParams {
+                rename_bands: RenameBands::Default,
+            },
+            sources: MultipleRasterSources {
+                // the stacker has at most 8 inputs, so we nest it to get 27 bands
+                rasters: (0..3)
+                    .map(|_| {
+                        RasterStacker {
+                            params: RasterStackerParams {
+                                rename_bands: RenameBands::Default,
+                            },
+                            sources: MultipleRasterSources {
+                                rasters: (0..8).map(|_| make_raster(None)).collect(),
+                            },
+                        }
+                        .boxed()
+                    })
+                    .chain((0..3).map(|_| make_raster(None)))
+                    .collect(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await
+        .unwrap();
+
+        let expression = ExpressionParser::new(&expression_parameters(27), DataType::Number)
+            .unwrap()
+            .parse("expression", "A + AA")
+            .unwrap();
+
+        let o = InitializedExpression {
+            name: CanonicOperatorName::new_unchecked(&"expression"),
+            result_descriptor: RasterResultDescriptor {
+                data_type: RasterDataType::I8,
+                spatial_reference: SpatialReference::epsg_4326().into(),
+                time: None,
+                bbox: None,
+                resolution: None,
+                bands: RasterBandDescriptors::new_single_band(),
+            },
+            source,
+            expressions: vec![expression],
+            map_no_data: false,
+            backend: ExpressionBackend::Interpreter,
+        };
+
+        let processor = o.query_processor().unwrap().get_i8().unwrap();
+
+        let ctx = MockQueryContext::new(1.into());
+        let result_stream = processor
+            .query(
+                RasterQueryRectangle {
+                    spatial_bounds: SpatialPartition2D::new_unchecked(
+                        (0., 3.).into(),
+                        (2., 0.).into(),
+                    ),
+                    time_interval: Default::default(),
+                    spatial_resolution: SpatialResolution::one(),
+                    attributes: BandSelection::first(),
+                },
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        let result: Vec<Result<RasterTile2D<i8>>> = result_stream.collect().await;
+
+        assert_eq!(result.len(), 1);
+
+        assert_eq!(
+            result[0].as_ref().unwrap().grid_array,
+            Grid2D::new([3, 2].into(), vec![2, 4, 6, 8, 10, 12],)
+                .unwrap()
+                .into()
+        );
+    }
+
+    #[tokio::test]
+    #[allow(clippy::too_many_lines)]
+    async fn it_computes_multiple_output_bands() {
+        let tile_size_in_pixels = [3, 2].into();
+        let tiling_specification = TilingSpecification {
+            origin_coordinate: [0.0, 0.0].into(),
+            tile_size_in_pixels,
+        };
+
+        let ectx = MockExecutionContext::new_with_tiling_spec(tiling_specification);
+
+        let o = Expression {
+            params: ExpressionParams {
+                expression: "let sum = near_infrared + red;
+                    [sum, red * 3, near_infrared]"
+                    .to_string(),
+                output_type: RasterDataType::I8,
+                output_band: None,
+                output_bands: vec![],
+                map_no_data: false,
+            },
+            sources: SingleRasterSource {
+                raster: RasterStacker {
+                    params: RasterStackerParams {
+                        rename_bands: RenameBands::Rename(vec![
+                            "red".to_string(),
+                            "near infrared".to_string(),
+                        ]),
+                    },
+                    sources: MultipleRasterSources {
+                        rasters: vec![make_raster(None), make_raster(None)],
+                    },
+                }
+                .boxed(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            o.result_descriptor()
+                .bands
+                .iter()
+                .map(|band| band.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["sum", "expression_1", "near infrared"]
+        );
+
+        let processor = o.query_processor().unwrap().get_i8().unwrap();
+
+        let ctx = MockQueryContext::new(1.into());
+        let query = RasterQueryRectangle {
+            spatial_bounds: SpatialPartition2D::new_unchecked((0., 3.).into(), (2., 0.).into()),
+            time_interval: Default::default(),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: BandSelection::first_n(3),
+        };
+
+        let result: Vec<RasterTile2D<i8>> = processor
+            .query(query.clone(), &ctx)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(result.len(), 3);
+
+        assert_eq!(
+            result.iter().map(|tile| tile.band).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            result[0].grid_array,
+            Grid2D::new([3, 2].into(), vec![2, 4, 6, 8, 10, 12])
+                .unwrap()
+                .into()
+        );
+        assert_eq!(
+            result[1].grid_array,
+            Grid2D::new([3, 2].into(), vec![3, 6, 9, 12, 15, 18])
+                .unwrap()
+                .into()
+        );
+        assert_eq!(
+            result[2].grid_array,
+            Grid2D::new([3, 2].into(), vec![1, 2, 3, 4, 5, 6])
+                .unwrap()
+                .into()
+        );
+
+        // only query a subset of the output bands
+        let result: Vec<RasterTile2D<i8>> = processor
+            .query(
+                RasterQueryRectangle {
+                    attributes: BandSelection::new(vec![1]).unwrap(),
+                    ..query
+                },
+                &ctx,
+            )
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].band, 0);
+        assert_eq!(
+            result[0].grid_array,
+            Grid2D::new([3, 2].into(), vec![3, 6, 9, 12, 15, 18])
+                .unwrap()
+                .into()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_uses_context_variables() {
+        let tile_size_in_pixels = [3, 2].into();
+        let tiling_specification = TilingSpecification {
+            origin_coordinate: [0.0, 0.0].into(),
+            tile_size_in_pixels,
+        };
+
+        let ectx = MockExecutionContext::new_with_tiling_spec(tiling_specification);
+
+        let o = Expression {
+            params: ExpressionParams {
+                expression: "[row * 10 + col, lat - y, A + x]".to_string(),
+                output_type: RasterDataType::F64,
+                output_band: None,
+                output_bands: vec![],
+                map_no_data: false,
+            },
+            sources: SingleRasterSource {
+                raster: make_raster(None),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await
+        .unwrap();
+
+        let processor = o.query_processor().unwrap().get_f64().unwrap();
+
+        let ctx = MockQueryContext::new(1.into());
+        let result: Vec<RasterTile2D<f64>> = processor
+            .query(
+                RasterQueryRectangle {
+                    spatial_bounds: SpatialPartition2D::new_unchecked(
+                        (0., 3.).into(),
+                        (2., 0.).into(),
+                    ),
+                    time_interval: Default::default(),
+                    spatial_resolution: SpatialResolution::one(),
+                    attributes: BandSelection::first_n(3),
+                },
+                &ctx,
+            )
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(result.len(), 3);
+
+        assert_eq!(
+            result[0].grid_array,
+            Grid2D::new([3, 2].into(), vec![-30., -29., -20., -19., -10., -9.])
+                .unwrap()
+                .into()
+        );
+        assert_eq!(
+            result[1].grid_array,
+            Grid2D::new([3, 2].into(), vec![0.; 6]).unwrap().into()
+        );
+        assert_eq!(
+            result[2].grid_array,
+            Grid2D::new([3, 2].into(), vec![1.5, 3.5, 3.5, 5.5, 5.5, 7.5])
+                .unwrap()
+                .into()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_checks_the_number_of_output_bands() {
+        let ectx = MockExecutionContext::test_default();
+
+        let result = Expression {
+            params: ExpressionParams {
+                expression: "[A, A * 2]".to_string(),
+                output_type: RasterDataType::I8,
+                output_band: None,
+                output_bands: vec![RasterBandDescriptor::new_unitless("a".into())],
+                map_no_data: false,
+            },
+            sources: SingleRasterSource {
+                raster: make_raster(None),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidNumberOfExpressionOutputBands {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_a_single_output_band_for_multiple_outputs() {
+        let ectx = MockExecutionContext::test_default();
+
+        let result = Expression {
+            params: ExpressionParams {
+                expression: "[A, A * 2]".to_string(),
+                output_type: RasterDataType::I8,
+                output_band: Some(RasterBandDescriptor::new_unitless("a".into())),
+                output_bands: vec![],
+                map_no_data: false,
+            },
+            sources: SingleRasterSource {
+                raster: make_raster(None),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidNumberOfExpressionOutputBands {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_checks_the_number_of_input_bands() {
+        let ectx = MockExecutionContext::test_default();
+
+        let result = Expression {
+            params: ExpressionParams {
+                expression: "A".to_string(),
+                output_type: RasterDataType::I8,
+                output_band: None,
+                output_bands: vec![],
+                map_no_data: false,
+            },
+            sources: SingleRasterSource {
+                raster: RasterStacker {
+                    params: RasterStackerParams {
+                        rename_bands: RenameBands::Default,
+                    },
+                    sources: MultipleRasterSources {
+                        rasters: vec![
+                            make_raster(None),
+                            RasterStacker {
+                                params: RasterStackerParams {
+                                    rename_bands: RenameBands::Default,
+                                },
+                                sources: MultipleRasterSources {
+                                    rasters: (0..MAX_COMPILED_INPUT_BANDS)
+                                        .map(|_| make_raster(None))
+                                        .collect(),
+                                },
+                            }
+                            .boxed(),
+                        ],
+                    },
+                }
+                .boxed(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &ectx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidNumberOfExpressionInputBands { found: 9, max: 8 })
+        ));
+    }
+
+    #[test]
+    fn it_names_parameters_like_spreadsheet_columns() {
+        assert_eq!(index_to_parameter(0), "A");
+        assert_eq!(index_to_parameter(25), "Z");
+        assert_eq!(index_to_parameter(26), "AA");
+        assert_eq!(index_to_parameter(51), "AZ");
+        assert_eq!(index_to_parameter(52), "BA");
+        assert_eq!(index_to_parameter(701), "ZZ");
+        assert_eq!(index_to_parameter(702), "AAA");
+    }
+
+    #[test]
+    fn it_converts_band_names_to_variables() {
+        assert_eq!(band_name_to_variable("red"), Some("red".to_string()));
+        assert_eq!(
+            band_name_to_variable("near infrared (1)"),
+            Some("near_infrared__1_".to_string())
+        );
+        assert_eq!(band_name_to_variable("1st band"), None);
+        assert_eq!(band_name_to_variable("_band"), None);
+    }
+
     fn make_raster(no_data_value: Option<i8>) -> Box<dyn RasterOperator> {
         make_raster_with_cache_hint(no_data_value, CacheHint::no_cache())
     }
@@ -830,6 +1473,7 @@ mod tests {
                 expression: "min(A * pi(), 10)".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -901,6 +1545,7 @@ mod tests {
                 expression: "A + B".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -974,6 +1619,7 @@ mod tests {
                 expression: "A + B".to_string(),
                 output_type: RasterDataType::I8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -1,29 +1,45 @@
-use super::RasterExpressionError;
+use super::{
+    raster_context::{ContextValues, TileContext},
+    ExpressionProgram, RasterExpressionError,
+};
 use crate::{
     engine::{BoxRasterQueryProcessor, QueryContext, QueryProcessor, RasterResultDescriptor},
     util::Result,
 };
 use async_trait::async_trait;
-use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
 use geoengine_datatypes::{
     primitives::{
         BandSelection, CacheHint, RasterQueryRectangle, SpatialPartition2D, TimeInterval,
     },
     raster::{
         ConvertDataType, FromIndexFnParallel, GeoTransform, GridIdx2D, GridIndexAccess,
-        GridOrEmpty, GridOrEmpty2D, GridShape2D, GridShapeAccess, MapElementsParallel, Pixel,
-        RasterTile2D,
+        GridOrEmpty, GridOrEmpty2D, GridShape2D, GridShapeAccess, Pixel, RasterTile2D,
+        TileInformation,
     },
 };
-use geoengine_expression::LinkedExpression;
-use libloading::Symbol;
+use geoengine_expression::{InterpretedExpression, Value};
 use num_traits::AsPrimitive;
 use std::{marker::PhantomData, sync::Arc};
 
+/// The maximum number of input bands of compiled expressions.
+/// They are evaluated with an [`ExpressionInput`] for a fixed number of bands.
+pub const MAX_COMPILED_INPUT_BANDS: usize = 8;
+
 pub struct ExpressionInput<const N: usize> {
     pub raster: BoxRasterQueryProcessor<f64>,
 }
 
+/// Input bands whose number is only known at runtime.
+/// Only interpreted expressions can be evaluated for them.
+pub struct DynamicExpressionInput {
+    pub raster: BoxRasterQueryProcessor<f64>,
+    pub num_bands: u32,
+}
+
 pub struct ExpressionQueryProcessor<TO, Sources>
 where
     TO: Pixel,
@@ -31,8 +47,11 @@ where
     pub sources: Sources,
     pub result_descriptor: RasterResultDescriptor,
     pub phantom_data: PhantomData<TO>,
-    pub program: Arc<LinkedExpression>,
+    /// One program per output band
+    pub programs: Arc<Vec<ExpressionProgram>>,
     pub map_no_data: bool,
+    /// Whether the expressions use the `lon` or `lat` context variables
+    pub with_lon_lat: bool,
 }
 
 impl<TO, Sources> ExpressionQueryProcessor<TO, Sources>
@@ -40,17 +59,19 @@ where
     TO: Pixel,
 {
     pub fn new(
-        program: LinkedExpression,
+        programs: Vec<ExpressionProgram>,
         sources: Sources,
         result_descriptor: RasterResultDescriptor,
         map_no_data: bool,
+        with_lon_lat: bool,
     ) -> Self {
         Self {
             sources,
             result_descriptor,
-            program: Arc::new(program),
+            programs: Arc::new(programs),
             phantom_data: PhantomData,
             map_no_data,
+            with_lon_lat,
         }
     }
 }
@@ -71,49 +92,97 @@ where
         query: RasterQueryRectangle,
         ctx: &'b dyn QueryContext,
     ) -> Result<BoxStream<'b, Result<Self::Output>>> {
-        // rewrite query to request all input bands from the source. They are all combined in the single output band by means of the expression.
+        // rewrite query to request all input bands from the source. They are all combined in the output bands by means of the expressions.
         let source_query = RasterQueryRectangle {
             spatial_bounds: query.spatial_bounds,
             time_interval: query.time_interval,
             spatial_resolution: query.spatial_resolution,
-            attributes: BandSelection::first_n(Tuple::num_bands()),
+            attributes: BandSelection::first_n(self.sources.num_bands()),
         };
 
-        let stream =
-            self.sources
-                .zip_bands(source_query, ctx)
-                .await?
-                .and_then(move |rasters| async move {
+        // the output bands are computed for each spatial tile at once
+        let output_bands = query.attributes.as_vec();
+
+        let stream = self
+            .sources
+            .zip_bands(source_query, ctx)
+            .await?
+            .and_then(move |rasters| {
+                let output_bands = output_bands.clone();
+
+                async move {
                     if Tuple::all_empty(&rasters) {
-                        return Ok(Tuple::empty_raster(&rasters));
+                        let empty_raster = Tuple::empty_raster(&rasters);
+
+                        return Ok((0..output_bands.len())
+                            .map(|band_idx| {
+                                let mut tile = empty_raster.clone();
+                                tile.band = band_idx as u32;
+                                tile
+                            })
+                            .collect::<Vec<_>>());
                     }
 
                     let (
                         out_time,
                         out_tile_position,
                         out_global_geo_transform,
-                        _output_grid_shape,
+                        output_grid_shape,
                         cache_hint,
                     ) = Tuple::metadata(&rasters);
 
-                    let program = self.program.clone();
+                    let programs = self.programs.clone();
                     let map_no_data = self.map_no_data;
+                    let spatial_reference = self.result_descriptor.spatial_reference;
+                    let with_lon_lat = self.with_lon_lat;
 
-                    let out = crate::util::spawn_blocking_with_thread_pool(
+                    let outs = crate::util::spawn_blocking_with_thread_pool(
                         ctx.thread_pool().clone(),
-                        move || Tuple::compute_expression(rasters, &program, map_no_data),
+                        move || {
+                            let context = TileContext::new(
+                                TileInformation::new(
+                                    out_tile_position,
+                                    output_grid_shape,
+                                    out_global_geo_transform,
+                                ),
+                                out_time,
+                                spatial_reference,
+                                with_lon_lat,
+                            );
+
+                            output_bands
+                                .iter()
+                                .map(|&band| {
+                                    Tuple::compute_expression(
+                                        &rasters,
+                                        &programs[band as usize],
+                                        map_no_data,
+                                        &context,
+                                    )
+                                })
+                                .collect::<Result<Vec<_>>>()
+                        },
                     )
                     .await??;
 
-                    Ok(RasterTile2D::new(
-                        out_time,
-                        out_tile_position,
-                        0,
-                        out_global_geo_transform,
-                        out,
-                        cache_hint,
-                    ))
-                });
+                    Ok(outs
+                        .into_iter()
+                        .enumerate()
+                        .map(|(band_idx, out)| {
+                            RasterTile2D::new(
+                                out_time,
+                                out_tile_position,
+                                band_idx as u32,
+                                out_global_geo_transform,
+                                out,
+                                cache_hint,
+                            )
+                        })
+                        .collect())
+                }
+            })
+            .map_ok(|tiles| stream::iter(tiles.into_iter().map(Ok)))
+            .try_flatten();
 
         Ok(stream.boxed())
     }
@@ -148,12 +217,94 @@ trait ExpressionTupleProcessor<TO: Pixel>: Send + Sync {
     );
 
     fn compute_expression(
-        tuple: Self::Tuple,
-        program: &LinkedExpression,
+        tuple: &Self::Tuple,
+        program: &ExpressionProgram,
         map_no_data: bool,
+        context: &TileContext,
     ) -> Result<GridOrEmpty2D<TO>>;
 
-    fn num_bands() -> u32;
+    fn num_bands(&self) -> u32;
+}
+
+/// The signature of a compiled expression with the input bands `$x` followed by the context variables.
+macro_rules! expression_fn_type {
+    (@number $x:tt) => {
+        Option<f64>
+    };
+
+    ( $( $x:tt ),* ) => {
+        fn(
+            $( expression_fn_type!(@number $x), )*
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+        ) -> Option<f64>
+    };
+}
+
+type Function1 = expression_fn_type!(0);
+type Function2 = expression_fn_type!(0, 1);
+type Function3 = expression_fn_type!(0, 1, 2);
+type Function4 = expression_fn_type!(0, 1, 2, 3);
+type Function5 = expression_fn_type!(0, 1, 2, 3, 4);
+type Function6 = expression_fn_type!(0, 1, 2, 3, 4, 5);
+type Function7 = expression_fn_type!(0, 1, 2, 3, 4, 5, 6);
+type Function8 = expression_fn_type!(0, 1, 2, 3, 4, 5, 6, 7);
+
+/// Calls a compiled expression with the pixel values followed by the context values.
+macro_rules! call_compiled {
+    ( $function:expr, [ $( $pixel:expr ),* ], $context:expr ) => {{
+        let [x, y, lon, lat, row, col, time_start, time_end] = $context;
+        $function($( $pixel, )* x, y, lon, lat, row, col, time_start, time_end)
+    }};
+}
+
+/// An expression that is evaluated with the values of `N` input bands and the context values of a pixel
+type PixelExpression<'p, const N: usize> =
+    Box<dyn Fn([Option<f64>; N], ContextValues) -> Option<f64> + Sync + 'p>;
+
+/// Evaluates an interpreted expression with the pixel values followed by the context values.
+fn interpret<const N: usize>(
+    program: &InterpretedExpression,
+    pixels: [Option<f64>; N],
+    context: ContextValues,
+) -> Option<f64> {
+    program
+        .evaluate(pixels.into_iter().chain(context).map(Value::Number))
+        .as_number()
+}
+
+/// Evaluates the expression for all pixels of a tile.
+/// `pixel_values` returns the values of the input bands for a linear pixel index.
+fn evaluate_pixels<TO, const N: usize>(
+    grid_shape: GridShape2D,
+    expression: PixelExpression<'_, N>,
+    pixel_values: impl Fn(usize) -> [Option<f64>; N] + Sync,
+    context: &TileContext,
+    map_no_data: bool,
+) -> GridOrEmpty2D<TO>
+where
+    TO: Pixel,
+    f64: AsPrimitive<TO>,
+{
+    let map_fn = |lin_idx: usize| {
+        let pixels = pixel_values(lin_idx);
+
+        if !map_no_data && pixels.iter().any(Option::is_none) {
+            return None;
+        }
+
+        let result = expression(pixels, context.values(lin_idx));
+
+        result.map(TO::from_)
+    };
+
+    GridOrEmpty::from_index_fn_parallel(&grid_shape, map_fn)
 }
 
 #[async_trait]
@@ -206,34 +357,40 @@ where
 
     #[inline]
     fn compute_expression(
-        raster: Self::Tuple,
-        program: &LinkedExpression,
+        raster: &Self::Tuple,
+        program: &ExpressionProgram,
         map_no_data: bool,
+        context: &TileContext,
     ) -> Result<GridOrEmpty2D<TO>> {
-        let expression = unsafe {
-            // we have to "trust" that the function has the signature we expect
-            program
-                .function_1::<Option<f64>>()
-                .map_err(RasterExpressionError::from)?
-        };
-
-        let map_fn = |in_value: Option<f64>| {
-            // TODO: could be a |in_value: T1| if map no data is false!
-            if !map_no_data && in_value.is_none() {
-                return None;
+        let expression: PixelExpression<'_, 1> = match program {
+            ExpressionProgram::Compiled(program) => {
+                let function = unsafe {
+                    // we have to "trust" that the function has the signature we expect
+                    program
+                        .function_nary::<Function1>()
+                        .map_err(RasterExpressionError::from)?
+                };
+                Box::new(move |[a]: [Option<f64>; 1], context: ContextValues| {
+                    call_compiled!(function, [a], context)
+                })
+            }
+            ExpressionProgram::Interpreted(program) => {
+                Box::new(|pixels: [Option<f64>; 1], context: ContextValues| {
+                    interpret(program, pixels, context)
+                })
             }
-
-            let result = expression(in_value);
-
-            result.map(TO::from_)
         };
 
-        let res = raster.grid_array.map_elements_parallel(map_fn);
-
-        Result::Ok(res)
+        Ok(evaluate_pixels(
+            raster.grid_shape(),
+            expression,
+            |lin_idx| [raster.get_at_grid_index_unchecked(lin_idx)],
+            context,
+            map_no_data,
+        ))
     }
 
-    fn num_bands() -> u32 {
+    fn num_bands(&self) -> u32 {
         1
     }
 }
@@ -309,66 +466,49 @@ where
 
     #[inline]
     fn compute_expression(
-        rasters: Self::Tuple,
-        program: &LinkedExpression,
+        rasters: &Self::Tuple,
+        program: &ExpressionProgram,
         map_no_data: bool,
+        context: &TileContext,
     ) -> Result<GridOrEmpty2D<TO>> {
-        let expression = unsafe {
-            // we have to "trust" that the function has the signature we expect
-            program
-                .function_2::<Option<f64>, Option<f64>>()
-                .map_err(RasterExpressionError::from)?
-        };
-
-        let map_fn = |lin_idx: usize| {
-            let t0_value = rasters.0.get_at_grid_index_unchecked(lin_idx);
-            let t1_value = rasters.1.get_at_grid_index_unchecked(lin_idx);
-
-            if !map_no_data && (t0_value.is_none() || t1_value.is_none()) {
-                return None;
+        let expression: PixelExpression<'_, 2> = match program {
+            ExpressionProgram::Compiled(program) => {
+                let function = unsafe {
+                    // we have to "trust" that the function has the signature we expect
+                    program
+                        .function_nary::<Function2>()
+                        .map_err(RasterExpressionError::from)?
+                };
+                Box::new(move |[a, b]: [Option<f64>; 2], context: ContextValues| {
+                    call_compiled!(function, [a, b], context)
+                })
+            }
+            ExpressionProgram::Interpreted(program) => {
+                Box::new(|pixels: [Option<f64>; 2], context: ContextValues| {
+                    interpret(program, pixels, context)
+                })
             }
-
-            let result = expression(t0_value, t1_value);
-
-            result.map(TO::from_)
         };
 
-        let grid_shape = rasters.0.grid_shape();
-        let out = GridOrEmpty::from_index_fn_parallel(&grid_shape, map_fn);
-
-        Result::Ok(out)
+        Ok(evaluate_pixels(
+            rasters.0.grid_shape(),
+            expression,
+            |lin_idx| {
+                [
+                    rasters.0.get_at_grid_index_unchecked(lin_idx),
+                    rasters.1.get_at_grid_index_unchecked(lin_idx),
+                ]
+            },
+            context,
+            map_no_data,
+        ))
     }
 
-    fn num_bands() -> u32 {
+    fn num_bands(&self) -> u32 {
         2
     }
 }
 
-type Function3 = fn(Option<f64>, Option<f64>, Option<f64>) -> Option<f64>;
-type Function4 = fn(Option<f64>, Option<f64>, Option<f64>, Option<f64>) -> Option<f64>;
-type Function5 = fn(Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>) -> Option<f64>;
-type Function6 =
-    fn(Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>) -> Option<f64>;
-type Function7 = fn(
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-) -> Option<f64>;
-type Function8 = fn(
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-    Option<f64>,
-) -> Option<f64>;
-
 macro_rules! impl_expression_tuple_processor {
     ( $i:tt => $( $x:tt ),+ ) => {
         paste::paste! {
@@ -380,15 +520,13 @@ macro_rules! impl_expression_tuple_processor {
                 |
                 $( [< pixel_ $x >] ),*
                 |
-                $( [< is_nodata_ $x >] ),*
-                |
                 [< Function $i >]
             );
         }
     };
 
     // We have `0, 1, 2, …` and `T0, T1, T2, …`
-    (@inner $N:tt | $( $I:tt ),+ | $( $PIXEL:tt ),+ | $( $IS_NODATA:tt ),+ | $FN_T:ty ) => {
+    (@inner $N:tt | $( $I:tt ),+ | $( $PIXEL:tt ),+ | $FN_T:ty ) => {
         #[async_trait]
         impl<TO> ExpressionTupleProcessor<TO> for ExpressionInput<$N>
         where
@@ -457,41 +595,36 @@ macro_rules! impl_expression_tuple_processor {
             }
 
             fn compute_expression(
-                rasters: Self::Tuple,
-                program: &LinkedExpression,
+                rasters: &Self::Tuple,
+                program: &ExpressionProgram,
                 map_no_data: bool,
+                context: &TileContext,
             ) -> Result<GridOrEmpty2D<TO>> {
-                let expression: Symbol<$FN_T> = unsafe {
-                    // we have to "trust" that the function has the signature we expect
-                    program.function_nary().map_err(RasterExpressionError::from)?
-                };
-
-                let map_fn = |lin_idx: usize| {
-                    $(
-                        let $PIXEL = rasters[$I].get_at_grid_index_unchecked(lin_idx);
-                        let $IS_NODATA = $PIXEL.is_none();
-                    )*
-
-                    if !map_no_data && ( $($IS_NODATA)||* ) {
-                        return None;
+                let expression: PixelExpression<'_, $N> = match program {
+                    ExpressionProgram::Compiled(program) => {
+                        let function = unsafe {
+                            // we have to "trust" that the function has the signature we expect
+                            program.function_nary::<$FN_T>().map_err(RasterExpressionError::from)?
+                        };
+                        Box::new(move |[$( $PIXEL ),*]: [Option<f64>; $N], context: ContextValues| {
+                            call_compiled!(function, [$( $PIXEL ),*], context)
+                        })
                     }
-
-                    let result = expression(
-                        $(
-                            $PIXEL
-                        ),*
-                    );
-
-                    result.map(TO::from_)
+                    ExpressionProgram::Interpreted(program) => Box::new(|pixels: [Option<f64>; $N], context: ContextValues| {
+                        interpret(program, pixels, context)
+                    }),
                 };
 
-                let grid_shape = rasters[0].grid_shape();
-                let out = GridOrEmpty::from_index_fn_parallel(&grid_shape, map_fn);
-
-                Result::Ok(out)
+                Ok(evaluate_pixels(
+                    rasters[0].grid_shape(),
+                    expression,
+                    |lin_idx| [$( rasters[$I].get_at_grid_index_unchecked(lin_idx) ),*],
+                    context,
+                    map_no_data,
+                ))
             }
 
-            fn num_bands() -> u32 {
+            fn num_bands(&self) -> u32 {
                 $N
             }
         }
@@ -509,3 +642,113 @@ impl_expression_tuple_processor!(5 => 0, 1, 2, 3, 4);
 impl_expression_tuple_processor!(6 => 0, 1, 2, 3, 4, 5);
 impl_expression_tuple_processor!(7 => 0, 1, 2, 3, 4, 5, 6);
 impl_expression_tuple_processor!(8 => 0, 1, 2, 3, 4, 5, 6, 7);
+
+#[async_trait]
+impl<TO> ExpressionTupleProcessor<TO> for DynamicExpressionInput
+where
+    TO: Pixel,
+    f64: AsPrimitive<TO>,
+{
+    type Tuple = Vec<RasterTile2D<f64>>;
+
+    #[inline]
+    async fn zip_bands<'a>(
+        &'a self,
+        query: RasterQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Tuple>>> {
+        let num_bands = self.num_bands as usize;
+
+        // chunk up the stream to get all bands for a spatial tile at once
+        let stream = self
+            .raster
+            .query(query, ctx)
+            .await?
+            .chunks(num_bands)
+            .map(move |chunk| {
+                if chunk.len() != num_bands {
+                    // if there are not exactly N tiles, it should mean the last tile was an error and the chunker ended prematurely
+                    if let Some(Err(e)) = chunk.into_iter().last() {
+                        return Err(e);
+                    }
+                    // if there is no error, the source did not produce all bands, which likely means a bug in an operator
+                    unreachable!("the source did not produce all bands");
+                }
+
+                chunk.into_iter().collect::<Result<Vec<_>>>()
+            });
+
+        Ok(stream.boxed())
+    }
+
+    #[inline]
+    fn all_empty(tuple: &Self::Tuple) -> bool {
+        tuple.iter().all(|raster| raster.grid_array.is_empty())
+    }
+
+    #[inline]
+    fn empty_raster(tuple: &Self::Tuple) -> RasterTile2D<TO> {
+        tuple[0].clone().convert_data_type()
+    }
+
+    #[inline]
+    fn metadata(
+        tuple: &Self::Tuple,
+    ) -> (
+        TimeInterval,
+        GridIdx2D,
+        GeoTransform,
+        GridShape2D,
+        CacheHint,
+    ) {
+        let raster = &tuple[0];
+
+        (
+            raster.time,
+            raster.tile_position,
+            raster.global_geo_transform,
+            raster.grid_shape(),
+            tuple.iter().fold(CacheHint::max_duration(), |acc, r| {
+                acc.merged(&r.cache_hint)
+            }),
+        )
+    }
+
+    fn compute_expression(
+        rasters: &Self::Tuple,
+        program: &ExpressionProgram,
+        map_no_data: bool,
+        context: &TileContext,
+    ) -> Result<GridOrEmpty2D<TO>> {
+        let ExpressionProgram::Interpreted(program) = program else {
+            return Err(crate::error::Error::InvalidNumberOfExpressionInputBands {
+                found: rasters.len(),
+                max: MAX_COMPILED_INPUT_BANDS,
+            });
+        };
+
+        let map_fn = |lin_idx: usize| {
+            let pixels = rasters
+                .iter()
+                .map(|raster| raster.get_at_grid_index_unchecked(lin_idx));
+
+            if !map_no_data && pixels.clone().any(|pixel| pixel.is_none()) {
+                return None;
+            }
+
+            program
+                .evaluate(pixels.chain(context.values(lin_idx)).map(Value::Number))
+                .as_number()
+                .map(TO::from_)
+        };
+
+        Ok(GridOrEmpty::from_index_fn_parallel(
+            &rasters[0].grid_shape(),
+            map_fn,
+        ))
+    }
+
+    fn num_bands(&self) -> u32 {
+        self.num_bands
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
-    error::vector as error, get_expression_dependencies, AsExpressionGeo, FromExpressionGeo,
-    VectorExpressionError,
+    error::vector as error, get_expression_backend, get_expression_dependencies, AsExpressionGeo,
+    ExpressionBackend, ExpressionProgram, FromExpressionGeo, VectorExpressionError,
 };
 use crate::{
     engine::{
@@ -15,8 +15,8 @@ use async_trait::async_trait;
 use futures::stream::BoxStream;
 use futures::StreamExt;
 use geoengine_datatypes::primitives::{
-    FeatureData, FeatureDataRef, FeatureDataType, FloatOptionsParIter, Geometry, Measurement,
-    MultiLineString, MultiPoint, MultiPolygon, VectorQueryRectangle,
+    FeatureData, FeatureDataRef, FeatureDataType, FeatureDataValue, FloatOptionsParIter, Geometry,
+    Measurement, MultiLineString, MultiPoint, MultiPolygon, TimeInstance, VectorQueryRectangle,
 };
 use geoengine_datatypes::util::arrow::ArrowTyped;
 use geoengine_datatypes::{
@@ -28,8 +28,8 @@ use geoengine_datatypes::{
     primitives::NoGeometry,
 };
 use geoengine_expression::{
-    is_allowed_variable_name, DataType, ExpressionParser, LinkedExpression,
-    Parameter as ExpressionParameter,
+    is_allowed_variable_name, DataType, ExpressionParser, InterpretedExpression, LinkedExpression,
+    Parameter as ExpressionParameter, Value, ValueType,
 };
 use rayon::iter::{
     FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
@@ -71,6 +71,12 @@ pub struct VectorExpressionParams {
     /// The type and name of the new column.
     pub output_column: OutputColumn,
 
+    /// The data type of the new column if the output is a column.
+    /// Numeric columns are computed from numbers and are no data if the number does not fit, e.g., for `Int`s.
+    /// The default is [`FeatureDataType::Float`].
+    #[serde(default = "output_default_column_type")]
+    pub output_column_type: FeatureDataType,
+
     /// The expression will always include the geometry column.
     /// Thus, it is necessary to specify the variable name of the geometry column.
     /// The default is `geom`.
@@ -87,14 +93,17 @@ fn geometry_default_column_name() -> String {
     "geom".into()
 }
 
+fn output_default_column_type() -> FeatureDataType {
+    FeatureDataType::Float
+}
+
 /// Specify the output of the expression.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "type", content = "value", rename_all = "camelCase")]
 pub enum OutputColumn {
     /// The expression will override the current geometry
     Geometry(GeoVectorDataType),
-    /// The expression will append a new `Float` column
-    // TODO: allow more types than `Float`s
+    /// The expression will append a new column of the `output_column_type`
     Column(String),
 }
 
@@ -102,9 +111,10 @@ struct InitializedVectorExpression {
     name: CanonicOperatorName,
     result_descriptor: VectorResultDescriptor,
     features: Box<dyn InitializedVectorOperator>,
-    expression: Arc<LinkedExpression>,
+    expression: Arc<ExpressionProgram>,
     input_columns: Vec<String>,
     output_column: OutputColumn,
+    output_column_type: FeatureDataType,
 }
 
 #[typetag::serde]
@@ -130,7 +140,8 @@ impl VectorOperator for VectorExpression {
         // we can reuse the result descriptor, because we only add a column later on
         let mut result_descriptor = initialized_source.vector.result_descriptor().clone();
 
-        check_input_column_validity(&result_descriptor.columns, &self.params.input_columns)?;
+        let input_types =
+            check_input_column_validity(&result_descriptor.columns, &self.params.input_columns)?;
         check_output_column_validity(&self.params.output_column)?;
 
         let expression_geom_input_type = result_descriptor.data_type;
@@ -147,14 +158,15 @@ impl VectorOperator for VectorExpression {
                 insert_new_column(
                     &mut result_descriptor.columns,
                     output_column_name.clone(),
+                    self.params.output_column_type,
                     self.params.output_measurement,
                 )?;
-                DataType::Number
+                expression_data_type(self.params.output_column_type)
             }
         };
 
-        let mut expression_input_names = Vec::with_capacity(self.params.input_columns.len());
-        for input_column in &self.params.input_columns {
+        let mut expression_inputs = Vec::with_capacity(self.params.input_columns.len());
+        for (input_column, input_type) in self.params.input_columns.iter().zip(input_types) {
             let variable_name = canonicalize_column_name(input_column);
 
             if !is_allowed_variable_name(&variable_name) {
@@ -163,20 +175,22 @@ impl VectorOperator for VectorExpression {
                 })?;
             }
 
-            expression_input_names.push(variable_name);
+            expression_inputs.push((variable_name, input_type));
         }
 
         let expression = {
             let expression_code = self.params.expression.clone();
             let geometry_column_name = self.params.geometry_column_name.clone();
+            let backend = get_expression_backend();
 
             crate::util::spawn_blocking(move || {
                 compile_expression(
                     &expression_code,
                     geometry_column_name,
                     expression_geom_input_type,
-                    &expression_input_names,
+                    &expression_inputs,
                     expression_output_type,
+                    backend,
                 )
                 .map(Arc::new)
             })
@@ -191,6 +205,7 @@ impl VectorOperator for VectorExpression {
             expression,
             input_columns: self.params.input_columns,
             output_column: self.params.output_column,
+            output_column_type: self.params.output_column_type,
         };
 
         Ok(initialized_operator.boxed())
@@ -199,10 +214,13 @@ impl VectorOperator for VectorExpression {
     span_fn!(VectorExpression);
 }
 
+/// Checks the input columns and returns their types in the expression.
 fn check_input_column_validity(
     columns: &HashMap<String, VectorColumnInfo>,
     input_columns: &[String],
-) -> Result<(), VectorExpressionError> {
+) -> Result<Vec<DataType>, VectorExpressionError> {
+    let mut input_types = Vec::with_capacity(input_columns.len());
+
     for input_column in input_columns {
         if input_column.contains(|c: char| !c.is_alphanumeric()) {
             Err(VectorExpressionError::ColumnNameContainsSpecialCharacters {
@@ -216,15 +234,22 @@ fn check_input_column_validity(
             });
         };
 
-        match column_info.data_type {
-            FeatureDataType::Float | FeatureDataType::Int => {}
-            _ => Err(VectorExpressionError::InputColumnNotNumeric {
-                name: input_column.clone(),
-            })?,
-        }
+        input_types.push(expression_data_type(column_info.data_type));
     }
 
-    Ok(())
+    Ok(input_types)
+}
+
+/// Categories and integers are numbers in the expression.
+fn expression_data_type(feature_data_type: FeatureDataType) -> DataType {
+    match feature_data_type {
+        FeatureDataType::Category | FeatureDataType::Int | FeatureDataType::Float => {
+            DataType::Number
+        }
+        FeatureDataType::Text => DataType::Text,
+        FeatureDataType::Bool => DataType::Bool,
+        FeatureDataType::DateTime => DataType::DateTime,
+    }
 }
 
 fn check_output_column_validity(output_column: &OutputColumn) -> Result<(), VectorExpressionError> {
@@ -264,12 +289,13 @@ fn canonicalize_column_name(name: &str) -> String {
 fn insert_new_column(
     columns: &mut HashMap<String, VectorColumnInfo>,
     name: String,
+    data_type: FeatureDataType,
     measurement: Measurement,
 ) -> Result<(), VectorExpressionError> {
     let output_column_collision = columns.insert(
         name.clone(),
         VectorColumnInfo {
-            data_type: FeatureDataType::Float,
+            data_type,
             measurement,
         },
     );
@@ -281,13 +307,18 @@ fn insert_new_column(
     Ok(())
 }
 
+/// Compiles the expression for the `backend`.
+///
+/// The compiled expression only gets numbers as inputs.
+/// Thus, expressions with `Text` or `Bool` inputs are always interpreted.
 fn compile_expression(
     expression_code: &str,
     geom_name: String,
     geom_type: VectorDataType,
-    parameters: &[String],
+    parameters: &[(String, DataType)],
     output_type: DataType,
-) -> Result<LinkedExpression, VectorExpressionError> {
+    backend: ExpressionBackend,
+) -> Result<ExpressionProgram, VectorExpressionError> {
     let geom_parameter = match geom_type {
         VectorDataType::Data | VectorDataType::MultiPoint => {
             ExpressionParameter::MultiPoint(geom_name.into())
@@ -297,20 +328,41 @@ fn compile_expression(
     };
     let mut expression_parameters = Vec::with_capacity(parameters.len() + 1);
     expression_parameters.push(geom_parameter);
-    expression_parameters.extend(
-        parameters
-            .iter()
-            .map(|p| ExpressionParameter::Number(p.into())),
-    );
+    expression_parameters.extend(parameters.iter().map(|(name, data_type)| {
+        let name = name.into();
+        match data_type {
+            DataType::Text => ExpressionParameter::Text(name),
+            DataType::Bool => ExpressionParameter::Bool(name),
+            DataType::DateTime => ExpressionParameter::DateTime(name),
+            _ => ExpressionParameter::Number(name),
+        }
+    }));
     let expression = ExpressionParser::new(&expression_parameters, output_type)?
         .parse(EXPRESSION_MAIN_NAME, expression_code)?;
 
-    let expression_dependencies = get_expression_dependencies().context(error::Dependencies)?;
+    let has_non_numeric_inputs = parameters
+        .iter()
+        .any(|(_, data_type)| matches!(data_type, DataType::Text | DataType::Bool));
+    let backend = if has_non_numeric_inputs {
+        ExpressionBackend::Interpreter
+    } else {
+        backend
+    };
 
-    Ok(LinkedExpression::from_ast(
-        &expression,
-        expression_dependencies,
-    )?)
+    Ok(match backend {
+        ExpressionBackend::Compiled => {
+            let expression_dependencies =
+                get_expression_dependencies().context(error::Dependencies)?;
+
+            ExpressionProgram::Compiled(LinkedExpression::from_ast(
+                &expression,
+                expression_dependencies,
+            )?)
+        }
+        ExpressionBackend::Interpreter => {
+            ExpressionProgram::Interpreted(InterpretedExpression::from_ast(&expression)?)
+        }
+    })
 }
 
 impl InitializedVectorExpression {
@@ -335,6 +387,7 @@ impl InitializedVectorExpression {
             expression: self.expression.clone(),
             input_columns: self.input_columns.clone(),
             output_column,
+            output_column_type: self.output_column_type,
         }
         .boxed()
         .into()
@@ -372,7 +425,7 @@ impl InitializedVectorExpression {
     }
 
     #[inline]
-    fn dispatch_float_column_output(
+    fn dispatch_column_output(
         &self,
         source_processor: TypedVectorQueryProcessor,
         output_column: String,
@@ -452,7 +505,7 @@ impl InitializedVectorOperator for InitializedVectorExpression {
                 self.dispatch_geometry_output(source_processor, vector_data_type)
             }
             OutputColumn::Column(output_column) => {
-                self.dispatch_float_column_output(source_processor, output_column)
+                self.dispatch_column_output(source_processor, output_column)
             }
         })
     }
@@ -471,9 +524,10 @@ where
 {
     source: Q,
     result_descriptor: VectorResultDescriptor,
-    expression: Arc<LinkedExpression>,
+    expression: Arc<ExpressionProgram>,
     input_columns: Vec<String>,
     output_column: String,
+    output_column_type: FeatureDataType,
 }
 
 /// A processor that evaluates an expression on the columns of a `FeatureCollection`.
@@ -486,7 +540,7 @@ where
 {
     source: Q,
     result_descriptor: VectorResultDescriptor,
-    expression: Arc<LinkedExpression>,
+    expression: Arc<ExpressionProgram>,
     input_columns: Vec<String>,
     _out: PhantomData<GOut>,
 }
@@ -518,18 +572,63 @@ where
             let collection = collection?;
             let input_columns = self.input_columns.clone();
             let output_column = self.output_column.clone();
+            let output_column_type = self.output_column_type;
             let expression = self.expression.clone();
 
             crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
-                let result: Vec<Option<f64>> = call_expression_function(
-                    &expression,
-                    &collection,
-                    &input_columns,
-                    std::convert::identity,
-                )?;
+                let result = match output_column_type {
+                    FeatureDataType::Float => FeatureData::NullableFloat(call_expression_function(
+                        &expression,
+                        &collection,
+                        &input_columns,
+                        std::convert::identity,
+                    )?),
+                    FeatureDataType::Int => FeatureData::NullableInt(call_expression_function(
+                        &expression,
+                        &collection,
+                        &input_columns,
+                        |number: Option<f64>| number.and_then(float_to_int),
+                    )?),
+                    FeatureDataType::Category => {
+                        FeatureData::NullableCategory(call_expression_function(
+                            &expression,
+                            &collection,
+                            &input_columns,
+                            |number: Option<f64>| {
+                                number
+                                    .and_then(float_to_int)
+                                    .and_then(|number| u8::try_from(number).ok())
+                            },
+                        )?)
+                    }
+                    FeatureDataType::Text => FeatureData::NullableText(call_expression_function(
+                        &expression,
+                        &collection,
+                        &input_columns,
+                        std::convert::identity,
+                    )?),
+                    FeatureDataType::Bool => FeatureData::NullableBool(call_expression_function(
+                        &expression,
+                        &collection,
+                        &input_columns,
+                        std::convert::identity,
+                    )?),
+                    FeatureDataType::DateTime => {
+                        FeatureData::NullableDateTime(call_expression_function(
+                            &expression,
+                            &collection,
+                            &input_columns,
+                            |millis: Option<f64>| {
+                                millis
+                                    .and_then(float_to_int)
+                                    .and_then(|millis| TimeInstance::from_millis(millis).ok())
+                            },
+                        )?)
+                    }
+                };
 
                 Ok(collection
-                    .add_column(&output_column, FeatureData::NullableFloat(result))
+                    .add_column(&output_column, result)
                     .context(error::AddColumn {
                         name: output_column,
                     })?)
@@ -612,8 +711,34 @@ where
     }
 }
 
+/// Converts a number to an integer if it is finite and in range. Decimals are truncated.
+fn float_to_int(number: f64) -> Option<i64> {
+    (number.is_finite() && number >= i64::MIN as f64 && number < i64::MAX as f64)
+        .then_some(number as i64)
+}
+
+/// Converts a column value to an expression input. Time instances are milliseconds since the Unix epoch.
+fn expression_value(value: FeatureDataValue) -> Value {
+    match value {
+        FeatureDataValue::Category(value) => Value::Number(Some(value.into())),
+        FeatureDataValue::NullableCategory(value) => Value::Number(value.map(Into::into)),
+        FeatureDataValue::Int(value) => Value::Number(Some(value as f64)),
+        FeatureDataValue::NullableInt(value) => Value::Number(value.map(|value| value as f64)),
+        FeatureDataValue::Float(value) => Value::Number(Some(value)),
+        FeatureDataValue::NullableFloat(value) => Value::Number(value),
+        FeatureDataValue::Text(value) => Value::Text(Some(value)),
+        FeatureDataValue::NullableText(value) => Value::Text(value),
+        FeatureDataValue::Bool(value) => Value::Bool(Some(value)),
+        FeatureDataValue::NullableBool(value) => Value::Bool(value),
+        FeatureDataValue::DateTime(value) => Value::DateTime(Some(value.inner() as f64)),
+        FeatureDataValue::NullableDateTime(value) => {
+            Value::DateTime(value.map(|value| value.inner() as f64))
+        }
+    }
+}
+
 fn call_expression_function<GIn, ExprOut, MapOut, Out>(
-    expression: &Arc<LinkedExpression>,
+    expression: &ExpressionProgram,
     collection: &FeatureCollection<GIn>,
     input_columns: &[String],
     map_fn: fn(Option<ExprOut>) -> MapOut,
@@ -624,7 +749,7 @@ where
     for<'g> <<FeatureCollection<GIn> as IntoGeometryOptionsIterator<'g>>::GeometryOptionIterator as IntoParallelIterator>::Iter:
         IndexedParallelIterator + Send,
     for<'g> <FeatureCollection<GIn> as IntoGeometryOptionsIterator<'g>>::GeometryType: AsExpressionGeo,
-    ExprOut: Send,
+    ExprOut: ValueType + Send,
     MapOut: Send,
     Out: FromParallelIterator<MapOut> + Send,
 {
@@ -637,11 +762,6 @@ where
         })
         .collect();
 
-    let float_inputs: Vec<FloatOptionsParIter> = data_columns
-        .iter()
-        .map(FeatureDataRef::float_options_par_iter)
-        .collect::<Vec<_>>();
-
     let geom_input = collection
         .geometry_options()
         .into_par_iter()
@@ -653,6 +773,30 @@ where
             }
         });
 
+    let expression = match expression {
+        ExpressionProgram::Compiled(expression) => expression,
+        ExpressionProgram::Interpreted(expression) => {
+            return Ok(geom_input
+                .enumerate()
+                .with_min_len(PARALLEL_MIN_BATCH_SIZE)
+                .map(|(i, geom)| {
+                    let inputs = std::iter::once(ValueType::into_value(geom)).chain(
+                        data_columns
+                            .iter()
+                            .map(|column| expression_value(column.get_unchecked(i))),
+                    );
+
+                    map_fn(ExprOut::from_value(expression.evaluate(inputs)))
+                })
+                .collect());
+        }
+    };
+
+    let float_inputs: Vec<FloatOptionsParIter> = data_columns
+        .iter()
+        .map(FeatureDataRef::float_options_par_iter)
+        .collect::<Vec<_>>();
+
     macro_rules! impl_expression_subcall {
         ($n:literal, $($i:ident),*) => {
             {
@@ -716,12 +860,12 @@ mod tests {
     };
     use geoengine_datatypes::{
         collections::{
-            ChunksEqualIgnoringCacheHint, IntoGeometryIterator, MultiPointCollection,
-            MultiPolygonCollection,
+            ChunksEqualIgnoringCacheHint, IntoGeometryIterator, MultiLineStringCollection,
+            MultiPointCollection, MultiPolygonCollection,
         },
         primitives::{
-            BoundingBox2D, ColumnSelection, MultiPoint, MultiPolygon, SpatialResolution,
-            TimeInterval,
+            BoundingBox2D, ColumnSelection, MultiLineString, MultiPoint, MultiPolygon,
+            SpatialResolution, TimeInstance, TimeInterval,
         },
         util::test::TestDefault,
     };
@@ -733,6 +877,7 @@ mod tests {
                 input_columns: vec!["foo".into(), "bar".into()],
                 expression: "foo + bar".into(),
                 output_column: OutputColumn::Column("baz".into()),
+                output_column_type: FeatureDataType::Float,
                 output_measurement: Measurement::Unitless,
                 geometry_column_name: "geom".to_string(),
             },
@@ -749,6 +894,7 @@ mod tests {
                     "type": "column",
                     "value": "baz",
                 },
+                "outputColumnType": "float",
                 "outputMeasurement": {
                     "type": "unitless",
                 },
@@ -791,6 +937,7 @@ mod tests {
                 input_columns: vec!["foo".into()],
                 expression: "2 * foo".into(),
                 output_column: OutputColumn::Column("bar".into()),
+                output_column_type: FeatureDataType::Float,
                 output_measurement: Measurement::Unitless,
                 geometry_column_name: "geom".to_string(),
             },
@@ -882,6 +1029,7 @@ mod tests {
                 input_columns: vec!["foo".into(), "bar".into()],
                 expression: "foo + bar".into(),
                 output_column: OutputColumn::Column("baz".into()),
+                output_column_type: FeatureDataType::Float,
                 output_measurement: Measurement::Unitless,
                 geometry_column_name: "geom".to_string(),
             },
@@ -950,6 +1098,99 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn it_evaluates_with_the_interpreter() {
+        let points = MultiPointCollection::from_slices(
+            MultiPoint::many(vec![
+                (0.0, 0.1),
+                (1.0, 1.1),
+                (2.0, 2.1),
+                (3.0, 3.1),
+                (4.0, 4.1),
+            ])
+            .unwrap()
+            .as_ref(),
+            &[TimeInterval::new_unchecked(0, 1); 5],
+            &[
+                (
+                    "foo",
+                    FeatureData::NullableFloat(vec![Some(1.0), None, Some(3.0), None, Some(5.0)]),
+                ),
+                (
+                    "bar",
+                    FeatureData::NullableInt(vec![Some(10), None, None, Some(40), Some(50)]),
+                ),
+            ],
+        )
+        .unwrap();
+
+        let source = MockFeatureCollectionSource::single(points.clone())
+            .boxed()
+            .initialize(
+                WorkflowOperatorPath::initialize_root(),
+                &MockExecutionContext::test_default(),
+            )
+            .await
+            .unwrap()
+            .query_processor()
+            .unwrap()
+            .multi_point()
+            .unwrap();
+
+        let expression = compile_expression(
+            "if foo IS NODATA { bar } else { foo + area(geom) }",
+            "geom".into(),
+            VectorDataType::MultiPoint,
+            &[
+                ("foo".into(), DataType::Number),
+                ("bar".into(), DataType::Number),
+            ],
+            DataType::Number,
+            ExpressionBackend::Interpreter,
+        )
+        .unwrap();
+
+        let query_processor = VectorExpressionColumnProcessor {
+            result_descriptor: source.vector_result_descriptor().clone(),
+            source,
+            expression: Arc::new(expression),
+            input_columns: vec!["foo".into(), "bar".into()],
+            output_column: "baz".into(),
+            output_column_type: FeatureDataType::Float,
+        }
+        .boxed();
+
+        let query_rectangle = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new((0., 0.).into(), (10., 10.).into()).unwrap(),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::zero_point_one(),
+            attributes: ColumnSelection::all(),
+        };
+        let ctx = MockQueryContext::new(ChunkByteSize::MAX);
+
+        let query = query_processor.query(query_rectangle, &ctx).await.unwrap();
+
+        let mut result = query
+            .map(Result::unwrap)
+            .collect::<Vec<MultiPointCollection>>()
+            .await;
+
+        assert_eq!(result.len(), 1);
+        let result = result.remove(0);
+
+        let expected_result = points
+            .add_column(
+                "baz",
+                FeatureData::NullableFloat(vec![Some(1.0), None, Some(3.0), Some(40.0), Some(5.0)]),
+            )
+            .unwrap();
+
+        assert!(
+            result.chunks_equal_ignoring_cache_hint(&expected_result),
+            "{result:#?} != {expected_result:#?}",
+        );
+    }
+
     #[tokio::test]
     async fn it_computes_the_area_from_a_geom() {
         let polygons = MockFeatureCollectionSource::single(
@@ -983,6 +1224,7 @@ mod tests {
                     input_columns: vec![],
                     expression: "area(geom)".into(),
                     output_column: OutputColumn::Column("area".into()),
+                    output_column_type: FeatureDataType::Float,
                     output_measurement: Measurement::Unitless,
                     geometry_column_name: "geom".to_string(),
                 },
@@ -1066,6 +1308,7 @@ mod tests {
                     input_columns: vec![],
                     expression: "centroid(geom)".into(),
                     output_column: OutputColumn::Geometry(GeoVectorDataType::MultiPoint),
+                    output_column_type: FeatureDataType::Float,
                     output_measurement: Measurement::Unitless,
                     geometry_column_name: "geom".to_string(),
                 },
@@ -1101,6 +1344,198 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn it_computes_geometry_functions() {
+        let lines = MultiLineStringCollection::from_slices(
+            &[
+                MultiLineString::new(vec![vec![
+                    (0., 0.).into(),
+                    (3., 0.1).into(),
+                    (6., 0.).into(),
+                ]])
+                .unwrap(),
+                MultiLineString::new(vec![vec![
+                    (0., 0.).into(),
+                    (3., 0.).into(),
+                    (3., 4.).into(),
+                ]])
+                .unwrap(),
+            ],
+            &[TimeInterval::new_unchecked(0, 1); 2],
+            &[] as &[(&str, FeatureData)],
+        )
+        .unwrap();
+
+        let query_rectangle = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new((0., 0.).into(), (10., 10.).into()).unwrap(),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::zero_point_one(),
+            attributes: ColumnSelection::all(),
+        };
+
+        let result = compute_result::<MultiLineStringCollection>(
+            VectorExpression {
+                params: VectorExpressionParams {
+                    input_columns: vec![],
+                    expression: "simplify(geom, 1)".into(),
+                    output_column: OutputColumn::Geometry(GeoVectorDataType::MultiLineString),
+                    output_column_type: FeatureDataType::Float,
+                    output_measurement: Measurement::Unitless,
+                    geometry_column_name: "geom".to_string(),
+                },
+                sources: MockFeatureCollectionSource::single(lines.clone())
+                    .boxed()
+                    .into(),
+            },
+            query_rectangle.clone(),
+        )
+        .await;
+
+        let expected_result = MultiLineStringCollection::from_slices(
+            &[
+                MultiLineString::new(vec![vec![(0., 0.).into(), (6., 0.).into()]]).unwrap(),
+                MultiLineString::new(vec![vec![
+                    (0., 0.).into(),
+                    (3., 0.).into(),
+                    (3., 4.).into(),
+                ]])
+                .unwrap(),
+            ],
+            &[TimeInterval::new_unchecked(0, 1); 2],
+            &[] as &[(&str, FeatureData)],
+        )
+        .unwrap();
+
+        assert!(
+            result.chunks_equal_ignoring_cache_hint(&expected_result),
+            "{result:#?} != {expected_result:#?}",
+        );
+
+        let result = compute_result::<MultiLineStringCollection>(
+            VectorExpression {
+                params: VectorExpressionParams {
+                    input_columns: vec![],
+                    expression: "length(simplify(geom, 1)) + num_points(geom)".into(),
+                    output_column: OutputColumn::Column("length".into()),
+                    output_column_type: FeatureDataType::Float,
+                    output_measurement: Measurement::Unitless,
+                    geometry_column_name: "geom".to_string(),
+                },
+                sources: MockFeatureCollectionSource::single(lines.clone())
+                    .boxed()
+                    .into(),
+            },
+            query_rectangle,
+        )
+        .await;
+
+        let expected_result = lines
+            .add_column(
+                "length",
+                FeatureData::NullableFloat(vec![Some(9.), Some(10.)]),
+            )
+            .unwrap();
+
+        assert!(
+            result.chunks_equal_ignoring_cache_hint(&expected_result),
+            "{result:#?} != {expected_result:#?}",
+        );
+    }
+
+    #[tokio::test]
+    async fn it_computes_text_bool_and_datetime_columns() {
+        let points = MultiPointCollection::from_slices(
+            MultiPoint::many(vec![(0.0, 0.1), (1.0, 1.1), (2.0, 3.1)])
+                .unwrap()
+                .as_ref(),
+            &[TimeInterval::new_unchecked(0, 1); 3],
+            &[
+                (
+                    "name",
+                    FeatureData::NullableText(vec![Some("foo".into()), Some("bar".into()), None]),
+                ),
+                (
+                    "flag",
+                    FeatureData::NullableBool(vec![Some(true), Some(false), Some(true)]),
+                ),
+                (
+                    "time",
+                    FeatureData::NullableDateTime(vec![
+                        // 2024-03-01T12:00:00Z
+                        Some(TimeInstance::from_millis_unchecked(1_709_294_400_000)),
+                        None,
+                        Some(TimeInstance::from_millis_unchecked(0)),
+                    ]),
+                ),
+            ],
+        )
+        .unwrap();
+
+        let query_rectangle = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new((0., 0.).into(), (10., 10.).into()).unwrap(),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::zero_point_one(),
+            attributes: ColumnSelection::all(),
+        };
+
+        let compute = |input_columns: &[&str], expression: &str, output_type: FeatureDataType| {
+            compute_result::<MultiPointCollection>(
+                VectorExpression {
+                    params: VectorExpressionParams {
+                        input_columns: input_columns.iter().map(ToString::to_string).collect(),
+                        expression: expression.into(),
+                        output_column: OutputColumn::Column("new".into()),
+                        output_column_type: output_type,
+                        output_measurement: Measurement::Unitless,
+                        geometry_column_name: "geom".to_string(),
+                    },
+                    sources: MockFeatureCollectionSource::single(points.clone())
+                        .boxed()
+                        .into(),
+                },
+                query_rectangle.clone(),
+            )
+        };
+
+        for (input_columns, expression, expected) in [
+            (
+                &["name", "flag"] as &[&str],
+                r#"if flag { concat(upper(name), "!") } else { "-" }"#,
+                FeatureData::NullableText(vec![Some("FOO!".into()), Some("-".into()), None]),
+            ),
+            (
+                &["name"],
+                r#"matches(name, "^b")"#,
+                FeatureData::NullableBool(vec![Some(false), Some(true), None]),
+            ),
+            (
+                &["time"],
+                "year(time)",
+                FeatureData::NullableInt(vec![Some(2024), None, Some(1970)]),
+            ),
+            (
+                &["time"],
+                "add_days(time, 1)",
+                FeatureData::NullableDateTime(vec![
+                    Some(TimeInstance::from_millis_unchecked(1_709_380_800_000)),
+                    None,
+                    Some(TimeInstance::from_millis_unchecked(86_400_000)),
+                ]),
+            ),
+        ] {
+            let output_type = FeatureDataType::from(&expected);
+
+            let result = compute(input_columns, expression, output_type).await;
+
+            let expected_result = points.add_column("new", expected).unwrap();
+
+            assert!(
+                result.chunks_equal_ignoring_cache_hint(&expected_result),
+                "{expression}: {result:#?} != {expected_result:#?}",
+            );
+        }
+    }
+
     #[tokio::test]
     async fn it_computes_eight_larger_inputs() {
         const NUMBER_OF_ROWS: usize = 100;
@@ -1161,6 +1596,7 @@ mod tests {
                     ],
                     expression: "f1 + f2 + f3 + f4 + f5 + f6 + f7 + f8".into(),
                     output_column: OutputColumn::Column("new".into()),
+                    output_column_type: FeatureDataType::Float,
                     output_measurement: Measurement::Unitless,
                     geometry_column_name: "geom".to_string(),
                 },
@@ -5,9 +5,10 @@ use crate::adapters::{
     FoldTileAccu, FoldTileAccuMut, RasterSubQueryAdapter, SubQueryTileAggregator,
 };
 use crate::engine::{
-    CanonicOperatorName, ExecutionContext, InitializedRasterOperator, InitializedSources, Operator,
-    OperatorName, QueryContext, QueryProcessor, RasterOperator, RasterQueryProcessor,
-    RasterResultDescriptor, SingleRasterSource, TypedRasterQueryProcessor, WorkflowOperatorPath,
+    BoxRasterQueryProcessor, CanonicOperatorName, ExecutionContext, InitializedRasterOperator,
+    InitializedSources, Operator, OperatorName, QueryContext, QueryProcessor, RasterOperator,
+    RasterQueryProcessor, RasterResultDescriptor, SingleRasterSource, TypedRasterQueryProcessor,
+    WorkflowOperatorPath,
 };
 use crate::util::Result;
 use async_trait::async_trait;
@@ -20,8 +21,9 @@ use geoengine_datatypes::primitives::{
 };
 use geoengine_datatypes::primitives::{BandSelection, CacheHint};
 use geoengine_datatypes::raster::{
-    Bilinear, Blit, EmptyGrid2D, GeoTransform, GridOrEmpty, GridSize, InterpolationAlgorithm,
-    NearestNeighbor, Pixel, RasterTile2D, TileInformation, TilingSpecification,
+    Average, BiCubic, Bilinear, Blit, EmptyGrid2D, GeoTransform, GridOrEmpty, GridSize,
+    InterpolationAlgorithm, Lanczos, Mode, NearestNeighbor, Pixel, RasterTile2D, TileInformation,
+    TilingSpecification,
 };
 use rayon::ThreadPool;
 use serde::{Deserialize, Serialize};
@@ -41,11 +43,18 @@ pub enum InputResolution {
     Source,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+/// The resampling method.
+/// `Average` and `Mode` aggregate all input pixels that are covered by an output pixel and are meant for downsampling.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum InterpolationMethod {
+    #[default]
     NearestNeighbor,
     BiLinear,
+    BiCubic,
+    Lanczos,
+    Average,
+    Mode,
 }
 
 #[derive(Debug, Snafu)]
@@ -128,20 +137,12 @@ impl InitializedRasterOperator for InitializedInterpolation {
 
         let res = call_on_generic_raster_processor!(
             source_processor, p => match self.interpolation_method  {
-                InterpolationMethod::NearestNeighbor => InterploationProcessor::<_,_, NearestNeighbor>::new(
-                        p,
-                        self.result_descriptor.clone(),
-                        self.input_resolution,
-                        self.tiling_specification,
-                    ).boxed()
-                    .into(),
-                InterpolationMethod::BiLinear =>InterploationProcessor::<_,_, Bilinear>::new(
-                        p,
-                        self.result_descriptor.clone(),
-                        self.input_resolution,
-                        self.tiling_specification,
-                    ).boxed()
-                    .into(),
+                InterpolationMethod::NearestNeighbor => self.processor::<_, NearestNeighbor>(p).into(),
+                InterpolationMethod::BiLinear => self.processor::<_, Bilinear>(p).into(),
+                InterpolationMethod::BiCubic => self.processor::<_, BiCubic>(p).into(),
+                InterpolationMethod::Lanczos => self.processor::<_, Lanczos>(p).into(),
+                InterpolationMethod::Average => self.processor::<_, Average>(p).into(),
+                InterpolationMethod::Mode => self.processor::<_, Mode>(p).into(),
             }
         );
 
@@ -157,6 +158,22 @@ impl InitializedRasterOperator for InitializedInterpolation {
     }
 }
 
+impl InitializedInterpolation {
+    fn processor<P, I>(&self, source: BoxRasterQueryProcessor<P>) -> BoxRasterQueryProcessor<P>
+    where
+        P: Pixel,
+        I: InterpolationAlgorithm<P>,
+    {
+        InterploationProcessor::<_, _, I>::new(
+            source,
+            self.result_descriptor.clone(),
+            self.input_resolution,
+            self.tiling_specification,
+        )
+        .boxed()
+    }
+}
+
 pub struct InterploationProcessor<Q, P, I>
 where
     Q: RasterQueryProcessor<RasterType = P>,
@@ -214,8 +231,9 @@ where
         query: RasterQueryRectangle,
         ctx: &'a dyn QueryContext,
     ) -> Result<BoxStream<'a, Result<Self::Output>>> {
-        // do not interpolate if the source resolution is already fine enough
-        if query.spatial_resolution.x >= self.input_resolution.x
+        // do not interpolate if the source resolution is already fine enough, unless the algorithm aggregates for downsampling
+        if !I::AGGREGATES
+            && query.spatial_resolution.x >= self.input_resolution.x
             && query.spatial_resolution.y >= self.input_resolution.y
         {
             // TODO: should we use the query or the input resolution here?
@@ -294,10 +312,11 @@ where
     ) -> Result<Option<RasterQueryRectangle>> {
         // enlarge the spatial bounds in order to have the neighbor pixels for the interpolation
         let spatial_bounds = tile_info.spatial_partition();
-        let enlarge: Coordinate2D = (self.input_resolution.x, -self.input_resolution.y).into();
+        let (margin_before, margin_after) = I::INPUT_MARGIN;
+        let pixel: Coordinate2D = (self.input_resolution.x, -self.input_resolution.y).into();
         let spatial_bounds = SpatialPartition2D::new(
-            spatial_bounds.upper_left(),
-            spatial_bounds.lower_right() + enlarge,
+            spatial_bounds.upper_left() - pixel * margin_before as f64,
+            spatial_bounds.lower_right() + pixel * margin_after as f64,
         )?;
 
         Ok(Some(RasterQueryRectangle {
@@ -794,4 +813,79 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn average_operator_downsamples() -> Result<()> {
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [2, 2].into(),
+        ));
+
+        let raster = make_raster(CacheHint::max_duration());
+
+        let operator = Interpolation {
+            params: InterpolationParams {
+                interpolation: InterpolationMethod::Average,
+                input_resolution: InputResolution::Value(SpatialResolution::one()),
+            },
+            sources: SingleRasterSource { raster },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await?;
+
+        let processor = operator.query_processor()?.get_i8().unwrap();
+
+        let query_rect = RasterQueryRectangle {
+            spatial_bounds: SpatialPartition2D::new_unchecked((0., 2.).into(), (4., 0.).into()),
+            time_interval: TimeInterval::new_unchecked(0, 20),
+            spatial_resolution: SpatialResolution::new_unchecked(2., 2.),
+            attributes: BandSelection::first(),
+        };
+        let query_ctx = MockQueryContext::test_default();
+
+        let result_stream = processor.query(query_rect, &query_ctx).await?;
+
+        let result: Vec<Result<RasterTile2D<i8>>> = result_stream.collect().await;
+        let result = result.into_iter().collect::<Result<Vec<_>>>()?;
+
+        let times = [
+            TimeInterval::new_unchecked(0, 10),
+            TimeInterval::new_unchecked(10, 20),
+        ];
+
+        // the means 3.5 and 5.5 are truncated to the pixel type
+        let data = [vec![0, 0, 3, 5], vec![0, 0, 5, 3]];
+
+        assert_eq!(result.len(), 2);
+
+        for (i, tile) in result.into_iter().enumerate() {
+            let tile = tile.into_materialized_tile();
+            assert_eq!(tile.time, times[i]);
+            assert_eq!(tile.grid_array.inner_grid.data, data[i]);
+            assert_eq!(
+                tile.grid_array.validity_mask.data,
+                vec![false, false, true, true]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_deserializes_interpolation_methods() {
+        for (name, method) in [
+            ("nearestNeighbor", InterpolationMethod::NearestNeighbor),
+            ("biLinear", InterpolationMethod::BiLinear),
+            ("biCubic", InterpolationMethod::BiCubic),
+            ("lanczos", InterpolationMethod::Lanczos),
+            ("average", InterpolationMethod::Average),
+            ("mode", InterpolationMethod::Mode),
+        ] {
+            assert_eq!(
+                serde_json::from_value::<InterpolationMethod>(serde_json::json!(name)).unwrap(),
+                method
+            );
+        }
+    }
 }
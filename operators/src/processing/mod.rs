@@ -1,5 +1,6 @@
 mod circle_merging_quadtree;
 mod column_range_filter;
+mod contour;
 mod expression;
 mod interpolation;
 mod line_simplification;
@@ -7,26 +8,34 @@ mod map_query;
 mod meteosat;
 mod neighborhood_aggregate;
 mod point_in_polygon;
+mod polygonize;
 mod raster_scaling;
 mod raster_stacker;
 mod raster_type_conversion;
 mod raster_vector_join;
 mod rasterization;
+mod reclassify;
 mod reprojection;
 mod rgb;
 mod temporal_raster_aggregation;
+mod terrain_analysis;
 mod time_projection;
 mod time_shift;
 mod vector_join;
+mod vector_overlay;
 
 pub use circle_merging_quadtree::{
     InitializedVisualPointClustering, VisualPointClustering, VisualPointClusteringParams,
 };
+pub use contour::{Contour, ContourError, ContourLevels, ContourOutput, ContourParams};
 pub use expression::{
-    initialize_expression_dependencies, Expression, ExpressionParams, RasterExpressionError,
-    VectorExpression, VectorExpressionError, VectorExpressionParams,
+    initialize_expression_dependencies, set_expression_backend, Expression, ExpressionBackend,
+    ExpressionParams, RasterExpressionError, VectorExpression, VectorExpressionError,
+    VectorExpressionParams,
+};
+pub use interpolation::{
+    Interpolation, InterpolationError, InterpolationMethod, InterpolationParams,
 };
-pub use interpolation::{Interpolation, InterpolationError, InterpolationParams};
 pub use line_simplification::{
     LineSimplification, LineSimplificationError, LineSimplificationParams,
 };
@@ -38,6 +47,7 @@ pub use point_in_polygon::{
     PointInPolygonFilter, PointInPolygonFilterParams, PointInPolygonFilterSource,
     PointInPolygonTester,
 };
+pub use polygonize::{Connectivity, Polygonize, PolygonizeError, PolygonizeParams};
 pub use raster_stacker::{RasterStacker, RasterStackerParams};
 pub use raster_type_conversion::{
     RasterTypeConversion, RasterTypeConversionParams, RasterTypeConversionQueryProcessor,
@@ -46,6 +56,9 @@ pub use raster_vector_join::{
     ColumnNames, FeatureAggregationMethod, RasterVectorJoin, RasterVectorJoinParams,
     TemporalAggregationMethod,
 };
+pub use reclassify::{
+    ReclassificationInput, ReclassificationRule, Reclassify, ReclassifyError, ReclassifyParams,
+};
 pub use reprojection::{
     InitializedRasterReprojection, InitializedVectorReprojection, Reprojection, ReprojectionParams,
 };
@@ -53,5 +66,12 @@ pub use rgb::{Rgb, RgbOperatorError, RgbParams, RgbSources};
 pub use temporal_raster_aggregation::{
     Aggregation, TemporalRasterAggregation, TemporalRasterAggregationParameters,
 };
+pub use terrain_analysis::{
+    SlopeUnit, TerrainAnalysis, TerrainAnalysisError, TerrainAnalysisMethod, TerrainAnalysisParams,
+};
 pub use time_projection::{TimeProjection, TimeProjectionError, TimeProjectionParams};
 pub use time_shift::{TimeShift, TimeShiftError, TimeShiftParams};
+pub use vector_overlay::{
+    BufferDistance, Dissolve, DissolveParams, OverlayOperation, VectorBuffer, VectorBufferParams,
+    VectorOverlay, VectorOverlayError, VectorOverlayParams, VectorOverlaySources,
+};
@@ -70,12 +70,52 @@ impl Neighborhood {
 
 /// A function that aggregates a neighborhood of pixels to a single pixel value.
 pub trait AggregateFunction: Sync + Send + Clone {
+    /// Aggregate the pixel neighborhood with respect to the weights of the `neighborhood`.
+    ///
+    /// By default, the weights are multiplied with the values before aggregating them.
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&neighborhood.apply(values))
+    }
+
     fn apply<P>(values: &[Option<f64>]) -> Option<P>
     where
         P: Pixel,
         f64: AsPrimitive<P>;
 }
 
+/// Only keeps the values that have a non-zero weight, i.e., the weights define the shape of the neighborhood.
+fn mask_values(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Vec<Option<f64>> {
+    values
+        .into_iter()
+        .zip(neighborhood.matrix().data.iter())
+        .filter(|(_, weight)| **weight != 0.)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+/// Returns the values that are not NODATA in ascending order.
+fn sorted_valid_values(values: &[Option<f64>]) -> Vec<f64> {
+    let mut values: Vec<f64> = values.iter().flatten().copied().collect();
+    values.sort_unstable_by(f64::total_cmp);
+    values
+}
+
+fn finite_or_none<P>(value: f64) -> Option<P>
+where
+    P: Pixel,
+    f64: AsPrimitive<P>,
+{
+    if value.is_finite() {
+        Some(value.as_())
+    } else {
+        None
+    }
+}
+
 /// An aggregate function that computes the standard deviation of a set of pixels.
 #[derive(Debug, Clone, Copy)]
 pub struct StandardDeviation;
@@ -95,13 +135,29 @@ impl AggregateFunction for StandardDeviation {
             }
         }
 
-        let std_dev = aggregator.std_dev();
+        finite_or_none(aggregator.std_dev())
+    }
+}
 
-        if std_dev.is_finite() {
-            Some(std_dev.as_())
-        } else {
-            None
+/// An aggregate function that computes the variance of a set of pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct Variance;
+
+impl AggregateFunction for Variance {
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let mut aggregator = NumberStatistics::default();
+        for value in values {
+            match value {
+                Some(v) => aggregator.add(*v),
+                None => aggregator.add_no_data(),
+            }
         }
+
+        finite_or_none(aggregator.var())
     }
 }
 
@@ -131,6 +187,243 @@ impl AggregateFunction for Sum {
     }
 }
 
+/// An aggregate function that computes the minimum of a set of pixels.
+/// NODATA values and values with a weight of zero are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Min;
+
+impl AggregateFunction for Min {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&mask_values(neighborhood, values))
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        values
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(f64::min)
+            .map(AsPrimitive::as_)
+    }
+}
+
+/// An aggregate function that computes the maximum of a set of pixels.
+/// NODATA values and values with a weight of zero are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Max;
+
+impl AggregateFunction for Max {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&mask_values(neighborhood, values))
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        values
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(f64::max)
+            .map(AsPrimitive::as_)
+    }
+}
+
+/// An aggregate function that computes the difference between the maximum and the minimum of a set of pixels.
+/// NODATA values and values with a weight of zero are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Range;
+
+impl AggregateFunction for Range {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&mask_values(neighborhood, values))
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let mut valid_values = values.iter().flatten().copied();
+        let first = valid_values.next()?;
+
+        let (min, max) = valid_values.fold((first, first), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+
+        Some((max - min).as_())
+    }
+}
+
+/// An aggregate function that computes the mean of a set of pixels.
+/// NODATA values are ignored and the weights are used for computing a weighted mean.
+#[derive(Debug, Clone, Copy)]
+pub struct Mean;
+
+impl AggregateFunction for Mean {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let (weighted_sum, weight_sum) = values
+            .into_iter()
+            .zip(neighborhood.matrix().data.iter())
+            .filter_map(|(value, weight)| value.map(|value| (value, *weight)))
+            .fold((0., 0.), |(weighted_sum, weight_sum), (value, weight)| {
+                (weighted_sum + value * weight, weight_sum + weight)
+            });
+
+        if weight_sum == 0. {
+            return None;
+        }
+
+        finite_or_none(weighted_sum / weight_sum)
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let (sum, count) = values
+            .iter()
+            .flatten()
+            .fold((0., 0_usize), |(sum, count), value| {
+                (sum + value, count + 1)
+            });
+
+        if count == 0 {
+            return None;
+        }
+
+        finite_or_none(sum / count as f64)
+    }
+}
+
+/// An aggregate function that computes the median of a set of pixels.
+/// For an even number of values, it is the mean of the two middle values.
+/// NODATA values and values with a weight of zero are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Median;
+
+impl AggregateFunction for Median {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&mask_values(neighborhood, values))
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let values = sorted_valid_values(values);
+
+        if values.is_empty() {
+            return None;
+        }
+
+        let middle = values.len() / 2;
+        let median = if values.len().is_even() {
+            (values[middle - 1] + values[middle]) / 2.
+        } else {
+            values[middle]
+        };
+
+        Some(median.as_())
+    }
+}
+
+/// An aggregate function that computes the most frequent value of a set of pixels, e.g., for categorical rasters.
+/// If several values are equally frequent, the smallest one is chosen.
+/// NODATA values and values with a weight of zero are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Mode;
+
+impl AggregateFunction for Mode {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&mask_values(neighborhood, values))
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let values = sorted_valid_values(values);
+
+        let mut mode: Option<(f64, usize)> = None;
+        let mut run_start = 0;
+
+        // the values are sorted, so equal values form consecutive runs
+        for (i, value) in values.iter().enumerate() {
+            let is_run_end = values.get(i + 1).map_or(true, |next| next > value);
+            if !is_run_end {
+                continue;
+            }
+
+            let run_length = i + 1 - run_start;
+            if mode.map_or(true, |(_, count)| run_length > count) {
+                mode = Some((*value, run_length));
+            }
+            run_start = i + 1;
+        }
+
+        mode.map(|(value, _)| value.as_())
+    }
+}
+
+/// An aggregate function that counts the pixels of a neighborhood that are not NODATA.
+/// Values with a weight of zero are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Count;
+
+impl AggregateFunction for Count {
+    fn aggregate<P>(neighborhood: &Neighborhood, values: Vec<Option<f64>>) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        Self::apply(&mask_values(neighborhood, values))
+    }
+
+    fn apply<P>(values: &[Option<f64>]) -> Option<P>
+    where
+        P: Pixel,
+        f64: AsPrimitive<P>,
+    {
+        let count = values.iter().flatten().count();
+
+        Some((count as f64).as_())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +490,109 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_order_statistics() {
+        let values = [
+            Some(4.),
+            Some(1.),
+            None,
+            Some(9.),
+            Some(2.),
+            Some(2.),
+            Some(7.),
+            None,
+            Some(5.),
+        ];
+
+        assert_eq!(Min::apply::<f64>(&values).unwrap(), 1.);
+        assert_eq!(Max::apply::<f64>(&values).unwrap(), 9.);
+        assert_eq!(Range::apply::<f64>(&values).unwrap(), 8.);
+        assert_eq!(Median::apply::<f64>(&values).unwrap(), 4.);
+        assert_eq!(Median::apply::<f64>(&values[..8]).unwrap(), 3.);
+        assert_eq!(Count::apply::<u8>(&values).unwrap(), 7);
+
+        let no_data = [None, None, None];
+
+        assert!(Min::apply::<f64>(&no_data).is_none());
+        assert!(Max::apply::<f64>(&no_data).is_none());
+        assert!(Range::apply::<f64>(&no_data).is_none());
+        assert!(Median::apply::<f64>(&no_data).is_none());
+        assert_eq!(Count::apply::<u8>(&no_data).unwrap(), 0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_mean_and_variance() {
+        let values = [Some(1.), Some(2.), Some(3.), None, Some(4.)];
+
+        assert_eq!(Mean::apply::<f64>(&values).unwrap(), 2.5);
+        assert_eq!(Variance::apply::<f64>(&values).unwrap(), 1.25);
+
+        assert!(Mean::apply::<f64>(&[None, None]).is_none());
+        assert!(Variance::apply::<f64>(&[None, None]).is_none());
+    }
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(
+            Mode::apply::<u8>(&[
+                Some(3.),
+                Some(1.),
+                Some(3.),
+                None,
+                Some(1.),
+                Some(2.),
+                Some(3.)
+            ]),
+            Some(3)
+        );
+
+        // ties are resolved by the smallest value
+        assert_eq!(
+            Mode::apply::<u8>(&[Some(5.), Some(4.), Some(5.), Some(4.), None, None, None]),
+            Some(4)
+        );
+
+        assert!(Mode::apply::<u8>(&[None]).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_aggregating_with_weights() {
+        let cross = Neighborhood::new(
+            Grid2D::new([3, 3].into(), vec![0., 1., 0., 1., 2., 1., 0., 1., 0.]).unwrap(),
+        )
+        .unwrap();
+
+        let values = vec![
+            Some(100.),
+            Some(1.),
+            Some(100.),
+            Some(2.),
+            Some(3.),
+            None,
+            Some(100.),
+            Some(6.),
+            Some(100.),
+        ];
+
+        // the corners are not part of the neighborhood
+        assert_eq!(Max::aggregate::<f64>(&cross, values.clone()).unwrap(), 6.);
+        assert_eq!(Min::aggregate::<f64>(&cross, values.clone()).unwrap(), 1.);
+        assert_eq!(
+            Median::aggregate::<f64>(&cross, values.clone()).unwrap(),
+            2.5
+        );
+        assert_eq!(Count::aggregate::<f64>(&cross, values.clone()).unwrap(), 4.);
+
+        // (1 + 2 + 2 * 3 + 6) / (1 + 1 + 2 + 1)
+        assert_eq!(Mean::aggregate::<f64>(&cross, values.clone()).unwrap(), 3.);
+
+        // the sum is NODATA if any pixel is NODATA
+        assert!(Sum::aggregate::<f64>(&cross, values).is_none());
+    }
+
     #[test]
     fn test_applying_weights() {
         let ones = Neighborhood::new(Grid2D::new([3, 3].into(), vec![1.; 9]).unwrap()).unwrap();
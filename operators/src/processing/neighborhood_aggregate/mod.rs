@@ -1,8 +1,12 @@
 mod aggregate;
 mod tile_sub_query;
 
-use self::aggregate::{AggregateFunction, Neighborhood, StandardDeviation, Sum};
-use self::tile_sub_query::NeighborhoodAggregateTileNeighborhood;
+use self::aggregate::{
+    AggregateFunction, Count, Max, Mean, Median, Min, Mode, Neighborhood, Range, StandardDeviation,
+    Sum, Variance,
+};
+use self::tile_sub_query::AggregateKernel;
+pub(crate) use self::tile_sub_query::{NeighborhoodKernel, TileNeighborhood};
 use crate::adapters::stack_individual_aligned_raster_bands;
 use crate::adapters::RasterSubQueryAdapter;
 use crate::engine::{
@@ -45,11 +49,26 @@ pub struct NeighborhoodAggregateParams {
     pub aggregate_function: AggregateFunctionParams,
 }
 
+/// The aggregate functions of the `NeighborhoodAggregate` operator.
+///
+/// `Sum`, `StandardDeviation` and `Variance` aggregate the weighted values and `Mean` computes the weighted mean.
+/// All other functions use the weights as a mask, i.e., they ignore pixels with a weight of zero.
+/// Except for `Sum`, NODATA pixels are ignored and the result is NODATA only if there are no valid pixels.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum AggregateFunctionParams {
     Sum,
     StandardDeviation,
+    Min,
+    Max,
+    Mean,
+    Median,
+    /// The most frequent value, e.g., for categorical rasters
+    Mode,
+    Variance,
+    Range,
+    /// The number of valid pixels
+    Count,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -175,24 +194,40 @@ pub struct InitializedNeighborhoodAggregate {
     tiling_specification: TilingSpecification,
 }
 
+impl InitializedNeighborhoodAggregate {
+    fn processor<Q, P, A>(&self, source: Q) -> NeighborhoodAggregateProcessor<Q, P, A>
+    where
+        Q: RasterQueryProcessor<RasterType = P>,
+        P: Pixel,
+    {
+        NeighborhoodAggregateProcessor::new(
+            source,
+            self.tiling_specification,
+            self.neighborhood.clone(),
+        )
+    }
+}
+
 impl InitializedRasterOperator for InitializedNeighborhoodAggregate {
     fn query_processor(&self) -> Result<TypedRasterQueryProcessor> {
         let source_processor = self.raster_source.query_processor()?;
 
         let res = call_on_generic_raster_processor!(
-            source_processor, p => match &self.aggregate_function  {
-                AggregateFunctionParams::Sum => NeighborhoodAggregateProcessor::<_,_, Sum>::new(
-                        p,
-                        self.tiling_specification,
-                        self.neighborhood.clone(),
-                    ).boxed()
-                    .into(),
-                    AggregateFunctionParams::StandardDeviation => NeighborhoodAggregateProcessor::<_,_, StandardDeviation>::new(
-                        p,
-                        self.tiling_specification,
-                        self.neighborhood.clone(),
-                    ).boxed()
-                    .into(),
+            source_processor, p => match &self.aggregate_function {
+                AggregateFunctionParams::Sum => self.processor::<_, _, Sum>(p).boxed().into(),
+                AggregateFunctionParams::StandardDeviation => {
+                    self.processor::<_, _, StandardDeviation>(p).boxed().into()
+                }
+                AggregateFunctionParams::Min => self.processor::<_, _, Min>(p).boxed().into(),
+                AggregateFunctionParams::Max => self.processor::<_, _, Max>(p).boxed().into(),
+                AggregateFunctionParams::Mean => self.processor::<_, _, Mean>(p).boxed().into(),
+                AggregateFunctionParams::Median => self.processor::<_, _, Median>(p).boxed().into(),
+                AggregateFunctionParams::Mode => self.processor::<_, _, Mode>(p).boxed().into(),
+                AggregateFunctionParams::Variance => {
+                    self.processor::<_, _, Variance>(p).boxed().into()
+                }
+                AggregateFunctionParams::Range => self.processor::<_, _, Range>(p).boxed().into(),
+                AggregateFunctionParams::Count => self.processor::<_, _, Count>(p).boxed().into(),
             }
         );
 
@@ -258,8 +293,8 @@ where
         ctx: &'a dyn QueryContext,
     ) -> Result<BoxStream<'a, Result<Self::Output>>> {
         stack_individual_aligned_raster_bands(&query, ctx, |query, ctx| async move {
-            let sub_query = NeighborhoodAggregateTileNeighborhood::<P, A>::new(
-                self.neighborhood.clone(),
+            let sub_query = TileNeighborhood::<P, _>::new(
+                AggregateKernel::<A>::new(self.neighborhood.clone()),
                 self.tiling_specification,
             );
 
@@ -409,6 +444,91 @@ mod tests {
         serde_json::from_value::<NeighborhoodAggregate>(serialized).unwrap();
     }
 
+    #[test]
+    fn test_deserialization_aggregate_functions() {
+        for name in [
+            "sum",
+            "standardDeviation",
+            "min",
+            "max",
+            "mean",
+            "median",
+            "mode",
+            "variance",
+            "range",
+            "count",
+        ] {
+            let params: NeighborhoodAggregateParams = serde_json::from_value(serde_json::json!({
+                "neighborhood": {
+                    "type": "rectangle",
+                    "dimensions": [3, 3]
+                },
+                "aggregateFunction": name
+            }))
+            .unwrap();
+
+            assert_eq!(
+                serde_json::to_value(params.aggregate_function).unwrap(),
+                serde_json::json!(name)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_filter() {
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [3, 3].into(),
+        ));
+
+        let operator = NeighborhoodAggregate {
+            params: NeighborhoodAggregateParams {
+                neighborhood: NeighborhoodParams::Rectangle { dimensions: [3, 3] },
+                aggregate_function: AggregateFunctionParams::Max,
+            },
+            sources: SingleRasterSource {
+                raster: make_raster(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await
+        .unwrap();
+
+        let processor = operator.query_processor().unwrap().get_i8().unwrap();
+
+        let query_rect = RasterQueryRectangle {
+            spatial_bounds: SpatialPartition2D::new((0., 3.).into(), (6., 0.).into()).unwrap(),
+            time_interval: TimeInterval::new_unchecked(0, 10),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: BandSelection::first(),
+        };
+        let query_ctx = MockQueryContext::test_default();
+
+        let result_stream = processor.query(query_rect, &query_ctx).await.unwrap();
+
+        let result: Vec<Result<RasterTile2D<i8>>> = result_stream.collect().await;
+        let result = result.into_iter().collect::<Result<Vec<_>>>().unwrap();
+
+        // NODATA at the raster borders is ignored, so all pixels get a value
+        let data = vec![
+            vec![8, 9, 10, 14, 15, 16, 14, 15, 16],
+            vec![11, 12, 12, 17, 18, 18, 17, 18, 18],
+        ];
+
+        assert_eq!(result.len(), 2);
+        for (i, tile) in result.into_iter().enumerate() {
+            let tile = tile.into_materialized_tile();
+            assert_eq!(tile.grid_array.inner_grid.data, data[i]);
+            assert!(tile
+                .grid_array
+                .validity_mask
+                .data
+                .iter()
+                .all(|valid| *valid));
+        }
+    }
+
     #[test]
     fn test_initialized_raster_kernel_method() {
         let neighborhood: Neighborhood = NeighborhoodParams::WeightsMatrix {
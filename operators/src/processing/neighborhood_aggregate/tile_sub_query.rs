@@ -37,36 +37,83 @@ use tokio::task::JoinHandle;
 /// --------|--------|--------
 /// ```
 ///
-/// It then applies a kernel function to each pixel and its surrounding.
+/// It then applies a [`NeighborhoodKernel`] to each pixel and its surrounding.
 ///
 #[derive(Debug, Clone)]
-pub struct NeighborhoodAggregateTileNeighborhood<P, A> {
-    neighborhood: Neighborhood,
+pub struct TileNeighborhood<P, K> {
+    kernel: K,
     tiling_specification: TilingSpecification,
-    _phantom_types: PhantomData<(P, A)>,
+    _phantom_types: PhantomData<P>,
 }
 
-impl<P, A> NeighborhoodAggregateTileNeighborhood<P, A> {
-    pub fn new(neighborhood: Neighborhood, tiling_specification: TilingSpecification) -> Self {
+impl<P, K> TileNeighborhood<P, K> {
+    pub fn new(kernel: K, tiling_specification: TilingSpecification) -> Self {
         Self {
-            neighborhood,
+            kernel,
             tiling_specification,
             _phantom_types: PhantomData,
         }
     }
 }
 
-impl<'a, P, A> SubQueryTileAggregator<'a, P> for NeighborhoodAggregateTileNeighborhood<P, A>
+/// A function that computes the value of a pixel from the values of its neighborhood.
+pub trait NeighborhoodKernel<P>: Clone + Send + Sync + 'static {
+    /// Specifies the x extent beginning from the center pixel
+    fn x_radius(&self) -> usize;
+
+    /// Specifies the y extent beginning from the center pixel
+    fn y_radius(&self) -> usize;
+
+    /// Computes the pixel value from the `values` of the neighborhood in row-major order.
+    /// The `geo_transform` of the output tile allows accessing the pixel size.
+    fn apply(&self, values: Vec<Option<f64>>, geo_transform: &GeoTransform) -> Option<P>;
+}
+
+/// A kernel that applies an [`AggregateFunction`] to a weighted [`Neighborhood`]
+#[derive(Debug, Clone)]
+pub struct AggregateKernel<A> {
+    neighborhood: Neighborhood,
+    _aggregate_fn: PhantomData<A>,
+}
+
+impl<A> AggregateKernel<A> {
+    pub fn new(neighborhood: Neighborhood) -> Self {
+        Self {
+            neighborhood,
+            _aggregate_fn: PhantomData,
+        }
+    }
+}
+
+impl<P, A> NeighborhoodKernel<P> for AggregateKernel<A>
 where
     P: Pixel,
     f64: AsPrimitive<P>,
     A: AggregateFunction + 'static,
 {
-    type FoldFuture = FoldFuture<P, A>;
+    fn x_radius(&self) -> usize {
+        self.neighborhood.x_radius()
+    }
+
+    fn y_radius(&self) -> usize {
+        self.neighborhood.y_radius()
+    }
+
+    fn apply(&self, values: Vec<Option<f64>>, _geo_transform: &GeoTransform) -> Option<P> {
+        A::aggregate(&self.neighborhood, values)
+    }
+}
+
+impl<'a, P, K> SubQueryTileAggregator<'a, P> for TileNeighborhood<P, K>
+where
+    P: Pixel,
+    K: NeighborhoodKernel<P>,
+{
+    type FoldFuture = FoldFuture<P, K>;
 
-    type FoldMethod = fn(NeighborhoodAggregateAccu<P, A>, RasterTile2D<P>) -> Self::FoldFuture;
+    type FoldMethod = fn(TileNeighborhoodAccu<P, K>, RasterTile2D<P>) -> Self::FoldFuture;
 
-    type TileAccu = NeighborhoodAggregateAccu<P, A>;
+    type TileAccu = TileNeighborhoodAccu<P, K>;
     type TileAccuFuture = BoxFuture<'a, Result<Self::TileAccu>>;
 
     /// Create an enlarged tile to store the values of the neighborhood
@@ -78,15 +125,9 @@ where
     ) -> Self::TileAccuFuture {
         let pool = pool.clone();
         let tiling_specification = self.tiling_specification;
-        let neighborhood = self.neighborhood.clone();
+        let kernel = self.kernel.clone();
         crate::util::spawn_blocking(move || {
-            create_enlarged_tile(
-                tile_info,
-                &query_rect,
-                pool,
-                tiling_specification,
-                neighborhood,
-            )
+            create_enlarged_tile(tile_info, &query_rect, pool, tiling_specification, kernel)
         })
         .map_err(From::from)
         .boxed()
@@ -103,8 +144,8 @@ where
         let spatial_bounds = tile_info.spatial_partition();
 
         let margin_pixels = Coordinate2D::from((
-            self.neighborhood.x_radius() as f64 * tile_info.global_geo_transform.x_pixel_size(),
-            self.neighborhood.y_radius() as f64 * tile_info.global_geo_transform.y_pixel_size(),
+            self.kernel.x_radius() as f64 * tile_info.global_geo_transform.x_pixel_size(),
+            self.kernel.y_radius() as f64 * tile_info.global_geo_transform.y_pixel_size(),
         ));
 
         let enlarged_spatial_bounds = SpatialPartition2D::new(
@@ -129,49 +170,41 @@ where
 }
 
 #[derive(Clone, Debug)]
-pub struct NeighborhoodAggregateAccu<P: Pixel, A> {
+pub struct TileNeighborhoodAccu<P: Pixel, K> {
     pub output_info: TileInformation,
     pub input_tile: RasterTile2D<P>,
     pub pool: Arc<ThreadPool>,
-    pub neighborhood: Neighborhood,
-    phantom_aggregate_fn: PhantomData<A>,
+    pub kernel: K,
 }
 
-impl<P: Pixel, A> NeighborhoodAggregateAccu<P, A> {
+impl<P: Pixel, K> TileNeighborhoodAccu<P, K> {
     pub fn new(
         input_tile: RasterTile2D<P>,
         output_info: TileInformation,
         pool: Arc<ThreadPool>,
-        neighborhood: Neighborhood,
+        kernel: K,
     ) -> Self {
-        NeighborhoodAggregateAccu {
+        TileNeighborhoodAccu {
             output_info,
             input_tile,
             pool,
-            neighborhood,
-            phantom_aggregate_fn: PhantomData,
+            kernel,
         }
     }
 }
 
 #[async_trait]
-impl<P, A> FoldTileAccu for NeighborhoodAggregateAccu<P, A>
+impl<P, K> FoldTileAccu for TileNeighborhoodAccu<P, K>
 where
     P: Pixel,
-    f64: AsPrimitive<P>,
-    A: AggregateFunction + 'static,
+    K: NeighborhoodKernel<P>,
 {
     type RasterType = P;
 
     /// now that we collected all the input tile pixels we perform the actual raster kernel
     async fn into_tile(self) -> Result<RasterTile2D<Self::RasterType>> {
-        let neighborhood = self.neighborhood.clone();
         let output_tile = crate::util::spawn_blocking_with_thread_pool(self.pool, move || {
-            apply_kernel_for_each_inner_pixel::<P, A>(
-                &self.input_tile,
-                &self.output_info,
-                &neighborhood,
-            )
+            apply_kernel_for_each_inner_pixel(&self.input_tile, &self.output_info, &self.kernel)
         })
         .await?;
 
@@ -183,16 +216,15 @@ where
     }
 }
 
-/// Apply kernel function to all pixels of the inner input tile in the 9x9 grid
-fn apply_kernel_for_each_inner_pixel<P, A>(
+/// Apply the kernel to all pixels of the inner input tile in the 9x9 grid
+fn apply_kernel_for_each_inner_pixel<P, K>(
     input: &RasterTile2D<P>,
     info_out: &TileInformation,
-    neighborhood: &Neighborhood,
+    kernel: &K,
 ) -> RasterTile2D<P>
 where
     P: Pixel,
-    f64: AsPrimitive<P>,
-    A: AggregateFunction,
+    K: NeighborhoodKernel<P>,
 {
     if input.is_empty() {
         return RasterTile2D::new_with_tile_info(
@@ -204,14 +236,16 @@ where
         );
     }
 
+    let x_width = 2 * kernel.x_radius() + 1;
+    let y_width = 2 * kernel.y_radius() + 1;
+
     let map_fn = |gidx: GridIdx2D| {
         let GridIdx([y, x]) = gidx;
 
-        let mut neighborhood_matrix =
-            Vec::<Option<f64>>::with_capacity(neighborhood.matrix().number_of_elements());
+        let mut neighborhood_matrix = Vec::<Option<f64>>::with_capacity(x_width * y_width);
 
-        let y_stop = y + neighborhood.y_width() as isize;
-        let x_stop = x + neighborhood.x_width() as isize;
+        let y_stop = y + y_width as isize;
+        let x_stop = x + x_width as isize;
         // copy row-by-row all pixels in x direction into kernel matrix
         for y_index in y..y_stop {
             for x_index in x..x_stop {
@@ -223,7 +257,7 @@ where
             }
         }
 
-        A::apply(&neighborhood.apply(neighborhood_matrix))
+        kernel.apply(neighborhood_matrix, &info_out.global_geo_transform)
     };
 
     // TODO: this will check for empty tiles. Change to MaskedGrid::from(…) to avoid this.
@@ -239,13 +273,13 @@ where
     )
 }
 
-fn create_enlarged_tile<P: Pixel, A: AggregateFunction>(
+fn create_enlarged_tile<P: Pixel, K: NeighborhoodKernel<P>>(
     tile_info: TileInformation,
     query_rect: &RasterQueryRectangle,
     pool: Arc<ThreadPool>,
     tiling_specification: TilingSpecification,
-    neighborhood: Neighborhood,
-) -> NeighborhoodAggregateAccu<P, A> {
+    kernel: K,
+) -> TileNeighborhoodAccu<P, K> {
     // create an accumulator as a single tile that fits all the input tiles + some margin for the kernel size
 
     let tiling = tiling_specification.strategy(
@@ -262,8 +296,8 @@ fn create_enlarged_tile<P: Pixel, A: AggregateFunction>(
     );
 
     let shape = [
-        tiling.tile_size_in_pixels.axis_size_y() + 2 * neighborhood.y_radius(),
-        tiling.tile_size_in_pixels.axis_size_x() + 2 * neighborhood.x_radius(),
+        tiling.tile_size_in_pixels.axis_size_y() + 2 * kernel.y_radius(),
+        tiling.tile_size_in_pixels.axis_size_x() + 2 * kernel.x_radius(),
     ];
 
     // create a non-aligned (w.r.t. the tiling specification) grid by setting the origin to the top-left of the tile and the tile-index to [0, 0]
@@ -278,22 +312,19 @@ fn create_enlarged_tile<P: Pixel, A: AggregateFunction>(
         CacheHint::max_duration(),
     );
 
-    NeighborhoodAggregateAccu::new(input_tile, tile_info, pool, neighborhood)
+    TileNeighborhoodAccu::new(input_tile, tile_info, pool, kernel)
 }
 
-type FoldFutureFn<P, F> = fn(
-    Result<Result<NeighborhoodAggregateAccu<P, F>>, tokio::task::JoinError>,
-) -> Result<NeighborhoodAggregateAccu<P, F>>;
-type FoldFuture<P, F> =
-    futures::future::Map<JoinHandle<Result<NeighborhoodAggregateAccu<P, F>>>, FoldFutureFn<P, F>>;
+type FoldFutureFn<P, K> = fn(
+    Result<Result<TileNeighborhoodAccu<P, K>>, tokio::task::JoinError>,
+) -> Result<TileNeighborhoodAccu<P, K>>;
+type FoldFuture<P, K> =
+    futures::future::Map<JoinHandle<Result<TileNeighborhoodAccu<P, K>>>, FoldFutureFn<P, K>>;
 
 /// Turn a result of results into a result
-fn flatten_result<P: Pixel, F: AggregateFunction>(
-    result: Result<Result<NeighborhoodAggregateAccu<P, F>>, tokio::task::JoinError>,
-) -> Result<NeighborhoodAggregateAccu<P, F>>
-where
-    f64: AsPrimitive<P>,
-{
+fn flatten_result<P: Pixel, K: NeighborhoodKernel<P>>(
+    result: Result<Result<TileNeighborhoodAccu<P, K>>, tokio::task::JoinError>,
+) -> Result<TileNeighborhoodAccu<P, K>> {
     match result {
         Ok(r) => r,
         Err(e) => Err(e.into()),
@@ -301,13 +332,10 @@ where
 }
 
 /// Merge, step by step, the 9 input tiles into the larger accumulator tile
-pub fn merge_tile_into_enlarged_tile<P: Pixel, F: AggregateFunction>(
-    mut accu: NeighborhoodAggregateAccu<P, F>,
+pub fn merge_tile_into_enlarged_tile<P: Pixel, K: NeighborhoodKernel<P>>(
+    mut accu: TileNeighborhoodAccu<P, K>,
     tile: RasterTile2D<P>,
-) -> Result<NeighborhoodAggregateAccu<P, F>>
-where
-    f64: AsPrimitive<P>,
-{
+) -> Result<TileNeighborhoodAccu<P, K>> {
     // get the time now because it is not known when the accu was created
     accu.input_tile.time = tile.time;
 
@@ -322,11 +350,11 @@ where
 
     let accu_input_tile: RasterTile2D<P> = accu_input_tile.into();
 
-    Ok(NeighborhoodAggregateAccu::new(
+    Ok(TileNeighborhoodAccu::new(
         accu_input_tile,
         accu.output_info,
         accu.pool,
-        accu.neighborhood,
+        accu.kernel,
     ))
 }
 
@@ -372,10 +400,12 @@ mod tests {
             attributes: BandSelection::first(),
         };
 
-        let aggregator = NeighborhoodAggregateTileNeighborhood::<u8, StandardDeviation>::new(
-            NeighborhoodParams::Rectangle { dimensions: [5, 5] }
-                .try_into()
-                .unwrap(),
+        let aggregator = TileNeighborhood::<u8, _>::new(
+            AggregateKernel::<StandardDeviation>::new(
+                NeighborhoodParams::Rectangle { dimensions: [5, 5] }
+                    .try_into()
+                    .unwrap(),
+            ),
             execution_context.tiling_specification,
         );
 
@@ -393,12 +423,12 @@ mod tests {
             SpatialPartition2D::new((-2., 514.).into(), (514., -2.).into()).unwrap()
         );
 
-        let accu = create_enlarged_tile::<u8, Sum>(
+        let accu = create_enlarged_tile::<u8, _>(
             tile_info,
             &tile_query_rectangle,
             execution_context.thread_pool.clone(),
             execution_context.tiling_specification,
-            aggregator.neighborhood,
+            AggregateKernel::<Sum>::new(aggregator.kernel.neighborhood),
         );
 
         assert_eq!(tile_info.tile_size_in_pixels.axis_size(), [512, 512]);
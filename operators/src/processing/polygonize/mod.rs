@@ -0,0 +1,548 @@
+mod regions;
+
+use crate::engine::{
+    BoxRasterQueryProcessor, CanonicOperatorName, ExecutionContext, InitializedRasterOperator,
+    InitializedSources, InitializedVectorOperator, Operator, OperatorName, QueryContext,
+    QueryProcessor, SingleRasterSource, TypedVectorQueryProcessor, VectorColumnInfo,
+    VectorOperator, VectorQueryProcessor, VectorResultDescriptor, WorkflowOperatorPath,
+};
+use crate::util::raster_mosaic::{mosaic_raster_stream, RasterMosaic};
+use crate::util::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use geoengine_datatypes::collections::{MultiPolygonCollection, VectorDataType};
+use geoengine_datatypes::primitives::{
+    AxisAlignedRectangle, BandSelection, BoundingBox2D, ColumnSelection, FeatureData,
+    FeatureDataType, MultiPolygon, RasterQueryRectangle, VectorQueryRectangle,
+};
+use geoengine_datatypes::raster::{Pixel, RasterDataType};
+use num_traits::AsPrimitive;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, Snafu};
+use std::collections::HashMap;
+
+use self::regions::polygonize_grid;
+
+/// An operator that converts contiguous regions of equal raster values into polygons.
+///
+/// Each region becomes a feature with the region's value as attribute and the validity of the raster.
+/// Regions are merged across tile borders within the query rectangle.
+/// Thus, the query rectangle must not exceed [`MAX_MOSAIC_PIXELS`](crate::util::raster_mosaic::MAX_MOSAIC_PIXELS) pixels.
+pub type Polygonize = Operator<PolygonizeParams, SingleRasterSource>;
+
+impl OperatorName for Polygonize {
+    const TYPE_NAME: &'static str = "Polygonize";
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolygonizeParams {
+    /// Which neighboring pixels belong to the same region.
+    /// The default is [`Connectivity::Four`].
+    #[serde(default)]
+    pub connectivity: Connectivity,
+
+    /// Only pixels with one of these values are polygonized, e.g., the classes of interest of a classification.
+    /// If `None`, all pixels except no data are polygonized.
+    #[serde(default)]
+    pub values: Option<Vec<f64>>,
+
+    /// The name of the column that stores the value of a region.
+    /// The default is `value`.
+    #[serde(default = "default_value_column")]
+    pub value_column: String,
+}
+
+fn default_value_column() -> String {
+    "value".into()
+}
+
+/// Specifies which pixels are neighbors
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Connectivity {
+    /// Pixels are connected if they share an edge
+    #[default]
+    Four,
+    /// Pixels are connected if they share an edge or a corner
+    Eight,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)), module(error))]
+pub enum PolygonizeError {
+    #[snafu(display("The name of the value column must not be empty"))]
+    EmptyValueColumn,
+}
+
+#[typetag::serde]
+#[async_trait]
+impl VectorOperator for Polygonize {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedVectorOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        ensure!(
+            !self.params.value_column.is_empty(),
+            error::EmptyValueColumn
+        );
+
+        let initialized_sources = self.sources.initialize_sources(path, context).await?;
+        let raster_source = initialized_sources.raster;
+
+        let in_descriptor = raster_source.result_descriptor();
+
+        ensure!(
+            in_descriptor.bands.len() == 1,
+            crate::error::OperatorDoesNotSupportMultiBandsSources {
+                operator: Polygonize::TYPE_NAME
+            }
+        );
+
+        let value_column_info = VectorColumnInfo {
+            data_type: value_column_type(in_descriptor.data_type),
+            measurement: in_descriptor.bands.bands()[0].measurement.clone(),
+        };
+
+        let result_descriptor = VectorResultDescriptor {
+            data_type: VectorDataType::MultiPolygon,
+            spatial_reference: in_descriptor.spatial_reference,
+            columns: [(self.params.value_column.clone(), value_column_info)]
+                .into_iter()
+                .collect(),
+            time: in_descriptor.time,
+            bbox: in_descriptor.bbox.map(|bbox| bbox.as_bbox()),
+        };
+
+        let initialized_operator = InitializedPolygonize {
+            name,
+            result_descriptor,
+            raster_source,
+            connectivity: self.params.connectivity,
+            values: self.params.values,
+            value_column: self.params.value_column,
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(Polygonize);
+}
+
+/// The column type of the values of a raster with the given `RasterDataType`
+fn value_column_type(data_type: RasterDataType) -> FeatureDataType {
+    match data_type {
+        RasterDataType::U8
+        | RasterDataType::U16
+        | RasterDataType::U32
+        | RasterDataType::U64
+        | RasterDataType::I8
+        | RasterDataType::I16
+        | RasterDataType::I32
+        | RasterDataType::I64 => FeatureDataType::Int,
+        RasterDataType::F32 | RasterDataType::F64 => FeatureDataType::Float,
+    }
+}
+
+pub struct InitializedPolygonize {
+    name: CanonicOperatorName,
+    result_descriptor: VectorResultDescriptor,
+    raster_source: Box<dyn InitializedRasterOperator>,
+    connectivity: Connectivity,
+    values: Option<Vec<f64>>,
+    value_column: String,
+}
+
+impl InitializedVectorOperator for InitializedPolygonize {
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn query_processor(&self) -> Result<TypedVectorQueryProcessor> {
+        let source_processor = self.raster_source.query_processor()?;
+
+        let processor = call_on_generic_raster_processor!(source_processor, source => {
+            PolygonizeProcessor {
+                source,
+                result_descriptor: self.result_descriptor.clone(),
+                connectivity: self.connectivity,
+                values: self.values.clone(),
+                value_column: self.value_column.clone(),
+            }
+            .boxed()
+        });
+
+        Ok(TypedVectorQueryProcessor::MultiPolygon(processor))
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+pub struct PolygonizeProcessor<P: Pixel> {
+    source: BoxRasterQueryProcessor<P>,
+    result_descriptor: VectorResultDescriptor,
+    connectivity: Connectivity,
+    values: Option<Vec<f64>>,
+    value_column: String,
+}
+
+#[async_trait]
+impl<P: Pixel> QueryProcessor for PolygonizeProcessor<P> {
+    type Output = MultiPolygonCollection;
+    type SpatialBounds = BoundingBox2D;
+    type Selection = ColumnSelection;
+    type ResultDescription = VectorResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: VectorQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        let raster_query =
+            RasterQueryRectangle::from_qrect_and_bands(&query, BandSelection::first());
+        let spatial_bounds = raster_query.spatial_bounds;
+
+        let tiles = self.source.raster_query(raster_query, ctx).await?;
+
+        let collections =
+            mosaic_raster_stream(tiles, spatial_bounds).and_then(move |mosaic| async move {
+                let connectivity = self.connectivity;
+                let values = self.values.clone();
+                let value_column = self.value_column.clone();
+
+                crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
+                    polygonize_mosaic(mosaic, connectivity, values.as_deref(), &value_column)
+                })
+                .await?
+            });
+
+        Ok(collections.boxed())
+    }
+
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+fn polygonize_mosaic<P: Pixel>(
+    mosaic: RasterMosaic<P>,
+    connectivity: Connectivity,
+    values: Option<&[f64]>,
+    value_column: &str,
+) -> Result<MultiPolygonCollection> {
+    let pixels = mosaic
+        .pixels
+        .into_iter()
+        .map(|pixel| {
+            pixel.filter(|&value| {
+                let value: f64 = value.as_();
+                !value.is_nan() && values.map_or(true, |values| values.contains(&value))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let regions = polygonize_grid(&pixels, mosaic.width, mosaic.height, connectivity);
+
+    let geo_transform = mosaic.geo_transform;
+    let polygons = regions
+        .iter()
+        .map(|region| {
+            let rings = region
+                .rings
+                .iter()
+                .map(|ring| {
+                    ring.iter()
+                        .map(|&(row, col)| {
+                            geo_transform.grid_idx_to_pixel_upper_left_coordinate_2d(
+                                [row as isize, col as isize].into(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect();
+
+            MultiPolygon::new(vec![rings])
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let value_data = match value_column_type(P::TYPE) {
+        FeatureDataType::Float => FeatureData::Float(
+            regions
+                .iter()
+                .map(|region| AsPrimitive::<f64>::as_(region.value))
+                .collect(),
+        ),
+        _ => FeatureData::Int(
+            regions
+                .iter()
+                .map(|region| AsPrimitive::<i64>::as_(region.value))
+                .collect(),
+        ),
+    };
+
+    MultiPolygonCollection::from_data(
+        polygons,
+        vec![mosaic.time; regions.len()],
+        HashMap::from([(value_column.to_string(), value_data)]),
+        mosaic.cache_hint,
+    )
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{
+        MockExecutionContext, MockQueryContext, RasterBandDescriptors, RasterOperator,
+        RasterResultDescriptor,
+    };
+    use crate::mock::{MockRasterSource, MockRasterSourceParams};
+    use geoengine_datatypes::collections::ChunksEqualIgnoringCacheHint;
+    use geoengine_datatypes::primitives::{
+        CacheHint, Coordinate2D, SpatialResolution, TimeInterval,
+    };
+    use geoengine_datatypes::raster::{
+        Grid2D, GridOrEmpty, MaskedGrid2D, RasterTile2D, TileInformation, TilingSpecification,
+    };
+    use geoengine_datatypes::spatial_reference::SpatialReference;
+    use geoengine_datatypes::util::test::TestDefault;
+
+    fn make_tile(tile_x: isize, values: Vec<u8>, validity: Vec<bool>) -> RasterTile2D<u8> {
+        RasterTile2D::new_with_tile_info(
+            TimeInterval::new_unchecked(0, 10),
+            TileInformation {
+                global_tile_position: [-1, tile_x].into(),
+                tile_size_in_pixels: [2, 2].into(),
+                global_geo_transform: TestDefault::test_default(),
+            },
+            0,
+            GridOrEmpty::from(
+                MaskedGrid2D::new(
+                    Grid2D::new([2, 2].into(), values).unwrap(),
+                    Grid2D::new([2, 2].into(), validity).unwrap(),
+                )
+                .unwrap(),
+            ),
+            CacheHint::default(),
+        )
+    }
+
+    fn make_raster(tiles: Vec<RasterTile2D<u8>>) -> Box<dyn RasterOperator> {
+        MockRasterSource {
+            params: MockRasterSourceParams {
+                data: tiles,
+                result_descriptor: RasterResultDescriptor {
+                    data_type: RasterDataType::U8,
+                    spatial_reference: SpatialReference::epsg_4326().into(),
+                    time: None,
+                    bbox: None,
+                    resolution: None,
+                    bands: RasterBandDescriptors::new_single_band(),
+                },
+            },
+        }
+        .boxed()
+    }
+
+    async fn polygonize(
+        raster: Box<dyn RasterOperator>,
+        params: PolygonizeParams,
+        spatial_bounds: BoundingBox2D,
+    ) -> Vec<MultiPolygonCollection> {
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [2, 2].into(),
+        ));
+
+        let operator = Polygonize {
+            params,
+            sources: raster.into(),
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await
+        .unwrap();
+
+        let processor = operator.query_processor().unwrap().multi_polygon().unwrap();
+
+        let query_ctx = MockQueryContext::test_default();
+        let stream = processor
+            .query(
+                VectorQueryRectangle {
+                    spatial_bounds,
+                    time_interval: TimeInterval::new_unchecked(0, 10),
+                    spatial_resolution: SpatialResolution::one(),
+                    attributes: ColumnSelection::all(),
+                },
+                &query_ctx,
+            )
+            .await
+            .unwrap();
+
+        stream.map(Result::unwrap).collect().await
+    }
+
+    fn polygon(coordinates: &[(f64, f64)]) -> MultiPolygon {
+        MultiPolygon::new(vec![vec![coordinates
+            .iter()
+            .copied()
+            .map(Coordinate2D::from)
+            .collect()]])
+        .unwrap()
+    }
+
+    #[test]
+    fn it_deserializes_the_params() {
+        let params: PolygonizeParams =
+            serde_json::from_value(serde_json::json!({ "connectivity": "eight" })).unwrap();
+
+        assert_eq!(
+            params,
+            PolygonizeParams {
+                connectivity: Connectivity::Eight,
+                values: None,
+                value_column: "value".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn it_merges_regions_across_tiles() {
+        // 1 1 | 1 1
+        // 2 2 | 1 1
+        let raster = make_raster(vec![
+            make_tile(0, vec![1, 1, 2, 2], vec![true; 4]),
+            make_tile(1, vec![1, 1, 1, 1], vec![true; 4]),
+        ]);
+
+        let collections = polygonize(
+            raster,
+            PolygonizeParams {
+                connectivity: Connectivity::Four,
+                values: None,
+                value_column: "class".to_string(),
+            },
+            BoundingBox2D::new_unchecked((0., 0.).into(), (4., 2.).into()),
+        )
+        .await;
+
+        assert_eq!(collections.len(), 1);
+
+        let expected = MultiPolygonCollection::from_data(
+            vec![
+                polygon(&[
+                    (0., 2.),
+                    (4., 2.),
+                    (4., 0.),
+                    (2., 0.),
+                    (2., 1.),
+                    (0., 1.),
+                    (0., 2.),
+                ]),
+                polygon(&[(0., 1.), (2., 1.), (2., 0.), (0., 0.), (0., 1.)]),
+            ],
+            vec![TimeInterval::new_unchecked(0, 10); 2],
+            HashMap::from([("class".to_string(), FeatureData::Int(vec![1, 2]))]),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        assert!(collections[0].chunks_equal_ignoring_cache_hint(&expected));
+    }
+
+    #[tokio::test]
+    async fn it_filters_values_and_respects_the_connectivity() {
+        // 1 _ | 0 _
+        // _ 1 | 0 1
+        let tiles = vec![
+            make_tile(0, vec![1, 0, 0, 1], vec![true, false, false, true]),
+            make_tile(1, vec![0, 0, 0, 1], vec![true, false, true, true]),
+        ];
+        let spatial_bounds = BoundingBox2D::new_unchecked((0., 0.).into(), (4., 2.).into());
+
+        let collections = polygonize(
+            make_raster(tiles.clone()),
+            PolygonizeParams {
+                connectivity: Connectivity::Four,
+                values: Some(vec![1.]),
+                value_column: "value".to_string(),
+            },
+            spatial_bounds,
+        )
+        .await;
+
+        let expected = MultiPolygonCollection::from_data(
+            vec![
+                polygon(&[(0., 2.), (1., 2.), (1., 1.), (0., 1.), (0., 2.)]),
+                polygon(&[(1., 1.), (2., 1.), (2., 0.), (1., 0.), (1., 1.)]),
+                polygon(&[(3., 1.), (4., 1.), (4., 0.), (3., 0.), (3., 1.)]),
+            ],
+            vec![TimeInterval::new_unchecked(0, 10); 3],
+            HashMap::from([("value".to_string(), FeatureData::Int(vec![1, 1, 1]))]),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        assert!(collections[0].chunks_equal_ignoring_cache_hint(&expected));
+
+        let collections = polygonize(
+            make_raster(tiles),
+            PolygonizeParams {
+                connectivity: Connectivity::Eight,
+                values: Some(vec![1.]),
+                value_column: "value".to_string(),
+            },
+            spatial_bounds,
+        )
+        .await;
+
+        let expected = MultiPolygonCollection::from_data(
+            vec![
+                polygon(&[
+                    (0., 2.),
+                    (1., 2.),
+                    (1., 1.),
+                    (2., 1.),
+                    (2., 0.),
+                    (1., 0.),
+                    (1., 1.),
+                    (0., 1.),
+                    (0., 2.),
+                ]),
+                polygon(&[(3., 1.), (4., 1.), (4., 0.), (3., 0.), (3., 1.)]),
+            ],
+            vec![TimeInterval::new_unchecked(0, 10); 2],
+            HashMap::from([("value".to_string(), FeatureData::Int(vec![1, 1]))]),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        assert!(collections[0].chunks_equal_ignoring_cache_hint(&expected));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_an_empty_value_column() {
+        let result = Polygonize {
+            params: PolygonizeParams {
+                connectivity: Connectivity::Four,
+                values: None,
+                value_column: String::new(),
+            },
+            sources: make_raster(vec![]).into(),
+        }
+        .boxed()
+        .initialize(
+            WorkflowOperatorPath::initialize_root(),
+            &MockExecutionContext::test_default(),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
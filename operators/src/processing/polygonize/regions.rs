@@ -0,0 +1,342 @@
+use super::Connectivity;
+use std::collections::BTreeSet;
+
+/// A pixel corner as (row, column) of the grid
+pub type Vertex = (usize, usize);
+
+/// A contiguous region of pixels with the same value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region<T> {
+    pub value: T,
+    /// The closed rings of pixel corners that bound the region.
+    /// The first ring is the exterior, all others are holes.
+    pub rings: Vec<Vec<Vertex>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Direction {
+    fn step(self, (row, col): Vertex) -> Vertex {
+        match self {
+            Direction::Right => (row, col + 1),
+            Direction::Down => (row + 1, col),
+            Direction::Left => (row, col - 1),
+            Direction::Up => (row - 1, col),
+        }
+    }
+
+    /// Turn clockwise, with rows pointing downwards
+    fn turn_right(self) -> Self {
+        match self {
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Up => Direction::Right,
+        }
+    }
+
+    /// Turn counter-clockwise, with rows pointing downwards
+    fn turn_left(self) -> Self {
+        match self {
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+            Direction::Up => Direction::Left,
+        }
+    }
+}
+
+/// A boundary edge of one pixel that starts at the vertex and is oriented s.t. the pixel is on its right
+type Edge = (Vertex, Direction);
+
+const FOUR_NEIGHBORS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const EIGHT_NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Find all regions of connected pixels with equal values in a row-major grid and trace their boundaries.
+///
+/// Pixels that are `None` do not belong to any region.
+/// The regions are ordered by their first pixel in row-major order.
+pub fn polygonize_grid<T>(
+    pixels: &[Option<T>],
+    width: usize,
+    height: usize,
+    connectivity: Connectivity,
+) -> Vec<Region<T>>
+where
+    T: Copy + PartialEq,
+{
+    debug_assert_eq!(pixels.len(), width * height);
+
+    let (labels, regions) = label_regions(pixels, width, height, connectivity);
+
+    regions
+        .into_iter()
+        .enumerate()
+        .map(|(label, (value, cells))| {
+            let edges = boundary_edges(&labels, width, height, label, &cells);
+            let mut rings = trace_rings(edges, connectivity);
+
+            // the exterior encloses all holes and thus has the largest area
+            if let Some(exterior_idx) = rings
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, ring)| ring_area_twice(ring))
+                .map(|(idx, _)| idx)
+            {
+                rings.swap(0, exterior_idx);
+            }
+
+            Region { value, rings }
+        })
+        .collect()
+}
+
+/// The value and the pixel indices of a region
+type RegionCells<T> = (T, Vec<usize>);
+
+/// Assign a region label to each pixel and collect the value and the pixels of each region
+fn label_regions<T>(
+    pixels: &[Option<T>],
+    width: usize,
+    height: usize,
+    connectivity: Connectivity,
+) -> (Vec<Option<usize>>, Vec<RegionCells<T>>)
+where
+    T: Copy + PartialEq,
+{
+    let neighbors: &[(isize, isize)] = match connectivity {
+        Connectivity::Four => &FOUR_NEIGHBORS,
+        Connectivity::Eight => &EIGHT_NEIGHBORS,
+    };
+
+    let mut labels = vec![None; pixels.len()];
+    let mut regions = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..pixels.len() {
+        let Some(value) = pixels[start] else {
+            continue;
+        };
+        if labels[start].is_some() {
+            continue;
+        }
+
+        let label = regions.len();
+        let mut cells = Vec::new();
+
+        labels[start] = Some(label);
+        stack.push(start);
+
+        while let Some(cell) = stack.pop() {
+            cells.push(cell);
+
+            let (row, col) = (cell / width, cell % width);
+
+            for &(d_row, d_col) in neighbors {
+                let (Some(n_row), Some(n_col)) =
+                    (row.checked_add_signed(d_row), col.checked_add_signed(d_col))
+                else {
+                    continue;
+                };
+                if n_row >= height || n_col >= width {
+                    continue;
+                }
+
+                let neighbor = n_row * width + n_col;
+                if labels[neighbor].is_none() && pixels[neighbor] == Some(value) {
+                    labels[neighbor] = Some(label);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        cells.sort_unstable();
+        regions.push((value, cells));
+    }
+
+    (labels, regions)
+}
+
+/// Collect all pixel edges of a region that do not border another pixel of the same region
+fn boundary_edges(
+    labels: &[Option<usize>],
+    width: usize,
+    height: usize,
+    label: usize,
+    cells: &[usize],
+) -> BTreeSet<Edge> {
+    let is_outside = |row: usize, col: usize| labels[row * width + col] != Some(label);
+
+    let mut edges = BTreeSet::new();
+
+    for &cell in cells {
+        let (row, col) = (cell / width, cell % width);
+
+        if row == 0 || is_outside(row - 1, col) {
+            edges.insert(((row, col), Direction::Right));
+        }
+        if col + 1 == width || is_outside(row, col + 1) {
+            edges.insert(((row, col + 1), Direction::Down));
+        }
+        if row + 1 == height || is_outside(row + 1, col) {
+            edges.insert(((row + 1, col + 1), Direction::Left));
+        }
+        if col == 0 || is_outside(row, col - 1) {
+            edges.insert(((row + 1, col), Direction::Up));
+        }
+    }
+
+    edges
+}
+
+/// Link the boundary edges of a region to closed rings.
+///
+/// If two diagonal pixels of the region touch at a vertex, the rings are separated there for
+/// [`Connectivity::Four`] and joined for [`Connectivity::Eight`].
+/// Vertices in the middle of straight lines are omitted.
+fn trace_rings(mut edges: BTreeSet<Edge>, connectivity: Connectivity) -> Vec<Vec<Vertex>> {
+    let mut rings = Vec::new();
+
+    // the smallest remaining edge always starts at the upper left corner of its ring
+    while let Some(start) = edges.pop_first() {
+        let (mut vertex, mut direction) = start;
+        let mut ring = vec![vertex];
+
+        loop {
+            vertex = direction.step(vertex);
+
+            let candidates = match connectivity {
+                Connectivity::Four => [direction.turn_right(), direction, direction.turn_left()],
+                Connectivity::Eight => [direction.turn_left(), direction, direction.turn_right()],
+            };
+
+            let Some(next) = candidates
+                .into_iter()
+                .map(|candidate| (vertex, candidate))
+                .find(|edge| *edge == start || edges.contains(edge))
+            else {
+                // cannot happen for edges of pixels, but ensures termination
+                break;
+            };
+
+            if next == start {
+                break;
+            }
+
+            edges.remove(&next);
+
+            if next.1 != direction {
+                ring.push(vertex);
+            }
+            direction = next.1;
+        }
+
+        ring.push(start.0);
+        rings.push(ring);
+    }
+
+    rings
+}
+
+/// The absolute area of a closed ring, multiplied by two to stay integral
+fn ring_area_twice(ring: &[Vertex]) -> usize {
+    let signed_area: isize = ring
+        .windows(2)
+        .map(|w| {
+            let ((row_a, col_a), (row_b, col_b)) = (w[0], w[1]);
+            (col_a * row_b) as isize - (col_b * row_a) as isize
+        })
+        .sum();
+
+    signed_area.unsigned_abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_traces_holes() {
+        #[rustfmt::skip]
+        let pixels = vec![
+            Some(1), Some(1), Some(1),
+            Some(1), Some(2), Some(1),
+            Some(1), Some(1), Some(1),
+        ];
+
+        let regions = polygonize_grid(&pixels, 3, 3, Connectivity::Four);
+
+        assert_eq!(
+            regions,
+            vec![
+                Region {
+                    value: 1,
+                    rings: vec![
+                        vec![(0, 0), (0, 3), (3, 3), (3, 0), (0, 0)],
+                        vec![(1, 1), (2, 1), (2, 2), (1, 2), (1, 1)],
+                    ],
+                },
+                Region {
+                    value: 2,
+                    rings: vec![vec![(1, 1), (1, 2), (2, 2), (2, 1), (1, 1)]],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_respects_the_connectivity() {
+        #[rustfmt::skip]
+        let pixels = vec![
+            Some(1), None,
+            None,    Some(1),
+        ];
+
+        assert_eq!(
+            polygonize_grid(&pixels, 2, 2, Connectivity::Four),
+            vec![
+                Region {
+                    value: 1,
+                    rings: vec![vec![(0, 0), (0, 1), (1, 1), (1, 0), (0, 0)]],
+                },
+                Region {
+                    value: 1,
+                    rings: vec![vec![(1, 1), (1, 2), (2, 2), (2, 1), (1, 1)]],
+                },
+            ]
+        );
+
+        assert_eq!(
+            polygonize_grid(&pixels, 2, 2, Connectivity::Eight),
+            vec![Region {
+                value: 1,
+                rings: vec![vec![
+                    (0, 0),
+                    (0, 1),
+                    (1, 1),
+                    (1, 2),
+                    (2, 2),
+                    (2, 1),
+                    (1, 1),
+                    (1, 0),
+                    (0, 0)
+                ]],
+            }]
+        );
+    }
+}
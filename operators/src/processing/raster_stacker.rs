@@ -1003,6 +1003,7 @@ mod tests {
                 expression: "if A > 100 { A } else { 0 }".into(),
                 output_type: RasterDataType::U8,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
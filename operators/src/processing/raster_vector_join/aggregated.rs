@@ -1,13 +1,14 @@
 use futures::stream::BoxStream;
 use futures::{StreamExt, TryStreamExt};
+use std::collections::HashMap;
 
 use geoengine_datatypes::collections::{
     FeatureCollection, FeatureCollectionInfos, FeatureCollectionModifications,
 };
 use geoengine_datatypes::primitives::{
-    BandSelection, CacheHint, ColumnSelection, RasterQueryRectangle,
+    BandSelection, CacheHint, ColumnSelection, FeatureDataType, RasterQueryRectangle,
 };
-use geoengine_datatypes::raster::{GridIndexAccess, Pixel, RasterDataType};
+use geoengine_datatypes::raster::{GridIdx2D, GridIndexAccess, Pixel};
 use geoengine_datatypes::util::arrow::ArrowTyped;
 
 use crate::engine::{
@@ -15,8 +16,8 @@ use crate::engine::{
     VectorResultDescriptor,
 };
 use crate::processing::raster_vector_join::aggregator::{
-    Aggregator, FirstValueFloatAggregator, FirstValueIntAggregator, MeanValueAggregator,
-    TypedAggregator,
+    Aggregator, FirstValueFloatAggregator, FirstValueIntAggregator, MaxValueAggregator,
+    MeanValueAggregator, MinValueAggregator, TypedAggregator,
 };
 use crate::processing::raster_vector_join::TemporalAggregationMethod;
 use crate::util::Result;
@@ -24,7 +25,7 @@ use async_trait::async_trait;
 use geoengine_datatypes::primitives::{BoundingBox2D, Geometry, VectorQueryRectangle};
 
 use super::util::{CoveredPixels, FeatureTimeSpanIter, PixelCoverCreator};
-use super::{create_feature_aggregator, FeatureAggregationMethod, RasterInput};
+use super::{create_feature_aggregators, FeatureAggregationMethod, RasterInput};
 
 pub struct RasterVectorAggregateJoinProcessor<G> {
     collection: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
@@ -32,6 +33,7 @@ pub struct RasterVectorAggregateJoinProcessor<G> {
     raster_inputs: Vec<RasterInput>,
     feature_aggregation: FeatureAggregationMethod,
     feature_aggregation_ignore_no_data: bool,
+    feature_aggregation_area_weighted: bool,
     temporal_aggregation: TemporalAggregationMethod,
     temporal_aggregation_ignore_no_data: bool,
 }
@@ -48,6 +50,7 @@ where
         raster_inputs: Vec<RasterInput>,
         feature_aggregation: FeatureAggregationMethod,
         feature_aggregation_ignore_no_data: bool,
+        feature_aggregation_area_weighted: bool,
         temporal_aggregation: TemporalAggregationMethod,
         temporal_aggregation_ignore_no_data: bool,
     ) -> Self {
@@ -57,6 +60,7 @@ where
             raster_inputs,
             feature_aggregation,
             feature_aggregation_ignore_no_data,
+            feature_aggregation_area_weighted,
             temporal_aggregation,
             temporal_aggregation_ignore_no_data,
         }
@@ -67,18 +71,25 @@ where
         collection: &FeatureCollection<G>,
         raster_processor: &dyn RasterQueryProcessor<RasterType = P>,
         column_names: &[String],
-        feature_aggreation: FeatureAggregationMethod,
+        feature_aggreation: &FeatureAggregationMethod,
         feature_aggregation_ignore_no_data: bool,
+        feature_aggregation_area_weighted: bool,
         temporal_aggregation: TemporalAggregationMethod,
         temporal_aggregation_ignore_no_data: bool,
         query: VectorQueryRectangle,
         ctx: &dyn QueryContext,
     ) -> Result<FeatureCollection<G>> {
-        let mut temporal_band_aggregators = (0..column_names.len())
+        // each band has one or more output columns, e.g., one for each class
+        let number_of_columns = feature_aggreation.number_of_columns();
+        let number_of_bands = column_names.len() / number_of_columns;
+        let band_columns = |band: usize| band * number_of_columns..(band + 1) * number_of_columns;
+
+        let mut temporal_column_aggregators = (0..column_names.len())
             .map(|_| {
-                Self::create_aggregator::<P>(
+                Self::create_aggregator(
                     collection.len(),
                     temporal_aggregation,
+                    feature_aggreation.output_data_type(P::TYPE),
                     temporal_aggregation_ignore_no_data,
                 )
             })
@@ -109,9 +120,9 @@ where
 
             // TODO: optimize geo access (only specific tiles, etc.)
 
-            let mut feature_band_aggregators = (0..column_names.len())
+            let mut feature_band_aggregators = (0..number_of_bands)
                 .map(|_| {
-                    create_feature_aggregator::<P>(
+                    create_feature_aggregators::<P>(
                         collection.len(),
                         feature_aggreation,
                         feature_aggregation_ignore_no_data,
@@ -121,6 +132,9 @@ where
 
             let mut time_end = None;
 
+            // the covered pixels only depend on the tile, so they are shared by all bands and time steps
+            let mut tile_feature_pixels = HashMap::<[isize; 2], Vec<Vec<(GridIdx2D, f64)>>>::new();
+
             while let Some(raster) = rasters.next().await {
                 let raster = raster?;
                 let band = raster.band as usize;
@@ -129,42 +143,75 @@ where
                     if end != raster.time.end() {
                         // new time slice => consume old aggregator and create new one
 
-                        let new_feature_agg = create_feature_aggregator::<P>(
+                        let new_feature_aggs = create_feature_aggregators::<P>(
                             collection.len(),
                             feature_aggreation,
                             feature_aggregation_ignore_no_data,
                         );
 
-                        let olg_feature_agg =
-                            std::mem::replace(&mut feature_band_aggregators[band], new_feature_agg);
+                        let old_feature_aggs = std::mem::replace(
+                            &mut feature_band_aggregators[band],
+                            new_feature_aggs,
+                        );
 
-                        temporal_band_aggregators[band].add_feature_data(
-                            olg_feature_agg.into_data(),
-                            time_span.time_interval.duration_ms(), // TODO: use individual feature duration?
-                        )?;
+                        for (column, old_feature_agg) in band_columns(band).zip(old_feature_aggs) {
+                            temporal_column_aggregators[column].add_feature_data(
+                                old_feature_agg.into_data(),
+                                time_span.time_interval.duration_ms() as f64, // TODO: use individual feature duration?
+                            )?;
+                        }
 
-                        if temporal_band_aggregators[band].is_satisfied() {
+                        if temporal_column_aggregators[band_columns(band)]
+                            .iter()
+                            .all(TypedAggregator::is_satisfied)
+                        {
                             break;
                         }
                     }
                 }
                 time_end = Some(raster.time.end());
 
-                for feature_index in time_span.feature_index_start..=time_span.feature_index_end {
+                let feature_pixels = tile_feature_pixels
+                    .entry(raster.tile_position.0)
+                    .or_insert_with(|| {
+                        (time_span.feature_index_start..=time_span.feature_index_end)
+                            .map(|feature_index| {
+                                if feature_aggregation_area_weighted {
+                                    covered_pixels.covered_pixel_fractions(feature_index, &raster)
+                                } else {
+                                    covered_pixels
+                                        .covered_pixels(feature_index, &raster)
+                                        .into_iter()
+                                        .map(|grid_idx| (grid_idx, 1.))
+                                        .collect()
+                                }
+                            })
+                            .collect()
+                    });
+
+                for (feature_index, feature_pixels) in
+                    (time_span.feature_index_start..).zip(feature_pixels.iter())
+                {
                     // TODO: don't do random access but use a single iterator
                     let mut satisfied = false;
-                    for grid_idx in covered_pixels.covered_pixels(feature_index, &raster) {
+
+                    for &(grid_idx, weight) in feature_pixels {
                         // try to get the pixel if the coordinate is within the current tile
                         if let Ok(pixel) = raster.get_at_grid_index(grid_idx) {
                             // finally, attach value to feature
-                            if let Some(data) = pixel {
-                                feature_band_aggregators[band].add_value(feature_index, data, 1);
-                            } else {
-                                // TODO: weigh by area?
-                                feature_band_aggregators[band].add_null(feature_index);
+                            for feature_aggregator in &mut feature_band_aggregators[band] {
+                                if let Some(data) = pixel {
+                                    feature_aggregator.add_value(feature_index, data, weight);
+                                } else {
+                                    // TODO: weigh by area?
+                                    feature_aggregator.add_null(feature_index);
+                                }
                             }
 
-                            if feature_band_aggregators[band].is_satisfied() {
+                            if feature_band_aggregators[band]
+                                .iter()
+                                .all(TypedAggregator::is_satisfied)
+                            {
                                 satisfied = true;
                                 break;
                             }
@@ -178,19 +225,24 @@ where
 
                 cache_hint.merge_with(&raster.cache_hint);
             }
-            for (band, feature_aggregator) in feature_band_aggregators.into_iter().enumerate() {
-                temporal_band_aggregators[band].add_feature_data(
+            for (column, feature_aggregator) in
+                feature_band_aggregators.into_iter().flatten().enumerate()
+            {
+                temporal_column_aggregators[column].add_feature_data(
                     feature_aggregator.into_data(),
-                    time_span.time_interval.duration_ms(), // TODO: use individual feature duration?
+                    time_span.time_interval.duration_ms() as f64, // TODO: use individual feature duration?
                 )?;
             }
 
-            if (0..column_names.len()).all(|band| temporal_band_aggregators[band].is_satisfied()) {
+            if temporal_column_aggregators
+                .iter()
+                .all(TypedAggregator::is_satisfied)
+            {
                 break;
             }
         }
 
-        let feature_data = temporal_band_aggregators
+        let feature_data = temporal_column_aggregators
             .into_iter()
             .map(TypedAggregator::into_data);
 
@@ -209,30 +261,37 @@ where
         Ok(new_collection)
     }
 
-    fn create_aggregator<P: Pixel>(
+    /// Creates the temporal aggregator for feature aggregates of the given `feature_data_type`
+    fn create_aggregator(
         number_of_features: usize,
         aggregation: TemporalAggregationMethod,
+        feature_data_type: FeatureDataType,
         ignore_no_data: bool,
     ) -> TypedAggregator {
+        let int_output = feature_data_type == FeatureDataType::Int;
+
         match aggregation {
-            TemporalAggregationMethod::First => match P::TYPE {
-                RasterDataType::U8
-                | RasterDataType::U16
-                | RasterDataType::U32
-                | RasterDataType::U64
-                | RasterDataType::I8
-                | RasterDataType::I16
-                | RasterDataType::I32
-                | RasterDataType::I64 => {
-                    FirstValueIntAggregator::new(number_of_features, ignore_no_data).into_typed()
-                }
-                RasterDataType::F32 | RasterDataType::F64 => {
-                    FirstValueFloatAggregator::new(number_of_features, ignore_no_data).into_typed()
-                }
-            },
+            TemporalAggregationMethod::First if int_output => {
+                FirstValueIntAggregator::new(number_of_features, ignore_no_data).into_typed()
+            }
+            TemporalAggregationMethod::First => {
+                FirstValueFloatAggregator::new(number_of_features, ignore_no_data).into_typed()
+            }
             TemporalAggregationMethod::Mean => {
                 MeanValueAggregator::new(number_of_features, ignore_no_data).into_typed()
             }
+            TemporalAggregationMethod::Min if int_output => {
+                MinValueAggregator::<i64>::new(number_of_features, ignore_no_data).into_typed()
+            }
+            TemporalAggregationMethod::Min => {
+                MinValueAggregator::<f64>::new(number_of_features, ignore_no_data).into_typed()
+            }
+            TemporalAggregationMethod::Max if int_output => {
+                MaxValueAggregator::<i64>::new(number_of_features, ignore_no_data).into_typed()
+            }
+            TemporalAggregationMethod::Max => {
+                MaxValueAggregator::<f64>::new(number_of_features, ignore_no_data).into_typed()
+            }
             TemporalAggregationMethod::None => {
                 unreachable!("this type of aggregator does not lead to this kind of processor")
             }
@@ -269,8 +328,9 @@ where
                                 &collection,
                                 raster,
                                 &raster_input.column_names,
-                                self.feature_aggregation,
+                                &self.feature_aggregation,
                                 self.feature_aggregation_ignore_no_data,
+                                self.feature_aggregation_area_weighted,
                                 self.temporal_aggregation,
                                 self.temporal_aggregation_ignore_no_data,
                                 query.clone(),
@@ -375,7 +435,8 @@ mod tests {
             &points,
             &raster_source.query_processor().unwrap().get_u8().unwrap(),
             &["foo".to_string()],
-            FeatureAggregationMethod::First,
+            &FeatureAggregationMethod::First,
+            false,
             false,
             TemporalAggregationMethod::First,
             false,
@@ -471,7 +532,8 @@ mod tests {
             &points,
             &raster_source.query_processor().unwrap().get_u8().unwrap(),
             &["foo".to_string()],
-            FeatureAggregationMethod::First,
+            &FeatureAggregationMethod::First,
+            false,
             false,
             TemporalAggregationMethod::Mean,
             false,
@@ -594,7 +656,8 @@ mod tests {
             &points,
             &raster_source.query_processor().unwrap().get_u8().unwrap(),
             &["foo".to_string()],
-            FeatureAggregationMethod::Mean,
+            &FeatureAggregationMethod::Mean,
+            false,
             false,
             TemporalAggregationMethod::Mean,
             false,
@@ -751,7 +814,8 @@ mod tests {
             &polygons,
             &raster_source.query_processor().unwrap().get_u8().unwrap(),
             &["foo".to_string()],
-            FeatureAggregationMethod::Mean,
+            &FeatureAggregationMethod::Mean,
+            false,
             false,
             TemporalAggregationMethod::Mean,
             false,
@@ -1032,6 +1096,7 @@ mod tests {
             }],
             FeatureAggregationMethod::Mean,
             false,
+            false,
             TemporalAggregationMethod::Mean,
             false,
         );
@@ -1089,4 +1154,138 @@ mod tests {
             .unwrap()
         ));
     }
+
+    #[tokio::test]
+    #[allow(clippy::float_cmp)]
+    async fn polygons_zonal_statistics() {
+        let raster_tile = RasterTile2D::<u8>::new_with_tile_info(
+            TimeInterval::new(0, 10).unwrap(),
+            TileInformation {
+                global_geo_transform: TestDefault::test_default(),
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [3, 2].into(),
+            },
+            0,
+            Grid2D::new([3, 2].into(), vec![1, 2, 3, 4, 5, 6])
+                .unwrap()
+                .into(),
+            CacheHint::default(),
+        );
+
+        let raster_source = MockRasterSource {
+            params: MockRasterSourceParams {
+                data: vec![raster_tile],
+                result_descriptor: RasterResultDescriptor {
+                    data_type: RasterDataType::U8,
+                    spatial_reference: SpatialReference::epsg_4326().into(),
+                    time: None,
+                    bbox: None,
+                    resolution: None,
+                    bands: RasterBandDescriptors::new_single_band(),
+                },
+            },
+        }
+        .boxed();
+
+        let execution_context = MockExecutionContext::new_with_tiling_spec(
+            TilingSpecification::new((0., 0.).into(), [3, 2].into()),
+        );
+
+        let raster_source = raster_source
+            .initialize(WorkflowOperatorPath::initialize_root(), &execution_context)
+            .await
+            .unwrap();
+        let raster_processor = raster_source.query_processor().unwrap().get_u8().unwrap();
+
+        // covers the first pixel completely, its right and lower neighbors by half and the diagonal one by a quarter
+        let polygons = MultiPolygonCollection::from_data(
+            vec![MultiPolygon::new(vec![vec![vec![
+                (-0.5, 0.5).into(),
+                (1.5, 0.5).into(),
+                (1.5, -1.5).into(),
+                (-0.5, -1.5).into(),
+                (-0.5, 0.5).into(),
+            ]]])
+            .unwrap()],
+            vec![TimeInterval::default(); 1],
+            Default::default(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let query = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new((0.0, -3.0).into(), (2.0, 0.0).into()).unwrap(),
+            time_interval: TimeInterval::new(0, 10).unwrap(),
+            spatial_resolution: SpatialResolution::new(1., 1.).unwrap(),
+            attributes: ColumnSelection::all(),
+        };
+        let ctx = MockQueryContext::new(ChunkByteSize::MIN);
+
+        let result = RasterVectorAggregateJoinProcessor::extract_raster_values(
+            &polygons,
+            &raster_processor,
+            &["max".to_string()],
+            &FeatureAggregationMethod::Max,
+            false,
+            false,
+            TemporalAggregationMethod::Max,
+            false,
+            query.clone(),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        if let FeatureDataRef::Int(extracted_data) = result.data("max").unwrap() {
+            assert_eq!(extracted_data.as_ref(), &[4]);
+        } else {
+            unreachable!();
+        }
+
+        let result = RasterVectorAggregateJoinProcessor::extract_raster_values(
+            &polygons,
+            &raster_processor,
+            &["sum".to_string()],
+            &FeatureAggregationMethod::Sum,
+            false,
+            true,
+            TemporalAggregationMethod::First,
+            false,
+            query.clone(),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        if let FeatureDataRef::Float(extracted_data) = result.data("sum").unwrap() {
+            float_cmp::assert_approx_eq!(
+                f64,
+                extracted_data.as_ref()[0],
+                1. + 2. * 0.5 + 3. * 0.5 + 4. * 0.25
+            );
+        } else {
+            unreachable!();
+        }
+
+        let result = RasterVectorAggregateJoinProcessor::extract_raster_values(
+            &polygons,
+            &raster_processor,
+            &["count".to_string()],
+            &FeatureAggregationMethod::Count,
+            false,
+            true,
+            TemporalAggregationMethod::First,
+            false,
+            query,
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        if let FeatureDataRef::Float(extracted_data) = result.data("count").unwrap() {
+            float_cmp::assert_approx_eq!(f64, extracted_data.as_ref()[0], 2.25);
+        } else {
+            unreachable!();
+        }
+    }
 }
@@ -2,174 +2,905 @@ use crate::error;
 use crate::error::Error;
 use crate::util::Result;
 use geoengine_datatypes::primitives::{FeatureData, FeatureDataType};
-use geoengine_datatypes::raster::Pixel;
+use geoengine_datatypes::raster::{FromPrimitive, Pixel};
 use num_traits::AsPrimitive;
 use snafu::ensure;
+use std::collections::HashMap;
+use std::marker::PhantomData;
 
 /// Aggregating raster pixel values for features
 pub trait Aggregator {
-    type Output: Pixel;
+    type Output: AggregatorOutputType;
 
     fn new(number_of_features: usize, ignore_nulls: bool) -> Self;
 
     // TODO: add values for slice
-    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: u64)
+    /// Add a value for a feature. The `weight` is either the covered fraction of the pixel
+    /// or the duration of a time step.
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
     where
         P: Pixel + AsPrimitive<Self::Output>;
 
     fn add_null(&mut self, feature_idx: usize);
 
+    fn feature_data_type() -> FeatureDataType {
+        Self::Output::feature_data_type()
+    }
+
+    /// The current aggregates of all features, regardless of whether they are null
+    fn data(&self) -> Vec<Self::Output>;
+
+    fn nulls(&self) -> &[bool];
+
+    fn into_data(self) -> Vec<Option<Self::Output>>;
+
+    fn into_typed(self) -> TypedAggregator;
+
+    /// Whether an aggregator needs no more values for producing the outcome
+    fn is_satisfied(&self) -> bool;
+
+    /// Add all values from `data` to the aggregator. Fails if the data length doesn't match
+    /// the aggregator or if the data is not compatible with the aggregator.
+    fn add_feature_data(&mut self, data: FeatureData, weight: f64) -> Result<()> {
+        ensure!(
+            data.len() == self.nulls().len(),
+            error::FeatureDataLengthMismatch
+        );
+
+        match data {
+            FeatureData::NullableInt(values) => {
+                for (i, value) in values.into_iter().enumerate() {
+                    if let Some(value) = value {
+                        let value = <Self::Output as FromPrimitive<i64>>::from_(value);
+                        self.add_value(i, value, weight);
+                    } else {
+                        self.add_null(i);
+                    }
+                }
+            }
+            FeatureData::NullableFloat(values) => {
+                for (i, value) in values.into_iter().enumerate() {
+                    if let Some(value) = value {
+                        let value = <Self::Output as FromPrimitive<f64>>::from_(value);
+                        self.add_value(i, value, weight);
+                    } else {
+                        self.add_null(i);
+                    }
+                }
+            }
+            _ => return Err(Error::FeatureDataNotAggregatable),
+        }
+
+        Ok(())
+    }
+}
+
+/// Calls `$body` with the inner aggregator of every variant of a `TypedAggregator`
+macro_rules! map_typed_aggregator {
+    ($typed_aggregator:expr, $aggregator:ident => $body:expr) => {
+        match $typed_aggregator {
+            TypedAggregator::FirstValueFloat($aggregator) => $body,
+            TypedAggregator::FirstValueInt($aggregator) => $body,
+            TypedAggregator::MeanNumber($aggregator) => $body,
+            TypedAggregator::MinFloat($aggregator) => $body,
+            TypedAggregator::MinInt($aggregator) => $body,
+            TypedAggregator::MaxFloat($aggregator) => $body,
+            TypedAggregator::MaxInt($aggregator) => $body,
+            TypedAggregator::Sum($aggregator) => $body,
+            TypedAggregator::Count($aggregator) => $body,
+            TypedAggregator::StdDev($aggregator) => $body,
+            TypedAggregator::Percentile($aggregator) => $body,
+            TypedAggregator::MajorityFloat($aggregator) => $body,
+            TypedAggregator::MajorityInt($aggregator) => $body,
+            TypedAggregator::ClassFraction($aggregator) => $body,
+        }
+    };
+}
+
+/// An aggregator wrapper for different return types
+pub enum TypedAggregator {
+    FirstValueFloat(FirstValueFloatAggregator),
+    FirstValueInt(FirstValueIntAggregator),
+    MeanNumber(MeanValueAggregator),
+    MinFloat(MinValueAggregator<f64>),
+    MinInt(MinValueAggregator<i64>),
+    MaxFloat(MaxValueAggregator<f64>),
+    MaxInt(MaxValueAggregator<i64>),
+    Sum(SumValueAggregator),
+    Count(CountAggregator),
+    StdDev(StdDevAggregator),
+    Percentile(PercentileAggregator),
+    MajorityFloat(MajorityValueAggregator<f64>),
+    MajorityInt(MajorityValueAggregator<i64>),
+    ClassFraction(ClassFractionAggregator),
+}
+
+impl TypedAggregator {
+    pub fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
+    where
+        P: Pixel + AsPrimitive<f64> + AsPrimitive<i64>,
+    {
+        map_typed_aggregator!(self, aggregator => aggregator.add_value(feature_idx, pixel, weight));
+    }
+
+    pub fn add_null(&mut self, feature_idx: usize) {
+        map_typed_aggregator!(self, aggregator => aggregator.add_null(feature_idx));
+    }
+
+    pub fn into_data(self) -> FeatureData {
+        map_typed_aggregator!(self, aggregator => AggregatorOutputType::feature_data(aggregator.into_data()))
+    }
+
+    #[allow(dead_code)]
+    pub fn nulls(&self) -> &[bool] {
+        map_typed_aggregator!(self, aggregator => aggregator.nulls())
+    }
+
+    /// Whether an aggregator needs no more values for producing the outcome
+    pub fn is_satisfied(&self) -> bool {
+        map_typed_aggregator!(self, aggregator => aggregator.is_satisfied())
+    }
+
+    pub fn add_feature_data(&mut self, data: FeatureData, weight: f64) -> Result<()> {
+        map_typed_aggregator!(self, aggregator => aggregator.add_feature_data(data, weight))
+    }
+}
+
+/// The output types of aggregators, i.e., integers and floats
+pub trait AggregatorOutputType: Pixel {
     fn feature_data_type() -> FeatureDataType;
 
-    fn data(&self) -> &[Self::Output];
+    fn feature_data(values: Vec<Option<Self>>) -> FeatureData;
+
+    fn first_value_aggregator(aggregator: FirstValueAggregator<Self>) -> TypedAggregator;
+
+    fn min_value_aggregator(aggregator: MinValueAggregator<Self>) -> TypedAggregator;
+
+    fn max_value_aggregator(aggregator: MaxValueAggregator<Self>) -> TypedAggregator;
+
+    fn majority_value_aggregator(aggregator: MajorityValueAggregator<Self>) -> TypedAggregator;
+}
+
+impl AggregatorOutputType for i64 {
+    fn feature_data_type() -> FeatureDataType {
+        FeatureDataType::Int
+    }
+
+    fn feature_data(values: Vec<Option<Self>>) -> FeatureData {
+        FeatureData::NullableInt(values)
+    }
+
+    fn first_value_aggregator(aggregator: FirstValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::FirstValueInt(aggregator)
+    }
+
+    fn min_value_aggregator(aggregator: MinValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::MinInt(aggregator)
+    }
+
+    fn max_value_aggregator(aggregator: MaxValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::MaxInt(aggregator)
+    }
+
+    fn majority_value_aggregator(aggregator: MajorityValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::MajorityInt(aggregator)
+    }
+}
+
+impl AggregatorOutputType for f64 {
+    fn feature_data_type() -> FeatureDataType {
+        FeatureDataType::Float
+    }
+
+    fn feature_data(values: Vec<Option<Self>>) -> FeatureData {
+        FeatureData::NullableFloat(values)
+    }
+
+    fn first_value_aggregator(aggregator: FirstValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::FirstValueFloat(aggregator)
+    }
+
+    fn min_value_aggregator(aggregator: MinValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::MinFloat(aggregator)
+    }
+
+    fn max_value_aggregator(aggregator: MaxValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::MaxFloat(aggregator)
+    }
+
+    fn majority_value_aggregator(aggregator: MajorityValueAggregator<Self>) -> TypedAggregator {
+        TypedAggregator::MajorityFloat(aggregator)
+    }
+}
+
+/// Replaces the values of features that are null by `None`
+fn values_with_nulls<T>(values: Vec<T>, nulls: Vec<bool>) -> Vec<Option<T>> {
+    values
+        .into_iter()
+        .zip(nulls)
+        .map(|(value, is_null)| if is_null { None } else { Some(value) })
+        .collect()
+}
+
+pub type FirstValueFloatAggregator = FirstValueAggregator<f64>;
+pub type FirstValueIntAggregator = FirstValueAggregator<i64>;
+
+/// Aggregation function that uses only the first value occurrence
+pub struct FirstValueAggregator<T> {
+    values: Vec<T>,
+    not_pristine: Vec<bool>,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_pristine_values: usize,
+}
+
+impl<T> Aggregator for FirstValueAggregator<T>
+where
+    T: AggregatorOutputType,
+{
+    type Output = T;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            values: vec![T::zero(); number_of_features],
+            not_pristine: vec![false; number_of_features],
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_pristine_values: number_of_features,
+        }
+    }
+
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, _weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        if self.not_pristine[feature_idx] {
+            return;
+        }
+
+        self.values[feature_idx] = pixel.as_();
+
+        self.not_pristine[feature_idx] = true;
+        self.number_of_pristine_values -= 1;
+    }
+
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.not_pristine[feature_idx] {
+            return;
+        }
+
+        self.null[feature_idx] = true;
+
+        self.not_pristine[feature_idx] = true;
+        self.number_of_pristine_values -= 1;
+    }
+
+    fn data(&self) -> Vec<Self::Output> {
+        self.values.clone()
+    }
+
+    fn nulls(&self) -> &[bool] {
+        &self.null
+    }
+
+    fn into_data(self) -> Vec<Option<Self::Output>> {
+        values_with_nulls(self.values, self.null)
+    }
+
+    fn into_typed(self) -> TypedAggregator {
+        T::first_value_aggregator(self)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.number_of_pristine_values == 0
+    }
+}
+
+/// Aggregation function that calculates the weighted mean
+pub struct MeanValueAggregator {
+    means: Vec<f64>,
+    sum_weights: Vec<f64>,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_non_null_values: usize,
+}
+
+impl Aggregator for MeanValueAggregator {
+    type Output = f64;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            means: vec![0.; number_of_features],
+            sum_weights: vec![0.; number_of_features],
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_non_null_values: number_of_features,
+        }
+    }
+
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        debug_assert!(weight > 0., "weights must be positive and non-zero");
+
+        if self.null[feature_idx] {
+            return;
+        }
+
+        let value: f64 = pixel.as_();
+
+        let old_mean = self.means[feature_idx];
+        let old_normalized_weight = self.sum_weights[feature_idx] / weight;
+
+        self.sum_weights[feature_idx] += weight;
+        self.means[feature_idx] += (value - old_mean) / (old_normalized_weight + 1.);
+    }
+
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.null[feature_idx] {
+            return;
+        }
+
+        self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
+    }
+
+    fn data(&self) -> Vec<Self::Output> {
+        self.means.clone()
+    }
+
+    fn nulls(&self) -> &[bool] {
+        &self.null
+    }
+
+    fn into_data(self) -> Vec<Option<Self::Output>> {
+        values_with_nulls(self.means, self.null)
+    }
+
+    fn into_typed(self) -> TypedAggregator {
+        TypedAggregator::MeanNumber(self)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.number_of_non_null_values == 0
+    }
+}
+
+/// Selects either the minimum or the maximum for an [`ExtremumAggregator`]
+pub trait Extremum: Send + Sync + Sized {
+    /// Whether `value` replaces the `current` extremum
+    fn replaces<T: PartialOrd>(value: T, current: T) -> bool;
+
+    fn typed_aggregator<T: AggregatorOutputType>(
+        aggregator: ExtremumAggregator<T, Self>,
+    ) -> TypedAggregator;
+}
+
+pub struct Min;
+
+impl Extremum for Min {
+    fn replaces<T: PartialOrd>(value: T, current: T) -> bool {
+        value < current
+    }
+
+    fn typed_aggregator<T: AggregatorOutputType>(
+        aggregator: ExtremumAggregator<T, Self>,
+    ) -> TypedAggregator {
+        T::min_value_aggregator(aggregator)
+    }
+}
+
+pub struct Max;
+
+impl Extremum for Max {
+    fn replaces<T: PartialOrd>(value: T, current: T) -> bool {
+        value > current
+    }
+
+    fn typed_aggregator<T: AggregatorOutputType>(
+        aggregator: ExtremumAggregator<T, Self>,
+    ) -> TypedAggregator {
+        T::max_value_aggregator(aggregator)
+    }
+}
+
+pub type MinValueAggregator<T> = ExtremumAggregator<T, Min>;
+pub type MaxValueAggregator<T> = ExtremumAggregator<T, Max>;
+
+/// Aggregation function that calculates the minimum or maximum value
+pub struct ExtremumAggregator<T, E> {
+    values: Vec<T>,
+    has_value: Vec<bool>,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_non_null_values: usize,
+    extremum: PhantomData<E>,
+}
+
+impl<T, E> Aggregator for ExtremumAggregator<T, E>
+where
+    T: AggregatorOutputType,
+    E: Extremum,
+{
+    type Output = T;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            values: vec![T::zero(); number_of_features],
+            has_value: vec![false; number_of_features],
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_non_null_values: number_of_features,
+            extremum: PhantomData,
+        }
+    }
+
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, _weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        if self.null[feature_idx] {
+            return;
+        }
+
+        let value: T = pixel.as_();
+
+        if !self.has_value[feature_idx] || E::replaces(value, self.values[feature_idx]) {
+            self.values[feature_idx] = value;
+            self.has_value[feature_idx] = true;
+        }
+    }
+
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.null[feature_idx] {
+            return;
+        }
+
+        self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
+    }
+
+    fn data(&self) -> Vec<Self::Output> {
+        self.values.clone()
+    }
+
+    fn nulls(&self) -> &[bool] {
+        &self.null
+    }
+
+    fn into_data(self) -> Vec<Option<Self::Output>> {
+        self.values
+            .into_iter()
+            .zip(self.has_value)
+            .zip(self.null)
+            .map(|((value, has_value), is_null)| (has_value && !is_null).then_some(value))
+            .collect()
+    }
+
+    fn into_typed(self) -> TypedAggregator {
+        E::typed_aggregator(self)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.number_of_non_null_values == 0
+    }
+}
+
+/// Aggregation function that calculates the weighted sum, i.e., partially covered pixels
+/// contribute only their covered fraction
+pub struct SumValueAggregator {
+    sums: Vec<f64>,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_non_null_values: usize,
+}
+
+impl Aggregator for SumValueAggregator {
+    type Output = f64;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            sums: vec![0.; number_of_features],
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_non_null_values: number_of_features,
+        }
+    }
+
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        if self.null[feature_idx] {
+            return;
+        }
+
+        let value: f64 = pixel.as_();
+
+        self.sums[feature_idx] += value * weight;
+    }
+
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.null[feature_idx] {
+            return;
+        }
+
+        self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
+    }
+
+    fn data(&self) -> Vec<Self::Output> {
+        self.sums.clone()
+    }
+
+    fn nulls(&self) -> &[bool] {
+        &self.null
+    }
+
+    fn into_data(self) -> Vec<Option<Self::Output>> {
+        values_with_nulls(self.sums, self.null)
+    }
+
+    fn into_typed(self) -> TypedAggregator {
+        TypedAggregator::Sum(self)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.number_of_non_null_values == 0
+    }
+}
+
+/// Aggregation function that counts the (weighted) number of pixels
+pub struct CountAggregator {
+    counts: Vec<f64>,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_non_null_values: usize,
+}
+
+impl Aggregator for CountAggregator {
+    type Output = f64;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            counts: vec![0.; number_of_features],
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_non_null_values: number_of_features,
+        }
+    }
+
+    fn add_value<P>(&mut self, feature_idx: usize, _pixel: P, weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        if self.null[feature_idx] {
+            return;
+        }
+
+        self.counts[feature_idx] += weight;
+    }
+
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.null[feature_idx] {
+            return;
+        }
+
+        self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
+    }
+
+    fn data(&self) -> Vec<Self::Output> {
+        self.counts.clone()
+    }
+
+    fn nulls(&self) -> &[bool] {
+        &self.null
+    }
+
+    fn into_data(self) -> Vec<Option<Self::Output>> {
+        values_with_nulls(self.counts, self.null)
+    }
+
+    fn into_typed(self) -> TypedAggregator {
+        TypedAggregator::Count(self)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.number_of_non_null_values == 0
+    }
+}
+
+/// Aggregation function that calculates the weighted population standard deviation
+///
+/// The algorithm is taken from West (1979): "Updating mean and variance estimates: an improved method"
+pub struct StdDevAggregator {
+    means: Vec<f64>,
+    squared_deviations: Vec<f64>,
+    sum_weights: Vec<f64>,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_non_null_values: usize,
+}
+
+impl Aggregator for StdDevAggregator {
+    type Output = f64;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            means: vec![0.; number_of_features],
+            squared_deviations: vec![0.; number_of_features],
+            sum_weights: vec![0.; number_of_features],
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_non_null_values: number_of_features,
+        }
+    }
+
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        debug_assert!(weight > 0., "weights must be positive and non-zero");
+
+        if self.null[feature_idx] {
+            return;
+        }
+
+        let value: f64 = pixel.as_();
+
+        let old_mean = self.means[feature_idx];
+
+        self.sum_weights[feature_idx] += weight;
+        self.means[feature_idx] += (weight / self.sum_weights[feature_idx]) * (value - old_mean);
+        self.squared_deviations[feature_idx] +=
+            weight * (value - old_mean) * (value - self.means[feature_idx]);
+    }
+
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.null[feature_idx] {
+            return;
+        }
+
+        self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
+    }
 
-    fn nulls(&self) -> &[bool];
+    fn data(&self) -> Vec<Self::Output> {
+        self.squared_deviations
+            .iter()
+            .zip(&self.sum_weights)
+            .map(|(squared_deviations, sum_weights)| {
+                if *sum_weights > 0. {
+                    (squared_deviations / sum_weights).sqrt()
+                } else {
+                    0.
+                }
+            })
+            .collect()
+    }
 
-    fn into_data(self) -> Vec<Option<Self::Output>>;
+    fn nulls(&self) -> &[bool] {
+        &self.null
+    }
 
-    fn into_typed(self) -> TypedAggregator;
+    fn into_data(self) -> Vec<Option<Self::Output>> {
+        self.data()
+            .into_iter()
+            .zip(self.sum_weights)
+            .zip(self.null)
+            .map(|((std_dev, sum_weights), is_null)| {
+                (sum_weights > 0. && !is_null).then_some(std_dev)
+            })
+            .collect()
+    }
 
-    /// Whether an aggregator needs no more values for producing the outcome
-    fn is_satisfied(&self) -> bool;
+    fn into_typed(self) -> TypedAggregator {
+        TypedAggregator::StdDev(self)
+    }
 
-    /// Add all values from `data` to the aggregator. Fails if the data length doesn't match
-    /// the aggregator or if the data is not compatible with the aggregator.
-    fn add_feature_data(&mut self, data: FeatureData, weight: u64) -> Result<()>;
+    fn is_satisfied(&self) -> bool {
+        self.number_of_non_null_values == 0
+    }
 }
 
-/// An aggregator wrapper for different return types
-pub enum TypedAggregator {
-    FirstValueFloat(FirstValueFloatAggregator),
-    FirstValueInt(FirstValueIntAggregator),
-    MeanNumber(MeanValueAggregator),
+/// Aggregation function that calculates a weighted percentile, e.g., the median
+///
+/// Each value is positioned at the center of its weight in the cumulative weights and the
+/// percentile is linearly interpolated between these positions.
+pub struct PercentileAggregator {
+    values: Vec<Vec<(f64, f64)>>,
+    quantile: f64,
+    null: Vec<bool>,
+    ignore_nulls: bool,
+    number_of_non_null_values: usize,
 }
 
-impl TypedAggregator {
-    pub fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: u64)
-    where
-        P: Pixel + AsPrimitive<f64> + AsPrimitive<i64>,
-    {
-        match self {
-            TypedAggregator::FirstValueFloat(aggregator) => {
-                aggregator.add_value(feature_idx, pixel, weight);
-            }
-            TypedAggregator::FirstValueInt(aggregator) => {
-                aggregator.add_value(feature_idx, pixel, weight);
-            }
-            TypedAggregator::MeanNumber(aggregator) => {
-                aggregator.add_value(feature_idx, pixel, weight);
+impl PercentileAggregator {
+    /// Calculate the `percentile` (between 0 and 100) instead of the median
+    #[must_use]
+    pub fn with_percentile(mut self, percentile: u8) -> Self {
+        self.quantile = f64::from(percentile) / 100.;
+        self
+    }
+
+    fn percentile(&self, values: &mut [(f64, f64)]) -> Option<f64> {
+        values.sort_unstable_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let total_weight: f64 = values.iter().map(|(_, weight)| weight).sum();
+
+        let mut cumulative_weight = 0.;
+        let mut previous: Option<(f64, f64)> = None;
+
+        for &(value, weight) in values.iter() {
+            let position = (cumulative_weight + weight / 2.) / total_weight;
+            cumulative_weight += weight;
+
+            if position >= self.quantile {
+                return Some(match previous {
+                    Some((previous_position, previous_value)) => {
+                        previous_value
+                            + (value - previous_value) * (self.quantile - previous_position)
+                                / (position - previous_position)
+                    }
+                    None => value,
+                });
             }
+
+            previous = Some((position, value));
         }
+
+        previous.map(|(_, value)| value)
     }
+}
 
-    pub fn add_null(&mut self, feature_idx: usize) {
-        match self {
-            TypedAggregator::FirstValueFloat(aggregator) => aggregator.add_null(feature_idx),
-            TypedAggregator::FirstValueInt(aggregator) => aggregator.add_null(feature_idx),
-            TypedAggregator::MeanNumber(aggregator) => aggregator.add_null(feature_idx),
+impl Aggregator for PercentileAggregator {
+    type Output = f64;
+
+    fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
+        Self {
+            values: vec![Vec::new(); number_of_features],
+            quantile: 0.5,
+            null: vec![false; number_of_features],
+            ignore_nulls,
+            number_of_non_null_values: number_of_features,
         }
     }
 
-    pub fn into_data(self) -> FeatureData {
-        match self {
-            TypedAggregator::FirstValueFloat(aggregator) => {
-                FeatureData::NullableFloat(aggregator.into_data())
-            }
-            TypedAggregator::FirstValueInt(aggregator) => {
-                FeatureData::NullableInt(aggregator.into_data())
-            }
-            TypedAggregator::MeanNumber(aggregator) => {
-                FeatureData::NullableFloat(aggregator.into_data())
-            }
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
+    where
+        P: Pixel + AsPrimitive<Self::Output>,
+    {
+        debug_assert!(weight > 0., "weights must be positive and non-zero");
+
+        if self.null[feature_idx] {
+            return;
         }
+
+        self.values[feature_idx].push((pixel.as_(), weight));
     }
 
-    #[allow(dead_code)]
-    pub fn nulls(&self) -> &[bool] {
-        match self {
-            TypedAggregator::FirstValueFloat(aggregator) => aggregator.nulls(),
-            TypedAggregator::FirstValueInt(aggregator) => aggregator.nulls(),
-            TypedAggregator::MeanNumber(aggregator) => aggregator.nulls(),
+    fn add_null(&mut self, feature_idx: usize) {
+        if self.ignore_nulls || self.null[feature_idx] {
+            return;
         }
+
+        self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
+
+        // the values are not needed anymore
+        self.values[feature_idx] = Vec::new();
     }
 
-    /// Whether an aggregator needs no more values for producing the outcome
-    pub fn is_satisfied(&self) -> bool {
-        match self {
-            TypedAggregator::FirstValueFloat(aggregator) => aggregator.is_satisfied(),
-            TypedAggregator::FirstValueInt(aggregator) => aggregator.is_satisfied(),
-            TypedAggregator::MeanNumber(aggregator) => aggregator.is_satisfied(),
-        }
+    fn data(&self) -> Vec<Self::Output> {
+        self.values
+            .iter()
+            .map(|values| self.percentile(&mut values.clone()).unwrap_or_default())
+            .collect()
     }
 
-    pub fn add_feature_data(&mut self, data: FeatureData, weight: u64) -> Result<()> {
-        match self {
-            TypedAggregator::FirstValueFloat(a) => a.add_feature_data(data, weight),
-            TypedAggregator::FirstValueInt(a) => a.add_feature_data(data, weight),
-            TypedAggregator::MeanNumber(a) => a.add_feature_data(data, weight),
-        }
+    fn nulls(&self) -> &[bool] {
+        &self.null
     }
-}
 
-pub type FirstValueFloatAggregator = FirstValueAggregator<f64>;
-pub type FirstValueIntAggregator = FirstValueAggregator<i64>;
+    fn into_data(mut self) -> Vec<Option<Self::Output>> {
+        let values = std::mem::take(&mut self.values);
 
-/// Aggregation function that uses only the first value occurrence
-pub struct FirstValueAggregator<T> {
-    values: Vec<T>,
-    not_pristine: Vec<bool>,
+        values
+            .into_iter()
+            .zip(&self.null)
+            .map(|(mut values, is_null)| {
+                if *is_null {
+                    None
+                } else {
+                    self.percentile(&mut values)
+                }
+            })
+            .collect()
+    }
+
+    fn into_typed(self) -> TypedAggregator {
+        TypedAggregator::Percentile(self)
+    }
+
+    fn is_satisfied(&self) -> bool {
+        self.number_of_non_null_values == 0
+    }
+}
+
+/// Aggregation function that determines the value with the largest total weight.
+/// Ties are resolved by choosing the smallest value.
+pub struct MajorityValueAggregator<T> {
+    weights: Vec<HashMap<u64, f64>>,
     null: Vec<bool>,
     ignore_nulls: bool,
-    number_of_pristine_values: usize,
+    number_of_non_null_values: usize,
+    output_type: PhantomData<T>,
 }
 
-impl<T> Aggregator for FirstValueAggregator<T>
+impl<T: AggregatorOutputType> MajorityValueAggregator<T> {
+    fn majority(weights: &HashMap<u64, f64>) -> Option<T> {
+        weights
+            .iter()
+            .map(|(&bits, &weight)| (f64::from_bits(bits), weight))
+            .max_by(|(a_value, a_weight), (b_value, b_weight)| {
+                a_weight
+                    .total_cmp(b_weight)
+                    .then_with(|| b_value.total_cmp(a_value))
+            })
+            .map(|(value, _)| <T as FromPrimitive<f64>>::from_(value))
+    }
+}
+
+impl<T> Aggregator for MajorityValueAggregator<T>
 where
-    T: Pixel + FirstValueOutputType,
+    T: AggregatorOutputType,
 {
     type Output = T;
 
     fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
         Self {
-            values: vec![T::zero(); number_of_features],
-            not_pristine: vec![false; number_of_features],
+            weights: vec![HashMap::new(); number_of_features],
             null: vec![false; number_of_features],
             ignore_nulls,
-            number_of_pristine_values: number_of_features,
+            number_of_non_null_values: number_of_features,
+            output_type: PhantomData,
         }
     }
 
-    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, _weight: u64)
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
     where
         P: Pixel + AsPrimitive<Self::Output>,
     {
-        if self.not_pristine[feature_idx] {
+        if self.null[feature_idx] {
             return;
         }
 
-        self.values[feature_idx] = pixel.as_();
+        let value: f64 = pixel.as_();
+        // `+ 0.` normalizes `-0.` to `0.`
+        let key = (value + 0.).to_bits();
 
-        self.not_pristine[feature_idx] = true;
-        self.number_of_pristine_values -= 1;
+        *self.weights[feature_idx].entry(key).or_default() += weight;
     }
 
     fn add_null(&mut self, feature_idx: usize) {
-        if self.ignore_nulls || self.not_pristine[feature_idx] {
+        if self.ignore_nulls || self.null[feature_idx] {
             return;
         }
 
         self.null[feature_idx] = true;
+        self.number_of_non_null_values -= 1;
 
-        self.not_pristine[feature_idx] = true;
-        self.number_of_pristine_values -= 1;
-    }
-
-    fn feature_data_type() -> FeatureDataType {
-        T::feature_data_type()
+        // the weights are not needed anymore
+        self.weights[feature_idx] = HashMap::new();
     }
 
-    fn data(&self) -> &[Self::Output] {
-        &self.values
+    fn data(&self) -> Vec<Self::Output> {
+        self.weights
+            .iter()
+            .map(|weights| Self::majority(weights).unwrap_or_else(T::zero))
+            .collect()
     }
 
     fn nulls(&self) -> &[bool] {
@@ -177,95 +908,54 @@ where
     }
 
     fn into_data(self) -> Vec<Option<Self::Output>> {
-        self.values
-            .into_iter()
+        self.weights
+            .iter()
             .zip(self.null)
-            .map(|(value, is_null)| if is_null { None } else { Some(value) })
+            .map(|(weights, is_null)| {
+                if is_null {
+                    None
+                } else {
+                    Self::majority(weights)
+                }
+            })
             .collect()
     }
 
     fn into_typed(self) -> TypedAggregator {
-        T::typed_aggregator(self)
+        T::majority_value_aggregator(self)
     }
 
     fn is_satisfied(&self) -> bool {
-        self.number_of_pristine_values == 0
-    }
-
-    fn add_feature_data(&mut self, data: FeatureData, weight: u64) -> Result<()> {
-        ensure!(
-            data.len() == self.values.len(),
-            error::FeatureDataLengthMismatch
-        );
-
-        match data {
-            geoengine_datatypes::primitives::FeatureData::NullableInt(values) => {
-                for (i, &value) in values.iter().enumerate() {
-                    if let Some(value) = value {
-                        self.add_value(i, T::from_(value), weight);
-                    } else {
-                        self.add_null(i);
-                    }
-                }
-            }
-            geoengine_datatypes::primitives::FeatureData::NullableFloat(values) => {
-                for (i, &value) in values.iter().enumerate() {
-                    if let Some(value) = value {
-                        self.add_value(i, T::from_(value), weight);
-                    } else {
-                        self.add_null(i);
-                    }
-                }
-            }
-            _ => return Err(Error::FeatureDataNotAggregatable),
-        }
-
-        Ok(())
-    }
-}
-
-pub trait FirstValueOutputType {
-    fn feature_data_type() -> FeatureDataType;
-    fn typed_aggregator(aggregator: FirstValueAggregator<Self>) -> TypedAggregator
-    where
-        Self: Sized;
-}
-
-impl FirstValueOutputType for i64 {
-    fn feature_data_type() -> FeatureDataType {
-        FeatureDataType::Int
-    }
-
-    fn typed_aggregator(aggregator: FirstValueAggregator<Self>) -> TypedAggregator {
-        TypedAggregator::FirstValueInt(aggregator)
-    }
-}
-
-impl FirstValueOutputType for f64 {
-    fn feature_data_type() -> FeatureDataType {
-        FeatureDataType::Float
-    }
-
-    fn typed_aggregator(aggregator: FirstValueAggregator<Self>) -> TypedAggregator {
-        TypedAggregator::FirstValueFloat(aggregator)
+        self.number_of_non_null_values == 0
     }
 }
 
-/// Aggregation function that calculates the weighted mean
-pub struct MeanValueAggregator {
-    means: Vec<f64>,
+/// Aggregation function that calculates the (weighted) fraction of pixels with a specific class value
+pub struct ClassFractionAggregator {
+    class: f64,
+    class_weights: Vec<f64>,
     sum_weights: Vec<f64>,
     null: Vec<bool>,
     ignore_nulls: bool,
     number_of_non_null_values: usize,
 }
 
-impl Aggregator for MeanValueAggregator {
+impl ClassFractionAggregator {
+    /// Calculate the fraction of the given `class` value, which is `0` by default
+    #[must_use]
+    pub fn with_class(mut self, class: i64) -> Self {
+        self.class = class as f64;
+        self
+    }
+}
+
+impl Aggregator for ClassFractionAggregator {
     type Output = f64;
 
     fn new(number_of_features: usize, ignore_nulls: bool) -> Self {
         Self {
-            means: vec![0.; number_of_features],
+            class: 0.,
+            class_weights: vec![0.; number_of_features],
             sum_weights: vec![0.; number_of_features],
             null: vec![false; number_of_features],
             ignore_nulls,
@@ -273,24 +963,21 @@ impl Aggregator for MeanValueAggregator {
         }
     }
 
-    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: u64)
+    #[allow(clippy::float_cmp)]
+    fn add_value<P>(&mut self, feature_idx: usize, pixel: P, weight: f64)
     where
         P: Pixel + AsPrimitive<Self::Output>,
     {
-        debug_assert!(weight > 0, "weights must be positive and non-zero");
-
         if self.null[feature_idx] {
             return;
         }
 
         let value: f64 = pixel.as_();
-        let weight: f64 = weight.as_();
-
-        let old_mean = self.means[feature_idx];
-        let old_normalized_weight = self.sum_weights[feature_idx] / weight;
 
+        if value == self.class {
+            self.class_weights[feature_idx] += weight;
+        }
         self.sum_weights[feature_idx] += weight;
-        self.means[feature_idx] += (value - old_mean) / (old_normalized_weight + 1.);
     }
 
     fn add_null(&mut self, feature_idx: usize) {
@@ -302,12 +989,18 @@ impl Aggregator for MeanValueAggregator {
         self.number_of_non_null_values -= 1;
     }
 
-    fn feature_data_type() -> FeatureDataType {
-        FeatureDataType::Float
-    }
-
-    fn data(&self) -> &[Self::Output] {
-        &self.means
+    fn data(&self) -> Vec<Self::Output> {
+        self.class_weights
+            .iter()
+            .zip(&self.sum_weights)
+            .map(|(class_weight, sum_weights)| {
+                if *sum_weights > 0. {
+                    class_weight / sum_weights
+                } else {
+                    0.
+                }
+            })
+            .collect()
     }
 
     fn nulls(&self) -> &[bool] {
@@ -315,51 +1008,23 @@ impl Aggregator for MeanValueAggregator {
     }
 
     fn into_data(self) -> Vec<Option<Self::Output>> {
-        self.means
+        self.data()
             .into_iter()
+            .zip(self.sum_weights)
             .zip(self.null)
-            .map(|(value, is_null)| if is_null { None } else { Some(value) })
+            .map(|((fraction, sum_weights), is_null)| {
+                (sum_weights > 0. && !is_null).then_some(fraction)
+            })
             .collect()
     }
 
     fn into_typed(self) -> TypedAggregator {
-        TypedAggregator::MeanNumber(self)
+        TypedAggregator::ClassFraction(self)
     }
 
     fn is_satisfied(&self) -> bool {
         self.number_of_non_null_values == 0
     }
-
-    fn add_feature_data(&mut self, data: FeatureData, weight: u64) -> Result<()> {
-        ensure!(
-            data.len() == self.means.len(),
-            error::FeatureDataLengthMismatch
-        );
-
-        match data {
-            geoengine_datatypes::primitives::FeatureData::NullableInt(values) => {
-                for (i, &value) in values.iter().enumerate() {
-                    if let Some(value) = value {
-                        self.add_value(i, value, weight);
-                    } else {
-                        self.add_null(i);
-                    }
-                }
-            }
-            geoengine_datatypes::primitives::FeatureData::NullableFloat(values) => {
-                for (i, &value) in values.iter().enumerate() {
-                    if let Some(value) = value {
-                        self.add_value(i, value, weight);
-                    } else {
-                        self.add_null(i);
-                    }
-                }
-            }
-            _ => return Err(Error::FeatureDataNotAggregatable),
-        }
-
-        Ok(())
-    }
 }
 
 #[cfg(test)]
@@ -371,10 +1036,10 @@ mod tests {
     fn fist_value_f64() {
         let mut aggregator = FirstValueFloatAggregator::new(2, false);
 
-        aggregator.add_value(0, 1, 1);
-        aggregator.add_value(0, 2, 1);
+        aggregator.add_value(0, 1, 1.);
+        aggregator.add_value(0, 2, 1.);
 
-        aggregator.add_value(1, 10, 1);
+        aggregator.add_value(1, 10, 1.);
 
         assert_eq!(aggregator.data(), &[1., 10.]);
     }
@@ -383,10 +1048,10 @@ mod tests {
     fn fist_value_i64() {
         let mut aggregator = FirstValueIntAggregator::new(2, false);
 
-        aggregator.add_value(0, 2., 1);
-        aggregator.add_value(0, 0., 1);
+        aggregator.add_value(0, 2., 1.);
+        aggregator.add_value(0, 0., 1.);
 
-        aggregator.add_value(1, 4., 1);
+        aggregator.add_value(1, 4., 1.);
 
         assert_eq!(aggregator.data(), &[2, 4]);
     }
@@ -397,8 +1062,8 @@ mod tests {
         let mut aggregator = MeanValueAggregator::new(2, false);
 
         for i in 1..=10 {
-            aggregator.add_value(0, i, 1);
-            aggregator.add_value(1, i, i);
+            aggregator.add_value(0, i, 1.);
+            aggregator.add_value(1, i, f64::from(i));
         }
 
         assert_eq!(aggregator.data(), &[5.5, 385. / 55.]);
@@ -408,10 +1073,10 @@ mod tests {
     fn typed() {
         let mut aggregator = FirstValueIntAggregator::new(2, false).into_typed();
 
-        aggregator.add_value(0, 2., 1);
-        aggregator.add_value(0, 0., 1);
+        aggregator.add_value(0, 2., 1.);
+        aggregator.add_value(0, 0., 1.);
 
-        aggregator.add_value(1, 4., 1);
+        aggregator.add_value(1, 4., 1.);
 
         if let TypedAggregator::FirstValueInt(ref aggregator) = aggregator {
             assert_eq!(aggregator.data(), &[2, 4]);
@@ -431,15 +1096,15 @@ mod tests {
 
         assert!(!aggregator.is_satisfied());
 
-        aggregator.add_value(0, 2., 1);
+        aggregator.add_value(0, 2., 1.);
 
         assert!(!aggregator.is_satisfied());
 
-        aggregator.add_value(1, 0., 1);
+        aggregator.add_value(1, 0., 1.);
 
         assert!(aggregator.is_satisfied());
 
-        aggregator.add_value(1, 4., 1);
+        aggregator.add_value(1, 4., 1.);
 
         assert!(aggregator.is_satisfied());
     }
@@ -462,7 +1127,7 @@ mod tests {
     fn value_then_null() {
         let mut aggregator = FirstValueIntAggregator::new(1, false).into_typed();
 
-        aggregator.add_value(0, 1337, 1);
+        aggregator.add_value(0, 1337, 1.);
         aggregator.add_null(0);
 
         assert_eq!(
@@ -476,7 +1141,7 @@ mod tests {
         let mut aggregator = FirstValueIntAggregator::new(1, true).into_typed();
 
         aggregator.add_null(0);
-        aggregator.add_value(0, 1337, 1);
+        aggregator.add_value(0, 1337, 1.);
 
         assert!(aggregator.is_satisfied());
 
@@ -492,11 +1157,92 @@ mod tests {
         let mut aggregator = MeanValueAggregator::new(2, true);
 
         for i in 1..=10 {
-            aggregator.add_value(0, i, 1);
+            aggregator.add_value(0, i, 1.);
             aggregator.add_null(0);
-            aggregator.add_value(1, i, i);
+            aggregator.add_value(1, i, f64::from(i));
         }
 
         assert_eq!(aggregator.data(), &[5.5, 385. / 55.]);
     }
+
+    #[test]
+    fn min_max() {
+        let mut min_aggregator = MinValueAggregator::<i64>::new(3, true);
+        let mut max_aggregator = MaxValueAggregator::<f64>::new(3, true);
+
+        for value in [3, 1, 2] {
+            min_aggregator.add_value(0, value, 1.);
+            max_aggregator.add_value(0, value, 1.);
+        }
+        min_aggregator.add_null(1);
+        max_aggregator.add_value(1, -1., 1.);
+
+        assert_eq!(min_aggregator.into_data(), vec![Some(1), None, None]);
+        assert_eq!(max_aggregator.into_data(), vec![Some(3.), Some(-1.), None]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn weighted_sum_count_and_std_dev() {
+        let mut sum_aggregator = SumValueAggregator::new(1, false);
+        let mut count_aggregator = CountAggregator::new(1, false);
+        let mut std_dev_aggregator = StdDevAggregator::new(1, false);
+
+        for (value, weight) in [
+            (2, 1.),
+            (4, 1.),
+            (4, 1.),
+            (4, 0.5),
+            (4, 0.5),
+            (5, 1.),
+            (5, 1.),
+            (7, 1.),
+            (9, 1.),
+        ] {
+            sum_aggregator.add_value(0, value, weight);
+            count_aggregator.add_value(0, value, weight);
+            std_dev_aggregator.add_value(0, value, weight);
+        }
+
+        assert_eq!(sum_aggregator.data(), &[40.]);
+        assert_eq!(count_aggregator.data(), &[8.]);
+        assert!((std_dev_aggregator.data()[0] - 2.).abs() < 1e-10);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn percentiles() {
+        let mut median_aggregator = PercentileAggregator::new(2, false);
+        let mut percentile_aggregator = PercentileAggregator::new(2, false).with_percentile(100);
+
+        for value in [4, 1, 3, 2] {
+            median_aggregator.add_value(0, value, 1.);
+            percentile_aggregator.add_value(0, value, 1.);
+        }
+        median_aggregator.add_value(1, 1, 1.);
+        median_aggregator.add_value(1, 2, 3.);
+
+        assert_eq!(median_aggregator.data(), &[2.5, 1.75]);
+        assert_eq!(percentile_aggregator.into_data(), vec![Some(4.), None]);
+    }
+
+    #[test]
+    fn majority_and_class_fraction() {
+        let mut majority_aggregator = MajorityValueAggregator::<i64>::new(2, false);
+        let mut class_fraction_aggregator = ClassFractionAggregator::new(2, false).with_class(2);
+
+        for value in [1, 2, 2, 3] {
+            majority_aggregator.add_value(0, value, 1.);
+            class_fraction_aggregator.add_value(0, value, 1.);
+        }
+        majority_aggregator.add_value(1, 5, 1.);
+        majority_aggregator.add_value(1, 4, 1.);
+        class_fraction_aggregator.add_null(1);
+
+        assert_eq!(majority_aggregator.into_data(), vec![Some(2), Some(4)]);
+        assert_eq!(
+            class_fraction_aggregator.into_typed().into_data(),
+            FeatureData::NullableFloat(vec![Some(0.5), None])
+        );
+    }
 }
@@ -18,11 +18,14 @@ use async_trait::async_trait;
 use geoengine_datatypes::collections::VectorDataType;
 use geoengine_datatypes::primitives::FeatureDataType;
 use geoengine_datatypes::raster::{Pixel, RasterDataType, RenameBands};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use snafu::ensure;
 
 use self::aggregator::{
-    Aggregator, FirstValueFloatAggregator, FirstValueIntAggregator, MeanValueAggregator,
+    Aggregator, ClassFractionAggregator, CountAggregator, FirstValueFloatAggregator,
+    FirstValueIntAggregator, MajorityValueAggregator, MaxValueAggregator, MeanValueAggregator,
+    MinValueAggregator, PercentileAggregator, StdDevAggregator, SumValueAggregator,
     TypedAggregator,
 };
 
@@ -51,6 +54,12 @@ pub struct RasterVectorJoinParams {
     #[serde(default)]
     pub feature_aggregation_ignore_no_data: bool,
 
+    /// Whether pixels that are only partially covered by a polygon are weighted by their covered area.
+    /// Otherwise, a pixel is covered if its upper left corner lies inside the polygon.
+    /// `false` by default
+    #[serde(default)]
+    pub feature_aggregation_area_weighted: bool,
+
     /// Specifies which method is used for aggregating values over time
     pub temporal_aggregation: TemporalAggregationMethod,
 
@@ -81,11 +90,71 @@ impl From<ColumnNames> for RenameBands {
 /// How to aggregate the values for the geometries inside a feature e.g.
 /// the mean of all the raster values corresponding to the individual
 /// points inside a `MultiPoint` feature.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum FeatureAggregationMethod {
     First,
     Mean,
+    Min,
+    Max,
+    Sum,
+    /// The number of covered pixels
+    Count,
+    /// The population standard deviation
+    StdDev,
+    Median,
+    /// The given percentile between 0 and 100
+    Percentile(u8),
+    /// The most frequent value
+    Majority,
+    /// The fractions of covered pixels that have the given class values.
+    /// There is one column per class, which is named after the band and the class, e.g., `landcover_3`.
+    ClassFraction(Vec<i64>),
+}
+
+impl FeatureAggregationMethod {
+    /// The number of output columns per raster band
+    pub fn number_of_columns(&self) -> usize {
+        match self {
+            Self::ClassFraction(classes) => classes.len(),
+            _ => 1,
+        }
+    }
+
+    /// The names of the output columns of a raster band with the given `column_name`
+    fn column_names(&self, column_name: &str) -> Vec<String> {
+        match self {
+            Self::ClassFraction(classes) => classes
+                .iter()
+                .map(|class| format!("{column_name}_{class}"))
+                .collect(),
+            _ => vec![column_name.to_string()],
+        }
+    }
+
+    /// The data type of the aggregated values of a raster with the given `raster_data_type`
+    pub fn output_data_type(&self, raster_data_type: RasterDataType) -> FeatureDataType {
+        match self {
+            Self::First | Self::Min | Self::Max | Self::Majority => match raster_data_type {
+                RasterDataType::U8
+                | RasterDataType::U16
+                | RasterDataType::U32
+                | RasterDataType::U64
+                | RasterDataType::I8
+                | RasterDataType::I16
+                | RasterDataType::I32
+                | RasterDataType::I64 => FeatureDataType::Int,
+                RasterDataType::F32 | RasterDataType::F64 => FeatureDataType::Float,
+            },
+            Self::Mean
+            | Self::Sum
+            | Self::Count
+            | Self::StdDev
+            | Self::Median
+            | Self::Percentile(_)
+            | Self::ClassFraction(_) => FeatureDataType::Float,
+        }
+    }
 }
 
 /// How to aggregate the values over time
@@ -98,6 +167,8 @@ pub enum TemporalAggregationMethod {
     None,
     First,
     Mean,
+    Min,
+    Max,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -117,6 +188,26 @@ impl VectorOperator for RasterVectorJoin {
             }
         );
 
+        match &self.params.feature_aggregation {
+            FeatureAggregationMethod::Percentile(percentile) => {
+                ensure!(
+                    *percentile <= 100,
+                    error::InvalidPercentile {
+                        percentile: *percentile
+                    }
+                );
+            }
+            FeatureAggregationMethod::ClassFraction(classes) => {
+                ensure!(
+                    !classes.is_empty() && classes.iter().all_unique(),
+                    error::InvalidClassFractionClasses {
+                        classes: classes.clone()
+                    }
+                );
+            }
+            _ => {}
+        }
+
         let name = CanonicOperatorName::from(&self);
 
         let vector_source = self
@@ -176,12 +267,16 @@ impl VectorOperator for RasterVectorJoin {
 
         let rename_bands: RenameBands = self.params.names.clone().into();
 
-        let new_column_names = rename_bands.apply(
-            source_descriptors
-                .iter()
-                .map(|d| d.bands.iter().map(|b| b.name.clone()).collect())
-                .collect(),
-        )?;
+        let new_column_names = rename_bands
+            .apply(
+                source_descriptors
+                    .iter()
+                    .map(|d| d.bands.iter().map(|b| b.name.clone()).collect())
+                    .collect(),
+            )?
+            .iter()
+            .flat_map(|name| self.params.feature_aggregation.column_names(name))
+            .collect::<Vec<_>>();
 
         for name in vector_rd.columns.keys() {
             ensure!(
@@ -198,33 +293,28 @@ impl VectorOperator for RasterVectorJoin {
 
             for source_descriptor in &source_descriptors {
                 let feature_data_type = match params.temporal_aggregation {
-                    TemporalAggregationMethod::First | TemporalAggregationMethod::None => {
-                        match source_descriptor.data_type {
-                            RasterDataType::U8
-                            | RasterDataType::U16
-                            | RasterDataType::U32
-                            | RasterDataType::U64
-                            | RasterDataType::I8
-                            | RasterDataType::I16
-                            | RasterDataType::I32
-                            | RasterDataType::I64 => FeatureDataType::Int,
-                            RasterDataType::F32 | RasterDataType::F64 => FeatureDataType::Float,
-                        }
-                    }
+                    TemporalAggregationMethod::None
+                    | TemporalAggregationMethod::First
+                    | TemporalAggregationMethod::Min
+                    | TemporalAggregationMethod::Max => params
+                        .feature_aggregation
+                        .output_data_type(source_descriptor.data_type),
                     TemporalAggregationMethod::Mean => FeatureDataType::Float,
                 };
 
                 for band in source_descriptor.bands.iter() {
-                    let column_name = new_column_names[new_column_name_idx].clone();
-                    new_column_name_idx += 1;
-
-                    columns.insert(
-                        column_name,
-                        VectorColumnInfo {
-                            data_type: feature_data_type,
-                            measurement: band.measurement.clone(),
-                        },
-                    );
+                    for _ in 0..params.feature_aggregation.number_of_columns() {
+                        let column_name = new_column_names[new_column_name_idx].clone();
+                        new_column_name_idx += 1;
+
+                        columns.insert(
+                            column_name,
+                            VectorColumnInfo {
+                                data_type: feature_data_type,
+                                measurement: band.measurement.clone(),
+                            },
+                        );
+                    }
                 }
             }
             columns
@@ -247,6 +337,7 @@ impl VectorOperator for RasterVectorJoin {
 
 pub struct RasterInput {
     pub processor: TypedRasterQueryProcessor,
+    /// The output columns of all bands, i.e., [`FeatureAggregationMethod::number_of_columns`] per band
     pub column_names: Vec<String>,
 }
 
@@ -275,7 +366,8 @@ impl InitializedVectorOperator for InitializedRasterVectorJoin {
             .zip(self.raster_sources_bands.iter())
         {
             let processor = raster_source.query_processor()?;
-            let column_names = names.drain(0..*num_bands).collect::<Vec<_>>();
+            let num_columns = num_bands * self.state.feature_aggregation.number_of_columns();
+            let column_names = names.drain(0..num_columns).collect::<Vec<_>>();
 
             raster_inputs.push(RasterInput {
                 processor,
@@ -291,22 +383,25 @@ impl InitializedVectorOperator for InitializedRasterVectorJoin {
                         points,
                         self.result_descriptor.clone(),
                         raster_inputs,
-                        self.state.feature_aggregation,
+                        self.state.feature_aggregation.clone(),
                         self.state.feature_aggregation_ignore_no_data,
+                        self.state.feature_aggregation_area_weighted,
+                    )
+                    .boxed(),
+                    TemporalAggregationMethod::First
+                    | TemporalAggregationMethod::Mean
+                    | TemporalAggregationMethod::Min
+                    | TemporalAggregationMethod::Max => RasterVectorAggregateJoinProcessor::new(
+                        points,
+                        self.result_descriptor.clone(),
+                        raster_inputs,
+                        self.state.feature_aggregation.clone(),
+                        self.state.feature_aggregation_ignore_no_data,
+                        self.state.feature_aggregation_area_weighted,
+                        self.state.temporal_aggregation,
+                        self.state.temporal_aggregation_ignore_no_data,
                     )
                     .boxed(),
-                    TemporalAggregationMethod::First | TemporalAggregationMethod::Mean => {
-                        RasterVectorAggregateJoinProcessor::new(
-                            points,
-                            self.result_descriptor.clone(),
-                            raster_inputs,
-                            self.state.feature_aggregation,
-                            self.state.feature_aggregation_ignore_no_data,
-                            self.state.temporal_aggregation,
-                            self.state.temporal_aggregation_ignore_no_data,
-                        )
-                        .boxed()
-                    }
                 })
             }
             TypedVectorQueryProcessor::MultiPolygon(polygons) => {
@@ -315,22 +410,25 @@ impl InitializedVectorOperator for InitializedRasterVectorJoin {
                         polygons,
                         self.result_descriptor.clone(),
                         raster_inputs,
-                        self.state.feature_aggregation,
+                        self.state.feature_aggregation.clone(),
                         self.state.feature_aggregation_ignore_no_data,
+                        self.state.feature_aggregation_area_weighted,
+                    )
+                    .boxed(),
+                    TemporalAggregationMethod::First
+                    | TemporalAggregationMethod::Mean
+                    | TemporalAggregationMethod::Min
+                    | TemporalAggregationMethod::Max => RasterVectorAggregateJoinProcessor::new(
+                        polygons,
+                        self.result_descriptor.clone(),
+                        raster_inputs,
+                        self.state.feature_aggregation.clone(),
+                        self.state.feature_aggregation_ignore_no_data,
+                        self.state.feature_aggregation_area_weighted,
+                        self.state.temporal_aggregation,
+                        self.state.temporal_aggregation_ignore_no_data,
                     )
                     .boxed(),
-                    TemporalAggregationMethod::First | TemporalAggregationMethod::Mean => {
-                        RasterVectorAggregateJoinProcessor::new(
-                            polygons,
-                            self.result_descriptor.clone(),
-                            raster_inputs,
-                            self.state.feature_aggregation,
-                            self.state.feature_aggregation_ignore_no_data,
-                            self.state.temporal_aggregation,
-                            self.state.temporal_aggregation_ignore_no_data,
-                        )
-                        .boxed()
-                    }
                 })
             }
             TypedVectorQueryProcessor::MultiLineString(_) => return Err(Error::NotYetImplemented),
@@ -342,31 +440,72 @@ impl InitializedVectorOperator for InitializedRasterVectorJoin {
     }
 }
 
-pub fn create_feature_aggregator<P: Pixel>(
+/// Creates the feature aggregators of a raster band, i.e., one for each of its output columns
+pub fn create_feature_aggregators<P: Pixel>(
     number_of_features: usize,
-    aggregation: FeatureAggregationMethod,
+    aggregation: &FeatureAggregationMethod,
     ignore_no_data: bool,
-) -> TypedAggregator {
-    match aggregation {
-        FeatureAggregationMethod::First => match P::TYPE {
-            RasterDataType::U8
-            | RasterDataType::U16
-            | RasterDataType::U32
-            | RasterDataType::U64
-            | RasterDataType::I8
-            | RasterDataType::I16
-            | RasterDataType::I32
-            | RasterDataType::I64 => {
-                FirstValueIntAggregator::new(number_of_features, ignore_no_data).into_typed()
-            }
-            RasterDataType::F32 | RasterDataType::F64 => {
-                FirstValueFloatAggregator::new(number_of_features, ignore_no_data).into_typed()
-            }
-        },
+) -> Vec<TypedAggregator> {
+    let int_output = aggregation.output_data_type(P::TYPE) == FeatureDataType::Int;
+
+    let aggregator = match aggregation {
+        FeatureAggregationMethod::First if int_output => {
+            FirstValueIntAggregator::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::First => {
+            FirstValueFloatAggregator::new(number_of_features, ignore_no_data).into_typed()
+        }
         FeatureAggregationMethod::Mean => {
             MeanValueAggregator::new(number_of_features, ignore_no_data).into_typed()
         }
-    }
+        FeatureAggregationMethod::Min if int_output => {
+            MinValueAggregator::<i64>::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Min => {
+            MinValueAggregator::<f64>::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Max if int_output => {
+            MaxValueAggregator::<i64>::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Max => {
+            MaxValueAggregator::<f64>::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Sum => {
+            SumValueAggregator::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Count => {
+            CountAggregator::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::StdDev => {
+            StdDevAggregator::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Median => {
+            PercentileAggregator::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Percentile(percentile) => {
+            PercentileAggregator::new(number_of_features, ignore_no_data)
+                .with_percentile(*percentile)
+                .into_typed()
+        }
+        FeatureAggregationMethod::Majority if int_output => {
+            MajorityValueAggregator::<i64>::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::Majority => {
+            MajorityValueAggregator::<f64>::new(number_of_features, ignore_no_data).into_typed()
+        }
+        FeatureAggregationMethod::ClassFraction(classes) => {
+            return classes
+                .iter()
+                .map(|class| {
+                    ClassFractionAggregator::new(number_of_features, ignore_no_data)
+                        .with_class(*class)
+                        .into_typed()
+                })
+                .collect();
+        }
+    };
+
+    vec![aggregator]
 }
 
 #[cfg(test)]
@@ -389,7 +528,7 @@ mod tests {
         SpatialResolution, TimeInterval, VectorQueryRectangle,
     };
     use geoengine_datatypes::primitives::{CacheHint, Measurement};
-    use geoengine_datatypes::raster::RasterTile2D;
+    use geoengine_datatypes::raster::{Grid2D, RasterTile2D, TileInformation, TilingSpecification};
     use geoengine_datatypes::spatial_reference::SpatialReference;
     use geoengine_datatypes::util::{gdal::hide_gdal_errors, test::TestDefault};
     use serde_json::json;
@@ -401,6 +540,7 @@ mod tests {
                 names: ColumnNames::Names(vec!["foo".to_string(), "bar".to_string()]),
                 feature_aggregation: FeatureAggregationMethod::First,
                 feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
                 temporal_aggregation: TemporalAggregationMethod::Mean,
                 temporal_aggregation_ignore_no_data: false,
             },
@@ -439,6 +579,40 @@ mod tests {
         assert_eq!(deserialized.params, raster_vector_join.params);
     }
 
+    #[test]
+    fn zonal_statistics_serialization() {
+        let params: RasterVectorJoinParams = serde_json::from_value(json!({
+            "names": {
+                "type": "default",
+            },
+            "featureAggregation": { "percentile": 90 },
+            "featureAggregationAreaWeighted": true,
+            "temporalAggregation": "max",
+        }))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            RasterVectorJoinParams {
+                names: ColumnNames::Default,
+                feature_aggregation: FeatureAggregationMethod::Percentile(90),
+                feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: true,
+                temporal_aggregation: TemporalAggregationMethod::Max,
+                temporal_aggregation_ignore_no_data: false,
+            }
+        );
+
+        assert_eq!(
+            serde_json::to_value(FeatureAggregationMethod::ClassFraction(vec![1, 3])).unwrap(),
+            json!({ "classFraction": [1, 3] })
+        );
+        assert_eq!(
+            serde_json::to_value(FeatureAggregationMethod::StdDev).unwrap(),
+            json!("stdDev")
+        );
+    }
+
     fn ndvi_source(name: NamedData) -> Box<dyn RasterOperator> {
         let gdal_source = GdalSource {
             params: GdalSourceParameters { data: name },
@@ -481,6 +655,7 @@ mod tests {
                 names: ColumnNames::Default,
                 feature_aggregation: FeatureAggregationMethod::First,
                 feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
                 temporal_aggregation: TemporalAggregationMethod::First,
                 temporal_aggregation_ignore_no_data: false,
             },
@@ -560,6 +735,7 @@ mod tests {
                 names: ColumnNames::Default,
                 feature_aggregation: FeatureAggregationMethod::First,
                 feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
                 temporal_aggregation: TemporalAggregationMethod::Mean,
                 temporal_aggregation_ignore_no_data: false,
             },
@@ -642,6 +818,7 @@ mod tests {
                 names: ColumnNames::Default,
                 feature_aggregation: FeatureAggregationMethod::First,
                 feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
                 temporal_aggregation: TemporalAggregationMethod::Mean,
                 temporal_aggregation_ignore_no_data: false,
             },
@@ -715,6 +892,7 @@ mod tests {
                 names: ColumnNames::Default,
                 feature_aggregation: FeatureAggregationMethod::First,
                 feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
                 temporal_aggregation: TemporalAggregationMethod::Mean,
                 temporal_aggregation_ignore_no_data: false,
             },
@@ -803,6 +981,7 @@ mod tests {
                 names: ColumnNames::Default,
                 feature_aggregation: FeatureAggregationMethod::First,
                 feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
                 temporal_aggregation: TemporalAggregationMethod::None,
                 temporal_aggregation_ignore_no_data: false,
             },
@@ -865,4 +1044,119 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    #[allow(clippy::float_cmp)]
+    async fn class_fractions() {
+        let point_source = MockFeatureCollectionSource::single(
+            MultiPointCollection::from_data(
+                MultiPoint::many(vec![
+                    vec![(0.5, -0.5), (1.5, -0.5)],
+                    vec![(0.5, -1.5), (1.5, -1.5), (1.5, -2.5)],
+                ])
+                .unwrap(),
+                vec![TimeInterval::default(); 2],
+                Default::default(),
+                CacheHint::default(),
+            )
+            .unwrap(),
+        )
+        .boxed();
+
+        let raster_source = MockRasterSource {
+            params: MockRasterSourceParams {
+                data: vec![RasterTile2D::new_with_tile_info(
+                    TimeInterval::default(),
+                    TileInformation {
+                        global_geo_transform: TestDefault::test_default(),
+                        global_tile_position: [0, 0].into(),
+                        tile_size_in_pixels: [3, 2].into(),
+                    },
+                    0,
+                    Grid2D::<u8>::new([3, 2].into(), vec![1, 2, 1, 1, 2, 3])
+                        .unwrap()
+                        .into(),
+                    CacheHint::default(),
+                )],
+                result_descriptor: RasterResultDescriptor {
+                    data_type: RasterDataType::U8,
+                    spatial_reference: SpatialReference::epsg_4326().into(),
+                    time: None,
+                    bbox: None,
+                    resolution: None,
+                    bands: RasterBandDescriptors::new(vec![RasterBandDescriptor::new_unitless(
+                        "landcover".into(),
+                    )])
+                    .unwrap(),
+                },
+            },
+        }
+        .boxed();
+
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [3, 2].into(),
+        ));
+
+        let join = RasterVectorJoin {
+            params: RasterVectorJoinParams {
+                names: ColumnNames::Default,
+                feature_aggregation: FeatureAggregationMethod::ClassFraction(vec![1, 2]),
+                feature_aggregation_ignore_no_data: false,
+                feature_aggregation_area_weighted: false,
+                temporal_aggregation: TemporalAggregationMethod::None,
+                temporal_aggregation_ignore_no_data: false,
+            },
+            sources: SingleVectorMultipleRasterSources {
+                vector: point_source,
+                rasters: vec![raster_source],
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            join.result_descriptor()
+                .columns
+                .keys()
+                .cloned()
+                .sorted()
+                .collect::<Vec<_>>(),
+            vec!["landcover_1".to_string(), "landcover_2".to_string()]
+        );
+
+        let result = join
+            .query_processor()
+            .unwrap()
+            .multi_point()
+            .unwrap()
+            .query(
+                VectorQueryRectangle {
+                    spatial_bounds: BoundingBox2D::new((0., -3.).into(), (2., 0.).into()).unwrap(),
+                    time_interval: TimeInterval::default(),
+                    spatial_resolution: SpatialResolution::one(),
+                    attributes: ColumnSelection::all(),
+                },
+                &MockQueryContext::new(ChunkByteSize::MAX),
+            )
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<MultiPointCollection>>()
+            .await;
+
+        assert_eq!(result.len(), 1);
+
+        let FeatureDataRef::Float(class_1) = result[0].data("landcover_1").unwrap() else {
+            unreachable!();
+        };
+        assert_eq!(class_1.as_ref(), &[0.5, 2. / 3.]);
+
+        let FeatureDataRef::Float(class_2) = result[0].data("landcover_2").unwrap() else {
+            unreachable!();
+        };
+        assert_eq!(class_2.as_ref(), &[0.5, 0.]);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::adapters::FeatureCollectionStreamExt;
-use crate::processing::raster_vector_join::create_feature_aggregator;
+use crate::processing::raster_vector_join::create_feature_aggregators;
 use futures::stream::{once as once_stream, BoxStream};
 use futures::{StreamExt, TryStreamExt};
 use geoengine_datatypes::primitives::{
@@ -37,6 +37,7 @@ pub struct RasterVectorJoinProcessor<G> {
     raster_inputs: Vec<RasterInput>,
     aggregation_method: FeatureAggregationMethod,
     ignore_no_data: bool,
+    area_weighted: bool,
 }
 
 impl<G> RasterVectorJoinProcessor<G>
@@ -50,6 +51,7 @@ where
         raster_inputs: Vec<RasterInput>,
         aggregation_method: FeatureAggregationMethod,
         ignore_no_data: bool,
+        area_weighted: bool,
     ) -> Self {
         Self {
             collection,
@@ -57,6 +59,7 @@ where
             raster_inputs,
             aggregation_method,
             ignore_no_data,
+            area_weighted,
         }
     }
 
@@ -67,8 +70,9 @@ where
         column_names: &'a [String],
         query: VectorQueryRectangle,
         ctx: &'a dyn QueryContext,
-        aggregation_method: FeatureAggregationMethod,
+        aggregation_method: &'a FeatureAggregationMethod,
         ignore_no_data: bool,
+        area_weighted: bool,
     ) -> BoxStream<'a, Result<FeatureCollection<G>>> {
         let stream = collection.and_then(move |collection| {
             Self::process_collection_chunk(
@@ -79,6 +83,7 @@ where
                 ctx,
                 aggregation_method,
                 ignore_no_data,
+                area_weighted,
             )
         });
 
@@ -95,8 +100,9 @@ where
         column_names: &'a [String],
         query: VectorQueryRectangle,
         ctx: &'a dyn QueryContext,
-        aggregation_method: FeatureAggregationMethod,
+        aggregation_method: &'a FeatureAggregationMethod,
         ignore_no_data: bool,
+        area_weighted: bool,
     ) -> Result<BoxStream<'a, Result<FeatureCollection<G>>>> {
         if collection.is_empty() {
             log::debug!(
@@ -106,7 +112,7 @@ where
             return Self::collection_with_new_null_columns(
                 &collection,
                 column_names,
-                raster_processor.raster_data_type().into(),
+                aggregation_method.output_data_type(raster_processor.raster_data_type()),
             );
         }
 
@@ -128,13 +134,15 @@ where
             return Self::collection_with_new_null_columns(
                 &collection,
                 column_names,
-                raster_processor.raster_data_type().into(),
+                aggregation_method.output_data_type(raster_processor.raster_data_type()),
             );
         };
 
+        let number_of_bands = column_names.len() / aggregation_method.number_of_columns();
+
         let query = RasterQueryRectangle::from_qrect_and_bands(
             &query,
-            BandSelection::first_n(column_names.len() as u32),
+            BandSelection::first_n(number_of_bands as u32),
         );
 
         call_on_generic_raster_processor!(raster_processor, raster_processor => {
@@ -146,6 +154,7 @@ where
                 ctx,
                 aggregation_method,
                 ignore_no_data,
+                area_weighted,
             )
             .await
         })
@@ -179,8 +188,9 @@ where
         column_names: &'a [String],
         query: RasterQueryRectangle,
         ctx: &'a dyn QueryContext,
-        aggregation_method: FeatureAggregationMethod,
+        aggregation_method: &'a FeatureAggregationMethod,
         ignore_no_data: bool,
+        area_weighted: bool,
     ) -> Result<BoxStream<'a, Result<FeatureCollection<G>>>> {
         let raster_query = raster_processor.raster_query(query, ctx).await?;
 
@@ -190,9 +200,10 @@ where
             .time_multi_fold(
                 move || {
                     Ok(VectorRasterJoiner::new(
-                        column_names.len() as u32,
-                        aggregation_method,
+                        (column_names.len() / aggregation_method.number_of_columns()) as u32,
+                        aggregation_method.clone(),
                         ignore_no_data,
+                        area_weighted,
                     ))
                 },
                 move |accum, raster| {
@@ -212,10 +223,10 @@ where
 
 struct JoinerState<G, C> {
     covered_pixels: C,
-    feature_pixels: Option<Vec<Vec<GridIdx2D>>>,
+    feature_pixels: Option<Vec<Vec<(GridIdx2D, f64)>>>,
     current_tile: GridIdx2D,
     current_band_idx: u32,
-    aggregators: Vec<TypedAggregator>, // one aggregator per band
+    aggregators: Vec<Vec<TypedAggregator>>, // the aggregators of each band, one per output column
     g: PhantomData<G>,
 }
 
@@ -224,6 +235,7 @@ struct VectorRasterJoiner<G, C> {
     num_bands: u32,
     aggregation_method: FeatureAggregationMethod,
     ignore_no_data: bool,
+    area_weighted: bool,
     cache_hint: CacheHint,
 }
 
@@ -237,6 +249,7 @@ where
         num_bands: u32,
         aggregation_method: FeatureAggregationMethod,
         ignore_no_data: bool,
+        area_weighted: bool,
     ) -> Self {
         // TODO: is it possible to do the initialization here?
 
@@ -245,6 +258,7 @@ where
             num_bands,
             aggregation_method,
             ignore_no_data,
+            area_weighted,
             cache_hint: CacheHint::max_duration(),
         }
     }
@@ -277,9 +291,9 @@ where
         self.state = Some(JoinerState::<G, C> {
             aggregators: (0..self.num_bands)
                 .map(|_| {
-                    create_feature_aggregator::<P>(
+                    create_feature_aggregators::<P>(
                         collection.len(),
-                        self.aggregation_method,
+                        &self.aggregation_method,
                         self.ignore_no_data,
                     )
                 })
@@ -299,6 +313,8 @@ where
         initial_collection: &FeatureCollection<G>,
         raster: &RasterTile2D<P>,
     ) -> Result<Self> {
+        let area_weighted = self.area_weighted;
+
         let state = loop {
             if let Some(state) = &mut self.state {
                 break state;
@@ -307,7 +323,7 @@ where
             self.initialize::<P>(initial_collection, &raster.time)?;
         };
         let collection = &state.covered_pixels.collection_ref();
-        let aggregator = &mut state.aggregators[raster.band as usize];
+        let aggregators = &mut state.aggregators[raster.band as usize];
         let covered_pixels = &state.covered_pixels;
 
         if state.feature_pixels.is_some() && raster.tile_position == state.current_tile {
@@ -323,7 +339,17 @@ where
 
             state.feature_pixels = Some(
                 (0..collection.len())
-                    .map(|feature_index| covered_pixels.covered_pixels(feature_index, raster))
+                    .map(|feature_index| {
+                        if area_weighted {
+                            covered_pixels.covered_pixel_fractions(feature_index, raster)
+                        } else {
+                            covered_pixels
+                                .covered_pixels(feature_index, raster)
+                                .into_iter()
+                                .map(|grid_idx| (grid_idx, 1.))
+                                .collect()
+                        }
+                    })
                     .collect::<Vec<_>>(),
             );
         };
@@ -335,15 +361,17 @@ where
             .iter()
             .enumerate()
         {
-            for grid_idx in feature_pixels {
-                let Ok(value) = raster.get_at_grid_index(*grid_idx) else {
+            for &(grid_idx, weight) in feature_pixels {
+                let Ok(value) = raster.get_at_grid_index(grid_idx) else {
                     continue; // not found in this raster tile
                 };
 
-                if let Some(data) = value {
-                    aggregator.add_value(feature_index, data, 1);
-                } else {
-                    aggregator.add_null(feature_index);
+                for aggregator in aggregators.iter_mut() {
+                    if let Some(data) = value {
+                        aggregator.add_value(feature_index, data, weight);
+                    } else {
+                        aggregator.add_null(feature_index);
+                    }
                 }
             }
         }
@@ -365,6 +393,7 @@ where
                 state
                     .aggregators
                     .into_iter()
+                    .flatten()
                     .map(TypedAggregator::into_data),
             )
             .collect::<Vec<_>>();
@@ -408,8 +437,9 @@ where
                 &raster_input.column_names,
                 query.clone(),
                 ctx,
-                self.aggregation_method,
+                &self.aggregation_method,
                 self.ignore_no_data,
+                self.area_weighted,
             );
         }
 
@@ -517,6 +547,7 @@ mod tests {
             }],
             FeatureAggregationMethod::First,
             false,
+            false,
         );
 
         let mut result = processor
@@ -625,6 +656,7 @@ mod tests {
             }],
             FeatureAggregationMethod::First,
             false,
+            false,
         );
 
         let mut result = processor
@@ -744,6 +776,7 @@ mod tests {
             }],
             FeatureAggregationMethod::First,
             false,
+            false,
         );
 
         let mut result = processor
@@ -866,6 +899,7 @@ mod tests {
             }],
             FeatureAggregationMethod::First,
             false,
+            false,
         );
 
         let mut result = processor
@@ -1061,6 +1095,7 @@ mod tests {
             }],
             FeatureAggregationMethod::Mean,
             false,
+            false,
         );
 
         let mut result = processor
@@ -1276,6 +1311,7 @@ mod tests {
             }],
             FeatureAggregationMethod::Mean,
             false,
+            false,
         );
 
         let mut result = processor
@@ -1589,6 +1625,7 @@ mod tests {
             }],
             FeatureAggregationMethod::Mean,
             false,
+            false,
         );
 
         let mut result = processor
@@ -1,8 +1,12 @@
+use std::collections::BTreeMap;
 use std::iter::Enumerate;
+use std::ops::RangeInclusive;
 
 use geoengine_datatypes::collections::{FeatureCollection, GeometryRandomAccess};
-use geoengine_datatypes::primitives::{Geometry, MultiPoint, MultiPointAccess, MultiPolygon};
-use geoengine_datatypes::raster::{GridContains, GridShapeAccess};
+use geoengine_datatypes::primitives::{
+    Coordinate2D, Geometry, MultiPoint, MultiPointAccess, MultiPolygon, MultiPolygonAccess,
+};
+use geoengine_datatypes::raster::{GeoTransform, GridContains, GridIdx, GridShapeAccess};
 use geoengine_datatypes::{
     primitives::TimeInterval,
     raster::{GridIdx2D, Pixel, RasterTile2D},
@@ -98,6 +102,19 @@ pub trait CoveredPixels<G: Geometry>: Send + Sync {
         raster: &RasterTile2D<P>,
     ) -> Vec<GridIdx2D>;
 
+    /// return the pixels of the given `raster` that are (partially) covered by the geometries of the
+    /// feature at the `feature_index` together with the covered fraction of their area
+    fn covered_pixel_fractions<P: Pixel>(
+        &self,
+        feature_index: usize,
+        raster: &RasterTile2D<P>,
+    ) -> Vec<(GridIdx2D, f64)> {
+        self.covered_pixels(feature_index, raster)
+            .into_iter()
+            .map(|idx| (idx, 1.))
+            .collect()
+    }
+
     fn collection_ref(&self) -> &FeatureCollection<G>;
 
     fn collection(self) -> FeatureCollection<G>;
@@ -176,6 +193,65 @@ impl CoveredPixels<MultiPolygon> for MultiPolygonCoveredPixels {
         pixels
     }
 
+    /// Computes the exact covered area of each pixel by clipping the rings of the polygons.
+    /// The rings are first clipped to the tile, then to each row and finally to each pixel of a row,
+    /// so that each step only processes the part of the ring that is relevant for it.
+    fn covered_pixel_fractions<P: Pixel>(
+        &self,
+        feature_index: usize,
+        raster: &RasterTile2D<P>,
+    ) -> Vec<(GridIdx2D, f64)> {
+        let Some(geometry) = self.collection_ref().geometry_at(feature_index) else {
+            return vec![];
+        };
+
+        let geo_transform = raster.tile_information().tile_geo_transform();
+        let pixel_area = (geo_transform.x_pixel_size() * geo_transform.y_pixel_size()).abs();
+
+        let [height, width] = raster.grid_shape_array();
+        let (max_row, max_col) = (height as isize - 1, width as isize - 1);
+        let (tile_min_x, tile_max_x) = pixel_range(&geo_transform, Axis::X, 0, max_col);
+        let (tile_min_y, tile_max_y) = pixel_range(&geo_transform, Axis::Y, 0, max_row);
+
+        // the covered area of each pixel, where the areas of the holes are subtracted
+        let mut covered_areas = BTreeMap::<(isize, isize), f64>::new();
+
+        for polygon in geometry.polygons() {
+            for (ring_index, ring) in polygon.iter().enumerate() {
+                let sign = if ring_index == 0 { 1. } else { -1. };
+
+                let ring = clip_ring(ring, Axis::X, tile_min_x, tile_max_x);
+                let ring = clip_ring(&ring, Axis::Y, tile_min_y, tile_max_y);
+                if ring.len() < 3 {
+                    continue;
+                }
+
+                for row in index_range(&ring, &geo_transform, Axis::Y, max_row) {
+                    let (min_y, max_y) = pixel_range(&geo_transform, Axis::Y, row, row);
+                    let row_ring = clip_ring(&ring, Axis::Y, min_y, max_y);
+                    if row_ring.len() < 3 {
+                        continue;
+                    }
+
+                    for col in index_range(&row_ring, &geo_transform, Axis::X, max_col) {
+                        let (min_x, max_x) = pixel_range(&geo_transform, Axis::X, col, col);
+                        let area = ring_area(&clip_ring(&row_ring, Axis::X, min_x, max_x));
+
+                        if area > 0. {
+                            *covered_areas.entry((row, col)).or_default() += sign * area;
+                        }
+                    }
+                }
+            }
+        }
+
+        covered_areas
+            .into_iter()
+            .map(|((row, col), area)| ([row, col].into(), (area / pixel_area).min(1.)))
+            .filter(|(_, fraction)| *fraction > f64::EPSILON)
+            .collect()
+    }
+
     fn collection_ref(&self) -> &FeatureCollection<MultiPolygon> {
         self.tester_with_collection.collection()
     }
@@ -185,6 +261,126 @@ impl CoveredPixels<MultiPolygon> for MultiPolygonCoveredPixels {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+}
+
+impl Axis {
+    fn value(self, coordinate: Coordinate2D) -> f64 {
+        match self {
+            Axis::X => coordinate.x,
+            Axis::Y => coordinate.y,
+        }
+    }
+}
+
+/// The minimum and maximum coordinate along the `axis` of the pixels from index `start` to `end` (inclusive)
+fn pixel_range(geo_transform: &GeoTransform, axis: Axis, start: isize, end: isize) -> (f64, f64) {
+    let coordinate = |index: isize| {
+        let grid_idx = match axis {
+            Axis::X => [0, index],
+            Axis::Y => [index, 0],
+        };
+        axis.value(geo_transform.grid_idx_to_pixel_upper_left_coordinate_2d(grid_idx.into()))
+    };
+
+    let (a, b) = (coordinate(start), coordinate(end + 1));
+    (a.min(b), a.max(b))
+}
+
+/// The indices along the `axis` of the pixels that intersect the bounds of the `ring`, clamped to `0..=max_index`
+fn index_range(
+    ring: &[Coordinate2D],
+    geo_transform: &GeoTransform,
+    axis: Axis,
+    max_index: isize,
+) -> RangeInclusive<isize> {
+    let index = |value: f64| {
+        let GridIdx([row, col]) =
+            geo_transform.coordinate_to_grid_idx_2d(Coordinate2D::new(value, value));
+        match axis {
+            Axis::X => col,
+            Axis::Y => row,
+        }
+    };
+
+    let (min, max) = ring
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), coordinate| {
+            let value = axis.value(*coordinate);
+            (min.min(value), max.max(value))
+        });
+
+    let (a, b) = (index(min), index(max));
+    a.min(b).clamp(0, max_index)..=a.max(b).clamp(0, max_index)
+}
+
+/// Clips the `ring` to the range from `min` to `max` along the `axis` with the Sutherland–Hodgman algorithm.
+/// The result may contain degenerated edges along the bounds, which do not contribute to its area.
+fn clip_ring(ring: &[Coordinate2D], axis: Axis, min: f64, max: f64) -> Vec<Coordinate2D> {
+    let ring = clip_ring_at(ring, axis, min, |value| value >= min);
+    clip_ring_at(&ring, axis, max, |value| value <= max)
+}
+
+/// Clips the `ring` at the `bound` along the `axis`, keeping the part where `is_inside` holds.
+fn clip_ring_at(
+    ring: &[Coordinate2D],
+    axis: Axis,
+    bound: f64,
+    is_inside: impl Fn(f64) -> bool,
+) -> Vec<Coordinate2D> {
+    let mut clipped = Vec::with_capacity(ring.len() + 2);
+
+    let Some(&last) = ring.last() else {
+        return clipped;
+    };
+
+    let intersection = |a: Coordinate2D, b: Coordinate2D| {
+        let t = (bound - axis.value(a)) / (axis.value(b) - axis.value(a));
+        match axis {
+            Axis::X => Coordinate2D::new(bound, a.y + t * (b.y - a.y)),
+            Axis::Y => Coordinate2D::new(a.x + t * (b.x - a.x), bound),
+        }
+    };
+
+    let mut previous = last;
+    for &current in ring {
+        match (
+            is_inside(axis.value(previous)),
+            is_inside(axis.value(current)),
+        ) {
+            (true, true) => clipped.push(current),
+            (true, false) => clipped.push(intersection(previous, current)),
+            (false, true) => {
+                clipped.push(intersection(previous, current));
+                clipped.push(current);
+            }
+            (false, false) => {}
+        }
+        previous = current;
+    }
+
+    clipped
+}
+
+/// The area of the `ring` using the shoelace formula.
+/// The coordinates are taken relative to the first one to reduce rounding errors.
+fn ring_area(ring: &[Coordinate2D]) -> f64 {
+    let Some(&origin) = ring.first() else {
+        return 0.;
+    };
+
+    let twice_area: f64 = ring
+        .iter()
+        .zip(ring.iter().cycle().skip(1))
+        .map(|(a, b)| (a.x - origin.x) * (b.y - origin.y) - (b.x - origin.x) * (a.y - origin.y))
+        .sum();
+
+    (twice_area / 2.).abs()
+}
+
 /// Creates a new calculator for for pixels covered by a given `feature_collection`'s geometries.
 pub trait PixelCoverCreator<G: Geometry> {
     type C: CoveredPixels<G>;
@@ -211,6 +407,10 @@ impl PixelCoverCreator<MultiPolygon> for FeatureCollection<MultiPolygon> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use geoengine_datatypes::collections::MultiPolygonCollection;
+    use geoengine_datatypes::primitives::CacheHint;
+    use geoengine_datatypes::raster::{Grid2D, TileInformation};
+    use geoengine_datatypes::util::test::TestDefault;
 
     #[test]
     fn time_spans() {
@@ -244,4 +444,92 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn covered_pixel_fractions() {
+        let raster = RasterTile2D::<u8>::new_with_tile_info(
+            TimeInterval::default(),
+            TileInformation {
+                global_geo_transform: TestDefault::test_default(),
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [2, 2].into(),
+            },
+            0,
+            Grid2D::new([2, 2].into(), vec![1; 4]).unwrap().into(),
+            CacheHint::default(),
+        );
+
+        let covered_pixels = MultiPolygonCollection::from_data(
+            vec![
+                // a triangle that covers the upper left pixel and half of its neighbors
+                MultiPolygon::new(vec![vec![vec![
+                    (0., 0.).into(),
+                    (2., 0.).into(),
+                    (0., -2.).into(),
+                    (0., 0.).into(),
+                ]]])
+                .unwrap(),
+                // a square with a hole in the upper left pixel
+                MultiPolygon::new(vec![vec![
+                    vec![
+                        (0., 0.).into(),
+                        (2., 0.).into(),
+                        (2., -2.).into(),
+                        (0., -2.).into(),
+                        (0., 0.).into(),
+                    ],
+                    vec![
+                        (0.5, -0.5).into(),
+                        (0.5, -1.).into(),
+                        (1., -1.).into(),
+                        (1., -0.5).into(),
+                        (0.5, -0.5).into(),
+                    ],
+                ]])
+                .unwrap(),
+                // a square that exceeds the tile
+                MultiPolygon::new(vec![vec![vec![
+                    (-10., 10.).into(),
+                    (10., 10.).into(),
+                    (10., -10.).into(),
+                    (-10., -10.).into(),
+                    (-10., 10.).into(),
+                ]]])
+                .unwrap(),
+            ],
+            vec![TimeInterval::default(); 3],
+            Default::default(),
+            CacheHint::default(),
+        )
+        .unwrap()
+        .create_covered_pixels();
+
+        assert_eq!(
+            covered_pixels.covered_pixel_fractions(0, &raster),
+            vec![
+                ([0, 0].into(), 1.),
+                ([0, 1].into(), 0.5),
+                ([1, 0].into(), 0.5),
+            ]
+        );
+        assert_eq!(
+            covered_pixels.covered_pixel_fractions(1, &raster),
+            vec![
+                ([0, 0].into(), 0.75),
+                ([0, 1].into(), 1.),
+                ([1, 0].into(), 1.),
+                ([1, 1].into(), 1.),
+            ]
+        );
+        assert_eq!(
+            covered_pixels.covered_pixel_fractions(2, &raster),
+            vec![
+                ([0, 0].into(), 1.),
+                ([0, 1].into(), 1.),
+                ([1, 0].into(), 1.),
+                ([1, 1].into(), 1.),
+            ]
+        );
+    }
 }
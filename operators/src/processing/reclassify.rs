@@ -0,0 +1,619 @@
+use crate::engine::{
+    CanonicOperatorName, ExecutionContext, InitializedRasterOperator, InitializedSources, Operator,
+    OperatorName, QueryContext, QueryProcessor, RasterBandDescriptor, RasterBandDescriptors,
+    RasterOperator, RasterQueryProcessor, RasterResultDescriptor, SingleRasterSource,
+    TypedRasterQueryProcessor, WorkflowOperatorPath,
+};
+use crate::util::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use geoengine_datatypes::primitives::{
+    BandSelection, ClassificationMeasurement, Measurement, RasterQueryRectangle, SpatialPartition2D,
+};
+use geoengine_datatypes::raster::{MapElementsParallel, Pixel, RasterDataType, RasterTile2D};
+use num_traits::AsPrimitive;
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, Snafu};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// The reclassify operator maps values and value ranges of a raster to new values, e.g., to create land-cover classes.
+/// The rules are checked in order and the first matching rule determines the output of a pixel.
+/// Each band of the input raster is reclassified separately.
+pub type Reclassify = Operator<ReclassifyParams, SingleRasterSource>;
+
+impl OperatorName for Reclassify {
+    const TYPE_NAME: &'static str = "Reclassify";
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclassifyParams {
+    pub rules: Vec<ReclassificationRule>,
+    /// The output for pixels that match no rule. If it is not set, they become NODATA.
+    #[serde(default)]
+    pub default_value: Option<f64>,
+    /// The output for NODATA pixels. If it is not set, they stay NODATA.
+    #[serde(default)]
+    pub no_data_value: Option<f64>,
+    /// The data type of the output raster. The default is the data type of the input raster.
+    #[serde(default)]
+    pub output_data_type: Option<RasterDataType>,
+    /// The measurement of all output bands, which names the classes of the output values.
+    pub output_measurement: ClassificationMeasurement,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclassificationRule {
+    #[serde(flatten)]
+    pub input: ReclassificationInput,
+    /// The output for pixels that match the rule. `null` maps them to NODATA.
+    pub output: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ReclassificationInput {
+    /// Matches pixels with exactly this value
+    Value { value: f64 },
+    /// Matches pixels between `min` and `max`. A missing bound is unbounded.
+    /// By default, `min` is inclusive and `max` is exclusive.
+    #[serde(rename_all = "camelCase")]
+    Range {
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+        #[serde(default = "default_min_inclusive")]
+        min_inclusive: bool,
+        #[serde(default)]
+        max_inclusive: bool,
+    },
+}
+
+fn default_min_inclusive() -> bool {
+    true
+}
+
+impl ReclassificationInput {
+    #[allow(clippy::float_cmp)]
+    fn matches(&self, value: f64) -> bool {
+        match *self {
+            Self::Value { value: expected } => value == expected,
+            Self::Range {
+                min,
+                max,
+                min_inclusive,
+                max_inclusive,
+            } => {
+                let above_min = min.map_or(true, |min| {
+                    if min_inclusive {
+                        value >= min
+                    } else {
+                        value > min
+                    }
+                });
+                let below_max = max.map_or(true, |max| {
+                    if max_inclusive {
+                        value <= max
+                    } else {
+                        value < max
+                    }
+                });
+
+                above_min && below_max
+            }
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)), module(error))]
+pub enum ReclassifyError {
+    #[snafu(display(
+        "The range of rule {} has a lower bound {} that is larger than its upper bound {}",
+        rule,
+        min,
+        max
+    ))]
+    InvalidRange { rule: usize, min: f64, max: f64 },
+
+    #[snafu(display("Rule {} must not match NaN", rule))]
+    NanInput { rule: usize },
+
+    #[snafu(display(
+        "The output value {} cannot be represented by the output data type {:?}",
+        value,
+        data_type
+    ))]
+    InvalidOutputValue {
+        value: f64,
+        data_type: RasterDataType,
+    },
+}
+
+#[typetag::serde]
+#[async_trait]
+impl RasterOperator for Reclassify {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedRasterOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        let initialized_source = self.sources.initialize_sources(path, context).await?;
+        let raster_source = initialized_source.raster;
+
+        let in_descriptor = raster_source.result_descriptor();
+
+        let output_data_type = self
+            .params
+            .output_data_type
+            .unwrap_or(in_descriptor.data_type);
+
+        check_rules(&self.params, output_data_type)?;
+
+        let measurement = Measurement::Classification(self.params.output_measurement);
+        let bands = in_descriptor
+            .bands
+            .iter()
+            .map(|band| RasterBandDescriptor::new(band.name.clone(), measurement.clone()))
+            .collect::<Vec<_>>();
+
+        let result_descriptor = RasterResultDescriptor {
+            data_type: output_data_type,
+            bands: RasterBandDescriptors::new(bands)?,
+            ..in_descriptor.clone()
+        };
+
+        let initialized_operator = InitializedReclassify {
+            name,
+            result_descriptor,
+            raster_source,
+            reclassification: Arc::new(Reclassification {
+                rules: self.params.rules,
+                default_value: self.params.default_value,
+                no_data_value: self.params.no_data_value,
+            }),
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(Reclassify);
+}
+
+/// Checks that the ranges are valid and that all outputs fit into the output data type.
+fn check_rules(params: &ReclassifyParams, output_data_type: RasterDataType) -> Result<()> {
+    for (rule_index, rule) in params.rules.iter().enumerate() {
+        match rule.input {
+            ReclassificationInput::Value { value } => {
+                ensure!(!value.is_nan(), error::NanInput { rule: rule_index });
+            }
+            ReclassificationInput::Range { min, max, .. } => {
+                ensure!(
+                    !min.is_some_and(f64::is_nan) && !max.is_some_and(f64::is_nan),
+                    error::NanInput { rule: rule_index }
+                );
+
+                if let (Some(min), Some(max)) = (min, max) {
+                    ensure!(
+                        min <= max,
+                        error::InvalidRange {
+                            rule: rule_index,
+                            min,
+                            max
+                        }
+                    );
+                }
+            }
+        }
+    }
+
+    let outputs = params
+        .rules
+        .iter()
+        .map(|rule| rule.output)
+        .chain([params.default_value, params.no_data_value])
+        .flatten();
+
+    for value in outputs {
+        ensure!(
+            output_data_type.is_valid(value),
+            error::InvalidOutputValue {
+                value,
+                data_type: output_data_type
+            }
+        );
+    }
+
+    Ok(())
+}
+
+/// The rules and fallbacks of a [`Reclassify`] operator
+#[derive(Debug)]
+struct Reclassification {
+    rules: Vec<ReclassificationRule>,
+    default_value: Option<f64>,
+    no_data_value: Option<f64>,
+}
+
+impl Reclassification {
+    fn reclassify<PIn, POut>(&self, pixel: Option<PIn>) -> Option<POut>
+    where
+        PIn: Pixel,
+        POut: Pixel,
+        f64: AsPrimitive<POut>,
+    {
+        let output = match pixel {
+            Some(pixel) => {
+                let value: f64 = pixel.as_();
+
+                self.rules
+                    .iter()
+                    .find(|rule| rule.input.matches(value))
+                    .map_or(self.default_value, |rule| rule.output)
+            }
+            None => self.no_data_value,
+        };
+
+        output.map(AsPrimitive::as_)
+    }
+}
+
+pub struct InitializedReclassify {
+    name: CanonicOperatorName,
+    result_descriptor: RasterResultDescriptor,
+    raster_source: Box<dyn InitializedRasterOperator>,
+    reclassification: Arc<Reclassification>,
+}
+
+impl InitializedRasterOperator for InitializedReclassify {
+    fn query_processor(&self) -> Result<TypedRasterQueryProcessor> {
+        let source_processor = self.raster_source.query_processor()?;
+        let output_data_type = self.result_descriptor.data_type;
+
+        Ok(
+            call_on_generic_raster_processor!(source_processor, source => {
+                call_generic_raster_processor!(
+                    output_data_type,
+                    ReclassifyProcessor::create_boxed(
+                        source,
+                        self.result_descriptor.clone(),
+                        self.reclassification.clone(),
+                    )
+                )
+            }),
+        )
+    }
+
+    fn result_descriptor(&self) -> &RasterResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+pub struct ReclassifyProcessor<Q, PIn, POut>
+where
+    Q: RasterQueryProcessor<RasterType = PIn>,
+{
+    source: Q,
+    result_descriptor: RasterResultDescriptor,
+    reclassification: Arc<Reclassification>,
+    _p_out: PhantomData<POut>,
+}
+
+impl<Q, PIn, POut> ReclassifyProcessor<Q, PIn, POut>
+where
+    Q: RasterQueryProcessor<RasterType = PIn> + 'static,
+    PIn: Pixel,
+    POut: Pixel + Default,
+    f64: AsPrimitive<POut>,
+{
+    fn create_boxed(
+        source: Q,
+        result_descriptor: RasterResultDescriptor,
+        reclassification: Arc<Reclassification>,
+    ) -> Box<dyn RasterQueryProcessor<RasterType = POut>> {
+        Self {
+            source,
+            result_descriptor,
+            reclassification,
+            _p_out: PhantomData,
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl<Q, PIn, POut> QueryProcessor for ReclassifyProcessor<Q, PIn, POut>
+where
+    Q: RasterQueryProcessor<RasterType = PIn>,
+    PIn: Pixel,
+    POut: Pixel + Default,
+    f64: AsPrimitive<POut>,
+{
+    type Output = RasterTile2D<POut>;
+    type SpatialBounds = SpatialPartition2D;
+    type Selection = BandSelection;
+    type ResultDescription = RasterResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: RasterQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        let stream = self.source.raster_query(query, ctx).await?;
+
+        let stream = stream.and_then(move |tile| {
+            let reclassification = self.reclassification.clone();
+
+            async move {
+                crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
+                    tile.map_elements_parallel(|pixel: Option<PIn>| {
+                        reclassification.reclassify::<PIn, POut>(pixel)
+                    })
+                })
+                .await
+                .map_err(Into::into)
+            }
+        });
+
+        Ok(stream.boxed())
+    }
+
+    fn result_descriptor(&self) -> &RasterResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{MockExecutionContext, MockQueryContext};
+    use crate::mock::{MockRasterSource, MockRasterSourceParams};
+    use geoengine_datatypes::primitives::{CacheHint, SpatialResolution, TimeInterval};
+    use geoengine_datatypes::raster::{
+        Grid2D, GridOrEmpty, MaskedGrid2D, TileInformation, TilingSpecification,
+    };
+    use geoengine_datatypes::spatial_reference::SpatialReference;
+    use geoengine_datatypes::util::test::TestDefault;
+    use std::collections::HashMap;
+
+    #[test]
+    fn it_deserializes_the_params() {
+        let params: ReclassifyParams = serde_json::from_value(serde_json::json!({
+            "rules": [
+                { "type": "value", "value": 0, "output": null },
+                { "type": "range", "min": 0, "max": 10, "maxInclusive": true, "output": 1 },
+                { "type": "range", "min": 10, "output": 2 },
+            ],
+            "defaultValue": 3,
+            "outputDataType": "U8",
+            "outputMeasurement": {
+                "measurement": "land cover",
+                "classes": { "1": "water", "2": "forest" },
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            ReclassifyParams {
+                rules: vec![
+                    ReclassificationRule {
+                        input: ReclassificationInput::Value { value: 0. },
+                        output: None,
+                    },
+                    ReclassificationRule {
+                        input: ReclassificationInput::Range {
+                            min: Some(0.),
+                            max: Some(10.),
+                            min_inclusive: true,
+                            max_inclusive: true,
+                        },
+                        output: Some(1.),
+                    },
+                    ReclassificationRule {
+                        input: ReclassificationInput::Range {
+                            min: Some(10.),
+                            max: None,
+                            min_inclusive: true,
+                            max_inclusive: false,
+                        },
+                        output: Some(2.),
+                    },
+                ],
+                default_value: Some(3.),
+                no_data_value: None,
+                output_data_type: Some(RasterDataType::U8),
+                output_measurement: ClassificationMeasurement {
+                    measurement: "land cover".to_string(),
+                    classes: HashMap::from([(1, "water".to_string()), (2, "forest".to_string())]),
+                },
+            }
+        );
+    }
+
+    fn make_raster() -> Box<dyn RasterOperator> {
+        let raster_tile = RasterTile2D::<f32>::new_with_tile_info(
+            TimeInterval::new_unchecked(0, 10),
+            TileInformation {
+                global_tile_position: [-1, 0].into(),
+                tile_size_in_pixels: [2, 3].into(),
+                global_geo_transform: TestDefault::test_default(),
+            },
+            0,
+            GridOrEmpty::from(
+                MaskedGrid2D::new(
+                    Grid2D::new([2, 3].into(), vec![-1., 0., 2.5, 5., 10., 0.]).unwrap(),
+                    Grid2D::new([2, 3].into(), vec![true, true, true, true, true, false]).unwrap(),
+                )
+                .unwrap(),
+            ),
+            CacheHint::default(),
+        );
+
+        MockRasterSource {
+            params: MockRasterSourceParams {
+                data: vec![raster_tile],
+                result_descriptor: RasterResultDescriptor {
+                    data_type: RasterDataType::F32,
+                    spatial_reference: SpatialReference::epsg_4326().into(),
+                    time: None,
+                    bbox: None,
+                    resolution: None,
+                    bands: RasterBandDescriptors::new_single_band(),
+                },
+            },
+        }
+        .boxed()
+    }
+
+    #[tokio::test]
+    async fn it_reclassifies_values_and_ranges() {
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [2, 3].into(),
+        ));
+
+        let output_measurement = ClassificationMeasurement {
+            measurement: "suitability".to_string(),
+            classes: HashMap::from([(1, "low".to_string()), (2, "high".to_string())]),
+        };
+
+        let operator = Reclassify {
+            params: ReclassifyParams {
+                rules: vec![
+                    ReclassificationRule {
+                        input: ReclassificationInput::Value { value: 0. },
+                        output: None,
+                    },
+                    ReclassificationRule {
+                        input: ReclassificationInput::Range {
+                            min: Some(0.),
+                            max: Some(5.),
+                            min_inclusive: false,
+                            max_inclusive: true,
+                        },
+                        output: Some(1.),
+                    },
+                    ReclassificationRule {
+                        input: ReclassificationInput::Range {
+                            min: Some(5.),
+                            max: None,
+                            min_inclusive: true,
+                            max_inclusive: false,
+                        },
+                        output: Some(2.),
+                    },
+                ],
+                default_value: Some(0.),
+                no_data_value: Some(255.),
+                output_data_type: Some(RasterDataType::U8),
+                output_measurement: output_measurement.clone(),
+            },
+            sources: SingleRasterSource {
+                raster: make_raster(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await
+        .unwrap();
+
+        assert_eq!(operator.result_descriptor().data_type, RasterDataType::U8);
+        assert_eq!(
+            operator.result_descriptor().bands[0].measurement,
+            Measurement::Classification(output_measurement)
+        );
+
+        let processor = operator.query_processor().unwrap().get_u8().unwrap();
+
+        let query_rect = RasterQueryRectangle {
+            spatial_bounds: SpatialPartition2D::new((0., 2.).into(), (3., 0.).into()).unwrap(),
+            time_interval: TimeInterval::new_unchecked(0, 10),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: BandSelection::first(),
+        };
+        let query_ctx = MockQueryContext::test_default();
+
+        let result_stream = processor.query(query_rect, &query_ctx).await.unwrap();
+
+        let result: Vec<Result<RasterTile2D<u8>>> = result_stream.collect().await;
+        let result = result.into_iter().collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(result.len(), 1);
+
+        let tile = result.into_iter().next().unwrap().into_materialized_tile();
+
+        // -1 matches no rule, 0 is mapped to NODATA and the NODATA pixel is mapped to 255
+        assert_eq!(tile.grid_array.inner_grid.data, vec![0, 0, 1, 1, 2, 255]);
+        assert_eq!(
+            tile.grid_array.validity_mask.data,
+            vec![true, false, true, true, true, true]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_rules() {
+        let exe_ctx = MockExecutionContext::test_default();
+
+        let reclassify = |rules: Vec<ReclassificationRule>| Reclassify {
+            params: ReclassifyParams {
+                rules,
+                default_value: None,
+                no_data_value: None,
+                output_data_type: Some(RasterDataType::U8),
+                output_measurement: ClassificationMeasurement {
+                    measurement: "class".to_string(),
+                    classes: HashMap::new(),
+                },
+            },
+            sources: SingleRasterSource {
+                raster: make_raster(),
+            },
+        };
+
+        let result = reclassify(vec![ReclassificationRule {
+            input: ReclassificationInput::Range {
+                min: Some(5.),
+                max: Some(1.),
+                min_inclusive: true,
+                max_inclusive: false,
+            },
+            output: Some(1.),
+        }])
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::Reclassify {
+                source: ReclassifyError::InvalidRange { rule: 0, .. }
+            })
+        ));
+
+        let result = reclassify(vec![ReclassificationRule {
+            input: ReclassificationInput::Value { value: 1. },
+            output: Some(256.),
+        }])
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::Reclassify {
+                source: ReclassifyError::InvalidOutputValue { .. }
+            })
+        ));
+    }
+}
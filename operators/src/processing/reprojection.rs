@@ -1,10 +1,12 @@
 use std::marker::PhantomData;
 
+use super::interpolation::InterpolationMethod;
 use super::map_query::MapQueryProcessor;
 use crate::{
     adapters::{
-        fold_by_coordinate_lookup_future, FillerTileCacheExpirationStrategy, RasterSubQueryAdapter,
-        SparseTilesFillAdapter, TileReprojectionSubQuery,
+        fold_by_coordinate_lookup_future, fold_by_interpolation_future,
+        FillerTileCacheExpirationStrategy, RasterSubQueryAdapter, SparseTilesFillAdapter,
+        TileInterpolationReprojectionSubQuery, TileReprojectionSubQuery,
     },
     engine::{
         CanonicOperatorName, ExecutionContext, InitializedRasterOperator, InitializedSources,
@@ -29,7 +31,10 @@ use geoengine_datatypes::{
         BandSelection, BoundingBox2D, ColumnSelection, Geometry, RasterQueryRectangle,
         SpatialPartition2D, SpatialPartitioned, SpatialResolution, VectorQueryRectangle,
     },
-    raster::{Pixel, RasterTile2D, TilingSpecification},
+    raster::{
+        Average, BiCubic, Bilinear, InterpolationAlgorithm, Lanczos, Mode, Pixel, RasterTile2D,
+        TilingSpecification,
+    },
     spatial_reference::SpatialReference,
     util::arrow::ArrowTyped,
 };
@@ -39,6 +44,9 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct ReprojectionParams {
     pub target_spatial_reference: SpatialReference,
+    /// The method for sampling raster inputs. It is ignored for vector inputs.
+    #[serde(default)]
+    pub interpolation: InterpolationMethod,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
@@ -71,6 +79,7 @@ pub struct InitializedRasterReprojection {
     source_srs: SpatialReference,
     target_srs: SpatialReference,
     tiling_spec: TilingSpecification,
+    interpolation: InterpolationMethod,
 }
 
 impl InitializedVectorReprojection {
@@ -162,6 +171,7 @@ impl InitializedRasterReprojection {
             source_srs: in_srs,
             target_srs: params.target_spatial_reference,
             tiling_spec,
+            interpolation: params.interpolation,
         })
     }
 
@@ -419,6 +429,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::U16 => {
@@ -432,6 +443,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
 
@@ -446,6 +458,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::U64 => {
@@ -459,6 +472,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::I8 => {
@@ -472,6 +486,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::I16 => {
@@ -485,6 +500,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::I32 => {
@@ -498,6 +514,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::I64 => {
@@ -511,6 +528,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::F32 => {
@@ -524,6 +542,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
             geoengine_datatypes::raster::RasterDataType::F64 => {
@@ -537,6 +556,7 @@ impl InitializedRasterOperator for InitializedRasterReprojection {
                     self.target_srs,
                     self.tiling_spec,
                     self.state,
+                    self.interpolation,
                 )))
             }
         })
@@ -557,6 +577,7 @@ where
     to: SpatialReference,
     tiling_spec: TilingSpecification,
     state: Option<ReprojectionBounds>,
+    interpolation: InterpolationMethod,
     _phantom_data: PhantomData<P>,
 }
 
@@ -577,6 +598,7 @@ where
         to: SpatialReference,
         tiling_spec: TilingSpecification,
         state: Option<ReprojectionBounds>,
+        interpolation: InterpolationMethod,
     ) -> Self {
         Self {
             source,
@@ -585,9 +607,100 @@ where
             to,
             tiling_spec,
             state,
+            interpolation,
             _phantom_data: PhantomData,
         }
     }
+
+    /// Reproject by looking up the input pixel at each projected output coordinate
+    fn coordinate_lookup_query<'a>(
+        &'a self,
+        query: RasterQueryRectangle,
+        ctx: &'a dyn QueryContext,
+        state: ReprojectionBounds,
+    ) -> Result<BoxStream<'a, Result<RasterTile2D<P>>>> {
+        let valid_bounds_in = state.valid_in_bounds;
+        let valid_bounds_out = state.valid_out_bounds;
+
+        // calculate the spatial resolution the input data should have using the intersection and the requested resolution
+        let in_spatial_res = suggest_pixel_size_from_diag_cross_projected(
+            valid_bounds_out,
+            valid_bounds_in,
+            query.spatial_resolution,
+        )?;
+
+        // setup the subquery
+        let sub_query_spec = TileReprojectionSubQuery {
+            in_srs: self.from,
+            out_srs: self.to,
+            fold_fn: fold_by_coordinate_lookup_future,
+            in_spatial_res,
+            valid_bounds_in,
+            valid_bounds_out,
+            _phantom_data: PhantomData,
+        };
+
+        // return the adapter which will reproject the tiles and uses the fill adapter to inject missing tiles
+        Ok(RasterSubQueryAdapter::<'a, P, _, _>::new(
+            &self.source,
+            query,
+            self.tiling_spec,
+            ctx,
+            sub_query_spec,
+        )
+        .filter_and_fill(FillerTileCacheExpirationStrategy::DerivedFromSurroundingTiles))
+    }
+
+    /// Reproject by collecting all required input pixels and sampling them with the `InterpolationAlgorithm`
+    fn interpolating_query<'a, I: InterpolationAlgorithm<P>>(
+        &'a self,
+        query: RasterQueryRectangle,
+        ctx: &'a dyn QueryContext,
+        state: ReprojectionBounds,
+    ) -> Result<BoxStream<'a, Result<RasterTile2D<P>>>> {
+        // calculate the spatial resolution the input data should have using the intersection and the requested resolution
+        let suggested_in_spatial_res = suggest_pixel_size_from_diag_cross_projected(
+            state.valid_out_bounds,
+            state.valid_in_bounds,
+            query.spatial_resolution,
+        )?;
+
+        // aggregating algorithms use the finer source resolution in order to cover all the source pixels
+        let in_spatial_res = match self.source.result_descriptor().resolution {
+            Some(source_res)
+                if I::AGGREGATES
+                    && source_res.x < suggested_in_spatial_res.x
+                    && source_res.y < suggested_in_spatial_res.y =>
+            {
+                source_res
+            }
+            _ => suggested_in_spatial_res,
+        };
+
+        let sub_query_spec = TileInterpolationReprojectionSubQuery::<_, I, _> {
+            in_srs: self.from,
+            out_srs: self.to,
+            fold_fn: fold_by_interpolation_future,
+            in_spatial_res,
+            valid_bounds_in: state.valid_in_bounds,
+            valid_bounds_out: state.valid_out_bounds,
+            footprint: [
+                suggested_in_spatial_res.y / in_spatial_res.y,
+                suggested_in_spatial_res.x / in_spatial_res.x,
+            ],
+            tiling_specification: self.tiling_spec,
+            _phantom_data: PhantomData,
+        };
+
+        Ok(RasterSubQueryAdapter::<'a, P, _, _>::new(
+            &self.source,
+            query,
+            self.tiling_spec,
+            ctx,
+            sub_query_spec,
+        )
+        .filter_and_fill(FillerTileCacheExpirationStrategy::DerivedFromSurroundingTiles))
+    }
 }
 
 #[async_trait]
@@ -611,37 +724,25 @@ where
         query: RasterQueryRectangle,
         ctx: &'a dyn QueryContext,
     ) -> Result<BoxStream<'a, Result<Self::Output>>> {
-        if let Some(state) = &self.state {
-            let valid_bounds_in = state.valid_in_bounds;
-            let valid_bounds_out = state.valid_out_bounds;
-
-            // calculate the spatial resolution the input data should have using the intersection and the requested resolution
-            let in_spatial_res = suggest_pixel_size_from_diag_cross_projected(
-                valid_bounds_out,
-                valid_bounds_in,
-                query.spatial_resolution,
-            )?;
-
-            // setup the subquery
-            let sub_query_spec = TileReprojectionSubQuery {
-                in_srs: self.from,
-                out_srs: self.to,
-                fold_fn: fold_by_coordinate_lookup_future,
-                in_spatial_res,
-                valid_bounds_in,
-                valid_bounds_out,
-                _phantom_data: PhantomData,
-            };
-
-            // return the adapter which will reproject the tiles and uses the fill adapter to inject missing tiles
-            Ok(RasterSubQueryAdapter::<'a, P, _, _>::new(
-                &self.source,
-                query,
-                self.tiling_spec,
-                ctx,
-                sub_query_spec,
-            )
-            .filter_and_fill(FillerTileCacheExpirationStrategy::DerivedFromSurroundingTiles))
+        if let Some(state) = self.state {
+            match self.interpolation {
+                InterpolationMethod::NearestNeighbor => {
+                    self.coordinate_lookup_query(query, ctx, state)
+                }
+                InterpolationMethod::BiLinear => {
+                    self.interpolating_query::<Bilinear>(query, ctx, state)
+                }
+                InterpolationMethod::BiCubic => {
+                    self.interpolating_query::<BiCubic>(query, ctx, state)
+                }
+                InterpolationMethod::Lanczos => {
+                    self.interpolating_query::<Lanczos>(query, ctx, state)
+                }
+                InterpolationMethod::Average => {
+                    self.interpolating_query::<Average>(query, ctx, state)
+                }
+                InterpolationMethod::Mode => self.interpolating_query::<Mode>(query, ctx, state),
+            }
         } else {
             log::debug!("No intersection between source data / srs and target srs");
 
@@ -746,6 +847,7 @@ mod tests {
         let initialized_operator = VectorOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference,
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: point_source.into(),
@@ -821,6 +923,7 @@ mod tests {
         let initialized_operator = VectorOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference,
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: lines_source.into(),
@@ -903,6 +1006,7 @@ mod tests {
         let initialized_operator = VectorOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference,
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: polygon_source.into(),
@@ -1025,6 +1129,7 @@ mod tests {
         let initialized_operator = RasterOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference: projection, // This test will do a identity reprojection
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: mrs1.into(),
@@ -1057,6 +1162,99 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn raster_identity_with_aggregating_interpolation() -> Result<()> {
+        let data = vec![
+            RasterTile2D {
+                time: TimeInterval::new_unchecked(0, 5),
+                tile_position: [-1, 0].into(),
+                band: 0,
+                global_geo_transform: TestDefault::test_default(),
+                grid_array: Grid::new([2, 2].into(), vec![1, 2, 3, 4]).unwrap().into(),
+                properties: Default::default(),
+                cache_hint: CacheHint::default(),
+            },
+            RasterTile2D {
+                time: TimeInterval::new_unchecked(0, 5),
+                tile_position: [-1, 1].into(),
+                band: 0,
+                global_geo_transform: TestDefault::test_default(),
+                grid_array: Grid::new([2, 2].into(), vec![7, 8, 9, 10]).unwrap().into(),
+                properties: Default::default(),
+                cache_hint: CacheHint::default(),
+            },
+        ];
+
+        let mut exe_ctx = MockExecutionContext::test_default();
+        exe_ctx.tiling_specification.tile_size_in_pixels = GridShape {
+            // we need a smaller tile size
+            shape_array: [2, 2],
+        };
+
+        let query_ctx = MockQueryContext::test_default();
+
+        for interpolation in [InterpolationMethod::Average, InterpolationMethod::Mode] {
+            let mrs = MockRasterSource {
+                params: MockRasterSourceParams {
+                    data: data.clone(),
+                    result_descriptor: RasterResultDescriptor {
+                        data_type: RasterDataType::U8,
+                        spatial_reference: SpatialReference::epsg_4326().into(),
+                        time: None,
+                        bbox: None,
+                        resolution: Some(SpatialResolution::one()),
+                        bands: RasterBandDescriptors::new_single_band(),
+                    },
+                },
+            }
+            .boxed();
+
+            let initialized_operator = RasterOperator::boxed(Reprojection {
+                params: ReprojectionParams {
+                    target_spatial_reference: SpatialReference::epsg_4326(),
+                    interpolation,
+                },
+                sources: SingleRasterOrVectorSource { source: mrs.into() },
+            })
+            .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+            .await?;
+
+            let qp = initialized_operator
+                .query_processor()
+                .unwrap()
+                .get_u8()
+                .unwrap();
+
+            let query_rect = RasterQueryRectangle {
+                spatial_bounds: SpatialPartition2D::new_unchecked((0., 1.).into(), (3., 0.).into()),
+                time_interval: TimeInterval::new_unchecked(0, 5),
+                spatial_resolution: SpatialResolution::one(),
+                attributes: BandSelection::first(),
+            };
+
+            let res = qp
+                .raster_query(query_rect, &query_ctx)
+                .await?
+                .map(Result::unwrap)
+                .collect::<Vec<RasterTile2D<u8>>>()
+                .await;
+
+            assert!(data.tiles_equal_ignoring_cache_hint(&res));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_deserializes_params_without_interpolation() {
+        let params: ReprojectionParams = serde_json::from_value(serde_json::json!({
+            "targetSpatialReference": "EPSG:4326",
+        }))
+        .unwrap();
+
+        assert_eq!(params.interpolation, InterpolationMethod::NearestNeighbor);
+    }
+
     #[tokio::test]
     async fn raster_ndvi_3857() -> Result<()> {
         let mut exe_ctx = MockExecutionContext::test_default();
@@ -1084,6 +1282,7 @@ mod tests {
         let initialized_operator = RasterOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference: projection,
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: gdal_op.into(),
@@ -1241,6 +1440,7 @@ mod tests {
         let initialized_operator = RasterOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference: SpatialReference::epsg_4326(),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: gdal_op.into(),
@@ -1376,6 +1576,7 @@ mod tests {
         let initialized_operator = RasterOperator::boxed(Reprojection {
             params: ReprojectionParams {
                 target_spatial_reference: SpatialReference::epsg_4326(),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: gdal_op.into(),
@@ -1446,6 +1647,7 @@ mod tests {
                     SpatialReferenceAuthority::Epsg,
                     32636, // utm36n
                 ),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: point_source.into(),
@@ -1524,6 +1726,7 @@ mod tests {
                     SpatialReferenceAuthority::Epsg,
                     4326, // utm36n
                 ),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: point_source.into(),
@@ -1603,6 +1806,7 @@ mod tests {
                     SpatialReferenceAuthority::Epsg,
                     4326, // utm36n
                 ),
+                interpolation: InterpolationMethod::NearestNeighbor,
             },
             sources: SingleRasterOrVectorSource {
                 source: point_source.into(),
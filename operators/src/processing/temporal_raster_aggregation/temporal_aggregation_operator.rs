@@ -2040,6 +2040,7 @@ mod tests {
                         expression: "20 * A".to_string(),
                         output_type: RasterDataType::U8,
                         output_band: None,
+                        output_bands: vec![],
                         map_no_data: true,
                     },
                     sources: SingleRasterSource {
@@ -0,0 +1,472 @@
+mod terrain;
+
+use self::terrain::TerrainKernel;
+use crate::adapters::{
+    stack_individual_aligned_raster_bands, FillerTileCacheExpirationStrategy, RasterSubQueryAdapter,
+};
+use crate::engine::{
+    CanonicOperatorName, ExecutionContext, InitializedRasterOperator, InitializedSources, Operator,
+    OperatorName, QueryContext, QueryProcessor, RasterBandDescriptor, RasterBandDescriptors,
+    RasterOperator, RasterQueryProcessor, RasterResultDescriptor, SingleRasterSource,
+    TypedRasterQueryProcessor, WorkflowOperatorPath,
+};
+use crate::processing::neighborhood_aggregate::TileNeighborhood;
+use crate::processing::RasterTypeConversionQueryProcessor;
+use crate::util::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use geoengine_datatypes::primitives::{
+    BandSelection, Measurement, RasterQueryRectangle, SpatialPartition2D,
+};
+use geoengine_datatypes::raster::{RasterDataType, RasterTile2D, TilingSpecification};
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, Snafu};
+
+/// The terrain analysis operator computes terrain derivatives, like the slope or a hillshade, from a digital elevation model.
+/// Each band of the input raster is treated as a separate elevation model and the output is a `F32` raster.
+pub type TerrainAnalysis = Operator<TerrainAnalysisParams, SingleRasterSource>;
+
+impl OperatorName for TerrainAnalysis {
+    const TYPE_NAME: &'static str = "TerrainAnalysis";
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TerrainAnalysisParams {
+    /// Defines the terrain derivative to compute.
+    pub method: TerrainAnalysisMethod,
+    /// The elevation values are multiplied by this factor before computing the slope, aspect, hillshade and curvature.
+    /// It must convert the unit of the elevation into the unit of the pixel size,
+    /// e.g., for a DEM in a geographic CRS with elevations in meters, it is about `1 / 111_320` near the equator.
+    #[serde(default = "default_z_factor")]
+    pub z_factor: f64,
+}
+
+fn default_z_factor() -> f64 {
+    1.
+}
+
+fn default_azimuth() -> f64 {
+    315.
+}
+
+fn default_altitude() -> f64 {
+    45.
+}
+
+/// The terrain derivatives. Pixels whose 3x3 neighborhood contains NODATA become NODATA.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TerrainAnalysisMethod {
+    /// The steepness of the terrain
+    Slope {
+        #[serde(default)]
+        unit: SlopeUnit,
+    },
+    /// The compass direction of the steepest descent in degrees. Flat pixels are NODATA.
+    Aspect,
+    /// The illumination of the terrain by a light source as values between 0 and 255
+    Hillshade {
+        /// The compass direction of the light source in degrees
+        #[serde(default = "default_azimuth")]
+        azimuth: f64,
+        /// The angle of the light source above the horizon in degrees
+        #[serde(default = "default_altitude")]
+        altitude: f64,
+    },
+    /// The difference between the elevation of a pixel and the mean elevation of its neighbors
+    #[serde(rename = "tpi")]
+    TopographicPositionIndex,
+    /// The square root of the sum of the squared differences between the elevation of a pixel and its neighbors
+    #[serde(rename = "tri")]
+    TerrainRuggednessIndex,
+    /// The curvature of the terrain. Positive values are convex and negative values are concave.
+    Curvature,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SlopeUnit {
+    #[default]
+    Degrees,
+    Percent,
+}
+
+impl TerrainAnalysisMethod {
+    fn measurement(self) -> Measurement {
+        let (measurement, unit) = match self {
+            Self::Slope {
+                unit: SlopeUnit::Degrees,
+            } => ("slope", Some("degrees")),
+            Self::Slope {
+                unit: SlopeUnit::Percent,
+            } => ("slope", Some("percent")),
+            Self::Aspect => ("aspect", Some("degrees")),
+            Self::Hillshade { .. } => ("hillshade", None),
+            Self::TopographicPositionIndex => ("topographic position index", None),
+            Self::TerrainRuggednessIndex => ("terrain ruggedness index", None),
+            Self::Curvature => ("curvature", None),
+        };
+
+        Measurement::continuous(measurement.to_string(), unit.map(ToString::to_string))
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)), module(error))]
+pub enum TerrainAnalysisError {
+    #[snafu(display("The z-factor must be a positive number, but is {}", z_factor))]
+    InvalidZFactor { z_factor: f64 },
+
+    #[snafu(display(
+        "The altitude of the light source must be between 0 and 90 degrees, but is {}",
+        altitude
+    ))]
+    InvalidAltitude { altitude: f64 },
+}
+
+#[typetag::serde]
+#[async_trait]
+impl RasterOperator for TerrainAnalysis {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedRasterOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        let z_factor = self.params.z_factor;
+        ensure!(
+            z_factor.is_finite() && z_factor > 0.,
+            error::InvalidZFactor { z_factor }
+        );
+
+        if let TerrainAnalysisMethod::Hillshade { altitude, .. } = self.params.method {
+            ensure!(
+                (0. ..=90.).contains(&altitude),
+                error::InvalidAltitude { altitude }
+            );
+        }
+
+        let initialized_source = self.sources.initialize_sources(path, context).await?;
+        let raster_source = initialized_source.raster;
+
+        let in_descriptor = raster_source.result_descriptor();
+
+        let bands = in_descriptor
+            .bands
+            .iter()
+            .map(|band| {
+                RasterBandDescriptor::new(band.name.clone(), self.params.method.measurement())
+            })
+            .collect::<Vec<_>>();
+
+        let result_descriptor = RasterResultDescriptor {
+            data_type: RasterDataType::F32,
+            bands: RasterBandDescriptors::new(bands)?,
+            ..in_descriptor.clone()
+        };
+
+        let initialized_operator = InitializedTerrainAnalysis {
+            name,
+            result_descriptor,
+            raster_source,
+            kernel: TerrainKernel::new(self.params.method, z_factor),
+            tiling_specification: context.tiling_specification(),
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(TerrainAnalysis);
+}
+
+pub struct InitializedTerrainAnalysis {
+    name: CanonicOperatorName,
+    result_descriptor: RasterResultDescriptor,
+    raster_source: Box<dyn InitializedRasterOperator>,
+    kernel: TerrainKernel,
+    tiling_specification: TilingSpecification,
+}
+
+impl InitializedRasterOperator for InitializedTerrainAnalysis {
+    fn query_processor(&self) -> Result<TypedRasterQueryProcessor> {
+        let source_processor = self.raster_source.query_processor()?;
+
+        // the derivatives are computed on `f32` values
+        let source_processor: Box<dyn RasterQueryProcessor<RasterType = f32>> =
+            call_on_generic_raster_processor!(source_processor, p => {
+                RasterTypeConversionQueryProcessor::create_boxed(p)
+            });
+
+        Ok(TerrainAnalysisProcessor {
+            source: source_processor,
+            result_descriptor: self.result_descriptor.clone(),
+            tiling_specification: self.tiling_specification,
+            kernel: self.kernel,
+        }
+        .boxed()
+        .into())
+    }
+
+    fn result_descriptor(&self) -> &RasterResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+pub struct TerrainAnalysisProcessor {
+    source: Box<dyn RasterQueryProcessor<RasterType = f32>>,
+    result_descriptor: RasterResultDescriptor,
+    tiling_specification: TilingSpecification,
+    kernel: TerrainKernel,
+}
+
+#[async_trait]
+impl QueryProcessor for TerrainAnalysisProcessor {
+    type Output = RasterTile2D<f32>;
+    type SpatialBounds = SpatialPartition2D;
+    type Selection = BandSelection;
+    type ResultDescription = RasterResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: RasterQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        stack_individual_aligned_raster_bands(&query, ctx, |query, ctx| async move {
+            let sub_query = TileNeighborhood::new(self.kernel, self.tiling_specification);
+
+            Ok(RasterSubQueryAdapter::<'a, f32, _, _>::new(
+                &self.source,
+                query,
+                self.tiling_specification,
+                ctx,
+                sub_query,
+            )
+            .filter_and_fill(FillerTileCacheExpirationStrategy::DerivedFromSurroundingTiles))
+        })
+        .await
+    }
+
+    fn result_descriptor(&self) -> &RasterResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{MockExecutionContext, MockQueryContext};
+    use crate::mock::{MockRasterSource, MockRasterSourceParams};
+    use futures::StreamExt;
+    use geoengine_datatypes::primitives::{CacheHint, SpatialResolution, TimeInterval};
+    use geoengine_datatypes::raster::{Grid2D, GridOrEmpty, TileInformation};
+    use geoengine_datatypes::spatial_reference::SpatialReference;
+    use geoengine_datatypes::util::test::TestDefault;
+
+    #[test]
+    fn it_deserializes_the_params() {
+        let params: TerrainAnalysisParams = serde_json::from_value(serde_json::json!({
+            "method": {
+                "type": "hillshade",
+                "azimuth": 270.0
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            TerrainAnalysisParams {
+                method: TerrainAnalysisMethod::Hillshade {
+                    azimuth: 270.,
+                    altitude: 45.,
+                },
+                z_factor: 1.,
+            }
+        );
+
+        let params: TerrainAnalysisParams = serde_json::from_value(serde_json::json!({
+            "method": {
+                "type": "slope",
+            },
+            "zFactor": 0.5
+        }))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            TerrainAnalysisParams {
+                method: TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Degrees
+                },
+                z_factor: 0.5,
+            }
+        );
+
+        assert_eq!(
+            serde_json::to_value(TerrainAnalysisMethod::TopographicPositionIndex).unwrap(),
+            serde_json::json!({ "type": "tpi" })
+        );
+    }
+
+    /// A 6x3 DEM that rises by 10 per pixel to the east, split into two 3x3 tiles
+    fn make_ramp() -> Box<dyn RasterOperator> {
+        let raster_tiles = vec![
+            RasterTile2D::<u8>::new_with_tile_info(
+                TimeInterval::new_unchecked(0, 10),
+                TileInformation {
+                    global_tile_position: [-1, 0].into(),
+                    tile_size_in_pixels: [3, 3].into(),
+                    global_geo_transform: TestDefault::test_default(),
+                },
+                0,
+                GridOrEmpty::from(
+                    Grid2D::new([3, 3].into(), vec![0, 10, 20, 0, 10, 20, 0, 10, 20]).unwrap(),
+                ),
+                CacheHint::default(),
+            ),
+            RasterTile2D::new_with_tile_info(
+                TimeInterval::new_unchecked(0, 10),
+                TileInformation {
+                    global_tile_position: [-1, 1].into(),
+                    tile_size_in_pixels: [3, 3].into(),
+                    global_geo_transform: TestDefault::test_default(),
+                },
+                0,
+                GridOrEmpty::from(
+                    Grid2D::new([3, 3].into(), vec![30, 40, 50, 30, 40, 50, 30, 40, 50]).unwrap(),
+                ),
+                CacheHint::default(),
+            ),
+        ];
+
+        MockRasterSource {
+            params: MockRasterSourceParams {
+                data: raster_tiles,
+                result_descriptor: RasterResultDescriptor {
+                    data_type: RasterDataType::U8,
+                    spatial_reference: SpatialReference::epsg_4326().into(),
+                    time: None,
+                    bbox: None,
+                    resolution: None,
+                    bands: RasterBandDescriptors::new_single_band(),
+                },
+            },
+        }
+        .boxed()
+    }
+
+    #[tokio::test]
+    async fn it_computes_the_slope_of_a_dem() {
+        let exe_ctx = MockExecutionContext::new_with_tiling_spec(TilingSpecification::new(
+            (0., 0.).into(),
+            [3, 3].into(),
+        ));
+
+        let operator = TerrainAnalysis {
+            params: TerrainAnalysisParams {
+                method: TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Percent,
+                },
+                z_factor: 0.5,
+            },
+            sources: SingleRasterSource {
+                raster: make_ramp(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await
+        .unwrap();
+
+        assert_eq!(operator.result_descriptor().data_type, RasterDataType::F32);
+        assert_eq!(
+            operator.result_descriptor().bands[0].measurement,
+            Measurement::continuous("slope".to_string(), Some("percent".to_string()))
+        );
+
+        let processor = operator.query_processor().unwrap().get_f32().unwrap();
+
+        let query_rect = RasterQueryRectangle {
+            spatial_bounds: SpatialPartition2D::new((0., 3.).into(), (6., 0.).into()).unwrap(),
+            time_interval: TimeInterval::new_unchecked(0, 10),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: BandSelection::first(),
+        };
+        let query_ctx = MockQueryContext::test_default();
+
+        let result_stream = processor.query(query_rect, &query_ctx).await.unwrap();
+
+        let result: Vec<Result<RasterTile2D<f32>>> = result_stream.collect().await;
+        let result = result.into_iter().collect::<Result<Vec<_>>>().unwrap();
+
+        // only the pixels with a complete neighborhood get a value
+        let data = vec![
+            vec![0., 0., 0., 0., 500., 500., 0., 0., 0.],
+            vec![0., 0., 0., 500., 500., 0., 0., 0., 0.],
+        ];
+        let valid = vec![
+            vec![false, false, false, false, true, true, false, false, false],
+            vec![false, false, false, true, true, false, false, false, false],
+        ];
+
+        assert_eq!(result.len(), 2);
+        for (i, tile) in result.into_iter().enumerate() {
+            let tile = tile.into_materialized_tile();
+            assert_eq!(tile.grid_array.inner_grid.data, data[i]);
+            assert_eq!(tile.grid_array.validity_mask.data, valid[i]);
+        }
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_params() {
+        let exe_ctx = MockExecutionContext::test_default();
+
+        let result = TerrainAnalysis {
+            params: TerrainAnalysisParams {
+                method: TerrainAnalysisMethod::Hillshade {
+                    azimuth: 315.,
+                    altitude: 100.,
+                },
+                z_factor: 1.,
+            },
+            sources: SingleRasterSource {
+                raster: make_ramp(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::TerrainAnalysis {
+                source: TerrainAnalysisError::InvalidAltitude { .. }
+            })
+        ));
+
+        let result = TerrainAnalysis {
+            params: TerrainAnalysisParams {
+                method: TerrainAnalysisMethod::Aspect,
+                z_factor: 0.,
+            },
+            sources: SingleRasterSource {
+                raster: make_ramp(),
+            },
+        }
+        .boxed()
+        .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::TerrainAnalysis {
+                source: TerrainAnalysisError::InvalidZFactor { .. }
+            })
+        ));
+    }
+}
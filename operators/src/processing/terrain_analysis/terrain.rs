@@ -0,0 +1,330 @@
+use super::{SlopeUnit, TerrainAnalysisMethod};
+use crate::processing::neighborhood_aggregate::NeighborhoodKernel;
+use geoengine_datatypes::raster::GeoTransform;
+
+/// Computes a terrain derivative from the 3x3 neighborhood of a pixel.
+///
+/// The derivatives are computed with Horn's method and the curvature with the method of Zevenbergen and Thorne.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TerrainKernel {
+    method: TerrainAnalysisMethod,
+    z_factor: f64,
+}
+
+impl TerrainKernel {
+    pub fn new(method: TerrainAnalysisMethod, z_factor: f64) -> Self {
+        Self { method, z_factor }
+    }
+
+    /// Apply the kernel to the `window`, which contains the 3x3 neighborhood in row-major order.
+    /// `x_size` and `y_size` are the (absolute) pixel sizes.
+    ///
+    /// Returns `None` if any pixel of the neighborhood is NODATA.
+    pub fn apply_to_window(
+        &self,
+        window: &[Option<f64>; 9],
+        x_size: f64,
+        y_size: f64,
+    ) -> Option<f32> {
+        let mut elevations = [0.; 9];
+        for (elevation, value) in elevations.iter_mut().zip(window) {
+            *elevation = (*value)?;
+        }
+
+        let value = match self.method {
+            TerrainAnalysisMethod::Slope { unit } => {
+                let (dz_dx, dz_dy) = self.gradient(&elevations, x_size, y_size);
+                let rise = dz_dx.hypot(dz_dy);
+
+                match unit {
+                    SlopeUnit::Degrees => rise.atan().to_degrees(),
+                    SlopeUnit::Percent => rise * 100.,
+                }
+            }
+            TerrainAnalysisMethod::Aspect => {
+                let (dz_dx, dz_dy) = self.gradient(&elevations, x_size, y_size);
+
+                // flat pixels have no aspect
+                if dz_dx == 0. && dz_dy == 0. {
+                    return None;
+                }
+
+                // the direction of the steepest descent as a compass direction
+                let aspect = (-dz_dx).atan2(dz_dy).to_degrees();
+                if aspect < 0. {
+                    aspect + 360.
+                } else {
+                    aspect
+                }
+            }
+            TerrainAnalysisMethod::Hillshade { azimuth, altitude } => {
+                let (dz_dx, dz_dy) = self.gradient(&elevations, x_size, y_size);
+
+                let zenith = (90. - altitude).to_radians();
+                let light_direction = (450. - azimuth).rem_euclid(360.).to_radians();
+
+                let slope = dz_dx.hypot(dz_dy).atan();
+                let aspect = dz_dy.atan2(-dz_dx);
+
+                let illumination = zenith.cos() * slope.cos()
+                    + zenith.sin() * slope.sin() * (light_direction - aspect).cos();
+
+                255. * illumination.max(0.)
+            }
+            TerrainAnalysisMethod::TopographicPositionIndex => {
+                let center = elevations[4];
+                let neighbors_sum: f64 = elevations.iter().sum::<f64>() - center;
+
+                center - neighbors_sum / 8.
+            }
+            TerrainAnalysisMethod::TerrainRuggednessIndex => {
+                let center = elevations[4];
+
+                elevations
+                    .iter()
+                    .map(|elevation| (elevation - center).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            }
+            TerrainAnalysisMethod::Curvature => {
+                let [_, b, _, d, e, f, _, h, _] =
+                    elevations.map(|elevation| elevation * self.z_factor);
+
+                let d2z_dx2 = ((d + f) / 2. - e) / x_size.powi(2);
+                let d2z_dy2 = ((b + h) / 2. - e) / y_size.powi(2);
+
+                -2. * (d2z_dx2 + d2z_dy2) * 100.
+            }
+        };
+
+        if value.is_finite() {
+            Some(value as f32)
+        } else {
+            None
+        }
+    }
+
+    /// The change of the elevation in x (east) and y (south) direction
+    fn gradient(&self, elevations: &[f64; 9], x_size: f64, y_size: f64) -> (f64, f64) {
+        let [a, b, c, d, _, f, g, h, i] = elevations.map(|elevation| elevation * self.z_factor);
+
+        let dz_dx = ((c + 2. * f + i) - (a + 2. * d + g)) / (8. * x_size);
+        let dz_dy = ((g + 2. * h + i) - (a + 2. * b + c)) / (8. * y_size);
+
+        (dz_dx, dz_dy)
+    }
+}
+
+impl NeighborhoodKernel<f32> for TerrainKernel {
+    fn x_radius(&self) -> usize {
+        1
+    }
+
+    fn y_radius(&self) -> usize {
+        1
+    }
+
+    fn apply(&self, values: Vec<Option<f64>>, geo_transform: &GeoTransform) -> Option<f32> {
+        let window: [Option<f64>; 9] = values.try_into().ok()?;
+
+        self.apply_to_window(
+            &window,
+            geo_transform.x_pixel_size().abs(),
+            geo_transform.y_pixel_size().abs(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plane that rises by `10` per pixel to the east
+    const EAST_RAMP: [Option<f64>; 9] = [
+        Some(0.),
+        Some(10.),
+        Some(20.),
+        Some(0.),
+        Some(10.),
+        Some(20.),
+        Some(0.),
+        Some(10.),
+        Some(20.),
+    ];
+
+    const FLAT: [Option<f64>; 9] = [Some(5.); 9];
+
+    fn apply(method: TerrainAnalysisMethod, window: &[Option<f64>; 9]) -> Option<f32> {
+        TerrainKernel::new(method, 1.).apply_to_window(window, 10., 10.)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn it_computes_the_slope() {
+        assert_eq!(
+            apply(
+                TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Degrees
+                },
+                &EAST_RAMP
+            ),
+            Some(45.)
+        );
+        assert_eq!(
+            apply(
+                TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Percent
+                },
+                &EAST_RAMP
+            ),
+            Some(100.)
+        );
+
+        // a z-factor of 2 doubles the rise
+        assert_eq!(
+            TerrainKernel::new(
+                TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Percent
+                },
+                2.
+            )
+            .apply_to_window(&EAST_RAMP, 10., 10.),
+            Some(200.)
+        );
+
+        assert_eq!(
+            apply(
+                TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Degrees
+                },
+                &FLAT
+            ),
+            Some(0.)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn it_computes_the_aspect() {
+        // the ramp descends to the west
+        assert_eq!(apply(TerrainAnalysisMethod::Aspect, &EAST_RAMP), Some(270.));
+
+        let north_ramp = [
+            Some(20.),
+            Some(20.),
+            Some(20.),
+            Some(10.),
+            Some(10.),
+            Some(10.),
+            Some(0.),
+            Some(0.),
+            Some(0.),
+        ];
+        assert_eq!(
+            apply(TerrainAnalysisMethod::Aspect, &north_ramp),
+            Some(180.)
+        );
+
+        assert_eq!(apply(TerrainAnalysisMethod::Aspect, &FLAT), None);
+    }
+
+    #[test]
+    fn it_computes_the_hillshade() {
+        let hillshade = |azimuth: f64, window: &[Option<f64>; 9]| {
+            apply(
+                TerrainAnalysisMethod::Hillshade {
+                    azimuth,
+                    altitude: 45.,
+                },
+                window,
+            )
+            .unwrap()
+        };
+
+        // a flat terrain is illuminated by the altitude of the light source only
+        assert!((hillshade(315., &FLAT) - 180.312_18).abs() < 1e-3);
+
+        // the ramp faces the west, so it is fully lit from there and not lit at all from the east
+        assert!((hillshade(270., &EAST_RAMP) - 255.).abs() < 1e-3);
+        assert!(hillshade(90., &EAST_RAMP).abs() < 1e-3);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn it_computes_tpi_and_tri() {
+        let peak = [
+            Some(1.),
+            Some(1.),
+            Some(1.),
+            Some(1.),
+            Some(9.),
+            Some(1.),
+            Some(1.),
+            Some(1.),
+            Some(1.),
+        ];
+
+        assert_eq!(
+            apply(TerrainAnalysisMethod::TopographicPositionIndex, &peak),
+            Some(8.)
+        );
+        assert_eq!(
+            apply(TerrainAnalysisMethod::TerrainRuggednessIndex, &peak),
+            Some(8f32 * 8f32.sqrt())
+        );
+
+        assert_eq!(
+            apply(TerrainAnalysisMethod::TopographicPositionIndex, &FLAT),
+            Some(0.)
+        );
+        assert_eq!(
+            apply(TerrainAnalysisMethod::TerrainRuggednessIndex, &FLAT),
+            Some(0.)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn it_computes_the_curvature() {
+        let peak = [
+            Some(0.),
+            Some(0.),
+            Some(0.),
+            Some(0.),
+            Some(100.),
+            Some(0.),
+            Some(0.),
+            Some(0.),
+            Some(0.),
+        ];
+
+        // a peak is convex
+        assert_eq!(apply(TerrainAnalysisMethod::Curvature, &peak), Some(400.));
+
+        // a plane has no curvature
+        assert_eq!(
+            apply(TerrainAnalysisMethod::Curvature, &EAST_RAMP),
+            Some(0.)
+        );
+    }
+
+    #[test]
+    fn it_returns_no_data_for_incomplete_neighborhoods() {
+        let mut window = EAST_RAMP;
+        window[0] = None;
+
+        assert_eq!(
+            apply(
+                TerrainAnalysisMethod::Slope {
+                    unit: SlopeUnit::Degrees
+                },
+                &window
+            ),
+            None
+        );
+        assert_eq!(
+            apply(TerrainAnalysisMethod::TerrainRuggednessIndex, &window),
+            None
+        );
+    }
+}
@@ -1188,6 +1188,7 @@ mod tests {
                 expression: "A - B".to_string(),
                 output_type: RasterDataType::F64,
                 output_band: None,
+                output_bands: vec![],
                 map_no_data: false,
             },
             sources: SingleRasterSource {
@@ -18,7 +18,7 @@ use async_trait::async_trait;
 use std::collections::HashMap;
 
 mod equi_data_join;
-mod util;
+pub(super) mod util;
 
 /// The vector join operator requires two inputs and the join type.
 pub type VectorJoin = Operator<VectorJoinParams, VectorJoinSources>;
@@ -0,0 +1,685 @@
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use geoengine_datatypes::collections::{
+    BuilderProvider, FeatureCollection, FeatureCollectionInfos, GeoFeatureCollectionRowBuilder,
+    IntoGeometryIterator, MultiLineStringCollection, MultiPointCollection, MultiPolygonCollection,
+    VectorDataType,
+};
+use geoengine_datatypes::operations::reproject::{
+    CoordinateProjection, CoordinateProjector, ReprojectClipped,
+};
+use geoengine_datatypes::primitives::{
+    AxisAlignedRectangle, BoundingBox2D, ColumnSelection, FeatureDataType, Geometry,
+    MultiLineString, MultiPoint, MultiPolygon, VectorQueryRectangle,
+};
+use geoengine_datatypes::spatial_reference::SpatialReference;
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use super::error;
+use super::geometry::{reproject_geometry, Buffer};
+use super::{collection_columns, VectorOverlayError};
+use crate::engine::{
+    CanonicOperatorName, ExecutionContext, InitializedSources, InitializedVectorOperator, Operator,
+    OperatorName, QueryContext, QueryProcessor, SingleVectorSource, TypedVectorQueryProcessor,
+    VectorOperator, VectorQueryProcessor, VectorResultDescriptor, WorkflowOperatorPath,
+};
+use crate::util::Result;
+
+/// The `VectorBuffer` operator computes the area within a distance around each feature.
+///
+/// The result is a `MultiPolygonCollection` with the attributes of the source features.
+/// Features without a positive distance are omitted.
+pub type VectorBuffer = Operator<VectorBufferParams, SingleVectorSource>;
+
+impl OperatorName for VectorBuffer {
+    const TYPE_NAME: &'static str = "VectorBuffer";
+}
+
+/// The maximum number of segments that approximate a quarter circle
+const MAX_QUADRANT_SEGMENTS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorBufferParams {
+    pub distance: BufferDistance,
+    /// The spatial reference in whose units the distance is measured.
+    /// The features are reprojected into it for buffering and the buffers are projected back.
+    /// If it is `None`, the distance is measured in the units of the source.
+    #[serde(default)]
+    pub distance_spatial_reference: Option<SpatialReference>,
+    /// The number of segments that approximate a quarter circle.
+    /// The default is 8.
+    #[serde(default = "default_quadrant_segments")]
+    pub quadrant_segments: usize,
+}
+
+fn default_quadrant_segments() -> usize {
+    8
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum BufferDistance {
+    /// The same distance for all features
+    Fixed { value: f64 },
+    /// The distance of each feature is read from a numeric column.
+    ///
+    /// Larger distances than `max_distance` are reduced to it,
+    /// since the source is only queried within `max_distance` around the query bounds.
+    #[serde(rename_all = "camelCase")]
+    Column { column: String, max_distance: f64 },
+}
+
+impl BufferDistance {
+    /// The largest distance of any feature
+    fn max_distance(&self) -> f64 {
+        match self {
+            Self::Fixed { value } => *value,
+            Self::Column { max_distance, .. } => *max_distance,
+        }
+    }
+}
+
+#[typetag::serde]
+#[async_trait]
+impl VectorOperator for VectorBuffer {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedVectorOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        let max_distance = self.params.distance.max_distance();
+        ensure!(
+            max_distance.is_finite() && max_distance > 0.,
+            error::InvalidBufferDistance {
+                distance: max_distance
+            }
+        );
+        ensure!(
+            (1..=MAX_QUADRANT_SEGMENTS).contains(&self.params.quadrant_segments),
+            error::InvalidQuadrantSegments {
+                quadrant_segments: self.params.quadrant_segments,
+                max: MAX_QUADRANT_SEGMENTS,
+            }
+        );
+
+        let source = self.sources.initialize_sources(path, context).await?.vector;
+        let in_desc = source.result_descriptor();
+
+        ensure!(
+            in_desc.data_type != VectorDataType::Data,
+            crate::error::InvalidType {
+                expected: "a geo data collection".to_string(),
+                found: in_desc.data_type.to_string(),
+            }
+        );
+
+        if let BufferDistance::Column { column, .. } = &self.params.distance {
+            let column_info = in_desc.columns.get(column).ok_or_else(|| {
+                crate::error::Error::ColumnDoesNotExist {
+                    column: column.clone(),
+                }
+            })?;
+
+            ensure!(
+                matches!(
+                    column_info.data_type,
+                    FeatureDataType::Int | FeatureDataType::Float
+                ),
+                error::NonNumericDistanceColumn {
+                    column: column.clone()
+                }
+            );
+        }
+
+        let projection = match self.params.distance_spatial_reference {
+            Some(distance_srs) if in_desc.spatial_reference != distance_srs.into() => {
+                let source_srs = Option::<SpatialReference>::from(in_desc.spatial_reference)
+                    .ok_or(VectorOverlayError::UnknownSpatialReference)?;
+
+                // fail early if there is no projection between the spatial references
+                CoordinateProjector::from_known_srs(source_srs, distance_srs)?;
+
+                Some((source_srs, distance_srs))
+            }
+            _ => None,
+        };
+
+        let result_descriptor = VectorResultDescriptor {
+            data_type: VectorDataType::MultiPolygon,
+            spatial_reference: in_desc.spatial_reference,
+            columns: in_desc.columns.clone(),
+            time: in_desc.time,
+            // the buffers exceed the bounds of the source
+            bbox: None,
+        };
+
+        let initialized_operator = InitializedVectorBuffer {
+            name,
+            result_descriptor,
+            source,
+            distance: self.params.distance,
+            projection,
+            quadrant_segments: self.params.quadrant_segments,
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(VectorBuffer);
+}
+
+pub struct InitializedVectorBuffer {
+    name: CanonicOperatorName,
+    result_descriptor: VectorResultDescriptor,
+    source: Box<dyn InitializedVectorOperator>,
+    distance: BufferDistance,
+    projection: Option<(SpatialReference, SpatialReference)>,
+    quadrant_segments: usize,
+}
+
+impl InitializedVectorBuffer {
+    fn processor<G: BufferGeometry>(
+        &self,
+        source: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    ) -> TypedVectorQueryProcessor {
+        TypedVectorQueryProcessor::MultiPolygon(
+            VectorBufferProcessor {
+                source,
+                result_descriptor: self.result_descriptor.clone(),
+                distance: self.distance.clone(),
+                projection: self.projection,
+                quadrant_segments: self.quadrant_segments,
+            }
+            .boxed(),
+        )
+    }
+}
+
+impl InitializedVectorOperator for InitializedVectorBuffer {
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn query_processor(&self) -> Result<TypedVectorQueryProcessor> {
+        match self.source.query_processor()? {
+            TypedVectorQueryProcessor::Data(_) => unreachable!("checked in constructor"),
+            TypedVectorQueryProcessor::MultiPoint(source) => Ok(self.processor(source)),
+            TypedVectorQueryProcessor::MultiLineString(source) => Ok(self.processor(source)),
+            TypedVectorQueryProcessor::MultiPolygon(source) => Ok(self.processor(source)),
+        }
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+/// A geometry type that can be buffered
+trait BufferGeometry: Geometry + ArrowTyped + Sized + Send + Sync + 'static {
+    type Geo: Buffer + geo::MapCoords<f64, f64, Output = Self::Geo>;
+
+    /// The geometries of the `collection` as [`geo`] geometries
+    fn geo_geometries(collection: &FeatureCollection<Self>) -> Vec<Self::Geo>;
+}
+
+impl BufferGeometry for MultiPoint {
+    type Geo = geo::MultiPoint<f64>;
+
+    fn geo_geometries(collection: &MultiPointCollection) -> Vec<Self::Geo> {
+        collection
+            .geometries()
+            .map(|geometry| (&geometry).into())
+            .collect()
+    }
+}
+
+impl BufferGeometry for MultiLineString {
+    type Geo = geo::MultiLineString<f64>;
+
+    fn geo_geometries(collection: &MultiLineStringCollection) -> Vec<Self::Geo> {
+        collection
+            .geometries()
+            .map(|geometry| (&geometry).into())
+            .collect()
+    }
+}
+
+impl BufferGeometry for MultiPolygon {
+    type Geo = geo::MultiPolygon<f64>;
+
+    fn geo_geometries(collection: &MultiPolygonCollection) -> Vec<Self::Geo> {
+        collection
+            .geometries()
+            .map(|geometry| (&geometry).into())
+            .collect()
+    }
+}
+
+struct VectorBufferProcessor<G: Geometry> {
+    source: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    result_descriptor: VectorResultDescriptor,
+    distance: BufferDistance,
+    projection: Option<(SpatialReference, SpatialReference)>,
+    quadrant_segments: usize,
+}
+
+#[async_trait]
+impl<G: BufferGeometry> QueryProcessor for VectorBufferProcessor<G> {
+    type Output = MultiPolygonCollection;
+    type SpatialBounds = BoundingBox2D;
+    type Selection = ColumnSelection;
+    type ResultDescription = VectorResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: VectorQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        // features outside of the query bounds may have buffers that reach into them
+        let source_query = VectorQueryRectangle {
+            spatial_bounds: source_bounds(query.spatial_bounds, &self.distance, self.projection)?,
+            ..query
+        };
+
+        let chunks = self.source.query(source_query, ctx).await?;
+
+        let buffered_chunks = chunks.and_then(move |chunk| async move {
+            let distance = self.distance.clone();
+            let projection = self.projection;
+            let quadrant_segments = self.quadrant_segments;
+
+            crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
+                buffer_collection(&chunk, &distance, projection, quadrant_segments)
+            })
+            .await?
+        });
+
+        Ok(buffered_chunks.boxed())
+    }
+
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+/// The `bounds` enlarged by the maximum buffer distance
+fn source_bounds(
+    bounds: BoundingBox2D,
+    distance: &BufferDistance,
+    projection: Option<(SpatialReference, SpatialReference)>,
+) -> Result<BoundingBox2D> {
+    let distance = distance.max_distance();
+    let enlarge = |bounds: BoundingBox2D| {
+        BoundingBox2D::new_unchecked(
+            (
+                bounds.lower_left().x - distance,
+                bounds.lower_left().y - distance,
+            )
+                .into(),
+            (
+                bounds.upper_right().x + distance,
+                bounds.upper_right().y + distance,
+            )
+                .into(),
+        )
+    };
+
+    let Some((source_srs, distance_srs)) = projection else {
+        return Ok(enlarge(bounds));
+    };
+
+    let to_distance_srs = CoordinateProjector::from_known_srs(source_srs, distance_srs)?;
+    let from_distance_srs = CoordinateProjector::from_known_srs(distance_srs, source_srs)?;
+
+    let Some(projected_bounds) = bounds.reproject_clipped(&to_distance_srs)? else {
+        return Ok(bounds);
+    };
+
+    Ok(enlarge(projected_bounds)
+        .reproject_clipped(&from_distance_srs)?
+        .map_or(bounds, |enlarged_bounds| enlarged_bounds.union(&bounds)))
+}
+
+fn buffer_collection<G: BufferGeometry>(
+    collection: &FeatureCollection<G>,
+    distance: &BufferDistance,
+    projection: Option<(SpatialReference, SpatialReference)>,
+    quadrant_segments: usize,
+) -> Result<MultiPolygonCollection> {
+    let distances: Vec<Option<f64>> = match distance {
+        BufferDistance::Fixed { value } => vec![Some(*value); collection.len()],
+        BufferDistance::Column {
+            column,
+            max_distance,
+        } => collection
+            .data(column)?
+            .float_options_iter()
+            .map(|distance| distance.map(|distance| distance.min(*max_distance)))
+            .collect(),
+    };
+
+    let projectors = projection
+        .map(|(source_srs, distance_srs)| -> Result<_> {
+            Ok((
+                CoordinateProjector::from_known_srs(source_srs, distance_srs)?,
+                CoordinateProjector::from_known_srs(distance_srs, source_srs)?,
+            ))
+        })
+        .transpose()?;
+
+    let columns = collection_columns(collection)?;
+
+    let mut builder = MultiPolygonCollection::builder();
+    for (column, data) in &columns {
+        builder.add_column(column.clone(), data.into())?;
+    }
+    let mut builder = builder.finish_header();
+
+    let features = G::geo_geometries(collection)
+        .into_iter()
+        .zip(distances)
+        .zip(collection.time_intervals())
+        .enumerate();
+
+    for (idx, ((geometry, distance), time_interval)) in features {
+        let Some(distance) = distance.filter(|distance| *distance > 0.) else {
+            continue;
+        };
+
+        let buffer = match &projectors {
+            Some((to_distance_srs, from_distance_srs)) => {
+                let geometry = reproject_geometry(&geometry, to_distance_srs)?;
+                let buffer = geometry
+                    .buffer(distance, quadrant_segments)
+                    .ok_or(VectorOverlayError::UnionFailed)?;
+                reproject_geometry(&buffer, from_distance_srs)?
+            }
+            None => geometry
+                .buffer(distance, quadrant_segments)
+                .ok_or(VectorOverlayError::UnionFailed)?,
+        };
+
+        if buffer.0.is_empty() {
+            continue;
+        }
+
+        builder.push_geometry(buffer.into());
+        builder.push_time_interval(*time_interval);
+        for (column, data) in &columns {
+            builder.push_data(column, data.get_unchecked(idx))?;
+        }
+        builder.finish_row();
+    }
+
+    builder.cache_hint(collection.cache_hint);
+
+    builder.build().map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{MockExecutionContext, MockQueryContext};
+    use crate::mock::MockFeatureCollectionSource;
+    use geo::Area;
+    use geoengine_datatypes::primitives::{
+        CacheHint, FeatureData, MultiPolygonAccess, SpatialResolution, TimeInterval,
+    };
+    use geoengine_datatypes::util::test::TestDefault;
+    use std::collections::HashMap;
+
+    fn points() -> MultiPointCollection {
+        MultiPointCollection::from_data(
+            MultiPoint::many(vec![(0., 0.), (10., 0.), (20., 0.)]).unwrap(),
+            vec![TimeInterval::default(); 3],
+            HashMap::from([
+                (
+                    "radius".to_string(),
+                    FeatureData::NullableFloat(vec![Some(1.), None, Some(2.)]),
+                ),
+                (
+                    "name".to_string(),
+                    FeatureData::Text(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+                ),
+            ]),
+            CacheHint::default(),
+        )
+        .unwrap()
+    }
+
+    async fn buffer(params: VectorBufferParams) -> Result<Vec<MultiPolygonCollection>> {
+        let operator = VectorBuffer {
+            params,
+            sources: MockFeatureCollectionSource::single(points()).boxed().into(),
+        }
+        .boxed()
+        .initialize(
+            WorkflowOperatorPath::initialize_root(),
+            &MockExecutionContext::test_default(),
+        )
+        .await?;
+
+        assert_eq!(
+            operator.result_descriptor().data_type,
+            VectorDataType::MultiPolygon
+        );
+
+        let processor = operator.query_processor()?.multi_polygon().unwrap();
+
+        let query = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new_unchecked((-50., -50.).into(), (50., 50.).into()),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: ColumnSelection::all(),
+        };
+        let ctx = MockQueryContext::test_default();
+
+        processor.query(query, &ctx).await?.try_collect().await
+    }
+
+    fn areas(collection: &MultiPolygonCollection) -> Vec<f64> {
+        collection
+            .geometries()
+            .map(|geometry| geo::MultiPolygon::<f64>::from(&geometry).unsigned_area())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_buffers_with_a_fixed_distance() {
+        let collections = buffer(VectorBufferParams {
+            distance: BufferDistance::Fixed { value: 1. },
+            distance_spatial_reference: None,
+            quadrant_segments: 8,
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(collections.len(), 1);
+
+        let collection = &collections[0];
+        assert_eq!(collection.len(), 3);
+        assert_eq!(
+            collection
+                .data("name")
+                .unwrap()
+                .strings_iter()
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+
+        for area in areas(collection) {
+            // a polygon with 32 segments approximates the unit circle
+            assert!(area > 3.1 && area < std::f64::consts::PI);
+        }
+    }
+
+    #[tokio::test]
+    async fn it_buffers_with_distances_from_a_column() {
+        let collections = buffer(VectorBufferParams {
+            distance: BufferDistance::Column {
+                column: "radius".to_string(),
+                max_distance: 10.,
+            },
+            distance_spatial_reference: None,
+            quadrant_segments: 4,
+        })
+        .await
+        .unwrap();
+
+        let collection = &collections[0];
+
+        // the feature without a distance is omitted
+        assert_eq!(collection.len(), 2);
+        assert_eq!(
+            collection
+                .data("name")
+                .unwrap()
+                .strings_iter()
+                .collect::<Vec<_>>(),
+            vec!["a", "c"]
+        );
+
+        let areas = areas(collection);
+        float_cmp::assert_approx_eq!(f64, areas[1] / areas[0], 4., epsilon = 1e-9);
+
+        let geometry = collection.geometries().nth(1).unwrap();
+        assert_eq!(geometry.polygons()[0][0].len(), 17);
+    }
+
+    #[tokio::test]
+    async fn it_validates_the_params() {
+        assert!(buffer(VectorBufferParams {
+            distance: BufferDistance::Fixed { value: -1. },
+            distance_spatial_reference: None,
+            quadrant_segments: 8,
+        })
+        .await
+        .is_err());
+
+        assert!(buffer(VectorBufferParams {
+            distance: BufferDistance::Column {
+                column: "name".to_string(),
+                max_distance: 10.,
+            },
+            distance_spatial_reference: None,
+            quadrant_segments: 8,
+        })
+        .await
+        .is_err());
+
+        assert!(buffer(VectorBufferParams {
+            distance: BufferDistance::Column {
+                column: "radius".to_string(),
+                max_distance: f64::INFINITY,
+            },
+            distance_spatial_reference: None,
+            quadrant_segments: 8,
+        })
+        .await
+        .is_err());
+
+        assert!(buffer(VectorBufferParams {
+            distance: BufferDistance::Fixed { value: 1. },
+            distance_spatial_reference: None,
+            quadrant_segments: 0,
+        })
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn it_buffers_in_another_spatial_reference() {
+        let collections = buffer(VectorBufferParams {
+            distance: BufferDistance::Fixed { value: 100_000. },
+            distance_spatial_reference: Some(SpatialReference::new(
+                geoengine_datatypes::spatial_reference::SpatialReferenceAuthority::Epsg,
+                3857,
+            )),
+            quadrant_segments: 8,
+        })
+        .await
+        .unwrap();
+
+        let collection = &collections[0];
+        assert_eq!(collection.len(), 3);
+
+        // 100 km are slightly less than one degree at the equator
+        for area in areas(collection) {
+            assert!(area > 2.4 && area < std::f64::consts::PI);
+        }
+    }
+
+    #[test]
+    fn it_deserializes_the_params() {
+        let params: VectorBufferParams = serde_json::from_value(serde_json::json!({
+            "distance": { "type": "column", "column": "radius", "maxDistance": 5.0 },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            params,
+            VectorBufferParams {
+                distance: BufferDistance::Column {
+                    column: "radius".to_string(),
+                    max_distance: 5.,
+                },
+                distance_spatial_reference: None,
+                quadrant_segments: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn it_enlarges_the_source_bounds() {
+        let bounds = BoundingBox2D::new_unchecked((0., 0.).into(), (10., 10.).into());
+
+        assert_eq!(
+            source_bounds(bounds, &BufferDistance::Fixed { value: 1. }, None).unwrap(),
+            BoundingBox2D::new_unchecked((-1., -1.).into(), (11., 11.).into())
+        );
+        assert_eq!(
+            source_bounds(
+                bounds,
+                &BufferDistance::Column {
+                    column: "radius".to_string(),
+                    max_distance: 2.,
+                },
+                None
+            )
+            .unwrap(),
+            BoundingBox2D::new_unchecked((-2., -2.).into(), (12., 12.).into())
+        );
+
+        // 100 km are slightly less than one degree at the equator
+        let projected_bounds = source_bounds(
+            bounds,
+            &BufferDistance::Fixed { value: 100_000. },
+            Some((
+                SpatialReference::epsg_4326(),
+                SpatialReference::new(
+                    geoengine_datatypes::spatial_reference::SpatialReferenceAuthority::Epsg,
+                    3857,
+                ),
+            )),
+        )
+        .unwrap();
+        assert!(
+            projected_bounds.contains_bbox(&BoundingBox2D::new_unchecked(
+                (-0.8, -0.8).into(),
+                (10.8, 10.8).into()
+            ))
+        );
+        assert!(
+            !projected_bounds.contains_bbox(&BoundingBox2D::new_unchecked(
+                (-1., -1.).into(),
+                (11., 11.).into()
+            ))
+        );
+    }
+}
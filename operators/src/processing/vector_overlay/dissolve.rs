@@ -0,0 +1,563 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use geoengine_datatypes::collections::{
+    BuilderProvider, FeatureCollection, FeatureCollectionInfos, FeatureCollectionModifications,
+    FeatureCollectionRowBuilder, GeoFeatureCollectionRowBuilder, GeometryRandomAccess,
+    MultiLineStringCollection, MultiPointCollection, MultiPolygonCollection, VectorDataType,
+};
+use geoengine_datatypes::primitives::{
+    BoundingBox2D, ColumnSelection, FeatureDataValue, Geometry, MultiLineString,
+    MultiLineStringAccess, MultiPoint, MultiPointAccess, MultiPolygon, TimeInterval,
+    VectorQueryRectangle,
+};
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use geoengine_expression::buffer::union_all;
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use super::{collection_columns, VectorOverlayError};
+use crate::engine::{
+    CanonicOperatorName, ExecutionContext, InitializedSources, InitializedVectorOperator, Operator,
+    OperatorName, QueryContext, QueryProcessor, SingleVectorSource, TypedVectorQueryProcessor,
+    VectorOperator, VectorQueryProcessor, VectorResultDescriptor, WorkflowOperatorPath,
+};
+use crate::util::Result;
+
+/// The `Dissolve` operator merges the geometries of all features with equal values in the given columns.
+///
+/// Only features with the same time interval are merged.
+/// Polygons are unioned, points and lines are combined into multi-geometries.
+/// The result contains only the given columns.
+/// All features of the source within the query are loaded into memory.
+pub type Dissolve = Operator<DissolveParams, SingleVectorSource>;
+
+impl OperatorName for Dissolve {
+    const TYPE_NAME: &'static str = "Dissolve";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DissolveParams {
+    /// The columns whose values define the groups of features to merge.
+    /// If it is empty, all features with the same time interval are merged.
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+#[typetag::serde]
+#[async_trait]
+impl VectorOperator for Dissolve {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedVectorOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        let source = self.sources.initialize_sources(path, context).await?.vector;
+        let in_desc = source.result_descriptor();
+
+        ensure!(
+            in_desc.data_type != VectorDataType::Data,
+            crate::error::InvalidType {
+                expected: "a geo data collection".to_string(),
+                found: in_desc.data_type.to_string(),
+            }
+        );
+
+        let mut columns = HashMap::with_capacity(self.params.columns.len());
+        for column in &self.params.columns {
+            let column_info = in_desc.columns.get(column).ok_or_else(|| {
+                crate::error::Error::ColumnDoesNotExist {
+                    column: column.clone(),
+                }
+            })?;
+            columns.insert(column.clone(), column_info.clone());
+        }
+
+        let result_descriptor = VectorResultDescriptor {
+            data_type: in_desc.data_type,
+            spatial_reference: in_desc.spatial_reference,
+            columns,
+            time: in_desc.time,
+            bbox: in_desc.bbox,
+        };
+
+        let initialized_operator = InitializedDissolve {
+            name,
+            result_descriptor,
+            source,
+            columns: self.params.columns,
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(Dissolve);
+}
+
+pub struct InitializedDissolve {
+    name: CanonicOperatorName,
+    result_descriptor: VectorResultDescriptor,
+    source: Box<dyn InitializedVectorOperator>,
+    columns: Vec<String>,
+}
+
+impl InitializedDissolve {
+    fn processor<G: DissolveGeometry>(
+        &self,
+        source: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    ) -> Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>
+    where
+        FeatureCollectionRowBuilder<G>: GeoFeatureCollectionRowBuilder<G>,
+    {
+        DissolveProcessor {
+            source,
+            result_descriptor: self.result_descriptor.clone(),
+            columns: self.columns.clone(),
+        }
+        .boxed()
+    }
+}
+
+impl InitializedVectorOperator for InitializedDissolve {
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn query_processor(&self) -> Result<TypedVectorQueryProcessor> {
+        Ok(match self.source.query_processor()? {
+            TypedVectorQueryProcessor::Data(_) => unreachable!("checked in constructor"),
+            TypedVectorQueryProcessor::MultiPoint(source) => {
+                TypedVectorQueryProcessor::MultiPoint(self.processor(source))
+            }
+            TypedVectorQueryProcessor::MultiLineString(source) => {
+                TypedVectorQueryProcessor::MultiLineString(self.processor(source))
+            }
+            TypedVectorQueryProcessor::MultiPolygon(source) => {
+                TypedVectorQueryProcessor::MultiPolygon(self.processor(source))
+            }
+        })
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+/// A geometry type whose features can be merged
+trait DissolveGeometry: Geometry + ArrowTyped + Sized + Send + Sync + 'static {
+    /// Merge the geometries of the features at `indices`
+    fn dissolve(collection: &FeatureCollection<Self>, indices: &[usize]) -> Result<Option<Self>>;
+}
+
+impl DissolveGeometry for MultiPoint {
+    fn dissolve(collection: &MultiPointCollection, indices: &[usize]) -> Result<Option<Self>> {
+        let points = indices
+            .iter()
+            .filter_map(|&idx| collection.geometry_at(idx))
+            .flat_map(|geometry| geometry.points().to_vec())
+            .collect::<Vec<_>>();
+
+        if points.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(MultiPoint::new(points)?))
+    }
+}
+
+impl DissolveGeometry for MultiLineString {
+    fn dissolve(collection: &MultiLineStringCollection, indices: &[usize]) -> Result<Option<Self>> {
+        let lines = indices
+            .iter()
+            .filter_map(|&idx| collection.geometry_at(idx))
+            .flat_map(|geometry| {
+                geometry
+                    .lines()
+                    .iter()
+                    .map(|line| line.to_vec())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        if lines.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(MultiLineString::new(lines)?))
+    }
+}
+
+impl DissolveGeometry for MultiPolygon {
+    fn dissolve(collection: &MultiPolygonCollection, indices: &[usize]) -> Result<Option<Self>> {
+        let polygons = indices
+            .iter()
+            .filter_map(|&idx| collection.geometry_at(idx))
+            .map(|geometry| geo::MultiPolygon::<f64>::from(&geometry))
+            .collect();
+
+        let union = union_all(polygons).ok_or(VectorOverlayError::UnionFailed)?;
+
+        if union.0.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(union.into()))
+    }
+}
+
+struct DissolveProcessor<G: Geometry> {
+    source: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    result_descriptor: VectorResultDescriptor,
+    columns: Vec<String>,
+}
+
+#[async_trait]
+impl<G> QueryProcessor for DissolveProcessor<G>
+where
+    G: DissolveGeometry,
+    FeatureCollectionRowBuilder<G>: GeoFeatureCollectionRowBuilder<G>,
+{
+    type Output = FeatureCollection<G>;
+    type SpatialBounds = BoundingBox2D;
+    type Selection = ColumnSelection;
+    type ResultDescription = VectorResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: VectorQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        let chunks: Vec<FeatureCollection<G>> =
+            self.source.query(query, ctx).await?.try_collect().await?;
+
+        let output_columns = self.result_descriptor.columns.clone();
+        let columns = self.columns.clone();
+
+        let collection =
+            crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
+                let mut builder = FeatureCollection::<G>::builder();
+                for (column, column_info) in output_columns {
+                    builder.add_column(column, column_info.data_type)?;
+                }
+
+                let Some(collection) = concatenate(chunks)? else {
+                    return builder.finish_header().build().map_err(Into::into);
+                };
+
+                dissolve(&collection, &columns, builder.finish_header())
+            })
+            .await??;
+
+        Ok(stream::once(async { Ok(collection) }).boxed())
+    }
+
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+/// Append all `collections` to a single collection
+fn concatenate<G>(collections: Vec<FeatureCollection<G>>) -> Result<Option<FeatureCollection<G>>>
+where
+    G: Geometry + ArrowTyped,
+{
+    let mut collections = collections.into_iter();
+
+    let Some(mut result) = collections.next() else {
+        return Ok(None);
+    };
+
+    for collection in collections {
+        let mut cache_hint = result.cache_hint;
+        cache_hint.merge_with(&collection.cache_hint);
+
+        result = result.append(&collection)?;
+        result.cache_hint = cache_hint;
+    }
+
+    Ok(Some(result))
+}
+
+/// A value of a column that can be used as a key for grouping
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum GroupValue {
+    Null,
+    Category(u8),
+    Int(i64),
+    /// The bits of the float, s.t. equal values are in the same group
+    Float(u64),
+    Text(String),
+    Bool(bool),
+    DateTime(i64),
+}
+
+impl From<FeatureDataValue> for GroupValue {
+    fn from(value: FeatureDataValue) -> Self {
+        match value {
+            FeatureDataValue::Category(value) | FeatureDataValue::NullableCategory(Some(value)) => {
+                GroupValue::Category(value)
+            }
+            FeatureDataValue::Int(value) | FeatureDataValue::NullableInt(Some(value)) => {
+                GroupValue::Int(value)
+            }
+            FeatureDataValue::Float(value) | FeatureDataValue::NullableFloat(Some(value)) => {
+                // treat `0.` and `-0.` as the same value
+                GroupValue::Float((value + 0.).to_bits())
+            }
+            FeatureDataValue::Text(value) | FeatureDataValue::NullableText(Some(value)) => {
+                GroupValue::Text(value)
+            }
+            FeatureDataValue::Bool(value) | FeatureDataValue::NullableBool(Some(value)) => {
+                GroupValue::Bool(value)
+            }
+            FeatureDataValue::DateTime(value) | FeatureDataValue::NullableDateTime(Some(value)) => {
+                GroupValue::DateTime(value.inner())
+            }
+            FeatureDataValue::NullableCategory(None)
+            | FeatureDataValue::NullableInt(None)
+            | FeatureDataValue::NullableFloat(None)
+            | FeatureDataValue::NullableText(None)
+            | FeatureDataValue::NullableBool(None)
+            | FeatureDataValue::NullableDateTime(None) => GroupValue::Null,
+        }
+    }
+}
+
+/// The values of the group columns and the bounds of the time interval
+type GroupKey = (Vec<GroupValue>, i64, i64);
+
+fn dissolve<G>(
+    collection: &FeatureCollection<G>,
+    columns: &[String],
+    mut builder: FeatureCollectionRowBuilder<G>,
+) -> Result<FeatureCollection<G>>
+where
+    G: DissolveGeometry,
+    FeatureCollectionRowBuilder<G>: GeoFeatureCollectionRowBuilder<G>,
+{
+    let column_data = collection_columns(collection)?
+        .into_iter()
+        .filter(|(column, _)| columns.contains(column))
+        .collect::<Vec<_>>();
+
+    // the features of each group in the order of their first occurrence
+    let mut groups: Vec<(TimeInterval, Vec<usize>)> = Vec::new();
+    let mut group_indices: HashMap<GroupKey, usize> = HashMap::new();
+
+    for (idx, time_interval) in collection.time_intervals().iter().enumerate() {
+        let values = column_data
+            .iter()
+            .map(|(_, data)| data.get_unchecked(idx).into())
+            .collect();
+        let key = (
+            values,
+            time_interval.start().inner(),
+            time_interval.end().inner(),
+        );
+
+        let group_idx = *group_indices.entry(key).or_insert_with(|| {
+            groups.push((*time_interval, Vec::new()));
+            groups.len() - 1
+        });
+        groups[group_idx].1.push(idx);
+    }
+
+    for (time_interval, indices) in groups {
+        let Some(geometry) = G::dissolve(collection, &indices)? else {
+            continue;
+        };
+
+        builder.push_geometry(geometry);
+        builder.push_time_interval(time_interval);
+        for (column, data) in &column_data {
+            builder.push_data(column, data.get_unchecked(indices[0]))?;
+        }
+        builder.finish_row();
+    }
+
+    builder.cache_hint(collection.cache_hint);
+
+    builder.build().map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{MockExecutionContext, MockQueryContext};
+    use crate::mock::MockFeatureCollectionSource;
+    use geo::Area;
+    use geoengine_datatypes::collections::{GeometryCollection, IntoGeometryIterator};
+    use geoengine_datatypes::primitives::{
+        CacheHint, FeatureData, MultiPolygonAccess, SpatialResolution,
+    };
+    use geoengine_datatypes::util::test::TestDefault;
+
+    fn square(x: f64, y: f64) -> MultiPolygon {
+        MultiPolygon::new(vec![vec![vec![
+            (x, y).into(),
+            (x + 1., y).into(),
+            (x + 1., y + 1.).into(),
+            (x, y + 1.).into(),
+            (x, y).into(),
+        ]]])
+        .unwrap()
+    }
+
+    async fn run_dissolve<G>(
+        collections: Vec<FeatureCollection<G>>,
+        columns: Vec<String>,
+        processor: fn(
+            TypedVectorQueryProcessor,
+        )
+            -> Option<Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>>,
+    ) -> Result<FeatureCollection<G>>
+    where
+        G: Geometry + ArrowTyped + 'static,
+        MockFeatureCollectionSource<G>: VectorOperator,
+    {
+        let operator = Dissolve {
+            params: DissolveParams { columns },
+            sources: SingleVectorSource {
+                vector: MockFeatureCollectionSource::multiple(collections).boxed(),
+            },
+        }
+        .boxed()
+        .initialize(
+            WorkflowOperatorPath::initialize_root(),
+            &MockExecutionContext::test_default(),
+        )
+        .await?;
+
+        let processor = processor(operator.query_processor()?).unwrap();
+
+        let query = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new_unchecked((-10., -10.).into(), (10., 10.).into()),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: ColumnSelection::all(),
+        };
+        let ctx = MockQueryContext::test_default();
+
+        let mut collections: Vec<FeatureCollection<G>> =
+            processor.query(query, &ctx).await?.try_collect().await?;
+
+        assert_eq!(collections.len(), 1);
+        Ok(collections.remove(0))
+    }
+
+    fn polygons() -> Vec<MultiPolygonCollection> {
+        let collection = |squares: Vec<(f64, f64)>, classes: Vec<i64>| {
+            MultiPolygonCollection::from_data(
+                squares.into_iter().map(|(x, y)| square(x, y)).collect(),
+                vec![TimeInterval::default(); classes.len()],
+                HashMap::from([
+                    ("class".to_string(), FeatureData::Int(classes.clone())),
+                    ("other".to_string(), FeatureData::Int(classes)),
+                ]),
+                CacheHint::default(),
+            )
+            .unwrap()
+        };
+
+        vec![
+            collection(vec![(0., 0.), (1., 0.), (5., 5.)], vec![1, 1, 2]),
+            collection(vec![(0., 1.)], vec![1]),
+        ]
+    }
+
+    #[tokio::test]
+    async fn it_dissolves_polygons_by_column() {
+        let collection = run_dissolve(
+            polygons(),
+            vec!["class".to_string()],
+            TypedVectorQueryProcessor::multi_polygon,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.column_names().collect::<Vec<_>>(), vec!["class"]);
+        assert_eq!(
+            collection
+                .data("class")
+                .unwrap()
+                .float_options_iter()
+                .collect::<Vec<_>>(),
+            vec![Some(1.), Some(2.)]
+        );
+
+        let geometries = collection.geometries().collect::<Vec<_>>();
+
+        // the three adjacent squares form one polygon
+        assert_eq!(geometries[0].polygons().len(), 1);
+        float_cmp::assert_approx_eq!(
+            f64,
+            geo::MultiPolygon::<f64>::from(&geometries[0]).unsigned_area(),
+            3.
+        );
+        float_cmp::assert_approx_eq!(
+            f64,
+            geo::MultiPolygon::<f64>::from(&geometries[1]).unsigned_area(),
+            1.
+        );
+    }
+
+    #[tokio::test]
+    async fn it_dissolves_all_features() {
+        let collection = run_dissolve(polygons(), vec![], TypedVectorQueryProcessor::multi_polygon)
+            .await
+            .unwrap();
+
+        assert_eq!(collection.len(), 1);
+        assert_eq!(collection.column_names().count(), 0);
+
+        let geometry = collection.geometries().next().unwrap();
+        assert_eq!(geometry.polygons().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn it_dissolves_points() {
+        let points = MultiPointCollection::from_data(
+            MultiPoint::many(vec![(0., 0.), (1., 1.), (2., 2.)]).unwrap(),
+            vec![
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20),
+            ],
+            HashMap::new(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let collection = run_dissolve(vec![points], vec![], TypedVectorQueryProcessor::multi_point)
+            .await
+            .unwrap();
+
+        // features with different time intervals are not merged
+        assert_eq!(collection.len(), 2);
+        assert_eq!(collection.feature_offsets(), &[0, 2, 3]);
+        assert_eq!(
+            collection.time_intervals(),
+            &[
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_checks_the_columns() {
+        assert!(run_dissolve(
+            polygons(),
+            vec!["foo".to_string()],
+            TypedVectorQueryProcessor::multi_polygon
+        )
+        .await
+        .is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use geo::MapCoords;
+use geoengine_datatypes::operations::reproject::{CoordinateProjection, CoordinateProjector};
+use geoengine_expression::buffer::{circle, line_buffer_parts, union_all};
+
+use crate::util::Result;
+
+/// Geometries that can be enlarged by a distance
+pub trait Buffer {
+    /// The area within `distance` of the geometry.
+    /// Circles are approximated by `4 * quadrant_segments` segments.
+    ///
+    /// Returns `None` if the buffer cannot be computed, e.g., for degenerated geometries.
+    fn buffer(&self, distance: f64, quadrant_segments: usize) -> Option<geo::MultiPolygon<f64>>;
+}
+
+impl Buffer for geo::MultiPoint<f64> {
+    fn buffer(&self, distance: f64, quadrant_segments: usize) -> Option<geo::MultiPolygon<f64>> {
+        let circles = self
+            .iter()
+            .map(|point| circle(point.0, distance, quadrant_segments).into())
+            .collect();
+
+        union_all(circles)
+    }
+}
+
+impl Buffer for geo::MultiLineString<f64> {
+    fn buffer(&self, distance: f64, quadrant_segments: usize) -> Option<geo::MultiPolygon<f64>> {
+        let parts = self
+            .iter()
+            .flat_map(|line| line_buffer_parts(line, distance, quadrant_segments))
+            .map(Into::into)
+            .collect();
+
+        union_all(parts)
+    }
+}
+
+impl Buffer for geo::MultiPolygon<f64> {
+    fn buffer(&self, distance: f64, quadrant_segments: usize) -> Option<geo::MultiPolygon<f64>> {
+        let parts = self
+            .iter()
+            .flat_map(|polygon| {
+                std::iter::once(polygon.clone()).chain(
+                    std::iter::once(polygon.exterior())
+                        .chain(polygon.interiors())
+                        .flat_map(|ring| line_buffer_parts(ring, distance, quadrant_segments)),
+                )
+            })
+            .map(Into::into)
+            .collect();
+
+        union_all(parts)
+    }
+}
+
+/// Transform all coordinates of a [`geo`] geometry with the `projector`
+pub fn reproject_geometry<G>(geometry: &G, projector: &CoordinateProjector) -> Result<G>
+where
+    G: MapCoords<f64, f64, Output = G>,
+{
+    geometry
+        .try_map_coords(|coord| projector.project_coordinate(coord.into()).map(Into::into))
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::Area;
+
+    #[test]
+    fn it_buffers_points() {
+        let points = geo::MultiPoint::from(vec![(0., 0.), (1., 0.), (10., 0.)]);
+
+        let buffer = points.buffer(1., 8).unwrap();
+
+        // the first two circles overlap
+        assert_eq!(buffer.0.len(), 2);
+
+        let circle_area = circle(geo::coord! { x: 0., y: 0. }, 1., 8).unsigned_area();
+        assert!(buffer.unsigned_area() > circle_area * 2.);
+        assert!(buffer.unsigned_area() < circle_area * 3.);
+    }
+
+    #[test]
+    fn it_buffers_polygons() {
+        let square = geo::MultiPolygon::new(vec![geo::Rect::new(
+            geo::coord! { x: 0., y: 0. },
+            geo::coord! { x: 10., y: 10. },
+        )
+        .to_polygon()]);
+
+        let buffer = square.buffer(1., 8).unwrap();
+
+        assert_eq!(buffer.0.len(), 1);
+        assert!(buffer.0[0].interiors().is_empty());
+
+        // the area of the square, its four sides and an approximated circle for the corners
+        let expected_area =
+            100. + 4. * 10. + circle(geo::coord! { x: 0., y: 0. }, 1., 8).unsigned_area();
+        float_cmp::assert_approx_eq!(f64, buffer.unsigned_area(), expected_area, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn it_unions_all_parts() {
+        let squares = (0..5)
+            .map(|i| {
+                geo::MultiPolygon::new(vec![geo::Rect::new(
+                    geo::coord! { x: f64::from(i), y: 0. },
+                    geo::coord! { x: f64::from(i) + 1., y: 1. },
+                )
+                .to_polygon()])
+            })
+            .collect();
+
+        let union = union_all(squares).unwrap();
+
+        assert_eq!(union.0.len(), 1);
+        float_cmp::assert_approx_eq!(f64, union.unsigned_area(), 5., epsilon = 1e-9);
+
+        assert!(union_all(vec![]).unwrap().0.is_empty());
+    }
+}
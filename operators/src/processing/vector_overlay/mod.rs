@@ -0,0 +1,60 @@
+mod buffer;
+mod dissolve;
+mod geometry;
+mod overlay;
+
+use geoengine_datatypes::collections::FeatureCollectionInfos;
+use geoengine_datatypes::primitives::FeatureDataRef;
+use snafu::Snafu;
+
+use crate::util::Result;
+
+pub use buffer::{BufferDistance, VectorBuffer, VectorBufferParams};
+pub use dissolve::{Dissolve, DissolveParams};
+pub use overlay::{OverlayOperation, VectorOverlay, VectorOverlayParams, VectorOverlaySources};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)), module(error))]
+pub enum VectorOverlayError {
+    #[snafu(display("The buffer distance must be a positive number, but is {}", distance))]
+    InvalidBufferDistance { distance: f64 },
+
+    #[snafu(display(
+        "The number of quadrant segments must be between 1 and {}, but is {}",
+        max,
+        quadrant_segments
+    ))]
+    InvalidQuadrantSegments {
+        quadrant_segments: usize,
+        max: usize,
+    },
+
+    #[snafu(display("The distance column `{}` must be numeric", column))]
+    NonNumericDistanceColumn { column: String },
+
+    #[snafu(display("The spatial reference of the source must be known to reproject it"))]
+    UnknownSpatialReference,
+
+    #[snafu(display("The union of the geometries failed, e.g., due to degenerated geometries"))]
+    UnionFailed,
+
+    #[snafu(display(
+        "The intersection of the geometries failed, e.g., due to degenerated geometries"
+    ))]
+    IntersectionFailed,
+
+    #[snafu(display(
+        "The difference of the geometries failed, e.g., due to degenerated geometries"
+    ))]
+    DifferenceFailed,
+}
+
+/// The names and data of all columns of the `collection`
+fn collection_columns<C: FeatureCollectionInfos>(
+    collection: &C,
+) -> Result<Vec<(String, FeatureDataRef<'_>)>> {
+    collection
+        .column_names()
+        .map(|column| Ok((column.clone(), collection.data(column)?)))
+        .collect()
+}
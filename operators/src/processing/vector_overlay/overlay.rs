@@ -0,0 +1,741 @@
+use std::collections::HashMap;
+use std::panic::UnwindSafe;
+
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use geo::{BooleanOps, BoundingRect, Intersects};
+use geoengine_datatypes::collections::{
+    BuilderProvider, FeatureCollectionInfos, FeatureCollectionRowBuilder,
+    GeoFeatureCollectionRowBuilder, IntoGeometryIterator, MultiPolygonCollection, VectorDataType,
+};
+use geoengine_datatypes::dataset::NamedData;
+use geoengine_datatypes::primitives::{
+    BoundingBox2D, CacheHint, ColumnSelection, FeatureDataRef, MultiPolygon, TimeInterval,
+    VectorQueryRectangle,
+};
+use geoengine_datatypes::spatial_reference::{SpatialReference, SpatialReferenceOption};
+use geoengine_expression::buffer::{catch_silent_panic, union_all};
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use super::VectorOverlayError;
+use crate::engine::{
+    CanonicOperatorName, ExecutionContext, InitializedSources, InitializedVectorOperator, Operator,
+    OperatorData, OperatorName, QueryContext, QueryProcessor, SingleRasterOrVectorSource,
+    TypedVectorQueryProcessor, VectorOperator, VectorQueryProcessor, VectorResultDescriptor,
+    WorkflowOperatorPath,
+};
+use crate::processing::vector_join::util::translation_table;
+use crate::processing::{
+    InitializedVectorReprojection, InterpolationMethod, Reprojection, ReprojectionParams,
+};
+use crate::util::input::RasterOrVectorOperator;
+use crate::util::Result;
+
+/// The `VectorOverlay` operator combines the polygons of two sources geometrically.
+///
+/// Both sources must consist of polygons, since the result of an operation is an area.
+/// Points and lines are rejected when the operator is initialized.
+///
+/// The right source is reprojected into the spatial reference of the left source if they differ.
+/// Features are only combined if their time intervals intersect.
+/// All features of both sources within the query are loaded into memory.
+pub type VectorOverlay = Operator<VectorOverlayParams, VectorOverlaySources>;
+
+impl OperatorName for VectorOverlay {
+    const TYPE_NAME: &'static str = "VectorOverlay";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorOverlayParams {
+    pub operation: OverlayOperation,
+    /// The suffix that is appended to the columns of the right source whose names exist in the left source.
+    /// The default is `_right`.
+    #[serde(default = "default_right_column_suffix")]
+    pub right_column_suffix: String,
+}
+
+fn default_right_column_suffix() -> String {
+    "_right".into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OverlayOperation {
+    /// The areas that are covered by a left and a right feature, with the attributes of both features
+    Intersection,
+    /// The areas of the left features that are not covered by any right feature, with the attributes of the left features
+    Difference,
+    /// The intersections and the areas that are covered by features of only one source.
+    /// The attributes of the other source are empty for the latter.
+    Union,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorOverlaySources {
+    pub left: Box<dyn VectorOperator>,
+    pub right: Box<dyn VectorOperator>,
+}
+
+impl OperatorData for VectorOverlaySources {
+    fn data_names_collect(&self, data_names: &mut Vec<NamedData>) {
+        self.left.data_names_collect(data_names);
+        self.right.data_names_collect(data_names);
+    }
+}
+
+struct InitializedVectorOverlaySources {
+    left: Box<dyn InitializedVectorOperator>,
+    right: Box<dyn InitializedVectorOperator>,
+}
+
+#[async_trait]
+impl InitializedSources<InitializedVectorOverlaySources> for VectorOverlaySources {
+    async fn initialize_sources(
+        self,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<InitializedVectorOverlaySources> {
+        Ok(InitializedVectorOverlaySources {
+            left: self
+                .left
+                .initialize(path.clone_and_append(0), context)
+                .await?,
+            right: self
+                .right
+                .initialize(path.clone_and_append(1), context)
+                .await?,
+        })
+    }
+}
+
+#[typetag::serde]
+#[async_trait]
+impl VectorOperator for VectorOverlay {
+    async fn _initialize(
+        self: Box<Self>,
+        path: WorkflowOperatorPath,
+        context: &dyn ExecutionContext,
+    ) -> Result<Box<dyn InitializedVectorOperator>> {
+        let name = CanonicOperatorName::from(&self);
+
+        let right_operator = self.sources.right.clone();
+        let sources = self.sources.initialize_sources(path, context).await?;
+
+        for source in [&sources.left, &sources.right] {
+            let data_type = source.result_descriptor().data_type;
+            ensure!(
+                data_type == VectorDataType::MultiPolygon,
+                crate::error::InvalidType {
+                    expected: VectorDataType::MultiPolygon.to_string(),
+                    found: data_type.to_string(),
+                }
+            );
+        }
+
+        let left = sources.left;
+        let right = reproject_source(
+            sources.right,
+            right_operator,
+            left.result_descriptor().spatial_reference,
+        )?;
+
+        let left_rd = left.result_descriptor();
+        let right_rd = right.result_descriptor();
+
+        let right_columns = match self.params.operation {
+            OverlayOperation::Difference => HashMap::new(),
+            OverlayOperation::Intersection | OverlayOperation::Union => translation_table(
+                left_rd.columns.keys(),
+                right_rd.columns.keys(),
+                &self.params.right_column_suffix,
+            ),
+        };
+
+        let mut columns = left_rd.columns.clone();
+        for (right_column, output_column) in &right_columns {
+            columns.insert(
+                output_column.clone(),
+                right_rd.columns[right_column].clone(),
+            );
+        }
+
+        let (time, bbox) = match self.params.operation {
+            OverlayOperation::Intersection | OverlayOperation::Difference => {
+                (left_rd.time, left_rd.bbox)
+            }
+            OverlayOperation::Union => (
+                left_rd.time.zip(right_rd.time).map(|(l, r)| l.extend(&r)),
+                left_rd.bbox.zip(right_rd.bbox).map(|(l, r)| l.union(&r)),
+            ),
+        };
+
+        let result_descriptor = VectorResultDescriptor {
+            data_type: VectorDataType::MultiPolygon,
+            spatial_reference: left_rd.spatial_reference,
+            columns,
+            time,
+            bbox,
+        };
+
+        let left_columns = left_rd
+            .columns
+            .keys()
+            .map(|column| (column.clone(), column.clone()))
+            .collect();
+
+        let initialized_operator = InitializedVectorOverlay {
+            name,
+            result_descriptor,
+            left,
+            right,
+            operation: self.params.operation,
+            left_columns,
+            right_columns,
+        };
+
+        Ok(initialized_operator.boxed())
+    }
+
+    span_fn!(VectorOverlay);
+}
+
+/// Reproject the `source` into the `spatial_reference` if they differ
+fn reproject_source(
+    source: Box<dyn InitializedVectorOperator>,
+    operator: Box<dyn VectorOperator>,
+    spatial_reference: SpatialReferenceOption,
+) -> Result<Box<dyn InitializedVectorOperator>> {
+    if source.result_descriptor().spatial_reference == spatial_reference {
+        return Ok(source);
+    }
+
+    let target_spatial_reference = Option::<SpatialReference>::from(spatial_reference)
+        .ok_or(VectorOverlayError::UnknownSpatialReference)?;
+
+    let params = ReprojectionParams {
+        target_spatial_reference,
+        interpolation: InterpolationMethod::default(),
+    };
+
+    // create the reprojection operator in order to get the canonic operator name
+    let reprojection = Reprojection {
+        params,
+        sources: SingleRasterOrVectorSource {
+            source: RasterOrVectorOperator::Vector(operator),
+        },
+    };
+
+    let initialized_reprojection = InitializedVectorReprojection::try_new_with_input(
+        CanonicOperatorName::from(&reprojection),
+        params,
+        source,
+    )?;
+
+    Ok(initialized_reprojection.boxed())
+}
+
+pub struct InitializedVectorOverlay {
+    name: CanonicOperatorName,
+    result_descriptor: VectorResultDescriptor,
+    left: Box<dyn InitializedVectorOperator>,
+    right: Box<dyn InitializedVectorOperator>,
+    operation: OverlayOperation,
+    /// The source columns of the left features and their names in the result
+    left_columns: HashMap<String, String>,
+    /// The source columns of the right features and their names in the result
+    right_columns: HashMap<String, String>,
+}
+
+impl InitializedVectorOperator for InitializedVectorOverlay {
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+
+    fn query_processor(&self) -> Result<TypedVectorQueryProcessor> {
+        let left = self
+            .left
+            .query_processor()?
+            .multi_polygon()
+            .expect("checked in constructor");
+        let right = self
+            .right
+            .query_processor()?
+            .multi_polygon()
+            .expect("checked in constructor");
+
+        Ok(TypedVectorQueryProcessor::MultiPolygon(
+            VectorOverlayProcessor {
+                result_descriptor: self.result_descriptor.clone(),
+                left,
+                right,
+                operation: self.operation,
+                left_columns: self.left_columns.clone(),
+                right_columns: self.right_columns.clone(),
+            }
+            .boxed(),
+        ))
+    }
+
+    fn canonic_name(&self) -> CanonicOperatorName {
+        self.name.clone()
+    }
+}
+
+struct VectorOverlayProcessor {
+    result_descriptor: VectorResultDescriptor,
+    left: Box<dyn VectorQueryProcessor<VectorType = MultiPolygonCollection>>,
+    right: Box<dyn VectorQueryProcessor<VectorType = MultiPolygonCollection>>,
+    operation: OverlayOperation,
+    left_columns: HashMap<String, String>,
+    right_columns: HashMap<String, String>,
+}
+
+#[async_trait]
+impl QueryProcessor for VectorOverlayProcessor {
+    type Output = MultiPolygonCollection;
+    type SpatialBounds = BoundingBox2D;
+    type Selection = ColumnSelection;
+    type ResultDescription = VectorResultDescriptor;
+
+    async fn _query<'a>(
+        &'a self,
+        query: VectorQueryRectangle,
+        ctx: &'a dyn QueryContext,
+    ) -> Result<BoxStream<'a, Result<Self::Output>>> {
+        let left = self.left.query(query.clone(), ctx).await?.try_collect();
+        let right = self.right.query(query, ctx).await?.try_collect();
+        let (left, right): (Vec<_>, Vec<_>) = futures::try_join!(left, right)?;
+
+        let operation = self.operation;
+        let output_columns = self.result_descriptor.columns.clone();
+        let left_columns = self.left_columns.clone();
+        let right_columns = self.right_columns.clone();
+
+        let collection =
+            crate::util::spawn_blocking_with_thread_pool(ctx.thread_pool().clone(), move || {
+                let left = PolygonFeatures::new(&left, &left_columns)?;
+                let right = PolygonFeatures::new(&right, &right_columns)?;
+
+                let mut builder = MultiPolygonCollection::builder();
+                for (column, column_info) in output_columns {
+                    builder.add_column(column, column_info.data_type)?;
+                }
+
+                overlay(&left, &right, operation, builder.finish_header())
+            })
+            .await??;
+
+        Ok(stream::once(async { Ok(collection) }).boxed())
+    }
+
+    fn result_descriptor(&self) -> &VectorResultDescriptor {
+        &self.result_descriptor
+    }
+}
+
+/// A feature of a `MultiPolygonCollection`, prepared for geometric operations
+struct PolygonFeature {
+    /// The index of the collection and the feature within the collection
+    collection: usize,
+    index: usize,
+    geometry: geo::MultiPolygon<f64>,
+    bounds: Option<geo::Rect<f64>>,
+    time_interval: TimeInterval,
+}
+
+/// All features of one source
+struct PolygonFeatures<'c> {
+    features: Vec<PolygonFeature>,
+    /// The output column names and the data of each collection
+    columns: Vec<Vec<(String, FeatureDataRef<'c>)>>,
+    output_columns: Vec<String>,
+    cache_hint: CacheHint,
+}
+
+impl<'c> PolygonFeatures<'c> {
+    fn new(
+        collections: &'c [MultiPolygonCollection],
+        column_names: &HashMap<String, String>,
+    ) -> Result<Self> {
+        let mut features = Vec::new();
+        let mut columns = Vec::with_capacity(collections.len());
+        let mut cache_hint = CacheHint::max_duration();
+
+        for (collection_idx, collection) in collections.iter().enumerate() {
+            let geometries = collection
+                .geometries()
+                .zip(collection.time_intervals())
+                .enumerate();
+
+            for (index, (geometry, time_interval)) in geometries {
+                let geometry = geo::MultiPolygon::<f64>::from(&geometry);
+
+                features.push(PolygonFeature {
+                    collection: collection_idx,
+                    index,
+                    bounds: geometry.bounding_rect(),
+                    geometry,
+                    time_interval: *time_interval,
+                });
+            }
+
+            columns.push(
+                column_names
+                    .iter()
+                    .map(|(column, output_column)| {
+                        Ok((output_column.clone(), collection.data(column)?))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            );
+
+            cache_hint.merge_with(&collection.cache_hint);
+        }
+
+        Ok(Self {
+            features,
+            columns,
+            output_columns: column_names.values().cloned().collect(),
+            cache_hint,
+        })
+    }
+
+    fn push_attributes(
+        &self,
+        builder: &mut FeatureCollectionRowBuilder<MultiPolygon>,
+        feature: &PolygonFeature,
+    ) -> Result<()> {
+        for (column, data) in &self.columns[feature.collection] {
+            builder.push_data(column, data.get_unchecked(feature.index))?;
+        }
+        Ok(())
+    }
+
+    fn push_nulls(&self, builder: &mut FeatureCollectionRowBuilder<MultiPolygon>) -> Result<()> {
+        for column in &self.output_columns {
+            builder.push_null(column)?;
+        }
+        Ok(())
+    }
+}
+
+/// The common time interval of two features if they can overlap
+fn overlap(a: &PolygonFeature, b: &PolygonFeature) -> Option<TimeInterval> {
+    match (a.bounds, b.bounds) {
+        (Some(a_bounds), Some(b_bounds)) if a_bounds.intersects(&b_bounds) => {
+            a.time_interval.intersect(&b.time_interval)
+        }
+        _ => None,
+    }
+}
+
+/// The part of the `feature` that is not covered by any of the `others`
+fn subtract(feature: &PolygonFeature, others: &[PolygonFeature]) -> Result<geo::MultiPolygon<f64>> {
+    let overlapping = others
+        .iter()
+        .filter(|other| overlap(feature, other).is_some())
+        .map(|other| other.geometry.clone())
+        .collect::<Vec<_>>();
+
+    if overlapping.is_empty() {
+        return Ok(feature.geometry.clone());
+    }
+
+    let union = union_all(overlapping).ok_or(VectorOverlayError::UnionFailed)?;
+
+    checked(
+        || feature.geometry.difference(&union),
+        VectorOverlayError::DifferenceFailed,
+    )
+}
+
+/// Applies a boolean operation of [`geo`], which panics for some inputs, e.g., nearly collinear edges.
+/// The panic is turned into the `error` instead of aborting the query.
+fn checked<F>(operation: F, error: VectorOverlayError) -> Result<geo::MultiPolygon<f64>>
+where
+    F: FnOnce() -> geo::MultiPolygon<f64> + UnwindSafe,
+{
+    catch_silent_panic(operation).ok_or_else(|| error.into())
+}
+
+fn overlay(
+    left: &PolygonFeatures,
+    right: &PolygonFeatures,
+    operation: OverlayOperation,
+    mut builder: FeatureCollectionRowBuilder<MultiPolygon>,
+) -> Result<MultiPolygonCollection> {
+    if operation != OverlayOperation::Difference {
+        for left_feature in &left.features {
+            for right_feature in &right.features {
+                let Some(time_interval) = overlap(left_feature, right_feature) else {
+                    continue;
+                };
+
+                let geometry = checked(
+                    || left_feature.geometry.intersection(&right_feature.geometry),
+                    VectorOverlayError::IntersectionFailed,
+                )?;
+                if geometry.0.is_empty() {
+                    continue;
+                }
+
+                builder.push_geometry(geometry.into());
+                builder.push_time_interval(time_interval);
+                left.push_attributes(&mut builder, left_feature)?;
+                right.push_attributes(&mut builder, right_feature)?;
+                builder.finish_row();
+            }
+        }
+    }
+
+    if operation != OverlayOperation::Intersection {
+        for left_feature in &left.features {
+            let geometry = subtract(left_feature, &right.features)?;
+            if geometry.0.is_empty() {
+                continue;
+            }
+
+            builder.push_geometry(geometry.into());
+            builder.push_time_interval(left_feature.time_interval);
+            left.push_attributes(&mut builder, left_feature)?;
+            right.push_nulls(&mut builder)?;
+            builder.finish_row();
+        }
+    }
+
+    if operation == OverlayOperation::Union {
+        for right_feature in &right.features {
+            let geometry = subtract(right_feature, &left.features)?;
+            if geometry.0.is_empty() {
+                continue;
+            }
+
+            builder.push_geometry(geometry.into());
+            builder.push_time_interval(right_feature.time_interval);
+            left.push_nulls(&mut builder)?;
+            right.push_attributes(&mut builder, right_feature)?;
+            builder.finish_row();
+        }
+    }
+
+    builder.cache_hint(left.cache_hint.merged(&right.cache_hint));
+
+    builder.build().map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{MockExecutionContext, MockQueryContext};
+    use crate::mock::MockFeatureCollectionSource;
+    use geo::Area;
+    use geoengine_datatypes::operations::reproject::{
+        CoordinateProjection, CoordinateProjector, Reproject,
+    };
+    use geoengine_datatypes::primitives::{FeatureData, FeatureDataValue, SpatialResolution};
+    use geoengine_datatypes::spatial_reference::SpatialReferenceAuthority;
+    use geoengine_datatypes::util::test::TestDefault;
+
+    fn square(x: f64, y: f64, size: f64) -> MultiPolygon {
+        MultiPolygon::new(vec![vec![vec![
+            (x, y).into(),
+            (x + size, y).into(),
+            (x + size, y + size).into(),
+            (x, y + size).into(),
+            (x, y).into(),
+        ]]])
+        .unwrap()
+    }
+
+    fn collection(geometry: MultiPolygon, name: &str) -> MultiPolygonCollection {
+        MultiPolygonCollection::from_data(
+            vec![geometry],
+            vec![TimeInterval::default()],
+            HashMap::from([(
+                "name".to_string(),
+                FeatureData::Text(vec![name.to_string()]),
+            )]),
+            CacheHint::default(),
+        )
+        .unwrap()
+    }
+
+    async fn run_overlay(
+        operation: OverlayOperation,
+        right: Box<dyn VectorOperator>,
+    ) -> MultiPolygonCollection {
+        let operator = VectorOverlay {
+            params: VectorOverlayParams {
+                operation,
+                right_column_suffix: default_right_column_suffix(),
+            },
+            sources: VectorOverlaySources {
+                left: MockFeatureCollectionSource::single(collection(square(0., 0., 2.), "a"))
+                    .boxed(),
+                right,
+            },
+        }
+        .boxed()
+        .initialize(
+            WorkflowOperatorPath::initialize_root(),
+            &MockExecutionContext::test_default(),
+        )
+        .await
+        .unwrap();
+
+        let processor = operator.query_processor().unwrap().multi_polygon().unwrap();
+
+        let query = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new_unchecked((-10., -10.).into(), (10., 10.).into()),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: ColumnSelection::all(),
+        };
+        let ctx = MockQueryContext::test_default();
+
+        let mut collections: Vec<MultiPolygonCollection> = processor
+            .query(query, &ctx)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(collections.len(), 1);
+        collections.remove(0)
+    }
+
+    fn right_square() -> Box<dyn VectorOperator> {
+        MockFeatureCollectionSource::single(collection(square(1., 1., 2.), "b")).boxed()
+    }
+
+    fn areas(collection: &MultiPolygonCollection) -> Vec<f64> {
+        collection
+            .geometries()
+            .map(|geometry| geo::MultiPolygon::<f64>::from(&geometry).unsigned_area())
+            .collect()
+    }
+
+    fn names(collection: &MultiPolygonCollection, column: &str) -> Vec<Option<String>> {
+        let data = collection.data(column).unwrap();
+        (0..collection.len())
+            .map(|idx| data.get_unchecked(idx))
+            .map(|value| match value {
+                FeatureDataValue::NullableText(text) => text,
+                FeatureDataValue::Text(text) => Some(text),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_intersects() {
+        let collection = run_overlay(OverlayOperation::Intersection, right_square()).await;
+
+        assert_eq!(collection.len(), 1);
+        float_cmp::assert_approx_eq!(f64, areas(&collection)[0], 1.);
+        assert_eq!(names(&collection, "name"), vec![Some("a".to_string())]);
+        assert_eq!(
+            names(&collection, "name_right"),
+            vec![Some("b".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_computes_the_difference() {
+        let collection = run_overlay(OverlayOperation::Difference, right_square()).await;
+
+        assert_eq!(collection.len(), 1);
+        float_cmp::assert_approx_eq!(f64, areas(&collection)[0], 3.);
+        assert!(collection.data("name_right").is_err());
+    }
+
+    #[tokio::test]
+    async fn it_computes_the_union() {
+        let collection = run_overlay(OverlayOperation::Union, right_square()).await;
+
+        assert_eq!(collection.len(), 3);
+
+        let areas = areas(&collection);
+        float_cmp::assert_approx_eq!(f64, areas[0], 1.);
+        float_cmp::assert_approx_eq!(f64, areas[1], 3.);
+        float_cmp::assert_approx_eq!(f64, areas[2], 3.);
+
+        assert_eq!(
+            names(&collection, "name"),
+            vec![Some("a".to_string()), Some("a".to_string()), None]
+        );
+        assert_eq!(
+            names(&collection, "name_right"),
+            vec![Some("b".to_string()), None, Some("b".to_string())]
+        );
+    }
+
+    #[test]
+    fn it_fails_instead_of_panicking_for_nearly_collinear_edges() {
+        let triangle = |coordinates: [(f64, f64); 3]| {
+            MultiPolygon::new(vec![vec![vec![
+                coordinates[0].into(),
+                coordinates[1].into(),
+                coordinates[2].into(),
+                coordinates[0].into(),
+            ]]])
+            .unwrap()
+        };
+
+        // the boolean operations of `geo` panic for these triangles
+        let left = [collection(triangle([(1e-15, 0.), (0., 1.), (1., 3.)]), "a")];
+        let right = [collection(triangle([(3., 1.), (3., 0.), (0., 0.)]), "b")];
+
+        let left = PolygonFeatures::new(&left, &HashMap::new()).unwrap();
+        let right = PolygonFeatures::new(&right, &HashMap::new()).unwrap();
+
+        assert!(matches!(
+            overlay(
+                &left,
+                &right,
+                OverlayOperation::Intersection,
+                MultiPolygonCollection::builder().finish_header(),
+            ),
+            Err(crate::error::Error::VectorOverlay {
+                source: VectorOverlayError::IntersectionFailed
+            })
+        ));
+        assert!(matches!(
+            overlay(
+                &left,
+                &right,
+                OverlayOperation::Difference,
+                MultiPolygonCollection::builder().finish_header(),
+            ),
+            Err(crate::error::Error::VectorOverlay {
+                source: VectorOverlayError::DifferenceFailed
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_reprojects_the_right_source() {
+        let web_mercator = SpatialReference::new(SpatialReferenceAuthority::Epsg, 3857);
+        let projector =
+            CoordinateProjector::from_known_srs(SpatialReference::epsg_4326(), web_mercator)
+                .unwrap();
+
+        let right = MockFeatureCollectionSource::with_collections_and_sref(
+            vec![collection(
+                square(1., 1., 2.).reproject(&projector).unwrap(),
+                "b",
+            )],
+            web_mercator,
+        )
+        .boxed();
+
+        let collection = run_overlay(OverlayOperation::Intersection, right).await;
+
+        assert_eq!(collection.len(), 1);
+        float_cmp::assert_approx_eq!(f64, areas(&collection)[0], 1., epsilon = 1e-6);
+    }
+}
@@ -4,6 +4,8 @@ pub mod input;
 pub mod math;
 pub mod number_statistics;
 pub mod raster_stream_to_geotiff;
+pub mod raster_stream_to_netcdf;
+pub mod raster_mosaic;
 pub mod raster_stream_to_png;
 mod rayon;
 pub mod retry;
@@ -12,6 +14,7 @@ pub mod stream_zip;
 pub mod string_token;
 pub mod sunpos;
 mod temporary_gdal_thread_local_config_options;
+pub mod vector_stream_to_ogr;
 
 use crate::error::Error;
 use std::collections::HashSet;
@@ -0,0 +1,165 @@
+use crate::adapters::RasterStreamExt;
+use crate::error::RasterMosaicTooLarge;
+use crate::util::Result;
+use futures::{Stream, TryStreamExt};
+use geoengine_datatypes::primitives::{CacheHint, SpatialPartition2D, TimeInterval};
+use geoengine_datatypes::raster::{
+    GeoTransform, GridBounds, GridIdx, GridIdx2D, GridIndexAccess, GridSize, Pixel, RasterTile2D,
+};
+use snafu::ensure;
+
+/// The maximum number of pixels of a `RasterMosaic`, which limits its memory consumption,
+/// e.g., to 256 MiB for `f64` pixels.
+pub const MAX_MOSAIC_PIXELS: usize = 4096 * 4096;
+
+/// The pixels of all tiles of one time step that lie inside a spatial partition.
+///
+/// This allows algorithms that need a contiguous grid, e.g., to connect features across tile borders.
+pub struct RasterMosaic<P> {
+    pub time: TimeInterval,
+    pub cache_hint: CacheHint,
+    /// The geo transform of the mosaic, i.e., its origin is the upper left corner of the first pixel
+    pub geo_transform: GeoTransform,
+    pub width: usize,
+    pub height: usize,
+    /// The pixels in row-major order, `None` for no data
+    pub pixels: Vec<Option<P>>,
+    /// The global pixel index of the upper left pixel of the mosaic
+    upper_left: GridIdx2D,
+}
+
+impl<P: Pixel> RasterMosaic<P> {
+    /// Creates an empty mosaic with the resolution of the `tile` that covers the `spatial_bounds`.
+    ///
+    /// Fails if the mosaic would have more than [`MAX_MOSAIC_PIXELS`] pixels.
+    pub fn new(tile: &RasterTile2D<P>, spatial_bounds: &SpatialPartition2D) -> Result<Self> {
+        let global_geo_transform = tile.global_geo_transform;
+        let grid_bounds = global_geo_transform.spatial_to_grid_bounds(spatial_bounds);
+        let upper_left = grid_bounds.min_index();
+        let [height, width] = grid_bounds.axis_size();
+
+        ensure!(
+            width.saturating_mul(height) <= MAX_MOSAIC_PIXELS,
+            RasterMosaicTooLarge {
+                width,
+                height,
+                max_pixels: MAX_MOSAIC_PIXELS,
+            }
+        );
+
+        Ok(Self {
+            time: tile.time,
+            cache_hint: CacheHint::max_duration(),
+            geo_transform: GeoTransform::new(
+                global_geo_transform.grid_idx_to_pixel_upper_left_coordinate_2d(upper_left),
+                global_geo_transform.x_pixel_size(),
+                global_geo_transform.y_pixel_size(),
+            ),
+            width,
+            height,
+            pixels: vec![None; width * height],
+            upper_left,
+        })
+    }
+
+    /// Copy the pixels of the `tile` that lie inside the mosaic
+    pub fn insert_tile(&mut self, tile: &RasterTile2D<P>) {
+        self.cache_hint.merge_with(&tile.cache_hint);
+
+        if tile.is_empty() {
+            return;
+        }
+
+        let tile_information = tile.tile_information();
+        let GridIdx([offset_y, offset_x]) =
+            tile_information.global_upper_left_pixel_idx() - self.upper_left;
+        let [tile_height, tile_width] = tile_information.tile_size_in_pixels.shape_array;
+
+        for tile_y in 0..tile_height as isize {
+            let y = offset_y + tile_y;
+            if y < 0 || y >= self.height as isize {
+                continue;
+            }
+
+            for tile_x in 0..tile_width as isize {
+                let x = offset_x + tile_x;
+                if x < 0 || x >= self.width as isize {
+                    continue;
+                }
+
+                self.pixels[y as usize * self.width + x as usize] =
+                    tile.get_at_grid_index_unchecked(GridIdx2D::from([tile_y, tile_x]));
+            }
+        }
+    }
+}
+
+/// Combine the tiles of each time step of a raster stream into one `RasterMosaic` that covers the `spatial_bounds`.
+///
+/// This method assumes all raster tiles arrive geo first, time second.
+pub fn mosaic_raster_stream<P, S>(
+    tiles: S,
+    spatial_bounds: SpatialPartition2D,
+) -> impl Stream<Item = Result<RasterMosaic<P>>>
+where
+    P: Pixel,
+    S: Stream<Item = Result<RasterTile2D<P>>>,
+{
+    tiles
+        .time_multi_fold(
+            || Ok(None),
+            move |mosaic: Result<Option<RasterMosaic<P>>>, tile| async move {
+                let tile = tile?;
+                let mut mosaic = match mosaic? {
+                    Some(mosaic) => mosaic,
+                    None => RasterMosaic::new(&tile, &spatial_bounds)?,
+                };
+                mosaic.insert_tile(&tile);
+                Ok(Some(mosaic))
+            },
+        )
+        .try_filter_map(|mosaic| async move { Ok(mosaic) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use geoengine_datatypes::raster::{EmptyGrid2D, TileInformation};
+    use geoengine_datatypes::util::test::TestDefault;
+
+    #[test]
+    fn it_limits_the_mosaic_size() {
+        let tile = RasterTile2D::<u8>::new_with_tile_info(
+            TimeInterval::default(),
+            TileInformation {
+                global_geo_transform: TestDefault::test_default(),
+                global_tile_position: [0, 0].into(),
+                tile_size_in_pixels: [2, 2].into(),
+            },
+            0,
+            EmptyGrid2D::new([2, 2].into()).into(),
+            CacheHint::default(),
+        );
+
+        let mosaic = RasterMosaic::new(
+            &tile,
+            &SpatialPartition2D::new_unchecked((0., 0.).into(), (4., -2.).into()),
+        )
+        .unwrap();
+        assert_eq!((mosaic.width, mosaic.height), (4, 2));
+        assert_eq!(mosaic.pixels.len(), 8);
+
+        assert!(matches!(
+            RasterMosaic::new(
+                &tile,
+                &SpatialPartition2D::new_unchecked((0., 0.).into(), (5000., -5000.).into()),
+            ),
+            Err(Error::RasterMosaicTooLarge {
+                width: 5000,
+                height: 5000,
+                max_pixels: MAX_MOSAIC_PIXELS,
+            })
+        ));
+    }
+}
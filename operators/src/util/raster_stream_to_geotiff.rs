@@ -66,7 +66,10 @@ where
         gdal_tiff_metadata,
     )?;
 
-    let cache_hint = spawn_blocking(move || {
+    let as_cog = gdal_tiff_options.as_cog;
+    let compression_num_threads = gdal_tiff_options.compression_num_threads;
+
+    let (output_path, cache_hint) = spawn_blocking(move || {
         let mut band_idx = 1;
         let mut time = initial_tile_time;
 
@@ -97,12 +100,27 @@ where
             writer.write_tile_into_band(tile, dataset.rasterband(band_idx)?)?;
         }
 
-        Result::<CacheHint, Error>::Ok(cache_hint)
+        if !as_cog {
+            drop(dataset);
+            return Result::<(PathBuf, CacheHint), Error>::Ok((file_path, cache_hint));
+        }
+
+        // the COG driver generates the internal overviews and orders the data as required by the COG layout
+        let cog_path = PathBuf::from(format!("/vsimem/{}.tiff", uuid::Uuid::new_v4()));
+        geotiff_to_cog(
+            dataset,
+            &file_path,
+            &cog_path,
+            compression_num_threads,
+            writer.use_big_tiff,
+        )?;
+
+        Ok((cog_path, cache_hint))
     })
     .await??;
 
     Ok((
-        gdal::vsi::get_vsi_mem_file_bytes_owned(file_path)?,
+        gdal::vsi::get_vsi_mem_file_bytes_owned(output_path)?,
         cache_hint,
     ))
 }
@@ -133,22 +151,7 @@ where
         .number_of_elements();
     let num_timesteps = tiles.len() / num_tiles_per_timestep;
 
-    let x_pixel_size = query_rect.spatial_resolution.x;
-    let y_pixel_size = query_rect.spatial_resolution.y;
-    let width = (query_rect.spatial_bounds.size_x() / x_pixel_size).ceil() as usize;
-    let height = (query_rect.spatial_bounds.size_y() / y_pixel_size).ceil() as usize;
-    let output_geo_transform = GeoTransform::new(
-        query_rect.spatial_bounds.upper_left(),
-        x_pixel_size,
-        -y_pixel_size,
-    );
-
-    let global_geo_transform = tiling_specification
-        .strategy(x_pixel_size, -y_pixel_size)
-        .geo_transform;
-    let window_start =
-        global_geo_transform.coordinate_to_grid_idx_2d(query_rect.spatial_bounds.upper_left());
-    let window_end = window_start + GridIdx2D::from([height as isize, width as isize]);
+    let (width, height) = output_size(query_rect);
 
     let uncompressed_byte_size = width * height * std::mem::size_of::<T>();
 
@@ -188,8 +191,16 @@ where
         num_timesteps as isize,
         &options,
     )?;
+    let writer = GdalDatasetWriter::<T>::new(
+        query_rect,
+        tiling_specification,
+        gdal_tiff_metadata,
+        gdal_tiff_options,
+        use_big_tiff,
+    );
+
     dataset.set_spatial_ref(&gdal_tiff_metadata.spatial_reference.try_into()?)?;
-    dataset.set_geo_transform(&output_geo_transform.into())?;
+    dataset.set_geo_transform(&writer.output_geo_transform.into())?;
 
     for band_idx in 0..dataset.raster_count() {
         let mut band = dataset.rasterband(band_idx + 1)?;
@@ -200,21 +211,18 @@ where
         }
     }
 
-    let writer = GdalDatasetWriter::<T> {
-        gdal_tiff_options,
-        gdal_tiff_metadata,
-        _output_bounds: query_rect.spatial_bounds,
-        output_geo_transform,
-        use_big_tiff,
-        _type: Default::default(),
-        window_start,
-        window_end,
-    };
-
     Ok((initial_tile_time, file_path, dataset, writer))
 }
 
-async fn consume_stream_into_vec<T, C: QueryContext + 'static>(
+/// The size of the output raster of a query in pixels as (width, height)
+pub(crate) fn output_size(query_rect: &RasterQueryRectangle) -> (usize, usize) {
+    let width = (query_rect.spatial_bounds.size_x() / query_rect.spatial_resolution.x).ceil();
+    let height = (query_rect.spatial_bounds.size_y() / query_rect.spatial_resolution.y).ceil();
+
+    (width as usize, height as usize)
+}
+
+pub(crate) async fn consume_stream_into_vec<T, C: QueryContext + 'static>(
     processor: Box<dyn RasterQueryProcessor<RasterType = T>>,
     query_rect: geoengine_datatypes::primitives::QueryRectangle<SpatialPartition2D, BandSelection>,
     query_ctx: C,
@@ -731,7 +739,7 @@ pub struct GdalGeoTiffDatasetMetadata {
 }
 
 #[derive(Debug)]
-struct GdalDatasetWriter<P: Pixel + GdalType> {
+pub(crate) struct GdalDatasetWriter<P: Pixel + GdalType> {
     gdal_tiff_metadata: GdalGeoTiffDatasetMetadata,
     gdal_tiff_options: GdalGeoTiffOptions,
     _output_bounds: SpatialPartition2D, // currently unused due to workaround for intersection and contained because of float precision
@@ -743,7 +751,52 @@ struct GdalDatasetWriter<P: Pixel + GdalType> {
 }
 
 impl<P: Pixel + GdalType> GdalDatasetWriter<P> {
-    fn write_tile_into_band(&self, tile: RasterTile2D<P>, raster_band: RasterBand) -> Result<()> {
+    /// Creates a writer for the output raster of the `query_rect`
+    pub(crate) fn new(
+        query_rect: &RasterQueryRectangle,
+        tiling_specification: TilingSpecification,
+        gdal_tiff_metadata: GdalGeoTiffDatasetMetadata,
+        gdal_tiff_options: GdalGeoTiffOptions,
+        use_big_tiff: bool,
+    ) -> Self {
+        let x_pixel_size = query_rect.spatial_resolution.x;
+        let y_pixel_size = query_rect.spatial_resolution.y;
+        let (width, height) = output_size(query_rect);
+
+        let output_geo_transform = GeoTransform::new(
+            query_rect.spatial_bounds.upper_left(),
+            x_pixel_size,
+            -y_pixel_size,
+        );
+
+        let global_geo_transform = tiling_specification
+            .strategy(x_pixel_size, -y_pixel_size)
+            .geo_transform;
+        let window_start =
+            global_geo_transform.coordinate_to_grid_idx_2d(query_rect.spatial_bounds.upper_left());
+        let window_end = window_start + GridIdx2D::from([height as isize, width as isize]);
+
+        Self {
+            gdal_tiff_options,
+            gdal_tiff_metadata,
+            _output_bounds: query_rect.spatial_bounds,
+            output_geo_transform,
+            use_big_tiff,
+            _type: Default::default(),
+            window_start,
+            window_end,
+        }
+    }
+
+    pub(crate) fn output_geo_transform(&self) -> GeoTransform {
+        self.output_geo_transform
+    }
+
+    pub(crate) fn write_tile_into_band(
+        &self,
+        tile: RasterTile2D<P>,
+        raster_band: RasterBand,
+    ) -> Result<()> {
         let tile_info = tile.tile_information();
 
         let tile_start = tile_info.global_upper_left_pixel_idx();
@@ -1026,6 +1079,10 @@ fn geotiff_to_cog(
             key: "BLOCKSIZE",
             value: COG_BLOCK_SIZE,
         },
+        RasterCreationOption {
+            key: "OVERVIEWS",
+            value: "IGNORE_EXISTING",
+        },
     ];
 
     if as_big_tiff {
@@ -1831,4 +1888,69 @@ mod tests {
 
         drop(ds);
     }
+
+    #[tokio::test]
+    async fn multi_band_cog() {
+        let ctx = MockQueryContext::test_default();
+        let tiling_specification =
+            TilingSpecification::new(Coordinate2D::default(), [512, 512].into());
+
+        let metadata = create_ndvi_meta_data();
+
+        let gdal_source = GdalSourceProcessor::<u8> {
+            result_descriptor: RasterResultDescriptor::with_datatype_and_num_bands(
+                RasterDataType::U8,
+                1,
+            ),
+            tiling_specification,
+            meta_data: Box::new(metadata),
+            _phantom_data: PhantomData,
+        };
+
+        let query_bbox = SpatialPartition2D::new((-180., 90.).into(), (180., -90.).into()).unwrap();
+
+        let (mut bytes, _) = raster_stream_to_multiband_geotiff_bytes(
+            gdal_source.boxed(),
+            RasterQueryRectangle {
+                spatial_bounds: query_bbox,
+                // 1.1.2014 - 1.3.2014
+                time_interval: TimeInterval::new(1_388_534_400_000, 1_393_628_400_000).unwrap(),
+                spatial_resolution: SpatialResolution::new_unchecked(0.1, 0.1),
+                attributes: BandSelection::first(),
+            },
+            ctx,
+            GdalGeoTiffDatasetMetadata {
+                no_data_value: Some(0.),
+                spatial_reference: SpatialReference::epsg_4326(),
+            },
+            GdalGeoTiffOptions {
+                as_cog: true,
+                compression_num_threads: GdalCompressionNumThreads::AllCpus,
+                force_big_tiff: false,
+            },
+            None,
+            Box::pin(futures::future::pending()),
+            tiling_specification,
+        )
+        .await
+        .unwrap();
+
+        let file_path = PathBuf::from(format!("/vsimem/{}/", uuid::Uuid::new_v4()));
+        let _mem_file =
+            gdal::vsi::create_mem_file_from_ref(&file_path, bytes.as_mut_slice()).unwrap();
+        let ds = gdal_open_dataset(&file_path).unwrap();
+
+        // two bands for Jan, Feb
+        assert_eq!(ds.raster_count(), 2);
+        assert_eq!(
+            ds.metadata_item("LAYOUT", "IMAGE_STRUCTURE"),
+            Some("COG".to_string())
+        );
+
+        let band = ds.rasterband(1).unwrap();
+        assert_eq!(band.block_size(), (512, 512));
+        assert!(band.overview_count().unwrap() > 0);
+
+        drop(ds);
+    }
 }
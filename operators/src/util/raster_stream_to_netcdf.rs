@@ -0,0 +1,334 @@
+use crate::engine::{QueryContext, RasterQueryProcessor};
+use crate::error::Error;
+use crate::util::raster_stream_to_geotiff::{
+    consume_stream_into_vec, output_size, GdalCompressionNumThreads, GdalDatasetWriter,
+    GdalGeoTiffDatasetMetadata, GdalGeoTiffOptions,
+};
+use crate::util::Result;
+use futures::future::BoxFuture;
+use gdal::raster::{GdalType, RasterCreationOption};
+use gdal::{DriverManager, Metadata};
+use geoengine_datatypes::primitives::{CacheHint, RasterQueryRectangle, TimeInterval};
+use geoengine_datatypes::raster::{Pixel, TilingSpecification};
+use geoengine_datatypes::spatial_reference::SpatialReference;
+use num_traits::AsPrimitive;
+use std::convert::TryInto;
+
+use super::{abortable_query_execution, spawn_blocking};
+
+/// `NetCDF` type code of the time variable (`NC_DOUBLE`)
+const NETCDF_TIME_TYPE: u8 = 6;
+const NETCDF_TIME_UNITS: &str = "milliseconds since 1970-01-01 00:00:00";
+
+#[derive(Debug, Clone)]
+pub struct NetCdfDatasetMetadata {
+    /// The fill value of the variables. Defaults to the maximum value of the data type.
+    pub no_data_value: Option<f64>,
+    pub spatial_reference: SpatialReference,
+    /// The names of the queried bands, one variable is created for each band
+    pub band_names: Vec<String>,
+}
+
+/// Consumes a raster stream and writes it to a `NetCDF` 4 file with one variable for each band.
+/// The variables have a `time` dimension with one entry for each time step of the stream.
+///
+/// Note: the entire process is done in memory and in a temporary directory, and will take 2x the size of the raster
+///       time series
+#[allow(clippy::too_many_arguments)]
+pub async fn raster_stream_to_netcdf_bytes<T, C: QueryContext + 'static>(
+    processor: Box<dyn RasterQueryProcessor<RasterType = T>>,
+    query_rect: RasterQueryRectangle,
+    mut query_ctx: C,
+    metadata: NetCdfDatasetMetadata,
+    tile_limit: Option<usize>,
+    conn_closed: BoxFuture<'_, ()>,
+    tiling_specification: TilingSpecification,
+) -> Result<(Vec<u8>, CacheHint)>
+where
+    T: Pixel + GdalType,
+{
+    let num_bands = query_rect.attributes.count() as usize;
+    if metadata.band_names.len() != num_bands {
+        return Err(Error::InvalidNumberOfBandNames {
+            expected: num_bands,
+            found: metadata.band_names.len(),
+        });
+    }
+
+    let query_abort_trigger = query_ctx.abort_trigger()?;
+
+    let tiles = abortable_query_execution(
+        consume_stream_into_vec(processor, query_rect.clone(), query_ctx, tile_limit),
+        conn_closed,
+        query_abort_trigger,
+    )
+    .await?;
+
+    let no_data_value = metadata
+        .no_data_value
+        .unwrap_or_else(|| T::max_value().as_());
+
+    let writer = GdalDatasetWriter::<T>::new(
+        &query_rect,
+        tiling_specification,
+        GdalGeoTiffDatasetMetadata {
+            no_data_value: Some(no_data_value),
+            spatial_reference: metadata.spatial_reference,
+        },
+        GdalGeoTiffOptions {
+            compression_num_threads: GdalCompressionNumThreads::AllCpus,
+            as_cog: false,
+            force_big_tiff: false,
+        },
+        false,
+    );
+
+    let (width, height) = output_size(&query_rect);
+
+    // the directory is removed on drop, i.e., also if writing the file fails
+    let temp_dir = tempfile::tempdir()?;
+    let file_path = temp_dir.path().join("output.nc");
+
+    let (file_path, cache_hint) = spawn_blocking(move || {
+        // the tiles are ordered by time, so we can enumerate the time steps as they appear
+        let mut time_steps: Vec<TimeInterval> = Vec::new();
+        let mut cache_hint = CacheHint::max_duration();
+        let tile_time_indices: Vec<usize> = tiles
+            .iter()
+            .map(|tile| {
+                if time_steps.last() != Some(&tile.time) {
+                    time_steps.push(tile.time);
+                }
+                cache_hint.merge_with(&tile.cache_hint);
+                time_steps.len() - 1
+            })
+            .collect();
+
+        let time_values = time_steps
+            .iter()
+            .map(|time| time.start().inner().to_string())
+            .collect::<Vec<_>>();
+
+        let mem_driver = DriverManager::get_driver_by_name("MEM")?;
+        let netcdf_driver = DriverManager::get_driver_by_name("netCDF")?;
+
+        let mut tiles_by_band: Vec<Vec<_>> = (0..num_bands).map(|_| Vec::new()).collect();
+        for (tile, time_index) in tiles.into_iter().zip(tile_time_indices) {
+            if let Some(band_tiles) = tiles_by_band.get_mut(tile.band as usize) {
+                band_tiles.push((tile, time_index));
+            }
+        }
+
+        for (band_index, (band_name, band_tiles)) in
+            metadata.band_names.iter().zip(tiles_by_band).enumerate()
+        {
+            let mut dataset = mem_driver.create_with_band_type::<T, _>(
+                "",
+                width as isize,
+                height as isize,
+                time_steps.len() as isize,
+            )?;
+            dataset.set_spatial_ref(&metadata.spatial_reference.try_into()?)?;
+            dataset.set_geo_transform(&writer.output_geo_transform().into())?;
+
+            // the netCDF driver maps the bands to an extra dimension if they are described in the metadata
+            dataset.set_metadata_item("NETCDF_DIM_EXTRA", "{time}", "")?;
+            dataset.set_metadata_item(
+                "NETCDF_DIM_time_DEF",
+                &format!("{{{},{NETCDF_TIME_TYPE}}}", time_steps.len()),
+                "",
+            )?;
+            dataset.set_metadata_item(
+                "NETCDF_DIM_time_VALUES",
+                &format!("{{{}}}", time_values.join(",")),
+                "",
+            )?;
+            dataset.set_metadata_item("time#standard_name", "time", "")?;
+            dataset.set_metadata_item("time#units", NETCDF_TIME_UNITS, "")?;
+            dataset.set_metadata_item("time#calendar", "proleptic_gregorian", "")?;
+
+            let variable_name = netcdf_variable_name(band_name);
+
+            for (time_index, time_value) in time_values.iter().enumerate() {
+                let mut band = dataset.rasterband(time_index as isize + 1)?;
+                band.set_no_data_value(Some(no_data_value))?;
+                band.set_metadata_item("NETCDF_VARNAME", &variable_name, "")?;
+                band.set_metadata_item("long_name", band_name, "")?;
+                band.set_metadata_item("NETCDF_DIM_time", time_value, "")?;
+            }
+
+            for (tile, time_index) in band_tiles {
+                writer.write_tile_into_band(tile, dataset.rasterband(time_index as isize + 1)?)?;
+            }
+
+            let mut options = vec![
+                RasterCreationOption {
+                    key: "FORMAT",
+                    value: "NC4",
+                },
+                RasterCreationOption {
+                    key: "COMPRESS",
+                    value: "DEFLATE",
+                },
+            ];
+            if band_index > 0 {
+                options.push(RasterCreationOption {
+                    key: "APPEND_SUBDATASET",
+                    value: "YES",
+                });
+            }
+
+            dataset.create_copy(&netcdf_driver, &file_path, &options)?;
+        }
+
+        Result::<_, Error>::Ok((file_path, cache_hint))
+    })
+    .await??;
+
+    let bytes = std::fs::read(file_path)?;
+
+    Ok((bytes, cache_hint))
+}
+
+/// Converts a band name into a valid `NetCDF` variable name
+fn netcdf_variable_name(band_name: &str) -> String {
+    let name: String = band_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("band_{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{MockQueryContext, RasterResultDescriptor};
+    use crate::source::GdalSourceProcessor;
+    use crate::util::gdal::{create_ndvi_meta_data, gdal_open_dataset};
+    use geoengine_datatypes::primitives::{
+        BandSelection, Coordinate2D, SpatialPartition2D, SpatialResolution,
+    };
+    use geoengine_datatypes::raster::RasterDataType;
+    use geoengine_datatypes::util::test::TestDefault;
+    use std::marker::PhantomData;
+
+    #[test]
+    fn it_creates_variable_names() {
+        assert_eq!(netcdf_variable_name("ndvi"), "ndvi");
+        assert_eq!(netcdf_variable_name("red band-1"), "red_band_1");
+        assert_eq!(
+            netcdf_variable_name("2m temperature"),
+            "band_2m_temperature"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_writes_a_time_series() {
+        let ctx = MockQueryContext::test_default();
+        let tiling_specification =
+            TilingSpecification::new(Coordinate2D::default(), [512, 512].into());
+
+        let gdal_source = GdalSourceProcessor::<u8> {
+            result_descriptor: RasterResultDescriptor::with_datatype_and_num_bands(
+                RasterDataType::U8,
+                1,
+            ),
+            tiling_specification,
+            meta_data: Box::new(create_ndvi_meta_data()),
+            _phantom_data: PhantomData,
+        };
+
+        let (bytes, _) = raster_stream_to_netcdf_bytes(
+            gdal_source.boxed(),
+            RasterQueryRectangle {
+                spatial_bounds: SpatialPartition2D::new((-180., 90.).into(), (180., -90.).into())
+                    .unwrap(),
+                // 1.1.2014 - 1.4.2014
+                time_interval: TimeInterval::new(1_388_534_400_000, 1_396_306_800_000).unwrap(),
+                spatial_resolution: SpatialResolution::new_unchecked(1.0, 1.0),
+                attributes: BandSelection::first(),
+            },
+            ctx,
+            NetCdfDatasetMetadata {
+                no_data_value: Some(0.),
+                spatial_reference: SpatialReference::epsg_4326(),
+                band_names: vec!["ndvi".to_string()],
+            },
+            None,
+            Box::pin(futures::future::pending()),
+            tiling_specification,
+        )
+        .await
+        .unwrap();
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), bytes).unwrap();
+
+        let dataset = gdal_open_dataset(file.path()).unwrap();
+
+        // three time steps for Jan, Feb, Mar
+        assert_eq!(dataset.raster_count(), 3);
+        assert_eq!(dataset.raster_size(), (360, 180));
+
+        let band = dataset.rasterband(1).unwrap();
+        assert_eq!(
+            band.metadata_item("NETCDF_VARNAME", ""),
+            Some("ndvi".to_string())
+        );
+        assert_eq!(
+            band.metadata_item("NETCDF_DIM_time", ""),
+            Some("1388534400000".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_missing_band_names() {
+        let tiling_specification =
+            TilingSpecification::new(Coordinate2D::default(), [512, 512].into());
+
+        let gdal_source = GdalSourceProcessor::<u8> {
+            result_descriptor: RasterResultDescriptor::with_datatype_and_num_bands(
+                RasterDataType::U8,
+                1,
+            ),
+            tiling_specification,
+            meta_data: Box::new(create_ndvi_meta_data()),
+            _phantom_data: PhantomData,
+        };
+
+        let result = raster_stream_to_netcdf_bytes(
+            gdal_source.boxed(),
+            RasterQueryRectangle {
+                spatial_bounds: SpatialPartition2D::new((-180., 90.).into(), (180., -90.).into())
+                    .unwrap(),
+                time_interval: TimeInterval::new(1_388_534_400_000, 1_388_534_400_000 + 1000)
+                    .unwrap(),
+                spatial_resolution: SpatialResolution::new_unchecked(1.0, 1.0),
+                attributes: BandSelection::first(),
+            },
+            MockQueryContext::test_default(),
+            NetCdfDatasetMetadata {
+                no_data_value: None,
+                spatial_reference: SpatialReference::epsg_4326(),
+                band_names: vec![],
+            },
+            None,
+            Box::pin(futures::future::pending()),
+            tiling_specification,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidNumberOfBandNames {
+                expected: 1,
+                found: 0
+            })
+        ));
+    }
+}
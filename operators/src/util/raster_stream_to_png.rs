@@ -1,6 +1,6 @@
 use futures::{future::BoxFuture, StreamExt};
 use geoengine_datatypes::{
-    operations::image::{Colorizer, RgbaColor, ToPng},
+    operations::image::{Colorizer, ImageOutputFormat, RgbaColor, ToImage},
     primitives::{AxisAlignedRectangle, CacheHint, RasterQueryRectangle, TimeInterval},
     raster::{Blit, EmptyGrid2D, GeoTransform, GridOrEmpty, Pixel, RasterTile2D},
 };
@@ -16,6 +16,35 @@ use super::abortable_query_execution;
 
 #[allow(clippy::too_many_arguments)]
 pub async fn raster_stream_to_png_bytes<T, C: QueryContext + 'static>(
+    processor: Box<dyn RasterQueryProcessor<RasterType = T>>,
+    query_rect: RasterQueryRectangle,
+    query_ctx: C,
+    width: u32,
+    height: u32,
+    time: Option<TimeInterval>,
+    colorizer: Option<Colorizer>,
+    conn_closed: BoxFuture<'_, ()>,
+) -> Result<(Vec<u8>, CacheHint)>
+where
+    T: Pixel,
+{
+    raster_stream_to_image_bytes(
+        processor,
+        query_rect,
+        query_ctx,
+        width,
+        height,
+        time,
+        colorizer,
+        ImageOutputFormat::Png,
+        conn_closed,
+    )
+    .await
+}
+
+/// Renders the raster stream into a colorized image of the given `format`
+#[allow(clippy::too_many_arguments)]
+pub async fn raster_stream_to_image_bytes<T, C: QueryContext + 'static>(
     processor: Box<dyn RasterQueryProcessor<RasterType = T>>,
     query_rect: RasterQueryRectangle,
     mut query_ctx: C,
@@ -23,6 +52,7 @@ pub async fn raster_stream_to_png_bytes<T, C: QueryContext + 'static>(
     height: u32,
     time: Option<TimeInterval>,
     colorizer: Option<Colorizer>,
+    format: ImageOutputFormat,
     conn_closed: BoxFuture<'_, ()>,
 ) -> Result<(Vec<u8>, CacheHint)>
 where
@@ -32,11 +62,11 @@ where
     ensure!(
         query_rect.attributes.count() == 1,
         crate::error::OperationDoesNotSupportMultiBandQueriesYet {
-            operation: "raster_stream_to_png_bytes"
+            operation: "raster_stream_to_image_bytes"
         }
     );
 
-    let span = span!(Level::TRACE, "raster_stream_to_png_bytes");
+    let span = span!(Level::TRACE, "raster_stream_to_image_bytes");
     let _enter = span.enter();
 
     let query_abort_trigger = query_ctx.abort_trigger()?;
@@ -46,7 +76,7 @@ where
     let x_query_resolution = query_rect.spatial_bounds.size_x() / f64::from(width);
     let y_query_resolution = query_rect.spatial_bounds.size_y() / f64::from(height);
 
-    // build png
+    // build image
     let dim = [height as usize, width as usize];
     let query_geo_transform = GeoTransform::new(
         query_rect.spatial_bounds.upper_left(),
@@ -85,7 +115,9 @@ where
 
     let colorizer = colorizer.unwrap_or(default_colorizer_gradient::<T>()?);
     Ok((
-        result.grid_array.to_png(width, height, &colorizer)?,
+        result
+            .grid_array
+            .to_image(width, height, &colorizer, format)?,
         result.cache_hint,
     ))
 }
@@ -0,0 +1,657 @@
+use crate::engine::{QueryContext, VectorQueryProcessor};
+use crate::error;
+use crate::source::{
+    OgrSourceColumnSpec, OgrSourceDataset, OgrSourceDatasetTimeType, OgrSourceErrorSpec,
+    OgrSourceTimeFormat,
+};
+use crate::util::Result;
+use futures::future::BoxFuture;
+use futures::TryStreamExt;
+use gdal::spatial_ref::SpatialRef;
+use gdal::vector::{
+    Feature, FieldValue, Geometry as OgrGeometry, LayerAccess, LayerOptions, OGRFieldType,
+    OGRwkbGeometryType,
+};
+use gdal::{Dataset, DriverManager};
+use geoengine_datatypes::collections::{
+    FeatureCollection, FeatureCollectionInfos, IntoGeometryOptionsIterator,
+};
+use geoengine_datatypes::primitives::{
+    BoundingBox2D, CacheTtlSeconds, FeatureDataType, FeatureDataValue, Geometry, GeometryRef,
+    TimeInterval, VectorDataType, VectorQueryRectangle,
+};
+use geoengine_datatypes::spatial_reference::SpatialReferenceOption;
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use snafu::ensure;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use wkt::ToWkt;
+
+use super::{abortable_query_execution, spawn_blocking};
+
+/// The OGR file formats a vector stream can be written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OgrVectorFileFormat {
+    GeoPackage,
+    FlatGeobuf,
+}
+
+impl OgrVectorFileFormat {
+    pub fn driver_name(self) -> &'static str {
+        match self {
+            Self::GeoPackage => "GPKG",
+            Self::FlatGeobuf => "FlatGeobuf",
+        }
+    }
+
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::GeoPackage => "gpkg",
+            Self::FlatGeobuf => "fgb",
+        }
+    }
+
+    /// Whether the format can store features without geometries
+    fn supports_data_collections(self) -> bool {
+        match self {
+            Self::GeoPackage => true,
+            Self::FlatGeobuf => false,
+        }
+    }
+
+    /// Whether the driver supports transactions for batching the feature inserts
+    fn supports_transactions(self) -> bool {
+        match self {
+            Self::GeoPackage => true,
+            Self::FlatGeobuf => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OgrVectorFileOptions {
+    pub format: OgrVectorFileFormat,
+    pub layer_name: String,
+    pub spatial_reference: SpatialReferenceOption,
+    /// the attribute columns of the stream that are written to the file
+    pub columns: HashMap<String, FeatureDataType>,
+}
+
+/// A vector file that was written from a stream, together with the information to load it again
+#[derive(Debug, Clone)]
+pub struct OgrVectorFile {
+    pub loading_info: OgrSourceDataset,
+    /// the attribute columns as they are read by the `OgrSource`
+    pub columns: HashMap<String, FeatureDataType>,
+    pub bbox: Option<BoundingBox2D>,
+    pub time: Option<TimeInterval>,
+}
+
+/// Consume a vector stream and write all features into a single layer of an OGR vector file.
+///
+/// The feature time is stored as milliseconds in two additional columns,
+/// category columns are stored as integers.
+pub async fn vector_stream_to_ogr<G, C>(
+    file_path: &Path,
+    processor: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    query_rect: VectorQueryRectangle,
+    mut query_ctx: C,
+    options: OgrVectorFileOptions,
+    conn_closed: BoxFuture<'_, ()>,
+) -> Result<OgrVectorFile>
+where
+    G: Geometry + ArrowTyped + 'static,
+    for<'g> FeatureCollection<G>: IntoGeometryOptionsIterator<'g>,
+    C: QueryContext + 'static,
+{
+    ensure!(
+        G::DATA_TYPE != VectorDataType::Data || options.format.supports_data_collections(),
+        error::OgrVectorFileFormatRequiresGeometry {
+            format: options.format.driver_name(),
+        }
+    );
+
+    let file_path = file_path.to_owned();
+    let writer =
+        spawn_blocking(move || OgrVectorFileWriter::create(file_path, G::DATA_TYPE, options))
+            .await??;
+
+    let query_abort_trigger = query_ctx.abort_trigger()?;
+
+    let writer = abortable_query_execution(
+        write_stream(writer, processor, query_rect, &query_ctx),
+        conn_closed,
+        query_abort_trigger,
+    )
+    .await?;
+
+    Ok(writer.finish(G::DATA_TYPE))
+}
+
+async fn write_stream<G, C>(
+    writer: OgrVectorFileWriter,
+    processor: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    query_rect: VectorQueryRectangle,
+    query_ctx: &C,
+) -> Result<OgrVectorFileWriter>
+where
+    G: Geometry + ArrowTyped + 'static,
+    for<'g> FeatureCollection<G>: IntoGeometryOptionsIterator<'g>,
+    C: QueryContext + 'static,
+{
+    let stream = processor.vector_query(query_rect, query_ctx).await?;
+
+    stream
+        .try_fold(writer, |mut writer, collection| async move {
+            spawn_blocking(move || -> Result<OgrVectorFileWriter> {
+                writer.write_collection(&collection)?;
+                Ok(writer)
+            })
+            .await?
+        })
+        .await
+}
+
+struct OgrVectorFileWriter {
+    dataset: Dataset,
+    file_path: PathBuf,
+    format: OgrVectorFileFormat,
+    layer_name: String,
+    columns: Vec<(String, FeatureDataType)>,
+    time_start_column: String,
+    time_end_column: String,
+    bbox: Option<BoundingBox2D>,
+    time: Option<TimeInterval>,
+}
+
+impl OgrVectorFileWriter {
+    fn create(
+        file_path: PathBuf,
+        data_type: VectorDataType,
+        options: OgrVectorFileOptions,
+    ) -> Result<Self> {
+        let driver = DriverManager::get_driver_by_name(options.format.driver_name())?;
+        let mut dataset = driver.create_vector_only(&file_path)?;
+
+        let spatial_ref: Option<SpatialRef> = match options.spatial_reference {
+            SpatialReferenceOption::SpatialReference(spatial_reference) => {
+                Some(spatial_reference.try_into()?)
+            }
+            SpatialReferenceOption::Unreferenced => None,
+        };
+
+        let mut columns: Vec<(String, FeatureDataType)> = options.columns.into_iter().collect();
+        columns.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let time_start_column = unique_column_name("time_start", &columns);
+        let time_end_column = unique_column_name("time_end", &columns);
+
+        let layer = dataset.create_layer(LayerOptions {
+            name: &options.layer_name,
+            srs: spatial_ref.as_ref(),
+            ty: ogr_geometry_type(data_type),
+            options: None,
+        })?;
+
+        let mut field_definitions = vec![
+            (time_start_column.as_str(), OGRFieldType::OFTInteger64),
+            (time_end_column.as_str(), OGRFieldType::OFTInteger64),
+        ];
+        field_definitions.extend(
+            columns
+                .iter()
+                .map(|(name, data_type)| (name.as_str(), ogr_field_type(*data_type))),
+        );
+        layer.create_defn_fields(&field_definitions)?;
+
+        Ok(Self {
+            dataset,
+            file_path,
+            format: options.format,
+            layer_name: options.layer_name,
+            columns,
+            time_start_column,
+            time_end_column,
+            bbox: None,
+            time: None,
+        })
+    }
+
+    fn write_collection<G>(&mut self, collection: &FeatureCollection<G>) -> Result<()>
+    where
+        G: Geometry + ArrowTyped,
+        for<'g> FeatureCollection<G>: IntoGeometryOptionsIterator<'g>,
+    {
+        if collection.is_empty() {
+            return Ok(());
+        }
+
+        if self.format.supports_transactions() {
+            let transaction = self.dataset.start_transaction()?;
+            let bbox = write_features(
+                &transaction,
+                &self.layer_name,
+                &self.columns,
+                (&self.time_start_column, &self.time_end_column),
+                collection,
+            )?;
+            transaction.commit()?;
+            self.extend_bounds(bbox, collection.time_bounds());
+        } else {
+            let bbox = write_features(
+                &self.dataset,
+                &self.layer_name,
+                &self.columns,
+                (&self.time_start_column, &self.time_end_column),
+                collection,
+            )?;
+            self.extend_bounds(bbox, collection.time_bounds());
+        }
+
+        Ok(())
+    }
+
+    fn extend_bounds(&mut self, bbox: Option<BoundingBox2D>, time: Option<TimeInterval>) {
+        self.bbox = match (self.bbox, bbox) {
+            (Some(a), Some(b)) => Some(a.union(&b)),
+            (a, b) => a.or(b),
+        };
+        self.time = match (self.time, time) {
+            (Some(a), Some(b)) => Some(a.extend(&b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    fn finish(self, data_type: VectorDataType) -> OgrVectorFile {
+        let mut column_spec = OgrSourceColumnSpec {
+            format_specifics: None,
+            x: String::new(),
+            y: None,
+            int: vec![],
+            float: vec![],
+            text: vec![],
+            bool: vec![],
+            datetime: vec![],
+            rename: None,
+        };
+
+        let mut columns = HashMap::with_capacity(self.columns.len());
+
+        for (name, data_type) in self.columns {
+            let data_type = match data_type {
+                FeatureDataType::Category | FeatureDataType::Int => {
+                    column_spec.int.push(name.clone());
+                    FeatureDataType::Int
+                }
+                FeatureDataType::Float => {
+                    column_spec.float.push(name.clone());
+                    FeatureDataType::Float
+                }
+                FeatureDataType::Text => {
+                    column_spec.text.push(name.clone());
+                    FeatureDataType::Text
+                }
+                FeatureDataType::Bool => {
+                    column_spec.bool.push(name.clone());
+                    FeatureDataType::Bool
+                }
+                FeatureDataType::DateTime => {
+                    column_spec.datetime.push(name.clone());
+                    FeatureDataType::DateTime
+                }
+            };
+            columns.insert(name, data_type);
+        }
+
+        // close the dataset to flush all remaining data to the file
+        drop(self.dataset);
+
+        OgrVectorFile {
+            loading_info: OgrSourceDataset {
+                file_name: self.file_path,
+                layer_name: self.layer_name,
+                data_type: Some(data_type),
+                time: OgrSourceDatasetTimeType::StartEnd {
+                    start_field: self.time_start_column,
+                    start_format: OgrSourceTimeFormat::milliseconds(),
+                    end_field: self.time_end_column,
+                    end_format: OgrSourceTimeFormat::milliseconds(),
+                },
+                default_geometry: None,
+                columns: Some(column_spec),
+                force_ogr_time_filter: false,
+                force_ogr_spatial_filter: false,
+                on_error: OgrSourceErrorSpec::Abort,
+                sql_query: None,
+                attribute_query: None,
+                cache_ttl: CacheTtlSeconds::default(),
+            },
+            columns,
+            bbox: self.bbox,
+            time: self.time,
+        }
+    }
+}
+
+/// Write all features of the collection into the layer and return their bounding box
+fn write_features<G>(
+    dataset: &Dataset,
+    layer_name: &str,
+    columns: &[(String, FeatureDataType)],
+    (time_start_column, time_end_column): (&str, &str),
+    collection: &FeatureCollection<G>,
+) -> Result<Option<BoundingBox2D>>
+where
+    G: Geometry + ArrowTyped,
+    for<'g> FeatureCollection<G>: IntoGeometryOptionsIterator<'g>,
+{
+    let layer = dataset.layer_by_name(layer_name)?;
+
+    let column_data = columns
+        .iter()
+        .map(|(name, _)| collection.data(name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bbox: Option<BoundingBox2D> = None;
+
+    for (feature_index, (geometry, time)) in collection
+        .geometry_options()
+        .zip(collection.time_intervals())
+        .enumerate()
+    {
+        let mut feature = Feature::new(layer.defn())?;
+
+        if let Some(geometry) = geometry {
+            if let Some(geometry_bbox) = geometry.bbox() {
+                bbox = Some(bbox.map_or(geometry_bbox, |bbox| bbox.union(&geometry_bbox)));
+            }
+            feature.set_geometry(OgrGeometry::from_wkt(&geometry.wkt_string())?)?;
+        }
+
+        feature.set_field(
+            time_start_column,
+            &FieldValue::Integer64Value(time.start().inner()),
+        )?;
+        feature.set_field(
+            time_end_column,
+            &FieldValue::Integer64Value(time.end().inner()),
+        )?;
+
+        for ((name, _), data) in columns.iter().zip(&column_data) {
+            if let Some(value) = ogr_field_value(data.get_unchecked(feature_index)) {
+                feature.set_field(name, &value)?;
+            }
+        }
+
+        feature.create(&layer)?;
+    }
+
+    Ok(bbox)
+}
+
+fn unique_column_name(name: &str, columns: &[(String, FeatureDataType)]) -> String {
+    let mut name = name.to_string();
+    while columns.iter().any(|(column, _)| *column == name) {
+        name.push('_');
+    }
+    name
+}
+
+fn ogr_geometry_type(data_type: VectorDataType) -> OGRwkbGeometryType::Type {
+    match data_type {
+        VectorDataType::Data => OGRwkbGeometryType::wkbNone,
+        VectorDataType::MultiPoint => OGRwkbGeometryType::wkbMultiPoint,
+        VectorDataType::MultiLineString => OGRwkbGeometryType::wkbMultiLineString,
+        VectorDataType::MultiPolygon => OGRwkbGeometryType::wkbMultiPolygon,
+    }
+}
+
+fn ogr_field_type(data_type: FeatureDataType) -> OGRFieldType::Type {
+    match data_type {
+        FeatureDataType::Category | FeatureDataType::Bool => OGRFieldType::OFTInteger,
+        FeatureDataType::Int | FeatureDataType::DateTime => OGRFieldType::OFTInteger64,
+        FeatureDataType::Float => OGRFieldType::OFTReal,
+        FeatureDataType::Text => OGRFieldType::OFTString,
+    }
+}
+
+/// Convert a feature value to an OGR field value, `None` for null values
+fn ogr_field_value(value: FeatureDataValue) -> Option<FieldValue> {
+    match value {
+        FeatureDataValue::Category(v) | FeatureDataValue::NullableCategory(Some(v)) => {
+            Some(FieldValue::IntegerValue(v.into()))
+        }
+        FeatureDataValue::Int(v) | FeatureDataValue::NullableInt(Some(v)) => {
+            Some(FieldValue::Integer64Value(v))
+        }
+        FeatureDataValue::Float(v) | FeatureDataValue::NullableFloat(Some(v)) => {
+            Some(FieldValue::RealValue(v))
+        }
+        FeatureDataValue::Text(v) | FeatureDataValue::NullableText(Some(v)) => {
+            Some(FieldValue::StringValue(v))
+        }
+        FeatureDataValue::Bool(v) | FeatureDataValue::NullableBool(Some(v)) => {
+            Some(FieldValue::IntegerValue(v.into()))
+        }
+        FeatureDataValue::DateTime(v) | FeatureDataValue::NullableDateTime(Some(v)) => {
+            Some(FieldValue::Integer64Value(v.inner()))
+        }
+        FeatureDataValue::NullableCategory(None)
+        | FeatureDataValue::NullableInt(None)
+        | FeatureDataValue::NullableFloat(None)
+        | FeatureDataValue::NullableText(None)
+        | FeatureDataValue::NullableBool(None)
+        | FeatureDataValue::NullableDateTime(None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::engine::{
+        ChunkByteSize, MockExecutionContext, MockQueryContext, QueryProcessor, StaticMetaData,
+        VectorColumnInfo, VectorOperator, VectorResultDescriptor, WorkflowOperatorPath,
+    };
+    use crate::mock::MockFeatureCollectionSource;
+    use crate::source::OgrSourceProcessor;
+    use geoengine_datatypes::collections::{ChunksEqualIgnoringCacheHint, MultiPointCollection};
+    use geoengine_datatypes::primitives::{
+        CacheHint, ColumnSelection, FeatureData, Measurement, MultiPoint, SpatialResolution,
+    };
+    use geoengine_datatypes::spatial_reference::SpatialReference;
+    use geoengine_datatypes::util::test::TestDefault;
+
+    #[tokio::test]
+    async fn it_writes_a_readable_geopackage() {
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![vec![(0.0, 0.1)], vec![(1.0, 1.1), (2.0, 2.1)]]).unwrap(),
+            vec![
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20),
+            ],
+            [
+                ("category".to_string(), FeatureData::Category(vec![1, 2])),
+                ("int".to_string(), FeatureData::Int(vec![1, 2])),
+                (
+                    "float".to_string(),
+                    FeatureData::NullableFloat(vec![Some(1.5), None]),
+                ),
+                (
+                    "text".to_string(),
+                    FeatureData::NullableText(vec![Some("foo".to_string()), None]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let processor = MockFeatureCollectionSource::single(collection.clone())
+            .boxed()
+            .initialize(
+                WorkflowOperatorPath::initialize_root(),
+                &MockExecutionContext::test_default(),
+            )
+            .await
+            .unwrap()
+            .query_processor()
+            .unwrap()
+            .multi_point()
+            .unwrap();
+
+        let query = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new((-180., -90.).into(), (180., 90.).into()).unwrap(),
+            time_interval: TimeInterval::default(),
+            spatial_resolution: SpatialResolution::one(),
+            attributes: ColumnSelection::all(),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("points.gpkg");
+
+        let file = vector_stream_to_ogr(
+            &file_path,
+            processor,
+            query.clone(),
+            MockQueryContext::test_default(),
+            OgrVectorFileOptions {
+                format: OgrVectorFileFormat::GeoPackage,
+                layer_name: "points".to_string(),
+                spatial_reference: SpatialReference::epsg_4326().into(),
+                columns: collection.column_types(),
+            },
+            Box::pin(futures::future::pending()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            file.bbox,
+            Some(BoundingBox2D::new_unchecked(
+                (0.0, 0.1).into(),
+                (2.0, 2.1).into()
+            ))
+        );
+        assert_eq!(file.time, Some(TimeInterval::new_unchecked(0, 20)));
+        assert_eq!(file.columns.get("category"), Some(&FeatureDataType::Int));
+
+        let result_descriptor = VectorResultDescriptor {
+            data_type: VectorDataType::MultiPoint,
+            spatial_reference: SpatialReference::epsg_4326().into(),
+            columns: file
+                .columns
+                .iter()
+                .map(|(name, data_type)| {
+                    (
+                        name.clone(),
+                        VectorColumnInfo {
+                            data_type: *data_type,
+                            measurement: Measurement::Unitless,
+                        },
+                    )
+                })
+                .collect(),
+            time: file.time,
+            bbox: file.bbox,
+        };
+
+        let processor = OgrSourceProcessor::<MultiPoint>::new(
+            result_descriptor.clone(),
+            Box::new(StaticMetaData {
+                loading_info: file.loading_info,
+                result_descriptor,
+                phantom: Default::default(),
+            }),
+            vec![],
+        );
+
+        let result: Vec<MultiPointCollection> = processor
+            .query(query, &MockQueryContext::new(ChunkByteSize::MAX))
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        let expected = MultiPointCollection::from_data(
+            MultiPoint::many(vec![vec![(0.0, 0.1)], vec![(1.0, 1.1), (2.0, 2.1)]]).unwrap(),
+            vec![
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20),
+            ],
+            [
+                (
+                    "category".to_string(),
+                    FeatureData::NullableInt(vec![Some(1), Some(2)]),
+                ),
+                (
+                    "int".to_string(),
+                    FeatureData::NullableInt(vec![Some(1), Some(2)]),
+                ),
+                (
+                    "float".to_string(),
+                    FeatureData::NullableFloat(vec![Some(1.5), None]),
+                ),
+                (
+                    "text".to_string(),
+                    FeatureData::NullableText(vec![Some("foo".to_string()), None]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].chunks_equal_ignoring_cache_hint(&expected));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_data_collections_for_flatgeobuf() {
+        let processor = MockFeatureCollectionSource::single(
+            geoengine_datatypes::collections::DataCollection::empty(),
+        )
+        .boxed()
+        .initialize(
+            WorkflowOperatorPath::initialize_root(),
+            &MockExecutionContext::test_default(),
+        )
+        .await
+        .unwrap()
+        .query_processor()
+        .unwrap()
+        .data()
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+
+        let result = vector_stream_to_ogr(
+            &dir.path().join("data.fgb"),
+            processor,
+            VectorQueryRectangle {
+                spatial_bounds: BoundingBox2D::new((-180., -90.).into(), (180., 90.).into())
+                    .unwrap(),
+                time_interval: TimeInterval::default(),
+                spatial_resolution: SpatialResolution::one(),
+                attributes: ColumnSelection::all(),
+            },
+            MockQueryContext::test_default(),
+            OgrVectorFileOptions {
+                format: OgrVectorFileFormat::FlatGeobuf,
+                layer_name: "data".to_string(),
+                spatial_reference: SpatialReferenceOption::Unreferenced,
+                columns: HashMap::new(),
+            },
+            Box::pin(futures::future::pending()),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(error::Error::OgrVectorFileFormatRequiresGeometry { .. })
+        ));
+    }
+}
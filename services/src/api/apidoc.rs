@@ -2,7 +2,10 @@ use super::handlers;
 use super::handlers::plots::WrappedPlotOutput;
 use super::handlers::spatial_references::{AxisOrder, SpatialReferenceSpecification};
 use super::handlers::tasks::{TaskAbortOptions, TaskResponse};
-use super::handlers::upload::{UploadFileLayersResponse, UploadFilesResponse};
+use super::handlers::upload::{
+    CreateResumableUpload, ResumableUploadFileStatus, ResumableUploadStatus,
+    UploadFileLayersResponse, UploadFilesResponse,
+};
 use super::handlers::wfs::{CollectionType, GeoJson};
 use super::handlers::workflows::{ProvenanceEntry, RasterStreamWebsocketResultType};
 use super::model::responses::{ErrorResponse, PngResponse, ZipResponse};
@@ -33,15 +36,19 @@ use crate::api::model::responses::{
     UnauthorizedUserResponse, UnsupportedMediaTypeForJsonResponse,
 };
 use crate::api::model::services::{
-    AddDataset, CreateDataset, DataPath, DatasetDefinition, MetaDataDefinition, MetaDataSuggestion,
-    Provenance, ProvenanceOutput, Provenances, UpdateDataset,
+    AddDataset, AppendDataset, AppendGdalMetaDataList, AppendGdalMetaDataRegular,
+    AppendOgrMetaData, CreateDataset, DataPath, DatasetDefinition, MetaDataDefinition,
+    MetaDataSuggestion, Provenance, ProvenanceOutput, Provenances, UpdateDataset,
 };
-use crate::api::ogc::{util::OgcBoundingBox, wcs, wfs, wms};
+use crate::api::ogc::{features, util::OgcBoundingBox, wcs, wfs, wms};
 use crate::contexts::{SessionId, SimpleSession};
 use crate::datasets::listing::{DatasetListing, OrderBy};
 use crate::datasets::storage::{AutoCreateDataset, Dataset};
-use crate::datasets::upload::{UploadId, Volume, VolumeName};
-use crate::datasets::{DatasetName, RasterDatasetFromWorkflow, RasterDatasetFromWorkflowResult};
+use crate::datasets::upload::{ResumableFileUpload, UploadId, Volume, VolumeName};
+use crate::datasets::{
+    DatasetName, RasterDatasetFromWorkflow, RasterDatasetFromWorkflowResult, VectorDatasetFormat,
+    VectorDatasetFromWorkflow, VectorDatasetFromWorkflowResult,
+};
 use crate::layers::layer::{
     AddLayer, AddLayerCollection, CollectionItem, Layer, LayerCollection, LayerCollectionListing,
     LayerListing, Property, ProviderLayerCollectionId, ProviderLayerId,
@@ -96,10 +103,18 @@ use utoipa::{Modify, OpenApi};
         handlers::wcs::wcs_get_coverage_handler,
         handlers::wfs::wfs_capabilities_handler,
         handlers::wfs::wfs_feature_handler,
+        handlers::features::landing_page_handler,
+        handlers::features::conformance_handler,
+        handlers::features::collections_handler,
+        handlers::features::collection_handler,
+        handlers::features::items_handler,
+        handlers::tiles::vector_tile_handler,
         handlers::wms::wms_capabilities_handler,
+        handlers::wms::wms_feature_info_handler,
         handlers::wms::wms_legend_graphic_handler,
         handlers::wms::wms_map_handler,
         handlers::workflows::dataset_from_workflow_handler,
+        handlers::workflows::vector_dataset_from_workflow_handler,
         handlers::workflows::get_workflow_metadata_handler,
         handlers::workflows::get_workflow_provenance_handler,
         handlers::workflows::load_workflow_handler,
@@ -117,6 +132,7 @@ use utoipa::{Modify, OpenApi};
         handlers::datasets::get_loading_info_handler,
         handlers::datasets::update_dataset_symbology_handler,
         handlers::datasets::update_dataset_provenance_handler,
+        handlers::datasets::append_to_dataset_handler,
         handlers::spatial_references::get_spatial_reference_specification_handler,
         handlers::plots::get_plot_handler,
         handlers::projects::list_projects_handler,
@@ -128,7 +144,12 @@ use utoipa::{Modify, OpenApi};
         handlers::projects::load_project_version_handler,
         handlers::upload::list_upload_files_handler,
         handlers::upload::list_upload_file_layers_handler,
-        handlers::upload::upload_handler
+        handlers::upload::upload_handler,
+        handlers::upload::create_resumable_upload_handler,
+        handlers::upload::resumable_upload_status_handler,
+        handlers::upload::upload_chunk_handler,
+        handlers::upload::finish_resumable_upload_handler,
+        handlers::upload::delete_resumable_upload_handler
     ),
     components(
         responses(
@@ -204,6 +225,9 @@ use utoipa::{Modify, OpenApi};
             VectorColumnInfo,
             RasterDatasetFromWorkflow,
             RasterDatasetFromWorkflowResult,
+            VectorDatasetFromWorkflow,
+            VectorDatasetFromWorkflowResult,
+            VectorDatasetFormat,
             RasterQueryRectangle,
             VectorQueryRectangle,
             PlotQueryRectangle,
@@ -266,6 +290,9 @@ use utoipa::{Modify, OpenApi};
             wms::request::GetMapExceptionFormat,
             wms::request::GetMapFormat,
             wms::request::GetLegendGraphicRequest,
+            wms::request::GetLegendGraphicFormat,
+            wms::request::GetFeatureInfoRequest,
+            wms::request::GetFeatureInfoFormat,
 
             wfs::request::WfsService,
             wfs::request::WfsVersion,
@@ -273,15 +300,34 @@ use utoipa::{Modify, OpenApi};
             wfs::request::WfsResolution,
             wfs::request::GetFeatureRequest,
             wfs::request::TypeNames,
+            wfs::request::ResultType,
+
+            features::response::LandingPage,
+            features::response::Link,
+            features::response::Conformance,
+            features::response::Collections,
+            features::response::Collection,
+            features::response::Extent,
+            features::response::SpatialExtent,
+            features::response::TemporalExtent,
+            features::response::Items,
 
             GeoJson,
             CollectionType,
 
             UploadFilesResponse,
             UploadFileLayersResponse,
+            CreateResumableUpload,
+            ResumableFileUpload,
+            ResumableUploadStatus,
+            ResumableUploadFileStatus,
             CreateDataset,
             UpdateDataset,
             AutoCreateDataset,
+            AppendDataset,
+            AppendGdalMetaDataList,
+            AppendGdalMetaDataRegular,
+            AppendOgrMetaData,
             OrderBy,
             DatasetListing,
             MetaDataSuggestion,
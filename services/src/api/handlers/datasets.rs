@@ -2,16 +2,18 @@ use crate::{
     api::model::{
         responses::datasets::{errors::*, DatasetNameResponse},
         services::{
-            AddDataset, CreateDataset, DataPath, DatasetDefinition, MetaDataDefinition,
+            AddDataset, AppendDataset, AppendGdalMetaDataList, AppendGdalMetaDataRegular,
+            AppendOgrMetaData, CreateDataset, DataPath, DatasetDefinition, MetaDataDefinition,
             MetaDataSuggestion, Provenances, UpdateDataset,
         },
     },
     contexts::{ApplicationContext, SessionContext},
     datasets::{
+        append_to_dataset,
         listing::{DatasetListOptions, DatasetProvider},
         storage::{AutoCreateDataset, DatasetStore, SuggestMetaData},
         upload::{AdjustFilePath, Upload, UploadDb, UploadId, UploadRootPath, Volume, VolumeName},
-        DatasetName,
+        DatasetAppend, DatasetName,
     },
     error::{self, Result},
     projects::Symbology,
@@ -34,8 +36,8 @@ use geoengine_datatypes::{
 use geoengine_operators::{
     engine::{StaticMetaData, VectorColumnInfo, VectorResultDescriptor},
     source::{
-        OgrSourceColumnSpec, OgrSourceDataset, OgrSourceDatasetTimeType, OgrSourceDurationSpec,
-        OgrSourceErrorSpec, OgrSourceTimeFormat,
+        GdalLoadingInfoTemporalSlice, OgrSourceColumnSpec, OgrSourceDataset,
+        OgrSourceDatasetTimeType, OgrSourceDurationSpec, OgrSourceErrorSpec, OgrSourceTimeFormat,
     },
     util::gdal::{gdal_open_dataset, gdal_open_dataset_ex},
 };
@@ -68,6 +70,10 @@ where
                 web::resource("/{dataset}/provenance")
                     .route(web::put().to(update_dataset_provenance_handler::<C>)),
             )
+            .service(
+                web::resource("/{dataset}/append")
+                    .route(web::post().to(append_to_dataset_handler::<C>)),
+            )
             .service(
                 web::resource("/{dataset}")
                     .route(web::get().to(get_dataset_handler::<C>))
@@ -410,6 +416,140 @@ pub async fn update_dataset_provenance_handler<C: ApplicationContext>(
     Ok(HttpResponse::Ok())
 }
 
+/// Appends data to an existing dataset, e.g., new time steps of a raster time series or new features of a vector dataset.
+/// The dataset keeps its name, so existing workflows will see the new data.
+#[utoipa::path(
+    tag = "Datasets",
+    post,
+    path = "/dataset/{dataset}/append",
+    request_body(content = AppendDataset, examples(
+        ("Time slice" = (value = json!({
+            "type": "GdalMetaDataList",
+            "dataPath": {
+                "upload": "420b06de-0a7e-45cb-9c1c-ea901b46ab69"
+            },
+            "params": [{
+                "time": {
+                    "start": 1_388_534_400_000_i64,
+                    "end": 1_391_212_800_000_i64
+                },
+                "params": {
+                    "filePath": "MOD13A2_M_NDVI_2014-01-01.TIFF",
+                    "rasterbandChannel": 1,
+                    "geoTransform": {
+                        "originCoordinate": {
+                            "x": -180.0,
+                            "y": 90.0
+                        },
+                        "xPixelSize": 0.1,
+                        "yPixelSize": -0.1
+                    },
+                    "width": 3600,
+                    "height": 1800,
+                    "fileNotFoundHandling": "NoData",
+                    "noDataValue": 0.0
+                }
+            }]
+        }))),
+        ("Vector features" = (value = json!({
+            "type": "OgrMetaData",
+            "upload": "420b06de-0a7e-45cb-9c1c-ea901b46ab69",
+            "mainFile": "ports_2023.gpkg",
+            "layerName": "ports"
+        })))
+    )),
+    responses(
+        (status = 200, description = "OK"),
+        (status = 400, description = "Bad request", body = ErrorResponse),
+        (status = 401, response = crate::api::model::responses::UnauthorizedUserResponse)
+    ),
+    params(
+        ("dataset" = DatasetName, description = "Dataset Name"),
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+pub async fn append_to_dataset_handler<C: ApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    dataset: web::Path<DatasetName>,
+    append: web::Json<AppendDataset>,
+) -> Result<HttpResponseBuilder> {
+    let session_ctx = app_ctx.session_context(session);
+    let db = session_ctx.db();
+
+    let real_dataset = dataset.into_inner();
+
+    let dataset_id = db.resolve_dataset_name_to_id(&real_dataset).await?;
+
+    // handle the case where the dataset name is not known
+    let dataset_id = dataset_id.ok_or(error::Error::UnknownDatasetName {
+        dataset_name: real_dataset.to_string(),
+    })?;
+
+    let append = match append.into_inner() {
+        AppendDataset::GdalMetaDataList(AppendGdalMetaDataList { data_path, params }) => {
+            let mut slices: Vec<GdalLoadingInfoTemporalSlice> =
+                params.into_iter().map(Into::into).collect();
+
+            match data_path {
+                DataPath::Upload(upload) => {
+                    adjust_temporal_slices_path(&mut slices, &db.load_upload(upload).await?)?;
+                }
+                DataPath::Volume(volume_name) => {
+                    let volume_path = get_config_element::<Data>()?
+                        .volumes
+                        .get(&volume_name)
+                        .cloned()
+                        .ok_or(error::Error::UnknownVolumeName {
+                            volume_name: volume_name.to_string(),
+                        })?;
+                    let volume = Volume {
+                        name: volume_name,
+                        path: volume_path,
+                    };
+                    adjust_temporal_slices_path(&mut slices, &volume)?;
+                }
+            }
+
+            DatasetAppend::TemporalSlices(slices)
+        }
+        AppendDataset::GdalMetaDataRegular(AppendGdalMetaDataRegular { data_time_end }) => {
+            DatasetAppend::DataTimeEnd(data_time_end.into())
+        }
+        AppendDataset::OgrMetaData(AppendOgrMetaData {
+            upload,
+            main_file,
+            layer_name,
+        }) => {
+            let upload = db.load_upload(upload).await?;
+            let file_path = path_with_base_path(&upload.id.root_path()?, Path::new(&main_file))?;
+
+            DatasetAppend::OgrLayer {
+                file_path,
+                layer_name,
+            }
+        }
+    };
+
+    append_to_dataset(&session_ctx, dataset_id, append).await?;
+
+    Ok(HttpResponse::Ok())
+}
+
+fn adjust_temporal_slices_path<A: AdjustFilePath>(
+    slices: &mut [GdalLoadingInfoTemporalSlice],
+    adjust: &A,
+) -> Result<()> {
+    for slice in slices {
+        if let Some(ref mut params) = slice.params {
+            params.file_path = adjust.adjust_file_path(&params.file_path)?;
+        }
+    }
+    Ok(())
+}
+
 /// Creates a new dataset referencing files. Users can reference previously uploaded files. Admins can reference files from a volume.
 #[utoipa::path(
     tag = "Datasets",
@@ -2772,4 +2912,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[ge_context::test()]
+    async fn it_appends_to_dataset(app_ctx: PostgresContext<NoTls>) -> Result<()> {
+        let session_id = app_ctx.default_session_id().await;
+
+        let ctx = app_ctx.default_session_context().await?;
+
+        let (dataset_id, dataset_name) = add_ndvi_to_datasets(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/dataset/{dataset_name}/append"))
+            .append_header((header::CONTENT_LENGTH, 0))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .set_json(json!({
+                "type": "GdalMetaDataList",
+                "dataPath": {
+                    "volume": "test_data"
+                },
+                "params": []
+            }));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 400);
+        assert!(read_body_string(res).await.contains("InvalidDatasetAppend"));
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/dataset/{dataset_name}/append"))
+            .append_header((header::CONTENT_LENGTH, 0))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .set_json(json!({
+                "type": "GdalMetaDataRegular",
+                "dataTimeEnd": 1_409_529_600_000_i64 // 2014-09-01
+            }));
+        let res = send_test_request(req, app_ctx).await;
+
+        let res_status = res.status();
+        assert_eq!(res_status, 200, "{}", read_body_string(res).await);
+
+        let crate::datasets::storage::MetaDataDefinition::GdalMetaDataRegular(meta_data) =
+            ctx.db().load_loading_info(&dataset_id).await?
+        else {
+            panic!("expected GdalMetaDataRegular");
+        };
+
+        let expected_time = geoengine_datatypes::primitives::TimeInterval::new_unchecked(
+            1_388_534_400_000, // 2014-01-01
+            1_409_529_600_000,
+        );
+        assert_eq!(meta_data.data_time, expected_time);
+        assert_eq!(meta_data.result_descriptor.time, Some(expected_time));
+
+        Ok(())
+    }
 }
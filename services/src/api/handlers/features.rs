@@ -0,0 +1,677 @@
+use crate::api::handlers::reproject_vector_if_necessary;
+use crate::api::handlers::wfs::{project_features, vector_stream_to_geojson};
+use crate::api::ogc::features::request::GetItems;
+use crate::api::ogc::features::response::{
+    Collection, Collections, Conformance, Extent, Items, LandingPage, Link, CRS84, MIME_GEO_JSON,
+};
+use crate::api::ogc::util::{ogc_endpoint_url, OgcProtocol};
+use crate::api::ogc::wfs::filter::resolve_property_name;
+use crate::api::ogc::wfs::request::ResultType;
+use crate::contexts::{ApplicationContext, SessionContext};
+use crate::error::{self, Result};
+use crate::util::config;
+use crate::util::server::{connection_closed, CacheControlHeader};
+use crate::workflows::registry::WorkflowRegistry;
+use crate::workflows::workflow::WorkflowId;
+use actix_web::{web, FromRequest, HttpRequest, HttpResponse};
+use geoengine_datatypes::primitives::{
+    ColumnSelection, SpatialResolution, TimeInstance, VectorQueryRectangle,
+};
+use geoengine_datatypes::spatial_reference::SpatialReference;
+use geoengine_operators::engine::{
+    InitializedVectorOperator, TypedVectorQueryProcessor, VectorOperator, VectorResultDescriptor,
+    WorkflowOperatorPath,
+};
+use reqwest::Url;
+use snafu::{ensure, ResultExt};
+use std::time::Duration;
+
+pub(crate) fn init_features_routes<C>(cfg: &mut web::ServiceConfig)
+where
+    C: ApplicationContext,
+    C::Session: FromRequest,
+{
+    cfg.service(web::resource("/features/{workflow}").route(web::get().to(landing_page_handler)))
+        .service(
+            web::resource("/features/{workflow}/conformance")
+                .route(web::get().to(conformance_handler)),
+        )
+        .service(
+            web::resource("/features/{workflow}/collections")
+                .route(web::get().to(collections_handler::<C>)),
+        )
+        .service(
+            web::resource("/features/{workflow}/collections/{collection}")
+                .route(web::get().to(collection_handler::<C>)),
+        )
+        .service(
+            web::resource("/features/{workflow}/collections/{collection}/items")
+                .route(web::get().to(items_handler::<C>)),
+        );
+}
+
+fn features_url(workflow: WorkflowId) -> Result<Url> {
+    let web_config = crate::util::config::get_config_element::<crate::util::config::Web>()?;
+    let base = web_config.api_url()?;
+
+    ogc_endpoint_url(&base, OgcProtocol::Features, workflow)
+}
+
+fn collection_url(workflow: WorkflowId) -> Result<Url> {
+    let base = features_url(workflow)?;
+
+    Url::parse(&format!("{base}/collections/{workflow}")).map_err(Into::into)
+}
+
+/// Get the OGC API – Features landing page of a workflow
+#[utoipa::path(
+    tag = "OGC API Features",
+    get,
+    path = "/features/{workflow}",
+    responses(
+        (status = 200, description = "OK", body = LandingPage),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+    ),
+)]
+#[allow(clippy::unused_async)] // the function signature of request handlers requires it
+async fn landing_page_handler(workflow: web::Path<WorkflowId>) -> Result<HttpResponse> {
+    let workflow = workflow.into_inner();
+    let base = features_url(workflow)?;
+
+    Ok(HttpResponse::Ok().json(LandingPage {
+        title: format!("Workflow {workflow}"),
+        description: format!("Access to the features of workflow {workflow}"),
+        links: vec![
+            Link::new(
+                base.to_string(),
+                "self",
+                mime::APPLICATION_JSON.as_ref(),
+                "This document",
+            ),
+            Link::new(
+                format!("{base}/conformance"),
+                "conformance",
+                mime::APPLICATION_JSON.as_ref(),
+                "Conformance classes implemented by this server",
+            ),
+            Link::new(
+                format!("{base}/collections"),
+                "data",
+                mime::APPLICATION_JSON.as_ref(),
+                "Feature collections",
+            ),
+        ],
+    }))
+}
+
+/// Get the OGC API – Features conformance classes
+#[utoipa::path(
+    tag = "OGC API Features",
+    get,
+    path = "/features/{workflow}/conformance",
+    responses(
+        (status = 200, description = "OK", body = Conformance),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+    ),
+)]
+#[allow(clippy::unused_async)] // the function signature of request handlers requires it
+async fn conformance_handler(_workflow: web::Path<WorkflowId>) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(Conformance::default()))
+}
+
+/// Get the feature collections of a workflow. This is only the workflow itself.
+#[utoipa::path(
+    tag = "OGC API Features",
+    get,
+    path = "/features/{workflow}/collections",
+    responses(
+        (status = 200, description = "OK", body = Collections),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn collections_handler<C: ApplicationContext>(
+    workflow: web::Path<WorkflowId>,
+    app_ctx: web::Data<C>,
+    session: C::Session,
+) -> Result<HttpResponse> {
+    let workflow = workflow.into_inner();
+    let ctx = app_ctx.session_context(session);
+
+    let (_, initialized) = initialize_workflow(&ctx, workflow).await?;
+
+    let base = features_url(workflow)?;
+
+    Ok(HttpResponse::Ok().json(Collections {
+        links: vec![Link::new(
+            format!("{base}/collections"),
+            "self",
+            mime::APPLICATION_JSON.as_ref(),
+            "This document",
+        )],
+        collections: vec![collection(workflow, initialized.result_descriptor())?],
+    }))
+}
+
+/// Get the feature collection of a workflow
+#[utoipa::path(
+    tag = "OGC API Features",
+    get,
+    path = "/features/{workflow}/collections/{collection}",
+    responses(
+        (status = 200, description = "OK", body = Collection),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+        ("collection" = String, description = "Collection id, must equal the workflow id"),
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn collection_handler<C: ApplicationContext>(
+    path: web::Path<(WorkflowId, String)>,
+    app_ctx: web::Data<C>,
+    session: C::Session,
+) -> Result<HttpResponse> {
+    let (workflow, collection_id) = path.into_inner();
+    ensure_collection(workflow, collection_id)?;
+
+    let ctx = app_ctx.session_context(session);
+
+    let (_, initialized) = initialize_workflow(&ctx, workflow).await?;
+
+    Ok(HttpResponse::Ok().json(collection(workflow, initialized.result_descriptor())?))
+}
+
+/// Get the features of a workflow as `GeoJSON` in CRS84
+#[utoipa::path(
+    tag = "OGC API Features",
+    get,
+    path = "/features/{workflow}/collections/{collection}/items",
+    responses(
+        (status = 200, description = "OK", content_type = "application/geo+json", body = Items),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+        ("collection" = String, description = "Collection id, must equal the workflow id"),
+        GetItems
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn items_handler<C: ApplicationContext>(
+    req: HttpRequest,
+    path: web::Path<(WorkflowId, String)>,
+    request: web::Query<GetItems>,
+    app_ctx: web::Data<C>,
+    session: C::Session,
+) -> Result<HttpResponse> {
+    let (workflow, collection_id) = path.into_inner();
+    ensure_collection(workflow, collection_id)?;
+
+    let request = request.into_inner();
+
+    // the items are served with the same timeout as the WFS
+    let conn_closed = connection_closed(
+        &req,
+        config::get_config_element::<config::Wfs>()?
+            .request_timeout_seconds
+            .map(Duration::from_secs),
+    );
+
+    let ctx = app_ctx.session_context(session);
+
+    let (operator, initialized) = initialize_workflow(&ctx, workflow).await?;
+
+    let workflow_spatial_ref: Option<SpatialReference> =
+        initialized.result_descriptor().spatial_reference.into();
+    let workflow_spatial_ref = workflow_spatial_ref.ok_or(error::Error::InvalidSpatialReference)?;
+
+    // features are always served in CRS84
+    let request_spatial_ref = SpatialReference::epsg_4326();
+
+    let initialized = reproject_vector_if_necessary(
+        operator,
+        initialized,
+        workflow_spatial_ref,
+        request_spatial_ref,
+    )?;
+
+    let property_names = request
+        .properties
+        .as_ref()
+        .map(|property_names| {
+            property_names
+                .iter()
+                .map(|property_name| {
+                    resolve_property_name(property_name, &initialized.result_descriptor().columns)
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?;
+
+    let processor = initialized.query_processor().context(error::Operator)?;
+
+    let query_rect = VectorQueryRectangle {
+        spatial_bounds: request.bounding_box(),
+        time_interval: request.time_interval(),
+        // TODO: find reasonable default
+        spatial_resolution: SpatialResolution::zero_point_one(),
+        attributes: ColumnSelection::all(),
+    };
+    let query_ctx = ctx.query_context()?;
+
+    let result_type = ResultType::Results;
+
+    let (features, number_matched, cache_hint) = match processor {
+        TypedVectorQueryProcessor::Data(p) => {
+            vector_stream_to_geojson(p, query_rect, query_ctx, None, result_type, conn_closed).await
+        }
+        TypedVectorQueryProcessor::MultiPoint(p) => {
+            vector_stream_to_geojson(p, query_rect, query_ctx, None, result_type, conn_closed).await
+        }
+        TypedVectorQueryProcessor::MultiLineString(p) => {
+            vector_stream_to_geojson(p, query_rect, query_ctx, None, result_type, conn_closed).await
+        }
+        TypedVectorQueryProcessor::MultiPolygon(p) => {
+            vector_stream_to_geojson(p, query_rect, query_ctx, None, result_type, conn_closed).await
+        }
+    }?;
+
+    let limit = request.limit() as usize;
+    let offset = request.offset() as usize;
+
+    let mut features: Vec<serde_json::Value> =
+        features.into_iter().skip(offset).take(limit).collect();
+
+    if let Some(property_names) = &property_names {
+        project_features(&mut features, property_names);
+    }
+
+    let items_url = Url::parse(&format!("{}/items", collection_url(workflow)?))?;
+    let page_href = |offset: usize| page_url(&items_url, req.query_string(), offset, limit);
+
+    let mut links = vec![
+        Link::new(page_href(offset), "self", MIME_GEO_JSON, "This document"),
+        Link::new(
+            collection_url(workflow)?.to_string(),
+            "collection",
+            mime::APPLICATION_JSON.as_ref(),
+            "The feature collection",
+        ),
+    ];
+    if offset + limit < number_matched {
+        links.push(Link::new(
+            page_href(offset + limit),
+            "next",
+            MIME_GEO_JSON,
+            "Next page",
+        ));
+    }
+    if offset > 0 {
+        links.push(Link::new(
+            page_href(offset.saturating_sub(limit)),
+            "prev",
+            MIME_GEO_JSON,
+            "Previous page",
+        ));
+    }
+
+    Ok(HttpResponse::Ok()
+        .append_header(cache_hint.cache_control_header())
+        .content_type(MIME_GEO_JSON)
+        .json(Items {
+            collection_type: "FeatureCollection".to_string(),
+            number_matched,
+            number_returned: features.len(),
+            features,
+            time_stamp: TimeInstance::now().as_datetime_string(),
+            links,
+        }))
+}
+
+fn ensure_collection(endpoint: WorkflowId, collection: String) -> Result<()> {
+    ensure!(
+        endpoint.to_string() == collection,
+        error::FeaturesEndpointCollectionMissmatch {
+            endpoint,
+            collection
+        }
+    );
+
+    Ok(())
+}
+
+async fn initialize_workflow<C: SessionContext>(
+    ctx: &C,
+    workflow: WorkflowId,
+) -> Result<(Box<dyn VectorOperator>, Box<dyn InitializedVectorOperator>)> {
+    let workflow = ctx.db().load_workflow(&workflow).await?;
+
+    let operator = workflow.operator.get_vector().context(error::Operator)?;
+
+    let execution_context = ctx.execution_context()?;
+
+    let initialized = operator
+        .clone()
+        .initialize(WorkflowOperatorPath::initialize_root(), &execution_context)
+        .await
+        .context(error::Operator)?;
+
+    Ok((operator, initialized))
+}
+
+fn collection(
+    workflow: WorkflowId,
+    result_descriptor: &VectorResultDescriptor,
+) -> Result<Collection> {
+    let collection_url = collection_url(workflow)?;
+
+    let storage_crs: Option<SpatialReference> = result_descriptor.spatial_reference.into();
+    let storage_crs = storage_crs.map_or_else(
+        || CRS84.to_string(),
+        |spatial_reference| {
+            format!(
+                "http://www.opengis.net/def/crs/{}/0/{}",
+                spatial_reference.authority(),
+                spatial_reference.code()
+            )
+        },
+    );
+
+    Ok(Collection {
+        id: workflow.to_string(),
+        title: format!("Workflow {workflow}"),
+        extent: Extent::from(result_descriptor),
+        item_type: "feature".to_string(),
+        crs: vec![CRS84.to_string()],
+        storage_crs,
+        links: vec![
+            Link::new(
+                collection_url.to_string(),
+                "self",
+                mime::APPLICATION_JSON.as_ref(),
+                "This document",
+            ),
+            Link::new(
+                format!("{collection_url}/items"),
+                "items",
+                MIME_GEO_JSON,
+                "The features of the collection",
+            ),
+        ],
+    })
+}
+
+/// Creates the url of a page of items, keeping all other query parameters of the request
+fn page_url(items_url: &Url, query: &str, offset: usize, limit: usize) -> String {
+    let mut url = items_url.clone();
+
+    {
+        let mut query_pairs = url.query_pairs_mut();
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            if key != "offset" && key != "limit" {
+                query_pairs.append_pair(&key, &value);
+            }
+        }
+        query_pairs.append_pair("limit", &limit.to_string());
+        query_pairs.append_pair("offset", &offset.to_string());
+    }
+
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::model::responses::ErrorResponse;
+    use crate::contexts::{PostgresContext, Session, SimpleApplicationContext};
+    use crate::ge_context;
+    use crate::util::tests::send_test_request;
+    use crate::workflows::workflow::Workflow;
+    use actix_web::http::header;
+    use actix_web::test;
+    use actix_web_httpauth::headers::authorization::Bearer;
+    use geoengine_datatypes::collections::MultiPointCollection;
+    use geoengine_datatypes::primitives::{CacheHint, FeatureData, MultiPoint, TimeInterval};
+    use geoengine_operators::mock::MockFeatureCollectionSource;
+    use serde_json::json;
+    use tokio_postgres::NoTls;
+
+    async fn register_test_workflow(app_ctx: &PostgresContext<NoTls>) -> WorkflowId {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]).unwrap(),
+            vec![
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20),
+                TimeInterval::new_unchecked(10, 20),
+            ],
+            [
+                (
+                    "name".to_string(),
+                    FeatureData::Text(vec![
+                        Some("Bello".to_string()),
+                        Some("Rex".to_string()),
+                        Some("Balu".to_string()),
+                        Some("Lassie".to_string()),
+                    ]),
+                ),
+                (
+                    "age".to_string(),
+                    FeatureData::NullableInt(vec![Some(2), None, Some(7), Some(4)]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let workflow = Workflow {
+            operator: MockFeatureCollectionSource::single(collection)
+                .boxed()
+                .into(),
+        };
+
+        ctx.db().register_workflow(workflow).await.unwrap()
+    }
+
+    async fn get_json(app_ctx: PostgresContext<NoTls>, uri: &str) -> (u16, serde_json::Value) {
+        let session_id = app_ctx
+            .default_session_context()
+            .await
+            .unwrap()
+            .session()
+            .id();
+
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx).await;
+
+        let status = res.status().as_u16();
+        (status, test::read_body_json(res).await)
+    }
+
+    #[ge_context::test]
+    async fn it_serves_the_landing_page_and_conformance(app_ctx: PostgresContext<NoTls>) {
+        let workflow_id = register_test_workflow(&app_ctx).await;
+
+        let (status, landing_page) =
+            get_json(app_ctx.clone(), &format!("/features/{workflow_id}")).await;
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            landing_page["links"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|link| link["rel"].as_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["self", "conformance", "data"]
+        );
+
+        let (status, conformance) =
+            get_json(app_ctx, &format!("/features/{workflow_id}/conformance")).await;
+
+        assert_eq!(status, 200);
+        assert_eq!(
+            conformance,
+            json!({
+                "conformsTo": [
+                    "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/core",
+                    "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/geojson",
+                ]
+            })
+        );
+    }
+
+    #[ge_context::test]
+    async fn it_serves_the_collections(app_ctx: PostgresContext<NoTls>) {
+        let workflow_id = register_test_workflow(&app_ctx).await;
+
+        let (status, collections) =
+            get_json(app_ctx, &format!("/features/{workflow_id}/collections")).await;
+
+        assert_eq!(status, 200);
+
+        let collection = &collections["collections"][0];
+        assert_eq!(collection["id"], json!(workflow_id.to_string()));
+        assert_eq!(collection["itemType"], json!("feature"));
+        assert_eq!(collection["crs"], json!([CRS84]));
+        assert_eq!(
+            collection["storageCrs"],
+            json!("http://www.opengis.net/def/crs/EPSG/0/4326")
+        );
+        assert_eq!(
+            collection["links"][1]["href"],
+            json!(format!(
+                "http://127.0.0.1:3030/api/features/{workflow_id}/collections/{workflow_id}/items"
+            ))
+        );
+    }
+
+    #[ge_context::test]
+    async fn it_pages_items(app_ctx: PostgresContext<NoTls>) {
+        let workflow_id = register_test_workflow(&app_ctx).await;
+
+        let (status, items) = get_json(
+            app_ctx,
+            &format!(
+                "/features/{workflow_id}/collections/{workflow_id}/items?bbox=-1,-1,4,4&limit=1&offset=1&properties=name"
+            ),
+        )
+        .await;
+
+        assert_eq!(status, 200);
+        assert_eq!(items["type"], json!("FeatureCollection"));
+        assert_eq!(items["numberMatched"], json!(4));
+        assert_eq!(items["numberReturned"], json!(1));
+        assert_eq!(
+            items["features"][0]["geometry"]["coordinates"],
+            json!([1.0, 1.0])
+        );
+        assert_eq!(items["features"][0]["properties"], json!({"name": "Rex"}));
+
+        let items_url = format!(
+            "http://127.0.0.1:3030/api/features/{workflow_id}/collections/{workflow_id}/items"
+        );
+        assert_eq!(
+            items["links"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|link| (link["rel"].clone(), link["href"].clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    json!("self"),
+                    json!(format!(
+                        "{items_url}?bbox=-1%2C-1%2C4%2C4&properties=name&limit=1&offset=1"
+                    ))
+                ),
+                (
+                    json!("collection"),
+                    json!(format!(
+                        "http://127.0.0.1:3030/api/features/{workflow_id}/collections/{workflow_id}"
+                    ))
+                ),
+                (
+                    json!("next"),
+                    json!(format!(
+                        "{items_url}?bbox=-1%2C-1%2C4%2C4&properties=name&limit=1&offset=2"
+                    ))
+                ),
+                (
+                    json!("prev"),
+                    json!(format!(
+                        "{items_url}?bbox=-1%2C-1%2C4%2C4&properties=name&limit=1&offset=0"
+                    ))
+                ),
+            ]
+        );
+    }
+
+    #[ge_context::test]
+    async fn it_filters_items_by_datetime(app_ctx: PostgresContext<NoTls>) {
+        let workflow_id = register_test_workflow(&app_ctx).await;
+
+        let (status, items) = get_json(
+            app_ctx,
+            &format!(
+                "/features/{workflow_id}/collections/{workflow_id}/items?datetime=1970-01-01T00:00:00.015Z/.."
+            ),
+        )
+        .await;
+
+        assert_eq!(status, 200);
+        assert_eq!(items["numberMatched"], json!(2));
+        assert_eq!(
+            items["features"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|feature| feature["properties"]["name"].clone())
+                .collect::<Vec<_>>(),
+            vec![json!("Balu"), json!("Lassie")]
+        );
+    }
+
+    #[ge_context::test]
+    async fn it_rejects_other_collections(app_ctx: PostgresContext<NoTls>) {
+        let workflow_id = register_test_workflow(&app_ctx).await;
+
+        let session_id = app_ctx
+            .default_session_context()
+            .await
+            .unwrap()
+            .session()
+            .id();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/features/{workflow_id}/collections/foo/items"))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx).await;
+
+        ErrorResponse::assert(
+            res,
+            400,
+            "FeaturesEndpointCollectionMissmatch",
+            &format!("OGC API Features endpoint {workflow_id} must match collection foo"),
+        )
+        .await;
+    }
+}
@@ -1,18 +1,29 @@
 use crate::contexts::SessionId;
-use crate::error::{Error, Result};
+use crate::error::{self, Error, Result};
 use actix_web::http::header;
 use actix_web::HttpRequest;
 use actix_web_httpauth::headers::authorization::{Bearer, Scheme};
+use geoengine_datatypes::spatial_reference::SpatialReference;
+use geoengine_operators::engine::{
+    CanonicOperatorName, InitializedVectorOperator, SingleRasterOrVectorSource, VectorOperator,
+};
+use geoengine_operators::processing::{
+    InitializedVectorReprojection, InterpolationMethod, Reprojection, ReprojectionParams,
+};
+use geoengine_operators::util::input::RasterOrVectorOperator;
+use snafu::ResultExt;
 use std::str::FromStr;
 
 pub mod datasets;
 pub mod ebv;
+pub mod features;
 pub mod layers;
 pub mod plots;
 pub mod projects;
 pub mod session;
 pub mod spatial_references;
 pub mod tasks;
+pub mod tiles;
 pub mod upload;
 pub mod wcs;
 pub mod wfs;
@@ -33,3 +44,44 @@ pub fn get_token(req: &HttpRequest) -> Result<SessionId> {
         source: Box::new(Error::InvalidUuid),
     })
 }
+
+/// Injects a reprojection into the initialized vector workflow if the requested spatial reference differs from the workflow's
+pub(crate) fn reproject_vector_if_necessary(
+    operator: Box<dyn VectorOperator>,
+    initialized: Box<dyn InitializedVectorOperator>,
+    workflow_spatial_ref: SpatialReference,
+    request_spatial_ref: SpatialReference,
+) -> Result<Box<dyn InitializedVectorOperator>> {
+    if request_spatial_ref == workflow_spatial_ref {
+        return Ok(initialized);
+    }
+
+    log::debug!(
+        "query srs: {}, workflow srs: {} --> injecting reprojection",
+        request_spatial_ref,
+        workflow_spatial_ref
+    );
+
+    let reprojection_params = ReprojectionParams {
+        target_spatial_reference: request_spatial_ref,
+        interpolation: InterpolationMethod::NearestNeighbor,
+    };
+
+    // create the reprojection operator in order to get the canonic operator name
+    let reprojected_workflow = Reprojection {
+        params: reprojection_params,
+        sources: SingleRasterOrVectorSource {
+            source: RasterOrVectorOperator::Vector(operator),
+        },
+    };
+
+    // create the inititalized operator directly, to avoid re-initializing everything
+    let ivp = InitializedVectorReprojection::try_new_with_input(
+        CanonicOperatorName::from(&reprojected_workflow),
+        reprojection_params,
+        initialized,
+    )
+    .context(error::Operator)?;
+
+    Ok(Box::new(ivp))
+}
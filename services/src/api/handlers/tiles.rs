@@ -0,0 +1,326 @@
+use crate::api::handlers::reproject_vector_if_necessary;
+use crate::api::ogc::tiles::request::GetVectorTile;
+use crate::contexts::{ApplicationContext, SessionContext};
+use crate::error::{self, Result};
+use crate::util::config;
+use crate::util::server::{connection_closed, CacheControlHeader};
+use crate::workflows::registry::WorkflowRegistry;
+use crate::workflows::workflow::WorkflowId;
+use actix_web::{web, FromRequest, HttpRequest, HttpResponse};
+use futures::future::BoxFuture;
+use futures_util::TryStreamExt;
+use geoengine_datatypes::collections::{FeatureCollection, IntoGeometryIterator};
+use geoengine_datatypes::operations::mvt::{MvtLayerBuilder, ToMvtGeometry, WebMercatorTile};
+use geoengine_datatypes::primitives::{CacheHint, ColumnSelection, Geometry, VectorQueryRectangle};
+use geoengine_datatypes::spatial_reference::{SpatialReference, SpatialReferenceAuthority};
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use geoengine_operators::engine::{
+    QueryContext, QueryProcessor, TypedVectorQueryProcessor, VectorQueryProcessor,
+    WorkflowOperatorPath,
+};
+use geoengine_operators::util::abortable_query_execution;
+use snafu::ResultExt;
+use std::time::Duration;
+
+pub const MIME_MVT: &str = "application/vnd.mapbox-vector-tile";
+
+pub(crate) fn init_tile_routes<C>(cfg: &mut web::ServiceConfig)
+where
+    C: ApplicationContext,
+    C::Session: FromRequest,
+{
+    cfg.service(
+        web::resource("/tiles/{workflow}/{z}/{x}/{y}")
+            .route(web::get().to(vector_tile_handler::<C>)),
+    );
+}
+
+/// Get a Mapbox Vector Tile of a vector workflow in the `WebMercatorQuad` tile matrix set.
+///
+/// The features are clipped to the tile and simplified according to the zoom level.
+/// The columns of the features are encoded as properties of a layer that is named after the workflow id.
+#[utoipa::path(
+    tag = "OGC API Tiles",
+    get,
+    path = "/tiles/{workflow}/{z}/{x}/{y}",
+    responses(
+        (status = 200, description = "OK", content_type = "application/vnd.mapbox-vector-tile", body = Vec<u8>),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+        ("z" = u8, description = "Zoom level"),
+        ("x" = u32, description = "Tile column"),
+        ("y" = u32, description = "Tile row, counted from the top"),
+        GetVectorTile
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn vector_tile_handler<C: ApplicationContext>(
+    req: HttpRequest,
+    path: web::Path<(WorkflowId, u8, u32, u32)>,
+    request: web::Query<GetVectorTile>,
+    app_ctx: web::Data<C>,
+    session: C::Session,
+) -> Result<HttpResponse> {
+    let (workflow_id, z, x, y) = path.into_inner();
+    let tile = WebMercatorTile::new(z, x, y)?;
+
+    // tiles are served with the same timeout as the WFS
+    let conn_closed = connection_closed(
+        &req,
+        config::get_config_element::<config::Wfs>()?
+            .request_timeout_seconds
+            .map(Duration::from_secs),
+    );
+
+    let ctx = app_ctx.session_context(session);
+
+    let workflow = ctx.db().load_workflow(&workflow_id).await?;
+
+    let operator = workflow.operator.get_vector().context(error::Operator)?;
+
+    let execution_context = ctx.execution_context()?;
+
+    let initialized = operator
+        .clone()
+        .initialize(WorkflowOperatorPath::initialize_root(), &execution_context)
+        .await
+        .context(error::Operator)?;
+
+    let workflow_spatial_ref: Option<SpatialReference> =
+        initialized.result_descriptor().spatial_reference.into();
+    let workflow_spatial_ref = workflow_spatial_ref.ok_or(error::Error::InvalidSpatialReference)?;
+
+    // vector tiles are always served in web mercator
+    let request_spatial_ref = SpatialReference::new(SpatialReferenceAuthority::Epsg, 3857);
+
+    let initialized = reproject_vector_if_necessary(
+        operator,
+        initialized,
+        workflow_spatial_ref,
+        request_spatial_ref,
+    )?;
+
+    let processor = initialized.query_processor().context(error::Operator)?;
+
+    let query_rect = VectorQueryRectangle {
+        spatial_bounds: tile.buffered_bounds(),
+        time_interval: request.time_interval(),
+        spatial_resolution: tile.spatial_resolution(),
+        attributes: ColumnSelection::all(),
+    };
+    let query_ctx = ctx.query_context()?;
+
+    let layer = MvtLayerBuilder::new(workflow_id.to_string(), tile);
+
+    let (layer, cache_hint) = match processor {
+        TypedVectorQueryProcessor::Data(_) => {
+            return Err(error::Error::VectorTilesRequireGeometries);
+        }
+        TypedVectorQueryProcessor::MultiPoint(p) => {
+            vector_stream_to_mvt_layer(p, query_rect, query_ctx, layer, conn_closed).await
+        }
+        TypedVectorQueryProcessor::MultiLineString(p) => {
+            vector_stream_to_mvt_layer(p, query_rect, query_ctx, layer, conn_closed).await
+        }
+        TypedVectorQueryProcessor::MultiPolygon(p) => {
+            vector_stream_to_mvt_layer(p, query_rect, query_ctx, layer, conn_closed).await
+        }
+    }?;
+
+    Ok(HttpResponse::Ok()
+        .append_header(cache_hint.cache_control_header())
+        .content_type(MIME_MVT)
+        .body(layer.into_tile_bytes()))
+}
+
+async fn vector_stream_to_mvt_layer<G, C: QueryContext + 'static>(
+    processor: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    query_rect: VectorQueryRectangle,
+    mut query_ctx: C,
+    layer: MvtLayerBuilder,
+    conn_closed: BoxFuture<'_, ()>,
+) -> Result<(MvtLayerBuilder, CacheHint)>
+where
+    G: Geometry + ArrowTyped + 'static,
+    for<'c> FeatureCollection<G>: IntoGeometryIterator<'c>,
+    for<'c> <FeatureCollection<G> as IntoGeometryIterator<'c>>::GeometryType: ToMvtGeometry,
+{
+    let query_abort_trigger = query_ctx.abort_trigger()?;
+
+    let stream = processor.query(query_rect, &query_ctx).await?;
+
+    let future: BoxFuture<geoengine_operators::util::Result<(MvtLayerBuilder, CacheHint)>> =
+        Box::pin(stream.try_fold(
+            (layer, CacheHint::max_duration()),
+            |(mut layer, mut cache_hint), collection| async move {
+                cache_hint.merge_with(&collection.cache_hint);
+
+                layer.add_collection(&collection)?;
+
+                Ok((layer, cache_hint))
+            },
+        ));
+
+    let result = abortable_query_execution(future, conn_closed, query_abort_trigger).await?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::model::responses::ErrorResponse;
+    use crate::contexts::{PostgresContext, Session, SimpleApplicationContext};
+    use crate::ge_context;
+    use crate::util::tests::send_test_request;
+    use crate::workflows::workflow::Workflow;
+    use actix_web::http::header;
+    use actix_web::test;
+    use actix_web_httpauth::headers::authorization::Bearer;
+    use geoengine_datatypes::collections::{DataCollection, MultiPointCollection};
+    use geoengine_datatypes::primitives::{FeatureData, MultiPoint, NoGeometry, TimeInterval};
+    use geoengine_operators::engine::VectorOperator;
+    use geoengine_operators::mock::MockFeatureCollectionSource;
+    use tokio_postgres::NoTls;
+
+    async fn register_workflow(app_ctx: &PostgresContext<NoTls>, workflow: Workflow) -> WorkflowId {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+
+        ctx.db().register_workflow(workflow).await.unwrap()
+    }
+
+    async fn send_tile_request(
+        app_ctx: PostgresContext<NoTls>,
+        uri: &str,
+    ) -> actix_web::dev::ServiceResponse {
+        let session_id = app_ctx
+            .default_session_context()
+            .await
+            .unwrap()
+            .session()
+            .id();
+
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        send_test_request(req, app_ctx).await
+    }
+
+    #[ge_context::test]
+    async fn it_serves_vector_tiles(app_ctx: PostgresContext<NoTls>) {
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![(-90.0, 45.0), (90.0, 45.0), (90.0, -45.0)]).unwrap(),
+            vec![TimeInterval::new_unchecked(0, 10); 3],
+            [(
+                "name".to_string(),
+                FeatureData::Text(vec![
+                    Some("Bello".to_string()),
+                    Some("Rex".to_string()),
+                    Some("Balu".to_string()),
+                ]),
+            )]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let workflow_id = register_workflow(
+            &app_ctx,
+            Workflow {
+                operator: MockFeatureCollectionSource::single(collection)
+                    .boxed()
+                    .into(),
+            },
+        )
+        .await;
+
+        let res = send_tile_request(app_ctx.clone(), &format!("/tiles/{workflow_id}/0/0/0")).await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/vnd.mapbox-vector-tile"
+        );
+
+        let bytes = test::read_body(res).await;
+
+        // the tile consists of a single layer, named after the workflow
+        assert_eq!(bytes[0], 0x1A);
+        assert!(bytes
+            .windows(workflow_id.to_string().len())
+            .any(|window| window == workflow_id.to_string().as_bytes()));
+        assert!(bytes.windows(4).any(|window| window == b"name"));
+        assert!(bytes.windows(5).any(|window| window == b"Bello"));
+
+        // the upper left tile of zoom level 1 only contains the first point
+        let res = send_tile_request(app_ctx, &format!("/tiles/{workflow_id}/1/0/0")).await;
+
+        assert_eq!(res.status(), 200);
+
+        let bytes = test::read_body(res).await;
+
+        assert!(bytes.windows(5).any(|window| window == b"Bello"));
+        assert!(!bytes.windows(3).any(|window| window == b"Rex"));
+        assert!(!bytes.windows(4).any(|window| window == b"Balu"));
+    }
+
+    #[ge_context::test]
+    async fn it_rejects_invalid_tiles(app_ctx: PostgresContext<NoTls>) {
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![(0.0, 0.0)]).unwrap(),
+            vec![TimeInterval::default()],
+            Default::default(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let workflow_id = register_workflow(
+            &app_ctx,
+            Workflow {
+                operator: MockFeatureCollectionSource::single(collection)
+                    .boxed()
+                    .into(),
+            },
+        )
+        .await;
+
+        let res = send_tile_request(app_ctx, &format!("/tiles/{workflow_id}/1/2/0")).await;
+
+        assert_eq!(res.status(), 400);
+    }
+
+    #[ge_context::test]
+    async fn it_rejects_data_workflows(app_ctx: PostgresContext<NoTls>) {
+        let collection = DataCollection::from_data(
+            vec![NoGeometry; 1],
+            vec![TimeInterval::default()],
+            Default::default(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let workflow_id = register_workflow(
+            &app_ctx,
+            Workflow {
+                operator: MockFeatureCollectionSource::single(collection)
+                    .boxed()
+                    .into(),
+            },
+        )
+        .await;
+
+        let res = send_tile_request(app_ctx, &format!("/tiles/{workflow_id}/0/0/0")).await;
+
+        ErrorResponse::assert(
+            res,
+            400,
+            "VectorTilesRequireGeometries",
+            "Vector tiles can only be created for workflows with geometries",
+        )
+        .await;
+    }
+}
@@ -1,20 +1,35 @@
 use crate::api::model::responses::IdResponse;
 use crate::contexts::{ApplicationContext, SessionContext};
-use crate::datasets::upload::{FileId, FileUpload, Upload, UploadDb, UploadId, UploadRootPath};
+use crate::datasets::upload::{
+    FileId, FileUpload, ResumableFileUpload, ResumableUpload, Upload, UploadDb, UploadId,
+    UploadRootPath,
+};
 use crate::error::Result;
 use crate::error::{self, Error};
+use crate::util::config::{self, get_config_element};
+use crate::util::extractors::ValidatedJson;
 use crate::util::path_with_base_path;
 use actix_multipart::Multipart;
-use actix_web::{web, FromRequest, Responder};
-use futures::StreamExt;
+use actix_web::{web, FromRequest, HttpRequest, HttpResponse, Responder};
+use futures::channel::mpsc;
+use futures::{FutureExt, StreamExt};
 use gdal::vector::LayerAccess;
 use geoengine_datatypes::util::Identifier;
 use geoengine_operators::util::gdal::gdal_open_dataset;
 use serde::{Deserialize, Serialize};
-use snafu::ResultExt;
+use sha2::{Digest, Sha256};
+use snafu::{ensure, ResultExt};
+use std::collections::HashSet;
+use std::io::Read;
 use std::path::Path;
 use tokio::{fs, io::AsyncWriteExt};
 use utoipa::{ToResponse, ToSchema};
+use validator::{Validate, ValidationError};
+use zip::read::ZipFile;
+use zip::ZipArchive;
+
+/// The header of a chunk request that states the position of the chunk within the file
+const UPLOAD_OFFSET_HEADER: &str = "Upload-Offset";
 
 pub(crate) fn init_upload_routes<C>(cfg: &mut web::ServiceConfig)
 where
@@ -22,6 +37,23 @@ where
     C::Session: FromRequest,
 {
     cfg.service(web::resource("/upload").route(web::post().to(upload_handler::<C>)))
+        .service(
+            web::resource("/upload/resumable")
+                .route(web::post().to(create_resumable_upload_handler::<C>)),
+        )
+        .service(
+            web::resource("/upload/resumable/{upload_id}")
+                .route(web::get().to(resumable_upload_status_handler::<C>))
+                .route(web::delete().to(delete_resumable_upload_handler::<C>)),
+        )
+        .service(
+            web::resource("/upload/resumable/{upload_id}/files/{file_name}")
+                .route(web::patch().to(upload_chunk_handler::<C>)),
+        )
+        .service(
+            web::resource("/upload/resumable/{upload_id}/finish")
+                .route(web::post().to(finish_resumable_upload_handler::<C>)),
+        )
         .service(
             web::resource("/uploads/{upload_id}/files")
                 .route(web::get().to(list_upload_files_handler)),
@@ -55,6 +87,7 @@ impl<'a> ToSchema<'a> for FileUploadRequest {
 }
 
 /// Uploads files.
+/// Zip archives are extracted into the upload.
 #[utoipa::path(
     tag = "Uploads",
     post,
@@ -70,15 +103,42 @@ impl<'a> ToSchema<'a> for FileUploadRequest {
 async fn upload_handler<C: ApplicationContext>(
     session: C::Session,
     app_ctx: web::Data<C>,
-    mut body: Multipart,
+    body: Multipart,
 ) -> Result<web::Json<IdResponse<UploadId>>> {
+    let db = app_ctx.session_context(session).db();
+
+    let quota = UploadQuota::of_user(&db).await?;
+
     let upload_id = UploadId::new();
 
     let root = upload_id.root_path()?;
 
     fs::create_dir_all(&root).await.context(error::Io)?;
 
+    let files = match receive_multipart_files(&root, body, quota).await {
+        Ok(files) => files,
+        Err(error) => {
+            fs::remove_dir_all(&root).await.context(error::Io)?;
+            return Err(error);
+        }
+    };
+
+    db.create_upload(Upload {
+        id: upload_id,
+        files,
+    })
+    .await?;
+
+    Ok(web::Json(IdResponse::from(upload_id)))
+}
+
+async fn receive_multipart_files(
+    root: &Path,
+    mut body: Multipart,
+    quota: Option<UploadQuota>,
+) -> Result<Vec<FileUpload>> {
     let mut files: Vec<FileUpload> = vec![];
+    let mut upload_byte_size = 0_u64;
     while let Some(item) = body.next().await {
         let mut field = item?;
         let file_name = field
@@ -95,6 +155,10 @@ async fn upload_handler<C: ApplicationContext>(
         let mut byte_size = 0_u64;
         while let Some(chunk) = field.next().await {
             let bytes = chunk?;
+            upload_byte_size += bytes.len() as u64;
+            if let Some(quota) = quota {
+                quota.ensure(upload_byte_size)?;
+            }
             file.write_all(&bytes).await.context(error::Io)?;
             byte_size += bytes.len() as u64;
         }
@@ -107,18 +171,535 @@ async fn upload_handler<C: ApplicationContext>(
         });
     }
 
-    app_ctx
+    let root = root.to_path_buf();
+    crate::util::spawn_blocking(move || extract_zip_archives(&root, files, quota)).await?
+}
+
+/// The limit of the size of the files a user may still upload
+#[derive(Debug, Clone, Copy)]
+struct UploadQuota {
+    quota: u64,
+    remaining: u64,
+}
+
+impl UploadQuota {
+    /// Returns `None` if the size of uploads is not limited
+    async fn of_user<D: UploadDb>(db: &D) -> Result<Option<Self>> {
+        let Some(quota) = get_config_element::<config::Upload>()?.user_quota_bytes else {
+            return Ok(None);
+        };
+
+        let used = db.used_upload_bytes().await?;
+
+        Ok(Some(Self {
+            quota,
+            remaining: quota.saturating_sub(used),
+        }))
+    }
+
+    /// Counts the bytes of an existing upload as available again, e.g., when its files are replaced
+    fn releasing(self, byte_size: u64) -> Self {
+        Self {
+            quota: self.quota,
+            remaining: self.remaining.saturating_add(byte_size),
+        }
+    }
+
+    fn ensure(self, byte_size: u64) -> Result<()> {
+        ensure!(
+            byte_size <= self.remaining,
+            error::UploadQuotaExceeded { quota: self.quota }
+        );
+        Ok(())
+    }
+}
+
+/// Replaces the zip archives among the files by their contents.
+/// Directories inside of archives are flattened, because datasets can only reference files at the top level of an upload.
+fn extract_zip_archives(
+    root: &Path,
+    files: Vec<FileUpload>,
+    quota: Option<UploadQuota>,
+) -> Result<Vec<FileUpload>> {
+    let (archives, mut files): (Vec<_>, Vec<_>) = files
+        .into_iter()
+        .partition(|file| is_zip_archive(&file.name));
+
+    let mut byte_size = files.iter().map(|file| file.byte_size).sum::<u64>();
+
+    for archive_file in archives {
+        let archive_path = root.join(&archive_file.name);
+        let mut archive = ZipArchive::new(std::fs::File::open(&archive_path).context(error::Io)?)
+            .context(error::UploadZipExtraction {
+            file_name: &archive_file.name,
+        })?;
+
+        for index in 0..archive.len() {
+            let mut entry = archive
+                .by_index(index)
+                .context(error::UploadZipExtraction {
+                    file_name: &archive_file.name,
+                })?;
+
+            let Some(file_name) = extracted_file_name(&entry) else {
+                continue;
+            };
+
+            ensure!(
+                !files.iter().any(|file| file.name == file_name),
+                error::DuplicateUploadFileName { file_name }
+            );
+
+            // the size is taken from the archive, so it is also enforced while extracting
+            let entry_size = entry.size();
+            byte_size += entry_size;
+            if let Some(quota) = quota {
+                quota.ensure(byte_size)?;
+            }
+
+            let mut file = std::fs::File::create(root.join(&file_name)).context(error::Io)?;
+            let written =
+                std::io::copy(&mut (&mut entry).take(entry_size), &mut file).context(error::Io)?;
+
+            files.push(FileUpload {
+                id: FileId::new(),
+                name: file_name,
+                byte_size: written,
+            });
+        }
+
+        std::fs::remove_file(&archive_path).context(error::Io)?;
+    }
+
+    Ok(files)
+}
+
+fn is_zip_archive(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// The name of the file that an archive entry is extracted to, `None` if the entry is skipped
+fn extracted_file_name(entry: &ZipFile) -> Option<String> {
+    if entry.is_dir() {
+        return None;
+    }
+
+    let path = entry.enclosed_name()?;
+
+    // resource forks of archives created on macOS
+    if path
+        .components()
+        .any(|component| component.as_os_str() == "__MACOSX")
+    {
+        return None;
+    }
+
+    path.file_name()?.to_str().map(ToString::to_string)
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema, Validate)]
+pub struct CreateResumableUpload {
+    #[validate(custom = "validate_resumable_upload_files")]
+    pub files: Vec<ResumableFileUpload>,
+}
+
+fn validate_resumable_upload_files(files: &[ResumableFileUpload]) -> Result<(), ValidationError> {
+    if files.is_empty() {
+        return Err(ValidationError::new("Upload must contain files"));
+    }
+
+    let mut file_names = HashSet::new();
+
+    for file in files {
+        if file.name.is_empty() || file.name.contains('/') || file.name.contains("..") {
+            return Err(ValidationError::new("Invalid upload file name"));
+        }
+
+        if !file_names.insert(&file.name) {
+            return Err(ValidationError::new("Duplicate upload file name"));
+        }
+
+        if let Some(sha256) = &file.sha256 {
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ValidationError::new("Invalid SHA-256 checksum"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates an upload whose files are sent in chunks, so that interrupted uploads can be resumed.
+///
+/// The chunks are sent via `PATCH /upload/resumable/{upload_id}/files/{file_name}`.
+/// Once all files are complete, the upload has to be finished via `POST /upload/resumable/{upload_id}/finish`.
+/// Only then it can be used like a regular upload.
+#[utoipa::path(
+    tag = "Uploads",
+    post,
+    path = "/upload/resumable",
+    request_body(content = CreateResumableUpload, example = json!({
+        "files": [{
+            "name": "ne_10m_ports.zip",
+            "byteSize": 27_156,
+            "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        }]
+    })),
+    responses(
+        (status = 200, response = crate::api::model::responses::IdResponse::<UploadId>),
+        (status = 413, description = "The upload exceeds the user's quota", body = crate::api::model::responses::ErrorResponse)
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn create_resumable_upload_handler<C: ApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    create: ValidatedJson<CreateResumableUpload>,
+) -> Result<web::Json<IdResponse<UploadId>>> {
+    let db = app_ctx.session_context(session).db();
+
+    let files = create.into_inner().files;
+
+    if let Some(quota) = UploadQuota::of_user(&db).await? {
+        quota.ensure(files.iter().map(|file| file.byte_size).sum())?;
+    }
+
+    let upload_id = UploadId::new();
+
+    let root = upload_id.root_path()?;
+
+    fs::create_dir_all(&root).await.context(error::Io)?;
+
+    // the number of received bytes of a file is its size on disk
+    for file in &files {
+        fs::File::create(root.join(&file.name))
+            .await
+            .context(error::Io)?;
+    }
+
+    db.create_resumable_upload(ResumableUpload {
+        id: upload_id,
+        files,
+    })
+    .await?;
+
+    Ok(web::Json(IdResponse::from(upload_id)))
+}
+
+#[derive(Deserialize, Serialize, ToSchema, ToResponse)]
+pub struct ResumableUploadStatus {
+    files: Vec<ResumableUploadFileStatus>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumableUploadFileStatus {
+    name: String,
+    byte_size: u64,
+    received: u64,
+}
+
+/// Retrieves how many bytes of each file of a resumable upload were received.
+#[utoipa::path(
+    tag = "Uploads",
+    get,
+    path = "/upload/resumable/{upload_id}",
+    responses(
+        (status = 200, body = ResumableUploadStatus,
+            example = json!({"files": [{"name": "ne_10m_ports.zip", "byteSize": 27_156, "received": 8_192}]}))
+    ),
+    params(
+        ("upload_id" = UploadId, description = "Upload id"),
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn resumable_upload_status_handler<C: ApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    upload_id: web::Path<UploadId>,
+) -> Result<web::Json<ResumableUploadStatus>> {
+    let upload = app_ctx
         .session_context(session)
         .db()
-        .create_upload(Upload {
-            id: upload_id,
-            files,
-        })
+        .load_resumable_upload(upload_id.into_inner())
         .await?;
 
+    let root = upload.id.root_path()?;
+
+    let mut files = Vec::with_capacity(upload.files.len());
+    for file in upload.files {
+        let received = fs::metadata(root.join(&file.name))
+            .await
+            .context(error::Io)?
+            .len();
+
+        files.push(ResumableUploadFileStatus {
+            name: file.name,
+            byte_size: file.byte_size,
+            received,
+        });
+    }
+
+    Ok(web::Json(ResumableUploadStatus { files }))
+}
+
+struct ResumableUploadChunk;
+
+impl<'a> ToSchema<'a> for ResumableUploadChunk {
+    fn schema() -> (&'a str, utoipa::openapi::RefOr<utoipa::openapi::Schema>) {
+        use utoipa::openapi::*;
+        (
+            "ResumableUploadChunk",
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)))
+                .into(),
+        )
+    }
+}
+
+/// Appends a chunk to a file of a resumable upload.
+///
+/// The `Upload-Offset` header must equal the number of bytes that were already received for the file.
+/// After an interrupted request, the offset to resume from can be retrieved via `GET /upload/resumable/{upload_id}`.
+#[utoipa::path(
+    tag = "Uploads",
+    patch,
+    path = "/upload/resumable/{upload_id}/files/{file_name}",
+    request_body(content = inline(ResumableUploadChunk), content_type = "application/offset+octet-stream"),
+    responses(
+        (status = 200, body = ResumableUploadFileStatus,
+            example = json!({"name": "ne_10m_ports.zip", "byteSize": 27_156, "received": 16_384})),
+        (status = 409, description = "The offset does not match the received bytes", body = crate::api::model::responses::ErrorResponse)
+    ),
+    params(
+        ("upload_id" = UploadId, description = "Upload id"),
+        ("file_name" = String, description = "File name"),
+        ("Upload-Offset" = u64, Header, description = "Position of the chunk within the file")
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn upload_chunk_handler<C: ApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    path: web::Path<(UploadId, String)>,
+    request: HttpRequest,
+    body: web::Payload,
+) -> Result<web::Json<ResumableUploadFileStatus>> {
+    let (upload_id, file_name) = path.into_inner();
+
+    let offset = request
+        .headers()
+        .get(UPLOAD_OFFSET_HEADER)
+        .and_then(|offset| offset.to_str().ok())
+        .and_then(|offset| offset.parse::<u64>().ok())
+        .ok_or(error::Error::MissingUploadOffset)?;
+
+    let db = app_ctx.session_context(session).db();
+
+    // the payload cannot be moved to the locked write, so its chunks are passed via a channel
+    let (chunks_tx, mut chunks) = mpsc::channel::<Result<web::Bytes>>(1);
+
+    // the upload is locked, so that concurrent requests cannot both pass the offset check
+    let write = db.lock_resumable_upload(upload_id, move |upload| {
+        async move {
+            let file_upload = upload
+                .files
+                .into_iter()
+                .find(|file| file.name == file_name)
+                .ok_or(error::Error::UnknownUploadFile { file_name })?;
+
+            let mut file = fs::OpenOptions::new()
+                .append(true)
+                .open(upload_id.root_path()?.join(&file_upload.name))
+                .await
+                .context(error::Io)?;
+
+            let mut received = file.metadata().await.context(error::Io)?.len();
+
+            ensure!(
+                offset == received,
+                error::UploadOffsetMismatch {
+                    file_name: file_upload.name,
+                    offset,
+                    received,
+                }
+            );
+
+            while let Some(chunk) = chunks.next().await {
+                let bytes = chunk?;
+                ensure!(
+                    received + bytes.len() as u64 <= file_upload.byte_size,
+                    error::UploadChunkExceedsFileSize {
+                        file_name: file_upload.name,
+                        byte_size: file_upload.byte_size,
+                    }
+                );
+                file.write_all(&bytes).await.context(error::Io)?;
+                received += bytes.len() as u64;
+            }
+            file.flush().await.context(error::Io)?;
+
+            Ok(ResumableUploadFileStatus {
+                name: file_upload.name,
+                byte_size: file_upload.byte_size,
+                received,
+            })
+        }
+        .boxed()
+    });
+
+    // forwarding stops early if the write fails and drops the receiver
+    let forward = body
+        .map(|chunk| Ok(chunk.map_err(error::Error::from)))
+        .forward(chunks_tx);
+
+    let (status, _) = futures::join!(write, forward);
+
+    Ok(web::Json(status?))
+}
+
+/// Finishes a resumable upload after all of its files were received.
+///
+/// The checksums of the files are verified. Files that do not match their checksum are discarded and must be sent again.
+/// Zip archives are extracted into the upload.
+/// Finishing an upload again, e.g., after a lost response, succeeds without changing it.
+#[utoipa::path(
+    tag = "Uploads",
+    post,
+    path = "/upload/resumable/{upload_id}/finish",
+    responses(
+        (status = 200, response = crate::api::model::responses::IdResponse::<UploadId>)
+    ),
+    params(
+        ("upload_id" = UploadId, description = "Upload id"),
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn finish_resumable_upload_handler<C: ApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    upload_id: web::Path<UploadId>,
+) -> Result<web::Json<IdResponse<UploadId>>> {
+    let db = app_ctx.session_context(session).db();
+    let upload_id = upload_id.into_inner();
+
+    let quota = UploadQuota::of_user(&db).await?;
+
+    db.finish_resumable_upload(upload_id, move |upload| {
+        async move {
+            // the files of this upload are already accounted for in the used bytes
+            let upload_byte_size = upload.files.iter().map(|file| file.byte_size).sum();
+            let quota = quota.map(|quota| quota.releasing(upload_byte_size));
+
+            let root = upload_id.root_path()?;
+
+            let files = crate::util::spawn_blocking(move || {
+                let files = verify_resumable_upload_files(&root, upload.files)?;
+                extract_zip_archives(&root, files, quota)
+            })
+            .await??;
+
+            Ok(Upload {
+                id: upload_id,
+                files,
+            })
+        }
+        .boxed()
+    })
+    .await?;
+
     Ok(web::Json(IdResponse::from(upload_id)))
 }
 
+fn verify_resumable_upload_files(
+    root: &Path,
+    files: Vec<ResumableFileUpload>,
+) -> Result<Vec<FileUpload>> {
+    files
+        .into_iter()
+        .map(|file_upload| {
+            let path = root.join(&file_upload.name);
+
+            let received = std::fs::metadata(&path).context(error::Io)?.len();
+            ensure!(
+                received == file_upload.byte_size,
+                error::UploadFileIncomplete {
+                    file_name: file_upload.name,
+                    received,
+                    byte_size: file_upload.byte_size,
+                }
+            );
+
+            if let Some(sha256) = &file_upload.sha256 {
+                let mut hasher = Sha256::new();
+                let mut file = std::fs::File::open(&path).context(error::Io)?;
+                std::io::copy(&mut file, &mut hasher).context(error::Io)?;
+
+                if !hex::encode(hasher.finalize()).eq_ignore_ascii_case(sha256) {
+                    // truncate the file, so that it can be sent again
+                    std::fs::File::create(&path).context(error::Io)?;
+
+                    return Err(error::Error::UploadChecksumMismatch {
+                        file_name: file_upload.name,
+                    });
+                }
+            }
+
+            Ok(FileUpload {
+                id: FileId::new(),
+                name: file_upload.name,
+                byte_size: file_upload.byte_size,
+            })
+        })
+        .collect()
+}
+
+/// Aborts a resumable upload and deletes the files received so far.
+#[utoipa::path(
+    tag = "Uploads",
+    delete,
+    path = "/upload/resumable/{upload_id}",
+    responses(
+        (status = 200, description = "OK")
+    ),
+    params(
+        ("upload_id" = UploadId, description = "Upload id"),
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn delete_resumable_upload_handler<C: ApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    upload_id: web::Path<UploadId>,
+) -> Result<HttpResponse> {
+    let upload_id = upload_id.into_inner();
+
+    app_ctx
+        .session_context(session)
+        .db()
+        .delete_resumable_upload(upload_id)
+        .await?;
+
+    fs::remove_dir_all(upload_id.root_path()?)
+        .await
+        .context(error::Io)?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
 #[derive(Deserialize, Serialize, ToSchema, ToResponse)]
 pub struct UploadFilesResponse {
     files: Vec<String>,
@@ -200,10 +781,46 @@ mod tests {
     use super::*;
     use crate::contexts::{PostgresContext, Session, SimpleApplicationContext};
     use crate::ge_context;
-    use crate::util::tests::{send_test_request, SetMultipartBody, TestDataUploads};
+    use crate::util::tests::{
+        read_body_string, send_test_request, SetMultipartBody, TestDataUploads,
+    };
     use actix_web::{http::header, test};
     use actix_web_httpauth::headers::authorization::Bearer;
+    use serde_json::json;
+    use std::io::Write;
     use tokio_postgres::NoTls;
+    use zip::{write::FileOptions, ZipWriter};
+
+    fn zip_archive(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (file_name, content) in files {
+            writer
+                .start_file(file_name, FileOptions::default())
+                .unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn ports_zip_archive() -> Vec<u8> {
+        let mut files = ["shp", "shx", "prj", "dbf", "cpg"]
+            .into_iter()
+            .map(|extension| {
+                let file_name = format!("ne_10m_ports/ne_10m_ports.{extension}");
+                let content = std::fs::read(geoengine_datatypes::test_data!(format!(
+                    "vector/data/{file_name}"
+                )))
+                .unwrap();
+                (file_name, content)
+            })
+            .collect::<Vec<_>>();
+        files.push((
+            "__MACOSX/ne_10m_ports/._ne_10m_ports.shp".to_string(),
+            vec![0; 4],
+        ));
+
+        zip_archive(&files)
+    }
 
     #[ge_context::test]
     async fn upload(app_ctx: PostgresContext<NoTls>) {
@@ -290,4 +907,213 @@ mod tests {
             ]
         );
     }
+
+    #[ge_context::test]
+    async fn it_extracts_zip_archives(app_ctx: PostgresContext<NoTls>) {
+        let mut test_data = TestDataUploads::default(); // remember created folder and remove them on drop
+
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let req = test::TestRequest::post()
+            .uri("/upload")
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .set_multipart(vec![
+                ("ports.zip", ports_zip_archive()),
+                ("foo.txt", b"foo".to_vec()),
+            ]);
+
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200);
+
+        let upload: IdResponse<UploadId> = test::read_body_json(res).await;
+        test_data.uploads.push(upload.id);
+
+        let upload = ctx.db().load_upload(upload.id).await.unwrap();
+
+        let mut file_names = upload
+            .files
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect::<Vec<_>>();
+        file_names.sort_unstable();
+
+        assert_eq!(
+            file_names,
+            vec![
+                "foo.txt",
+                "ne_10m_ports.cpg",
+                "ne_10m_ports.dbf",
+                "ne_10m_ports.prj",
+                "ne_10m_ports.shp",
+                "ne_10m_ports.shx",
+            ]
+        );
+
+        let root = upload.id.root_path().unwrap();
+        assert!(!root.join("ports.zip").exists());
+        assert!(root.join("ne_10m_ports.shp").exists());
+    }
+
+    #[ge_context::test]
+    async fn it_resumes_chunked_uploads(app_ctx: PostgresContext<NoTls>) {
+        let mut test_data = TestDataUploads::default(); // remember created folder and remove them on drop
+
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let archive = ports_zip_archive();
+        let (first_chunk, second_chunk) = archive.split_at(archive.len() / 2);
+
+        let req = test::TestRequest::post()
+            .uri("/upload/resumable")
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .set_json(json!({
+                "files": [{
+                    "name": "ports.zip",
+                    "byteSize": archive.len(),
+                    "sha256": hex::encode(Sha256::digest(&archive)),
+                }]
+            }));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200);
+
+        let upload: IdResponse<UploadId> = test::read_body_json(res).await;
+        test_data.uploads.push(upload.id);
+
+        let chunk_request = |offset: usize, chunk: &[u8]| {
+            test::TestRequest::patch()
+                .uri(&format!("/upload/resumable/{}/files/ports.zip", upload.id))
+                .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+                .append_header((header::CONTENT_TYPE, "application/offset+octet-stream"))
+                .append_header((UPLOAD_OFFSET_HEADER, offset.to_string()))
+                .set_payload(chunk.to_vec())
+        };
+
+        let res = send_test_request(chunk_request(0, first_chunk), app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200);
+
+        // the upload cannot be finished before all bytes are received
+        let req = test::TestRequest::post()
+            .uri(&format!("/upload/resumable/{}/finish", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 400);
+
+        // e.g., a retry of a chunk that was already received
+        let res = send_test_request(chunk_request(0, first_chunk), app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 409);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/upload/resumable/{}", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200);
+
+        let status: ResumableUploadStatus = test::read_body_json(res).await;
+        assert_eq!(status.files.len(), 1);
+        assert_eq!(status.files[0].byte_size, archive.len() as u64);
+        assert_eq!(status.files[0].received, first_chunk.len() as u64);
+
+        let res = send_test_request(
+            chunk_request(first_chunk.len(), second_chunk),
+            app_ctx.clone(),
+        )
+        .await;
+
+        assert_eq!(res.status(), 200);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/upload/resumable/{}/finish", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200, "{}", read_body_string(res).await);
+
+        // finishing again, e.g., after a lost response, succeeds
+        let req = test::TestRequest::post()
+            .uri(&format!("/upload/resumable/{}/finish", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200, "{}", read_body_string(res).await);
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/uploads/{}/files/ne_10m_ports.shp/layers",
+                upload.id
+            ))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx).await;
+
+        assert_eq!(res.status(), 200);
+
+        let layers: UploadFileLayersResponse = test::read_body_json(res).await;
+
+        assert_eq!(layers.layers, vec!["ne_10m_ports".to_string()]);
+
+        assert!(ctx.db().load_resumable_upload(upload.id).await.is_err());
+        assert_eq!(
+            ctx.db().load_upload(upload.id).await.unwrap().files.len(),
+            5
+        );
+    }
+
+    #[ge_context::test]
+    async fn it_discards_files_with_wrong_checksum(app_ctx: PostgresContext<NoTls>) {
+        let mut test_data = TestDataUploads::default(); // remember created folder and remove them on drop
+
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let req = test::TestRequest::post()
+            .uri("/upload/resumable")
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .set_json(json!({
+                "files": [{
+                    "name": "foo.txt",
+                    "byteSize": 3,
+                    "sha256": hex::encode(Sha256::digest(b"foo")),
+                }]
+            }));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200);
+
+        let upload: IdResponse<UploadId> = test::read_body_json(res).await;
+        test_data.uploads.push(upload.id);
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/upload/resumable/{}/files/foo.txt", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .append_header((UPLOAD_OFFSET_HEADER, "0"))
+            .set_payload("bar");
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/upload/resumable/{}/finish", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 400);
+        assert!(read_body_string(res)
+            .await
+            .contains("UploadChecksumMismatch"));
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/upload/resumable/{}", upload.id))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx).await;
+
+        let status: ResumableUploadStatus = test::read_body_json(res).await;
+        assert_eq!(status.files[0].received, 0);
+    }
 }
@@ -1,7 +1,9 @@
 use crate::api::handlers::spatial_references::{spatial_reference_specification, AxisOrder};
 use crate::api::model::datatypes::TimeInterval;
 use crate::api::ogc::util::{ogc_endpoint_url, OgcProtocol, OgcRequestGuard};
-use crate::api::ogc::wcs::request::{DescribeCoverage, GetCapabilities, GetCoverage, WcsVersion};
+use crate::api::ogc::wcs::request::{
+    DescribeCoverage, GetCapabilities, GetCoverage, GetCoverageFormat, WcsVersion,
+};
 use crate::contexts::{ApplicationContext, SessionContext};
 use crate::error::Result;
 use crate::error::{self, Error};
@@ -19,12 +21,15 @@ use geoengine_operators::call_on_generic_raster_processor_gdal_types;
 use geoengine_operators::engine::{CanonicOperatorName, ExecutionContext, WorkflowOperatorPath};
 use geoengine_operators::engine::{ResultDescriptor, SingleRasterOrVectorSource};
 use geoengine_operators::processing::{
-    InitializedRasterReprojection, Reprojection, ReprojectionParams,
+    InitializedRasterReprojection, InterpolationMethod, Reprojection, ReprojectionParams,
 };
 use geoengine_operators::util::input::RasterOrVectorOperator;
 use geoengine_operators::util::raster_stream_to_geotiff::{
     raster_stream_to_multiband_geotiff_bytes, GdalGeoTiffDatasetMetadata, GdalGeoTiffOptions,
 };
+use geoengine_operators::util::raster_stream_to_netcdf::{
+    raster_stream_to_netcdf_bytes, NetCdfDatasetMetadata,
+};
 use log::info;
 use snafu::{ensure, ResultExt};
 use std::str::FromStr;
@@ -274,6 +279,8 @@ async fn wcs_describe_coverage_handler<C: ApplicationContext>(
             </wcs:Domain>
             <wcs:SupportedCRS>{srs_authority}:{srs_code}</wcs:SupportedCRS>
             <wcs:SupportedFormat>image/tiff</wcs:SupportedFormat>
+            <wcs:SupportedFormat>image/tiff; application=geotiff; profile=cloud-optimized</wcs:SupportedFormat>
+            <wcs:SupportedFormat>application/x-netcdf</wcs:SupportedFormat>
         </wcs:CoverageDescription>
     </wcs:CoverageDescriptions>"#,
         wcs_url = wcs_url,
@@ -393,6 +400,7 @@ async fn wcs_get_coverage_handler<C: ApplicationContext>(
 
         let reprojection_params = ReprojectionParams {
             target_spatial_reference: request_spatial_ref,
+            interpolation: InterpolationMethod::NearestNeighbor,
         };
 
         // create the reprojection operator in order to get the canonic operator name
@@ -415,6 +423,14 @@ async fn wcs_get_coverage_handler<C: ApplicationContext>(
         Box::new(irp)
     };
 
+    let band_names: Vec<String> = initialized
+        .result_descriptor()
+        .bands
+        .bands()
+        .iter()
+        .map(|band| band.name.clone())
+        .collect();
+
     let processor = initialized.query_processor().context(error::Operator)?;
 
     let spatial_resolution: SpatialResolution =
@@ -428,39 +444,69 @@ async fn wcs_get_coverage_handler<C: ApplicationContext>(
             }
         };
 
+    // the NetCDF output contains all bands of the workflow as variables
+    let attributes = if request.format == GetCoverageFormat::ApplicationNetCdf {
+        BandSelection::first_n(band_names.len() as u32)
+    } else {
+        BandSelection::first() // TODO: support multi bands in API and set the selection here
+    };
+
     let query_rect = RasterQueryRectangle {
         spatial_bounds: request_partition,
         time_interval: request.time.unwrap_or_else(default_time_from_config).into(),
         spatial_resolution,
-        attributes: BandSelection::first(), // TODO: support multi bands in API and set the selection here
+        attributes,
     };
 
     let query_ctx = ctx.query_context()?;
 
-    let (bytes, cache_hint) = call_on_generic_raster_processor_gdal_types!(processor, p =>
-        raster_stream_to_multiband_geotiff_bytes(
-            p,
-            query_rect,
-            query_ctx,
-            GdalGeoTiffDatasetMetadata {
-                no_data_value: request_no_data_value,
-                spatial_reference: request_spatial_ref,
-            },
-            GdalGeoTiffOptions {
-                compression_num_threads: get_config_element::<crate::util::config::Gdal>()?.compression_num_threads,
-                as_cog: false,
-                force_big_tiff: false,
-            },
-            Some(get_config_element::<crate::util::config::Wcs>()?.tile_limit),
-            conn_closed,
-            execution_context.tiling_specification(),
-        )
-        .await)?
+    let tile_limit = Some(get_config_element::<crate::util::config::Wcs>()?.tile_limit);
+
+    let (bytes, cache_hint) = match request.format {
+        GetCoverageFormat::ImageTiff | GetCoverageFormat::ImageTiffCog => {
+            call_on_generic_raster_processor_gdal_types!(processor, p =>
+                raster_stream_to_multiband_geotiff_bytes(
+                    p,
+                    query_rect,
+                    query_ctx,
+                    GdalGeoTiffDatasetMetadata {
+                        no_data_value: request_no_data_value,
+                        spatial_reference: request_spatial_ref,
+                    },
+                    GdalGeoTiffOptions {
+                        compression_num_threads: get_config_element::<crate::util::config::Gdal>()?.compression_num_threads,
+                        as_cog: request.format == GetCoverageFormat::ImageTiffCog,
+                        force_big_tiff: false,
+                    },
+                    tile_limit,
+                    conn_closed,
+                    execution_context.tiling_specification(),
+                )
+                .await)?
+        }
+        GetCoverageFormat::ApplicationNetCdf => {
+            call_on_generic_raster_processor_gdal_types!(processor, p =>
+                raster_stream_to_netcdf_bytes(
+                    p,
+                    query_rect,
+                    query_ctx,
+                    NetCdfDatasetMetadata {
+                        no_data_value: request_no_data_value,
+                        spatial_reference: request_spatial_ref,
+                        band_names,
+                    },
+                    tile_limit,
+                    conn_closed,
+                    execution_context.tiling_specification(),
+                )
+                .await)?
+        }
+    }
     .map_err(error::Error::from)?;
 
     Ok(HttpResponse::Ok()
         .append_header(cache_hint.cache_control_header())
-        .content_type("image/tiff")
+        .content_type(request.format.content_type())
         .body(bytes))
 }
 
@@ -642,6 +688,8 @@ mod tests {
             </wcs:Domain>
             <wcs:SupportedCRS>EPSG:4326</wcs:SupportedCRS>
             <wcs:SupportedFormat>image/tiff</wcs:SupportedFormat>
+            <wcs:SupportedFormat>image/tiff; application=geotiff; profile=cloud-optimized</wcs:SupportedFormat>
+            <wcs:SupportedFormat>application/x-netcdf</wcs:SupportedFormat>
         </wcs:CoverageDescription>
     </wcs:CoverageDescriptions>"#
             ),
@@ -709,6 +757,75 @@ mod tests {
         .await;
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn get_coverage_netcdf_and_cog() {
+        let exe_ctx_tiling_spec = TilingSpecification {
+            origin_coordinate: (0., 0.).into(),
+            tile_size_in_pixels: GridShape2D::new([600, 600]),
+        };
+
+        with_temp_context_from_spec(
+            exe_ctx_tiling_spec,
+            TestDefault::test_default(),
+            |app_ctx, _| async move {
+                let ctx = app_ctx.default_session_context().await.unwrap();
+                let session_id = ctx.session().id();
+
+                let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+                for (format, content_type, magic_bytes) in [
+                    (
+                        "application/x-netcdf",
+                        "application/x-netcdf",
+                        b"\x89HDF".as_slice(),
+                    ),
+                    (
+                        "image/tiff; application=geotiff; profile=cloud-optimized",
+                        "image/tiff",
+                        b"II*\0".as_slice(),
+                    ),
+                ] {
+                    let params = &[
+                        ("service", "WCS"),
+                        ("request", "GetCoverage"),
+                        ("version", "1.1.1"),
+                        ("identifier", &id.to_string()),
+                        ("boundingbox", "20,-10,80,50,urn:ogc:def:crs:EPSG::4326"),
+                        ("format", format),
+                        ("gridbasecrs", "urn:ogc:def:crs:EPSG::4326"),
+                        ("gridcs", "urn:ogc:def:cs:OGC:0.0:Grid2dSquareCS"),
+                        ("gridtype", "urn:ogc:def:method:WCS:1.1:2dSimpleGrid"),
+                        ("gridorigin", "80,-10"),
+                        ("gridoffsets", "0.1,0.1"),
+                        ("time", "2014-01-01T00:00:00.0Z"),
+                        ("nodatavalue", "0.0"),
+                    ];
+
+                    let req = test::TestRequest::get()
+                        .uri(&format!(
+                            "/wcs/{}?{}",
+                            &id.to_string(),
+                            serde_urlencoded::to_string(params).unwrap()
+                        ))
+                        .append_header((
+                            header::AUTHORIZATION,
+                            Bearer::new(session_id.to_string()),
+                        ));
+
+                    let res = send_test_request(req, app_ctx.clone()).await;
+
+                    assert_eq!(res.status(), 200, "{format}");
+                    assert_eq!(
+                        res.headers().get(header::CONTENT_TYPE).unwrap(),
+                        content_type
+                    );
+                    assert!(test::read_body(res).await.starts_with(magic_bytes));
+                }
+            },
+        )
+        .await;
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn it_sets_cache_control_header() {
         let exe_ctx_tiling_spec = TilingSpecification {
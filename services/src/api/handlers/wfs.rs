@@ -1,6 +1,7 @@
 use crate::api::model::datatypes::TimeInterval;
 use crate::api::ogc::util::{ogc_endpoint_url, OgcProtocol, OgcRequestGuard};
-use crate::api::ogc::wfs::request::{GetCapabilities, GetFeature};
+use crate::api::ogc::wfs::filter::{resolve_property_name, Filter};
+use crate::api::ogc::wfs::request::{GetCapabilities, GetFeature, ResultType, SortBy, SortOrder};
 use crate::contexts::{ApplicationContext, SessionContext};
 use crate::error;
 use crate::error::Result;
@@ -12,9 +13,12 @@ use crate::workflows::workflow::{Workflow, WorkflowId};
 use actix_web::{web, FromRequest, HttpRequest, HttpResponse};
 use futures::future::BoxFuture;
 use futures_util::TryStreamExt;
-use geoengine_datatypes::collections::ToGeoJson;
+use geoengine_datatypes::collections::{
+    FeatureCollectionInfos, FeatureCollectionModifications, IntoGeometryOptionsIterator, ToGeoJson,
+};
 use geoengine_datatypes::primitives::VectorQueryRectangle;
 use geoengine_datatypes::primitives::{CacheHint, ColumnSelection};
+use geoengine_datatypes::util::arrow::ArrowTyped;
 use geoengine_datatypes::{
     collections::{FeatureCollection, MultiPointCollection},
     primitives::SpatialResolution,
@@ -29,7 +33,7 @@ use geoengine_operators::engine::{
 };
 use geoengine_operators::engine::{QueryProcessor, WorkflowOperatorPath};
 use geoengine_operators::processing::{
-    InitializedVectorReprojection, Reprojection, ReprojectionParams,
+    InitializedVectorReprojection, InterpolationMethod, Reprojection, ReprojectionParams,
 };
 use geoengine_operators::util::abortable_query_execution;
 use geoengine_operators::util::input::RasterOrVectorOperator;
@@ -37,6 +41,7 @@ use reqwest::Url;
 use serde::Deserialize;
 use serde_json::json;
 use snafu::{ensure, ResultExt};
+use std::cmp::Ordering;
 use std::str::FromStr;
 use std::time::Duration;
 use utoipa::ToSchema;
@@ -494,6 +499,7 @@ async fn wfs_feature_handler<C: ApplicationContext>(
 
         let reprojection_params = ReprojectionParams {
             target_spatial_reference: request_spatial_ref,
+            interpolation: InterpolationMethod::NearestNeighbor,
         };
 
         // create the reprojection operator in order to get the canonic operator name
@@ -515,6 +521,41 @@ async fn wfs_feature_handler<C: ApplicationContext>(
         Box::new(ivp)
     };
 
+    let columns = &initialized.result_descriptor().columns;
+
+    let filter = request
+        .filter
+        .map(|filter| {
+            filter
+                .resolve_properties(columns)?
+                .reproject(request_spatial_ref)
+        })
+        .transpose()?;
+
+    let sort_by = request
+        .sort_by
+        .unwrap_or_default()
+        .into_iter()
+        .map(|sort_by| {
+            Ok(SortBy {
+                property: resolve_property_name(&sort_by.property, columns)?,
+                order: sort_by.order,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let property_names = request
+        .property_name
+        .map(|property_names| {
+            property_names
+                .iter()
+                .map(|property_name| resolve_property_name(property_name, columns))
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?;
+
+    let result_type = request.result_type.unwrap_or_default();
+
     let processor = initialized.query_processor().context(error::Operator)?;
 
     let query_rect = VectorQueryRectangle {
@@ -528,21 +569,52 @@ async fn wfs_feature_handler<C: ApplicationContext>(
     };
     let query_ctx = ctx.query_context()?;
 
-    let (json, cache_hint) = match processor {
+    let filter = filter.as_ref();
+
+    let (mut features, number_matched, cache_hint) = match processor {
         TypedVectorQueryProcessor::Data(p) => {
-            vector_stream_to_geojson(p, query_rect, query_ctx, conn_closed).await
+            vector_stream_to_geojson(p, query_rect, query_ctx, filter, result_type, conn_closed)
+                .await
         }
         TypedVectorQueryProcessor::MultiPoint(p) => {
-            vector_stream_to_geojson(p, query_rect, query_ctx, conn_closed).await
+            vector_stream_to_geojson(p, query_rect, query_ctx, filter, result_type, conn_closed)
+                .await
         }
         TypedVectorQueryProcessor::MultiLineString(p) => {
-            vector_stream_to_geojson(p, query_rect, query_ctx, conn_closed).await
+            vector_stream_to_geojson(p, query_rect, query_ctx, filter, result_type, conn_closed)
+                .await
         }
         TypedVectorQueryProcessor::MultiPolygon(p) => {
-            vector_stream_to_geojson(p, query_rect, query_ctx, conn_closed).await
+            vector_stream_to_geojson(p, query_rect, query_ctx, filter, result_type, conn_closed)
+                .await
         }
     }?;
 
+    let json = match result_type {
+        ResultType::Hits => json!({
+            "type": "FeatureCollection",
+            "numberMatched": number_matched,
+            "numberReturned": 0,
+            "features": [],
+        }),
+        ResultType::Results => {
+            sort_features(&mut features, &sort_by);
+
+            if let Some(count) = request.count {
+                features.truncate(count as usize);
+            }
+
+            if let Some(property_names) = &property_names {
+                project_features(&mut features, property_names);
+            }
+
+            json!({
+                "type": "FeatureCollection",
+                "features": features,
+            })
+        }
+    };
+
     Ok(HttpResponse::Ok()
         .append_header(cache_hint.cache_control_header())
         .json(json))
@@ -553,6 +625,12 @@ async fn wfs_feature_handler<C: ApplicationContext>(
 pub struct GeoJson {
     #[serde(rename = "type")]
     pub collection_type: CollectionType,
+    /// Only set if `resultType=hits` is requested
+    #[serde(rename = "numberMatched")]
+    pub number_matched: Option<usize>,
+    /// Only set if `resultType=hits` is requested
+    #[serde(rename = "numberReturned")]
+    pub number_returned: Option<usize>,
     pub features: Vec<serde_json::Value>,
 }
 
@@ -561,15 +639,19 @@ pub enum CollectionType {
     FeatureCollection,
 }
 
-async fn vector_stream_to_geojson<G, C: QueryContext + 'static>(
+/// Queries the features that match the `filter`.
+/// Returns the features as `GeoJSON` (unless only the hits are requested) and the number of matched features.
+pub(crate) async fn vector_stream_to_geojson<G, C: QueryContext + 'static>(
     processor: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
     query_rect: VectorQueryRectangle,
     mut query_ctx: C,
+    filter: Option<&Filter>,
+    result_type: ResultType,
     conn_closed: BoxFuture<'_, ()>,
-) -> Result<(serde_json::Value, CacheHint)>
+) -> Result<(Vec<serde_json::Value>, usize, CacheHint)>
 where
-    G: Geometry + 'static,
-    for<'c> FeatureCollection<G>: ToGeoJson<'c>,
+    G: Geometry + ArrowTyped + 'static,
+    for<'c> FeatureCollection<G>: ToGeoJson<'c> + IntoGeometryOptionsIterator<'c>,
 {
     let query_abort_trigger = query_ctx.abort_trigger()?;
 
@@ -577,40 +659,94 @@ where
     // TODO: more efficient merging of the partial feature collections
     let stream = processor.query(query_rect, &query_ctx).await?;
 
-    let future: BoxFuture<geoengine_operators::util::Result<(Vec<serde_json::Value>, CacheHint)>> =
-        Box::pin(stream.try_fold(
-            (features, CacheHint::max_duration()),
-            |(mut output, mut cache_hint), collection| async move {
-                // TODO: avoid parsing the generated json
-                let mut json: serde_json::Value =
-                    serde_json::from_str(&collection.to_geo_json()).expect("to_geojson is correct");
-                let more_features = json
-                    .get_mut("features")
-                    .expect("to_geojson is correct")
-                    .as_array_mut()
-                    .expect("to geojson is correct");
+    let future: BoxFuture<
+        geoengine_operators::util::Result<(Vec<serde_json::Value>, usize, CacheHint)>,
+    > = Box::pin(stream.try_fold(
+        (features, 0, CacheHint::max_duration()),
+        |(mut output, mut number_matched, mut cache_hint), collection| async move {
+            cache_hint.merge_with(&collection.cache_hint);
+
+            let collection = match filter {
+                Some(filter) => {
+                    let mask = filter.evaluate(&collection)?;
+                    collection.filter(mask)?
+                }
+                None => collection,
+            };
 
-                output.append(more_features);
+            number_matched += collection.len();
 
-                cache_hint.merge_with(&collection.cache_hint);
+            if result_type == ResultType::Hits {
+                return Ok((output, number_matched, cache_hint));
+            }
 
-                Ok((output, cache_hint))
-            },
-        ));
+            // TODO: avoid parsing the generated json
+            let mut json: serde_json::Value =
+                serde_json::from_str(&collection.to_geo_json()).expect("to_geojson is correct");
+            let more_features = json
+                .get_mut("features")
+                .expect("to_geojson is correct")
+                .as_array_mut()
+                .expect("to geojson is correct");
 
-    let (features, cache_hint) =
-        abortable_query_execution(future, conn_closed, query_abort_trigger).await?;
+            output.append(more_features);
 
-    let mut output = json!({
-        "type": "FeatureCollection"
+            Ok((output, number_matched, cache_hint))
+        },
+    ));
+
+    let result = abortable_query_execution(future, conn_closed, query_abort_trigger).await?;
+
+    Ok(result)
+}
+
+/// Sorts `GeoJSON` features by their properties. Null values come last in ascending order.
+fn sort_features(features: &mut [serde_json::Value], sort_by: &[SortBy]) {
+    if sort_by.is_empty() {
+        return;
+    }
+
+    features.sort_by(|a, b| {
+        sort_by
+            .iter()
+            .map(|criterion| {
+                let ordering = compare_json_values(
+                    &a["properties"][&criterion.property],
+                    &b["properties"][&criterion.property],
+                );
+
+                match criterion.order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
     });
+}
 
-    output
-        .as_object_mut()
-        .expect("as defined")
-        .insert("features".into(), serde_json::Value::Array(features));
+fn compare_json_values(a: &serde_json::Value, b: &serde_json::Value) -> Ordering {
+    match (a, b) {
+        (serde_json::Value::Number(a), serde_json::Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (serde_json::Value::String(a), serde_json::Value::String(b)) => a.cmp(b),
+        (serde_json::Value::Bool(a), serde_json::Value::Bool(b)) => a.cmp(b),
+        _ => a.is_null().cmp(&b.is_null()),
+    }
+}
 
-    Ok((output, cache_hint))
+/// Removes all properties of `GeoJSON` features that are not in `property_names`
+pub(crate) fn project_features(features: &mut [serde_json::Value], property_names: &[String]) {
+    for feature in features {
+        if let Some(properties) = feature
+            .get_mut("properties")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            properties.retain(|name, _| property_names.contains(name));
+        }
+    }
 }
 
 fn get_feature_mock(_request: &GetFeature) -> Result<HttpResponse> {
@@ -678,7 +814,8 @@ mod tests {
     use actix_web_httpauth::headers::authorization::Bearer;
     use geoengine_datatypes::raster::{GridShape2D, TilingSpecification};
     use geoengine_datatypes::test_data;
-    use geoengine_operators::engine::TypedOperator;
+    use geoengine_operators::engine::{TypedOperator, VectorOperator};
+    use geoengine_operators::mock::MockFeatureCollectionSource;
     use geoengine_operators::source::CsvSourceParameters;
     use geoengine_operators::source::{CsvGeometrySpecification, CsvSource, CsvTimeSpecification};
     use serde_json::json;
@@ -1123,6 +1260,146 @@ x;y
         .await;
     }
 
+    async fn get_feature_query_test_helper(
+        app_ctx: PostgresContext<NoTls>,
+        query_params: &[(&str, &str)],
+    ) -> ServiceResponse {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let collection = |points: Vec<(f64, f64)>, names: &[&str], ages: Vec<Option<i64>>| {
+            MultiPointCollection::from_data(
+                MultiPoint::many(points).unwrap(),
+                vec![geoengine_datatypes::primitives::TimeInterval::default(); names.len()],
+                [
+                    (
+                        "name".to_string(),
+                        FeatureData::Text(names.iter().map(ToString::to_string).collect()),
+                    ),
+                    ("age".to_string(), FeatureData::NullableInt(ages)),
+                ]
+                .into_iter()
+                .collect(),
+                CacheHint::default(),
+            )
+            .unwrap()
+        };
+
+        let workflow = Workflow {
+            operator: MockFeatureCollectionSource::multiple(vec![
+                collection(
+                    vec![(0.0, 0.0), (1.0, 1.0)],
+                    &["Bello", "Rex"],
+                    vec![Some(2), None],
+                ),
+                collection(
+                    vec![(2.0, 2.0), (3.0, 3.0)],
+                    &["Balu", "Lassie"],
+                    vec![Some(7), Some(4)],
+                ),
+            ])
+            .boxed()
+            .into(),
+        };
+
+        let workflow_id = ctx.db().register_workflow(workflow).await.unwrap();
+
+        let mut params = vec![
+            ("request", "GetFeature"),
+            ("service", "WFS"),
+            ("version", "2.0.0"),
+            ("bbox", "-90,-180,90,180"),
+            ("srsName", "EPSG:4326"),
+        ];
+        params.extend_from_slice(query_params);
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/wfs/{workflow_id}?typeNames={workflow_id}&{}",
+                &serde_urlencoded::to_string(params).unwrap()
+            ))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        send_test_request(req, app_ctx).await
+    }
+
+    #[ge_context::test]
+    async fn get_feature_filter_sort_and_project(app_ctx: PostgresContext<NoTls>) {
+        let res = get_feature_query_test_helper(
+            app_ctx,
+            &[
+                (
+                    "filter",
+                    "<Filter><Not><PropertyIsLike><ValueReference>ns:name</ValueReference><Literal>R%</Literal></PropertyIsLike></Not></Filter>",
+                ),
+                ("sortBy", "age DESC"),
+                ("propertyName", "name"),
+                ("count", "2"),
+            ],
+        )
+        .await;
+
+        assert_eq!(res.status(), 200);
+
+        let body: serde_json::Value = test::read_body_json(res).await;
+        let features = body["features"].as_array().unwrap();
+
+        assert_eq!(
+            features
+                .iter()
+                .map(|feature| (
+                    feature["geometry"]["coordinates"].clone(),
+                    feature["properties"].clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (json!([2.0, 2.0]), json!({"name": "Balu"})),
+                (json!([3.0, 3.0]), json!({"name": "Lassie"})),
+            ]
+        );
+    }
+
+    #[ge_context::test]
+    async fn get_feature_hits(app_ctx: PostgresContext<NoTls>) {
+        let res = get_feature_query_test_helper(
+            app_ctx,
+            &[
+                (
+                    "filter",
+                    "<Filter><Or><PropertyIsNull><ValueReference>age</ValueReference></PropertyIsNull><BBOX><Envelope><lowerCorner>1.5 1.5</lowerCorner><upperCorner>2.5 2.5</upperCorner></Envelope></BBOX></Or></Filter>",
+                ),
+                ("resultType", "hits"),
+            ],
+        )
+        .await;
+
+        assert_eq!(res.status(), 200);
+
+        let body: serde_json::Value = test::read_body_json(res).await;
+
+        assert_eq!(
+            body,
+            json!({
+                "type": "FeatureCollection",
+                "numberMatched": 2,
+                "numberReturned": 0,
+                "features": [],
+            })
+        );
+    }
+
+    #[ge_context::test]
+    async fn get_feature_unknown_property(app_ctx: PostgresContext<NoTls>) {
+        let res = get_feature_query_test_helper(app_ctx, &[("sortBy", "weight")]).await;
+
+        ErrorResponse::assert(
+            res,
+            400,
+            "WfsUnknownProperty",
+            "WFS property weight does not exist in the requested feature type",
+        )
+        .await;
+    }
+
     async fn add_dataset_definition_to_datasets<Tls: std::fmt::Debug>(
         app_ctx: &PostgresContext<Tls>,
         dataset_definition_path: &Path,
@@ -1,9 +1,12 @@
+use crate::api::handlers::reproject_vector_if_necessary;
 use crate::api::model::datatypes::{
     RasterColorizer, SpatialReference, SpatialReferenceOption, TimeInterval,
 };
 use crate::api::ogc::util::{ogc_endpoint_url, OgcProtocol, OgcRequestGuard};
+use crate::api::ogc::wms::feature_info::{FeatureInfo, FeatureInfoFeature};
 use crate::api::ogc::wms::request::{
-    GetCapabilities, GetLegendGraphic, GetMap, GetMapExceptionFormat,
+    GetCapabilities, GetFeatureInfo, GetFeatureInfoFormat, GetLegendGraphic, GetMap,
+    GetMapExceptionFormat, GetMapFormat,
 };
 use crate::contexts::{ApplicationContext, SessionContext};
 use crate::error::Result;
@@ -14,28 +17,61 @@ use crate::util::server::{connection_closed, not_implemented_handler, CacheContr
 use crate::workflows::registry::WorkflowRegistry;
 use crate::workflows::workflow::WorkflowId;
 use actix_web::{web, FromRequest, HttpRequest, HttpResponse};
-use geoengine_datatypes::primitives::SpatialResolution;
+use futures::future::BoxFuture;
+use futures_util::TryStreamExt;
+use geoengine_datatypes::collections::{
+    FeatureCollection, FeatureCollectionInfos, IntoGeometryOptionsIterator,
+};
+use geoengine_datatypes::operations::image::{
+    message_to_image, Colorizer, ImageOutputFormat, LegendOptions, RgbaColor, ToLegendPng,
+};
 use geoengine_datatypes::primitives::{
     AxisAlignedRectangle, RasterQueryRectangle, SpatialPartition2D,
 };
 use geoengine_datatypes::primitives::{BandSelection, CacheHint};
+use geoengine_datatypes::primitives::{
+    BoundingBox2D, ClassificationMeasurement, ColumnSelection, ContinuousMeasurement, Coordinate2D,
+    Geometry, GeometryRef, Measurement, SpatialResolution, VectorQueryRectangle,
+};
+use geoengine_datatypes::raster::{
+    GridContains, GridIndexAccess, GridShapeAccess, Pixel, RasterDataType, TilingSpecification,
+};
+use geoengine_datatypes::util::arrow::ArrowTyped;
 use geoengine_operators::engine::{
-    CanonicOperatorName, ExecutionContext, ResultDescriptor, SingleRasterOrVectorSource,
+    CanonicOperatorName, ExecutionContext, InitializedRasterOperator, QueryContext, QueryProcessor,
+    RasterBandDescriptor, RasterOperator, RasterQueryProcessor, ResultDescriptor,
+    SingleRasterOrVectorSource, TypedOperator, TypedVectorQueryProcessor, VectorQueryProcessor,
     WorkflowOperatorPath,
 };
 use geoengine_operators::processing::{
-    InitializedRasterReprojection, Reprojection, ReprojectionParams,
+    InitializedRasterReprojection, InterpolationMethod, Reprojection, ReprojectionParams,
 };
+use geoengine_operators::util::abortable_query_execution;
 use geoengine_operators::util::input::RasterOrVectorOperator;
+use geoengine_operators::util::raster_stream_to_geotiff::{
+    raster_stream_to_multiband_geotiff_bytes, GdalGeoTiffDatasetMetadata, GdalGeoTiffOptions,
+};
+use geoengine_operators::util::raster_stream_to_png::{
+    default_colorizer_gradient, raster_stream_to_image_bytes,
+};
 use geoengine_operators::{
-    call_on_generic_raster_processor, util::raster_stream_to_png::raster_stream_to_png_bytes,
+    call_on_generic_raster_processor, call_on_generic_raster_processor_gdal_types,
 };
+use num_traits::AsPrimitive;
 use reqwest::Url;
 use serde_json::json;
 use snafu::{ensure, ResultExt};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 
+/// Number of map pixels around the queried pixel in which vector features are considered to be hit
+const FEATURE_INFO_PIXEL_TOLERANCE: f64 = 3.;
+
+const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+const GEOTIFF_MIME_TYPE: &str = "image/tiff";
+
 pub(crate) fn init_wms_routes<C>(cfg: &mut web::ServiceConfig)
 where
     C: ApplicationContext,
@@ -53,6 +89,11 @@ where
                     .guard(OgcRequestGuard::new("GetMap"))
                     .to(wms_map_handler::<C>),
             )
+            .route(
+                web::get()
+                    .guard(OgcRequestGuard::new("GetFeatureInfo"))
+                    .to(wms_feature_info_handler::<C>),
+            )
             .route(
                 web::get()
                     .guard(OgcRequestGuard::new("GetLegendGraphic"))
@@ -186,6 +227,9 @@ where
             </GetCapabilities>
             <GetMap>
                 <Format>image/png</Format>
+                <Format>image/jpeg</Format>
+                <Format>image/webp</Format>
+                <Format>image/tiff</Format>
                 <DCPType>
                     <HTTP>
                         <Get>
@@ -194,10 +238,24 @@ where
                     </HTTP>
                 </DCPType>
             </GetMap>
+            <GetFeatureInfo>
+                <Format>text/xml</Format>
+                <Format>application/json</Format>
+                <Format>text/html</Format>
+                <DCPType>
+                    <HTTP>
+                        <Get>
+                            <OnlineResource xlink:href="{wms_url}"/>
+                        </Get>
+                    </HTTP>
+                </DCPType>
+            </GetFeatureInfo>
         </Request>
         <Exception>
             <Format>XML</Format>
             <Format>JSON</Format>
+            <Format>INIMAGE</Format>
+            <Format>BLANK</Format>
         </Exception>
         <Layer queryable="1">
             <Name>{workflow}</Name>
@@ -261,7 +319,7 @@ async fn wms_map_handler<C: ApplicationContext>(
         request: &web::Query<GetMap>,
         app_ctx: web::Data<C>,
         session: C::Session,
-    ) -> Result<(Vec<u8>, CacheHint)> {
+    ) -> Result<(Vec<u8>, CacheHint, &'static str)> {
         let endpoint = workflow.into_inner();
         let layer = WorkflowId::from_str(&request.layers)?;
 
@@ -310,37 +368,13 @@ async fn wms_map_handler<C: ApplicationContext>(
             request.crs.ok_or(error::Error::MissingSpatialReference)?;
 
         // perform reprojection if necessary
-        let initialized = if request_spatial_ref == workflow_spatial_ref {
-            initialized
-        } else {
-            log::debug!(
-                "WMS query srs: {}, workflow srs: {} --> injecting reprojection",
-                request_spatial_ref,
-                workflow_spatial_ref
-            );
-
-            let reprojection_params = ReprojectionParams {
-                target_spatial_reference: request_spatial_ref.into(),
-            };
-
-            // create the reprojection operator in order to get the canonic operator name
-            let reprojected_workflow = Reprojection {
-                params: reprojection_params,
-                sources: SingleRasterOrVectorSource {
-                    source: RasterOrVectorOperator::Raster(operator),
-                },
-            };
-
-            let irp = InitializedRasterReprojection::try_new_with_input(
-                CanonicOperatorName::from(&reprojected_workflow),
-                reprojection_params,
-                initialized,
-                execution_context.tiling_specification(),
-            )
-            .context(error::Operator)?;
-
-            Box::new(irp)
-        };
+        let initialized = reproject_raster_if_necessary(
+            operator,
+            initialized,
+            workflow_spatial_ref,
+            request_spatial_ref,
+            execution_context.tiling_specification(),
+        )?;
 
         let processor = initialized.query_processor().context(error::Operator)?;
 
@@ -370,22 +404,557 @@ async fn wms_map_handler<C: ApplicationContext>(
 
         let query_ctx = ctx.query_context()?;
 
+        let Some(image_format) = map_image_format(request)? else {
+            // GeoTIFF outputs the raw raster values instead of a colorized image
+            return call_on_generic_raster_processor_gdal_types!(processor, p =>
+                raster_stream_to_multiband_geotiff_bytes(
+                    p,
+                    query_rect,
+                    query_ctx,
+                    GdalGeoTiffDatasetMetadata {
+                        no_data_value: None,
+                        spatial_reference: request_spatial_ref.into(),
+                    },
+                    GdalGeoTiffOptions {
+                        compression_num_threads: get_config_element::<config::Gdal>()?.compression_num_threads,
+                        as_cog: false,
+                        force_big_tiff: false,
+                    },
+                    None,
+                    conn_closed,
+                    execution_context.tiling_specification(),
+                )
+                .await)?
+            .map(|(bytes, cache_hint)| (bytes, cache_hint, GEOTIFF_MIME_TYPE))
+            .map_err(error::Error::from);
+        };
+
         call_on_generic_raster_processor!(
             processor,
             p =>
-                raster_stream_to_png_bytes(p, query_rect, query_ctx, request.width, request.height, request.time.map(Into::into), colorizer, conn_closed).await
-        ).map_err(error::Error::from)
+                raster_stream_to_image_bytes(p, query_rect, query_ctx, request.width, request.height, request.time.map(Into::into), colorizer, image_format, conn_closed).await
+        )
+        .map(|(bytes, cache_hint)| (bytes, cache_hint, image_format.mime_type()))
+        .map_err(error::Error::from)
     }
 
     match compute_result(req, workflow, &request, app_ctx, session).await {
-        Ok((image_bytes, cache_hint)) => Ok(HttpResponse::Ok()
-            .content_type(mime::IMAGE_PNG)
+        Ok((image_bytes, cache_hint, mime_type)) => Ok(HttpResponse::Ok()
+            .content_type(mime_type)
             .append_header(cache_hint.cache_control_header())
             .body(image_bytes)),
+        Err(error) => Ok(handle_wms_map_error(&request, &error)),
+    }
+}
+
+/// Returns the image encoding of the requested map or `None` for raw `GeoTIFF` outputs
+fn map_image_format(request: &GetMap) -> Result<Option<ImageOutputFormat>> {
+    if let Some(quality) = request.quality {
+        ensure!(
+            (1..=100).contains(&quality),
+            error::WmsInvalidImageQuality { quality }
+        );
+    }
+
+    Ok(match request.format {
+        GetMapFormat::ImagePng => Some(ImageOutputFormat::Png),
+        GetMapFormat::ImageJpeg => Some(ImageOutputFormat::Jpeg {
+            quality: request.quality.unwrap_or(DEFAULT_JPEG_QUALITY),
+            background: parse_bgcolor(request.bgcolor.as_deref())?,
+        }),
+        // WebP is encoded lossless unless a quality is requested
+        GetMapFormat::ImageWebp => Some(ImageOutputFormat::WebP {
+            quality: request.quality,
+        }),
+        GetMapFormat::ImageTiff => None,
+    })
+}
+
+/// Parses the `BGCOLOR` parameter of the form `0xRRGGBB`, which defaults to white
+fn parse_bgcolor(bgcolor: Option<&str>) -> Result<RgbaColor> {
+    let Some(bgcolor) = bgcolor else {
+        return Ok(RgbaColor::white());
+    };
+
+    let hex = bgcolor
+        .strip_prefix("0x")
+        .or_else(|| bgcolor.strip_prefix("0X"))
+        .or_else(|| bgcolor.strip_prefix('#'))
+        .unwrap_or(bgcolor);
+
+    ensure!(
+        hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        error::WmsInvalidBackgroundColor {
+            bgcolor: bgcolor.to_string()
+        }
+    );
+
+    let rgb =
+        u32::from_str_radix(hex, 16).map_err(|_| error::Error::WmsInvalidBackgroundColor {
+            bgcolor: bgcolor.to_string(),
+        })?;
+
+    Ok(RgbaColor::new(
+        (rgb >> 16) as u8,
+        (rgb >> 8) as u8,
+        rgb as u8,
+        255,
+    ))
+}
+
+/// Injects a reprojection into the initialized raster workflow if the requested spatial reference differs from the workflow's
+fn reproject_raster_if_necessary(
+    operator: Box<dyn RasterOperator>,
+    initialized: Box<dyn InitializedRasterOperator>,
+    workflow_spatial_ref: SpatialReference,
+    request_spatial_ref: SpatialReference,
+    tiling_specification: TilingSpecification,
+) -> Result<Box<dyn InitializedRasterOperator>> {
+    if request_spatial_ref == workflow_spatial_ref {
+        return Ok(initialized);
+    }
+
+    log::debug!(
+        "WMS query srs: {}, workflow srs: {} --> injecting reprojection",
+        request_spatial_ref,
+        workflow_spatial_ref
+    );
+
+    let reprojection_params = ReprojectionParams {
+        target_spatial_reference: request_spatial_ref.into(),
+        interpolation: InterpolationMethod::NearestNeighbor,
+    };
+
+    // create the reprojection operator in order to get the canonic operator name
+    let reprojected_workflow = Reprojection {
+        params: reprojection_params,
+        sources: SingleRasterOrVectorSource {
+            source: RasterOrVectorOperator::Raster(operator),
+        },
+    };
+
+    let irp = InitializedRasterReprojection::try_new_with_input(
+        CanonicOperatorName::from(&reprojected_workflow),
+        reprojection_params,
+        initialized,
+        tiling_specification,
+    )
+    .context(error::Operator)?;
+
+    Ok(Box::new(irp))
+}
+
+/// Get WMS Feature Info
+#[utoipa::path(
+    tag = "OGC WMS",
+    get,
+    path = "/wms/{workflow}?request=GetFeatureInfo",
+    responses(
+        (status = 200, description = "OK", content_type = ["text/xml", "application/json", "text/html"], body = String),
+    ),
+    params(
+        ("workflow" = WorkflowId, description = "Workflow id"),
+        GetFeatureInfo
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn wms_feature_info_handler<C: ApplicationContext>(
+    req: HttpRequest,
+    workflow: web::Path<WorkflowId>,
+    request: web::Query<GetFeatureInfo>,
+    app_ctx: web::Data<C>,
+    session: C::Session,
+) -> Result<HttpResponse> {
+    #[allow(clippy::too_many_lines)]
+    async fn compute_result<C: ApplicationContext>(
+        req: HttpRequest,
+        workflow: web::Path<WorkflowId>,
+        request: &web::Query<GetFeatureInfo>,
+        app_ctx: web::Data<C>,
+        session: C::Session,
+    ) -> Result<(FeatureInfo, CacheHint)> {
+        let endpoint = workflow.into_inner();
+        let layer = WorkflowId::from_str(&request.query_layers)?;
+
+        ensure!(
+            endpoint == layer,
+            error::WMSEndpointLayerMissmatch { endpoint, layer }
+        );
+
+        ensure!(
+            request.i < request.width && request.j < request.height,
+            error::WmsFeatureInfoPixelOutOfBounds {
+                i: request.i,
+                j: request.j,
+                width: request.width,
+                height: request.height,
+            }
+        );
+
+        let conn_closed = connection_closed(
+            &req,
+            config::get_config_element::<config::Wms>()?
+                .request_timeout_seconds
+                .map(Duration::from_secs),
+        );
+
+        let ctx = app_ctx.session_context(session);
+
+        let workflow = ctx.db().load_workflow(&layer).await?;
+
+        let execution_context = ctx.execution_context()?;
+
+        // TODO: use a default spatial reference if it is not set?
+        let request_spatial_ref: SpatialReference =
+            request.crs.ok_or(error::Error::MissingSpatialReference)?;
+
+        let map_bbox: SpatialPartition2D = request.bbox.bounds(request_spatial_ref)?;
+        let x_query_resolution = map_bbox.size_x() / f64::from(request.width);
+        let y_query_resolution = map_bbox.size_y() / f64::from(request.height);
+        let spatial_resolution =
+            SpatialResolution::new_unchecked(x_query_resolution, y_query_resolution);
+
+        // the upper left corner and the center of the queried pixel
+        let pixel_upper_left = Coordinate2D::new(
+            map_bbox.upper_left().x + f64::from(request.i) * x_query_resolution,
+            map_bbox.upper_left().y - f64::from(request.j) * y_query_resolution,
+        );
+        let pixel_center = Coordinate2D::new(
+            pixel_upper_left.x + 0.5 * x_query_resolution,
+            pixel_upper_left.y - 0.5 * y_query_resolution,
+        );
+
+        let time_interval: geoengine_datatypes::primitives::TimeInterval =
+            request.time.unwrap_or_else(default_time_from_config).into();
+
+        let query_ctx = ctx.query_context()?;
+
+        let workflow_operator_path_root = WorkflowOperatorPath::initialize_root();
+
+        let (features, cache_hint) = match workflow.operator {
+            TypedOperator::Raster(operator) => {
+                let initialized = operator
+                    .clone()
+                    .initialize(workflow_operator_path_root, &execution_context)
+                    .await
+                    .context(error::Operator)?;
+
+                let workflow_spatial_ref: SpatialReferenceOption =
+                    initialized.result_descriptor().spatial_reference().into();
+                let workflow_spatial_ref: Option<SpatialReference> = workflow_spatial_ref.into();
+                let workflow_spatial_ref =
+                    workflow_spatial_ref.ok_or(error::Error::InvalidSpatialReference)?;
+
+                let initialized = reproject_raster_if_necessary(
+                    operator,
+                    initialized,
+                    workflow_spatial_ref,
+                    request_spatial_ref,
+                    execution_context.tiling_specification(),
+                )?;
+
+                let bands = initialized.result_descriptor().bands.clone();
+
+                let processor = initialized.query_processor().context(error::Operator)?;
+
+                let query_rect = RasterQueryRectangle {
+                    spatial_bounds: SpatialPartition2D::new(
+                        pixel_upper_left,
+                        Coordinate2D::new(
+                            pixel_upper_left.x + x_query_resolution,
+                            pixel_upper_left.y - y_query_resolution,
+                        ),
+                    )
+                    .context(error::DataType)?,
+                    time_interval,
+                    spatial_resolution,
+                    attributes: BandSelection::first_n(bands.count()),
+                };
+
+                let (pixel_values, cache_hint) = call_on_generic_raster_processor!(
+                    processor,
+                    p => raster_stream_to_pixel_values(p, query_rect, query_ctx, pixel_center, bands.len(), conn_closed).await
+                )?;
+
+                let features: Vec<FeatureInfoFeature> = pixel_values
+                    .into_iter()
+                    .map(|(time, values)| FeatureInfoFeature {
+                        geometry: Some(geojson::Geometry::new(geojson::Value::Point(vec![
+                            pixel_center.x,
+                            pixel_center.y,
+                        ]))),
+                        time,
+                        properties: bands
+                            .iter()
+                            .zip(values)
+                            .map(|(band, value)| {
+                                (
+                                    band.name.clone(),
+                                    value.map_or(serde_json::Value::Null, serde_json::Value::from),
+                                )
+                            })
+                            .collect(),
+                    })
+                    .collect();
+
+                (features, cache_hint)
+            }
+            TypedOperator::Vector(operator) => {
+                let initialized = operator
+                    .clone()
+                    .initialize(workflow_operator_path_root, &execution_context)
+                    .await
+                    .context(error::Operator)?;
+
+                let workflow_spatial_ref: SpatialReferenceOption =
+                    initialized.result_descriptor().spatial_reference().into();
+                let workflow_spatial_ref: Option<SpatialReference> = workflow_spatial_ref.into();
+                let workflow_spatial_ref =
+                    workflow_spatial_ref.ok_or(error::Error::InvalidSpatialReference)?;
+
+                let initialized = reproject_vector_if_necessary(
+                    operator,
+                    initialized,
+                    workflow_spatial_ref,
+                    request_spatial_ref,
+                )?;
+
+                let processor = initialized.query_processor().context(error::Operator)?;
+
+                let tolerance_x = FEATURE_INFO_PIXEL_TOLERANCE * x_query_resolution;
+                let tolerance_y = FEATURE_INFO_PIXEL_TOLERANCE * y_query_resolution;
+
+                let query_rect = VectorQueryRectangle {
+                    spatial_bounds: BoundingBox2D::new(
+                        (pixel_center.x - tolerance_x, pixel_center.y - tolerance_y).into(),
+                        (pixel_center.x + tolerance_x, pixel_center.y + tolerance_y).into(),
+                    )
+                    .context(error::DataType)?,
+                    time_interval,
+                    spatial_resolution,
+                    attributes: ColumnSelection::all(),
+                };
+
+                let feature_count = request.feature_count.unwrap_or(1) as usize;
+
+                match processor {
+                    TypedVectorQueryProcessor::Data(_) => {
+                        return Err(error::Error::WmsFeatureInfoUnsupportedWorkflowType {
+                            workflow_type: "Data".to_string(),
+                        })
+                    }
+                    TypedVectorQueryProcessor::MultiPoint(p) => {
+                        vector_stream_to_feature_info(
+                            p,
+                            query_rect,
+                            query_ctx,
+                            feature_count,
+                            conn_closed,
+                        )
+                        .await
+                    }
+                    TypedVectorQueryProcessor::MultiLineString(p) => {
+                        vector_stream_to_feature_info(
+                            p,
+                            query_rect,
+                            query_ctx,
+                            feature_count,
+                            conn_closed,
+                        )
+                        .await
+                    }
+                    TypedVectorQueryProcessor::MultiPolygon(p) => {
+                        vector_stream_to_feature_info(
+                            p,
+                            query_rect,
+                            query_ctx,
+                            feature_count,
+                            conn_closed,
+                        )
+                        .await
+                    }
+                }?
+            }
+            TypedOperator::Plot(_) => {
+                return Err(error::Error::WmsFeatureInfoUnsupportedWorkflowType {
+                    workflow_type: "Plot".to_string(),
+                })
+            }
+        };
+
+        Ok((FeatureInfo::new(layer.to_string(), features), cache_hint))
+    }
+
+    match compute_result(req, workflow, &request, app_ctx, session).await {
+        Ok((feature_info, cache_hint)) => {
+            let (content_type, body) =
+                feature_info.encode(request.info_format.unwrap_or(GetFeatureInfoFormat::TextXml));
+
+            Ok(HttpResponse::Ok()
+                .content_type(content_type)
+                .append_header(cache_hint.cache_control_header())
+                .body(body))
+        }
         Err(error) => Ok(handle_wms_error(request.exceptions, &error)),
     }
 }
 
+/// The values of all bands of a pixel for each time step
+type PixelValues = Vec<(
+    geoengine_datatypes::primitives::TimeInterval,
+    Vec<Option<f64>>,
+)>;
+
+/// Collects the values of all bands at `coordinate` for each time step of the raster stream
+async fn raster_stream_to_pixel_values<T, C: QueryContext + 'static>(
+    processor: Box<dyn RasterQueryProcessor<RasterType = T>>,
+    query_rect: RasterQueryRectangle,
+    mut query_ctx: C,
+    coordinate: Coordinate2D,
+    number_of_bands: usize,
+    conn_closed: BoxFuture<'_, ()>,
+) -> Result<(PixelValues, CacheHint)>
+where
+    T: Pixel,
+{
+    let query_abort_trigger = query_ctx.abort_trigger()?;
+
+    let tile_stream = processor.query(query_rect, &query_ctx).await?;
+
+    let future: BoxFuture<geoengine_operators::util::Result<(PixelValues, CacheHint)>> =
+        Box::pin(tile_stream.try_fold(
+            (PixelValues::new(), CacheHint::max_duration()),
+            |(mut pixel_values, mut cache_hint), tile| async move {
+                cache_hint.merge_with(&tile.cache_hint);
+
+                let time_idx = if let Some(time_idx) =
+                    pixel_values.iter().position(|(time, _)| *time == tile.time)
+                {
+                    time_idx
+                } else {
+                    pixel_values.push((tile.time, vec![None; number_of_bands]));
+                    pixel_values.len() - 1
+                };
+
+                let grid_idx = tile
+                    .tile_information()
+                    .tile_geo_transform()
+                    .coordinate_to_grid_idx_2d(coordinate);
+
+                if tile.grid_shape().contains(&grid_idx) {
+                    if let (Some(value), Ok(Some(pixel))) = (
+                        pixel_values[time_idx].1.get_mut(tile.band as usize),
+                        tile.get_at_grid_index(grid_idx),
+                    ) {
+                        *value = Some(AsPrimitive::<f64>::as_(pixel));
+                    }
+                }
+
+                Ok((pixel_values, cache_hint))
+            },
+        ));
+
+    let result = abortable_query_execution(future, conn_closed, query_abort_trigger).await?;
+
+    Ok(result)
+}
+
+/// Collects up to `feature_count` features of the vector stream that intersect the query rectangle
+async fn vector_stream_to_feature_info<G, C: QueryContext + 'static>(
+    processor: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    query_rect: VectorQueryRectangle,
+    mut query_ctx: C,
+    feature_count: usize,
+    conn_closed: BoxFuture<'_, ()>,
+) -> Result<(Vec<FeatureInfoFeature>, CacheHint)>
+where
+    G: Geometry + ArrowTyped + 'static,
+    for<'c> FeatureCollection<G>: IntoGeometryOptionsIterator<'c>,
+{
+    let query_abort_trigger = query_ctx.abort_trigger()?;
+
+    let search_bbox = query_rect.spatial_bounds;
+
+    let stream = processor.query(query_rect, &query_ctx).await?;
+
+    let future: BoxFuture<geoengine_operators::util::Result<(Vec<FeatureInfoFeature>, CacheHint)>> =
+        Box::pin(stream.try_fold(
+            (Vec::new(), CacheHint::max_duration()),
+            |(mut features, mut cache_hint), collection| async move {
+                cache_hint.merge_with(&collection.cache_hint);
+
+                if features.len() < feature_count {
+                    features.extend(
+                        feature_info_features(&collection, &search_bbox)
+                            .into_iter()
+                            .take(feature_count - features.len()),
+                    );
+                }
+
+                Ok((features, cache_hint))
+            },
+        ));
+
+    let result = abortable_query_execution(future, conn_closed, query_abort_trigger).await?;
+
+    Ok(result)
+}
+
+/// Outputs the features of the `collection` that intersect the `search_bbox`
+fn feature_info_features<'c, G>(
+    collection: &'c FeatureCollection<G>,
+    search_bbox: &BoundingBox2D,
+) -> Vec<FeatureInfoFeature>
+where
+    G: Geometry + ArrowTyped,
+    FeatureCollection<G>: IntoGeometryOptionsIterator<'c>,
+{
+    let hits: Vec<(usize, Option<geojson::Geometry>)> = collection
+        .geometry_options()
+        .enumerate()
+        .filter_map(|(feature_index, geometry)| match geometry {
+            Some(geometry) if geometry.as_geometry().intersects_bbox(search_bbox) => {
+                Some((feature_index, Some(geometry.into())))
+            }
+            _ => None,
+        })
+        .collect();
+
+    if hits.is_empty() {
+        return Vec::new();
+    }
+
+    let mut column_names = collection.column_names().cloned().collect::<Vec<_>>();
+    column_names.sort();
+
+    let column_values = column_names
+        .iter()
+        .map(|column_name| {
+            collection
+                .data(column_name)
+                .expect("must exist since it's in the column names")
+                .json_values()
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let time_intervals = collection.time_intervals();
+
+    hits.into_iter()
+        .map(|(feature_index, geometry)| FeatureInfoFeature {
+            geometry,
+            time: time_intervals[feature_index],
+            properties: column_names
+                .iter()
+                .zip(&column_values)
+                .map(|(column_name, values)| (column_name.clone(), values[feature_index].clone()))
+                .collect(),
+        })
+        .collect()
+}
+
 fn handle_wms_error(
     exception_format: Option<GetMapExceptionFormat>,
     error: &Error,
@@ -393,7 +962,10 @@ fn handle_wms_error(
     let exception_format = exception_format.unwrap_or(GetMapExceptionFormat::Xml);
 
     match exception_format {
-        GetMapExceptionFormat::Xml => {
+        // image exceptions are only available for `GetMap`, cf. `handle_wms_map_error`
+        GetMapExceptionFormat::Xml
+        | GetMapExceptionFormat::InImage
+        | GetMapExceptionFormat::Blank => {
             let body = format!(
                 r#"
 <?xml version="1.0" encoding="UTF-8"?>
@@ -412,6 +984,37 @@ fn handle_wms_error(
     }
 }
 
+/// Handles errors of `GetMap` requests, which additionally support reporting errors as images
+fn handle_wms_map_error(request: &GetMap, error: &Error) -> HttpResponse {
+    let message = match request.exceptions {
+        Some(GetMapExceptionFormat::InImage) => error.to_string(),
+        Some(GetMapExceptionFormat::Blank) => String::new(),
+        exception_format => return handle_wms_error(exception_format, error),
+    };
+
+    // there is no colorized image for GeoTIFF outputs, so use PNG instead
+    let format = match map_image_format(request) {
+        Ok(Some(format)) => format,
+        Ok(None) => ImageOutputFormat::Png,
+        Err(format_error) => return handle_wms_error(None, &format_error),
+    };
+
+    let supports_transparency = !matches!(format, ImageOutputFormat::Jpeg { .. });
+
+    let background = if request.transparent == Some(true) && supports_transparency {
+        RgbaColor::transparent()
+    } else {
+        parse_bgcolor(request.bgcolor.as_deref()).unwrap_or_else(|_| RgbaColor::white())
+    };
+
+    match message_to_image(request.width, request.height, &message, background, format) {
+        Ok(image_bytes) => HttpResponse::Ok()
+            .content_type(format.mime_type())
+            .body(image_bytes),
+        Err(image_error) => handle_wms_error(None, &image_error.into()),
+    }
+}
+
 fn raster_colorizer_from_style(styles: &str) -> Result<Option<RasterColorizer>> {
     match styles.strip_prefix("custom:") {
         None => Ok(None),
@@ -425,7 +1028,7 @@ fn raster_colorizer_from_style(styles: &str) -> Result<Option<RasterColorizer>>
     get,
     path = "/wms/{workflow}?request=GetLegendGraphic",
     responses(
-        (status = 501, description = "Not implemented")
+        (status = 200, response = crate::api::model::responses::PngResponse),
     ),
     params(
         ("workflow" = WorkflowId, description = "Workflow id"),
@@ -435,18 +1038,123 @@ fn raster_colorizer_from_style(styles: &str) -> Result<Option<RasterColorizer>>
         ("session_token" = [])
     )
 )]
-#[allow(
-    clippy::unused_async, // the function signature of request handlers requires it
-    clippy::no_effect_underscore_binding // need `_session` to quire authentication
-)]
 async fn wms_legend_graphic_handler<C: ApplicationContext>(
-    // TODO: incorporate workflow and `GetLegendGraphic` query
-    // _workflow: web::Path<WorkflowId>,
-    // _request: web::Query<GetLegendGraphic>,
-    // _app_ctx: web::Data<C>,
-    _session: C::Session,
-) -> HttpResponse {
-    HttpResponse::NotImplemented().finish()
+    workflow: web::Path<WorkflowId>,
+    request: web::Query<GetLegendGraphic>,
+    app_ctx: web::Data<C>,
+    session: C::Session,
+) -> Result<HttpResponse> {
+    async fn compute_result<C: ApplicationContext>(
+        workflow: web::Path<WorkflowId>,
+        request: &web::Query<GetLegendGraphic>,
+        app_ctx: web::Data<C>,
+        session: C::Session,
+    ) -> Result<Vec<u8>> {
+        let endpoint = workflow.into_inner();
+        let layer = WorkflowId::from_str(&request.layer)?;
+
+        ensure!(
+            endpoint == layer,
+            error::WMSEndpointLayerMissmatch { endpoint, layer }
+        );
+
+        let ctx = app_ctx.session_context(session);
+
+        let workflow = ctx.db().load_workflow(&layer).await?;
+
+        let operator = workflow.operator.get_raster().context(error::Operator)?;
+
+        let execution_context = ctx.execution_context()?;
+
+        let initialized = operator
+            .initialize(WorkflowOperatorPath::initialize_root(), &execution_context)
+            .await
+            .context(error::Operator)?;
+
+        let result_descriptor = initialized.result_descriptor();
+
+        let raster_colorizer =
+            raster_colorizer_from_style(request.style.as_deref().unwrap_or_default())?;
+
+        // use the same default colorizer as `GetMap` if there is no style
+        let (band, colorizer): (u32, Colorizer) = match raster_colorizer {
+            Some(RasterColorizer::SingleBand {
+                band,
+                band_colorizer,
+            }) => (band, band_colorizer.into()),
+            None => (
+                0,
+                default_colorizer_for_data_type(result_descriptor.data_type)?,
+            ),
+        };
+
+        let band_descriptor =
+            result_descriptor
+                .bands
+                .bands()
+                .get(band as usize)
+                .ok_or(error::Error::Operator {
+                    source: geoengine_operators::error::Error::BandDoesNotExist { band_idx: band },
+                })?;
+
+        colorizer
+            .to_legend_png(&legend_options(band_descriptor))
+            .map_err(error::Error::from)
+    }
+
+    match compute_result(workflow, &request, app_ctx, session).await {
+        Ok(image_bytes) => Ok(HttpResponse::Ok()
+            .content_type(mime::IMAGE_PNG)
+            .body(image_bytes)),
+        Err(error) => Ok(handle_wms_error(request.exceptions, &error)),
+    }
+}
+
+/// Labels the legend with the band's measurement and unit or its classes
+fn legend_options(band_descriptor: &RasterBandDescriptor) -> LegendOptions {
+    match &band_descriptor.measurement {
+        Measurement::Unitless => LegendOptions {
+            title: Some(band_descriptor.name.clone()),
+            class_labels: HashMap::new(),
+        },
+        Measurement::Continuous(ContinuousMeasurement {
+            measurement,
+            unit: Some(unit),
+        }) => LegendOptions {
+            title: Some(format!("{measurement} ({unit})")),
+            class_labels: HashMap::new(),
+        },
+        Measurement::Continuous(ContinuousMeasurement {
+            measurement,
+            unit: None,
+        }) => LegendOptions {
+            title: Some(measurement.clone()),
+            class_labels: HashMap::new(),
+        },
+        Measurement::Classification(ClassificationMeasurement {
+            measurement,
+            classes,
+        }) => LegendOptions {
+            title: Some(measurement.clone()),
+            class_labels: classes.clone(),
+        },
+    }
+}
+
+fn default_colorizer_for_data_type(data_type: RasterDataType) -> Result<Colorizer> {
+    match data_type {
+        RasterDataType::U8 => default_colorizer_gradient::<u8>(),
+        RasterDataType::U16 => default_colorizer_gradient::<u16>(),
+        RasterDataType::U32 => default_colorizer_gradient::<u32>(),
+        RasterDataType::U64 => default_colorizer_gradient::<u64>(),
+        RasterDataType::I8 => default_colorizer_gradient::<i8>(),
+        RasterDataType::I16 => default_colorizer_gradient::<i16>(),
+        RasterDataType::I32 => default_colorizer_gradient::<i32>(),
+        RasterDataType::I64 => default_colorizer_gradient::<i64>(),
+        RasterDataType::F32 => default_colorizer_gradient::<f32>(),
+        RasterDataType::F64 => default_colorizer_gradient::<f64>(),
+    }
+    .context(error::Operator)
 }
 
 fn default_time_from_config() -> TimeInterval {
@@ -487,18 +1195,22 @@ mod tests {
         check_allowed_http_methods, read_body_string, register_ndvi_workflow_helper,
         register_ndvi_workflow_helper_with_cache_ttl, send_test_request,
     };
+    use crate::workflows::workflow::Workflow;
     use actix_http::header::{self, CONTENT_TYPE};
     use actix_web::dev::ServiceResponse;
     use actix_web::http::Method;
     use actix_web_httpauth::headers::authorization::Bearer;
+    use geoengine_datatypes::collections::MultiPointCollection;
     use geoengine_datatypes::operations::image::{Colorizer, RgbaColor};
-    use geoengine_datatypes::primitives::CacheTtlSeconds;
+    use geoengine_datatypes::primitives::{CacheTtlSeconds, FeatureData, MultiPoint};
     use geoengine_datatypes::raster::{GridShape2D, RasterDataType, TilingSpecification};
     use geoengine_operators::engine::{
-        ExecutionContext, RasterQueryProcessor, RasterResultDescriptor,
+        ExecutionContext, RasterQueryProcessor, RasterResultDescriptor, VectorOperator,
     };
+    use geoengine_operators::mock::MockFeatureCollectionSource;
     use geoengine_operators::source::GdalSourceProcessor;
     use geoengine_operators::util::gdal::create_ndvi_meta_data;
+    use geoengine_operators::util::raster_stream_to_png::raster_stream_to_png_bytes;
     use std::convert::TryInto;
     use std::marker::PhantomData;
     use tokio_postgres::NoTls;
@@ -1102,4 +1814,426 @@ mod tests {
                 || cache_header == "private, max-age=58"
         );
     }
+
+    #[ge_context::test]
+    async fn get_feature_info_raster(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?service=WMS&version=1.3.0&request=GetFeatureInfo&layers={id}&query_layers={id}&styles=&width=360&height=180&crs=EPSG:4326&bbox=-90.0,-180.0,90.0,180.0&i=190&j=40&info_format=application/json&time=2014-04-01T12%3A00%3A00.000%2B00%3A00", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+
+        let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+
+        let features = body["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+
+        assert_eq!(
+            features[0]["geometry"],
+            json!({
+                "type": "Point",
+                "coordinates": [10.5, 49.5]
+            })
+        );
+        assert_eq!(
+            features[0]["when"],
+            json!({
+                "start": "2014-04-01T00:00:00+00:00",
+                "end": "2014-05-01T00:00:00+00:00",
+                "type": "Interval"
+            })
+        );
+        assert!(features[0]["properties"]["ndvi"].is_number());
+    }
+
+    #[ge_context::test]
+    async fn get_feature_info_raster_xml(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?service=WMS&version=1.3.0&request=GetFeatureInfo&layers={id}&query_layers={id}&styles=&width=360&height=180&crs=EPSG:4326&bbox=-90.0,-180.0,90.0,180.0&i=190&j=40&time=2014-04-01T12%3A00%3A00.000%2B00%3A00", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "text/xml");
+
+        let body = actix_web::test::read_body(response).await;
+        let reader = ParserConfig::default().create_reader(body.as_ref());
+
+        for event in reader {
+            assert!(event.is_ok());
+        }
+    }
+
+    #[ge_context::test]
+    async fn get_feature_info_out_of_bounds(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?service=WMS&version=1.3.0&request=GetFeatureInfo&layers={id}&query_layers={id}&styles=&width=360&height=180&crs=EPSG:4326&bbox=-90.0,-180.0,90.0,180.0&i=360&j=40&info_format=application/json&exceptions=application/json", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        ErrorResponse::assert(
+            response,
+            200,
+            "WmsFeatureInfoPixelOutOfBounds",
+            "WMS GetFeatureInfo pixel (360, 40) must be inside the map of size 360x180",
+        )
+        .await;
+    }
+
+    async fn register_vector_workflow_helper(app_ctx: &PostgresContext<NoTls>) -> WorkflowId {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![(10.5, 39.5), (-100.5, -30.5)]).unwrap(),
+            vec![geoengine_datatypes::primitives::TimeInterval::default(); 2],
+            HashMap::from([(
+                "name".to_string(),
+                FeatureData::Text(vec!["Bello".to_string(), "Rex & Co".to_string()]),
+            )]),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let workflow = Workflow {
+            operator: MockFeatureCollectionSource::single(collection)
+                .boxed()
+                .into(),
+        };
+
+        ctx.db().register_workflow(workflow).await.unwrap()
+    }
+
+    #[ge_context::test]
+    async fn get_feature_info_vector(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let id = register_vector_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?service=WMS&version=1.3.0&request=GetFeatureInfo&layers={id}&query_layers={id}&styles=&width=360&height=180&crs=EPSG:4326&bbox=-90.0,-180.0,90.0,180.0&i=190&j=50&feature_count=10&info_format=application/json", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+
+        let body: serde_json::Value = actix_web::test::read_body_json(response).await;
+
+        let features = body["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+
+        assert_eq!(
+            features[0]["geometry"],
+            json!({
+                "type": "Point",
+                "coordinates": [10.5, 39.5]
+            })
+        );
+        assert_eq!(features[0]["properties"], json!({"name": "Bello"}));
+    }
+
+    #[ge_context::test]
+    async fn get_feature_info_vector_html(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let id = register_vector_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?service=WMS&version=1.3.0&request=GetFeatureInfo&layers={id}&query_layers={id}&styles=&width=360&height=180&crs=EPSG:4326&bbox=-90.0,-180.0,90.0,180.0&i=79&j=120&info_format=text/html", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+
+        let body = read_body_string(response).await;
+
+        assert!(body.starts_with("<!DOCTYPE html>"));
+        assert!(body.contains("<tr><td>name</td><td>Rex &amp; Co</td></tr>"));
+        assert!(!body.contains("Bello"));
+    }
+
+    #[ge_context::test]
+    async fn get_feature_info_vector_without_hits_html(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let id = register_vector_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?service=WMS&version=1.3.0&request=GetFeatureInfo&layers={id}&query_layers={id}&styles=&width=360&height=180&crs=EPSG:4326&bbox=-90.0,-180.0,90.0,180.0&i=0&j=0&info_format=text/html", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        assert_eq!(response.status(), 200);
+
+        let body = read_body_string(response).await;
+
+        assert!(body.contains("<p>No features found</p>"));
+    }
+
+    #[ge_context::test]
+    async fn get_legend_graphic(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+        let colorizer = Colorizer::linear_gradient(
+            vec![
+                (0.0, RgbaColor::white()).try_into().unwrap(),
+                (255.0, RgbaColor::black()).try_into().unwrap(),
+            ],
+            RgbaColor::transparent(),
+            RgbaColor::white(),
+            RgbaColor::black(),
+        )
+        .unwrap();
+
+        let raster_colorizer = RasterColorizer::SingleBand {
+            band: 0,
+            band_colorizer: colorizer.clone().into(),
+        };
+
+        let params = &[
+            ("request", "GetLegendGraphic"),
+            ("service", "WMS"),
+            ("version", "1.3.0"),
+            ("layer", &id.to_string()),
+            (
+                "style",
+                &format!(
+                    "custom:{}",
+                    serde_json::to_string(&raster_colorizer).unwrap()
+                ),
+            ),
+            ("format", "image/png"),
+        ];
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!(
+                "/wms/{}?{}",
+                id,
+                serde_urlencoded::to_string(params).unwrap()
+            ))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let res = send_test_request(req, app_ctx).await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/png");
+
+        let image_bytes = actix_web::test::read_body(res).await;
+
+        // the ndvi band measures vegetation without a unit
+        let expected = colorizer
+            .to_legend_png(&LegendOptions {
+                title: Some("vegetation".to_string()),
+                class_labels: HashMap::new(),
+            })
+            .unwrap();
+
+        assert_eq!(expected, image_bytes);
+    }
+
+    #[ge_context::test]
+    async fn get_legend_graphic_rgba_error(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+        let raster_colorizer = RasterColorizer::SingleBand {
+            band: 0,
+            band_colorizer: Colorizer::rgba().into(),
+        };
+
+        let params = &[
+            ("request", "GetLegendGraphic"),
+            ("service", "WMS"),
+            ("version", "1.3.0"),
+            ("layer", &id.to_string()),
+            (
+                "style",
+                &format!(
+                    "custom:{}",
+                    serde_json::to_string(&raster_colorizer).unwrap()
+                ),
+            ),
+            ("exceptions", "application/json"),
+        ];
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&format!(
+                "/wms/{}?{}",
+                id,
+                serde_urlencoded::to_string(params).unwrap()
+            ))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+        let response = send_test_request(req, app_ctx).await;
+
+        assert_eq!(response.status(), 200);
+
+        let body: ErrorResponse = actix_web::test::read_body_json(response).await;
+        assert_eq!(body.error, "DataType");
+    }
+
+    async fn get_map_format_test_helper(
+        app_ctx: PostgresContext<NoTls>,
+        parameters: &str,
+    ) -> ServiceResponse {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+        let session_id = ctx.session().id();
+
+        let (_, id) = register_ndvi_workflow_helper(&app_ctx).await;
+
+        let req = actix_web::test::TestRequest::get().uri(&format!("/wms/{id}?request=GetMap&service=WMS&version=1.3.0&layers={id}&bbox=20,-10,80,50&width=200&height=200&styles=&time=2014-01-01T00:00:00.0Z&{parameters}", id = id.to_string())).append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())));
+
+        send_test_request(req, app_ctx).await
+    }
+
+    #[ge_context::test]
+    async fn get_map_jpeg(app_ctx: PostgresContext<NoTls>) {
+        let res = get_map_format_test_helper(
+            app_ctx,
+            "crs=EPSG:4326&format=image/jpeg&quality=50&bgcolor=0x000000",
+        )
+        .await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/jpeg");
+
+        let image_bytes = actix_web::test::read_body(res).await;
+        assert_eq!(&image_bytes[..3], &[0xFF, 0xD8, 0xFF]);
+    }
+
+    #[ge_context::test]
+    async fn get_map_webp(app_ctx: PostgresContext<NoTls>) {
+        let res = get_map_format_test_helper(app_ctx, "crs=EPSG:4326&format=image/webp").await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/webp");
+
+        let image_bytes = actix_web::test::read_body(res).await;
+        assert_eq!(&image_bytes[..4], b"RIFF");
+        assert_eq!(&image_bytes[8..12], b"WEBP");
+    }
+
+    #[ge_context::test]
+    async fn get_map_webp_quality(app_ctx: PostgresContext<NoTls>) {
+        let res =
+            get_map_format_test_helper(app_ctx.clone(), "crs=EPSG:4326&format=image/webp").await;
+        assert_eq!(res.status(), 200);
+        let lossless_bytes = actix_web::test::read_body(res).await;
+
+        let res = get_map_format_test_helper(
+            app_ctx.clone(),
+            "crs=EPSG:4326&format=image/webp&quality=10",
+        )
+        .await;
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/webp");
+        let lossy_bytes = actix_web::test::read_body(res).await;
+
+        assert_eq!(&lossy_bytes[8..12], b"WEBP");
+        assert!(lossy_bytes.len() < lossless_bytes.len());
+
+        let res = get_map_format_test_helper(
+            app_ctx,
+            "crs=EPSG:4326&format=image/webp&quality=0&exceptions=JSON",
+        )
+        .await;
+
+        ErrorResponse::assert(
+            res,
+            200,
+            "WmsInvalidImageQuality",
+            "WMS image quality must be between 1 and 100, but is 0",
+        )
+        .await;
+    }
+
+    #[ge_context::test]
+    async fn get_map_tiff(app_ctx: PostgresContext<NoTls>) {
+        let res = get_map_format_test_helper(app_ctx, "crs=EPSG:4326&format=image/tiff").await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/tiff");
+
+        let image_bytes = actix_web::test::read_body(res).await;
+        assert_eq!(&image_bytes[..2], b"II");
+    }
+
+    #[ge_context::test]
+    async fn get_map_invalid_bgcolor(app_ctx: PostgresContext<NoTls>) {
+        let res = get_map_format_test_helper(
+            app_ctx,
+            "crs=EPSG:4326&format=image/jpeg&bgcolor=0xGGGGGG&exceptions=JSON",
+        )
+        .await;
+
+        ErrorResponse::assert(
+            res,
+            200,
+            "WmsInvalidBackgroundColor",
+            "WMS background color must be of the form 0xRRGGBB, but is 0xGGGGGG",
+        )
+        .await;
+    }
+
+    #[ge_context::test]
+    async fn get_map_in_image_error(app_ctx: PostgresContext<NoTls>) {
+        let res =
+            get_map_format_test_helper(app_ctx, "crs=EPSG:432&format=image/png&exceptions=INIMAGE")
+                .await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/png");
+
+        let image_bytes = actix_web::test::read_body(res).await;
+        assert_eq!(&image_bytes[1..4], b"PNG");
+    }
+
+    #[ge_context::test]
+    async fn get_map_blank_error(app_ctx: PostgresContext<NoTls>) {
+        let res =
+            get_map_format_test_helper(app_ctx, "crs=EPSG:432&format=image/jpeg&exceptions=BLANK")
+                .await;
+
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "image/jpeg");
+
+        let image_bytes = actix_web::test::read_body(res).await;
+        assert_eq!(&image_bytes[..3], &[0xFF, 0xD8, 0xFF]);
+    }
+
+    #[test]
+    fn it_parses_bgcolor() {
+        assert_eq!(parse_bgcolor(None).unwrap(), RgbaColor::white());
+        assert_eq!(
+            parse_bgcolor(Some("0xFF8000")).unwrap(),
+            RgbaColor::new(255, 128, 0, 255)
+        );
+        assert_eq!(
+            parse_bgcolor(Some("#00ff00")).unwrap(),
+            RgbaColor::new(0, 255, 0, 255)
+        );
+        assert!(parse_bgcolor(Some("0x+12345")).is_err());
+        assert!(parse_bgcolor(Some("red")).is_err());
+    }
 }
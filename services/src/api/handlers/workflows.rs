@@ -3,31 +3,30 @@ use crate::api::model::datatypes::{BandSelection, DataId, TimeInterval};
 use crate::api::model::responses::IdResponse;
 use crate::api::ogc::util::{parse_bbox, parse_time};
 use crate::contexts::{ApplicationContext, SessionContext};
-use crate::datasets::listing::{DatasetProvider, Provenance, ProvenanceOutput};
-use crate::datasets::{schedule_raster_dataset_from_workflow_task, RasterDatasetFromWorkflow};
+use crate::datasets::listing::Provenance;
+use crate::datasets::{
+    schedule_raster_dataset_from_workflow_task, schedule_vector_dataset_from_workflow_task,
+    RasterDatasetFromWorkflow, VectorDatasetFromWorkflow,
+};
 use crate::error::Result;
-use crate::layers::storage::LayerProviderDb;
 use crate::util::config::get_config_element;
 use crate::util::parsing::{
     parse_band_selection, parse_spatial_partition, parse_spatial_resolution,
 };
+use crate::workflows::provenance::{workflow_provenance, WorkflowProvenance};
 use crate::workflows::registry::WorkflowRegistry;
 use crate::workflows::workflow::{Workflow, WorkflowId};
 use crate::workflows::{RasterWebsocketStreamHandler, VectorWebsocketStreamHandler};
 use actix_web::{web, FromRequest, HttpRequest, HttpResponse, Responder};
-use futures::future::join_all;
 use geoengine_datatypes::error::{BoxedResultExt, ErrorSource};
 use geoengine_datatypes::primitives::{
     BoundingBox2D, ColumnSelection, RasterQueryRectangle, SpatialPartition2D, SpatialResolution,
     VectorQueryRectangle,
 };
 use geoengine_operators::call_on_typed_operator;
-use geoengine_operators::engine::{
-    ExecutionContext, OperatorData, TypedOperator, TypedResultDescriptor, WorkflowOperatorPath,
-};
+use geoengine_operators::engine::{TypedOperator, TypedResultDescriptor, WorkflowOperatorPath};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::collections::HashMap;
 use std::io::{Cursor, Write};
 use std::sync::Arc;
 use utoipa::{IntoParams, ToSchema};
@@ -70,6 +69,10 @@ where
     .service(
         web::resource("datasetFromWorkflow/{id}")
             .route(web::post().to(dataset_from_workflow_handler::<C>)),
+    )
+    .service(
+        web::resource("vectorDatasetFromWorkflow/{id}")
+            .route(web::post().to(vector_dataset_from_workflow_handler::<C>)),
     );
 }
 
@@ -263,7 +266,11 @@ async fn get_workflow_provenance_handler<C: ApplicationContext>(
 
     let workflow: Workflow = ctx.db().load_workflow(&id.into_inner()).await?;
 
-    let provenance = workflow_provenance(&workflow, &ctx).await?;
+    let provenance: Vec<ProvenanceEntry> = workflow_provenance(&workflow, &ctx)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
 
     Ok(web::Json(provenance))
 }
@@ -274,51 +281,13 @@ pub struct ProvenanceEntry {
     data: Vec<DataId>,
 }
 
-async fn workflow_provenance<C: SessionContext>(
-    workflow: &Workflow,
-    ctx: &C,
-) -> Result<Vec<ProvenanceEntry>> {
-    let db = ctx.db();
-    let execution_ctx = ctx.execution_context()?;
-
-    let data_names = workflow.operator.data_names();
-    let mut datasets = Vec::<DataId>::with_capacity(data_names.len());
-    for data_name in data_names {
-        let data_id = execution_ctx.resolve_named_data(&data_name).await?;
-        datasets.push(data_id.into());
-    }
-
-    let provenance: Vec<_> = datasets
-        .iter()
-        .map(|id| resolve_provenance::<C>(&db, id))
-        .collect();
-    let provenance: Result<Vec<_>> = join_all(provenance).await.into_iter().collect();
-
-    // Filter duplicate DataIds and missing Provenance
-    let provenance: HashMap<DataId, Vec<Provenance>> = provenance?
-        .into_iter()
-        .filter_map(|p| {
-            if let Some(provenance) = p.provenance {
-                Some((p.data.into(), provenance))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    // Group DataIds by Provenance
-    let mut result: HashMap<Provenance, Vec<DataId>> = HashMap::new();
-    for (data, provenances) in provenance {
-        for item in provenances {
-            result.entry(item).or_default().push(data.clone());
+impl From<WorkflowProvenance> for ProvenanceEntry {
+    fn from(value: WorkflowProvenance) -> Self {
+        Self {
+            provenance: value.provenance,
+            data: value.data.into_iter().map(Into::into).collect(),
         }
     }
-    let result = result
-        .into_iter()
-        .map(|(provenance, data)| ProvenanceEntry { provenance, data })
-        .collect();
-
-    Ok(result)
 }
 
 /// Gets a ZIP archive of the worklow, its provenance and the output metadata.
@@ -351,6 +320,7 @@ async fn get_workflow_all_metadata_zip_handler<C: ApplicationContext>(
         workflow_metadata::<C::SessionContext>(workflow.clone(), ctx.execution_context()?),
         workflow_provenance(&workflow, &ctx),
     )?;
+    let provenance: Vec<ProvenanceEntry> = provenance.into_iter().map(Into::into).collect();
 
     let output = crate::util::spawn_blocking(move || {
         let mut output = Vec::new();
@@ -415,21 +385,6 @@ async fn get_workflow_all_metadata_zip_handler<C: ApplicationContext>(
     Ok(response)
 }
 
-async fn resolve_provenance<C: SessionContext>(
-    db: &C::GeoEngineDB,
-    id: &DataId,
-) -> Result<ProvenanceOutput> {
-    match id {
-        DataId::Internal { dataset_id } => db.load_provenance(&dataset_id.into()).await,
-        DataId::External(e) => {
-            db.load_layer_provider(e.provider_id.into())
-                .await?
-                .provenance(&id.into())
-                .await
-        }
-    }
-}
-
 /// Create a task for creating a new dataset from the result of the workflow given by its `id` and the dataset parameters in the request body.
 /// Returns the id of the created task
 #[utoipa::path(
@@ -474,6 +429,48 @@ async fn dataset_from_workflow_handler<C: ApplicationContext>(
     Ok(web::Json(TaskResponse::new(task_id)))
 }
 
+/// Create a task for creating a new vector dataset from the result of the workflow given by its `id` and the dataset parameters in the request body.
+/// The features are stored as a `GeoPackage` or `FlatGeobuf` file.
+/// Returns the id of the created task
+#[utoipa::path(
+    tag = "Workflows",
+    post,
+    path = "/vectorDatasetFromWorkflow/{id}",
+    request_body = VectorDatasetFromWorkflow,
+    responses(
+        (status = 200, description = "Id of created task", body = TaskResponse,
+            example = json!({"taskId": "7f8a4cfe-76ab-4972-b347-b197e5ef0f3c"})
+        )
+    ),
+    params(
+        ("id" = WorkflowId, description = "Workflow id")
+    ),
+    security(
+        ("session_token" = [])
+    )
+)]
+async fn vector_dataset_from_workflow_handler<C: ApplicationContext>(
+    id: web::Path<WorkflowId>,
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    info: web::Json<VectorDatasetFromWorkflow>,
+) -> Result<web::Json<TaskResponse>> {
+    let ctx = Arc::new(app_ctx.session_context(session));
+
+    let id = id.into_inner();
+    let workflow = ctx.db().load_workflow(&id).await?;
+
+    let task_id = schedule_vector_dataset_from_workflow_task(
+        format!("workflow {id}"),
+        workflow,
+        ctx,
+        info.into_inner(),
+    )
+    .await?;
+
+    Ok(web::Json(TaskResponse::new(task_id)))
+}
+
 /// The query parameters for `raster_stream_websocket`.
 #[derive(Clone, Debug, PartialEq, Deserialize, IntoParams)]
 #[serde(rename_all = "camelCase")]
@@ -674,7 +671,9 @@ mod tests {
     use crate::api::model::responses::ErrorResponse;
     use crate::contexts::{PostgresContext, Session, SimpleApplicationContext};
     use crate::datasets::storage::DatasetStore;
-    use crate::datasets::{DatasetName, RasterDatasetFromWorkflowResult};
+    use crate::datasets::{
+        DatasetName, RasterDatasetFromWorkflowResult, VectorDatasetFromWorkflowResult,
+    };
     use crate::ge_context;
     use crate::tasks::util::test::wait_for_task_to_finish;
     use crate::tasks::{TaskManager, TaskStatus};
@@ -687,7 +686,8 @@ mod tests {
     use actix_web::dev::ServiceResponse;
     use actix_web::{http::header, http::Method, test};
     use actix_web_httpauth::headers::authorization::Bearer;
-    use geoengine_datatypes::collections::MultiPointCollection;
+    use futures::TryStreamExt;
+    use geoengine_datatypes::collections::{ChunksEqualIgnoringCacheHint, MultiPointCollection};
     use geoengine_datatypes::primitives::CacheHint;
     use geoengine_datatypes::primitives::{
         ContinuousMeasurement, FeatureData, Measurement, MultiPoint, RasterQueryRectangle,
@@ -696,8 +696,8 @@ mod tests {
     use geoengine_datatypes::raster::{GridShape, RasterDataType, TilingSpecification};
     use geoengine_datatypes::spatial_reference::SpatialReference;
     use geoengine_operators::engine::{
-        ExecutionContext, MultipleRasterOrSingleVectorSource, PlotOperator, RasterBandDescriptor,
-        RasterBandDescriptors, TypedOperator,
+        ExecutionContext, MultipleRasterOrSingleVectorSource, PlotOperator, QueryProcessor,
+        RasterBandDescriptor, RasterBandDescriptors, TypedOperator,
     };
     use geoengine_operators::engine::{RasterOperator, RasterResultDescriptor, VectorOperator};
     use geoengine_operators::mock::{
@@ -705,7 +705,9 @@ mod tests {
         MockRasterSourceParams,
     };
     use geoengine_operators::plot::{Statistics, StatisticsParams};
-    use geoengine_operators::source::{GdalSource, GdalSourceParameters};
+    use geoengine_operators::source::{
+        GdalSource, GdalSourceParameters, OgrSource, OgrSourceParameters,
+    };
     use geoengine_operators::util::input::MultiRasterOrVectorOperator::Raster;
     use geoengine_operators::util::raster_stream_to_geotiff::{
         single_timestep_raster_stream_to_geotiff_bytes, GdalGeoTiffDatasetMetadata,
@@ -1495,4 +1497,164 @@ mod tests {
             result.as_slice()
         );
     }
+
+    #[ge_context::test]
+    #[allow(clippy::too_many_lines)]
+    async fn vector_dataset_from_workflow_task_success(app_ctx: PostgresContext<NoTls>) {
+        let ctx = app_ctx.default_session_context().await.unwrap();
+
+        let session_id = app_ctx.default_session_id().await;
+
+        let collection = MultiPointCollection::from_data(
+            MultiPoint::many(vec![vec![(0.0, 0.1)], vec![(1.0, 1.1), (2.0, 2.1)]]).unwrap(),
+            vec![
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20),
+            ],
+            [
+                ("foo".to_string(), FeatureData::Float(vec![42.0, 43.0])),
+                (
+                    "bar".to_string(),
+                    FeatureData::NullableText(vec![Some("baz".to_string()), None]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap();
+
+        let workflow = Workflow {
+            operator: MockFeatureCollectionSource::single(collection)
+                .boxed()
+                .into(),
+        };
+
+        let workflow_id = ctx.db().register_workflow(workflow).await.unwrap();
+
+        // create dataset from workflow
+        let req = test::TestRequest::post()
+            .uri(&format!("/vectorDatasetFromWorkflow/{workflow_id}"))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .append_header((header::CONTENT_TYPE, mime::APPLICATION_JSON))
+            .set_payload(
+                r#"{
+                "displayName": "foo",
+                "description": null,
+                "query": {
+                    "spatialBounds": {
+                        "lowerLeftCoordinate": {
+                            "x": -180.0,
+                            "y": -90.0
+                        },
+                        "upperRightCoordinate": {
+                            "x": 180.0,
+                            "y": 90.0
+                        }
+                    },
+                    "timeInterval": {
+                        "start": 0,
+                        "end": 20
+                    },
+                    "spatialResolution": {
+                        "x": 0.1,
+                        "y": 0.1
+                    }
+                },
+                "format": "geoPackage"
+            }"#,
+            );
+        let res = send_test_request(req, app_ctx.clone()).await;
+
+        assert_eq!(res.status(), 200, "{:?}", res.response());
+
+        let task_response =
+            serde_json::from_str::<TaskResponse>(&read_body_string(res).await).unwrap();
+
+        let tasks = Arc::new(ctx.tasks());
+
+        wait_for_task_to_finish(tasks.clone(), task_response.task_id).await;
+
+        let status = tasks.get_task_status(task_response.task_id).await.unwrap();
+
+        let response = if let TaskStatus::Completed { info, .. } = status {
+            info.as_any_arc()
+                .downcast::<VectorDatasetFromWorkflowResult>()
+                .unwrap()
+                .as_ref()
+                .clone()
+        } else {
+            panic!("Task must be completed");
+        };
+
+        // automatically deletes uploads on drop
+        let _test_uploads = TestDataUploads {
+            uploads: vec![response.upload],
+        };
+
+        // query the newly created dataset
+        let op = OgrSource {
+            params: OgrSourceParameters {
+                data: response.dataset.into(),
+                attribute_projection: None,
+                attribute_filters: None,
+            },
+        }
+        .boxed();
+
+        let exe_ctx = ctx.execution_context().unwrap();
+
+        let o = op
+            .initialize(WorkflowOperatorPath::initialize_root(), &exe_ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            o.result_descriptor().time,
+            Some(TimeInterval::new_unchecked(0, 20))
+        );
+
+        let query_ctx = ctx.query_context().unwrap();
+        let query_rect = VectorQueryRectangle {
+            spatial_bounds: BoundingBox2D::new((-180., -90.).into(), (180., 90.).into()).unwrap(),
+            time_interval: TimeInterval::new_unchecked(0, 20),
+            spatial_resolution: SpatialResolution::zero_point_one(),
+            attributes: ColumnSelection::all(),
+        };
+
+        let processor = o.query_processor().unwrap().multi_point().unwrap();
+
+        let result: Vec<MultiPointCollection> = processor
+            .query(query_rect, &query_ctx)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert!(result[0].chunks_equal_ignoring_cache_hint(
+            &MultiPointCollection::from_data(
+                MultiPoint::many(vec![vec![(0.0, 0.1)], vec![(1.0, 1.1), (2.0, 2.1)]]).unwrap(),
+                vec![
+                    TimeInterval::new_unchecked(0, 10),
+                    TimeInterval::new_unchecked(10, 20),
+                ],
+                [
+                    (
+                        "foo".to_string(),
+                        FeatureData::NullableFloat(vec![Some(42.0), Some(43.0)]),
+                    ),
+                    (
+                        "bar".to_string(),
+                        FeatureData::NullableText(vec![Some("baz".to_string()), None]),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+                CacheHint::default(),
+            )
+            .unwrap()
+        ));
+    }
 }
@@ -996,6 +996,17 @@ impl From<QueryRectangle<SpatialPartition2D>>
     }
 }
 
+impl From<QueryRectangle<BoundingBox2D>> for geoengine_datatypes::primitives::VectorQueryRectangle {
+    fn from(value: QueryRectangle<BoundingBox2D>) -> Self {
+        Self {
+            spatial_bounds: value.spatial_bounds.into(),
+            time_interval: value.time_interval.into(),
+            spatial_resolution: value.spatial_resolution.into(),
+            attributes: geoengine_datatypes::primitives::ColumnSelection::all(), // TODO: adjust once API supports attribute selection
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, ToSchema)]
 pub struct BandSelection(pub Vec<usize>);
 
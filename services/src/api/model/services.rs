@@ -1,6 +1,7 @@
+use crate::api::model::datatypes::TimeInstance;
 use crate::api::model::operators::{
-    GdalMetaDataList, GdalMetaDataRegular, GdalMetaDataStatic, GdalMetadataNetCdfCf, MockMetaData,
-    OgrMetaData,
+    GdalLoadingInfoTemporalSlice, GdalMetaDataList, GdalMetaDataRegular, GdalMetaDataStatic,
+    GdalMetadataNetCdfCf, MockMetaData, OgrMetaData,
 };
 use crate::datasets::storage::validate_tags;
 use crate::datasets::upload::{UploadId, VolumeName};
@@ -136,6 +137,40 @@ pub enum DataPath {
     Upload(UploadId),
 }
 
+/// Data to append to an existing dataset.
+/// The variant must match the type of the dataset's meta data.
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(tag = "type")]
+pub enum AppendDataset {
+    GdalMetaDataList(AppendGdalMetaDataList),
+    GdalMetaDataRegular(AppendGdalMetaDataRegular),
+    OgrMetaData(AppendOgrMetaData),
+}
+
+/// New temporal slices whose file paths are relative to the given upload or volume
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppendGdalMetaDataList {
+    pub data_path: DataPath,
+    pub params: Vec<GdalLoadingInfoTemporalSlice>,
+}
+
+/// A new (exclusive) end of the time range that is covered by the dataset's files
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppendGdalMetaDataRegular {
+    pub data_time_end: TimeInstance,
+}
+
+/// A layer of an uploaded file whose features are added to the dataset's file
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AppendOgrMetaData {
+    pub upload: UploadId,
+    pub main_file: String,
+    pub layer_name: String,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema, Validate)]
 pub struct UpdateDataset {
     pub name: DatasetName,
@@ -0,0 +1,217 @@
+use crate::api::model::datatypes::TimeInterval;
+use crate::api::ogc::wfs::request::parse_property_names_option;
+use crate::util::from_str_option;
+use geoengine_datatypes::primitives::{BoundingBox2D, Coordinate2D, DateTime, TimeInstance};
+use serde::de::Error;
+use serde::Deserialize;
+use std::str::FromStr;
+use utoipa::IntoParams;
+
+/// The number of features that are returned if no `limit` is requested
+pub const DEFAULT_LIMIT: u32 = 10;
+/// Larger `limit`s are replaced by this value
+pub const MAX_LIMIT: u32 = 10_000;
+
+#[derive(PartialEq, Debug, Deserialize, IntoParams)]
+pub struct GetItems {
+    /// Only features that intersect the bounding box (in CRS84) are returned
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_bbox_option")]
+    #[param(value_type = Option<String>, example = "-180,-90,180,90")]
+    pub bbox: Option<BoundingBox2D>,
+    /// Only features that intersect the instant or interval are returned. Intervals may be half-bounded using `..`.
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_datetime_option")]
+    #[param(value_type = Option<String>, example = "2014-01-01T00:00:00Z/..")]
+    pub datetime: Option<TimeInterval>,
+    /// The maximum number of features in the response
+    #[serde(default)]
+    #[serde(deserialize_with = "from_str_option")]
+    #[param(example = 10)]
+    pub limit: Option<u32>,
+    /// The number of features to skip for paging
+    #[serde(default)]
+    #[serde(deserialize_with = "from_str_option")]
+    #[param(example = 0)]
+    pub offset: Option<u32>,
+    /// The properties that are included in the features
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_property_names_option")]
+    #[param(value_type = Option<String>, example = "name,age")]
+    pub properties: Option<Vec<String>>,
+}
+
+impl GetItems {
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset.unwrap_or(0)
+    }
+
+    /// The requested bounding box or the whole world if it is not set
+    pub fn bounding_box(&self) -> BoundingBox2D {
+        self.bbox.unwrap_or_else(|| {
+            BoundingBox2D::new_unchecked(
+                Coordinate2D::new(-180., -90.),
+                Coordinate2D::new(180., 90.),
+            )
+        })
+    }
+
+    /// The requested time interval or the whole time axis if it is not set
+    pub fn time_interval(&self) -> geoengine_datatypes::primitives::TimeInterval {
+        self.datetime.map_or_else(Default::default, Into::into)
+    }
+}
+
+/// Parse bbox, format is: "minx,miny,maxx,maxy"
+pub fn parse_bbox_option<'de, D>(deserializer: D) -> Result<Option<BoundingBox2D>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    let split: Vec<Result<f64, std::num::ParseFloatError>> = s.split(',').map(str::parse).collect();
+
+    if let [Ok(x1), Ok(y1), Ok(x2), Ok(y2)] = *split.as_slice() {
+        BoundingBox2D::new(Coordinate2D::new(x1, y1), Coordinate2D::new(x2, y2))
+            .map(Some)
+            .map_err(D::Error::custom)
+    } else {
+        Err(D::Error::custom("Invalid bbox"))
+    }
+}
+
+/// Parse the `datetime` parameter of OGC API – Features.
+/// It is either an RFC 3339 instant or an interval separated by "/", where an open start or end
+/// is denoted by ".." or an empty string.
+pub fn parse_datetime_option<'de, D>(deserializer: D) -> Result<Option<TimeInterval>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    let parse_bound = |bound: &str, open: TimeInstance| -> Result<TimeInstance, D::Error> {
+        if bound.is_empty() || bound == ".." {
+            return Ok(open);
+        }
+
+        DateTime::from_str(bound)
+            .map(Into::into)
+            .map_err(|_| D::Error::custom(format!("Invalid datetime {s}")))
+    };
+
+    let (start, end) = match *s.split('/').collect::<Vec<_>>().as_slice() {
+        [instant] => {
+            let instant = parse_bound(instant, TimeInstance::MIN)?;
+            (instant, instant)
+        }
+        [start, end] => (
+            parse_bound(start, TimeInstance::MIN)?,
+            parse_bound(end, TimeInstance::MAX)?,
+        ),
+        _ => return Err(D::Error::custom(format!("Invalid datetime {s}"))),
+    };
+
+    geoengine_datatypes::primitives::TimeInterval::new(start, end)
+        .map(|time| Some(time.into()))
+        .map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let params = &[
+            ("bbox", "-10,-20,10,20"),
+            ("datetime", "2014-01-01T00:00:00Z/.."),
+            ("limit", "100000"),
+            ("offset", "20"),
+            ("properties", "name,age"),
+        ];
+        let string = serde_urlencoded::to_string(params).unwrap();
+
+        let items: GetItems = serde_urlencoded::from_str(&string).unwrap();
+
+        assert_eq!(
+            items,
+            GetItems {
+                bbox: Some(BoundingBox2D::new_unchecked(
+                    (-10., -20.).into(),
+                    (10., 20.).into()
+                )),
+                datetime: Some(
+                    geoengine_datatypes::primitives::TimeInterval::new_unchecked(
+                        1_388_534_400_000,
+                        TimeInstance::MAX
+                    )
+                    .into()
+                ),
+                limit: Some(100_000),
+                offset: Some(20),
+                properties: Some(vec!["name".to_string(), "age".to_string()]),
+            }
+        );
+        assert_eq!(items.limit(), MAX_LIMIT);
+        assert_eq!(items.offset(), 20);
+    }
+
+    #[test]
+    fn deserialize_defaults() {
+        let items: GetItems = serde_urlencoded::from_str("").unwrap();
+
+        assert_eq!(items.limit(), DEFAULT_LIMIT);
+        assert_eq!(items.offset(), 0);
+        assert_eq!(
+            items.bounding_box(),
+            BoundingBox2D::new_unchecked((-180., -90.).into(), (180., 90.).into())
+        );
+        assert_eq!(
+            items.time_interval(),
+            geoengine_datatypes::primitives::TimeInterval::default()
+        );
+    }
+
+    #[test]
+    fn it_parses_datetimes() {
+        let parse = |s: &str| -> Result<Option<TimeInterval>, serde::de::value::Error> {
+            parse_datetime_option(serde::de::IntoDeserializer::into_deserializer(
+                s.to_string(),
+            ))
+        };
+
+        assert_eq!(
+            parse("2014-01-01T00:00:00Z").unwrap(),
+            Some(
+                geoengine_datatypes::primitives::TimeInterval::new_instant(1_388_534_400_000)
+                    .unwrap()
+                    .into()
+            )
+        );
+        assert_eq!(
+            parse("../2014-01-01T00:00:00Z").unwrap(),
+            Some(
+                geoengine_datatypes::primitives::TimeInterval::new_unchecked(
+                    TimeInstance::MIN,
+                    1_388_534_400_000
+                )
+                .into()
+            )
+        );
+        assert!(parse("2014-01-01T00:00:00Z/2013-01-01T00:00:00Z").is_err());
+        assert!(parse("foo").is_err());
+        assert!(parse("a/b/c").is_err());
+    }
+}
@@ -0,0 +1,227 @@
+use geoengine_datatypes::operations::reproject::{
+    CoordinateProjection, CoordinateProjector, ReprojectClipped,
+};
+use geoengine_datatypes::primitives::{
+    AxisAlignedRectangle, BoundingBox2D, Coordinate2D, TimeInstance,
+};
+use geoengine_datatypes::spatial_reference::SpatialReference;
+use geoengine_operators::engine::VectorResultDescriptor;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+pub const CRS84: &str = "http://www.opengis.net/def/crs/OGC/1.3/CRS84";
+pub const GREGORIAN_TRS: &str = "http://www.opengis.net/def/uom/ISO-8601/0/Gregorian";
+
+pub const CONFORMANCE_CLASSES: [&str; 2] = [
+    "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/core",
+    "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/geojson",
+];
+
+pub const MIME_GEO_JSON: &str = "application/geo+json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Link {
+    pub href: String,
+    pub rel: String,
+    #[serde(rename = "type")]
+    pub media_type: String,
+    pub title: String,
+}
+
+impl Link {
+    pub fn new(href: String, rel: &str, media_type: &str, title: &str) -> Self {
+        Self {
+            href,
+            rel: rel.to_string(),
+            media_type: media_type.to_string(),
+            title: title.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct LandingPage {
+    pub title: String,
+    pub description: String,
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Conformance {
+    pub conforms_to: Vec<String>,
+}
+
+impl Default for Conformance {
+    fn default() -> Self {
+        Self {
+            conforms_to: CONFORMANCE_CLASSES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Collections {
+    pub links: Vec<Link>,
+    pub collections: Vec<Collection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Collection {
+    pub id: String,
+    pub title: String,
+    pub extent: Extent,
+    pub item_type: String,
+    pub crs: Vec<String>,
+    pub storage_crs: String,
+    pub links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Extent {
+    pub spatial: SpatialExtent,
+    pub temporal: TemporalExtent,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SpatialExtent {
+    /// Bounding boxes as `[minx, miny, maxx, maxy]`
+    pub bbox: Vec<Vec<f64>>,
+    pub crs: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TemporalExtent {
+    /// Intervals as `[start, end]`, where `null` denotes an open start or end
+    pub interval: Vec<Vec<Option<String>>>,
+    pub trs: String,
+}
+
+impl From<&VectorResultDescriptor> for Extent {
+    fn from(result_descriptor: &VectorResultDescriptor) -> Self {
+        let bbox = spatial_extent_in_crs84(result_descriptor);
+
+        let time_bound = |time: TimeInstance| {
+            (!time.is_min() && !time.is_max()).then(|| time.as_datetime_string())
+        };
+
+        let interval = result_descriptor.time.map_or(vec![None, None], |time| {
+            vec![time_bound(time.start()), time_bound(time.end())]
+        });
+
+        Self {
+            spatial: SpatialExtent {
+                bbox: vec![vec![
+                    bbox.lower_left().x,
+                    bbox.lower_left().y,
+                    bbox.upper_right().x,
+                    bbox.upper_right().y,
+                ]],
+                crs: CRS84.to_string(),
+            },
+            temporal: TemporalExtent {
+                interval: vec![interval],
+                trs: GREGORIAN_TRS.to_string(),
+            },
+        }
+    }
+}
+
+/// The bounding box of the result descriptor in CRS84.
+/// Falls back to the area of use of the spatial reference or the whole world if it is unknown.
+fn spatial_extent_in_crs84(result_descriptor: &VectorResultDescriptor) -> BoundingBox2D {
+    let world =
+        BoundingBox2D::new_unchecked(Coordinate2D::new(-180., -90.), Coordinate2D::new(180., 90.));
+
+    let Some(spatial_reference): Option<SpatialReference> =
+        result_descriptor.spatial_reference.into()
+    else {
+        return world;
+    };
+
+    let bbox = result_descriptor.bbox.and_then(|bbox| {
+        if spatial_reference == SpatialReference::epsg_4326() {
+            return Some(bbox);
+        }
+
+        CoordinateProjector::from_known_srs(spatial_reference, SpatialReference::epsg_4326())
+            .and_then(|projector| bbox.reproject_clipped(&projector))
+            .ok()
+            .flatten()
+    });
+
+    bbox.or_else(|| spatial_reference.area_of_use().ok())
+        .unwrap_or(world)
+}
+
+/// A `GeoJSON` feature collection with paging information
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Items {
+    #[serde(rename = "type")]
+    pub collection_type: String,
+    pub features: Vec<serde_json::Value>,
+    pub number_matched: usize,
+    pub number_returned: usize,
+    pub time_stamp: String,
+    pub links: Vec<Link>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geoengine_datatypes::collections::VectorDataType;
+    use geoengine_datatypes::primitives::TimeInterval;
+    use std::collections::HashMap;
+
+    #[test]
+    fn it_derives_the_extent() {
+        let result_descriptor = VectorResultDescriptor {
+            data_type: VectorDataType::MultiPoint,
+            spatial_reference: SpatialReference::epsg_4326().into(),
+            columns: HashMap::new(),
+            time: Some(TimeInterval::new_unchecked(
+                1_388_534_400_000,
+                TimeInstance::MAX,
+            )),
+            bbox: Some(BoundingBox2D::new_unchecked(
+                (-10., -20.).into(),
+                (10., 20.).into(),
+            )),
+        };
+
+        assert_eq!(
+            serde_json::to_value(Extent::from(&result_descriptor)).unwrap(),
+            serde_json::json!({
+                "spatial": {
+                    "bbox": [[-10.0, -20.0, 10.0, 20.0]],
+                    "crs": CRS84,
+                },
+                "temporal": {
+                    "interval": [["2014-01-01T00:00:00+00:00", null]],
+                    "trs": GREGORIAN_TRS,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn it_falls_back_to_the_area_of_use() {
+        let result_descriptor = VectorResultDescriptor {
+            data_type: VectorDataType::MultiPoint,
+            spatial_reference: SpatialReference::epsg_4326().into(),
+            columns: HashMap::new(),
+            time: None,
+            bbox: None,
+        };
+
+        let extent = Extent::from(&result_descriptor);
+
+        assert_eq!(extent.spatial.bbox, vec![vec![-180., -90., 180., 90.]]);
+        assert_eq!(extent.temporal.interval, vec![vec![None, None]]);
+    }
+}
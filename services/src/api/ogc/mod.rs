@@ -1,3 +1,5 @@
+pub mod features;
+pub mod tiles;
 pub mod util;
 pub mod wcs;
 pub mod wfs;
@@ -0,0 +1,43 @@
+use crate::api::model::datatypes::TimeInterval;
+use crate::api::ogc::features::request::parse_datetime_option;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(PartialEq, Debug, Deserialize, IntoParams)]
+pub struct GetVectorTile {
+    /// Only features that intersect the instant or interval are returned. Intervals may be half-bounded using `..`.
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_datetime_option")]
+    #[param(value_type = Option<String>, example = "2014-01-01T00:00:00Z/..")]
+    pub datetime: Option<TimeInterval>,
+}
+
+impl GetVectorTile {
+    /// The requested time interval or the whole time axis if it is not set
+    pub fn time_interval(&self) -> geoengine_datatypes::primitives::TimeInterval {
+        self.datetime.map_or_else(Default::default, Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let tile: GetVectorTile =
+            serde_urlencoded::from_str("datetime=2014-01-01T00%3A00%3A00Z").unwrap();
+
+        assert_eq!(
+            tile.time_interval(),
+            geoengine_datatypes::primitives::TimeInterval::new_instant(1_388_534_400_000).unwrap()
+        );
+
+        let tile: GetVectorTile = serde_urlencoded::from_str("").unwrap();
+
+        assert_eq!(
+            tile.time_interval(),
+            geoengine_datatypes::primitives::TimeInterval::default()
+        );
+    }
+}
@@ -283,6 +283,7 @@ pub enum OgcProtocol {
     Wcs,
     Wms,
     Wfs,
+    Features,
 }
 
 impl OgcProtocol {
@@ -291,6 +292,7 @@ impl OgcProtocol {
             OgcProtocol::Wcs => "wcs/",
             OgcProtocol::Wms => "wms/",
             OgcProtocol::Wfs => "wfs/",
+            OgcProtocol::Features => "features/",
         }
     }
 }
@@ -182,10 +182,23 @@ impl GridOrigin {
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Deserialize, Serialize, ToSchema)]
 pub enum GetCoverageFormat {
     #[serde(rename = "image/tiff")]
     ImageTiff,
+    #[serde(rename = "image/tiff; application=geotiff; profile=cloud-optimized")]
+    ImageTiffCog,
+    #[serde(rename = "application/x-netcdf")]
+    ApplicationNetCdf,
+}
+
+impl GetCoverageFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            GetCoverageFormat::ImageTiff | GetCoverageFormat::ImageTiffCog => "image/tiff",
+            GetCoverageFormat::ApplicationNetCdf => "application/x-netcdf",
+        }
+    }
 }
 
 /// parse coordinate, format is "x,y"
@@ -303,6 +316,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_deserializes_formats() {
+        assert_eq!(
+            GetCoverageFormat::deserialize(to_deserializer("image/tiff")).unwrap(),
+            GetCoverageFormat::ImageTiff
+        );
+        assert_eq!(
+            GetCoverageFormat::deserialize(to_deserializer(
+                "image/tiff; application=geotiff; profile=cloud-optimized"
+            ))
+            .unwrap(),
+            GetCoverageFormat::ImageTiffCog
+        );
+        assert_eq!(
+            GetCoverageFormat::deserialize(to_deserializer("application/x-netcdf")).unwrap(),
+            GetCoverageFormat::ApplicationNetCdf
+        );
+        assert!(GetCoverageFormat::deserialize(to_deserializer("image/png")).is_err());
+    }
+
     #[test]
     fn it_parses_grid_offset() {
         let s = "-8,5";
@@ -0,0 +1,1233 @@
+use crate::api::ogc::util::tuple_from_ogc_params;
+use crate::error::{self, Result};
+use geo::{Intersects, MapCoords};
+use geoengine_datatypes::collections::{
+    FeatureCollection, FeatureCollectionInfos, IntoGeometryOptionsIterator,
+};
+use geoengine_datatypes::operations::reproject::{
+    CoordinateProjection, CoordinateProjector, Reproject,
+};
+use geoengine_datatypes::primitives::{
+    BoundingBox2D, Coordinate2D, DateTime, FeatureDataType, FeatureDataValue, Geometry,
+    TimeInstance,
+};
+use geoengine_datatypes::spatial_reference::{SpatialReference, SpatialReferenceAuthority};
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use geoengine_operators::engine::VectorColumnInfo;
+use snafu::ensure;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+use xml::reader::{EventReader, XmlEvent};
+
+/// A filter expression of the OGC Filter Encoding (FES) 2.0 standard.
+///
+/// Properties refer to the columns of the feature collection.
+/// Spatial operators always refer to the geometry of the features.
+/// Their geometries are in x/y order and in the spatial reference of the request unless they specify an `srs`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+    Comparison {
+        operator: ComparisonOperator,
+        property: String,
+        literal: String,
+        match_case: bool,
+    },
+    Like {
+        property: String,
+        pattern: String,
+        wild_card: char,
+        single_char: char,
+        escape_char: char,
+        match_case: bool,
+    },
+    IsNull {
+        property: String,
+    },
+    Between {
+        property: String,
+        lower: String,
+        upper: String,
+    },
+    BBox {
+        bbox: BoundingBox2D,
+        srs: Option<GmlSrs>,
+    },
+    Intersects {
+        geometry: geo::Geometry<f64>,
+        srs: Option<GmlSrs>,
+    },
+}
+
+/// The `srsName` of a GML geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GmlSrs {
+    pub spatial_reference: SpatialReference,
+    /// Whether the coordinates follow the axis order of the CRS definition, e.g., lat/lon for `urn:ogc:def:crs:EPSG::4326`.
+    /// Otherwise, they are in x/y order, e.g., for `EPSG:4326`.
+    pub crs_axis_order: bool,
+}
+
+impl GmlSrs {
+    /// Parses the URN (`urn:ogc:def:crs:EPSG::4326`) and URL (`http://www.opengis.net/def/crs/EPSG/0/4326`) forms,
+    /// which use the axis order of the CRS, as well as `CRS84` and the short form `EPSG:4326`, which use x/y order.
+    fn parse(srs_name: &str) -> Result<Self> {
+        let invalid_srs_name = || invalid_filter(&format!("Unsupported srsName {srs_name}"));
+
+        let parse_authority_and_code = |authority: &str, code: &str| -> Result<_> {
+            if authority == "OGC" && code == "CRS84" {
+                return Ok((SpatialReference::epsg_4326(), false));
+            }
+
+            let authority =
+                SpatialReferenceAuthority::from_str(authority).map_err(|_| invalid_srs_name())?;
+            let code = code.parse::<u32>().map_err(|_| invalid_srs_name())?;
+
+            Ok((SpatialReference::new(authority, code), true))
+        };
+
+        let (spatial_reference, crs_axis_order) = if let Some(urn) = srs_name
+            .strip_prefix("urn:ogc:def:crs:")
+            .or_else(|| srs_name.strip_prefix("urn:x-ogc:def:crs:"))
+        {
+            // the version between authority and code is optional, e.g., `EPSG::4326` or `EPSG:6.6:4326`
+            let parts = urn.split(':').collect::<Vec<_>>();
+            match parts.as_slice() {
+                [authority, code] | [authority, _, code] => {
+                    parse_authority_and_code(authority, code)?
+                }
+                _ => return Err(invalid_srs_name()),
+            }
+        } else if let Some(url) = srs_name
+            .strip_prefix("http://www.opengis.net/def/crs/")
+            .or_else(|| srs_name.strip_prefix("https://www.opengis.net/def/crs/"))
+        {
+            let parts = url.split('/').collect::<Vec<_>>();
+            match parts.as_slice() {
+                [authority, _version, code] => parse_authority_and_code(authority, code)?,
+                _ => return Err(invalid_srs_name()),
+            }
+        } else if let Some(code) = srs_name.strip_prefix("http://www.opengis.net/gml/srs/epsg.xml#")
+        {
+            let (spatial_reference, _) = parse_authority_and_code("EPSG", code)?;
+            (spatial_reference, false)
+        } else {
+            (
+                SpatialReference::from_str(srs_name).map_err(|_| invalid_srs_name())?,
+                false,
+            )
+        };
+
+        Ok(Self {
+            spatial_reference,
+            crs_axis_order,
+        })
+    }
+
+    fn of_element(element: &XmlElement) -> Result<Option<Self>> {
+        element.attribute("srsName").map(Self::parse).transpose()
+    }
+
+    /// Converts a coordinate of this srs into x/y order and projects it with the `projector`, if any
+    fn convert_coordinate(
+        self,
+        coordinate: Coordinate2D,
+        projector: Option<&CoordinateProjector>,
+    ) -> Result<Coordinate2D> {
+        let coordinate = if self.crs_axis_order {
+            tuple_from_ogc_params(coordinate.x, coordinate.y, self.spatial_reference)?.into()
+        } else {
+            coordinate
+        };
+
+        match projector {
+            Some(projector) => Ok(projector.project_coordinate(coordinate)?),
+            None => Ok(coordinate),
+        }
+    }
+
+    fn projector(self, target: SpatialReference) -> Result<Option<CoordinateProjector>> {
+        if self.spatial_reference == target {
+            return Ok(None);
+        }
+
+        Ok(Some(CoordinateProjector::from_known_srs(
+            self.spatial_reference,
+            target,
+        )?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    EqualTo,
+    NotEqualTo,
+    LessThan,
+    GreaterThan,
+    LessThanOrEqualTo,
+    GreaterThanOrEqualTo,
+}
+
+impl ComparisonOperator {
+    fn from_element_name(name: &str) -> Option<Self> {
+        match name {
+            "PropertyIsEqualTo" => Some(Self::EqualTo),
+            "PropertyIsNotEqualTo" => Some(Self::NotEqualTo),
+            "PropertyIsLessThan" => Some(Self::LessThan),
+            "PropertyIsGreaterThan" => Some(Self::GreaterThan),
+            "PropertyIsLessThanOrEqualTo" => Some(Self::LessThanOrEqualTo),
+            "PropertyIsGreaterThanOrEqualTo" => Some(Self::GreaterThanOrEqualTo),
+            _ => None,
+        }
+    }
+
+    fn matches(self, ordering: Option<Ordering>) -> bool {
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match self {
+            Self::EqualTo => ordering == Ordering::Equal,
+            Self::NotEqualTo => ordering != Ordering::Equal,
+            Self::LessThan => ordering == Ordering::Less,
+            Self::GreaterThan => ordering == Ordering::Greater,
+            Self::LessThanOrEqualTo => ordering != Ordering::Greater,
+            Self::GreaterThanOrEqualTo => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl Filter {
+    /// Parses a filter from its XML encoding, e.g. `<Filter><PropertyIsEqualTo>...</PropertyIsEqualTo></Filter>`.
+    /// Namespace prefixes of the elements (e.g. `fes:`) are ignored.
+    pub fn parse(xml: &str) -> Result<Self> {
+        let root = XmlElement::parse(xml)?;
+
+        if root.name == "Filter" {
+            match root.children.as_slice() {
+                [operator] => Self::from_element(operator),
+                _ => Err(invalid_filter("Filter must contain exactly one operator")),
+            }
+        } else {
+            Self::from_element(&root)
+        }
+    }
+
+    fn from_element(element: &XmlElement) -> Result<Self> {
+        if let Some(operator) = ComparisonOperator::from_element_name(&element.name) {
+            return Ok(Self::Comparison {
+                operator,
+                property: element.property()?,
+                literal: element.child_text("Literal")?,
+                match_case: element.match_case()?,
+            });
+        }
+
+        match element.name.as_str() {
+            "And" | "Or" => {
+                ensure!(
+                    element.children.len() >= 2,
+                    error::WfsInvalidFilter {
+                        reason: format!("{} must contain at least two operators", element.name)
+                    }
+                );
+
+                let operands = element
+                    .children
+                    .iter()
+                    .map(Self::from_element)
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(if element.name == "And" {
+                    Self::And(operands)
+                } else {
+                    Self::Or(operands)
+                })
+            }
+            "Not" => match element.children.as_slice() {
+                [operand] => Ok(Self::Not(Box::new(Self::from_element(operand)?))),
+                _ => Err(invalid_filter("Not must contain exactly one operator")),
+            },
+            "PropertyIsLike" => Ok(Self::Like {
+                property: element.property()?,
+                pattern: element.child_text("Literal")?,
+                wild_card: element.char_attribute("wildCard", '%')?,
+                single_char: element.char_attribute("singleChar", '_')?,
+                escape_char: element.char_attribute("escapeChar", '\\')?,
+                match_case: element.match_case()?,
+            }),
+            "PropertyIsNull" => Ok(Self::IsNull {
+                property: element.property()?,
+            }),
+            "PropertyIsBetween" => Ok(Self::Between {
+                property: element.property()?,
+                lower: element.child("LowerBoundary")?.child_text("Literal")?,
+                upper: element.child("UpperBoundary")?.child_text("Literal")?,
+            }),
+            "BBOX" => {
+                let envelope = element.child("Envelope")?;
+
+                Ok(Self::BBox {
+                    bbox: parse_envelope(envelope)?,
+                    srs: GmlSrs::of_element(envelope)?,
+                })
+            }
+            "Intersects" => {
+                let geometry = element
+                    .children
+                    .iter()
+                    .find(|child| !is_value_reference(child))
+                    .ok_or_else(|| invalid_filter("Intersects must contain a geometry"))?;
+
+                Ok(Self::Intersects {
+                    geometry: parse_gml_geometry(geometry)?,
+                    srs: GmlSrs::of_element(geometry)?,
+                })
+            }
+            name => Err(invalid_filter(&format!("Unsupported operator {name}"))),
+        }
+    }
+
+    /// Replaces the property names of the filter with the names of the `columns` they refer to.
+    /// Properties may be qualified with a namespace prefix, e.g. `ns:name`.
+    /// Fails if a property does not exist or if a literal does not fit the type of its column.
+    pub fn resolve_properties(self, columns: &HashMap<String, VectorColumnInfo>) -> Result<Self> {
+        let resolve_all = |filters: Vec<Filter>| {
+            filters
+                .into_iter()
+                .map(|filter| filter.resolve_properties(columns))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(match self {
+            Self::And(filters) => Self::And(resolve_all(filters)?),
+            Self::Or(filters) => Self::Or(resolve_all(filters)?),
+            Self::Not(filter) => Self::Not(Box::new(filter.resolve_properties(columns)?)),
+            Self::Comparison {
+                operator,
+                property,
+                literal,
+                match_case,
+            } => {
+                let property = resolve_property_name(&property, columns)?;
+                check_literal(&literal, columns[&property].data_type)?;
+
+                Self::Comparison {
+                    operator,
+                    property,
+                    literal,
+                    match_case,
+                }
+            }
+            Self::Like {
+                property,
+                pattern,
+                wild_card,
+                single_char,
+                escape_char,
+                match_case,
+            } => {
+                let property = resolve_property_name(&property, columns)?;
+
+                ensure!(
+                    columns[&property].data_type == FeatureDataType::Text,
+                    error::WfsInvalidFilter {
+                        reason: format!(
+                            "PropertyIsLike requires a text property, but {property} is not"
+                        )
+                    }
+                );
+
+                Self::Like {
+                    property,
+                    pattern,
+                    wild_card,
+                    single_char,
+                    escape_char,
+                    match_case,
+                }
+            }
+            Self::IsNull { property } => Self::IsNull {
+                property: resolve_property_name(&property, columns)?,
+            },
+            Self::Between {
+                property,
+                lower,
+                upper,
+            } => {
+                let property = resolve_property_name(&property, columns)?;
+                check_literal(&lower, columns[&property].data_type)?;
+                check_literal(&upper, columns[&property].data_type)?;
+
+                Self::Between {
+                    property,
+                    lower,
+                    upper,
+                }
+            }
+            filter @ (Self::BBox { .. } | Self::Intersects { .. }) => filter,
+        })
+    }
+
+    /// Converts the geometries of the spatial operators that specify an `srs` into x/y coordinates of the `target` spatial reference,
+    /// i.e., the spatial reference of the features.
+    pub fn reproject(self, target: SpatialReference) -> Result<Self> {
+        let convert_all = |filters: Vec<Filter>| {
+            filters
+                .into_iter()
+                .map(|filter| filter.reproject(target))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(match self {
+            Self::And(filters) => Self::And(convert_all(filters)?),
+            Self::Or(filters) => Self::Or(convert_all(filters)?),
+            Self::Not(filter) => Self::Not(Box::new(filter.reproject(target)?)),
+            Self::BBox {
+                bbox,
+                srs: Some(srs),
+            } => {
+                let lower_left = srs.convert_coordinate(bbox.lower_left(), None)?;
+                let upper_right = srs.convert_coordinate(bbox.upper_right(), None)?;
+                let bbox = BoundingBox2D::new(lower_left, upper_right)?;
+
+                let bbox = match srs.projector(target)? {
+                    Some(projector) => bbox.reproject(&projector)?,
+                    None => bbox,
+                };
+
+                Self::BBox { bbox, srs: None }
+            }
+            Self::Intersects {
+                geometry,
+                srs: Some(srs),
+            } => {
+                let projector = srs.projector(target)?;
+
+                let geometry = geometry.try_map_coords(|coordinate| {
+                    srs.convert_coordinate(coordinate.into(), projector.as_ref())
+                        .map(geo::Coord::from)
+                })?;
+
+                Self::Intersects {
+                    geometry,
+                    srs: None,
+                }
+            }
+            filter => filter,
+        })
+    }
+
+    /// Evaluates the filter for each feature of the `collection`.
+    /// The properties must have been resolved with [`Filter::resolve_properties`]
+    /// and the geometries must have been converted with [`Filter::reproject`] before.
+    pub fn evaluate<G>(
+        &self,
+        collection: &FeatureCollection<G>,
+    ) -> geoengine_datatypes::util::Result<Vec<bool>>
+    where
+        G: Geometry + ArrowTyped,
+        FeatureCollection<G>: for<'g> IntoGeometryOptionsIterator<'g>,
+    {
+        Ok(match self {
+            Self::And(filters) => {
+                let mut mask = vec![true; collection.len()];
+                for filter in filters {
+                    for (m, f) in mask.iter_mut().zip(filter.evaluate(collection)?) {
+                        *m &= f;
+                    }
+                }
+                mask
+            }
+            Self::Or(filters) => {
+                let mut mask = vec![false; collection.len()];
+                for filter in filters {
+                    for (m, f) in mask.iter_mut().zip(filter.evaluate(collection)?) {
+                        *m |= f;
+                    }
+                }
+                mask
+            }
+            Self::Not(filter) => filter
+                .evaluate(collection)?
+                .into_iter()
+                .map(|m| !m)
+                .collect(),
+            Self::Comparison {
+                operator,
+                property,
+                literal,
+                match_case,
+            } => {
+                let literal = parse_literal(literal, collection.column_type(property)?);
+
+                evaluate_values(collection, property, |value| {
+                    literal.as_ref().map_or(false, |literal| {
+                        operator.matches(compare_value(value, literal, *match_case))
+                    })
+                })?
+            }
+            Self::Like {
+                property,
+                pattern,
+                wild_card,
+                single_char,
+                escape_char,
+                match_case,
+            } => {
+                let pattern =
+                    LikePattern::new(pattern, *wild_card, *single_char, *escape_char, *match_case);
+
+                evaluate_values(collection, property, |value| match value {
+                    FeatureDataValue::Text(text) | FeatureDataValue::NullableText(Some(text)) => {
+                        pattern.matches(text)
+                    }
+                    _ => false,
+                })?
+            }
+            Self::IsNull { property } => evaluate_values(collection, property, is_null)?,
+            Self::Between {
+                property,
+                lower,
+                upper,
+            } => {
+                let data_type = collection.column_type(property)?;
+                let lower = parse_literal(lower, data_type);
+                let upper = parse_literal(upper, data_type);
+
+                evaluate_values(collection, property, |value| {
+                    let (Some(lower), Some(upper)) = (&lower, &upper) else {
+                        return false;
+                    };
+
+                    ComparisonOperator::GreaterThanOrEqualTo
+                        .matches(compare_value(value, lower, true))
+                        && ComparisonOperator::LessThanOrEqualTo
+                            .matches(compare_value(value, upper, true))
+                })?
+            }
+            Self::BBox { bbox, .. } => {
+                evaluate_geometries(collection, &geo::Geometry::Rect(bbox.into()))
+            }
+            Self::Intersects { geometry, .. } => evaluate_geometries(collection, geometry),
+        })
+    }
+}
+
+/// Finds the column a (possibly namespace-qualified) property name refers to
+pub fn resolve_property_name(
+    property: &str,
+    columns: &HashMap<String, VectorColumnInfo>,
+) -> Result<String> {
+    if columns.contains_key(property) {
+        return Ok(property.to_string());
+    }
+
+    match property.rsplit_once(':') {
+        Some((_namespace, name)) if columns.contains_key(name) => Ok(name.to_string()),
+        _ => Err(error::Error::WfsUnknownProperty {
+            property: property.to_string(),
+        }),
+    }
+}
+
+fn invalid_filter(reason: &str) -> error::Error {
+    error::Error::WfsInvalidFilter {
+        reason: reason.to_string(),
+    }
+}
+
+fn evaluate_values<G, F>(
+    collection: &FeatureCollection<G>,
+    property: &str,
+    predicate: F,
+) -> geoengine_datatypes::util::Result<Vec<bool>>
+where
+    G: Geometry + ArrowTyped,
+    F: Fn(&FeatureDataValue) -> bool,
+{
+    let data = collection.data(property)?;
+
+    Ok((0..collection.len())
+        .map(|i| predicate(&data.get_unchecked(i)))
+        .collect())
+}
+
+fn evaluate_geometries<G>(
+    collection: &FeatureCollection<G>,
+    target: &geo::Geometry<f64>,
+) -> Vec<bool>
+where
+    G: Geometry + ArrowTyped,
+    FeatureCollection<G>: for<'g> IntoGeometryOptionsIterator<'g>,
+{
+    collection
+        .geometry_options()
+        .map(|geometry| {
+            geometry.map_or(false, |geometry| {
+                let geometry: geojson::Geometry = geometry.into();
+                geo::Geometry::<f64>::try_from(geometry.value)
+                    .map_or(false, |geometry| geometry.intersects(target))
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum LiteralValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    DateTime(TimeInstance),
+}
+
+/// Interprets a literal as a value of the column's data type
+fn parse_literal(literal: &str, data_type: FeatureDataType) -> Option<LiteralValue> {
+    let literal = literal.trim();
+
+    match data_type {
+        FeatureDataType::Category | FeatureDataType::Int | FeatureDataType::Float => {
+            literal.parse().ok().map(LiteralValue::Number)
+        }
+        FeatureDataType::Text => Some(LiteralValue::Text(literal.to_string())),
+        FeatureDataType::Bool => match literal {
+            "true" | "1" => Some(LiteralValue::Bool(true)),
+            "false" | "0" => Some(LiteralValue::Bool(false)),
+            _ => None,
+        },
+        FeatureDataType::DateTime => DateTime::from_str(literal)
+            .ok()
+            .map(|date_time| LiteralValue::DateTime(date_time.into())),
+    }
+}
+
+fn check_literal(literal: &str, data_type: FeatureDataType) -> Result<()> {
+    ensure!(
+        parse_literal(literal, data_type).is_some(),
+        error::WfsInvalidFilter {
+            reason: format!("Literal {literal} does not match the property type {data_type:?}")
+        }
+    );
+
+    Ok(())
+}
+
+/// Compares a feature value with a literal. Null values are incomparable.
+fn compare_value(
+    value: &FeatureDataValue,
+    literal: &LiteralValue,
+    match_case: bool,
+) -> Option<Ordering> {
+    match (value, literal) {
+        (
+            FeatureDataValue::Category(v) | FeatureDataValue::NullableCategory(Some(v)),
+            LiteralValue::Number(l),
+        ) => f64::from(*v).partial_cmp(l),
+        (
+            FeatureDataValue::Int(v) | FeatureDataValue::NullableInt(Some(v)),
+            LiteralValue::Number(l),
+        ) => (*v as f64).partial_cmp(l),
+        (
+            FeatureDataValue::Float(v) | FeatureDataValue::NullableFloat(Some(v)),
+            LiteralValue::Number(l),
+        ) => v.partial_cmp(l),
+        (
+            FeatureDataValue::Text(v) | FeatureDataValue::NullableText(Some(v)),
+            LiteralValue::Text(l),
+        ) => Some(if match_case {
+            v.cmp(l)
+        } else {
+            v.to_lowercase().cmp(&l.to_lowercase())
+        }),
+        (
+            FeatureDataValue::Bool(v) | FeatureDataValue::NullableBool(Some(v)),
+            LiteralValue::Bool(l),
+        ) => Some(v.cmp(l)),
+        (
+            FeatureDataValue::DateTime(v) | FeatureDataValue::NullableDateTime(Some(v)),
+            LiteralValue::DateTime(l),
+        ) => Some(v.cmp(l)),
+        _ => None,
+    }
+}
+
+fn is_null(value: &FeatureDataValue) -> bool {
+    matches!(
+        value,
+        FeatureDataValue::NullableCategory(None)
+            | FeatureDataValue::NullableInt(None)
+            | FeatureDataValue::NullableFloat(None)
+            | FeatureDataValue::NullableText(None)
+            | FeatureDataValue::NullableBool(None)
+            | FeatureDataValue::NullableDateTime(None)
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LikeToken {
+    AnyChars,
+    AnyChar,
+    Char(char),
+}
+
+/// A pattern of the `PropertyIsLike` operator
+struct LikePattern {
+    tokens: Vec<LikeToken>,
+    match_case: bool,
+}
+
+impl LikePattern {
+    fn new(
+        pattern: &str,
+        wild_card: char,
+        single_char: char,
+        escape_char: char,
+        match_case: bool,
+    ) -> Self {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            let token = if c == escape_char {
+                chars.next().map_or(LikeToken::Char(c), LikeToken::Char)
+            } else if c == wild_card {
+                LikeToken::AnyChars
+            } else if c == single_char {
+                LikeToken::AnyChar
+            } else {
+                LikeToken::Char(c)
+            };
+
+            tokens.push(token);
+        }
+
+        if !match_case {
+            tokens = tokens
+                .into_iter()
+                .flat_map(|token| match token {
+                    LikeToken::Char(c) => c.to_lowercase().map(LikeToken::Char).collect(),
+                    token => vec![token],
+                })
+                .collect();
+        }
+
+        Self { tokens, match_case }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        let text: Vec<char> = if self.match_case {
+            text.chars().collect()
+        } else {
+            text.to_lowercase().chars().collect()
+        };
+
+        // `matched[j]` is true if the tokens seen so far match the first `j` chars of the text
+        let mut matched = vec![false; text.len() + 1];
+        matched[0] = true;
+
+        for token in &self.tokens {
+            let mut next = vec![false; text.len() + 1];
+
+            for j in 0..=text.len() {
+                next[j] = match token {
+                    LikeToken::AnyChars => matched[j] || (j > 0 && next[j - 1]),
+                    LikeToken::AnyChar => j > 0 && matched[j - 1],
+                    LikeToken::Char(c) => j > 0 && matched[j - 1] && text[j - 1] == *c,
+                };
+            }
+
+            matched = next;
+        }
+
+        matched[text.len()]
+    }
+}
+
+/// A simplified XML element tree that ignores namespaces
+#[derive(Debug, Clone, PartialEq)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    text: String,
+}
+
+impl XmlElement {
+    fn parse(xml: &str) -> Result<Self> {
+        let mut stack: Vec<XmlElement> = Vec::new();
+
+        for event in EventReader::from_str(xml) {
+            let event = event.map_err(|e| invalid_filter(&e.to_string()))?;
+
+            match event {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => stack.push(XmlElement {
+                    name: name.local_name,
+                    attributes: attributes
+                        .into_iter()
+                        .map(|attribute| (attribute.name.local_name, attribute.value))
+                        .collect(),
+                    children: Vec::new(),
+                    text: String::new(),
+                }),
+                XmlEvent::EndElement { .. } => {
+                    let element = stack
+                        .pop()
+                        .ok_or_else(|| invalid_filter("Unbalanced XML elements"))?;
+
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                    if let Some(element) = stack.last_mut() {
+                        element.text.push_str(&text);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Err(invalid_filter("Filter must be an XML element"))
+    }
+
+    fn child(&self, name: &str) -> Result<&XmlElement> {
+        self.children
+            .iter()
+            .find(|child| child.name == name)
+            .ok_or_else(|| invalid_filter(&format!("{} must contain {name}", self.name)))
+    }
+
+    fn child_text(&self, name: &str) -> Result<String> {
+        self.child(name).map(|child| child.text.trim().to_string())
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the property of an operator. Accepts `PropertyName` of FES 1.1 as well.
+    fn property(&self) -> Result<String> {
+        self.children
+            .iter()
+            .find(|child| is_value_reference(child))
+            .map(|child| child.text.trim().to_string())
+            .ok_or_else(|| invalid_filter(&format!("{} must contain ValueReference", self.name)))
+    }
+
+    fn match_case(&self) -> Result<bool> {
+        match self.attribute("matchCase") {
+            None | Some("true") => Ok(true),
+            Some("false") => Ok(false),
+            Some(value) => Err(invalid_filter(&format!(
+                "matchCase must be true or false, but is {value}"
+            ))),
+        }
+    }
+
+    fn char_attribute(&self, name: &str, default: char) -> Result<char> {
+        let Some(value) = self.attribute(name) else {
+            return Ok(default);
+        };
+
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(invalid_filter(&format!(
+                "{name} must be a single character, but is {value}"
+            ))),
+        }
+    }
+}
+
+fn is_value_reference(element: &XmlElement) -> bool {
+    element.name == "ValueReference" || element.name == "PropertyName"
+}
+
+/// Parses a GML position list, e.g. `1 2 3 4`.
+/// Coordinates are read in the given order and converted with [`Filter::reproject`] later.
+fn parse_positions(positions: &str) -> Result<Vec<geo::Coord<f64>>> {
+    let values = positions
+        .split_whitespace()
+        .map(f64::from_str)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid_filter(&format!("Invalid GML positions {positions}")))?;
+
+    ensure!(
+        values.len() % 2 == 0,
+        error::WfsInvalidFilter {
+            reason: format!("GML positions must be two-dimensional, but are {positions}")
+        }
+    );
+
+    Ok(values
+        .chunks_exact(2)
+        .map(|xy| geo::Coord { x: xy[0], y: xy[1] })
+        .collect())
+}
+
+fn parse_position(element: &XmlElement) -> Result<geo::Coord<f64>> {
+    match parse_positions(&element.text)?.as_slice() {
+        [coordinate] => Ok(*coordinate),
+        _ => Err(invalid_filter(&format!(
+            "{} must contain exactly one position",
+            element.name
+        ))),
+    }
+}
+
+/// Reads the coordinates of a `posList` or a sequence of `pos` elements
+fn parse_coordinates(element: &XmlElement) -> Result<Vec<geo::Coord<f64>>> {
+    if let Ok(pos_list) = element.child("posList") {
+        return parse_positions(&pos_list.text);
+    }
+
+    element
+        .children
+        .iter()
+        .filter(|child| child.name == "pos")
+        .map(parse_position)
+        .collect()
+}
+
+fn parse_envelope(envelope: &XmlElement) -> Result<BoundingBox2D> {
+    let lower = parse_position(envelope.child("lowerCorner")?)?;
+    let upper = parse_position(envelope.child("upperCorner")?)?;
+
+    BoundingBox2D::new(
+        Coordinate2D::new(lower.x, lower.y),
+        Coordinate2D::new(upper.x, upper.y),
+    )
+    .map_err(|e| invalid_filter(&e.to_string()))
+}
+
+fn parse_linear_ring(ring: &XmlElement) -> Result<geo::LineString<f64>> {
+    parse_coordinates(ring.child("LinearRing")?).map(geo::LineString::new)
+}
+
+fn parse_gml_geometry(element: &XmlElement) -> Result<geo::Geometry<f64>> {
+    match element.name.as_str() {
+        "Point" => {
+            let position = parse_position(element.child("pos")?)?;
+            Ok(geo::Geometry::Point(position.into()))
+        }
+        "LineString" => Ok(geo::Geometry::LineString(geo::LineString::new(
+            parse_coordinates(element)?,
+        ))),
+        "Polygon" => {
+            let exterior = parse_linear_ring(element.child("exterior")?)?;
+            let interiors = element
+                .children
+                .iter()
+                .filter(|child| child.name == "interior")
+                .map(parse_linear_ring)
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(geo::Geometry::Polygon(geo::Polygon::new(
+                exterior, interiors,
+            )))
+        }
+        "Envelope" => Ok(geo::Geometry::Rect(parse_envelope(element)?.into())),
+        name => Err(invalid_filter(&format!("Unsupported GML geometry {name}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geoengine_datatypes::collections::{FeatureCollectionModifications, MultiPointCollection};
+    use geoengine_datatypes::primitives::{
+        CacheHint, FeatureData, Measurement, MultiPoint, TimeInterval,
+    };
+
+    fn collection() -> MultiPointCollection {
+        MultiPointCollection::from_data(
+            MultiPoint::many(vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]).unwrap(),
+            vec![TimeInterval::default(); 4],
+            [
+                (
+                    "age".to_string(),
+                    FeatureData::NullableInt(vec![Some(2), Some(5), None, Some(2)]),
+                ),
+                (
+                    "name".to_string(),
+                    FeatureData::Text(vec![
+                        "Bello".to_string(),
+                        "Rex".to_string(),
+                        "Balu".to_string(),
+                        "rex".to_string(),
+                    ]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            CacheHint::default(),
+        )
+        .unwrap()
+    }
+
+    fn columns() -> HashMap<String, VectorColumnInfo> {
+        [
+            (
+                "age".to_string(),
+                VectorColumnInfo {
+                    data_type: FeatureDataType::Int,
+                    measurement: Measurement::Unitless,
+                },
+            ),
+            (
+                "name".to_string(),
+                VectorColumnInfo {
+                    data_type: FeatureDataType::Text,
+                    measurement: Measurement::Unitless,
+                },
+            ),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn evaluate(xml: &str) -> Vec<bool> {
+        Filter::parse(xml)
+            .unwrap()
+            .resolve_properties(&columns())
+            .unwrap()
+            .reproject(SpatialReference::epsg_4326())
+            .unwrap()
+            .evaluate(&collection())
+            .unwrap()
+    }
+
+    #[test]
+    fn it_parses_logical_operators() {
+        let filter = Filter::parse(
+            r#"<fes:Filter xmlns:fes="http://www.opengis.net/fes/2.0">
+                <fes:And>
+                    <fes:PropertyIsEqualTo><fes:ValueReference>dog:age</fes:ValueReference><fes:Literal>2</fes:Literal></fes:PropertyIsEqualTo>
+                    <fes:Not><fes:PropertyIsNull><fes:ValueReference>name</fes:ValueReference></fes:PropertyIsNull></fes:Not>
+                </fes:And>
+            </fes:Filter>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            Filter::And(vec![
+                Filter::Comparison {
+                    operator: ComparisonOperator::EqualTo,
+                    property: "dog:age".to_string(),
+                    literal: "2".to_string(),
+                    match_case: true,
+                },
+                Filter::Not(Box::new(Filter::IsNull {
+                    property: "name".to_string()
+                })),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_parses_spatial_operators() {
+        let filter = Filter::parse(
+            "<Filter><BBOX><ValueReference>geom</ValueReference><Envelope><lowerCorner>0 1</lowerCorner><upperCorner>2 3</upperCorner></Envelope></BBOX></Filter>",
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            Filter::BBox {
+                bbox: BoundingBox2D::new((0., 1.).into(), (2., 3.).into()).unwrap(),
+                srs: None,
+            }
+        );
+
+        let filter = Filter::parse(
+            "<Filter><Intersects><Polygon><exterior><LinearRing><posList>0 0 1 0 1 1 0 0</posList></LinearRing></exterior></Polygon></Intersects></Filter>",
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            Filter::Intersects {
+                geometry: geo::Geometry::Polygon(geo::Polygon::new(
+                    vec![(0., 0.), (1., 0.), (1., 1.), (0., 0.)].into(),
+                    vec![]
+                )),
+                srs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_filters() {
+        assert!(Filter::parse("<Filter>").is_err());
+        assert!(Filter::parse("<Filter><And><PropertyIsNull><ValueReference>a</ValueReference></PropertyIsNull></And></Filter>").is_err());
+        assert!(Filter::parse("<Filter><PropertyIsFoo/></Filter>").is_err());
+        assert!(Filter::parse(
+            "<Filter><PropertyIsEqualTo><Literal>2</Literal></PropertyIsEqualTo></Filter>"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_resolves_properties() {
+        assert!(matches!(
+            Filter::parse("<Filter><PropertyIsNull><ValueReference>foo</ValueReference></PropertyIsNull></Filter>")
+                .unwrap()
+                .resolve_properties(&columns()),
+            Err(error::Error::WfsUnknownProperty { property }) if property == "foo"
+        ));
+
+        assert!(matches!(
+            Filter::parse("<Filter><PropertyIsEqualTo><ValueReference>age</ValueReference><Literal>two</Literal></PropertyIsEqualTo></Filter>")
+                .unwrap()
+                .resolve_properties(&columns()),
+            Err(error::Error::WfsInvalidFilter { .. })
+        ));
+    }
+
+    #[test]
+    fn it_evaluates_comparisons() {
+        assert_eq!(
+            evaluate("<Filter><PropertyIsEqualTo><ValueReference>dog:age</ValueReference><Literal>2</Literal></PropertyIsEqualTo></Filter>"),
+            vec![true, false, false, true]
+        );
+        assert_eq!(
+            evaluate("<Filter><PropertyIsGreaterThan><ValueReference>age</ValueReference><Literal>2</Literal></PropertyIsGreaterThan></Filter>"),
+            vec![false, true, false, false]
+        );
+        assert_eq!(
+            evaluate("<Filter><PropertyIsEqualTo matchCase=\"false\"><ValueReference>name</ValueReference><Literal>REX</Literal></PropertyIsEqualTo></Filter>"),
+            vec![false, true, false, true]
+        );
+        assert_eq!(
+            evaluate("<Filter><PropertyIsBetween><ValueReference>age</ValueReference><LowerBoundary><Literal>3</Literal></LowerBoundary><UpperBoundary><Literal>5</Literal></UpperBoundary></PropertyIsBetween></Filter>"),
+            vec![false, true, false, false]
+        );
+        assert_eq!(
+            evaluate("<Filter><PropertyIsNull><ValueReference>age</ValueReference></PropertyIsNull></Filter>"),
+            vec![false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn it_evaluates_like() {
+        assert_eq!(
+            evaluate("<Filter><PropertyIsLike wildCard=\"*\" singleChar=\"?\" escapeChar=\"!\"><ValueReference>name</ValueReference><Literal>B*</Literal></PropertyIsLike></Filter>"),
+            vec![true, false, true, false]
+        );
+        assert_eq!(
+            evaluate("<Filter><PropertyIsLike matchCase=\"false\"><ValueReference>name</ValueReference><Literal>r_x</Literal></PropertyIsLike></Filter>"),
+            vec![false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn it_evaluates_logical_and_spatial_operators() {
+        assert_eq!(
+            evaluate("<Filter><Or><PropertyIsNull><ValueReference>age</ValueReference></PropertyIsNull><BBOX><Envelope><lowerCorner>0.5 0.5</lowerCorner><upperCorner>1.5 1.5</upperCorner></Envelope></BBOX></Or></Filter>"),
+            vec![false, true, true, false]
+        );
+        assert_eq!(
+            evaluate("<Filter><Not><Intersects><ValueReference>geom</ValueReference><Point><pos>3 3</pos></Point></Intersects></Not></Filter>"),
+            vec![true, true, true, false]
+        );
+
+        let mask = evaluate("<Filter><PropertyIsLessThan><ValueReference>age</ValueReference><Literal>5</Literal></PropertyIsLessThan></Filter>");
+        assert_eq!(collection().filter(mask).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn it_applies_the_axis_order_of_the_srs_name() {
+        let filter = Filter::parse(
+            r#"<fes:Filter xmlns:fes="http://www.opengis.net/fes/2.0" xmlns:gml="http://www.opengis.net/gml/3.2">
+                <fes:BBOX>
+                    <fes:ValueReference>geom</fes:ValueReference>
+                    <gml:Envelope srsName="urn:ogc:def:crs:EPSG::4326">
+                        <gml:lowerCorner>50 7</gml:lowerCorner>
+                        <gml:upperCorner>51 9</gml:upperCorner>
+                    </gml:Envelope>
+                </fes:BBOX>
+            </fes:Filter>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            Filter::BBox {
+                bbox: BoundingBox2D::new((50., 7.).into(), (51., 9.).into()).unwrap(),
+                srs: Some(GmlSrs {
+                    spatial_reference: SpatialReference::epsg_4326(),
+                    crs_axis_order: true,
+                }),
+            }
+        );
+
+        assert_eq!(
+            filter.reproject(SpatialReference::epsg_4326()).unwrap(),
+            Filter::BBox {
+                bbox: BoundingBox2D::new((7., 50.).into(), (9., 51.).into()).unwrap(),
+                srs: None,
+            }
+        );
+
+        // the short form uses x/y order
+        let filter = Filter::parse(
+            r#"<Filter><Intersects><Point srsName="EPSG:4326"><pos>7 50</pos></Point></Intersects></Filter>"#,
+        )
+        .unwrap()
+        .reproject(SpatialReference::epsg_4326())
+        .unwrap();
+
+        assert_eq!(
+            filter,
+            Filter::Intersects {
+                geometry: geo::Geometry::Point((7., 50.).into()),
+                srs: None,
+            }
+        );
+    }
+
+    #[test]
+    fn it_reprojects_to_the_spatial_reference_of_the_features() {
+        let filter = Filter::parse(
+            r#"<Filter><BBOX><Envelope srsName="http://www.opengis.net/def/crs/EPSG/0/4326"><lowerCorner>-10 -20</lowerCorner><upperCorner>10 20</upperCorner></Envelope></BBOX></Filter>"#,
+        )
+        .unwrap()
+        .reproject(SpatialReference::new(
+            SpatialReferenceAuthority::Epsg,
+            3857,
+        ))
+        .unwrap();
+
+        let Filter::BBox { bbox, srs: None } = filter else {
+            panic!("expected a reprojected BBOX filter");
+        };
+
+        // 20° longitude and 10° latitude in web mercator
+        assert!((bbox.upper_right().x - 2_226_389.815).abs() < 1.);
+        assert!((bbox.upper_right().y - 1_118_889.975).abs() < 1.);
+        assert!((bbox.lower_left().x + 2_226_389.815).abs() < 1.);
+        assert!((bbox.lower_left().y + 1_118_889.975).abs() < 1.);
+
+        assert_eq!(
+            evaluate(
+                r#"<Filter><BBOX><Envelope srsName="urn:ogc:def:crs:OGC:1.3:CRS84"><lowerCorner>0.5 0.5</lowerCorner><upperCorner>1.5 1.5</upperCorner></Envelope></BBOX></Filter>"#
+            ),
+            vec![false, true, false, false]
+        );
+
+        assert!(Filter::parse(
+            r#"<Filter><BBOX><Envelope srsName="urn:ogc:def:crs:FOO::1"><lowerCorner>0 0</lowerCorner><upperCorner>1 1</upperCorner></Envelope></BBOX></Filter>"#,
+        )
+        .is_err());
+    }
+}
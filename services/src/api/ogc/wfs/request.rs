@@ -2,10 +2,13 @@ use crate::api::model::datatypes::TimeInterval;
 use crate::api::ogc::util::{
     parse_ogc_bbox, parse_time_option, parse_wfs_resolution_option, OgcBoundingBox,
 };
+use crate::api::ogc::wfs::filter::Filter;
 use crate::util::from_str_option;
 use geoengine_datatypes::primitives::SpatialResolution;
 use geoengine_datatypes::spatial_reference::SpatialReference;
+use serde::de::Error;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use utoipa::openapi::{ObjectBuilder, SchemaType};
 use utoipa::{IntoParams, ToSchema};
 
@@ -74,10 +77,19 @@ pub struct GetFeature {
     #[serde(default)]
     #[serde(deserialize_with = "from_str_option")]
     pub count: Option<u64>,
-    pub sort_by: Option<String>,       // TODO: Name[+A|+D] (asc/desc)
-    pub result_type: Option<String>,   // TODO: enum: results/hits?
-    pub filter: Option<String>,        // TODO: parse filters
-    pub property_name: Option<String>, // TODO comma separated list
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_sort_by_option")]
+    #[param(value_type = Option<String>, example = "name DESC,age")]
+    pub sort_by: Option<Vec<SortBy>>,
+    pub result_type: Option<ResultType>,
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_filter_option")]
+    #[param(value_type = Option<String>, example = "<Filter><PropertyIsEqualTo><ValueReference>name</ValueReference><Literal>foo</Literal></PropertyIsEqualTo></Filter>")]
+    pub filter: Option<Filter>,
+    #[serde(default)]
+    #[serde(deserialize_with = "parse_property_names_option")]
+    #[param(value_type = Option<String>, example = "name,age")]
+    pub property_name: Option<Vec<String>>,
     // TODO: feature_id, ...
     /// Vendor parameter for specifying a spatial query resolution
     #[serde(default)]
@@ -102,6 +114,109 @@ pub enum GetFeatureRequest {
     GetFeature,
 }
 
+/// Whether to return the features or only the number of matching features
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultType {
+    #[default]
+    Results,
+    Hits,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SortBy {
+    pub property: String,
+    pub order: SortOrder,
+}
+
+impl FromStr for SortBy {
+    type Err = String;
+
+    /// Parses `property`, `property ASC|DESC` or `property +A|+D`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (property, order) = match s.rsplit_once(|c: char| c.is_whitespace() || c == '+') {
+            Some((property, order)) => {
+                let order = match order.to_uppercase().as_str() {
+                    "ASC" | "A" => SortOrder::Ascending,
+                    "DESC" | "D" => SortOrder::Descending,
+                    _ => return Err(format!("Invalid sort order in {s}")),
+                };
+                (property.trim_end(), order)
+            }
+            None => (s, SortOrder::Ascending),
+        };
+
+        if property.is_empty() {
+            return Err(format!("Missing sort property in {s}"));
+        }
+
+        Ok(Self {
+            property: property.to_string(),
+            order,
+        })
+    }
+}
+
+/// Parse a comma separated list of sort criteria, e.g. "name DESC,age"
+pub fn parse_sort_by_option<'de, D>(deserializer: D) -> Result<Option<Vec<SortBy>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    s.split(',')
+        .map(SortBy::from_str)
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+        .map_err(D::Error::custom)
+}
+
+/// Parse a filter in the XML encoding of OGC Filter Encoding 2.0
+pub fn parse_filter_option<'de, D>(deserializer: D) -> Result<Option<Filter>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    Filter::parse(&s).map(Some).map_err(D::Error::custom)
+}
+
+/// Parse a comma separated list of property names, e.g. "name,age"
+pub fn parse_property_names_option<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    let property_names: Vec<String> = s.split(',').map(|p| p.trim().to_string()).collect();
+
+    if property_names.iter().any(String::is_empty) {
+        return Err(D::Error::custom("Invalid property names"));
+    }
+
+    Ok(Some(property_names))
+}
+
 #[allow(clippy::option_if_let_else)]
 pub fn parse_type_names<'de, D>(deserializer: D) -> Result<TypeNames, D::Error>
 where
@@ -128,6 +243,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::ogc::wfs::filter::ComparisonOperator;
     use geoengine_datatypes::spatial_reference::SpatialReferenceAuthority;
 
     #[test]
@@ -171,7 +287,7 @@ mod tests {
             ("time", "2000-01-01T00:00:00.0Z/2000-01-02T00:00:00.0Z"),
             ("namespaces","xmlns(dog=http://www.example.com/namespaces/dog)"),
             ("count","10"),
-            ("sortBy","Name[+A]"),
+            ("sortBy","Name DESC,dog:age+A,P1"),
             ("resultType","results"),
             ("filter","<Filter>
   <And>
@@ -185,28 +301,56 @@ mod tests {
         let query = serde_urlencoded::to_string(params).unwrap();
         let parsed: GetFeature = serde_urlencoded::from_str(&query).unwrap();
 
-        let request =GetFeature {
+        let request = GetFeature {
             service: WfsService::Wfs,
             request: GetFeatureRequest::GetFeature,
             version: Some(WfsVersion::V2_0_0),
-            time: Some(geoengine_datatypes::primitives::TimeInterval::new(946_684_800_000, 946_771_200_000).unwrap().into()),
+            time: Some(
+                geoengine_datatypes::primitives::TimeInterval::new(
+                    946_684_800_000,
+                    946_771_200_000,
+                )
+                .unwrap()
+                .into(),
+            ),
             srs_name: Some(SpatialReference::new(SpatialReferenceAuthority::Epsg, 4326)),
             namespaces: Some("xmlns(dog=http://www.example.com/namespaces/dog)".into()),
             count: Some(10),
-            sort_by: Some("Name[+A]".into()),
-            result_type: Some("results".into()),
-            filter: Some("<Filter>
-  <And>
-    <PropertyIsEqualTo><ValueReference>dog:age</ValueReference><Literal>2</Literal></PropertyIsEqualTo>
-    <PropertyIsEqualTo><ValueReference>dog:weight</ValueReference><Literal>5</Literal></PropertyIsEqualTo>
-  </And>
-</Filter>".into()),
+            sort_by: Some(vec![
+                SortBy {
+                    property: "Name".into(),
+                    order: SortOrder::Descending,
+                },
+                SortBy {
+                    property: "dog:age".into(),
+                    order: SortOrder::Ascending,
+                },
+                SortBy {
+                    property: "P1".into(),
+                    order: SortOrder::Ascending,
+                },
+            ]),
+            result_type: Some(ResultType::Results),
+            filter: Some(Filter::And(vec![
+                Filter::Comparison {
+                    operator: ComparisonOperator::EqualTo,
+                    property: "dog:age".into(),
+                    literal: "2".into(),
+                    match_case: true,
+                },
+                Filter::Comparison {
+                    operator: ComparisonOperator::EqualTo,
+                    property: "dog:weight".into(),
+                    literal: "5".into(),
+                    match_case: true,
+                },
+            ])),
             bbox: OgcBoundingBox::new(1., 2., 3., 4.),
             type_names: TypeNames {
                 namespace: Some("ns".into()),
                 feature_type: "test".into(),
             },
-            property_name: Some("P1,P2".into()),
+            property_name: Some(vec!["P1".into(), "P2".into()]),
             query_resolution: Some(WfsResolution(SpatialResolution::zero_point_one())),
         };
 
@@ -252,6 +396,49 @@ mod tests {
         assert_eq!(parsed, request);
     }
 
+    #[test]
+    fn deserialize_hits() {
+        let query = "request=GetFeature&service=WFS&version=2.0.0&typeNames=test&bbox=1,2,3,4&resultType=hits";
+        let parsed: GetFeature = serde_urlencoded::from_str(query).unwrap();
+
+        assert_eq!(parsed.result_type, Some(ResultType::Hits));
+    }
+
+    #[test]
+    fn deserialize_invalid_filter_and_sort_by() {
+        let query = "request=GetFeature&service=WFS&version=2.0.0&typeNames=test&bbox=1,2,3,4&filter=%3CFilter%3E";
+        assert!(serde_urlencoded::from_str::<GetFeature>(query).is_err());
+
+        let query = "request=GetFeature&service=WFS&version=2.0.0&typeNames=test&bbox=1,2,3,4&sortBy=name%20UP";
+        assert!(serde_urlencoded::from_str::<GetFeature>(query).is_err());
+    }
+
+    #[test]
+    fn it_parses_sort_by() {
+        assert_eq!(
+            SortBy::from_str("name").unwrap(),
+            SortBy {
+                property: "name".into(),
+                order: SortOrder::Ascending
+            }
+        );
+        assert_eq!(
+            SortBy::from_str("name desc").unwrap(),
+            SortBy {
+                property: "name".into(),
+                order: SortOrder::Descending
+            }
+        );
+        assert_eq!(
+            SortBy::from_str("name+D").unwrap(),
+            SortBy {
+                property: "name".into(),
+                order: SortOrder::Descending
+            }
+        );
+        assert!(SortBy::from_str(" DESC").is_err());
+    }
+
     // #[test]
     // fn deserialize_ol_example_request() {
     //     let op = r#"{"a":"b"}"#.to_string();
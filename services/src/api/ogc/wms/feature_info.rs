@@ -0,0 +1,242 @@
+use geoengine_datatypes::primitives::TimeInterval;
+use serde_json::json;
+use std::fmt::Write;
+
+use super::request::GetFeatureInfoFormat;
+
+/// The answer of a WMS `GetFeatureInfo` request for one queried layer.
+///
+/// For raster workflows, there is one feature per time step that holds the pixel values of all bands.
+/// For vector workflows, there is one feature per hit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureInfo {
+    pub layer: String,
+    pub features: Vec<FeatureInfoFeature>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureInfoFeature {
+    pub geometry: Option<geojson::Geometry>,
+    pub time: TimeInterval,
+    /// property names and values in output order
+    pub properties: Vec<(String, serde_json::Value)>,
+}
+
+impl FeatureInfo {
+    pub fn new(layer: String, features: Vec<FeatureInfoFeature>) -> Self {
+        Self { layer, features }
+    }
+
+    /// Encodes the feature info in the requested format and returns the body with its mime type
+    pub fn encode(&self, format: GetFeatureInfoFormat) -> (mime::Mime, String) {
+        match format {
+            GetFeatureInfoFormat::TextXml => (mime::TEXT_XML, self.to_xml()),
+            GetFeatureInfoFormat::ApplicationJson => {
+                (mime::APPLICATION_JSON, self.to_geo_json().to_string())
+            }
+            GetFeatureInfoFormat::TextHtml => (mime::TEXT_HTML_UTF_8, self.to_html()),
+        }
+    }
+
+    /// Outputs a `GeoJSON` feature collection with the same structure as the WFS output
+    pub fn to_geo_json(&self) -> serde_json::Value {
+        let features = self
+            .features
+            .iter()
+            .map(|feature| {
+                let properties: serde_json::Map<String, serde_json::Value> =
+                    feature.properties.iter().cloned().collect();
+
+                json!({
+                    "type": "Feature",
+                    "geometry": feature.geometry,
+                    "properties": properties,
+                    "when": feature.time.as_geo_json_event(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "type": "FeatureCollection",
+            "layer": self.layer,
+            "features": features,
+        })
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+
+        let _ = write!(
+            xml,
+            r#"<FeatureInfoResponse><Layer name="{}">"#,
+            escape_markup(&self.layer)
+        );
+
+        for feature in &self.features {
+            let _ = write!(
+                xml,
+                r#"<Feature start="{}" end="{}">"#,
+                feature.time.start().as_datetime_string(),
+                feature.time.end().as_datetime_string()
+            );
+
+            for (name, value) in &feature.properties {
+                let _ = write!(
+                    xml,
+                    r#"<Property name="{}">{}</Property>"#,
+                    escape_markup(name),
+                    escape_markup(&value_to_text(value))
+                );
+            }
+
+            xml.push_str("</Feature>");
+        }
+
+        xml.push_str("</Layer></FeatureInfoResponse>");
+
+        xml
+    }
+
+    pub fn to_html(&self) -> String {
+        let layer = escape_markup(&self.layer);
+
+        let mut html = format!(
+            r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>{layer}</title></head><body><h1>{layer}</h1>"#
+        );
+
+        if self.features.is_empty() {
+            html.push_str("<p>No features found</p>");
+        }
+
+        for feature in &self.features {
+            let _ = write!(
+                html,
+                "<table><caption>{} - {}</caption><tr><th>Property</th><th>Value</th></tr>",
+                feature.time.start().as_datetime_string(),
+                feature.time.end().as_datetime_string()
+            );
+
+            for (name, value) in &feature.properties {
+                let _ = write!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td></tr>",
+                    escape_markup(name),
+                    escape_markup(&value_to_text(value))
+                );
+            }
+
+            html.push_str("</table>");
+        }
+
+        html.push_str("</body></html>");
+
+        html
+    }
+}
+
+/// Outputs strings without quotes and null values as empty strings
+fn value_to_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Escapes the special characters of XML and HTML in texts and attribute values
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature_info() -> FeatureInfo {
+        FeatureInfo::new(
+            "layer".to_string(),
+            vec![FeatureInfoFeature {
+                geometry: Some(geojson::Geometry::new(geojson::Value::Point(vec![1., 2.]))),
+                time: TimeInterval::new_unchecked(0, 1000),
+                properties: vec![
+                    ("ndvi".to_string(), json!(42.0)),
+                    ("name".to_string(), json!("<a & b>")),
+                    ("missing".to_string(), serde_json::Value::Null),
+                ],
+            }],
+        )
+    }
+
+    #[test]
+    fn it_encodes_geo_json() {
+        assert_eq!(
+            feature_info().to_geo_json(),
+            json!({
+                "type": "FeatureCollection",
+                "layer": "layer",
+                "features": [{
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "Point",
+                        "coordinates": [1.0, 2.0]
+                    },
+                    "properties": {
+                        "ndvi": 42.0,
+                        "name": "<a & b>",
+                        "missing": null
+                    },
+                    "when": {
+                        "start": "1970-01-01T00:00:00+00:00",
+                        "end": "1970-01-01T00:00:01+00:00",
+                        "type": "Interval"
+                    }
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn it_encodes_xml() {
+        assert_eq!(
+            feature_info().to_xml(),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<FeatureInfoResponse><Layer name="layer">"#,
+                r#"<Feature start="1970-01-01T00:00:00+00:00" end="1970-01-01T00:00:01+00:00">"#,
+                r#"<Property name="ndvi">42.0</Property>"#,
+                r#"<Property name="name">&lt;a &amp; b&gt;</Property>"#,
+                r#"<Property name="missing"></Property>"#,
+                r#"</Feature></Layer></FeatureInfoResponse>"#
+            )
+        );
+    }
+
+    #[test]
+    fn it_encodes_html() {
+        let html = feature_info().to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<tr><td>ndvi</td><td>42.0</td></tr>"));
+        assert!(html.contains("<tr><td>name</td><td>&lt;a &amp; b&gt;</td></tr>"));
+    }
+
+    #[test]
+    fn it_encodes_empty_html() {
+        let html = FeatureInfo::new("layer".to_string(), vec![]).to_html();
+
+        assert!(html.contains("<p>No features found</p>"));
+    }
+}
@@ -1 +1,2 @@
+pub mod feature_info;
 pub mod request;
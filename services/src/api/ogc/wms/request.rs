@@ -1,6 +1,6 @@
 use crate::api::model::datatypes::{SpatialReference, TimeInterval};
 use crate::api::ogc::util::{parse_ogc_bbox, parse_time_option, OgcBoundingBox};
-use crate::util::{bool_option_case_insensitive, from_str};
+use crate::util::{bool_option_case_insensitive, from_str, from_str_option};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
@@ -92,7 +92,14 @@ pub struct GetMap {
     pub elevation: Option<String>,
     #[serde(alias = "EXCEPTIONS")]
     pub exceptions: Option<GetMapExceptionFormat>, // TODO: parse Option<GetMapExceptionFormat>
-                                                   // TODO: DIM_<name>
+    /// vendor parameter for the quality of `image/jpeg` and `image/webp` from 1 to 100.
+    /// JPEG defaults to 85, while WebP is encoded lossless without it.
+    #[serde(alias = "QUALITY")]
+    #[serde(default)]
+    #[serde(deserialize_with = "from_str_option")]
+    #[param(example = 85)]
+    pub quality: Option<u8>,
+    // TODO: DIM_<name>
 }
 
 #[derive(PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
@@ -105,26 +112,99 @@ pub enum GetMapExceptionFormat {
     #[serde(rename = "XML", alias = "application/vnd.ogc.se_xml")]
     Xml,
     #[serde(rename = "JSON", alias = "application/json")]
-    Json, // UNSUPPORTED: INIMAGE, BLANK
+    Json,
+    /// renders the error message into an image of the requested format
+    #[serde(rename = "INIMAGE", alias = "application/vnd.ogc.se_inimage")]
+    InImage,
+    /// returns an image of the requested format that only consists of the background color
+    #[serde(rename = "BLANK", alias = "application/vnd.ogc.se_blank")]
+    Blank,
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
 pub enum GetMapFormat {
     #[serde(rename = "image/png")]
-    ImagePng, // TODO: remaining formats
+    ImagePng,
+    #[serde(rename = "image/jpeg")]
+    ImageJpeg,
+    #[serde(rename = "image/webp")]
+    ImageWebp,
+    /// the raw raster values as a georeferenced `GeoTIFF`
+    #[serde(rename = "image/tiff", alias = "image/geotiff")]
+    ImageTiff,
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[derive(PartialEq, Debug, Deserialize, Serialize, IntoParams)]
 pub struct GetFeatureInfo {
-    pub version: String,
+    #[serde(alias = "VERSION")]
+    pub version: WmsVersion,
+    #[serde(alias = "SERVICE")]
+    pub service: WmsService,
+    #[serde(alias = "REQUEST")]
+    pub request: GetFeatureInfoRequest,
+    #[serde(alias = "LAYERS")]
+    #[param(example = "<Workflow Id>")]
+    pub layers: String,
+    #[serde(alias = "QUERY_LAYERS")]
+    #[param(example = "<Workflow Id>")]
     pub query_layers: String,
-    pub info_format: Option<String>, // TODO: parse Option<GetFeatureInfoFormat>,
-                                     // TODO: remaining fields
+    #[serde(alias = "STYLES")]
+    #[serde(default)]
+    pub styles: Option<String>,
+    #[serde(alias = "CRS")]
+    #[param(example = "EPSG:4326", value_type = Option<String>)]
+    pub crs: Option<SpatialReference>,
+    #[serde(alias = "BBOX")]
+    #[serde(deserialize_with = "parse_ogc_bbox")]
+    #[param(example = "-90,-180,90,180")]
+    pub bbox: OgcBoundingBox,
+    #[serde(alias = "WIDTH")]
+    #[serde(deserialize_with = "from_str")]
+    #[param(example = 512)]
+    pub width: u32,
+    #[serde(alias = "HEIGHT")]
+    #[serde(deserialize_with = "from_str")]
+    #[param(example = 256)]
+    pub height: u32,
+    /// column of the queried pixel, counted from the left edge of the map
+    #[serde(alias = "I")]
+    #[serde(deserialize_with = "from_str")]
+    #[param(example = 256)]
+    pub i: u32,
+    /// row of the queried pixel, counted from the upper edge of the map
+    #[serde(alias = "J")]
+    #[serde(deserialize_with = "from_str")]
+    #[param(example = 128)]
+    pub j: u32,
+    #[serde(alias = "INFO_FORMAT")]
+    pub info_format: Option<GetFeatureInfoFormat>,
+    /// maximum number of features to return, defaults to 1
+    #[serde(alias = "FEATURE_COUNT")]
+    #[serde(default)]
+    #[serde(deserialize_with = "from_str_option")]
+    pub feature_count: Option<u32>,
+    #[serde(default)]
+    #[serde(alias = "TIME")]
+    #[serde(deserialize_with = "parse_time_option")]
+    #[param(value_type = String, example = "2014-04-01T12:00:00.000Z")]
+    pub time: Option<TimeInterval>,
+    #[serde(alias = "EXCEPTIONS")]
+    pub exceptions: Option<GetMapExceptionFormat>,
 }
 
-#[derive(PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
+pub enum GetFeatureInfoRequest {
+    GetFeatureInfo,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
 pub enum GetFeatureInfoFormat {
-    TextXml, // TODO: remaining formats
+    #[serde(rename = "text/xml")]
+    TextXml,
+    #[serde(rename = "application/json", alias = "application/geo+json")]
+    ApplicationJson,
+    #[serde(rename = "text/html")]
+    TextHtml,
 }
 
 #[derive(PartialEq, Eq, Debug, Deserialize, Serialize)]
@@ -141,9 +221,18 @@ pub struct GetLegendGraphic {
     pub service: WmsService,
     #[serde(alias = "REQUEST")]
     pub request: GetLegendGraphicRequest,
+    #[serde(alias = "LAYER")]
     #[param(example = "<Workflow Id>")]
     pub layer: String,
-    // TODO: remaining fields
+    /// the style of the layer as used in `GetMap`, i.e., a custom raster colorizer
+    #[serde(alias = "STYLE")]
+    #[serde(default)]
+    #[param(example = "custom:{...}")]
+    pub style: Option<String>,
+    #[serde(alias = "FORMAT")]
+    pub format: Option<GetLegendGraphicFormat>,
+    #[serde(alias = "EXCEPTIONS")]
+    pub exceptions: Option<GetMapExceptionFormat>,
 }
 
 #[derive(PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
@@ -151,6 +240,12 @@ pub enum GetLegendGraphicRequest {
     GetLegendGraphic,
 }
 
+#[derive(PartialEq, Eq, Debug, Deserialize, Serialize, ToSchema)]
+pub enum GetLegendGraphicFormat {
+    #[serde(rename = "image/png")]
+    ImagePng,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,6 +280,7 @@ mod tests {
             height: 2,
             format: GetMapFormat::ImagePng,
             exceptions: Some(GetMapExceptionFormat::Json),
+            quality: None,
         };
 
         assert_eq!(parsed, request);
@@ -213,10 +309,108 @@ mod tests {
             height: 2,
             format: GetMapFormat::ImagePng,
             exceptions: None,
+            quality: None,
+        };
+
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn deserialize_get_map_formats() {
+        let query = "REQUEST=GetMap&SERVICE=WMS&VERSION=1.3.0&LAYERS=modis_ndvi&BBOX=1,2,3,4&WIDTH=2&HEIGHT=2&CRS=EPSG:4326&STYLES=&FORMAT=image/jpeg&QUALITY=70&EXCEPTIONS=INIMAGE";
+        let parsed: GetMap = serde_urlencoded::from_str(query).unwrap();
+
+        assert_eq!(parsed.format, GetMapFormat::ImageJpeg);
+        assert_eq!(parsed.quality, Some(70));
+        assert_eq!(parsed.exceptions, Some(GetMapExceptionFormat::InImage));
+
+        let query = "request=GetMap&service=WMS&version=1.3.0&layers=modis_ndvi&bbox=1,2,3,4&width=2&height=2&crs=EPSG:4326&styles=&format=image/webp&exceptions=application/vnd.ogc.se_blank";
+        let parsed: GetMap = serde_urlencoded::from_str(query).unwrap();
+
+        assert_eq!(parsed.format, GetMapFormat::ImageWebp);
+        assert_eq!(parsed.quality, None);
+        assert_eq!(parsed.exceptions, Some(GetMapExceptionFormat::Blank));
+
+        let query = "request=GetMap&service=WMS&version=1.3.0&layers=modis_ndvi&bbox=1,2,3,4&width=2&height=2&crs=EPSG:4326&styles=&format=image/tiff";
+        let parsed: GetMap = serde_urlencoded::from_str(query).unwrap();
+
+        assert_eq!(parsed.format, GetMapFormat::ImageTiff);
+    }
+
+    #[test]
+    fn deserialize_get_feature_info() {
+        let query = "request=GetFeatureInfo&service=WMS&version=1.3.0&layers=modis_ndvi&query_layers=modis_ndvi&bbox=1,2,3,4&width=2&height=2&crs=EPSG:4326&styles=&i=1&j=0&info_format=application/json&feature_count=5&time=2000-01-01T00:00:00.0Z/2000-01-02T00:00:00.0Z";
+        let parsed: GetFeatureInfo = serde_urlencoded::from_str(query).unwrap();
+
+        let request = GetFeatureInfo {
+            version: WmsVersion::V1_3_0,
+            service: WmsService::Wms,
+            request: GetFeatureInfoRequest::GetFeatureInfo,
+            layers: "modis_ndvi".into(),
+            query_layers: "modis_ndvi".into(),
+            styles: Some(String::new()),
+            crs: Some(geoengine_datatypes::spatial_reference::SpatialReference::epsg_4326().into()),
+            bbox: OgcBoundingBox::new(1., 2., 3., 4.),
+            width: 2,
+            height: 2,
+            i: 1,
+            j: 0,
+            info_format: Some(GetFeatureInfoFormat::ApplicationJson),
+            feature_count: Some(5),
+            time: Some(
+                geoengine_datatypes::primitives::TimeInterval::new(
+                    946_684_800_000,
+                    946_771_200_000,
+                )
+                .unwrap()
+                .into(),
+            ),
+            exceptions: None,
         };
 
         assert_eq!(parsed, request);
     }
 
+    #[test]
+    fn deserialize_get_feature_info_uppercase() {
+        let query = "REQUEST=GetFeatureInfo&SERVICE=WMS&VERSION=1.3.0&LAYERS=modis_ndvi&QUERY_LAYERS=modis_ndvi&BBOX=1,2,3,4&WIDTH=2&HEIGHT=2&CRS=EPSG:4326&I=0&J=1&INFO_FORMAT=text/html";
+        let parsed: GetFeatureInfo = serde_urlencoded::from_str(query).unwrap();
+
+        assert_eq!(parsed.i, 0);
+        assert_eq!(parsed.j, 1);
+        assert_eq!(parsed.info_format, Some(GetFeatureInfoFormat::TextHtml));
+        assert_eq!(parsed.feature_count, None);
+        assert_eq!(parsed.styles, None);
+    }
+
+    #[test]
+    fn deserialize_get_legend_graphic() {
+        let query = "request=GetLegendGraphic&service=WMS&version=1.3.0&layer=modis_ndvi&style=custom:{}&format=image/png&exceptions=JSON";
+        let parsed: GetLegendGraphic = serde_urlencoded::from_str(query).unwrap();
+
+        let request = GetLegendGraphic {
+            version: WmsVersion::V1_3_0,
+            service: WmsService::Wms,
+            request: GetLegendGraphicRequest::GetLegendGraphic,
+            layer: "modis_ndvi".into(),
+            style: Some("custom:{}".into()),
+            format: Some(GetLegendGraphicFormat::ImagePng),
+            exceptions: Some(GetMapExceptionFormat::Json),
+        };
+
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn deserialize_get_legend_graphic_uppercase() {
+        let query = "REQUEST=GetLegendGraphic&SERVICE=WMS&VERSION=1.3.0&LAYER=modis_ndvi";
+        let parsed: GetLegendGraphic = serde_urlencoded::from_str(query).unwrap();
+
+        assert_eq!(parsed.layer, "modis_ndvi");
+        assert_eq!(parsed.style, None);
+        assert_eq!(parsed.format, None);
+        assert_eq!(parsed.exceptions, None);
+    }
+
     // TODO: add a test with xml error
 }
@@ -1,6 +1,8 @@
 use flexi_logger::writers::{FileLogWriter, FileLogWriterHandle};
 use flexi_logger::{Age, Cleanup, Criterion, FileSpec, Naming, WriteMode};
-use geoengine_operators::processing::initialize_expression_dependencies;
+use geoengine_operators::processing::{
+    initialize_expression_dependencies, set_expression_backend, ExpressionBackend,
+};
 use geoengine_services::error::Result;
 use geoengine_services::util::config;
 use geoengine_services::util::config::get_config_element;
@@ -16,9 +18,16 @@ use tracing_subscriber::Layer;
 
 #[tokio::main]
 async fn main() {
-    initialize_expression_dependencies()
-        .await
-        .expect("successful compilation process is necessary for expression operators to work");
+    let expression_config: config::Expression =
+        get_config_element().expect("the expression configuration has to be valid");
+    set_expression_backend(expression_config.backend);
+
+    // the interpreter does not need any dependencies
+    if expression_config.backend == ExpressionBackend::Compiled {
+        initialize_expression_dependencies()
+            .await
+            .expect("successful compilation process is necessary for expression operators to work");
+    }
 
     start_server().await.expect("the server has to start");
 }
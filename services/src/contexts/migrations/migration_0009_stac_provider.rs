@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+use tokio_postgres::Transaction;
+
+use crate::error::Result;
+
+use super::database_migration::{DatabaseVersion, Migration};
+
+/// This migration adds the generic STAC data provider
+pub struct Migration0009StacProvider;
+
+#[async_trait]
+impl Migration for Migration0009StacProvider {
+    fn prev_version(&self) -> Option<DatabaseVersion> {
+        Some("0008_band_names".into())
+    }
+
+    fn version(&self) -> DatabaseVersion {
+        "0009_stac_provider".into()
+    }
+
+    async fn migrate(&self, _tx: &Transaction<'_>) -> Result<()> {
+        // the STAC provider only exists in Pro, nothing to do here
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use tokio_postgres::Transaction;
+
+use crate::error::Result;
+
+use super::database_migration::{DatabaseVersion, Migration};
+
+/// This migration adds the tables of the persistent task manager
+pub struct Migration0010Tasks;
+
+#[async_trait]
+impl Migration for Migration0010Tasks {
+    fn prev_version(&self) -> Option<DatabaseVersion> {
+        Some("0009_stac_provider".into())
+    }
+
+    fn version(&self) -> DatabaseVersion {
+        "0010_tasks".into()
+    }
+
+    async fn migrate(&self, tx: &Transaction<'_>) -> Result<()> {
+        tx.batch_execute(include_str!("migration_0010_tasks.sql"))
+            .await?;
+
+        Ok(())
+    }
+}
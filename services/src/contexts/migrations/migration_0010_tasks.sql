@@ -0,0 +1,51 @@
+CREATE TYPE "TaskStatusType" AS ENUM (
+    'Running',
+    'Completed',
+    'Aborted',
+    'Failed'
+);
+
+CREATE TABLE tasks (
+    id uuid PRIMARY KEY,
+    task_type text NOT NULL,
+    unique_id text,
+    description text,
+    status "TaskStatusType" NOT NULL,
+    finished boolean NOT NULL,
+    status_json jsonb NOT NULL,
+    time_created timestamp with time zone NOT NULL,
+    time_updated timestamp with time zone NOT NULL,
+    -- the instance that runs the task as long as it is unfinished
+    lease_owner uuid,
+    lease_expires timestamp with time zone,
+    abort_requested boolean NOT NULL DEFAULT FALSE,
+    force_abort boolean NOT NULL DEFAULT FALSE,
+    -- the serialized task for resuming it on another instance
+    payload jsonb
+);
+
+CREATE INDEX tasks_time_created_idx ON tasks (time_created DESC);
+
+-- there must only be one unfinished task per unique id
+CREATE UNIQUE INDEX tasks_unique_id_idx ON tasks (
+    task_type, unique_id
+) WHERE NOT finished;
+
+CREATE TABLE task_status_history (
+    task_id uuid NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    "time" timestamp with time zone NOT NULL,
+    status "TaskStatusType" NOT NULL,
+    status_json jsonb NOT NULL
+);
+
+CREATE INDEX task_status_history_task_id_idx ON task_status_history (
+    task_id, "time"
+);
+
+-- unfinished tasks that wait for an instance to claim them
+CREATE TABLE task_queue (
+    task_id uuid PRIMARY KEY REFERENCES tasks (id) ON DELETE CASCADE,
+    time_enqueued timestamp with time zone NOT NULL
+);
+
+CREATE INDEX task_queue_time_enqueued_idx ON task_queue (time_enqueued);
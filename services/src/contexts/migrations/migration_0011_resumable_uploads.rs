@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use tokio_postgres::Transaction;
+
+use crate::error::Result;
+
+use super::database_migration::{DatabaseVersion, Migration};
+
+/// This migration adds the table of unfinished resumable uploads
+pub struct Migration0011ResumableUploads;
+
+#[async_trait]
+impl Migration for Migration0011ResumableUploads {
+    fn prev_version(&self) -> Option<DatabaseVersion> {
+        Some("0010_tasks".into())
+    }
+
+    fn version(&self) -> DatabaseVersion {
+        "0011_resumable_uploads".into()
+    }
+
+    async fn migrate(&self, tx: &Transaction<'_>) -> Result<()> {
+        tx.batch_execute(
+            r#"
+            CREATE TYPE "ResumableFileUpload" AS (
+                "name" text,
+                byte_size bigint,
+                sha256 text
+            );
+
+            CREATE TABLE resumable_uploads (
+                id uuid PRIMARY KEY,
+                files "ResumableFileUpload" [] NOT NULL
+            );
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -8,6 +8,9 @@ pub use crate::contexts::migrations::{
     migration_0006_ebv_provider::Migration0006EbvProvider,
     migration_0007_owner_role::Migration0007OwnerRole,
     migration_0008_band_names::Migration0008BandNames,
+    migration_0009_stac_provider::Migration0009StacProvider,
+    migration_0010_tasks::Migration0010Tasks,
+    migration_0011_resumable_uploads::Migration0011ResumableUploads,
 };
 pub use database_migration::{
     initialize_database, migrate_database, DatabaseVersion, Migration, MigrationResult,
@@ -24,6 +27,9 @@ pub mod migration_0005_gbif_column_selection;
 mod migration_0006_ebv_provider;
 pub mod migration_0007_owner_role;
 pub mod migration_0008_band_names;
+pub mod migration_0009_stac_provider;
+pub mod migration_0010_tasks;
+pub mod migration_0011_resumable_uploads;
 
 #[cfg(test)]
 mod schema_info;
@@ -45,6 +51,9 @@ pub fn all_migrations() -> Vec<Box<dyn Migration>> {
         Box::new(Migration0006EbvProvider),
         Box::new(Migration0007OwnerRole),
         Box::new(Migration0008BandNames),
+        Box::new(Migration0009StacProvider),
+        Box::new(Migration0010Tasks),
+        Box::new(Migration0011ResumableUploads),
     ]
 }
 
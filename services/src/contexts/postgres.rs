@@ -4,13 +4,16 @@ use crate::api::cli::{add_datasets_from_directory, add_providers_from_directory}
 use crate::contexts::{ApplicationContext, QueryContextImpl, SessionId, SimpleSession};
 use crate::contexts::{GeoEngineDb, SessionContext};
 use crate::datasets::upload::{Volume, Volumes};
-use crate::datasets::DatasetName;
+use crate::datasets::{
+    raster_dataset_from_workflow_task_loader, vector_dataset_from_workflow_task_loader,
+    DatasetName, RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE, VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE,
+};
 use crate::error::{self, Error, Result};
 use crate::layers::add_from_directory::{
     add_layer_collections_from_directory, add_layers_from_directory,
 };
 use crate::projects::{ProjectId, STRectangle};
-use crate::tasks::{SimpleTaskManager, SimpleTaskManagerBackend, SimpleTaskManagerContext};
+use crate::tasks::{PostgresTaskManager, PostgresTaskManagerBackend, SimpleTaskManagerContext};
 use crate::util::config;
 use crate::util::config::get_config_element;
 use async_trait::async_trait;
@@ -44,7 +47,7 @@ where
     thread_pool: Arc<ThreadPool>,
     exe_ctx_tiling_spec: TilingSpecification,
     query_ctx_chunk_size: ChunkByteSize,
-    task_manager: Arc<SimpleTaskManagerBackend>,
+    task_manager: Arc<PostgresTaskManagerBackend<Tls>>,
     pool: Pool<PostgresConnectionManager<Tls>>,
     volumes: Volumes,
 }
@@ -81,15 +84,19 @@ where
             Self::load_default_session(pool.get().await?).await?
         };
 
-        Ok(PostgresContext {
+        let app_ctx = PostgresContext {
             default_session_id: session.id(),
-            task_manager: Default::default(),
+            task_manager: Arc::new(PostgresTaskManagerBackend::new_with_config(pool.clone())?),
             thread_pool: create_rayon_thread_pool(0),
             exe_ctx_tiling_spec,
             query_ctx_chunk_size,
             pool,
             volumes: Default::default(),
-        })
+        };
+
+        app_ctx.register_task_loaders();
+
+        Ok(app_ctx)
     }
 
     // TODO: check if the datasets exist already and don't output warnings when skipping them
@@ -119,7 +126,7 @@ where
 
         let app_ctx = PostgresContext {
             default_session_id: session.id(),
-            task_manager: Default::default(),
+            task_manager: Arc::new(PostgresTaskManagerBackend::new_with_config(pool.clone())?),
             thread_pool: create_rayon_thread_pool(0),
             exe_ctx_tiling_spec,
             query_ctx_chunk_size,
@@ -127,6 +134,8 @@ where
             volumes: Default::default(),
         };
 
+        app_ctx.register_task_loaders();
+
         if created_schema {
             info!("Populating database with initial data...");
 
@@ -144,6 +153,18 @@ where
         Ok(app_ctx)
     }
 
+    /// Register the loaders of the tasks that instances resume from the shared task queue
+    fn register_task_loaders(&self) {
+        self.task_manager.register_task_loader(
+            RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE,
+            raster_dataset_from_workflow_task_loader(self.clone()),
+        );
+        self.task_manager.register_task_loader(
+            VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE,
+            vector_dataset_from_workflow_task_loader(self.clone()),
+        );
+    }
+
     async fn check_schema_status(
         conn: &PooledConnection<'_, PostgresConnectionManager<Tls>>,
     ) -> Result<DatabaseStatus> {
@@ -354,7 +375,7 @@ where
     type GeoEngineDB = PostgresDb<Tls>;
 
     type TaskContext = SimpleTaskManagerContext;
-    type TaskManager = SimpleTaskManager; // this does not persist across restarts
+    type TaskManager = PostgresTaskManager<Tls>;
     type QueryContext = QueryContextImpl;
     type ExecutionContext = ExecutionContextImpl<Self::GeoEngineDB>;
 
@@ -363,7 +384,7 @@ where
     }
 
     fn tasks(&self) -> Self::TaskManager {
-        SimpleTaskManager::new(self.context.task_manager.clone())
+        PostgresTaskManager::new(self.context.task_manager.clone())
     }
 
     fn query_context(&self) -> Result<Self::QueryContext> {
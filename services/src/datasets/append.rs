@@ -0,0 +1,539 @@
+use crate::contexts::SessionContext;
+use crate::datasets::storage::{DatasetStore, MetaDataDefinition};
+use crate::error::{self, Result};
+use crate::util::config::{self, get_config_element};
+use crate::util::spawn_blocking;
+use futures::{FutureExt, TryStreamExt};
+use gdal::vector::{Feature, Layer, LayerAccess};
+use gdal::{DatasetOptions, GdalOpenFlags};
+use geoengine_datatypes::collections::{
+    FeatureCollection, FeatureCollectionInfos, IntoGeometryOptionsIterator,
+};
+use geoengine_datatypes::dataset::DatasetId;
+use geoengine_datatypes::primitives::{
+    BoundingBox2D, ColumnSelection, Geometry, GeometryRef, MultiLineString, MultiPoint,
+    MultiPolygon, NoGeometry, SpatialResolution, TimeInstance, TimeInterval, VectorDataType,
+    VectorQueryRectangle,
+};
+use geoengine_datatypes::util::arrow::ArrowTyped;
+use geoengine_operators::call_on_generic_vector_processor;
+use geoengine_operators::engine::{
+    QueryContext, StaticMetaData, TypedVectorQueryProcessor, VectorQueryProcessor,
+    VectorResultDescriptor,
+};
+use geoengine_operators::source::{
+    GdalLoadingInfoTemporalSlice, GdalMetaDataList, GdalMetaDataRegular, OgrSourceDataset,
+    OgrSourceProcessor,
+};
+use geoengine_operators::util::gdal::{gdal_open_dataset, gdal_open_dataset_ex};
+use snafu::ensure;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+type OgrMetaData = StaticMetaData<OgrSourceDataset, VectorResultDescriptor, VectorQueryRectangle>;
+
+/// OGR drivers that support transactions when appending features.
+/// Only datasets of these drivers can be appended to, so that a failed copy leaves the file unchanged.
+const TRANSACTIONAL_OGR_DRIVERS: [&str; 2] = ["GPKG", "SQLite"];
+
+/// Data that is appended to an existing dataset
+#[derive(Debug, Clone)]
+pub enum DatasetAppend {
+    /// New time slices of a `GdalMetaDataList` dataset
+    TemporalSlices(Vec<GdalLoadingInfoTemporalSlice>),
+    /// A new end of the data time of a `GdalMetaDataRegular` dataset
+    DataTimeEnd(TimeInstance),
+    /// A layer whose features are copied into the file of an `OgrMetaData` dataset
+    OgrLayer {
+        file_path: PathBuf,
+        layer_name: String,
+    },
+}
+
+impl DatasetAppend {
+    fn type_name(&self) -> &str {
+        match self {
+            DatasetAppend::TemporalSlices(_) => "Temporal slices",
+            DatasetAppend::DataTimeEnd(_) => "A data time end",
+            DatasetAppend::OgrLayer { .. } => "An OGR layer",
+        }
+    }
+}
+
+/// Appends data to an existing dataset and extends its result descriptor accordingly.
+/// The dataset keeps its id and name, so workflows that reference it see the new data.
+///
+/// The dataset is locked during the append, so concurrent appends are applied one after another.
+/// Features of an OGR layer are copied while the dataset is locked,
+/// so a failed copy leaves the loading information unchanged.
+pub async fn append_to_dataset<C: SessionContext>(
+    ctx: &C,
+    dataset: DatasetId,
+    append: DatasetAppend,
+) -> Result<()> {
+    let db = ctx.db();
+
+    db.update_dataset_loading_info(dataset, move |mut meta_data| {
+        async move {
+            match (&mut meta_data, append) {
+                (
+                    MetaDataDefinition::GdalMetaDataList(meta_data),
+                    DatasetAppend::TemporalSlices(slices),
+                ) => append_temporal_slices(meta_data, slices)?,
+                (
+                    MetaDataDefinition::GdalMetaDataRegular(meta_data),
+                    DatasetAppend::DataTimeEnd(end),
+                ) => extend_data_time(meta_data, end)?,
+                (
+                    MetaDataDefinition::OgrMetaData(meta_data),
+                    DatasetAppend::OgrLayer {
+                        file_path,
+                        layer_name,
+                    },
+                ) => {
+                    let query_ctx = ctx.query_context()?;
+                    let feature_copy =
+                        append_ogr_layer(meta_data, file_path, layer_name, &query_ctx).await?;
+                    spawn_blocking(move || feature_copy.run()).await??;
+                }
+                (meta_data, append) => {
+                    return Err(error::Error::InvalidDatasetAppend {
+                        reason: format!(
+                            "{} cannot be appended to a dataset of type {}",
+                            append.type_name(),
+                            meta_data.type_name()
+                        ),
+                    });
+                }
+            }
+
+            Ok(meta_data)
+        }
+        .boxed()
+    })
+    .await
+}
+
+fn append_temporal_slices(
+    meta_data: &mut GdalMetaDataList,
+    slices: Vec<GdalLoadingInfoTemporalSlice>,
+) -> Result<()> {
+    let new_time = slices
+        .iter()
+        .map(|slice| slice.time)
+        .reduce(|a, b| a.extend(&b));
+
+    let Some(new_time) = new_time else {
+        return Err(error::Error::InvalidDatasetAppend {
+            reason: "No temporal slices given".to_string(),
+        });
+    };
+
+    meta_data.params.extend(slices);
+    meta_data.params.sort_by_key(|slice| slice.time.start());
+
+    ensure!(
+        meta_data
+            .params
+            .windows(2)
+            .all(|slices| slices[0].time.end() <= slices[1].time.start()),
+        error::InvalidDatasetAppend {
+            reason: "Temporal slices must not overlap",
+        }
+    );
+
+    meta_data.result_descriptor.time = meta_data
+        .result_descriptor
+        .time
+        .map(|time| time.extend(&new_time));
+
+    Ok(())
+}
+
+fn extend_data_time(meta_data: &mut GdalMetaDataRegular, end: TimeInstance) -> Result<()> {
+    ensure!(
+        end > meta_data.data_time.end(),
+        error::InvalidDatasetAppend {
+            reason: "The new end must be after the current end of the data time",
+        }
+    );
+
+    meta_data.data_time = TimeInterval::new(meta_data.data_time.start(), end)?;
+
+    meta_data.result_descriptor.time = meta_data
+        .result_descriptor
+        .time
+        .map(|time| time.extend(&meta_data.data_time));
+
+    Ok(())
+}
+
+/// Extends the result descriptor by the bounds of the layer.
+/// Returns the copy of the layer's features that is still to be run.
+async fn append_ogr_layer(
+    meta_data: &mut OgrMetaData,
+    file_path: PathBuf,
+    layer_name: String,
+    query_ctx: &dyn QueryContext,
+) -> Result<OgrFeatureCopy> {
+    // only files of uploads are modified, never files on volumes
+    let upload_path = get_config_element::<config::Upload>()?.path;
+    ensure!(
+        meta_data.loading_info.file_name.starts_with(upload_path),
+        error::InvalidDatasetAppend {
+            reason: "Only datasets created from uploads can be appended to",
+        }
+    );
+
+    let feature_copy = OgrFeatureCopy {
+        source_path: file_path.clone(),
+        source_layer_name: layer_name.clone(),
+        target_path: meta_data.loading_info.file_name.clone(),
+        target_layer_name: meta_data.loading_info.layer_name.clone(),
+    };
+    let feature_copy =
+        spawn_blocking(move || feature_copy.check().map(|()| feature_copy)).await??;
+
+    let (bbox, time) = ogr_layer_bounds(meta_data, file_path, layer_name, query_ctx).await?;
+
+    // unknown bounds stay unknown
+    let result_descriptor = &mut meta_data.result_descriptor;
+    if let (Some(current), Some(bbox)) = (result_descriptor.bbox, bbox) {
+        result_descriptor.bbox = Some(current.union(&bbox));
+    }
+    if let (Some(current), Some(time)) = (result_descriptor.time, time) {
+        result_descriptor.time = Some(current.extend(&time));
+    }
+
+    Ok(feature_copy)
+}
+
+/// Computes the bounds of a layer as read with the loading information of the dataset
+async fn ogr_layer_bounds(
+    meta_data: &OgrMetaData,
+    file_path: PathBuf,
+    layer_name: String,
+    query_ctx: &dyn QueryContext,
+) -> Result<(Option<BoundingBox2D>, Option<TimeInterval>)> {
+    let result_descriptor = meta_data.result_descriptor.clone();
+    let layer_meta_data = Box::new(OgrMetaData {
+        loading_info: OgrSourceDataset {
+            file_name: file_path,
+            layer_name,
+            sql_query: None,
+            ..meta_data.loading_info.clone()
+        },
+        result_descriptor: result_descriptor.clone(),
+        phantom: Default::default(),
+    });
+
+    let processor = match result_descriptor.data_type {
+        VectorDataType::Data => TypedVectorQueryProcessor::Data(
+            OgrSourceProcessor::<NoGeometry>::new(result_descriptor, layer_meta_data, vec![])
+                .boxed(),
+        ),
+        VectorDataType::MultiPoint => TypedVectorQueryProcessor::MultiPoint(
+            OgrSourceProcessor::<MultiPoint>::new(result_descriptor, layer_meta_data, vec![])
+                .boxed(),
+        ),
+        VectorDataType::MultiLineString => TypedVectorQueryProcessor::MultiLineString(
+            OgrSourceProcessor::<MultiLineString>::new(result_descriptor, layer_meta_data, vec![])
+                .boxed(),
+        ),
+        VectorDataType::MultiPolygon => TypedVectorQueryProcessor::MultiPolygon(
+            OgrSourceProcessor::<MultiPolygon>::new(result_descriptor, layer_meta_data, vec![])
+                .boxed(),
+        ),
+    };
+
+    let query = VectorQueryRectangle {
+        spatial_bounds: BoundingBox2D::new(
+            (f64::MIN, f64::MIN).into(),
+            (f64::MAX, f64::MAX).into(),
+        )?,
+        time_interval: TimeInterval::default(),
+        spatial_resolution: SpatialResolution::one(),
+        attributes: ColumnSelection::all(),
+    };
+
+    call_on_generic_vector_processor!(processor, p => {
+        collection_stream_bounds(p, query, query_ctx).await
+    })
+}
+
+async fn collection_stream_bounds<G>(
+    processor: Box<dyn VectorQueryProcessor<VectorType = FeatureCollection<G>>>,
+    query: VectorQueryRectangle,
+    query_ctx: &dyn QueryContext,
+) -> Result<(Option<BoundingBox2D>, Option<TimeInterval>)>
+where
+    G: Geometry + ArrowTyped,
+    for<'g> FeatureCollection<G>: IntoGeometryOptionsIterator<'g>,
+{
+    let stream = processor.vector_query(query, query_ctx).await?;
+
+    stream
+        .map_err(error::Error::from)
+        .try_fold((None, None), |(bbox, time), collection| async move {
+            let bbox = collection
+                .geometry_options()
+                .flatten()
+                .filter_map(|geometry| geometry.bbox())
+                .fold(bbox, |bbox: Option<BoundingBox2D>, geometry_bbox| {
+                    Some(bbox.map_or(geometry_bbox, |bbox| bbox.union(&geometry_bbox)))
+                });
+
+            let time = match (time, collection.time_bounds()) {
+                (Some(time), Some(collection_time)) => Some(time.extend(&collection_time)),
+                (time, collection_time) => time.or(collection_time),
+            };
+
+            Ok((bbox, time))
+        })
+        .await
+}
+
+/// Copies all features of a layer into the layer of the dataset's file
+struct OgrFeatureCopy {
+    source_path: PathBuf,
+    source_layer_name: String,
+    target_path: PathBuf,
+    target_layer_name: String,
+}
+
+impl OgrFeatureCopy {
+    /// Checks that the dataset's file supports transactions and
+    /// that the layer's features fit into it without conversion.
+    fn check(&self) -> Result<()> {
+        let source = gdal_open_dataset(&self.source_path)?;
+        let source_layer = source.layer_by_name(&self.source_layer_name)?;
+
+        let target = gdal_open_dataset(&self.target_path)?;
+        let target_layer = target.layer_by_name(&self.target_layer_name)?;
+
+        ensure!(
+            TRANSACTIONAL_OGR_DRIVERS.contains(&target.driver().short_name().as_str()),
+            error::InvalidDatasetAppend {
+                reason: "Only datasets stored in GeoPackage or SQLite files can be appended to",
+            }
+        );
+
+        // coordinates are copied as they are, so they must refer to the same spatial reference
+        ensure!(
+            source_layer.spatial_ref() == target_layer.spatial_ref(),
+            error::InvalidDatasetAppend {
+                reason: "The layer has a different spatial reference than the dataset",
+            }
+        );
+
+        let target_fields = target_layer
+            .defn()
+            .fields()
+            .map(|field| (field.name(), field.field_type()))
+            .collect::<HashMap<_, _>>();
+
+        for field in source_layer.defn().fields() {
+            let name = field.name();
+
+            match target_fields.get(&name) {
+                None => {
+                    return Err(error::Error::InvalidDatasetAppend {
+                        reason: format!("The dataset has no attribute `{name}`"),
+                    })
+                }
+                Some(field_type) if *field_type != field.field_type() => {
+                    return Err(error::Error::InvalidDatasetAppend {
+                        reason: format!(
+                            "The attribute `{name}` has a different type than in the dataset"
+                        ),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies the features in a transaction, so that a failed copy leaves the dataset's file unchanged
+    fn run(self) -> Result<()> {
+        let source = gdal_open_dataset(&self.source_path)?;
+        let mut source_layer = source.layer_by_name(&self.source_layer_name)?;
+
+        let mut target = gdal_open_dataset_ex(
+            &self.target_path,
+            DatasetOptions {
+                open_flags: GdalOpenFlags::GDAL_OF_VECTOR | GdalOpenFlags::GDAL_OF_UPDATE,
+                ..Default::default()
+            },
+        )?;
+
+        let transaction = target.start_transaction()?;
+        copy_layer_features(
+            &mut source_layer,
+            &transaction.layer_by_name(&self.target_layer_name)?,
+        )?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
+
+fn copy_layer_features(source: &mut Layer, target: &Layer) -> Result<()> {
+    for source_feature in source.features() {
+        let mut feature = Feature::new(target.defn())?;
+
+        // features without geometry are copied as such
+        if let Ok(geometry) = source_feature.geometry_by_index(0) {
+            feature.set_geometry(geometry.clone())?;
+        }
+
+        for (name, value) in source_feature.fields() {
+            if let Some(value) = value {
+                feature.set_field(&name, &value)?;
+            }
+        }
+
+        feature.create(target)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geoengine_datatypes::primitives::{TimeGranularity, TimeStep};
+    use geoengine_datatypes::raster::RasterDataType;
+    use geoengine_datatypes::spatial_reference::SpatialReference;
+    use geoengine_datatypes::test_data;
+    use geoengine_operators::engine::{RasterBandDescriptors, RasterResultDescriptor};
+    use geoengine_operators::source::{
+        FileNotFoundHandling, GdalDatasetGeoTransform, GdalDatasetParameters,
+    };
+
+    fn slice(start: i64, end: i64) -> GdalLoadingInfoTemporalSlice {
+        GdalLoadingInfoTemporalSlice {
+            time: TimeInterval::new_unchecked(start, end),
+            params: Some(GdalDatasetParameters {
+                file_path: format!("{start}.tiff").into(),
+                rasterband_channel: 1,
+                geo_transform: GdalDatasetGeoTransform {
+                    origin_coordinate: (0., 0.).into(),
+                    x_pixel_size: 1.,
+                    y_pixel_size: -1.,
+                },
+                width: 10,
+                height: 10,
+                file_not_found_handling: FileNotFoundHandling::NoData,
+                no_data_value: None,
+                properties_mapping: None,
+                gdal_open_options: None,
+                gdal_config_options: None,
+                allow_alphaband_as_mask: true,
+                retry: None,
+            }),
+            cache_ttl: Default::default(),
+        }
+    }
+
+    fn meta_data_list(params: Vec<GdalLoadingInfoTemporalSlice>) -> GdalMetaDataList {
+        GdalMetaDataList {
+            result_descriptor: RasterResultDescriptor {
+                data_type: RasterDataType::U8,
+                spatial_reference: SpatialReference::epsg_4326().into(),
+                time: Some(TimeInterval::new_unchecked(0, 20)),
+                bbox: None,
+                resolution: None,
+                bands: RasterBandDescriptors::new_single_band(),
+            },
+            params,
+        }
+    }
+
+    #[test]
+    fn it_appends_temporal_slices() {
+        let mut meta_data = meta_data_list(vec![slice(0, 10), slice(10, 20)]);
+
+        append_temporal_slices(&mut meta_data, vec![slice(30, 40), slice(20, 30)]).unwrap();
+
+        assert_eq!(
+            meta_data
+                .params
+                .iter()
+                .map(|slice| slice.time)
+                .collect::<Vec<_>>(),
+            vec![
+                TimeInterval::new_unchecked(0, 10),
+                TimeInterval::new_unchecked(10, 20),
+                TimeInterval::new_unchecked(20, 30),
+                TimeInterval::new_unchecked(30, 40),
+            ]
+        );
+        assert_eq!(
+            meta_data.result_descriptor.time,
+            Some(TimeInterval::new_unchecked(0, 40))
+        );
+    }
+
+    #[test]
+    fn it_rejects_overlapping_temporal_slices() {
+        let mut meta_data = meta_data_list(vec![slice(0, 10), slice(10, 20)]);
+
+        assert!(append_temporal_slices(&mut meta_data, vec![slice(15, 30)]).is_err());
+        assert!(append_temporal_slices(&mut meta_data_list(vec![]), vec![]).is_err());
+    }
+
+    #[test]
+    fn it_extends_the_data_time() {
+        let mut meta_data = GdalMetaDataRegular {
+            result_descriptor: meta_data_list(vec![]).result_descriptor,
+            params: slice(0, 10).params.unwrap(),
+            time_placeholders: Default::default(),
+            data_time: TimeInterval::new_unchecked(0, 20),
+            step: TimeStep {
+                granularity: TimeGranularity::Millis,
+                step: 10,
+            },
+            cache_ttl: Default::default(),
+        };
+
+        assert!(extend_data_time(&mut meta_data, TimeInstance::from_millis_unchecked(10)).is_err());
+
+        extend_data_time(&mut meta_data, TimeInstance::from_millis_unchecked(40)).unwrap();
+
+        assert_eq!(meta_data.data_time, TimeInterval::new_unchecked(0, 40));
+        assert_eq!(
+            meta_data.result_descriptor.time,
+            Some(TimeInterval::new_unchecked(0, 40))
+        );
+    }
+
+    fn ports_copy(source_path: &str, target_path: &str) -> OgrFeatureCopy {
+        OgrFeatureCopy {
+            source_path: test_data!(source_path).to_path_buf(),
+            source_layer_name: "ne_10m_ports".to_string(),
+            target_path: test_data!(target_path).to_path_buf(),
+            target_layer_name: "ne_10m_ports".to_string(),
+        }
+    }
+
+    #[test]
+    fn it_checks_ogr_feature_copies() {
+        let gpkg = "vector/data/ne_10m_ports/with_spatial_index/ne_10m_ports.gpkg";
+
+        assert!(ports_copy(gpkg, gpkg).check().is_ok());
+
+        assert!(matches!(
+            ports_copy(gpkg, "vector/data/ne_10m_ports/ne_10m_ports.shp").check(),
+            Err(error::Error::InvalidDatasetAppend { reason })
+                if reason == "Only datasets stored in GeoPackage or SQLite files can be appended to"
+        ));
+
+        assert!(matches!(
+            ports_copy("vector/data/ne_10m_ports/projected_3857/ne_10m_ports.shp", gpkg).check(),
+            Err(error::Error::InvalidDatasetAppend { reason })
+                if reason == "The layer has a different spatial reference than the dataset"
+        ));
+    }
+}
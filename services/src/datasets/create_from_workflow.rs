@@ -1,19 +1,23 @@
-use crate::api::model::datatypes::RasterQueryRectangle;
-use crate::contexts::SessionContext;
-use crate::datasets::listing::DatasetProvider;
+use crate::api::model::datatypes::{RasterQueryRectangle, VectorQueryRectangle};
+use crate::contexts::{ApplicationContext, Session, SessionContext, SessionId};
+use crate::datasets::listing::{DatasetProvider, Provenance};
 use crate::datasets::storage::{DatasetDefinition, DatasetStore, MetaDataDefinition};
 use crate::datasets::upload::{UploadId, UploadRootPath};
 use crate::datasets::AddDataset;
 use crate::error;
-use crate::tasks::{Task, TaskId, TaskManager, TaskStatusInfo};
+use crate::tasks::{
+    SimpleTaskManagerContext, Task, TaskId, TaskLoader, TaskManager, TaskStatusInfo,
+};
+use crate::workflows::provenance::workflow_provenance;
 use crate::workflows::workflow::Workflow;
+use futures::FutureExt;
 use geoengine_datatypes::error::ErrorSource;
 use geoengine_datatypes::primitives::TimeInterval;
 use geoengine_datatypes::spatial_reference::SpatialReference;
 use geoengine_datatypes::util::Identifier;
-use geoengine_operators::call_on_generic_raster_processor_gdal_types;
 use geoengine_operators::engine::{
-    ExecutionContext, InitializedRasterOperator, RasterResultDescriptor, WorkflowOperatorPath,
+    ExecutionContext, InitializedRasterOperator, InitializedVectorOperator, RasterResultDescriptor,
+    StaticMetaData, VectorColumnInfo, VectorResultDescriptor, WorkflowOperatorPath,
 };
 use geoengine_operators::source::{
     GdalLoadingInfoTemporalSlice, GdalMetaDataList, GdalMetaDataStatic,
@@ -22,6 +26,12 @@ use geoengine_operators::util::raster_stream_to_geotiff::{
     raster_stream_to_geotiff, GdalCompressionNumThreads, GdalGeoTiffDatasetMetadata,
     GdalGeoTiffOptions,
 };
+use geoengine_operators::util::vector_stream_to_ogr::{
+    vector_stream_to_ogr, OgrVectorFile, OgrVectorFileFormat, OgrVectorFileOptions,
+};
+use geoengine_operators::{
+    call_on_generic_raster_processor_gdal_types, call_on_generic_vector_processor,
+};
 use serde::{Deserialize, Serialize};
 use snafu::{ensure, ResultExt};
 use std::path::PathBuf;
@@ -60,6 +70,8 @@ pub struct RasterDatasetFromWorkflowResult {
 
 impl TaskStatusInfo for RasterDatasetFromWorkflowResult {}
 
+pub const RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE: &str = "create-dataset";
+
 pub struct RasterDatasetFromWorkflowTask<C: SessionContext> {
     pub source_name: String,
     pub workflow: Workflow,
@@ -165,7 +177,7 @@ impl<C: SessionContext> Task<C::TaskContext> for RasterDatasetFromWorkflowTask<C
     }
 
     fn task_type(&self) -> &'static str {
-        "create-dataset"
+        RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE
     }
 
     fn task_unique_id(&self) -> Option<String> {
@@ -178,6 +190,84 @@ impl<C: SessionContext> Task<C::TaskContext> for RasterDatasetFromWorkflowTask<C
             self.info.display_name, self.source_name
         )
     }
+
+    fn task_payload(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(RasterDatasetFromWorkflowTaskPayload {
+            session: self.ctx.session().id(),
+            source_name: self.source_name.clone(),
+            workflow: self.workflow.clone(),
+            info: self.info.clone(),
+            upload: self.upload,
+            compression_num_threads: self.compression_num_threads,
+        })
+        .ok()
+    }
+}
+
+/// The serialized [`RasterDatasetFromWorkflowTask`] for resuming it on any instance
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RasterDatasetFromWorkflowTaskPayload {
+    session: SessionId,
+    source_name: String,
+    workflow: Workflow,
+    info: RasterDatasetFromWorkflow,
+    upload: UploadId,
+    compression_num_threads: GdalCompressionNumThreads,
+}
+
+/// A [`TaskLoader`] that restores [`RasterDatasetFromWorkflowTask`]s in the session that scheduled them.
+///
+/// The task starts over, so the files of the interrupted run are removed.
+pub fn raster_dataset_from_workflow_task_loader<A>(app_ctx: A) -> TaskLoader
+where
+    A: ApplicationContext,
+    A::SessionContext: SessionContext<TaskContext = SimpleTaskManagerContext>,
+{
+    Arc::new(move |payload: serde_json::Value| {
+        load_raster_dataset_from_workflow_task(app_ctx.clone(), payload).boxed()
+    })
+}
+
+async fn load_raster_dataset_from_workflow_task<A>(
+    app_ctx: A,
+    payload: serde_json::Value,
+) -> error::Result<Box<dyn Task<SimpleTaskManagerContext>>>
+where
+    A: ApplicationContext,
+    A::SessionContext: SessionContext<TaskContext = SimpleTaskManagerContext>,
+{
+    let payload: RasterDatasetFromWorkflowTaskPayload = serde_json::from_value(payload)?;
+    let session = app_ctx.session_by_id(payload.session).await?;
+
+    let task = RasterDatasetFromWorkflowTask {
+        source_name: payload.source_name,
+        workflow: payload.workflow,
+        ctx: Arc::new(app_ctx.session_context(session)),
+        info: payload.info,
+        upload: payload.upload,
+        file_path: reset_upload_directory(payload.upload).await?,
+        compression_num_threads: payload.compression_num_threads,
+    };
+
+    Ok(task.boxed())
+}
+
+/// Empty the directory of an upload whose task starts over
+async fn reset_upload_directory(upload: UploadId) -> error::Result<PathBuf> {
+    let upload_path = upload.root_path()?;
+
+    match fs::remove_dir_all(&upload_path).await {
+        Ok(()) => {}
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(source) => return Err(error::Error::Io { source }),
+    }
+
+    fs::create_dir_all(&upload_path)
+        .await
+        .context(crate::error::Io)?;
+
+    Ok(upload_path)
 }
 
 pub async fn schedule_raster_dataset_from_workflow_task<C: SessionContext>(
@@ -187,20 +277,7 @@ pub async fn schedule_raster_dataset_from_workflow_task<C: SessionContext>(
     info: RasterDatasetFromWorkflow,
     compression_num_threads: GdalCompressionNumThreads,
 ) -> error::Result<TaskId> {
-    if let Some(dataset_name) = &info.name {
-        let db = ctx.db();
-
-        // try to resolve the dataset name to an id
-        let potential_id_result = db.resolve_dataset_name_to_id(dataset_name).await?;
-
-        // handle the case where the dataset name is already taken
-        if let Some(dataset_id) = potential_id_result {
-            return Err(error::Error::DatasetNameAlreadyExists {
-                dataset_name: dataset_name.to_string(),
-                dataset_id: dataset_id.into(),
-            });
-        }
-    }
+    ensure_dataset_name_is_available(ctx.as_ref(), info.name.as_ref()).await?;
 
     let upload = UploadId::new();
     let upload_path = upload.root_path()?;
@@ -225,6 +302,30 @@ pub async fn schedule_raster_dataset_from_workflow_task<C: SessionContext>(
     Ok(task_id)
 }
 
+async fn ensure_dataset_name_is_available<C: SessionContext>(
+    ctx: &C,
+    dataset_name: Option<&DatasetName>,
+) -> error::Result<()> {
+    let Some(dataset_name) = dataset_name else {
+        return Ok(());
+    };
+
+    let db = ctx.db();
+
+    // try to resolve the dataset name to an id
+    let potential_id_result = db.resolve_dataset_name_to_id(dataset_name).await?;
+
+    // handle the case where the dataset name is already taken
+    if let Some(dataset_id) = potential_id_result {
+        return Err(error::Error::DatasetNameAlreadyExists {
+            dataset_name: dataset_name.to_string(),
+            dataset_id: dataset_id.into(),
+        });
+    }
+
+    Ok(())
+}
+
 async fn create_dataset<C: SessionContext>(
     info: RasterDatasetFromWorkflow,
     mut slice_info: Vec<GdalLoadingInfoTemporalSlice>,
@@ -295,3 +396,319 @@ async fn create_dataset<C: SessionContext>(
 
     Ok(result)
 }
+
+/// parameter for the vector dataset from workflow handler (body)
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[schema(example = json!({"name": "foo", "displayName": "a new dataset", "description": null, "query": {"spatialBounds": {"lowerLeftCoordinate": {"x": -180.0, "y": -90.0}, "upperRightCoordinate": {"x": 180.0, "y": 90.0}}, "timeInterval": {"start": 1_388_534_400_000_i64, "end": 1_388_534_401_000_i64}, "spatialResolution": {"x": 0.1, "y": 0.1}}, "format": "geoPackage"}))]
+#[serde(rename_all = "camelCase")]
+pub struct VectorDatasetFromWorkflow {
+    pub name: Option<DatasetName>,
+    pub display_name: String,
+    pub description: Option<String>,
+    pub query: VectorQueryRectangle,
+    #[serde(default)]
+    pub format: VectorDatasetFormat,
+}
+
+/// file format for storing the features of a vector dataset
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum VectorDatasetFormat {
+    #[default]
+    GeoPackage,
+    FlatGeobuf,
+}
+
+impl From<VectorDatasetFormat> for OgrVectorFileFormat {
+    fn from(value: VectorDatasetFormat) -> Self {
+        match value {
+            VectorDatasetFormat::GeoPackage => Self::GeoPackage,
+            VectorDatasetFormat::FlatGeobuf => Self::FlatGeobuf,
+        }
+    }
+}
+
+/// response of the vector dataset from workflow handler
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct VectorDatasetFromWorkflowResult {
+    pub dataset: DatasetName,
+    pub upload: UploadId,
+}
+
+impl TaskStatusInfo for VectorDatasetFromWorkflowResult {}
+
+pub const VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE: &str = "create-vector-dataset";
+
+/// name of the file and layer the features are written to
+const VECTOR_DATASET_LAYER_NAME: &str = "dataset";
+
+pub struct VectorDatasetFromWorkflowTask<C: SessionContext> {
+    pub source_name: String,
+    pub workflow: Workflow,
+    pub ctx: Arc<C>,
+    pub info: VectorDatasetFromWorkflow,
+    pub upload: UploadId,
+    pub upload_path: PathBuf,
+}
+
+impl<C: SessionContext> VectorDatasetFromWorkflowTask<C> {
+    async fn process(&self) -> error::Result<VectorDatasetFromWorkflowResult> {
+        let operator = self.workflow.operator.clone();
+
+        let operator = operator.get_vector().context(crate::error::Operator)?;
+
+        let execution_context = self.ctx.execution_context()?;
+
+        let workflow_operator_path_root = WorkflowOperatorPath::initialize_root();
+
+        let initialized = operator
+            .initialize(workflow_operator_path_root, &execution_context)
+            .await
+            .context(crate::error::Operator)?;
+
+        let result_descriptor = initialized.result_descriptor().clone();
+
+        let processor = initialized
+            .query_processor()
+            .context(crate::error::Operator)?;
+
+        let format = OgrVectorFileFormat::from(self.info.format);
+        let file_path = self.upload_path.join(format!(
+            "{VECTOR_DATASET_LAYER_NAME}.{}",
+            format.file_extension()
+        ));
+
+        let options = OgrVectorFileOptions {
+            format,
+            layer_name: VECTOR_DATASET_LAYER_NAME.to_owned(),
+            spatial_reference: result_descriptor.spatial_reference,
+            columns: result_descriptor
+                .columns
+                .iter()
+                .map(|(name, column)| (name.clone(), column.data_type))
+                .collect(),
+        };
+
+        let query_rect = self.info.query;
+        let query_ctx = self.ctx.query_context()?;
+
+        // write the features
+        let file = call_on_generic_vector_processor!(processor, p => vector_stream_to_ogr(
+            &file_path,
+            p,
+            query_rect.into(),
+            query_ctx,
+            options,
+            Box::pin(futures::future::pending()), // datasets shall continue to be built in the background and not cancelled
+        ).await)
+        .map_err(crate::error::Error::from)?;
+
+        let provenance = workflow_provenance(&self.workflow, self.ctx.as_ref())
+            .await?
+            .into_iter()
+            .map(|entry| entry.provenance)
+            .collect();
+
+        // create the dataset
+        let dataset = create_vector_dataset(
+            self.info.clone(),
+            file,
+            &result_descriptor,
+            provenance,
+            self.ctx.as_ref(),
+        )
+        .await?;
+
+        Ok(VectorDatasetFromWorkflowResult {
+            dataset: dataset.name,
+            upload: self.upload,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<C: SessionContext> Task<C::TaskContext> for VectorDatasetFromWorkflowTask<C> {
+    async fn run(
+        &self,
+        _ctx: C::TaskContext,
+    ) -> error::Result<Box<dyn crate::tasks::TaskStatusInfo>, Box<dyn ErrorSource>> {
+        let response = self.process().await;
+
+        response
+            .map(TaskStatusInfo::boxed)
+            .map_err(ErrorSource::boxed)
+    }
+
+    async fn cleanup_on_error(
+        &self,
+        _ctx: C::TaskContext,
+    ) -> error::Result<(), Box<dyn ErrorSource>> {
+        fs::remove_dir_all(&self.upload_path)
+            .await
+            .context(crate::error::Io)
+            .map_err(ErrorSource::boxed)?;
+
+        Ok(())
+    }
+
+    fn task_type(&self) -> &'static str {
+        VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE
+    }
+
+    fn task_unique_id(&self) -> Option<String> {
+        Some(self.upload.to_string())
+    }
+
+    fn task_description(&self) -> String {
+        format!(
+            "Creating vector dataset {} from {}",
+            self.info.display_name, self.source_name
+        )
+    }
+
+    fn task_payload(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(VectorDatasetFromWorkflowTaskPayload {
+            session: self.ctx.session().id(),
+            source_name: self.source_name.clone(),
+            workflow: self.workflow.clone(),
+            info: self.info.clone(),
+            upload: self.upload,
+        })
+        .ok()
+    }
+}
+
+/// The serialized [`VectorDatasetFromWorkflowTask`] for resuming it on any instance
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VectorDatasetFromWorkflowTaskPayload {
+    session: SessionId,
+    source_name: String,
+    workflow: Workflow,
+    info: VectorDatasetFromWorkflow,
+    upload: UploadId,
+}
+
+/// A [`TaskLoader`] that restores [`VectorDatasetFromWorkflowTask`]s in the session that scheduled them.
+///
+/// The task starts over, so the files of the interrupted run are removed.
+pub fn vector_dataset_from_workflow_task_loader<A>(app_ctx: A) -> TaskLoader
+where
+    A: ApplicationContext,
+    A::SessionContext: SessionContext<TaskContext = SimpleTaskManagerContext>,
+{
+    Arc::new(move |payload: serde_json::Value| {
+        load_vector_dataset_from_workflow_task(app_ctx.clone(), payload).boxed()
+    })
+}
+
+async fn load_vector_dataset_from_workflow_task<A>(
+    app_ctx: A,
+    payload: serde_json::Value,
+) -> error::Result<Box<dyn Task<SimpleTaskManagerContext>>>
+where
+    A: ApplicationContext,
+    A::SessionContext: SessionContext<TaskContext = SimpleTaskManagerContext>,
+{
+    let payload: VectorDatasetFromWorkflowTaskPayload = serde_json::from_value(payload)?;
+    let session = app_ctx.session_by_id(payload.session).await?;
+
+    let task = VectorDatasetFromWorkflowTask {
+        source_name: payload.source_name,
+        workflow: payload.workflow,
+        ctx: Arc::new(app_ctx.session_context(session)),
+        info: payload.info,
+        upload: payload.upload,
+        upload_path: reset_upload_directory(payload.upload).await?,
+    };
+
+    Ok(task.boxed())
+}
+
+pub async fn schedule_vector_dataset_from_workflow_task<C: SessionContext>(
+    source_name: String,
+    workflow: Workflow,
+    ctx: Arc<C>,
+    info: VectorDatasetFromWorkflow,
+) -> error::Result<TaskId> {
+    ensure_dataset_name_is_available(ctx.as_ref(), info.name.as_ref()).await?;
+
+    let upload = UploadId::new();
+    let upload_path = upload.root_path()?;
+    fs::create_dir_all(&upload_path)
+        .await
+        .context(crate::error::Io)?;
+
+    let task = VectorDatasetFromWorkflowTask {
+        source_name,
+        workflow,
+        ctx: ctx.clone(),
+        info,
+        upload,
+        upload_path,
+    }
+    .boxed();
+
+    let task_id = ctx.tasks().schedule_task(task, None).await?;
+
+    Ok(task_id)
+}
+
+async fn create_vector_dataset<C: SessionContext>(
+    info: VectorDatasetFromWorkflow,
+    file: OgrVectorFile,
+    origin_result_descriptor: &VectorResultDescriptor,
+    provenance: Vec<Provenance>,
+    ctx: &C,
+) -> error::Result<DatasetIdAndName> {
+    // the column types may differ from the workflow, e.g., categories are stored as integers
+    let columns = origin_result_descriptor
+        .columns
+        .iter()
+        .filter_map(|(name, column)| {
+            file.columns.get(name).map(|data_type| {
+                (
+                    name.clone(),
+                    VectorColumnInfo {
+                        data_type: *data_type,
+                        measurement: column.measurement.clone(),
+                    },
+                )
+            })
+        })
+        .collect();
+
+    let result_descriptor = VectorResultDescriptor {
+        data_type: origin_result_descriptor.data_type,
+        spatial_reference: origin_result_descriptor.spatial_reference,
+        columns,
+        time: file.time,
+        bbox: file.bbox,
+    };
+
+    let meta_data = MetaDataDefinition::OgrMetaData(StaticMetaData {
+        loading_info: file.loading_info,
+        result_descriptor,
+        phantom: Default::default(),
+    });
+
+    let dataset_definition = DatasetDefinition {
+        properties: AddDataset {
+            name: info.name,
+            display_name: info.display_name,
+            description: info.description.unwrap_or_default(),
+            source_operator: "OgrSource".to_owned(),
+            symbology: None,
+            provenance: (!provenance.is_empty()).then_some(provenance),
+            tags: Some(vec!["workflow".to_owned()]),
+        },
+        meta_data,
+    };
+
+    let db = ctx.db();
+    let result = db
+        .add_dataset(dataset_definition.properties, dataset_definition.meta_data)
+        .await?;
+
+    Ok(result)
+}
@@ -2276,6 +2276,7 @@ mod tests {
                     expression: "A".to_string(),
                     output_type: RasterDataType::F64,
                     output_band: None,
+                    output_bands: vec![],
                     map_no_data: false,
                 },
                 sources: SingleRasterSource {
@@ -1,3 +1,4 @@
+mod append;
 mod create_from_workflow;
 pub(crate) mod dataset_listing_provider;
 pub mod external; // TODO: move to layers/external
@@ -7,9 +8,13 @@ pub mod postgres;
 pub mod storage;
 pub mod upload;
 
+pub(crate) use append::{append_to_dataset, DatasetAppend};
 pub(crate) use create_from_workflow::{
-    schedule_raster_dataset_from_workflow_task, RasterDatasetFromWorkflow,
-    RasterDatasetFromWorkflowResult,
+    raster_dataset_from_workflow_task_loader, schedule_raster_dataset_from_workflow_task,
+    schedule_vector_dataset_from_workflow_task, vector_dataset_from_workflow_task_loader,
+    RasterDatasetFromWorkflow, RasterDatasetFromWorkflowResult, VectorDatasetFormat,
+    VectorDatasetFromWorkflow, VectorDatasetFromWorkflowResult,
+    RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE, VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE,
 };
 pub use name::{DatasetIdAndName, DatasetName};
 pub use storage::AddDataset;
@@ -6,7 +6,7 @@ use crate::datasets::listing::ProvenanceOutput;
 use crate::datasets::listing::{DatasetListOptions, DatasetListing, DatasetProvider};
 use crate::datasets::storage::{Dataset, DatasetDb, DatasetStore, MetaDataDefinition};
 use crate::datasets::upload::FileId;
-use crate::datasets::upload::{Upload, UploadDb, UploadId};
+use crate::datasets::upload::{ResumableUpload, Upload, UploadDb, UploadId};
 use crate::error::{self, Result};
 use crate::projects::Symbology;
 use crate::util::postgres::PostgresErrorExt;
@@ -15,6 +15,7 @@ use bb8_postgres::bb8::PooledConnection;
 use bb8_postgres::tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
 use bb8_postgres::tokio_postgres::Socket;
 use bb8_postgres::PostgresConnectionManager;
+use futures::future::BoxFuture;
 use geoengine_datatypes::dataset::{DataId, DatasetId};
 use geoengine_datatypes::primitives::RasterQueryRectangle;
 use geoengine_datatypes::primitives::VectorQueryRectangle;
@@ -598,6 +599,44 @@ where
         Ok(())
     }
 
+    async fn update_dataset_loading_info<'a, F>(
+        &'a self,
+        dataset: DatasetId,
+        update: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(MetaDataDefinition) -> BoxFuture<'a, Result<MetaDataDefinition>> + Send + 'a,
+    {
+        let mut conn = self.conn_pool.get().await?;
+
+        let tx = conn.build_transaction().start().await?;
+
+        let row = tx
+            .query_opt(
+                "SELECT meta_data FROM datasets WHERE id = $1 FOR UPDATE;",
+                &[&dataset],
+            )
+            .await?
+            .ok_or(error::Error::UnknownDatasetId)?;
+
+        let meta_data = update(row.get(0)).await?;
+        let typed_meta_data = meta_data.to_typed_metadata();
+
+        tx.execute(
+            "UPDATE datasets SET meta_data = $2, result_descriptor = $3 WHERE id = $1;",
+            &[
+                &dataset,
+                typed_meta_data.meta_data,
+                &typed_meta_data.result_descriptor,
+            ],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     async fn delete_dataset(&self, dataset_id: DatasetId) -> Result<()> {
         let conn = self.conn_pool.get().await?;
 
@@ -659,6 +698,199 @@ where
         .await?;
         Ok(())
     }
+
+    async fn create_resumable_upload(&self, upload: ResumableUpload) -> Result<()> {
+        let conn = self.conn_pool.get().await?;
+
+        let stmt = conn
+            .prepare("INSERT INTO resumable_uploads (id, files) VALUES ($1, $2)")
+            .await?;
+
+        conn.execute(
+            &stmt,
+            &[
+                &upload.id,
+                &upload
+                    .files
+                    .iter()
+                    .map(ResumableFileUpload::from)
+                    .collect::<Vec<_>>(),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn load_resumable_upload(&self, upload: UploadId) -> Result<ResumableUpload> {
+        let conn = self.conn_pool.get().await?;
+
+        let stmt = conn
+            .prepare("SELECT id, files FROM resumable_uploads WHERE id = $1;")
+            .await?;
+
+        let row = conn
+            .query_opt(&stmt, &[&upload])
+            .await?
+            .ok_or(error::Error::UnknownUploadId)?;
+
+        Ok(ResumableUpload {
+            id: row.get(0),
+            files: row
+                .get::<_, Vec<ResumableFileUpload>>(1)
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+
+    async fn lock_resumable_upload<'a, T, F>(&'a self, upload: UploadId, write: F) -> Result<T>
+    where
+        T: Send + 'a,
+        F: FnOnce(ResumableUpload) -> BoxFuture<'a, Result<T>> + Send + 'a,
+    {
+        let mut conn = self.conn_pool.get().await?;
+        let tx = conn.build_transaction().start().await?;
+
+        let row = tx
+            .query_opt(
+                "SELECT id, files FROM resumable_uploads WHERE id = $1 FOR UPDATE;",
+                &[&upload],
+            )
+            .await?
+            .ok_or(error::Error::UnknownUploadId)?;
+
+        let result = write(ResumableUpload {
+            id: row.get(0),
+            files: row
+                .get::<_, Vec<ResumableFileUpload>>(1)
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn finish_resumable_upload<'a, F>(&'a self, upload: UploadId, finish: F) -> Result<()>
+    where
+        F: FnOnce(ResumableUpload) -> BoxFuture<'a, Result<Upload>> + Send + 'a,
+    {
+        let mut conn = self.conn_pool.get().await?;
+        let tx = conn.build_transaction().start().await?;
+
+        let row = tx
+            .query_opt(
+                "SELECT id, files FROM resumable_uploads WHERE id = $1 FOR UPDATE;",
+                &[&upload],
+            )
+            .await?;
+
+        let Some(row) = row else {
+            // the upload may have been finished by a previous request
+            let finished = tx
+                .query_opt("SELECT TRUE FROM uploads WHERE id = $1;", &[&upload])
+                .await?;
+
+            return finished.map(|_| ()).ok_or(error::Error::UnknownUploadId);
+        };
+
+        let upload = finish(ResumableUpload {
+            id: row.get(0),
+            files: row
+                .get::<_, Vec<ResumableFileUpload>>(1)
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+        .await?;
+
+        tx.execute(
+            "DELETE FROM resumable_uploads WHERE id = $1;",
+            &[&upload.id],
+        )
+        .await?;
+
+        tx.execute(
+            "INSERT INTO uploads (id, files) VALUES ($1, $2)",
+            &[
+                &upload.id,
+                &upload
+                    .files
+                    .iter()
+                    .map(FileUpload::from)
+                    .collect::<Vec<_>>(),
+            ],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_resumable_upload(&self, upload: UploadId) -> Result<()> {
+        let conn = self.conn_pool.get().await?;
+
+        let deleted = conn
+            .execute("DELETE FROM resumable_uploads WHERE id = $1;", &[&upload])
+            .await?;
+
+        if deleted == 0 {
+            return Err(error::Error::UnknownUploadId);
+        }
+
+        Ok(())
+    }
+
+    async fn used_upload_bytes(&self) -> Result<u64> {
+        let conn = self.conn_pool.get().await?;
+
+        let row = conn
+            .query_one(
+                "
+                SELECT ((
+                    SELECT COALESCE(SUM(f.byte_size), 0)
+                    FROM uploads, unnest(files) AS f
+                ) + (
+                    SELECT COALESCE(SUM(f.byte_size), 0)
+                    FROM resumable_uploads, unnest(files) AS f
+                ))::bigint;",
+                &[],
+            )
+            .await?;
+
+        Ok(row.get::<_, i64>(0) as u64)
+    }
+}
+
+#[derive(Debug, Clone, ToSql, FromSql)]
+pub struct ResumableFileUpload {
+    pub name: String,
+    pub byte_size: i64,
+    pub sha256: Option<String>,
+}
+
+impl From<&crate::datasets::upload::ResumableFileUpload> for ResumableFileUpload {
+    fn from(upload: &crate::datasets::upload::ResumableFileUpload) -> Self {
+        Self {
+            name: upload.name.clone(),
+            byte_size: upload.byte_size as i64,
+            sha256: upload.sha256.clone(),
+        }
+    }
+}
+
+impl From<ResumableFileUpload> for crate::datasets::upload::ResumableFileUpload {
+    fn from(upload: ResumableFileUpload) -> Self {
+        Self {
+            name: upload.name,
+            byte_size: upload.byte_size as u64,
+            sha256: upload.sha256,
+        }
+    }
 }
 
 #[derive(Debug, Clone, ToSql, FromSql)]
@@ -9,6 +9,7 @@ use crate::error;
 use crate::error::Result;
 use crate::projects::Symbology;
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use geoengine_datatypes::dataset::DatasetId;
 use geoengine_datatypes::primitives::VectorQueryRectangle;
 use geoengine_operators::engine::{MetaData, TypedResultDescriptor};
@@ -299,5 +300,19 @@ pub trait DatasetStore {
         provenance: &[Provenance],
     ) -> Result<()>;
 
+    /// Replace the loading information of a dataset, e.g., after appending data.
+    /// The stored result descriptor is updated as well.
+    ///
+    /// The `update` maps the current loading information to the new one.
+    /// The dataset is locked while `update` runs, so concurrent updates are applied one after another.
+    /// If `update` fails, the loading information stays unchanged.
+    async fn update_dataset_loading_info<'a, F>(
+        &'a self,
+        dataset: DatasetId,
+        update: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(MetaDataDefinition) -> BoxFuture<'a, Result<MetaDataDefinition>> + Send + 'a;
+
     async fn delete_dataset(&self, dataset: DatasetId) -> Result<()>;
 }
@@ -9,6 +9,7 @@ use crate::{
     util::config::{self, get_config_element},
 };
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::ToSchema;
 
@@ -119,9 +120,49 @@ pub struct UploadListing {
     pub num_files: usize,
 }
 
+/// An upload whose files are transferred in chunks and that is not finished yet
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResumableUpload {
+    pub id: UploadId,
+    pub files: Vec<ResumableFileUpload>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumableFileUpload {
+    pub name: String,
+    pub byte_size: u64,
+    /// hex encoded SHA-256 checksum that is verified when the upload is finished
+    pub sha256: Option<String>,
+}
+
 #[async_trait]
 pub trait UploadDb {
     async fn load_upload(&self, upload: UploadId) -> Result<Upload>;
 
     async fn create_upload(&self, upload: Upload) -> Result<()>;
+
+    async fn create_resumable_upload(&self, upload: ResumableUpload) -> Result<()>;
+
+    async fn load_resumable_upload(&self, upload: UploadId) -> Result<ResumableUpload>;
+
+    /// Runs `write` on the resumable upload while it is locked, e.g., to append to one of its files.
+    /// Concurrent calls for the same upload wait for each other, also across instances that share the database.
+    async fn lock_resumable_upload<'a, T, F>(&'a self, upload: UploadId, write: F) -> Result<T>
+    where
+        T: Send + 'a,
+        F: FnOnce(ResumableUpload) -> BoxFuture<'a, Result<T>> + Send + 'a;
+
+    /// Replaces the resumable upload with the regular upload that `finish` creates from it.
+    /// The resumable upload is locked like in [`UploadDb::lock_resumable_upload`] while `finish` runs.
+    ///
+    /// Finishing an upload that was already finished succeeds without calling `finish`.
+    async fn finish_resumable_upload<'a, F>(&'a self, upload: UploadId, finish: F) -> Result<()>
+    where
+        F: FnOnce(ResumableUpload) -> BoxFuture<'a, Result<Upload>> + Send + 'a;
+
+    async fn delete_resumable_upload(&self, upload: UploadId) -> Result<()>;
+
+    /// The size of all files of the user's uploads, including unfinished resumable uploads
+    async fn used_upload_bytes(&self) -> Result<u64>;
 }
@@ -217,7 +217,64 @@ pub enum Error {
         // source: actix_multipart::MultipartError,
         reason: String,
     },
+    #[snafu(display("Payload: {}", reason))]
+    Payload {
+        reason: String,
+    },
     InvalidUploadFileName,
+    #[snafu(display("The upload exceeds the quota of {} bytes", quota))]
+    UploadQuotaExceeded {
+        quota: u64,
+    },
+    MissingUploadOffset,
+    #[snafu(display(
+        "Offset {} does not match the {} bytes received for file '{}'",
+        offset,
+        received,
+        file_name
+    ))]
+    UploadOffsetMismatch {
+        file_name: String,
+        offset: u64,
+        received: u64,
+    },
+    #[snafu(display(
+        "Chunk exceeds the size of {} bytes of file '{}'",
+        byte_size,
+        file_name
+    ))]
+    UploadChunkExceedsFileSize {
+        file_name: String,
+        byte_size: u64,
+    },
+    #[snafu(display("File '{}' is not part of the upload", file_name))]
+    UnknownUploadFile {
+        file_name: String,
+    },
+    #[snafu(display(
+        "Only {} of {} bytes of file '{}' were received",
+        received,
+        byte_size,
+        file_name
+    ))]
+    UploadFileIncomplete {
+        file_name: String,
+        received: u64,
+        byte_size: u64,
+    },
+    #[snafu(display("The SHA-256 checksum of file '{}' does not match", file_name))]
+    UploadChecksumMismatch {
+        file_name: String,
+    },
+    #[snafu(display("File '{}' occurs more than once in the upload", file_name))]
+    DuplicateUploadFileName {
+        file_name: String,
+    },
+    #[snafu(display("Cannot extract zip archive '{}': {}", file_name, source))]
+    UploadZipExtraction {
+        file_name: String,
+        source: zip::result::ZipError,
+    },
     InvalidDatasetIdNamespace,
     DuplicateDatasetId,
     #[snafu(display("Dataset name '{}' already exists", dataset_name))]
@@ -234,6 +291,16 @@ pub enum Error {
         layer_name: String,
     },
     DatasetHasNoAutoImportableLayer,
+    #[snafu(display("Cannot append to dataset: {}", reason))]
+    InvalidDatasetAppend {
+        reason: String,
+    },
+    #[snafu(display("Only admins can append files from a volume to a dataset"))]
+    OnlyAdminsCanAppendToDatasetFromVolume,
+    #[snafu(display("Unknown volume name: {}", volume_name))]
+    UnknownVolumeName {
+        volume_name: String,
+    },
     #[snafu(display("GdalError: {}", source))]
     Gdal {
         source: gdal::errors::GdalError,
@@ -258,6 +325,13 @@ pub enum Error {
         response: String,
         error: serde_json::Error,
     },
+    #[snafu(display(
+        "The STAC location `{}` is not allowed. Remote catalogs may only reference http(s) or s3 locations.",
+        href
+    ))]
+    StacForbiddenHref {
+        href: String,
+    },
     RasterDataTypeNotSupportByGdal,
 
     MissingSpatialReference,
@@ -338,6 +412,59 @@ pub enum Error {
         endpoint: WorkflowId,
         type_names: WorkflowId,
     },
+    #[snafu(display(
+        "WMS GetFeatureInfo pixel ({}, {}) must be inside the map of size {}x{}",
+        i,
+        j,
+        width,
+        height
+    ))]
+    WmsFeatureInfoPixelOutOfBounds {
+        i: u32,
+        j: u32,
+        width: u32,
+        height: u32,
+    },
+    #[snafu(display(
+        "WMS GetFeatureInfo is not supported for workflows of type {}",
+        workflow_type
+    ))]
+    WmsFeatureInfoUnsupportedWorkflowType {
+        workflow_type: String,
+    },
+    #[snafu(display(
+        "WMS background color must be of the form 0xRRGGBB, but is {}",
+        bgcolor
+    ))]
+    WmsInvalidBackgroundColor {
+        bgcolor: String,
+    },
+    #[snafu(display("WMS image quality must be between 1 and 100, but is {}", quality))]
+    WmsInvalidImageQuality {
+        quality: u8,
+    },
+    #[snafu(display("WFS filter is invalid: {}", reason))]
+    WfsInvalidFilter {
+        reason: String,
+    },
+    #[snafu(display(
+        "WFS property {} does not exist in the requested feature type",
+        property
+    ))]
+    WfsUnknownProperty {
+        property: String,
+    },
+    #[snafu(display(
+        "OGC API Features endpoint {} must match collection {}",
+        endpoint,
+        collection
+    ))]
+    FeaturesEndpointCollectionMissmatch {
+        endpoint: WorkflowId,
+        collection: String,
+    },
+    #[snafu(display("Vector tiles can only be created for workflows with geometries"))]
+    VectorTilesRequireGeometries,
 
     #[snafu(context(false))]
     ArunaProvider {
@@ -501,7 +628,10 @@ impl actix_web::error::ResponseError for Error {
     fn status_code(&self) -> StatusCode {
         match self {
             Error::Unauthorized { source: _ } => StatusCode::UNAUTHORIZED,
-            Error::Duplicate { reason: _ } => StatusCode::CONFLICT,
+            Error::Duplicate { reason: _ } | Error::UploadOffsetMismatch { .. } => {
+                StatusCode::CONFLICT
+            }
+            Error::UploadQuotaExceeded { quota: _ } => StatusCode::PAYLOAD_TOO_LARGE,
             _ => StatusCode::BAD_REQUEST,
         }
     }
@@ -568,6 +698,14 @@ impl From<actix_multipart::MultipartError> for Error {
     }
 }
 
+impl From<actix_web::error::PayloadError> for Error {
+    fn from(source: actix_web::error::PayloadError) -> Self {
+        Self::Payload {
+            reason: source.to_string(),
+        }
+    }
+}
+
 impl From<url::ParseError> for Error {
     fn from(source: url::ParseError) -> Self {
         Self::Url { source }
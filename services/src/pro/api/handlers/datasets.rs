@@ -1,32 +1,35 @@
 use crate::{
     api::{
         handlers::datasets::{
-            adjust_meta_data_path, auto_create_dataset_handler, create_upload_dataset,
-            delete_dataset_handler, get_dataset_handler, get_loading_info_handler,
-            list_datasets_handler, list_volumes_handler, suggest_meta_data_handler,
-            update_dataset_handler, update_dataset_provenance_handler,
+            adjust_meta_data_path, append_to_dataset_handler, auto_create_dataset_handler,
+            create_upload_dataset, delete_dataset_handler, get_dataset_handler,
+            get_loading_info_handler, list_datasets_handler, list_volumes_handler,
+            suggest_meta_data_handler, update_dataset_handler, update_dataset_provenance_handler,
             update_dataset_symbology_handler,
         },
         model::{
             responses::datasets::{errors::*, DatasetNameResponse},
-            services::{CreateDataset, DataPath, DatasetDefinition},
+            services::{
+                AppendDataset, AppendGdalMetaDataList, CreateDataset, DataPath, DatasetDefinition,
+            },
         },
     },
     contexts::{ApplicationContext, SessionContext},
     datasets::{
         storage::DatasetStore,
         upload::{Volume, VolumeName},
+        DatasetName,
     },
-    error::Result,
+    error::{self, Result},
     pro::{
         contexts::{ProApplicationContext, ProGeoEngineDb},
         permissions::{Permission, PermissionDb, Role},
     },
     util::config::{get_config_element, Data},
 };
-use actix_web::{web, FromRequest};
+use actix_web::{web, FromRequest, HttpResponseBuilder};
 use geoengine_datatypes::error::BoxedResultExt;
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
 
 pub(crate) fn init_dataset_routes<C>(cfg: &mut web::ServiceConfig)
 where
@@ -51,6 +54,10 @@ where
                 web::resource("/{dataset}/provenance")
                     .route(web::put().to(update_dataset_provenance_handler::<C>)),
             )
+            .service(
+                web::resource("/{dataset}/append")
+                    .route(web::post().to(append_to_pro_dataset_handler::<C>)),
+            )
             .service(
                 web::resource("/{dataset}")
                     .route(web::get().to(get_dataset_handler::<C>))
@@ -145,6 +152,33 @@ where
     Ok(web::Json(dataset.name.into()))
 }
 
+/// Appends data to an existing dataset like [`append_to_dataset_handler`].
+/// Appending files from a volume requires an admin session, like creating a dataset from a volume.
+async fn append_to_pro_dataset_handler<C: ProApplicationContext>(
+    session: C::Session,
+    app_ctx: web::Data<C>,
+    dataset: web::Path<DatasetName>,
+    append: web::Json<AppendDataset>,
+) -> Result<HttpResponseBuilder>
+where
+    <<C as ApplicationContext>::SessionContext as SessionContext>::GeoEngineDB: ProGeoEngineDb,
+{
+    let from_volume = matches!(
+        &*append,
+        AppendDataset::GdalMetaDataList(AppendGdalMetaDataList {
+            data_path: DataPath::Volume(_),
+            ..
+        })
+    );
+
+    ensure!(
+        !from_volume || session.is_admin(),
+        error::OnlyAdminsCanAppendToDatasetFromVolume
+    );
+
+    append_to_dataset_handler(session, app_ctx, dataset, append).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -542,4 +576,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[ge_context::test]
+    async fn it_refuses_appending_from_a_volume_without_admin_session(
+        app_ctx: ProPostgresContext<NoTls>,
+    ) -> Result<()> {
+        let mut test_data = TestDataUploads::default(); // remember created folder and remove them on drop
+
+        let session = app_ctx.create_anonymous_session().await.unwrap();
+        let session_id = session.id();
+
+        let upload_id = upload_ne_10m_ports_files(app_ctx.clone(), session_id).await?;
+        test_data.uploads.push(upload_id);
+
+        let dataset_name =
+            construct_dataset_from_upload(app_ctx.clone(), upload_id, session_id).await;
+
+        let req = actix_web::test::TestRequest::post()
+            .uri(&format!("/dataset/{dataset_name}/append"))
+            .append_header((header::AUTHORIZATION, Bearer::new(session_id.to_string())))
+            .set_json(json!({
+                "type": "GdalMetaDataList",
+                "dataPath": {
+                    "volume": "test_data"
+                },
+                "params": []
+            }));
+
+        let res = send_pro_test_request(req, app_ctx.clone()).await;
+
+        crate::api::model::responses::ErrorResponse::assert(
+            res,
+            400,
+            "OnlyAdminsCanAppendToDatasetFromVolume",
+            "Only admins can append files from a volume to a dataset",
+        )
+        .await;
+
+        Ok(())
+    }
 }
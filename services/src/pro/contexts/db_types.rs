@@ -2,7 +2,7 @@ use crate::{
     error::Error,
     pro::datasets::{
         GdalRetries, SentinelS2L2ACogsProviderDefinition, StacApiRetries,
-        TypedProDataProviderDefinition,
+        StacDataProviderDefinition, TypedProDataProviderDefinition,
     },
 };
 use geoengine_datatypes::delegate_from_to_sql;
@@ -66,6 +66,7 @@ impl TryFrom<GdalRetriesDbType> for GdalRetries {
 #[postgres(name = "ProDataProviderDefinition")]
 pub struct TypedProDataProviderDefinitionDbType {
     sentinel_s2_l2_a_cogs_provider_definition: Option<SentinelS2L2ACogsProviderDefinition>,
+    stac_data_provider_definition: Option<StacDataProviderDefinition>,
 }
 
 impl From<&TypedProDataProviderDefinition> for TypedProDataProviderDefinitionDbType {
@@ -75,6 +76,13 @@ impl From<&TypedProDataProviderDefinition> for TypedProDataProviderDefinitionDbT
                 data_provider_definition,
             ) => Self {
                 sentinel_s2_l2_a_cogs_provider_definition: Some(data_provider_definition.clone()),
+                stac_data_provider_definition: None,
+            },
+            TypedProDataProviderDefinition::StacDataProviderDefinition(
+                data_provider_definition,
+            ) => Self {
+                sentinel_s2_l2_a_cogs_provider_definition: None,
+                stac_data_provider_definition: Some(data_provider_definition.clone()),
             },
         }
     }
@@ -90,11 +98,18 @@ impl TryFrom<TypedProDataProviderDefinitionDbType> for TypedProDataProviderDefin
         match result_descriptor {
             TypedProDataProviderDefinitionDbType {
                 sentinel_s2_l2_a_cogs_provider_definition: Some(data_provider_definition),
+                stac_data_provider_definition: None,
             } => Ok(
                 TypedProDataProviderDefinition::SentinelS2L2ACogsProviderDefinition(
                     data_provider_definition,
                 ),
             ),
+            TypedProDataProviderDefinitionDbType {
+                sentinel_s2_l2_a_cogs_provider_definition: None,
+                stac_data_provider_definition: Some(data_provider_definition),
+            } => Ok(TypedProDataProviderDefinition::StacDataProviderDefinition(
+                data_provider_definition,
+            )),
 
             _ => Err(Error::UnexpectedInvalidDbTypeConversion),
         }
@@ -117,7 +132,7 @@ mod tests {
     use super::*;
     use crate::{
         pro::{
-            datasets::{StacBand, StacZone},
+            datasets::{StacBand, StacCatalogType, StacZone},
             util::tests::with_pro_temp_context,
         },
         util::postgres::assert_sql_type,
@@ -201,6 +216,37 @@ mod tests {
             )
             .await;
 
+            assert_sql_type(
+                &pool,
+                "StacCatalogType",
+                [StacCatalogType::Api, StacCatalogType::Static],
+            )
+            .await;
+
+            assert_sql_type(
+                &pool,
+                "StacDataProviderDefinition",
+                [StacDataProviderDefinition {
+                    name: "foo".to_owned(),
+                    id: DataProviderId::new(),
+                    description: "A provider".to_owned(),
+                    priority: Some(1),
+                    url: "https://stac.example.com".to_owned(),
+                    catalog_type: StacCatalogType::Api,
+                    collections: vec!["sentinel-2-l2a".to_owned()],
+                    stac_api_retries: StacApiRetries {
+                        number_of_retries: 3,
+                        initial_delay_ms: 4,
+                        exponential_backoff_factor: 5.,
+                    },
+                    gdal_retries: GdalRetries {
+                        number_of_retries: 3,
+                    },
+                    cache_ttl: CacheTtlSeconds::new(60),
+                }],
+            )
+            .await;
+
             assert_sql_type(
                 &pool,
                 "ProDataProviderDefinition",
@@ -232,6 +278,20 @@ mod tests {
                             cache_ttl: CacheTtlSeconds::new(60),
                         },
                     ),
+                    TypedProDataProviderDefinition::StacDataProviderDefinition(
+                        StacDataProviderDefinition {
+                            name: "foo".to_owned(),
+                            id: DataProviderId::new(),
+                            description: "A provider".to_owned(),
+                            priority: Some(2),
+                            url: "https://example.com/catalog.json".to_owned(),
+                            catalog_type: StacCatalogType::Static,
+                            collections: vec![],
+                            stac_api_retries: StacApiRetries::default(),
+                            gdal_retries: GdalRetries::default(),
+                            cache_ttl: CacheTtlSeconds::default(),
+                        },
+                    ),
                 ],
             )
             .await;
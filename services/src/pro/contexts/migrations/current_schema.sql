@@ -34,10 +34,26 @@ CREATE TYPE "SentinelS2L2ACogsProviderDefinition" AS (
     priority smallint
 );
 
+CREATE TYPE "StacCatalogType" AS ENUM ('Api', 'Static');
+
+CREATE TYPE "StacDataProviderDefinition" AS (
+    "name" text,
+    id uuid,
+    description text,
+    priority smallint,
+    url text,
+    catalog_type "StacCatalogType",
+    collections text [],
+    stac_api_retries "StacApiRetries",
+    gdal_retries "GdalRetries",
+    cache_ttl int
+);
+
 CREATE TYPE "ProDataProviderDefinition" AS (
     -- one of
     sentinel_s2_l2_a_cogs_provider_definition
-    "SentinelS2L2ACogsProviderDefinition"
+    "SentinelS2L2ACogsProviderDefinition",
+    stac_data_provider_definition "StacDataProviderDefinition"
 );
 
 CREATE TABLE pro_layer_providers (
@@ -114,6 +130,14 @@ CREATE TABLE user_uploads (
     PRIMARY KEY (user_id, upload_id)
 );
 
+CREATE TABLE user_resumable_uploads (
+    user_id uuid REFERENCES users (id) ON DELETE CASCADE NOT NULL,
+    upload_id uuid REFERENCES resumable_uploads (
+        id
+    ) ON DELETE CASCADE NOT NULL,
+    PRIMARY KEY (user_id, upload_id)
+);
+
 CREATE TYPE "Permission" AS ENUM ('Read', 'Owner');
 
 -- TODO: uploads, providers permissions
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use tokio_postgres::Transaction;
+
+use super::database_migration::{ProMigration, ProMigrationImpl};
+use crate::{contexts::Migration0009StacProvider, error::Result};
+
+#[async_trait]
+impl ProMigration for ProMigrationImpl<Migration0009StacProvider> {
+    async fn pro_migrate(&self, tx: &Transaction<'_>) -> Result<()> {
+        tx.batch_execute(
+            r#"
+            CREATE TYPE "StacCatalogType" AS ENUM ('Api', 'Static');
+
+            CREATE TYPE "StacDataProviderDefinition" AS (
+                "name" text,
+                id uuid,
+                description text,
+                priority smallint,
+                url text,
+                catalog_type "StacCatalogType",
+                collections text [],
+                stac_api_retries "StacApiRetries",
+                gdal_retries "GdalRetries",
+                cache_ttl int
+            );
+
+            ALTER TYPE "ProDataProviderDefinition"
+            ADD ATTRIBUTE stac_data_provider_definition "StacDataProviderDefinition";
+        "#,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
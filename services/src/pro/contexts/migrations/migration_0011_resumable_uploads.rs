@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use tokio_postgres::Transaction;
+
+use super::database_migration::{ProMigration, ProMigrationImpl};
+use crate::{contexts::Migration0011ResumableUploads, error::Result};
+
+#[async_trait]
+impl ProMigration for ProMigrationImpl<Migration0011ResumableUploads> {
+    async fn pro_migrate(&self, tx: &Transaction<'_>) -> Result<()> {
+        tx.batch_execute(
+            "
+            CREATE TABLE user_resumable_uploads (
+                user_id uuid REFERENCES users (id) ON DELETE CASCADE NOT NULL,
+                upload_id uuid REFERENCES resumable_uploads (
+                    id
+                ) ON DELETE CASCADE NOT NULL,
+                PRIMARY KEY (user_id, upload_id)
+            );
+        ",
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -7,7 +7,10 @@ use crate::contexts::{
 };
 use crate::contexts::{GeoEngineDb, SessionContext};
 use crate::datasets::upload::{Volume, Volumes};
-use crate::datasets::DatasetName;
+use crate::datasets::{
+    raster_dataset_from_workflow_task_loader, vector_dataset_from_workflow_task_loader,
+    DatasetName, RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE, VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE,
+};
 use crate::error::{self, Error, Result};
 use crate::pro::api::cli::add_datasets_from_directory;
 use crate::pro::layers::add_from_directory::{
@@ -16,10 +19,10 @@ use crate::pro::layers::add_from_directory::{
 };
 use crate::pro::machine_learning::ml_model::{MlModel, MlModelDb};
 use crate::pro::quota::{initialize_quota_tracking, QuotaTrackingFactory};
-use crate::pro::tasks::{ProTaskManager, ProTaskManagerBackend};
+use crate::pro::tasks::ProTaskManager;
 use crate::pro::users::{OidcRequestDb, UserAuth, UserSession};
 use crate::pro::util::config::{Cache, Oidc, Quota};
-use crate::tasks::SimpleTaskManagerContext;
+use crate::tasks::{PostgresTaskManagerBackend, SimpleTaskManagerContext};
 use async_trait::async_trait;
 use bb8_postgres::{
     bb8::Pool,
@@ -58,7 +61,7 @@ where
     thread_pool: Arc<ThreadPool>,
     exe_ctx_tiling_spec: TilingSpecification,
     query_ctx_chunk_size: ChunkByteSize,
-    task_manager: Arc<ProTaskManagerBackend>,
+    task_manager: Arc<PostgresTaskManagerBackend<Tls>>,
     oidc_request_db: Arc<Option<OidcRequestDb>>,
     quota: QuotaTrackingFactory,
     pub(crate) pool: Pool<PostgresConnectionManager<Tls>>,
@@ -95,8 +98,8 @@ where
             quota_config.increment_quota_buffer_timeout_seconds,
         );
 
-        Ok(ProPostgresContext {
-            task_manager: Default::default(),
+        let app_ctx = ProPostgresContext {
+            task_manager: Arc::new(PostgresTaskManagerBackend::new_with_config(pool.clone())?),
             thread_pool: create_rayon_thread_pool(0),
             exe_ctx_tiling_spec,
             query_ctx_chunk_size,
@@ -105,7 +108,11 @@ where
             pool,
             volumes: Default::default(),
             tile_cache: Arc::new(SharedCache::test_default()),
-        })
+        };
+
+        app_ctx.register_task_loaders();
+
+        Ok(app_ctx)
     }
 
     #[allow(clippy::missing_panics_doc)]
@@ -130,8 +137,8 @@ where
             quota_config.increment_quota_buffer_timeout_seconds,
         );
 
-        Ok(ProPostgresContext {
-            task_manager: Default::default(),
+        let app_ctx = ProPostgresContext {
+            task_manager: Arc::new(PostgresTaskManagerBackend::new_with_config(pool.clone())?),
             thread_pool: create_rayon_thread_pool(0),
             exe_ctx_tiling_spec: TestDefault::test_default(),
             query_ctx_chunk_size: TestDefault::test_default(),
@@ -143,7 +150,11 @@ where
                 SharedCache::new(cache_config.size_in_mb, cache_config.landing_zone_ratio)
                     .expect("tile cache creation should work because the config is valid"),
             ),
-        })
+        };
+
+        app_ctx.register_task_loaders();
+
+        Ok(app_ctx)
     }
 
     // TODO: check if the datasets exist already and don't output warnings when skipping them
@@ -176,7 +187,7 @@ where
         );
 
         let app_ctx = ProPostgresContext {
-            task_manager: Default::default(),
+            task_manager: Arc::new(PostgresTaskManagerBackend::new_with_config(pool.clone())?),
             thread_pool: create_rayon_thread_pool(0),
             exe_ctx_tiling_spec,
             query_ctx_chunk_size,
@@ -190,6 +201,8 @@ where
             ),
         };
 
+        app_ctx.register_task_loaders();
+
         if created_schema {
             info!("Populating database with initial data...");
 
@@ -208,6 +221,18 @@ where
         Ok(app_ctx)
     }
 
+    /// Register the loaders of the tasks that instances resume from the shared task queue
+    fn register_task_loaders(&self) {
+        self.task_manager.register_task_loader(
+            RASTER_DATASET_FROM_WORKFLOW_TASK_TYPE,
+            raster_dataset_from_workflow_task_loader(self.clone()),
+        );
+        self.task_manager.register_task_loader(
+            VECTOR_DATASET_FROM_WORKFLOW_TASK_TYPE,
+            vector_dataset_from_workflow_task_loader(self.clone()),
+        );
+    }
+
     #[allow(clippy::too_many_lines)]
     /// Creates the database schema. Returns true if the schema was created, false if it already existed.
     pub(crate) async fn create_pro_database(
@@ -293,7 +318,7 @@ where
     type GeoEngineDB = ProPostgresDb<Tls>;
 
     type TaskContext = SimpleTaskManagerContext;
-    type TaskManager = ProTaskManager; // this does not persist across restarts
+    type TaskManager = ProTaskManager<Tls>;
     type QueryContext = QueryContextImpl;
     type ExecutionContext = ExecutionContextImpl<Self::GeoEngineDB>;
 
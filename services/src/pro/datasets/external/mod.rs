@@ -1,5 +1,6 @@
 mod netcdfcf;
 mod sentinel_s2_l2a_cogs;
+mod stac;
 
 use crate::contexts::GeoEngineDb;
 use crate::error::Result;
@@ -10,12 +11,14 @@ pub use sentinel_s2_l2a_cogs::{
     GdalRetries, SentinelS2L2ACogsProviderDefinition, StacApiRetries, StacBand, StacZone,
 };
 use serde::{Deserialize, Serialize};
+pub use stac::{StacCatalogType, StacDataProviderDefinition};
 
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")] // TODO: rename_all = "camelCase"
 #[allow(clippy::enum_variant_names)] // TODO: think about better names
 pub enum TypedProDataProviderDefinition {
     SentinelS2L2ACogsProviderDefinition(SentinelS2L2ACogsProviderDefinition),
+    StacDataProviderDefinition(StacDataProviderDefinition),
 }
 
 impl From<SentinelS2L2ACogsProviderDefinition> for TypedProDataProviderDefinition {
@@ -24,12 +27,19 @@ impl From<SentinelS2L2ACogsProviderDefinition> for TypedProDataProviderDefinitio
     }
 }
 
+impl From<StacDataProviderDefinition> for TypedProDataProviderDefinition {
+    fn from(def: StacDataProviderDefinition) -> Self {
+        Self::StacDataProviderDefinition(def)
+    }
+}
+
 impl<D: GeoEngineDb> From<TypedProDataProviderDefinition> for Box<dyn DataProviderDefinition<D>> {
     fn from(typed: TypedProDataProviderDefinition) -> Self {
         match typed {
             TypedProDataProviderDefinition::SentinelS2L2ACogsProviderDefinition(def) => {
                 Box::new(def)
             }
+            TypedProDataProviderDefinition::StacDataProviderDefinition(def) => Box::new(def),
         }
     }
 }
@@ -38,6 +48,7 @@ impl<D: GeoEngineDb> AsRef<dyn DataProviderDefinition<D>> for TypedProDataProvid
     fn as_ref(&self) -> &(dyn DataProviderDefinition<D> + 'static) {
         match self {
             Self::SentinelS2L2ACogsProviderDefinition(def) => def,
+            Self::StacDataProviderDefinition(def) => def,
         }
     }
 }
@@ -47,12 +58,14 @@ impl<D: GeoEngineDb> DataProviderDefinition<D> for TypedProDataProviderDefinitio
     async fn initialize(self: Box<Self>, db: D) -> Result<Box<dyn DataProvider>> {
         match *self {
             Self::SentinelS2L2ACogsProviderDefinition(def) => Box::new(def).initialize(db).await,
+            Self::StacDataProviderDefinition(def) => Box::new(def).initialize(db).await,
         }
     }
 
     fn type_name(&self) -> &'static str {
         match self {
             Self::SentinelS2L2ACogsProviderDefinition(_) => "SentinelS2L2ACogsProviderDefinition",
+            Self::StacDataProviderDefinition(_) => "StacDataProviderDefinition",
         }
     }
 
@@ -61,12 +74,14 @@ impl<D: GeoEngineDb> DataProviderDefinition<D> for TypedProDataProviderDefinitio
             Self::SentinelS2L2ACogsProviderDefinition(def) => {
                 DataProviderDefinition::<D>::name(def)
             }
+            Self::StacDataProviderDefinition(def) => DataProviderDefinition::<D>::name(def),
         }
     }
 
     fn id(&self) -> DataProviderId {
         match self {
             Self::SentinelS2L2ACogsProviderDefinition(def) => DataProviderDefinition::<D>::id(def),
+            Self::StacDataProviderDefinition(def) => DataProviderDefinition::<D>::id(def),
         }
     }
 }
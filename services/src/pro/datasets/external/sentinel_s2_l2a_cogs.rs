@@ -47,7 +47,7 @@ use std::convert::TryInto;
 use std::fmt::Debug;
 use std::path::PathBuf;
 
-static STAC_RETRY_MAX_BACKOFF_MS: u64 = 60 * 60 * 1000;
+pub(super) static STAC_RETRY_MAX_BACKOFF_MS: u64 = 60 * 60 * 1000;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, FromSql, ToSql)]
 #[serde(rename_all = "camelCase")]
@@ -401,17 +401,19 @@ impl SentinelS2L2aCogsMetaData {
         let mut features: Vec<StacFeature> = features
             .into_iter()
             .filter(|f| {
-                f.properties
-                    .proj_epsg
-                    .map_or(false, |epsg| epsg == self.zone.epsg)
+                f.properties.datetime.is_some()
+                    && f.properties
+                        .proj_epsg
+                        .map_or(false, |epsg| epsg == self.zone.epsg)
             })
             .collect();
 
         features.sort_by_key(|a| a.properties.datetime);
 
+        // all features have a `datetime` since the others were filtered out above
         let start_times_pre: Vec<TimeInstance> = features
             .iter()
-            .map(|f| TimeInstance::from(f.properties.datetime))
+            .filter_map(|f| f.properties.datetime.map(TimeInstance::from))
             .collect();
         let start_times = Self::make_unique_start_times_from_sorted_features(&start_times_pre);
 
@@ -462,7 +464,7 @@ impl SentinelS2L2aCogsMetaData {
         })
     }
 
-    fn make_unique_start_times_from_sorted_features(
+    pub(super) fn make_unique_start_times_from_sorted_features(
         start_times: &[TimeInstance],
     ) -> Vec<TimeInstance> {
         let mut unique_start_times: Vec<TimeInstance> = Vec::with_capacity(start_times.len());
@@ -597,8 +599,11 @@ impl SentinelS2L2aCogsMetaData {
         let mut collection = self.load_collection(params, 1).await?;
         features.append(&mut collection.features);
 
-        let num_pages =
-            (collection.context.matched as f64 / collection.context.limit as f64).ceil() as u32;
+        let Some(context) = collection.context else {
+            return Ok(features);
+        };
+
+        let num_pages = (context.matched as f64 / context.limit as f64).ceil() as u32;
 
         for page in 2..=num_pages {
             let mut collection = self.load_collection(params, page).await?;
@@ -644,7 +649,7 @@ impl SentinelS2L2aCogsMetaData {
         .await
     }
 
-    fn time_range_request(time: &TimeInterval) -> Result<(DateTime, DateTime)> {
+    pub(super) fn time_range_request(time: &TimeInterval) -> Result<(DateTime, DateTime)> {
         let t_start =
             time.start()
                 .as_date_time()
@@ -0,0 +1,1545 @@
+use super::sentinel_s2_l2a_cogs::{
+    GdalRetries, SentinelS2L2aCogsMetaData, StacApiRetries, STAC_RETRY_MAX_BACKOFF_MS,
+};
+use crate::contexts::GeoEngineDb;
+use crate::datasets::listing::ProvenanceOutput;
+use crate::error::{self, Error, Result};
+use crate::layers::external::{DataProvider, DataProviderDefinition};
+use crate::layers::layer::{
+    CollectionItem, Layer, LayerCollection, LayerCollectionListOptions, LayerCollectionListing,
+    LayerListing, ProviderLayerCollectionId, ProviderLayerId,
+};
+use crate::layers::listing::{
+    LayerCollectionId, LayerCollectionProvider, ProviderCapabilities, SearchCapabilities,
+};
+use crate::stac::{
+    Catalog, Collections, Feature as StacFeature, FeatureCollection as StacItemCollection,
+    ItemAsset, Link, StacAsset,
+};
+use crate::workflows::workflow::Workflow;
+use async_trait::async_trait;
+use geoengine_datatypes::dataset::{DataId, DataProviderId, LayerId, NamedData};
+use geoengine_datatypes::operations::reproject::{
+    CoordinateProjection, CoordinateProjector, ReprojectClipped,
+};
+use geoengine_datatypes::primitives::{
+    AxisAlignedRectangle, BoundingBox2D, CacheTtlSeconds, DateTime, RasterQueryRectangle,
+    SpatialPartitioned, TimeInstance, TimeInterval, VectorQueryRectangle,
+};
+use geoengine_datatypes::raster::RasterDataType;
+use geoengine_datatypes::spatial_reference::{SpatialReference, SpatialReferenceAuthority};
+use geoengine_operators::engine::{
+    MetaData, MetaDataProvider, RasterBandDescriptors, RasterOperator, RasterResultDescriptor,
+    TypedOperator, VectorResultDescriptor,
+};
+use geoengine_operators::mock::MockDatasetDataSourceLoadingInfo;
+use geoengine_operators::source::{
+    FileNotFoundHandling, GdalDatasetGeoTransform, GdalDatasetParameters, GdalLoadingInfo,
+    GdalLoadingInfoTemporalSlice, GdalLoadingInfoTemporalSliceIterator, GdalRetryOptions,
+    GdalSource, GdalSourceParameters, OgrSourceDataset,
+};
+use geoengine_operators::util::retry::retry;
+use log::debug;
+use postgres_types::{FromSql, ToSql};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use url::Url;
+
+/// The spatial reference of items that do not specify one
+const DEFAULT_EPSG: u32 = 4326;
+
+/// The number of items that are requested per page from a STAC API
+const STAC_API_PAGE_LIMIT: u32 = 500;
+
+/// A provider for the raster assets of the collections of a STAC API or a static STAC catalog.
+///
+/// Each band of each raster asset of a collection is a layer.
+/// If the items of a collection are in different spatial references, there is a layer per spatial reference.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, FromSql, ToSql)]
+#[serde(rename_all = "camelCase")]
+pub struct StacDataProviderDefinition {
+    pub name: String,
+    pub id: DataProviderId,
+    pub description: String,
+    pub priority: Option<i16>,
+    /// The landing page of a STAC API or the root catalog of a static catalog.
+    /// A static catalog can also be a local file.
+    /// Only the documents of a local catalog may reference local files.
+    pub url: String,
+    pub catalog_type: StacCatalogType,
+    /// The ids of the collections to provide. If it is empty, all collections are provided.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    #[serde(default)]
+    pub stac_api_retries: StacApiRetries,
+    #[serde(default)]
+    pub gdal_retries: GdalRetries,
+    #[serde(default)]
+    pub cache_ttl: CacheTtlSeconds,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, FromSql, ToSql)]
+#[serde(rename_all = "camelCase")]
+pub enum StacCatalogType {
+    /// A STAC API with `collections` and `search` endpoints
+    Api,
+    /// JSON files that are linked from a root catalog
+    Static,
+}
+
+#[async_trait]
+impl<D: GeoEngineDb> DataProviderDefinition<D> for StacDataProviderDefinition {
+    async fn initialize(self: Box<Self>, _db: D) -> crate::error::Result<Box<dyn DataProvider>> {
+        Ok(Box::new(StacDataProvider::new(*self)))
+    }
+
+    fn type_name(&self) -> &'static str {
+        "Stac"
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn id(&self) -> DataProviderId {
+        self.id
+    }
+
+    fn priority(&self) -> i16 {
+        self.priority.unwrap_or(0)
+    }
+}
+
+#[derive(Debug)]
+pub struct StacDataProvider {
+    id: DataProviderId,
+
+    name: String,
+    description: String,
+
+    collections: Vec<String>,
+
+    client: StacClient,
+
+    gdal_retries: GdalRetries,
+
+    cache_ttl: CacheTtlSeconds,
+}
+
+impl StacDataProvider {
+    pub fn new(definition: StacDataProviderDefinition) -> Self {
+        Self {
+            id: definition.id,
+            name: definition.name,
+            description: definition.description,
+            collections: definition.collections,
+            client: StacClient {
+                url: definition.url,
+                catalog_type: definition.catalog_type,
+                retries: definition.stac_api_retries,
+                http: Client::new(),
+            },
+            gdal_retries: definition.gdal_retries,
+            cache_ttl: definition.cache_ttl,
+        }
+    }
+
+    fn is_provided(&self, collection: &str) -> bool {
+        self.collections.is_empty() || self.collections.iter().any(|c| c == collection)
+    }
+
+    async fn collection(&self, collection: &str) -> Result<Catalog> {
+        let unknown_collection = || Error::UnknownLayerCollectionId {
+            id: LayerCollectionId(collection.to_string()),
+        };
+
+        if !self.is_provided(collection) {
+            return Err(unknown_collection());
+        }
+
+        self.client
+            .collection(collection)
+            .await?
+            .ok_or_else(unknown_collection)
+    }
+
+    /// The layers of a collection. If the collection neither describes its assets nor its
+    /// spatial references, they are taken from its first item.
+    async fn band_layers(&self, collection: &Catalog) -> Result<Vec<StacBandLayer>> {
+        let mut assets = collection.item_assets.clone();
+        let mut epsg_codes = collection.summary_epsg_codes();
+
+        if assets.is_empty() || epsg_codes.is_empty() {
+            if let Some(item) = self.client.first_item(collection).await? {
+                if assets.is_empty() {
+                    assets = item
+                        .assets
+                        .iter()
+                        .map(|(key, asset)| (key.clone(), ItemAsset::from(asset)))
+                        .collect();
+                }
+
+                if epsg_codes.is_empty() {
+                    epsg_codes = item
+                        .assets
+                        .values()
+                        .filter_map(|asset| asset.proj_epsg)
+                        .chain(item.properties.proj_epsg)
+                        .collect();
+                }
+            }
+        }
+
+        epsg_codes.sort_unstable();
+        epsg_codes.dedup();
+
+        if epsg_codes.is_empty() {
+            epsg_codes.push(DEFAULT_EPSG);
+        }
+
+        Ok(band_layers(collection, &assets, &epsg_codes))
+    }
+
+    async fn band_layer(
+        &self,
+        collection: &str,
+        asset: &str,
+        band: usize,
+        epsg: u32,
+    ) -> Result<(Catalog, StacBandLayer)> {
+        let collection = self.collection(collection).await?;
+
+        let layer = self
+            .band_layers(&collection)
+            .await?
+            .into_iter()
+            .find(|layer| layer.asset == asset && layer.band == band && layer.epsg == epsg)
+            .ok_or_else(|| Error::StacNoSuchBand {
+                band_name: format!("{asset}:{band}"),
+            })?;
+
+        Ok((collection, layer))
+    }
+}
+
+/// The layers for all bands of the raster assets in all spatial references
+fn band_layers(
+    collection: &Catalog,
+    assets: &HashMap<String, ItemAsset>,
+    epsg_codes: &[u32],
+) -> Vec<StacBandLayer> {
+    let collection_title = collection.title.as_ref().unwrap_or(&collection.id);
+
+    let mut raster_assets: Vec<_> = assets
+        .iter()
+        .filter(|(_, asset)| asset.is_raster_data())
+        .collect();
+    raster_assets.sort_by_key(|(key, _)| *key);
+
+    let mut layers = Vec::new();
+
+    for (key, asset) in raster_assets {
+        let raster_bands = asset.raster_bands.as_deref().unwrap_or_default();
+        let eo_bands = asset.eo_bands.as_deref().unwrap_or_default();
+        let num_bands = raster_bands.len().max(eo_bands.len()).max(1);
+
+        for band in 0..num_bands {
+            let mut name = format!("{collection_title} {}", asset.title.as_ref().unwrap_or(key));
+
+            if num_bands > 1 {
+                let band_name = eo_bands
+                    .get(band)
+                    .and_then(|eo_band| eo_band.common_name.as_ref().or(eo_band.name.as_ref()))
+                    .map_or_else(|| (band + 1).to_string(), Clone::clone);
+                name = format!("{name} {band_name}");
+            }
+
+            let raster_band = raster_bands.get(band);
+
+            for &epsg in epsg_codes {
+                layers.push(StacBandLayer {
+                    collection: collection.id.clone(),
+                    asset: key.clone(),
+                    band,
+                    epsg,
+                    name: if epsg_codes.len() > 1 {
+                        format!("{name} (EPSG:{epsg})")
+                    } else {
+                        name.clone()
+                    },
+                    data_type: raster_band
+                        .and_then(crate::stac::RasterBand::raster_data_type)
+                        .unwrap_or(RasterDataType::F32),
+                    no_data_value: raster_band.and_then(|raster_band| raster_band.nodata),
+                });
+            }
+        }
+    }
+
+    layers
+}
+
+/// A band of a raster asset in a spatial reference
+#[derive(Debug, Clone, PartialEq)]
+struct StacBandLayer {
+    collection: String,
+    asset: String,
+    /// The zero-based index of the band in the asset
+    band: usize,
+    epsg: u32,
+    name: String,
+    data_type: RasterDataType,
+    no_data_value: Option<f64>,
+}
+
+impl StacBandLayer {
+    fn id(&self) -> StacLayerId {
+        StacLayerId::Band {
+            collection: self.collection.clone(),
+            asset: self.asset.clone(),
+            band: self.band,
+            epsg: self.epsg,
+        }
+    }
+}
+
+/// Loads catalogs, collections and items from a STAC API or a static catalog
+#[derive(Debug, Clone)]
+struct StacClient {
+    url: String,
+    catalog_type: StacCatalogType,
+    retries: StacApiRetries,
+    http: Client,
+}
+
+impl StacClient {
+    async fn collections(&self) -> Result<Vec<Catalog>> {
+        let mut collections = Vec::new();
+
+        match self.catalog_type {
+            StacCatalogType::Api => {
+                let mut next = Some(self.endpoint("collections"));
+                while let Some(href) = next {
+                    let mut page: Collections = self.load_json(&href, &[]).await?;
+                    next = next_link(&page.links);
+                    collections.append(&mut page.collections);
+                }
+            }
+            StacCatalogType::Static => {
+                let mut hrefs = vec![self.url.clone()];
+                while let Some(href) = hrefs.pop() {
+                    let catalog = self.load_catalog(&href).await?;
+                    hrefs.extend(
+                        catalog
+                            .links_with_rel("child")
+                            .map(|link| link.href.clone()),
+                    );
+
+                    if catalog.is_collection() {
+                        collections.push(catalog);
+                    }
+                }
+            }
+        }
+
+        Ok(collections)
+    }
+
+    async fn collection(&self, collection: &str) -> Result<Option<Catalog>> {
+        match self.catalog_type {
+            StacCatalogType::Api => self
+                .load_catalog(&self.endpoint(&format!("collections/{collection}")))
+                .await
+                .map(Some),
+            StacCatalogType::Static => Ok(self
+                .collections()
+                .await?
+                .into_iter()
+                .find(|c| c.id == collection)),
+        }
+    }
+
+    /// The items of the `collection` that intersect the `bbox` in WGS 84 and the time range
+    async fn items(
+        &self,
+        collection: &Catalog,
+        bbox: BoundingBox2D,
+        (t_start, t_end): (DateTime, DateTime),
+    ) -> Result<Vec<StacFeature>> {
+        let mut items = Vec::new();
+
+        match self.catalog_type {
+            StacCatalogType::Api => {
+                let mut href = self.endpoint("search");
+                let mut params = vec![
+                    ("collections", collection.id.clone()),
+                    (
+                        "bbox",
+                        format!(
+                            "{},{},{},{}",
+                            bbox.lower_left().x,
+                            bbox.lower_left().y,
+                            bbox.upper_right().x,
+                            bbox.upper_right().y
+                        ),
+                    ),
+                    (
+                        "datetime",
+                        format!(
+                            "{}/{}",
+                            t_start.to_datetime_string(),
+                            t_end.to_datetime_string()
+                        ),
+                    ),
+                    ("limit", STAC_API_PAGE_LIMIT.to_string()),
+                ];
+
+                loop {
+                    let page = self.load_items(&href, &params).await?;
+                    items.extend(page.features);
+
+                    let Some(next) = next_link(&page.links) else {
+                        break;
+                    };
+
+                    // the next link contains all parameters
+                    href = next;
+                    params.clear();
+                }
+            }
+            StacCatalogType::Static => {
+                let mut catalogs = vec![collection.clone()];
+                while let Some(catalog) = catalogs.pop() {
+                    for link in catalog.links_with_rel("item") {
+                        let item = self.load_item(&link.href).await?;
+                        if item_intersects(&item, bbox, t_start, t_end) {
+                            items.push(item);
+                        }
+                    }
+
+                    for link in catalog.links_with_rel("child") {
+                        catalogs.push(self.load_catalog(&link.href).await?);
+                    }
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    async fn first_item(&self, collection: &Catalog) -> Result<Option<StacFeature>> {
+        match self.catalog_type {
+            StacCatalogType::Api => {
+                let page = self
+                    .load_items(
+                        &self.endpoint("search"),
+                        &[
+                            ("collections", collection.id.clone()),
+                            ("limit", "1".to_string()),
+                        ],
+                    )
+                    .await?;
+
+                Ok(page.features.into_iter().next())
+            }
+            StacCatalogType::Static => {
+                let mut catalogs = vec![collection.clone()];
+                while let Some(catalog) = catalogs.pop() {
+                    if let Some(link) = catalog.links_with_rel("item").next() {
+                        return self.load_item(&link.href).await.map(Some);
+                    }
+
+                    for link in catalog.links_with_rel("child") {
+                        catalogs.push(self.load_catalog(&link.href).await?);
+                    }
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{path}", self.url.trim_end_matches('/'))
+    }
+
+    /// Load a catalog and resolve its links relative to its location
+    async fn load_catalog(&self, href: &str) -> Result<Catalog> {
+        let mut catalog: Catalog = self.load_json(href, &[]).await?;
+
+        resolve_links(href, &mut catalog.links);
+
+        Ok(catalog)
+    }
+
+    /// Load an item and resolve its asset locations relative to its location
+    async fn load_item(&self, href: &str) -> Result<StacFeature> {
+        let mut item: StacFeature = self.load_json(href, &[]).await?;
+
+        for asset in item.assets.values_mut() {
+            asset.href = resolve_href(href, &asset.href);
+        }
+
+        Ok(item)
+    }
+
+    async fn load_items(
+        &self,
+        href: &str,
+        params: &[(&str, String)],
+    ) -> Result<StacItemCollection> {
+        let mut items: StacItemCollection = self.load_json(href, params).await?;
+
+        for asset in items
+            .features
+            .iter_mut()
+            .flat_map(|item| item.assets.values_mut())
+        {
+            asset.href = resolve_href(href, &asset.href);
+        }
+        resolve_links(href, &mut items.links);
+
+        Ok(items)
+    }
+
+    /// Whether the root catalog is a local file. Only then, its documents may reference local files.
+    fn is_local(&self) -> bool {
+        match Url::parse(&self.url) {
+            Ok(url) => url.scheme() == "file",
+            Err(_) => true,
+        }
+    }
+
+    /// Ensure that the documents of a remote catalog only reference remote locations,
+    /// since they must not be able to read local files.
+    fn check_href(&self, href: &str) -> Result<()> {
+        if self.is_local() {
+            return Ok(());
+        }
+
+        match Url::parse(href) {
+            Ok(url) if matches!(url.scheme(), "http" | "https" | "s3") => Ok(()),
+            _ => Err(Error::StacForbiddenHref {
+                href: href.to_string(),
+            }),
+        }
+    }
+
+    /// Load a JSON document from a URL or a local file. The `params` are only used for URLs.
+    async fn load_json<T: DeserializeOwned>(
+        &self,
+        href: &str,
+        params: &[(&str, String)],
+    ) -> Result<T> {
+        self.check_href(href)?;
+
+        let text = match Url::parse(href) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => self.fetch(url, params).await?,
+            Ok(url) if url.scheme() == "file" => tokio::fs::read_to_string(url.path()).await?,
+            _ => tokio::fs::read_to_string(href).await?,
+        };
+
+        serde_json::from_str::<T>(&text).map_err(|error| Error::StacJsonResponse {
+            url: href.to_string(),
+            response: text,
+            error,
+        })
+    }
+
+    async fn fetch(&self, url: Url, params: &[(&str, String)]) -> Result<String> {
+        retry(
+            self.retries.number_of_retries,
+            self.retries.initial_delay_ms,
+            self.retries.exponential_backoff_factor,
+            Some(STAC_RETRY_MAX_BACKOFF_MS),
+            || async {
+                self.http
+                    .get(url.clone())
+                    .query(params)
+                    .send()
+                    .await
+                    .context(error::Reqwest)?
+                    .error_for_status()
+                    .context(error::Reqwest)?
+                    .text()
+                    .await
+                    .context(error::Reqwest)
+            },
+        )
+        .await
+    }
+}
+
+/// The location of the next page
+fn next_link(links: &[Link]) -> Option<String> {
+    links
+        .iter()
+        .find(|link| {
+            link.rel == "next"
+                && link
+                    .method
+                    .as_deref()
+                    .map_or(true, |method| method == "GET")
+        })
+        .map(|link| link.href.clone())
+}
+
+fn resolve_links(base: &str, links: &mut [Link]) {
+    for link in links {
+        link.href = resolve_href(base, &link.href);
+    }
+}
+
+/// Resolve a `href` that might be relative to the document at `base`, which is either a URL or a local path
+fn resolve_href(base: &str, href: &str) -> String {
+    if Url::parse(href).is_ok() || Path::new(href).is_absolute() {
+        return href.to_string();
+    }
+
+    if let Ok(base) = Url::parse(base) {
+        if let Ok(url) = base.join(href) {
+            return url.to_string();
+        }
+    }
+
+    Path::new(base)
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(href.trim_start_matches("./"))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Whether the bounding box and the time of the `item` intersect the bounding box and the time range
+fn item_intersects(
+    item: &StacFeature,
+    bbox: BoundingBox2D,
+    t_start: DateTime,
+    t_end: DateTime,
+) -> bool {
+    let Some(item_start) = item.properties.start_time() else {
+        return false;
+    };
+    let item_end = item.properties.end_datetime.unwrap_or(item_start);
+
+    item.bbox.min().x <= bbox.upper_right().x
+        && item.bbox.max().x >= bbox.lower_left().x
+        && item.bbox.min().y <= bbox.upper_right().y
+        && item.bbox.max().y >= bbox.lower_left().y
+        && item_start <= t_end
+        && item_end >= t_start
+}
+
+/// The path of an asset for GDAL, using its virtual file systems for remote files
+fn gdal_file_path(href: &str) -> PathBuf {
+    match Url::parse(href) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => format!("/vsicurl/{href}").into(),
+        Ok(url) if url.scheme() == "s3" => format!(
+            "/vsis3/{}{}",
+            url.host_str().unwrap_or_default(),
+            url.path()
+        )
+        .into(),
+        Ok(url) if url.scheme() == "file" => url.path().into(),
+        _ => href.into(),
+    }
+}
+
+/// The ids of the layer collections and layers of the provider.
+/// They use exclamation marks as separators because collection ids may contain slashes or colons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StacLayerId {
+    Collections,
+    Collection {
+        collection: String,
+    },
+    Band {
+        collection: String,
+        asset: String,
+        band: usize,
+        epsg: u32,
+    },
+}
+
+impl FromStr for StacLayerId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let split = s.split('!').collect::<Vec<_>>();
+
+        Ok(match *split.as_slice() {
+            ["collections"] => StacLayerId::Collections,
+            ["collections", collection] => StacLayerId::Collection {
+                collection: collection.to_string(),
+            },
+            ["collections", collection, asset, band, epsg] => StacLayerId::Band {
+                collection: collection.to_string(),
+                asset: asset.to_string(),
+                band: band.parse().map_err(|_| Error::InvalidLayerId)?,
+                epsg: epsg.parse().map_err(|_| Error::InvalidLayerId)?,
+            },
+            _ => return Err(Error::InvalidLayerCollectionId),
+        })
+    }
+}
+
+impl TryFrom<StacLayerId> for LayerCollectionId {
+    type Error = Error;
+
+    fn try_from(value: StacLayerId) -> std::result::Result<Self, Self::Error> {
+        let s = match value {
+            StacLayerId::Collections => "collections".to_string(),
+            StacLayerId::Collection { collection } => format!("collections!{collection}"),
+            StacLayerId::Band { .. } => return Err(Error::InvalidLayerCollectionId),
+        };
+
+        Ok(LayerCollectionId(s))
+    }
+}
+
+impl TryFrom<StacLayerId> for LayerId {
+    type Error = Error;
+
+    fn try_from(value: StacLayerId) -> std::result::Result<Self, Self::Error> {
+        match value {
+            StacLayerId::Band {
+                collection,
+                asset,
+                band,
+                epsg,
+            } => Ok(LayerId(format!(
+                "collections!{collection}!{asset}!{band}!{epsg}"
+            ))),
+            StacLayerId::Collections | StacLayerId::Collection { .. } => Err(Error::InvalidLayerId),
+        }
+    }
+}
+
+#[async_trait]
+impl DataProvider for StacDataProvider {
+    async fn provenance(&self, id: &DataId) -> Result<ProvenanceOutput> {
+        Ok(ProvenanceOutput {
+            data: id.clone(),
+            provenance: None, // TODO: use the providers of the collection
+        })
+    }
+}
+
+#[async_trait]
+impl LayerCollectionProvider for StacDataProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            listing: true,
+            search: SearchCapabilities::none(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    async fn load_layer_collection(
+        &self,
+        collection_id: &LayerCollectionId,
+        options: LayerCollectionListOptions,
+    ) -> Result<LayerCollection> {
+        let stac_id = StacLayerId::from_str(&collection_id.0)
+            .map_err(|_e| Error::InvalidLayerCollectionId)?;
+
+        let (name, description, mut items) = match stac_id {
+            StacLayerId::Collections => {
+                let items = self
+                    .client
+                    .collections()
+                    .await?
+                    .into_iter()
+                    .filter(|collection| self.is_provided(&collection.id))
+                    .map(|collection| {
+                        Ok(CollectionItem::Collection(LayerCollectionListing {
+                            id: ProviderLayerCollectionId {
+                                provider_id: self.id,
+                                collection_id: StacLayerId::Collection {
+                                    collection: collection.id.clone(),
+                                }
+                                .try_into()?,
+                            },
+                            name: collection.title.unwrap_or(collection.id),
+                            description: collection.description,
+                            properties: vec![],
+                        }))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                (self.name.clone(), self.description.clone(), items)
+            }
+            StacLayerId::Collection { collection } => {
+                let collection = self.collection(&collection).await?;
+
+                let items = self
+                    .band_layers(&collection)
+                    .await?
+                    .into_iter()
+                    .map(|layer| {
+                        Ok(CollectionItem::Layer(LayerListing {
+                            id: ProviderLayerId {
+                                provider_id: self.id,
+                                layer_id: layer.id().try_into()?,
+                            },
+                            name: layer.name,
+                            description: String::new(),
+                            properties: vec![],
+                        }))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                (
+                    collection.title.unwrap_or(collection.id),
+                    collection.description,
+                    items,
+                )
+            }
+            StacLayerId::Band { .. } => return Err(Error::InvalidLayerCollectionId),
+        };
+
+        items.sort_by_key(|e| e.name().to_string());
+
+        let items = items
+            .into_iter()
+            .skip(options.offset as usize)
+            .take(options.limit as usize)
+            .collect();
+
+        Ok(LayerCollection {
+            id: ProviderLayerCollectionId {
+                provider_id: self.id,
+                collection_id: collection_id.clone(),
+            },
+            name,
+            description,
+            items,
+            entry_label: None,
+            properties: vec![],
+        })
+    }
+
+    async fn get_root_layer_collection_id(&self) -> Result<LayerCollectionId> {
+        StacLayerId::Collections.try_into()
+    }
+
+    async fn load_layer(&self, id: &LayerId) -> Result<Layer> {
+        let StacLayerId::Band {
+            collection,
+            asset,
+            band,
+            epsg,
+        } = StacLayerId::from_str(&id.0).map_err(|_e| Error::InvalidLayerId)?
+        else {
+            return Err(Error::InvalidLayerId);
+        };
+
+        let (collection, layer) = self.band_layer(&collection, &asset, band, epsg).await?;
+
+        Ok(Layer {
+            id: ProviderLayerId {
+                provider_id: self.id,
+                layer_id: id.clone(),
+            },
+            name: layer.name,
+            description: collection.description,
+            workflow: Workflow {
+                operator: TypedOperator::Raster(
+                    GdalSource {
+                        params: GdalSourceParameters {
+                            data: NamedData {
+                                namespace: None,
+                                provider: Some(self.id.to_string()),
+                                name: id.to_string(),
+                            },
+                        },
+                    }
+                    .boxed(),
+                ),
+            },
+            symbology: None,
+            properties: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StacMetaData {
+    client: StacClient,
+    collection: Catalog,
+    layer: StacBandLayer,
+    gdal_retries: GdalRetries,
+    cache_ttl: CacheTtlSeconds,
+}
+
+impl StacMetaData {
+    async fn create_loading_info(&self, query: RasterQueryRectangle) -> Result<GdalLoadingInfo> {
+        debug!("create_loading_info with: {:?}", &query);
+
+        let time_range = SentinelS2L2aCogsMetaData::time_range_request(&query.time_interval)?;
+
+        let projector = CoordinateProjector::from_known_srs(
+            SpatialReference::new(SpatialReferenceAuthority::Epsg, self.layer.epsg),
+            SpatialReference::epsg_4326(),
+        )?;
+
+        let spatial_partition = query.spatial_partition();
+        let bbox = BoundingBox2D::new_upper_left_lower_right_unchecked(
+            spatial_partition.upper_left(),
+            spatial_partition.lower_right(),
+        );
+
+        let Some(bbox) = bbox.reproject_clipped(&projector)? else {
+            debug!(
+                "query bbox is outside of the spatial reference -> returning empty loading info"
+            );
+            return Ok(GdalLoadingInfo {
+                info: GdalLoadingInfoTemporalSliceIterator::Static {
+                    parts: vec![].into_iter(),
+                },
+            });
+        };
+
+        let items = self
+            .client
+            .items(&self.collection, bbox, time_range)
+            .await?;
+        debug!("number of items returned by STAC: {}", items.len());
+
+        let mut items: Vec<(TimeInstance, StacFeature)> = items
+            .into_iter()
+            .filter(|item| self.item_epsg(item) == self.layer.epsg)
+            .filter_map(|item| Some((item.properties.start_time()?.into(), item)))
+            .collect();
+
+        items.sort_by_key(|(start, _)| *start);
+
+        let start_times_pre: Vec<TimeInstance> = items.iter().map(|(start, _)| *start).collect();
+        let start_times = SentinelS2L2aCogsMetaData::make_unique_start_times_from_sorted_features(
+            &start_times_pre,
+        );
+
+        let mut parts = vec![];
+        let num_items = items.len();
+        for (i, (_, item)) in items.iter().enumerate() {
+            let start = start_times[i];
+
+            // item is valid until next item starts
+            let end = if i < num_items - 1 {
+                start_times[i + 1]
+            } else {
+                item.properties
+                    .end_datetime
+                    .map(TimeInstance::from)
+                    .filter(|end| *end > start)
+                    .unwrap_or(start + 1000)
+            };
+
+            let time_interval = TimeInterval::new(start, end)?;
+
+            if time_interval.intersects(&query.time_interval) {
+                parts.push(self.create_loading_info_part(time_interval, item)?);
+            }
+        }
+        debug!("number of generated loading infos: {}", parts.len());
+
+        Ok(GdalLoadingInfo {
+            info: GdalLoadingInfoTemporalSliceIterator::Static {
+                parts: parts.into_iter(),
+            },
+        })
+    }
+
+    fn item_epsg(&self, item: &StacFeature) -> u32 {
+        item.assets
+            .get(&self.layer.asset)
+            .and_then(|asset| asset.proj_epsg)
+            .or(item.properties.proj_epsg)
+            .unwrap_or(DEFAULT_EPSG)
+    }
+
+    fn create_loading_info_part(
+        &self,
+        time_interval: TimeInterval,
+        item: &StacFeature,
+    ) -> Result<GdalLoadingInfoTemporalSlice> {
+        let asset = item
+            .assets
+            .get(&self.layer.asset)
+            .ok_or(Error::StacNoSuchBand {
+                band_name: self.layer.asset.clone(),
+            })?;
+
+        self.client.check_href(&asset.href)?;
+
+        // the projection information is either part of the asset or of the item
+        let [shape_y, shape_x] = asset
+            .proj_shape
+            .or(item.properties.proj_shape)
+            .ok_or(Error::StacInvalidBbox)?;
+        let geo_transform = asset
+            .gdal_geotransform()
+            .or_else(|| {
+                item.properties
+                    .proj_transform
+                    .as_deref()
+                    .and_then(StacAsset::gdal_geotransform_from)
+            })
+            .ok_or(Error::StacInvalidGeoTransform)?;
+
+        Ok(GdalLoadingInfoTemporalSlice {
+            time: time_interval,
+            params: Some(GdalDatasetParameters {
+                file_path: gdal_file_path(&asset.href),
+                rasterband_channel: self.layer.band + 1,
+                geo_transform: GdalDatasetGeoTransform::from(geo_transform),
+                width: shape_x as usize,
+                height: shape_y as usize,
+                file_not_found_handling: FileNotFoundHandling::NoData,
+                no_data_value: self.layer.no_data_value,
+                properties_mapping: None,
+                gdal_open_options: None,
+                gdal_config_options: Some(vec![
+                    // do not perform a directory scan on remote storage
+                    (
+                        "GDAL_DISABLE_READDIR_ON_OPEN".to_string(),
+                        "EMPTY_DIR".to_string(),
+                    ),
+                    // do not try to read credentials from home directory
+                    ("GDAL_HTTP_NETRC".to_string(), "NO".to_string()),
+                    // disable Gdal's retry because geo engine does its own retry
+                    ("GDAL_HTTP_MAX_RETRY".to_string(), "0".to_string()),
+                ]),
+                allow_alphaband_as_mask: true,
+                retry: Some(GdalRetryOptions {
+                    max_retries: self.gdal_retries.number_of_retries,
+                }),
+            }),
+            cache_ttl: self.cache_ttl,
+        })
+    }
+}
+
+#[async_trait]
+impl MetaData<GdalLoadingInfo, RasterResultDescriptor, RasterQueryRectangle> for StacMetaData {
+    async fn loading_info(
+        &self,
+        query: RasterQueryRectangle,
+    ) -> geoengine_operators::util::Result<GdalLoadingInfo> {
+        self.create_loading_info(query).await.map_err(|e| {
+            geoengine_operators::error::Error::LoadingInfo {
+                source: Box::new(e),
+            }
+        })
+    }
+
+    async fn result_descriptor(&self) -> geoengine_operators::util::Result<RasterResultDescriptor> {
+        Ok(RasterResultDescriptor {
+            data_type: self.layer.data_type,
+            spatial_reference: SpatialReference::new(
+                SpatialReferenceAuthority::Epsg,
+                self.layer.epsg,
+            )
+            .into(),
+            time: None,
+            bbox: None,
+            resolution: None,
+            bands: RasterBandDescriptors::new_single_band(),
+        })
+    }
+
+    fn box_clone(
+        &self,
+    ) -> Box<dyn MetaData<GdalLoadingInfo, RasterResultDescriptor, RasterQueryRectangle>> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl MetaDataProvider<GdalLoadingInfo, RasterResultDescriptor, RasterQueryRectangle>
+    for StacDataProvider
+{
+    async fn meta_data(
+        &self,
+        id: &geoengine_datatypes::dataset::DataId,
+    ) -> Result<
+        Box<dyn MetaData<GdalLoadingInfo, RasterResultDescriptor, RasterQueryRectangle>>,
+        geoengine_operators::error::Error,
+    > {
+        let loading_info_error = |e: Error| geoengine_operators::error::Error::LoadingInfo {
+            source: Box::new(e),
+        };
+
+        let layer_id = id
+            .external()
+            .ok_or(Error::DataIdTypeMissMatch)
+            .map_err(loading_info_error)?
+            .layer_id;
+
+        let StacLayerId::Band {
+            collection,
+            asset,
+            band,
+            epsg,
+        } = StacLayerId::from_str(&layer_id.0).map_err(loading_info_error)?
+        else {
+            return Err(geoengine_operators::error::Error::UnknownDataId);
+        };
+
+        let (collection, layer) = self
+            .band_layer(&collection, &asset, band, epsg)
+            .await
+            .map_err(loading_info_error)?;
+
+        Ok(Box::new(StacMetaData {
+            client: self.client.clone(),
+            collection,
+            layer,
+            gdal_retries: self.gdal_retries,
+            cache_ttl: self.cache_ttl,
+        }))
+    }
+}
+
+#[async_trait]
+impl
+    MetaDataProvider<MockDatasetDataSourceLoadingInfo, VectorResultDescriptor, VectorQueryRectangle>
+    for StacDataProvider
+{
+    async fn meta_data(
+        &self,
+        _id: &geoengine_datatypes::dataset::DataId,
+    ) -> Result<
+        Box<
+            dyn MetaData<
+                MockDatasetDataSourceLoadingInfo,
+                VectorResultDescriptor,
+                VectorQueryRectangle,
+            >,
+        >,
+        geoengine_operators::error::Error,
+    > {
+        Err(geoengine_operators::error::Error::NotImplemented)
+    }
+}
+
+#[async_trait]
+impl MetaDataProvider<OgrSourceDataset, VectorResultDescriptor, VectorQueryRectangle>
+    for StacDataProvider
+{
+    async fn meta_data(
+        &self,
+        _id: &geoengine_datatypes::dataset::DataId,
+    ) -> Result<
+        Box<dyn MetaData<OgrSourceDataset, VectorResultDescriptor, VectorQueryRectangle>>,
+        geoengine_operators::error::Error,
+    > {
+        Err(geoengine_operators::error::Error::NotImplemented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geoengine_datatypes::dataset::ExternalDataId;
+    use geoengine_datatypes::primitives::{BandSelection, SpatialPartition2D, SpatialResolution};
+    use geoengine_datatypes::test_data;
+    use geoengine_datatypes::util::Identifier;
+    use httptest::matchers::{contains, request, url_decoded};
+    use httptest::{all_of, responders, Expectation, Server};
+
+    fn definition(url: String, catalog_type: StacCatalogType) -> StacDataProviderDefinition {
+        StacDataProviderDefinition {
+            name: "STAC".to_string(),
+            id: DataProviderId::new(),
+            description: "STAC catalog".to_string(),
+            priority: None,
+            url,
+            catalog_type,
+            collections: vec![],
+            stac_api_retries: StacApiRetries::default(),
+            gdal_retries: GdalRetries::default(),
+            cache_ttl: CacheTtlSeconds::default(),
+        }
+    }
+
+    fn static_provider() -> StacDataProvider {
+        StacDataProvider::new(definition(
+            test_data!("stac/static_catalog/catalog.json")
+                .to_string_lossy()
+                .to_string(),
+            StacCatalogType::Static,
+        ))
+    }
+
+    fn dem_layer_id() -> LayerId {
+        StacLayerId::Band {
+            collection: "dem".to_string(),
+            asset: "data".to_string(),
+            band: 0,
+            epsg: 4326,
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    #[test]
+    fn it_parses_layer_ids() {
+        let id = StacLayerId::Band {
+            collection: "sentinel-2-l2a".to_string(),
+            asset: "red".to_string(),
+            band: 0,
+            epsg: 32632,
+        };
+
+        let layer_id: LayerId = id.clone().try_into().unwrap();
+        assert_eq!(layer_id.0, "collections!sentinel-2-l2a!red!0!32632");
+        assert_eq!(StacLayerId::from_str(&layer_id.0).unwrap(), id);
+
+        assert_eq!(
+            StacLayerId::from_str("collections").unwrap(),
+            StacLayerId::Collections
+        );
+        assert!(StacLayerId::from_str("collections!a!b!c!d").is_err());
+    }
+
+    #[test]
+    fn it_deserializes_the_provider_definition() {
+        let definition: StacDataProviderDefinition = serde_json::from_reader(
+            std::fs::File::open(test_data!("provider_defs/pro/stac.json")).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(definition.catalog_type, StacCatalogType::Api);
+        assert_eq!(definition.collections.len(), 2);
+        assert_eq!(definition.cache_ttl, CacheTtlSeconds::new(86400));
+        assert_eq!(definition.stac_api_retries, StacApiRetries::default());
+    }
+
+    #[test]
+    fn it_resolves_hrefs() {
+        assert_eq!(
+            resolve_href(
+                "https://example.com/stac/catalog.json",
+                "./dem/collection.json"
+            ),
+            "https://example.com/stac/dem/collection.json"
+        );
+        assert_eq!(
+            resolve_href("/data/stac/catalog.json", "./dem/collection.json"),
+            "/data/stac/dem/collection.json"
+        );
+        assert_eq!(
+            resolve_href("/data/stac/catalog.json", "https://example.com/a.tif"),
+            "https://example.com/a.tif"
+        );
+
+        assert_eq!(
+            gdal_file_path("https://example.com/a.tif"),
+            PathBuf::from("/vsicurl/https://example.com/a.tif")
+        );
+        assert_eq!(
+            gdal_file_path("s3://bucket/a/b.tif"),
+            PathBuf::from("/vsis3/bucket/a/b.tif")
+        );
+    }
+
+    #[test]
+    fn it_creates_layers_for_bands_and_spatial_references() {
+        let collection: Catalog = serde_json::from_str(
+            r#"{
+                "type": "Collection",
+                "id": "landsat",
+                "title": "Landsat",
+                "item_assets": {
+                    "red": {
+                        "type": "image/tiff; application=geotiff; profile=cloud-optimized",
+                        "roles": ["data"],
+                        "eo:bands": [{"name": "B4", "common_name": "red"}],
+                        "raster:bands": [{"data_type": "uint16", "nodata": 0}]
+                    },
+                    "rgb": {
+                        "title": "RGB",
+                        "type": "image/tiff",
+                        "eo:bands": [{"name": "B4"}, {"name": "B3"}, {"name": "B2"}]
+                    },
+                    "metadata": {
+                        "type": "application/xml",
+                        "roles": ["metadata"]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let layers = band_layers(&collection, &collection.item_assets, &[32631, 32632]);
+
+        assert_eq!(
+            layers.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(),
+            vec![
+                "Landsat red (EPSG:32631)",
+                "Landsat red (EPSG:32632)",
+                "Landsat RGB B4 (EPSG:32631)",
+                "Landsat RGB B4 (EPSG:32632)",
+                "Landsat RGB B3 (EPSG:32631)",
+                "Landsat RGB B3 (EPSG:32632)",
+                "Landsat RGB B2 (EPSG:32631)",
+                "Landsat RGB B2 (EPSG:32632)",
+            ]
+        );
+
+        assert_eq!(layers[0].data_type, RasterDataType::U16);
+        assert_eq!(layers[0].no_data_value, Some(0.));
+        assert_eq!(layers[2].data_type, RasterDataType::F32);
+        assert_eq!(layers[6].band, 2);
+    }
+
+    #[tokio::test]
+    async fn it_lists_static_collections_and_layers() {
+        let provider = static_provider();
+
+        let root = provider
+            .load_layer_collection(
+                &provider.get_root_layer_collection_id().await.unwrap(),
+                LayerCollectionListOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(root.items.len(), 1);
+        let CollectionItem::Collection(dem) = &root.items[0] else {
+            panic!("expected a collection");
+        };
+        assert_eq!(dem.name, "Test DEM");
+
+        let dem = provider
+            .load_layer_collection(&dem.id.collection_id, LayerCollectionListOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            dem.items,
+            vec![CollectionItem::Layer(LayerListing {
+                id: ProviderLayerId {
+                    provider_id: provider.id,
+                    layer_id: dem_layer_id(),
+                },
+                name: "Test DEM Elevation".to_string(),
+                description: String::new(),
+                properties: vec![],
+            })]
+        );
+
+        let layer = provider.load_layer(&dem_layer_id()).await.unwrap();
+        assert_eq!(layer.name, "Test DEM Elevation");
+    }
+
+    #[tokio::test]
+    async fn it_creates_loading_infos_from_a_static_catalog() {
+        let provider = static_provider();
+
+        let meta: Box<dyn MetaData<GdalLoadingInfo, RasterResultDescriptor, RasterQueryRectangle>> =
+            provider
+                .meta_data(
+                    &ExternalDataId {
+                        provider_id: provider.id,
+                        layer_id: dem_layer_id(),
+                    }
+                    .into(),
+                )
+                .await
+                .unwrap();
+
+        assert_eq!(
+            meta.result_descriptor().await.unwrap().data_type,
+            RasterDataType::I16
+        );
+
+        let loading_info = meta
+            .loading_info(RasterQueryRectangle {
+                spatial_bounds: SpatialPartition2D::new((8.5, 0.5).into(), (9.5, 0.).into())
+                    .unwrap(),
+                time_interval: TimeInterval::new(
+                    DateTime::new_utc(2021, 4, 22, 0, 0, 0),
+                    DateTime::new_utc(2021, 4, 23, 0, 0, 0),
+                )
+                .unwrap(),
+                spatial_resolution: SpatialResolution::zero_point_one(),
+                attributes: BandSelection::first(),
+            })
+            .await
+            .unwrap();
+
+        let GdalLoadingInfoTemporalSliceIterator::Static { parts } = loading_info.info else {
+            unreachable!();
+        };
+        let parts: Vec<_> = parts.collect();
+
+        // items with the same time are made unique
+        let start = TimeInstance::from(DateTime::new_utc(2021, 4, 22, 0, 0, 0));
+        assert_eq!(
+            parts.iter().map(|part| part.time).collect::<Vec<_>>(),
+            vec![
+                TimeInterval::new_unchecked(start, start + 1),
+                TimeInterval::new_unchecked(start + 1, start + 1001),
+            ]
+        );
+
+        let mut file_paths = parts
+            .iter()
+            .map(|part| part.params.as_ref().unwrap().file_path.clone())
+            .collect::<Vec<_>>();
+        file_paths.sort();
+        assert_eq!(
+            file_paths,
+            vec![
+                test_data!("stac/static_catalog/dem/items/n00e008.tif").to_path_buf(),
+                test_data!("stac/static_catalog/dem/items/n00e009.tif").to_path_buf(),
+            ]
+        );
+
+        let params = parts[0].params.as_ref().unwrap();
+        assert_eq!(params.width, 1200);
+        assert_eq!(params.height, 1200);
+        assert_eq!(params.no_data_value, Some(-32768.));
+        assert_eq!(params.rasterband_channel, 1);
+    }
+
+    #[tokio::test]
+    async fn it_searches_a_stac_api() {
+        let server = Server::run();
+
+        let collection =
+            std::fs::read_to_string(test_data!("stac/static_catalog/dem/collection.json")).unwrap();
+        let item = |name: &str| -> serde_json::Value {
+            let mut item: serde_json::Value = serde_json::from_str(
+                &std::fs::read_to_string(test_data!(format!(
+                    "stac/static_catalog/dem/items/{name}.json"
+                )))
+                .unwrap(),
+            )
+            .unwrap();
+            item["assets"]["data"]["href"] = format!("https://example.com/{name}.tif").into();
+            item
+        };
+
+        server.expect(
+            Expectation::matching(request::method_path("GET", "/collections/dem"))
+                .respond_with(responders::status_code(200).body(collection)),
+        );
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("GET", "/search"),
+                request::query(url_decoded(contains(("collections", "dem")))),
+                request::query(url_decoded(contains(("limit", "500")))),
+            ])
+            .respond_with(responders::json_encoded(serde_json::json!({
+                "type": "FeatureCollection",
+                "features": [item("n00e008")],
+                "links": [{
+                    "rel": "next",
+                    "href": server.url_str("/search?token=page2"),
+                    "method": "GET"
+                }]
+            }))),
+        );
+        server.expect(
+            Expectation::matching(all_of![
+                request::method_path("GET", "/search"),
+                request::query(url_decoded(contains(("token", "page2")))),
+            ])
+            .respond_with(responders::json_encoded(serde_json::json!({
+                "type": "FeatureCollection",
+                "features": [item("n00e009")],
+                "links": []
+            }))),
+        );
+
+        let provider = StacDataProvider::new(definition(server.url_str(""), StacCatalogType::Api));
+
+        let meta: Box<dyn MetaData<GdalLoadingInfo, RasterResultDescriptor, RasterQueryRectangle>> =
+            provider
+                .meta_data(
+                    &ExternalDataId {
+                        provider_id: provider.id,
+                        layer_id: dem_layer_id(),
+                    }
+                    .into(),
+                )
+                .await
+                .unwrap();
+
+        let loading_info = meta
+            .loading_info(RasterQueryRectangle {
+                spatial_bounds: SpatialPartition2D::new((8.5, 0.5).into(), (9.5, 0.).into())
+                    .unwrap(),
+                time_interval: TimeInterval::new_instant(DateTime::new_utc(2021, 4, 22, 0, 0, 0))
+                    .unwrap(),
+                spatial_resolution: SpatialResolution::zero_point_one(),
+                attributes: BandSelection::first(),
+            })
+            .await
+            .unwrap();
+
+        let GdalLoadingInfoTemporalSliceIterator::Static { parts } = loading_info.info else {
+            unreachable!();
+        };
+        let parts: Vec<_> = parts.collect();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            parts[0].params.as_ref().unwrap().file_path,
+            PathBuf::from("/vsicurl/https://example.com/n00e008.tif")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_local_hrefs_of_remote_catalogs() {
+        let server = Server::run();
+
+        server.expect(
+            Expectation::matching(request::method_path("GET", "/catalog.json")).respond_with(
+                responders::json_encoded(serde_json::json!({
+                    "type": "Catalog",
+                    "id": "root",
+                    "stac_version": "1.0.0",
+                    "description": "Root catalog",
+                    "links": [{ "rel": "child", "href": "/etc/passwd" }]
+                })),
+            ),
+        );
+
+        let provider = StacDataProvider::new(definition(
+            server.url_str("/catalog.json"),
+            StacCatalogType::Static,
+        ));
+
+        assert!(matches!(
+            provider.client.collections().await,
+            Err(Error::StacForbiddenHref { href }) if href == "/etc/passwd"
+        ));
+
+        assert!(provider
+            .client
+            .check_href("https://example.com/a.tif")
+            .is_ok());
+        assert!(provider.client.check_href("s3://bucket/a.tif").is_ok());
+        assert!(provider.client.check_href("file:///etc/passwd").is_err());
+
+        // local catalogs may reference local files
+        assert!(static_provider().client.check_href("/data/a.tif").is_ok());
+    }
+}
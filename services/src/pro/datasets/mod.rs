@@ -2,6 +2,6 @@ mod external;
 mod postgres;
 
 pub use external::{
-    GdalRetries, SentinelS2L2ACogsProviderDefinition, StacApiRetries, StacBand, StacZone,
-    TypedProDataProviderDefinition,
+    GdalRetries, SentinelS2L2ACogsProviderDefinition, StacApiRetries, StacBand,
+    StacCatalogType, StacDataProviderDefinition, StacZone, TypedProDataProviderDefinition,
 };
@@ -2,10 +2,10 @@ use crate::api::model::services::UpdateDataset;
 use crate::datasets::listing::Provenance;
 use crate::datasets::listing::{DatasetListOptions, DatasetListing, DatasetProvider};
 use crate::datasets::listing::{OrderBy, ProvenanceOutput};
-use crate::datasets::postgres::resolve_dataset_name_to_id;
+use crate::datasets::postgres::{resolve_dataset_name_to_id, ResumableFileUpload};
 use crate::datasets::storage::{Dataset, DatasetDb, DatasetStore, MetaDataDefinition};
 use crate::datasets::upload::FileId;
-use crate::datasets::upload::{Upload, UploadDb, UploadId};
+use crate::datasets::upload::{ResumableUpload, Upload, UploadDb, UploadId};
 use crate::datasets::{AddDataset, DatasetIdAndName, DatasetName};
 use crate::error::{self, Error, Result};
 use crate::pro::contexts::ProPostgresDb;
@@ -16,6 +16,7 @@ use crate::util::postgres::PostgresErrorExt;
 use async_trait::async_trait;
 use bb8_postgres::tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
 use bb8_postgres::tokio_postgres::Socket;
+use futures::future::BoxFuture;
 use geoengine_datatypes::dataset::{DataId, DatasetId};
 use geoengine_datatypes::error::BoxedResultExt;
 use geoengine_datatypes::primitives::RasterQueryRectangle;
@@ -717,6 +718,49 @@ where
         Ok(())
     }
 
+    async fn update_dataset_loading_info<'a, F>(
+        &'a self,
+        dataset: DatasetId,
+        update: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(MetaDataDefinition) -> BoxFuture<'a, Result<MetaDataDefinition>> + Send + 'a,
+    {
+        let mut conn = self.conn_pool.get().await?;
+
+        let tx = conn.build_transaction().start().await?;
+
+        // the permission check must precede the update, since it may change files of the dataset
+        self.ensure_permission_in_tx(dataset.into(), Permission::Owner, &tx)
+            .await
+            .boxed_context(crate::error::PermissionDb)?;
+
+        let row = tx
+            .query_opt(
+                "SELECT meta_data FROM datasets WHERE id = $1 FOR UPDATE;",
+                &[&dataset],
+            )
+            .await?
+            .ok_or(error::Error::UnknownDatasetId)?;
+
+        let meta_data = update(row.get(0)).await?;
+        let typed_meta_data = meta_data.to_typed_metadata();
+
+        tx.execute(
+            "UPDATE datasets SET meta_data = $2, result_descriptor = $3 WHERE id = $1;",
+            &[
+                &dataset,
+                typed_meta_data.meta_data,
+                &typed_meta_data.result_descriptor,
+            ],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     async fn delete_dataset(&self, dataset_id: DatasetId) -> Result<()> {
         let mut conn = self.conn_pool.get().await?;
         let tx = conn.build_transaction().start().await?;
@@ -822,6 +866,213 @@ where
 
         Ok(())
     }
+
+    async fn create_resumable_upload(&self, upload: ResumableUpload) -> Result<()> {
+        let mut conn = self.conn_pool.get().await?;
+        let tx = conn.build_transaction().start().await?;
+
+        tx.execute(
+            "INSERT INTO resumable_uploads (id, files) VALUES ($1, $2)",
+            &[
+                &upload.id,
+                &upload
+                    .files
+                    .iter()
+                    .map(ResumableFileUpload::from)
+                    .collect::<Vec<_>>(),
+            ],
+        )
+        .await?;
+
+        tx.execute(
+            "INSERT INTO user_resumable_uploads (user_id, upload_id) VALUES ($1, $2)",
+            &[&self.session.user.id, &upload.id],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn load_resumable_upload(&self, upload: UploadId) -> Result<ResumableUpload> {
+        let conn = self.conn_pool.get().await?;
+
+        let stmt = conn
+            .prepare(
+                "
+            SELECT u.id, u.files
+            FROM resumable_uploads u JOIN user_resumable_uploads uu ON(u.id = uu.upload_id)
+            WHERE u.id = $1 AND uu.user_id = $2",
+            )
+            .await?;
+
+        let row = conn
+            .query_opt(&stmt, &[&upload, &self.session.user.id])
+            .await?
+            .ok_or(error::Error::UnknownUploadId)?;
+
+        Ok(ResumableUpload {
+            id: row.get(0),
+            files: row
+                .get::<_, Vec<ResumableFileUpload>>(1)
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+
+    async fn lock_resumable_upload<'a, T, F>(&'a self, upload: UploadId, write: F) -> Result<T>
+    where
+        T: Send + 'a,
+        F: FnOnce(ResumableUpload) -> BoxFuture<'a, Result<T>> + Send + 'a,
+    {
+        let mut conn = self.conn_pool.get().await?;
+        let tx = conn.build_transaction().start().await?;
+
+        let row = tx
+            .query_opt(
+                "
+            SELECT u.id, u.files
+            FROM resumable_uploads u JOIN user_resumable_uploads uu ON(u.id = uu.upload_id)
+            WHERE u.id = $1 AND uu.user_id = $2
+            FOR UPDATE OF u",
+                &[&upload, &self.session.user.id],
+            )
+            .await?
+            .ok_or(error::Error::UnknownUploadId)?;
+
+        let result = write(ResumableUpload {
+            id: row.get(0),
+            files: row
+                .get::<_, Vec<ResumableFileUpload>>(1)
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn finish_resumable_upload<'a, F>(&'a self, upload: UploadId, finish: F) -> Result<()>
+    where
+        F: FnOnce(ResumableUpload) -> BoxFuture<'a, Result<Upload>> + Send + 'a,
+    {
+        let mut conn = self.conn_pool.get().await?;
+        let tx = conn.build_transaction().start().await?;
+
+        let row = tx
+            .query_opt(
+                "
+            SELECT u.id, u.files
+            FROM resumable_uploads u JOIN user_resumable_uploads uu ON(u.id = uu.upload_id)
+            WHERE u.id = $1 AND uu.user_id = $2
+            FOR UPDATE OF u",
+                &[&upload, &self.session.user.id],
+            )
+            .await?;
+
+        let Some(row) = row else {
+            // the upload may have been finished by a previous request
+            let finished = tx
+                .query_opt(
+                    "SELECT TRUE FROM user_uploads WHERE upload_id = $1 AND user_id = $2",
+                    &[&upload, &self.session.user.id],
+                )
+                .await?;
+
+            return finished.map(|_| ()).ok_or(error::Error::UnknownUploadId);
+        };
+
+        let upload = finish(ResumableUpload {
+            id: row.get(0),
+            files: row
+                .get::<_, Vec<ResumableFileUpload>>(1)
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+        .await?;
+
+        tx.execute(
+            "DELETE FROM resumable_uploads WHERE id = $1;",
+            &[&upload.id],
+        )
+        .await?;
+
+        tx.execute(
+            "INSERT INTO uploads (id, files) VALUES ($1, $2)",
+            &[
+                &upload.id,
+                &upload
+                    .files
+                    .iter()
+                    .map(FileUpload::from)
+                    .collect::<Vec<_>>(),
+            ],
+        )
+        .await?;
+
+        tx.execute(
+            "INSERT INTO user_uploads (user_id, upload_id) VALUES ($1, $2)",
+            &[&self.session.user.id, &upload.id],
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_resumable_upload(&self, upload: UploadId) -> Result<()> {
+        let conn = self.conn_pool.get().await?;
+
+        let deleted = conn
+            .execute(
+                "
+            DELETE FROM resumable_uploads
+            WHERE id = $1 AND id IN (
+                SELECT upload_id FROM user_resumable_uploads WHERE user_id = $2
+            )",
+                &[&upload, &self.session.user.id],
+            )
+            .await?;
+
+        if deleted == 0 {
+            return Err(error::Error::UnknownUploadId);
+        }
+
+        Ok(())
+    }
+
+    async fn used_upload_bytes(&self) -> Result<u64> {
+        let conn = self.conn_pool.get().await?;
+
+        let row = conn
+            .query_one(
+                "
+            SELECT ((
+                SELECT COALESCE(SUM(f.byte_size), 0)
+                FROM uploads u
+                    JOIN user_uploads uu ON (u.id = uu.upload_id),
+                    unnest(u.files) AS f
+                WHERE uu.user_id = $1
+            ) + (
+                SELECT COALESCE(SUM(f.byte_size), 0)
+                FROM resumable_uploads u
+                    JOIN user_resumable_uploads uu ON (u.id = uu.upload_id),
+                    unnest(u.files) AS f
+                WHERE uu.user_id = $1
+            ))::bigint",
+                &[&self.session.user.id],
+            )
+            .await?;
+
+        Ok(row.get::<_, i64>(0) as u64)
+    }
 }
 
 #[derive(Debug, Clone, ToSql, FromSql)]
@@ -46,6 +46,8 @@ where
         let mut api = web::scope(&api_prefix)
             .configure(configure_extractors)
             .configure(pro::api::handlers::datasets::init_dataset_routes::<C>)
+            .configure(handlers::features::init_features_routes::<C>)
+            .configure(handlers::tiles::init_tile_routes::<C>)
             .configure(handlers::layers::init_layer_routes::<C>)
             .configure(pro::api::handlers::machine_learning::init_ml_routes::<C>)
             .configure(pro::api::handlers::permissions::init_permissions_routes::<C>)
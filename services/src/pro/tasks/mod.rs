@@ -2,13 +2,16 @@ use super::users::UserSession;
 use crate::{
     error,
     tasks::{
-        SimpleTaskManagerBackend, SimpleTaskManagerContext, Task, TaskError, TaskId,
+        PostgresTaskManagerBackend, SimpleTaskManagerContext, Task, TaskError, TaskId,
         TaskListOptions, TaskManager, TaskStatus, TaskStatusWithId,
     },
 };
+use bb8_postgres::tokio_postgres::{
+    tls::{MakeTlsConnect, TlsConnect},
+    Socket,
+};
 use futures::channel::oneshot;
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
+use std::sync::Arc;
 
 // TODO: implement real permissions on task types
 const ADMIN_ONLY_TASKS: [&str; 3] = [
@@ -17,28 +20,36 @@ const ADMIN_ONLY_TASKS: [&str; 3] = [
     crate::api::handlers::ebv::EBV_REMOVE_OVERVIEW_TASK_TYPE,
 ];
 
-#[derive(Default)]
-pub struct ProTaskManagerBackend {
-    simple_task_manager: SimpleTaskManagerBackend,
-    task_type_by_id: RwLock<HashMap<TaskId, &'static str>>,
-}
-
-pub struct ProTaskManager {
-    backend: Arc<ProTaskManagerBackend>,
+pub struct ProTaskManager<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    backend: Arc<PostgresTaskManagerBackend<Tls>>,
     session: UserSession,
 }
 
-impl ProTaskManager {
-    pub fn new(backend: Arc<ProTaskManagerBackend>, session: UserSession) -> Self {
+impl<Tls> ProTaskManager<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    pub fn new(backend: Arc<PostgresTaskManagerBackend<Tls>>, session: UserSession) -> Self {
         Self { backend, session }
     }
+
+    async fn check_task_is_allowed(&self, task_id: TaskId) -> Result<(), TaskError> {
+        let task_type = self.backend.task_type(task_id).await?;
+        check_task_type_is_allowed(&self.session, &task_type)
+    }
 }
 
-fn check_task_type_is_allowed(
-    session: &UserSession,
-    task_type: &'static str,
-) -> Result<(), TaskError> {
-    if ADMIN_ONLY_TASKS.contains(&task_type) && !session.is_admin() {
+fn check_task_type_is_allowed(session: &UserSession, task_type: &str) -> Result<(), TaskError> {
+    if ADMIN_ONLY_TASKS.iter().any(|t| *t == task_type) && !session.is_admin() {
         return Err(crate::tasks::TaskError::TaskManagerOperationFailed {
             source: Box::new(error::Error::PermissionDenied),
         });
@@ -48,49 +59,31 @@ fn check_task_type_is_allowed(
 }
 
 #[async_trait::async_trait]
-impl TaskManager<SimpleTaskManagerContext> for ProTaskManager {
+impl<Tls> TaskManager<SimpleTaskManagerContext> for ProTaskManager<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
     async fn schedule_task(
         &self,
         task: Box<dyn Task<SimpleTaskManagerContext>>,
         notify: Option<oneshot::Sender<TaskStatus>>,
     ) -> Result<TaskId, TaskError> {
-        let task_type = task.task_type();
-        check_task_type_is_allowed(&self.session, task_type)?;
+        check_task_type_is_allowed(&self.session, task.task_type())?;
 
         // TODO: check permissions for user tasks
 
-        let task_id = self
-            .backend
-            .simple_task_manager
-            .schedule_task(task, notify)
-            .await?;
-
-        self.backend
-            .task_type_by_id
-            .write()
-            .await
-            .insert(task_id, task_type);
-
-        Ok(task_id)
+        self.backend.schedule_task(task, notify).await
     }
 
     async fn get_task_status(&self, task_id: TaskId) -> Result<TaskStatus, TaskError> {
-        check_task_type_is_allowed(
-            &self.session,
-            self.backend
-                .task_type_by_id
-                .read()
-                .await
-                .get(&task_id)
-                .ok_or(TaskError::TaskNotFound { task_id })?,
-        )?;
+        self.check_task_is_allowed(task_id).await?;
 
         // TODO: check permissions for user tasks
 
-        self.backend
-            .simple_task_manager
-            .get_task_status(task_id)
-            .await
+        self.backend.get_task_status(task_id).await
     }
 
     async fn list_tasks(
@@ -99,49 +92,23 @@ impl TaskManager<SimpleTaskManagerContext> for ProTaskManager {
     ) -> Result<Vec<TaskStatusWithId>, TaskError> {
         // TODO: check permissions for user tasks
 
-        let tasks = self
-            .backend
-            .simple_task_manager
-            .list_tasks(TaskListOptions {
-                filter: options.filter,
-                offset: 0,
-                limit: u32::MAX,
-            })
-            .await?;
-
-        let task_types = self.backend.task_type_by_id.read().await;
-
-        Ok(tasks
-            .iter()
-            .filter(|t| {
-                if let Some(task_type) = task_types.get(&t.task_id) {
-                    return check_task_type_is_allowed(&self.session, task_type).is_ok();
-                }
-                false
-            })
-            .skip(options.offset as usize)
-            .take(options.limit as usize)
-            .cloned()
-            .collect::<Vec<_>>())
+        let excluded_task_types: &[&str] = if self.session.is_admin() {
+            &[]
+        } else {
+            &ADMIN_ONLY_TASKS
+        };
+
+        self.backend
+            .list_tasks_excluding(options, excluded_task_types)
+            .await
     }
 
     async fn abort_tasks(&self, task_id: TaskId, force: bool) -> Result<(), TaskError> {
-        check_task_type_is_allowed(
-            &self.session,
-            self.backend
-                .task_type_by_id
-                .read()
-                .await
-                .get(&task_id)
-                .ok_or(TaskError::TaskNotFound { task_id })?,
-        )?;
+        self.check_task_is_allowed(task_id).await?;
 
         // TODO: check permissions for user tasks
 
-        self.backend
-            .simple_task_manager
-            .abort_tasks(task_id, force)
-            .await
+        self.backend.abort_tasks(task_id, force).await
     }
 }
 
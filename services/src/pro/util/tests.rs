@@ -126,6 +126,8 @@ pub async fn send_pro_test_request(
             .wrap(middleware::NormalizePath::trim())
             .configure(configure_extractors)
             .configure(pro::api::handlers::datasets::init_dataset_routes::<ProPostgresContext<NoTls>>)
+            .configure(handlers::features::init_features_routes::<ProPostgresContext<NoTls>>)
+            .configure(handlers::tiles::init_tile_routes::<ProPostgresContext<NoTls>>)
             .configure(handlers::layers::init_layer_routes::<ProPostgresContext<NoTls>>)
             .configure(pro::api::handlers::machine_learning::init_ml_routes::<ProPostgresContext<NoTls>>)
             .configure(
@@ -86,6 +86,8 @@ where
         let mut api = web::scope(&api_prefix)
             .configure(configure_extractors)
             .configure(handlers::datasets::init_dataset_routes::<C>)
+            .configure(handlers::features::init_features_routes::<C>)
+            .configure(handlers::tiles::init_tile_routes::<C>)
             .configure(handlers::layers::init_layer_routes::<C>)
             .configure(handlers::plots::init_plot_routes::<C>)
             .configure(handlers::projects::init_project_routes::<C>)
@@ -2,6 +2,7 @@ use std::{collections::HashMap, convert::TryFrom};
 
 use geo::Rect;
 use geoengine_datatypes::primitives::DateTime;
+use geoengine_datatypes::raster::RasterDataType;
 use serde::{de::value::MapDeserializer, de::Error, Deserialize, Deserializer};
 use serde_with::with_prefix;
 
@@ -9,10 +10,63 @@ use snafu::Snafu;
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct FeatureCollection {
-    pub stac_version: String,
+    pub stac_version: Option<String>,
+    #[serde(default)]
     pub stac_extensions: Vec<String>,
-    pub context: Context,
+    pub context: Option<Context>,
     pub features: Vec<Feature>,
+    #[serde(default)]
+    pub links: Vec<Link>,
+}
+
+/// A STAC catalog or collection
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Catalog {
+    /// Either `Catalog` or `Collection`
+    #[serde(rename = "type")]
+    pub catalog_type: Option<String>,
+    pub id: String,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub links: Vec<Link>,
+    #[serde(default)]
+    pub summaries: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub item_assets: HashMap<String, ItemAsset>,
+}
+
+impl Catalog {
+    pub fn is_collection(&self) -> bool {
+        self.catalog_type.as_deref() == Some("Collection")
+    }
+
+    pub fn links_with_rel<'a>(&'a self, rel: &'a str) -> impl Iterator<Item = &'a Link> + 'a {
+        self.links.iter().filter(move |link| link.rel == rel)
+    }
+
+    /// The EPSG codes listed in the `proj:epsg` summary
+    pub fn summary_epsg_codes(&self) -> Vec<u32> {
+        self.summaries
+            .get("proj:epsg")
+            .and_then(serde_json::Value::as_array)
+            .map(|codes| {
+                codes
+                    .iter()
+                    .filter_map(serde_json::Value::as_u64)
+                    .filter_map(|code| u32::try_from(code).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The response of the `/collections` endpoint of a STAC API
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Collections {
+    pub collections: Vec<Catalog>,
+    #[serde(default)]
     pub links: Vec<Link>,
 }
 
@@ -47,24 +101,37 @@ pub struct Feature {
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Properties {
-    pub datetime: DateTime,
-    pub platform: String,
-    pub constellation: String,
-    pub instruments: Vec<String>,
-    pub gsd: f64,
+    pub datetime: Option<DateTime>,
+    pub start_datetime: Option<DateTime>,
+    pub end_datetime: Option<DateTime>,
+    pub platform: Option<String>,
+    pub constellation: Option<String>,
+    pub instruments: Option<Vec<String>>,
+    pub gsd: Option<f64>,
     #[serde(rename = "view:off_nadir")]
-    pub view_off_nadir: f64,
+    pub view_off_nadir: Option<f64>,
     #[serde(rename = "proj:epsg")]
     pub proj_epsg: Option<u32>,
+    #[serde(rename = "proj:shape")]
+    pub proj_shape: Option<[u32; 2]>,
+    #[serde(rename = "proj:transform")]
+    pub proj_transform: Option<Vec<f64>>,
     #[serde(flatten, with = "prefix_sentinel")]
     pub sentinel: Option<SentinelProperties>,
     #[serde(rename = "eo:cloud_cover")]
     pub eo_cloud_cover: Option<f32>,
-    pub created: DateTime,
-    pub updated: DateTime,
+    pub created: Option<DateTime>,
+    pub updated: Option<DateTime>,
     pub collection: Option<String>,
 }
 
+impl Properties {
+    /// The `datetime` of the item or the start of its time range if it has none
+    pub fn start_time(&self) -> Option<DateTime> {
+        self.datetime.or(self.start_datetime)
+    }
+}
+
 with_prefix!(prefix_sentinel "sentinel:");
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -79,32 +146,139 @@ pub struct SentinelProperties {
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct EoBand {
-    name: String,
-    common_name: Option<String>,
+    pub name: Option<String>,
+    pub common_name: Option<String>,
     center_wavelenght: Option<f64>,
     full_width_half_max: Option<f64>,
 }
 
+/// A band of the STAC raster extension
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RasterBand {
+    #[serde(default, deserialize_with = "deserialize_no_data")]
+    pub nodata: Option<f64>,
+    pub data_type: Option<String>,
+}
+
+impl RasterBand {
+    pub fn raster_data_type(&self) -> Option<RasterDataType> {
+        Some(match self.data_type.as_deref()? {
+            "uint8" => RasterDataType::U8,
+            "uint16" => RasterDataType::U16,
+            "uint32" => RasterDataType::U32,
+            "uint64" => RasterDataType::U64,
+            "int8" => RasterDataType::I8,
+            "int16" => RasterDataType::I16,
+            "int32" => RasterDataType::I32,
+            "int64" => RasterDataType::I64,
+            "float16" | "float32" => RasterDataType::F32,
+            "float64" => RasterDataType::F64,
+            _ => return None,
+        })
+    }
+}
+
+/// The no data value is either a number or one of `nan`, `inf` and `-inf`
+fn deserialize_no_data<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NoData {
+        Number(f64),
+        Text(String),
+    }
+
+    Ok(match Option::<NoData>::deserialize(deserializer)? {
+        None => None,
+        Some(NoData::Number(value)) => Some(value),
+        Some(NoData::Text(text)) => match text.as_str() {
+            "nan" => Some(f64::NAN),
+            "inf" => Some(f64::INFINITY),
+            "-inf" => Some(f64::NEG_INFINITY),
+            _ => return Err(D::Error::custom(format!("invalid no data value `{text}`"))),
+        },
+    })
+}
+
+/// The description of an asset that the items of a collection provide
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ItemAsset {
+    pub title: Option<String>,
+    #[serde(rename = "type")]
+    pub mime_type: Option<String>,
+    pub roles: Option<Vec<String>>,
+    #[serde(rename = "eo:bands")]
+    pub eo_bands: Option<Vec<EoBand>>,
+    #[serde(rename = "raster:bands")]
+    pub raster_bands: Option<Vec<RasterBand>>,
+    #[serde(rename = "proj:epsg")]
+    pub proj_epsg: Option<u32>,
+}
+
+impl ItemAsset {
+    /// Whether the asset is raster data, i.e., not a thumbnail or metadata
+    pub fn is_raster_data(&self) -> bool {
+        let is_image = self
+            .mime_type
+            .as_ref()
+            .map_or(false, |mime_type| mime_type.starts_with("image/tiff"));
+        let is_data = self
+            .roles
+            .as_ref()
+            .map_or(true, |roles| roles.iter().any(|role| role == "data"));
+
+        (is_image || self.raster_bands.is_some()) && is_data
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct StacAsset {
-    pub title: String,
+    pub title: Option<String>,
     #[serde(rename = "type")]
-    pub mime_type: String,
+    pub mime_type: Option<String>,
     pub roles: Option<Vec<String>>,
     pub gsd: Option<f64>,
     #[serde(rename = "eo:bands")]
     pub eo_bands: Option<Vec<EoBand>>,
+    #[serde(rename = "raster:bands")]
+    pub raster_bands: Option<Vec<RasterBand>>,
     pub href: String,
+    #[serde(rename = "proj:epsg")]
+    pub proj_epsg: Option<u32>,
     #[serde(rename = "proj:shape")]
     pub proj_shape: Option<[u32; 2]>,
     #[serde(rename = "proj:transform")]
-    pub proj_transform: Option<[f64; 9]>,
+    pub proj_transform: Option<Vec<f64>>,
+}
+
+impl From<&StacAsset> for ItemAsset {
+    fn from(asset: &StacAsset) -> Self {
+        Self {
+            title: asset.title.clone(),
+            mime_type: asset.mime_type.clone(),
+            roles: asset.roles.clone(),
+            eo_bands: asset.eo_bands.clone(),
+            raster_bands: asset.raster_bands.clone(),
+            proj_epsg: asset.proj_epsg,
+        }
+    }
 }
 
 impl StacAsset {
+    /// The geo transform of the asset, which is stored in its `proj:transform` field
     pub fn gdal_geotransform(&self) -> Option<[f64; 6]> {
-        self.proj_transform
-            .map(|t| [t[2], t[0], t[1], t[5], t[3], t[4]])
+        Self::gdal_geotransform_from(self.proj_transform.as_deref()?)
+    }
+
+    /// Converts a `proj:transform` with at least six elements to a GDAL geo transform
+    pub fn gdal_geotransform_from(t: &[f64]) -> Option<[f64; 6]> {
+        if t.len() < 6 {
+            return None;
+        }
+
+        Some([t[2], t[0], t[1], t[5], t[3], t[4]])
     }
 
     pub fn native_bbox(&self) -> Option<Rect<f64>> {
@@ -203,14 +377,11 @@ impl TryFrom<geojson::Feature> for Feature {
 
             let stac_version: String = serde_json::from_value(stac_version)?;
 
-            let stac_extensions = foreign_members
+            let stac_extensions: Vec<String> = foreign_members
                 .get("stac_extensions")
-                .map(Clone::clone)
-                .ok_or(StacError::MissingRequiredField {
-                    field_name: "stac_extensions".to_owned(),
-                })?;
-
-            let stac_extensions: Vec<String> = serde_json::from_value(stac_extensions)?;
+                .map(|stac_extensions| serde_json::from_value(stac_extensions.clone()))
+                .transpose()?
+                .unwrap_or_default();
 
             return Ok(Feature {
                 stac_version,
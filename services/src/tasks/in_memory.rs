@@ -79,15 +79,15 @@ struct WriteLockForUpdate<'a> {
     pub unique_tasks: RwLockWriteGuard<'a, HashSet<(&'static str, String)>>,
 }
 
-#[async_trait::async_trait]
-impl TaskManager<SimpleTaskManagerContext> for SimpleTaskManagerBackend {
-    async fn schedule_task(
+impl SimpleTaskManagerBackend {
+    /// Schedule a task like [`TaskManager::schedule_task`] but with a given id,
+    /// e.g., for resuming a task that another instance scheduled.
+    pub(super) async fn schedule_task_with_id(
         &self,
+        task_id: TaskId,
         task: Box<dyn Task<SimpleTaskManagerContext>>,
         notify: Option<oneshot::Sender<TaskStatus>>,
     ) -> Result<TaskId, TaskError> {
-        let task_id = TaskId::new();
-
         // get lock before starting the task to prevent a race condition of initial status setting
         let mut lock = self.write_lock_all().await;
 
@@ -147,6 +147,18 @@ impl TaskManager<SimpleTaskManagerContext> for SimpleTaskManagerBackend {
 
         Ok(task_id)
     }
+}
+
+#[async_trait::async_trait]
+impl TaskManager<SimpleTaskManagerContext> for SimpleTaskManagerBackend {
+    async fn schedule_task(
+        &self,
+        task: Box<dyn Task<SimpleTaskManagerContext>>,
+        notify: Option<oneshot::Sender<TaskStatus>>,
+    ) -> Result<TaskId, TaskError> {
+        self.schedule_task_with_id(TaskId::new(), task, notify)
+            .await
+    }
 
     async fn get_task_status(&self, task_id: TaskId) -> Result<TaskStatus, TaskError> {
         let task_status_map = self.status_by_id.read().await;
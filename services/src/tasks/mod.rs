@@ -1,5 +1,6 @@
 mod error;
 mod in_memory;
+mod postgres;
 mod time_estimation;
 pub mod util;
 
@@ -11,6 +12,7 @@ use futures::channel::oneshot;
 use geoengine_datatypes::primitives::DateTime;
 use geoengine_datatypes::{error::ErrorSource, util::AsAnyArc};
 pub use in_memory::{SimpleTaskManager, SimpleTaskManagerBackend, SimpleTaskManagerContext};
+pub use postgres::{PostgresTaskManager, PostgresTaskManagerBackend, TaskLoader};
 use serde::{Deserialize, Serialize, Serializer};
 use std::borrow::Cow;
 use std::{fmt, sync::Arc};
@@ -69,6 +71,14 @@ pub trait Task<C: TaskContext>: Send + Sync {
 
     fn task_description(&self) -> String;
 
+    /// A serialized form of the task from which a [`TaskLoader`] restores it.
+    ///
+    /// Only tasks with a payload can be queued and resumed after the instance running them stopped.
+    ///
+    fn task_payload(&self) -> Option<serde_json::Value> {
+        None
+    }
+
     /// Return subtasks of this tasks.
     ///
     /// For instance, they will get aborted when this tasks gets aborted.
@@ -402,6 +412,7 @@ erased_serde::serialize_trait_object!(TaskStatusInfo);
 
 impl TaskStatusInfo for () {}
 impl TaskStatusInfo for String {}
+impl TaskStatusInfo for serde_json::Value {}
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema, IntoParams, Validate)]
 pub struct TaskListOptions {
@@ -0,0 +1,1692 @@
+use super::time_estimation::TimeEstimation;
+use super::{
+    RunningTaskStatusInfo, SimpleTaskManagerBackend, SimpleTaskManagerContext, Task,
+    TaskCleanUpStatus, TaskError, TaskFilter, TaskId, TaskListOptions, TaskManager, TaskStatus,
+    TaskStatusInfo, TaskStatusWithId,
+};
+use crate::error::Result;
+use crate::util::config::{self, get_config_element};
+use bb8_postgres::{
+    bb8::Pool,
+    tokio_postgres::{
+        error::SqlState,
+        tls::{MakeTlsConnect, TlsConnect},
+        Socket, Transaction,
+    },
+    PostgresConnectionManager,
+};
+use futures::channel::oneshot;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use geoengine_datatypes::primitives::DateTime;
+use geoengine_datatypes::util::Identifier;
+use log::{debug, warn};
+use postgres_types::{FromSql, ToSql};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Once, OnceLock, PoisonError, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// The interval in which the status of running tasks is checked for changes
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The interval in which instances look for queued tasks to claim
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const INTERRUPTED_TASK_ERROR: &str =
+    "The task was interrupted because the instance running it stopped";
+
+/// A [`TaskManager`] that persists its tasks in Postgres and shares a work queue between all instances that use the database.
+///
+/// While a task is unfinished, the instance running it holds a lease on it that it renews regularly.
+/// If the lease expires, e.g., because the instance crashed, the task is put into the queue
+/// if it has a [`Task::task_payload`] and is marked as failed otherwise.
+///
+/// Tasks with a payload whose type has a registered [`TaskLoader`] are put into the queue when they are scheduled.
+/// Each instance claims queued tasks of the types it can restore, up to its maximum number of claimed tasks.
+/// All other tasks run on the instance that schedules them.
+///
+/// Status requests, task listings and abort requests work across all instances that share the database.
+///
+#[derive(Clone)]
+pub struct PostgresTaskManagerBackend<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    local: SimpleTaskManagerBackend,
+    pool: Pool<PostgresConnectionManager<Tls>>,
+    instance_id: Uuid,
+    lease_duration: Duration,
+    loaders: Arc<RwLock<HashMap<String, TaskLoader>>>,
+    claimed_tasks: Arc<AtomicUsize>,
+    max_claimed_tasks: usize,
+    queue_worker: Arc<Once>,
+}
+
+/// Restores a queued task from its [`Task::task_payload`].
+pub type TaskLoader = Arc<
+    dyn Fn(serde_json::Value) -> BoxFuture<'static, Result<Box<dyn Task<SimpleTaskManagerContext>>>>
+        + Send
+        + Sync,
+>;
+
+/// The result of persisting the status of a task that this instance runs
+enum LeaseUpdate {
+    Renewed {
+        abort_requested: bool,
+        force_abort: bool,
+    },
+    Lost,
+}
+
+impl<Tls> PostgresTaskManagerBackend<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    pub fn new(
+        pool: Pool<PostgresConnectionManager<Tls>>,
+        lease_duration: Duration,
+        max_claimed_tasks: usize,
+    ) -> Self {
+        Self {
+            local: SimpleTaskManagerBackend::default(),
+            pool,
+            instance_id: Uuid::new_v4(),
+            lease_duration,
+            loaders: Default::default(),
+            claimed_tasks: Default::default(),
+            max_claimed_tasks,
+            queue_worker: Default::default(),
+        }
+    }
+
+    /// Create a task manager with the lease duration and queue settings of the [`config::TaskManager`] config.
+    pub fn new_with_config(pool: Pool<PostgresConnectionManager<Tls>>) -> Result<Self> {
+        let config = get_config_element::<config::TaskManager>()?;
+
+        Ok(Self::new(
+            pool,
+            Duration::from_secs(config.lease_duration_seconds),
+            config.max_claimed_tasks,
+        ))
+    }
+
+    /// Register the `loader` for restoring queued tasks of type `task_type`.
+    ///
+    /// This instance then claims queued tasks of this type in the background.
+    pub fn register_task_loader(&self, task_type: &'static str, loader: TaskLoader) {
+        self.loaders
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(task_type.to_string(), loader);
+
+        self.queue_worker.call_once(|| {
+            crate::util::spawn(self.clone().process_queue());
+        });
+    }
+
+    fn task_loader(&self, task_type: &str) -> Option<TaskLoader> {
+        self.loaders
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(task_type)
+            .cloned()
+    }
+
+    /// Regularly handle expired tasks and claim queued tasks.
+    async fn process_queue(self) {
+        loop {
+            if let Err(error) = self.expire_tasks().await {
+                warn!("failed to handle expired tasks: {error:?}");
+            }
+
+            if let Err(error) = self.claim_queued_tasks().await {
+                warn!("failed to claim queued tasks: {error:?}");
+            }
+
+            tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Handle all unfinished tasks whose lease expired.
+    ///
+    /// Running tasks with a payload are put into the queue again, so that any instance can claim and resume them.
+    /// All other tasks are marked as failed.
+    /// Tasks that another instance is currently handling are skipped instead of waiting for its lock.
+    pub async fn expire_tasks(&self) -> Result<(), TaskError> {
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        conn.execute(
+            r#"
+            WITH claimed AS (
+                SELECT
+                    id,
+                    payload IS NOT NULL AND status = 'Running' AND NOT abort_requested AS requeue
+                FROM tasks
+                WHERE NOT finished AND lease_expires < now()
+                FOR UPDATE SKIP LOCKED
+            ), requeued AS (
+                UPDATE tasks
+                SET
+                    time_updated = now(),
+                    lease_owner = NULL,
+                    lease_expires = NULL
+                WHERE id IN (SELECT id FROM claimed WHERE requeue)
+                RETURNING id
+            ), queued AS (
+                INSERT INTO task_queue (task_id, time_enqueued)
+                SELECT id, now()
+                FROM requeued
+            ), expired AS (
+                UPDATE tasks
+                SET
+                    status = CASE
+                        WHEN status = 'Running' THEN 'Failed'::"TaskStatusType"
+                        ELSE status
+                    END,
+                    finished = TRUE,
+                    status_json = CASE
+                        WHEN status = 'Running' THEN jsonb_build_object(
+                            'status', 'failed',
+                            'error', $1::text,
+                            'cleanUp', jsonb_build_object('status', 'noCleanUp')
+                        )
+                        ELSE jsonb_set(
+                            status_json,
+                            '{cleanUp}',
+                            jsonb_build_object('status', 'failed', 'error', $1::text)
+                        )
+                    END,
+                    time_updated = now(),
+                    lease_owner = NULL,
+                    lease_expires = NULL
+                WHERE id IN (SELECT id FROM claimed WHERE NOT requeue)
+                RETURNING id, status, status_json
+            )
+            INSERT INTO task_status_history (task_id, "time", status, status_json)
+            SELECT id, clock_timestamp(), status, status_json
+            FROM expired"#,
+            &[&INTERRUPTED_TASK_ERROR],
+        )
+        .await
+        .map_err(operation_failed)?;
+
+        Ok(())
+    }
+
+    /// List tasks like [`TaskManager::list_tasks`] but omit tasks of the given types.
+    pub async fn list_tasks_excluding(
+        &self,
+        options: TaskListOptions,
+        excluded_task_types: &[&str],
+    ) -> Result<Vec<TaskStatusWithId>, TaskError> {
+        self.expire_tasks().await?;
+
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        let rows = conn
+            .query(
+                r#"
+                SELECT id, status_json
+                FROM tasks
+                WHERE
+                    ($1::"TaskStatusType" IS NULL OR status = $1) AND
+                    NOT (task_type = ANY($2))
+                ORDER BY time_created DESC
+                OFFSET $3
+                LIMIT $4"#,
+                &[
+                    &options.filter.map(TaskStatusType::from),
+                    &excluded_task_types,
+                    &i64::from(options.offset),
+                    &i64::from(options.limit),
+                ],
+            )
+            .await
+            .map_err(operation_failed)?;
+
+        let mut tasks = Vec::with_capacity(rows.len());
+        for row in rows {
+            let task_id: TaskId = row.get(0);
+
+            // tasks of this instance have a more recent status in memory
+            let status = match self.local.get_task_status(task_id).await {
+                Ok(status) => status,
+                Err(_) => task_status_from_json(row.get(1))?,
+            };
+
+            tasks.push(TaskStatusWithId { task_id, status });
+        }
+
+        Ok(tasks)
+    }
+
+    /// The type of the task with id `task_id`
+    pub async fn task_type(&self, task_id: TaskId) -> Result<String, TaskError> {
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        let row = conn
+            .query_opt("SELECT task_type FROM tasks WHERE id = $1", &[&task_id])
+            .await
+            .map_err(operation_failed)?
+            .ok_or(TaskError::TaskNotFound { task_id })?;
+
+        Ok(row.get(0))
+    }
+
+    /// All status changes of the task with id `task_id` in chronological order
+    pub async fn task_status_history(
+        &self,
+        task_id: TaskId,
+    ) -> Result<Vec<(DateTime, TaskStatus)>, TaskError> {
+        self.expire_tasks().await?;
+
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        let rows = conn
+            .query(
+                r#"
+                SELECT "time", status_json
+                FROM task_status_history
+                WHERE task_id = $1
+                ORDER BY "time""#,
+                &[&task_id],
+            )
+            .await
+            .map_err(operation_failed)?;
+
+        if rows.is_empty() {
+            return Err(TaskError::TaskNotFound { task_id });
+        }
+
+        rows.into_iter()
+            .map(|row| Ok((row.get(0), task_status_from_json(row.get(1))?)))
+            .collect()
+    }
+
+    /// Fail with [`TaskError::DuplicateTask`] if an unfinished task with the same unique id exists.
+    async fn check_duplicate(
+        &self,
+        task_type: &'static str,
+        task_unique_id: &str,
+    ) -> Result<(), TaskError> {
+        self.expire_tasks().await?;
+
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        let rows = conn
+            .query(
+                "SELECT id FROM tasks WHERE task_type = $1 AND unique_id = $2 AND NOT finished",
+                &[&task_type, &task_unique_id],
+            )
+            .await
+            .map_err(operation_failed)?;
+
+        drop(conn);
+
+        for row in rows {
+            let task_id: TaskId = row.get(0);
+
+            // the task may have finished on this instance without the status being persisted yet
+            match self.local.get_task_status(task_id).await {
+                Ok(status) if status.is_finished() => {
+                    self.update_task(task_id, &status).await?;
+                }
+                _ => {
+                    return Err(TaskError::DuplicateTask {
+                        task_type,
+                        task_unique_id: task_unique_id.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Persist a new task that either runs on this instance or, if `queued`, waits in the queue.
+    async fn insert_task(
+        &self,
+        task_id: TaskId,
+        task: &NewTask,
+        status: &TaskStatus,
+        queued: bool,
+    ) -> Result<(), TaskError> {
+        let status_type = TaskStatusType::from(status);
+        let status_json = serde_json::to_value(status).map_err(operation_failed)?;
+
+        let mut conn = self.pool.get().await.map_err(operation_failed)?;
+        let tx = conn
+            .build_transaction()
+            .start()
+            .await
+            .map_err(operation_failed)?;
+
+        tx.execute(
+            "
+            INSERT INTO tasks (
+                id,
+                task_type,
+                unique_id,
+                description,
+                status,
+                finished,
+                status_json,
+                time_created,
+                time_updated,
+                lease_owner,
+                lease_expires,
+                payload
+            ) VALUES (
+                $1,
+                $2,
+                $3,
+                $4,
+                $5,
+                $6,
+                $7,
+                now(),
+                now(),
+                CASE WHEN $8 THEN NULL ELSE $9::uuid END,
+                CASE WHEN $8 THEN NULL ELSE now() + make_interval(secs => $10) END,
+                $11
+            )",
+            &[
+                &task_id,
+                &task.task_type,
+                &task.unique_id,
+                &task.description,
+                &status_type,
+                &status.is_finished(),
+                &status_json,
+                &queued,
+                &self.instance_id,
+                &self.lease_duration.as_secs_f64(),
+                &task.payload,
+            ],
+        )
+        .await
+        .map_err(|error| {
+            if error.code() == Some(&SqlState::UNIQUE_VIOLATION) {
+                TaskError::DuplicateTask {
+                    task_type: task.task_type,
+                    task_unique_id: task.unique_id.clone().unwrap_or_default(),
+                }
+            } else {
+                operation_failed(error)
+            }
+        })?;
+
+        if queued {
+            tx.execute(
+                "INSERT INTO task_queue (task_id, time_enqueued) VALUES ($1, now())",
+                &[&task_id],
+            )
+            .await
+            .map_err(operation_failed)?;
+        }
+
+        tx.execute(
+            r#"
+            INSERT INTO task_status_history (task_id, "time", status, status_json)
+            VALUES ($1, clock_timestamp(), $2, $3)"#,
+            &[&task_id, &status_type, &status_json],
+        )
+        .await
+        .map_err(operation_failed)?;
+
+        tx.commit().await.map_err(operation_failed)?;
+
+        Ok(())
+    }
+
+    /// Persist the status of a task that this instance runs and renew its lease.
+    ///
+    /// Returns whether another instance requested to abort the task.
+    ///
+    async fn update_task(
+        &self,
+        task_id: TaskId,
+        status: &TaskStatus,
+    ) -> Result<LeaseUpdate, TaskError> {
+        let status_type = TaskStatusType::from(status);
+        let finished = status.is_finished();
+        let status_json = serde_json::to_value(status).map_err(operation_failed)?;
+
+        let mut conn = self.pool.get().await.map_err(operation_failed)?;
+        let tx = conn
+            .build_transaction()
+            .start()
+            .await
+            .map_err(operation_failed)?;
+
+        let Some(row) = tx
+            .query_opt(
+                "
+                SELECT status, finished, abort_requested, force_abort
+                FROM tasks
+                WHERE id = $1 AND lease_owner = $2
+                FOR UPDATE",
+                &[&task_id, &self.instance_id],
+            )
+            .await
+            .map_err(operation_failed)?
+        else {
+            return Ok(LeaseUpdate::Lost);
+        };
+
+        let previous_status_type: TaskStatusType = row.get(0);
+        let previously_finished: bool = row.get(1);
+
+        tx.execute(
+            "
+            UPDATE tasks
+            SET
+                status = $2,
+                finished = $3,
+                status_json = $4,
+                time_updated = now(),
+                lease_owner = CASE WHEN $3 THEN NULL ELSE lease_owner END,
+                lease_expires = CASE
+                    WHEN $3 THEN NULL
+                    ELSE now() + make_interval(secs => $5)
+                END,
+                abort_requested = FALSE,
+                force_abort = FALSE
+            WHERE id = $1",
+            &[
+                &task_id,
+                &status_type,
+                &finished,
+                &status_json,
+                &self.lease_duration.as_secs_f64(),
+            ],
+        )
+        .await
+        .map_err(operation_failed)?;
+
+        if previous_status_type != status_type || previously_finished != finished {
+            tx.execute(
+                r#"
+                INSERT INTO task_status_history (task_id, "time", status, status_json)
+                VALUES ($1, clock_timestamp(), $2, $3)"#,
+                &[&task_id, &status_type, &status_json],
+            )
+            .await
+            .map_err(operation_failed)?;
+        }
+
+        tx.commit().await.map_err(operation_failed)?;
+
+        Ok(LeaseUpdate::Renewed {
+            abort_requested: row.get(2),
+            force_abort: row.get(3),
+        })
+    }
+
+    /// Persist the status of a local task until it is finished.
+    ///
+    /// This renews the lease of the task, executes abort requests of other instances and
+    /// forwards the result of the task run to `notify`.
+    ///
+    async fn watch_task(
+        self,
+        task_id: TaskId,
+        run_finished: oneshot::Receiver<TaskStatus>,
+        mut notify: Option<oneshot::Sender<TaskStatus>>,
+    ) {
+        let mut run_finished = run_finished.fuse();
+        let lease_renewal_interval = self.lease_duration / 3;
+
+        let mut last_persisted_status = None;
+        let mut last_persisted = Instant::now();
+        let mut finished_since: Option<Instant> = None;
+
+        loop {
+            let run_status = tokio::select! {
+                status = &mut run_finished => status.ok(),
+                () = tokio::time::sleep(STATUS_POLL_INTERVAL) => None,
+            };
+
+            let Ok(status) = self.local.get_task_status(task_id).await else {
+                return; // never happens
+            };
+
+            let finished = status.is_finished();
+            let status_json = serde_json::to_value(&status).ok();
+
+            let update = if run_status.is_some()
+                || finished
+                || status_json != last_persisted_status
+                || last_persisted.elapsed() >= lease_renewal_interval
+            {
+                Some(self.update_task(task_id, &status).await)
+            } else {
+                None
+            };
+
+            if let Some(run_status) = run_status {
+                if let Some(notify) = notify.take() {
+                    // we can ignore the returned error because this means
+                    // that the receiver has already been dropped
+                    notify.send(run_status).unwrap_or_default();
+                }
+            }
+
+            match update {
+                Some(Ok(LeaseUpdate::Renewed {
+                    abort_requested,
+                    force_abort,
+                })) => {
+                    if finished {
+                        return;
+                    }
+
+                    last_persisted_status = status_json;
+                    last_persisted = Instant::now();
+
+                    if abort_requested {
+                        if let Err(error) = self.local.abort_tasks(task_id, force_abort).await {
+                            warn!("failed to abort task {task_id} on request: {error:?}");
+                        }
+                    }
+                }
+                Some(Ok(LeaseUpdate::Lost)) => {
+                    // if the task is finished, its final status was already persisted
+                    if !finished {
+                        warn!("lost the lease of task {task_id}, aborting it");
+
+                        if let Err(error) = self.local.abort_tasks(task_id, true).await {
+                            debug!("failed to abort task {task_id}: {error:?}");
+                        }
+                    }
+
+                    return;
+                }
+                Some(Err(error)) => {
+                    warn!("failed to persist the status of task {task_id}: {error:?}");
+                }
+                None => {}
+            }
+
+            // stop retrying after the lease would have expired anyway
+            if finished
+                && finished_since.get_or_insert_with(Instant::now).elapsed() >= self.lease_duration
+            {
+                return;
+            }
+        }
+    }
+
+    /// Claim queued tasks that this instance can restore until it runs its maximum number of claimed tasks.
+    pub async fn claim_queued_tasks(&self) -> Result<(), TaskError> {
+        while self.claimed_tasks.load(Ordering::SeqCst) < self.max_claimed_tasks {
+            if !self.claim_queued_task().await? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Claim the oldest queued task that this instance can restore and run it.
+    ///
+    /// Tasks that another instance is currently claiming are skipped instead of waiting for its lock.
+    /// Returns `false` if there is no task to claim.
+    ///
+    async fn claim_queued_task(&self) -> Result<bool, TaskError> {
+        let task_types: Vec<String> = self
+            .loaders
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+
+        let mut conn = self.pool.get().await.map_err(operation_failed)?;
+        let tx = conn
+            .build_transaction()
+            .start()
+            .await
+            .map_err(operation_failed)?;
+
+        let Some(row) = tx
+            .query_opt(
+                "
+                SELECT t.id, t.task_type, t.payload
+                FROM task_queue q JOIN tasks t ON (q.task_id = t.id)
+                WHERE t.task_type = ANY($1) AND t.payload IS NOT NULL
+                ORDER BY q.time_enqueued
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED",
+                &[&task_types],
+            )
+            .await
+            .map_err(operation_failed)?
+        else {
+            return Ok(false);
+        };
+
+        let task_id: TaskId = row.get(0);
+        let task_type: String = row.get(1);
+        let payload: serde_json::Value = row.get(2);
+
+        let task = match self.task_loader(&task_type) {
+            Some(loader) => loader(payload).await,
+            None => return Ok(false), // never happens
+        };
+
+        let task = match task {
+            Ok(task) => task,
+            Err(error) => {
+                // no instance can restore the task, so it fails
+                let status = TaskStatus::failed(Arc::new(error), TaskCleanUpStatus::NoCleanUp);
+                finish_queued_task(&tx, task_id, &status).await?;
+                tx.commit().await.map_err(operation_failed)?;
+
+                return Ok(true);
+            }
+        };
+
+        tx.execute("DELETE FROM task_queue WHERE task_id = $1", &[&task_id])
+            .await
+            .map_err(operation_failed)?;
+
+        tx.execute(
+            "
+            UPDATE tasks
+            SET
+                time_updated = now(),
+                lease_owner = $2,
+                lease_expires = now() + make_interval(secs => $3)
+            WHERE id = $1",
+            &[
+                &task_id,
+                &self.instance_id,
+                &self.lease_duration.as_secs_f64(),
+            ],
+        )
+        .await
+        .map_err(operation_failed)?;
+
+        tx.commit().await.map_err(operation_failed)?;
+
+        let (run_finished_tx, run_finished_rx) = oneshot::channel();
+        if let Err(error) = self
+            .local
+            .schedule_task_with_id(task_id, task, Some(run_finished_tx))
+            .await
+        {
+            // the lease expires and the task is queued again
+            warn!("failed to run claimed task {task_id}: {error:?}");
+            return Ok(true);
+        }
+
+        self.claimed_tasks.fetch_add(1, Ordering::SeqCst);
+
+        let backend = self.clone();
+        crate::util::spawn(async move {
+            backend
+                .clone()
+                .watch_task(task_id, run_finished_rx, None)
+                .await;
+
+            backend.claimed_tasks.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        Ok(true)
+    }
+
+    /// Abort a task that waits in the queue.
+    ///
+    /// Returns `false` if the task is not queued.
+    ///
+    async fn abort_queued_task(&self, task_id: TaskId) -> Result<bool, TaskError> {
+        let mut conn = self.pool.get().await.map_err(operation_failed)?;
+        let tx = conn
+            .build_transaction()
+            .start()
+            .await
+            .map_err(operation_failed)?;
+
+        let queued = tx
+            .query_opt(
+                "SELECT task_id FROM task_queue WHERE task_id = $1 FOR UPDATE",
+                &[&task_id],
+            )
+            .await
+            .map_err(operation_failed)?
+            .is_some();
+
+        if !queued {
+            return Ok(false);
+        }
+
+        finish_queued_task(
+            &tx,
+            task_id,
+            &TaskStatus::aborted(TaskCleanUpStatus::NoCleanUp),
+        )
+        .await?;
+
+        tx.commit().await.map_err(operation_failed)?;
+
+        Ok(true)
+    }
+}
+
+/// Remove a task from the queue and persist its final `status`.
+async fn finish_queued_task(
+    tx: &Transaction<'_>,
+    task_id: TaskId,
+    status: &TaskStatus,
+) -> Result<(), TaskError> {
+    let status_type = TaskStatusType::from(status);
+    let status_json = serde_json::to_value(status).map_err(operation_failed)?;
+
+    tx.execute("DELETE FROM task_queue WHERE task_id = $1", &[&task_id])
+        .await
+        .map_err(operation_failed)?;
+
+    tx.execute(
+        "
+        UPDATE tasks
+        SET
+            status = $2,
+            finished = TRUE,
+            status_json = $3,
+            time_updated = now()
+        WHERE id = $1",
+        &[&task_id, &status_type, &status_json],
+    )
+    .await
+    .map_err(operation_failed)?;
+
+    tx.execute(
+        r#"
+        INSERT INTO task_status_history (task_id, "time", status, status_json)
+        VALUES ($1, clock_timestamp(), $2, $3)"#,
+        &[&task_id, &status_type, &status_json],
+    )
+    .await
+    .map_err(operation_failed)?;
+
+    Ok(())
+}
+
+/// The properties of a task that are persisted when it is scheduled
+struct NewTask {
+    task_type: &'static str,
+    unique_id: Option<String>,
+    description: String,
+    payload: Option<serde_json::Value>,
+}
+
+impl NewTask {
+    fn of(task: &dyn Task<SimpleTaskManagerContext>) -> Self {
+        Self {
+            task_type: task.task_type(),
+            unique_id: task.task_unique_id(),
+            description: task.task_description(),
+            payload: task.task_payload(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Tls> TaskManager<SimpleTaskManagerContext> for PostgresTaskManagerBackend<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    async fn schedule_task(
+        &self,
+        task: Box<dyn Task<SimpleTaskManagerContext>>,
+        notify: Option<oneshot::Sender<TaskStatus>>,
+    ) -> Result<TaskId, TaskError> {
+        let mut new_task = NewTask::of(task.as_ref());
+
+        // only tasks that the instances can restore are queued and resumed
+        if self.task_loader(new_task.task_type).is_none() {
+            new_task.payload = None;
+        }
+
+        if let Some(task_unique_id) = &new_task.unique_id {
+            self.check_duplicate(new_task.task_type, task_unique_id)
+                .await?;
+        }
+
+        // tasks whose result nobody awaits can run on any instance that can restore them
+        if notify.is_none() && new_task.payload.is_some() {
+            let task_id = TaskId::new();
+            let status = TaskStatus::Running(RunningTaskStatusInfo::new(
+                new_task.task_type,
+                Some(new_task.description.clone()),
+                0.,
+                ().boxed(),
+            ));
+
+            self.insert_task(task_id, &new_task, &status, true).await?;
+
+            if let Err(error) = self.claim_queued_tasks().await {
+                warn!("failed to claim queued tasks: {error:?}");
+            }
+
+            return Ok(task_id);
+        }
+
+        let (run_finished_tx, run_finished_rx) = oneshot::channel();
+        let task_id = self
+            .local
+            .schedule_task(task, Some(run_finished_tx))
+            .await?;
+
+        let status = self.local.get_task_status(task_id).await?;
+
+        if let Err(error) = self.insert_task(task_id, &new_task, &status, false).await {
+            // do not run tasks that are unknown to the other instances
+            if let Err(abort_error) = self.local.abort_tasks(task_id, true).await {
+                debug!("failed to abort task {task_id}: {abort_error:?}");
+            }
+
+            return Err(error);
+        }
+
+        crate::util::spawn(self.clone().watch_task(task_id, run_finished_rx, notify));
+
+        Ok(task_id)
+    }
+
+    async fn get_task_status(&self, task_id: TaskId) -> Result<TaskStatus, TaskError> {
+        match self.local.get_task_status(task_id).await {
+            Err(TaskError::TaskNotFound { .. }) => {}
+            result => return result,
+        }
+
+        self.expire_tasks().await?;
+
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        let row = conn
+            .query_opt("SELECT status_json FROM tasks WHERE id = $1", &[&task_id])
+            .await
+            .map_err(operation_failed)?
+            .ok_or(TaskError::TaskNotFound { task_id })?;
+
+        task_status_from_json(row.get(0))
+    }
+
+    async fn list_tasks(
+        &self,
+        options: TaskListOptions,
+    ) -> Result<Vec<TaskStatusWithId>, TaskError> {
+        self.list_tasks_excluding(options, &[]).await
+    }
+
+    async fn abort_tasks(&self, task_id: TaskId, force: bool) -> Result<(), TaskError> {
+        if self.local.get_task_status(task_id).await.is_ok() {
+            return self.local.abort_tasks(task_id, force).await;
+        }
+
+        self.expire_tasks().await?;
+
+        if self.abort_queued_task(task_id).await? {
+            return Ok(());
+        }
+
+        // the task runs on another instance, so we request it to abort the task
+        let conn = self.pool.get().await.map_err(operation_failed)?;
+
+        let row = conn
+            .query_opt(
+                "SELECT status, finished FROM tasks WHERE id = $1",
+                &[&task_id],
+            )
+            .await
+            .map_err(operation_failed)?
+            .ok_or(TaskError::TaskNotFound { task_id })?;
+
+        let status_type: TaskStatusType = row.get(0);
+        let finished: bool = row.get(1);
+
+        if finished {
+            return Err(TaskError::TaskAlreadyFinished { task_id });
+        } else if !force && status_type == TaskStatusType::Aborted {
+            return Err(TaskError::TaskAlreadyAborted { task_id });
+        }
+
+        conn.execute(
+            "
+            UPDATE tasks
+            SET abort_requested = TRUE, force_abort = force_abort OR $2
+            WHERE id = $1",
+            &[&task_id, &force],
+        )
+        .await
+        .map_err(operation_failed)?;
+
+        Ok(())
+    }
+}
+
+pub struct PostgresTaskManager<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    backend: Arc<PostgresTaskManagerBackend<Tls>>,
+}
+
+impl<Tls> PostgresTaskManager<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    pub fn new(backend: Arc<PostgresTaskManagerBackend<Tls>>) -> Self {
+        Self { backend }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Tls> TaskManager<SimpleTaskManagerContext> for PostgresTaskManager<Tls>
+where
+    Tls: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    <Tls as MakeTlsConnect<Socket>>::Stream: Send + Sync,
+    <Tls as MakeTlsConnect<Socket>>::TlsConnect: Send,
+    <<Tls as MakeTlsConnect<Socket>>::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    async fn schedule_task(
+        &self,
+        task: Box<dyn Task<SimpleTaskManagerContext>>,
+        notify: Option<oneshot::Sender<TaskStatus>>,
+    ) -> Result<TaskId, TaskError> {
+        self.backend.schedule_task(task, notify).await
+    }
+
+    async fn get_task_status(&self, task_id: TaskId) -> Result<TaskStatus, TaskError> {
+        self.backend.get_task_status(task_id).await
+    }
+
+    async fn list_tasks(
+        &self,
+        options: TaskListOptions,
+    ) -> Result<Vec<TaskStatusWithId>, TaskError> {
+        self.backend.list_tasks(options).await
+    }
+
+    async fn abort_tasks(&self, task_id: TaskId, force: bool) -> Result<(), TaskError> {
+        self.backend.abort_tasks(task_id, force).await
+    }
+}
+
+fn operation_failed<E>(error: E) -> TaskError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    TaskError::TaskManagerOperationFailed {
+        source: Box::new(error),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "TaskStatusType")]
+enum TaskStatusType {
+    Running,
+    Completed,
+    Aborted,
+    Failed,
+}
+
+impl From<&TaskStatus> for TaskStatusType {
+    fn from(status: &TaskStatus) -> Self {
+        match status {
+            TaskStatus::Running(_) => Self::Running,
+            TaskStatus::Completed { .. } => Self::Completed,
+            TaskStatus::Aborted { .. } => Self::Aborted,
+            TaskStatus::Failed { .. } => Self::Failed,
+        }
+    }
+}
+
+impl From<TaskFilter> for TaskStatusType {
+    fn from(filter: TaskFilter) -> Self {
+        match filter {
+            TaskFilter::Running => Self::Running,
+            TaskFilter::Completed => Self::Completed,
+            TaskFilter::Aborted => Self::Aborted,
+            TaskFilter::Failed => Self::Failed,
+        }
+    }
+}
+
+/// The serialized form of a [`TaskStatus`] as it is stored in the database
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+enum StoredTaskStatus {
+    Running(StoredRunningTaskStatusInfo),
+    #[serde(rename_all = "camelCase")]
+    Completed {
+        task_type: String,
+        description: Option<String>,
+        #[serde(default)]
+        info: serde_json::Value,
+        time_total: String,
+        time_started: DateTime,
+    },
+    #[serde(rename_all = "camelCase")]
+    Aborted {
+        clean_up: StoredTaskCleanUpStatus,
+    },
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        error: String,
+        clean_up: StoredTaskCleanUpStatus,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+enum StoredTaskCleanUpStatus {
+    NoCleanUp,
+    Running(StoredRunningTaskStatusInfo),
+    Completed {
+        #[serde(default)]
+        info: serde_json::Value,
+    },
+    Aborted {
+        #[serde(default)]
+        info: serde_json::Value,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredRunningTaskStatusInfo {
+    task_type: String,
+    description: Option<String>,
+    pct_complete: String,
+    time_started: DateTime,
+    #[serde(default)]
+    info: serde_json::Value,
+}
+
+/// The error of a failed task that ran on another instance
+#[derive(Debug)]
+struct StoredTaskError(String);
+
+impl std::fmt::Display for StoredTaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for StoredTaskError {}
+
+fn task_status_from_json(status_json: serde_json::Value) -> Result<TaskStatus, TaskError> {
+    let stored_status: StoredTaskStatus =
+        serde_json::from_value(status_json).map_err(operation_failed)?;
+
+    Ok(stored_status.into())
+}
+
+impl From<StoredTaskStatus> for TaskStatus {
+    fn from(status: StoredTaskStatus) -> Self {
+        match status {
+            StoredTaskStatus::Running(info) => TaskStatus::Running(info.into()),
+            StoredTaskStatus::Completed {
+                task_type,
+                description,
+                info,
+                time_total,
+                time_started,
+            } => TaskStatus::Completed {
+                task_type: static_task_type(task_type),
+                description,
+                info: Arc::new(info),
+                time_total,
+                time_started,
+            },
+            StoredTaskStatus::Aborted { clean_up } => TaskStatus::aborted(clean_up.into()),
+            StoredTaskStatus::Failed { error, clean_up } => {
+                TaskStatus::failed(Arc::new(StoredTaskError(error)), clean_up.into())
+            }
+        }
+    }
+}
+
+impl From<StoredTaskCleanUpStatus> for TaskCleanUpStatus {
+    fn from(status: StoredTaskCleanUpStatus) -> Self {
+        match status {
+            StoredTaskCleanUpStatus::NoCleanUp => TaskCleanUpStatus::NoCleanUp,
+            StoredTaskCleanUpStatus::Running(info) => TaskCleanUpStatus::Running(info.into()),
+            StoredTaskCleanUpStatus::Completed { info } => TaskCleanUpStatus::Completed {
+                info: Arc::new(info.boxed()),
+            },
+            StoredTaskCleanUpStatus::Aborted { info } => TaskCleanUpStatus::Aborted {
+                info: Arc::new(info.boxed()),
+            },
+            StoredTaskCleanUpStatus::Failed { error } => TaskCleanUpStatus::Failed {
+                error: Arc::new(StoredTaskError(error)),
+            },
+        }
+    }
+}
+
+impl From<StoredRunningTaskStatusInfo> for Arc<RunningTaskStatusInfo> {
+    fn from(info: StoredRunningTaskStatusInfo) -> Self {
+        let pct_complete = info
+            .pct_complete
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_or(0., |pct| pct / 100.);
+
+        Arc::new(RunningTaskStatusInfo {
+            task_type: static_task_type(info.task_type),
+            description: info.description,
+            pct_complete,
+            time_started: info.time_started,
+            estimated_time_remaining: TimeEstimation::started_at(info.time_started),
+            info: info.info.boxed(),
+        })
+    }
+}
+
+/// Task types are `&'static str`s, so we leak each distinct task type read from the database once.
+fn static_task_type(task_type: String) -> &'static str {
+    static TASK_TYPES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut task_types = TASK_TYPES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(task_type) = task_types.get(task_type.as_str()) {
+        return task_type;
+    }
+
+    let task_type: &'static str = Box::leak(task_type.into_boxed_str());
+    task_types.insert(task_type);
+
+    task_type
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contexts::PostgresContext;
+    use crate::ge_context;
+    use crate::tasks::util::test::wait_for_task_to_finish;
+    use geoengine_datatypes::error::ErrorSource;
+    use serde_json::json;
+    use tokio::sync::Notify;
+    use tokio_postgres::NoTls;
+
+    /// A task that completes after being notified
+    struct TestTask {
+        complete: Arc<Notify>,
+    }
+
+    #[async_trait::async_trait]
+    impl Task<SimpleTaskManagerContext> for TestTask {
+        async fn run(
+            &self,
+            _ctx: SimpleTaskManagerContext,
+        ) -> Result<Box<dyn TaskStatusInfo>, Box<dyn ErrorSource>> {
+            self.complete.notified().await;
+
+            Ok("done".to_string().boxed())
+        }
+
+        async fn cleanup_on_error(
+            &self,
+            _ctx: SimpleTaskManagerContext,
+        ) -> Result<(), Box<dyn ErrorSource>> {
+            Ok(())
+        }
+
+        fn task_type(&self) -> &'static str {
+            "testTask"
+        }
+
+        fn task_description(&self) -> String {
+            "A test task".to_string()
+        }
+    }
+
+    /// A task that any instance can restore and that completes with its payload
+    struct ResumableTask {
+        info: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Task<SimpleTaskManagerContext> for ResumableTask {
+        async fn run(
+            &self,
+            _ctx: SimpleTaskManagerContext,
+        ) -> Result<Box<dyn TaskStatusInfo>, Box<dyn ErrorSource>> {
+            Ok(self.info.clone().boxed())
+        }
+
+        async fn cleanup_on_error(
+            &self,
+            _ctx: SimpleTaskManagerContext,
+        ) -> Result<(), Box<dyn ErrorSource>> {
+            Ok(())
+        }
+
+        fn task_type(&self) -> &'static str {
+            "resumableTask"
+        }
+
+        fn task_description(&self) -> String {
+            "A resumable task".to_string()
+        }
+
+        fn task_payload(&self) -> Option<serde_json::Value> {
+            Some(json!({ "info": self.info }))
+        }
+    }
+
+    fn resumable_task_loader() -> TaskLoader {
+        Arc::new(|payload: serde_json::Value| {
+            async move {
+                let info = payload["info"].as_str().unwrap_or_default().to_string();
+                Ok::<_, crate::error::Error>(ResumableTask { info }.boxed())
+            }
+            .boxed()
+        })
+    }
+
+    async fn task_manager(
+        pg_config: &tokio_postgres::Config,
+        lease_duration: Duration,
+        max_claimed_tasks: usize,
+    ) -> PostgresTaskManagerBackend<NoTls> {
+        let pool = Pool::builder()
+            .build(PostgresConnectionManager::new(pg_config.clone(), NoTls))
+            .await
+            .unwrap();
+
+        PostgresTaskManagerBackend::new(pool, lease_duration, max_claimed_tasks)
+    }
+
+    async fn wait_for_status<F>(
+        task_manager: &PostgresTaskManagerBackend<NoTls>,
+        task_id: TaskId,
+        predicate: F,
+    ) -> TaskStatus
+    where
+        F: Fn(&TaskStatus) -> bool,
+    {
+        geoengine_operators::util::retry::retry(10, 100, 2., None, || async {
+            let status = task_manager.get_task_status(task_id).await.unwrap();
+            if predicate(&status) {
+                Ok(status)
+            } else {
+                Err(())
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[ge_context::test]
+    async fn it_shares_task_status_between_instances(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        let instance_a = Arc::new(task_manager(&pg_config, Duration::from_secs(60), 1).await);
+        let instance_b = task_manager(&pg_config, Duration::from_secs(60), 1).await;
+
+        let complete = Arc::new(Notify::new());
+        let task_id = instance_a
+            .schedule_task(
+                TestTask {
+                    complete: complete.clone(),
+                }
+                .boxed(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        complete.notify_one();
+
+        wait_for_task_to_finish(instance_a.clone(), task_id).await;
+
+        let status = wait_for_status(&instance_b, task_id, TaskStatus::is_finished).await;
+
+        let status_json = serde_json::to_value(&status).unwrap();
+        assert_eq!(status_json["status"], json!("completed"));
+        assert_eq!(status_json["taskType"], json!("testTask"));
+        assert_eq!(status_json["description"], json!("A test task"));
+        assert_eq!(status_json["info"], json!("done"));
+
+        let list = instance_b
+            .list_tasks(TaskListOptions {
+                filter: Some(TaskFilter::Completed),
+                offset: 0,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].task_id, task_id);
+
+        let history = instance_b.task_status_history(task_id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history[0].1.is_running());
+        assert!(history[1].1.is_completed());
+        assert!(history[0].0 <= history[1].0);
+    }
+
+    #[ge_context::test]
+    async fn it_fails_tasks_of_stopped_instances(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        let task_manager = task_manager(&pg_config, Duration::from_secs(60), 1).await;
+
+        // simulate a task of an instance that stopped without finishing it
+        let task_id = TaskId::new();
+        let running_status = TaskStatus::Running(RunningTaskStatusInfo::new(
+            "testTask",
+            Some("A test task".to_string()),
+            0.5,
+            ().boxed(),
+        ));
+
+        let conn = task_manager.pool.get().await.unwrap();
+        conn.execute(
+            "
+            INSERT INTO tasks (
+                id,
+                task_type,
+                description,
+                status,
+                finished,
+                status_json,
+                time_created,
+                time_updated,
+                lease_owner,
+                lease_expires
+            ) VALUES (
+                $1, 'testTask', 'A test task', 'Running', FALSE, $2, now(), now(), $3, now() - interval '1 second'
+            )",
+            &[
+                &task_id,
+                &serde_json::to_value(&running_status).unwrap(),
+                &Uuid::new_v4(),
+            ],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+
+        assert!(matches!(
+            task_manager.abort_tasks(task_id, false).await,
+            Err(TaskError::TaskAlreadyFinished { .. })
+        ));
+
+        let status = task_manager.get_task_status(task_id).await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            json!({
+                "status": "failed",
+                "error": INTERRUPTED_TASK_ERROR,
+                "cleanUp": {"status": "noCleanUp"}
+            })
+        );
+
+        let list = task_manager
+            .list_tasks(TaskListOptions {
+                filter: Some(TaskFilter::Failed),
+                offset: 0,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].task_id, task_id);
+    }
+
+    #[ge_context::test]
+    async fn it_aborts_tasks_of_other_instances(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        let instance_a = task_manager(&pg_config, Duration::from_secs(3), 1).await;
+        let instance_b = task_manager(&pg_config, Duration::from_secs(3), 1).await;
+
+        let task_id = instance_a
+            .schedule_task(
+                TestTask {
+                    complete: Arc::new(Notify::new()),
+                }
+                .boxed(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(instance_b
+            .get_task_status(task_id)
+            .await
+            .unwrap()
+            .is_running());
+
+        instance_b.abort_tasks(task_id, true).await.unwrap();
+
+        let status = wait_for_status(&instance_b, task_id, TaskStatus::is_finished).await;
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            json!({
+                "status": "aborted",
+                "cleanUp": {"status": "noCleanUp"}
+            })
+        );
+    }
+
+    #[ge_context::test]
+    async fn it_rejects_duplicate_tasks_of_other_instances(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        struct UniqueTask;
+
+        #[async_trait::async_trait]
+        impl Task<SimpleTaskManagerContext> for UniqueTask {
+            async fn run(
+                &self,
+                _ctx: SimpleTaskManagerContext,
+            ) -> Result<Box<dyn TaskStatusInfo>, Box<dyn ErrorSource>> {
+                futures::future::pending::<()>().await;
+                Ok(().boxed())
+            }
+
+            async fn cleanup_on_error(
+                &self,
+                _ctx: SimpleTaskManagerContext,
+            ) -> Result<(), Box<dyn ErrorSource>> {
+                Ok(())
+            }
+
+            fn task_type(&self) -> &'static str {
+                "uniqueTask"
+            }
+
+            fn task_unique_id(&self) -> Option<String> {
+                Some("highlander".to_string())
+            }
+
+            fn task_description(&self) -> String {
+                "A unique task".to_string()
+            }
+        }
+
+        let instance_a = task_manager(&pg_config, Duration::from_secs(60), 1).await;
+        let instance_b = task_manager(&pg_config, Duration::from_secs(60), 1).await;
+
+        let task_id = instance_a
+            .schedule_task(UniqueTask.boxed(), None)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            instance_b.schedule_task(UniqueTask.boxed(), None).await,
+            Err(TaskError::DuplicateTask { .. })
+        ));
+
+        instance_a.abort_tasks(task_id, true).await.unwrap();
+    }
+
+    #[ge_context::test]
+    async fn it_shares_queued_tasks_between_instances(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        // instance a has no capacity for claiming tasks, so instance b has to run it
+        let instance_a = task_manager(&pg_config, Duration::from_secs(60), 0).await;
+        let instance_b = task_manager(&pg_config, Duration::from_secs(60), 1).await;
+
+        instance_a.register_task_loader("resumableTask", resumable_task_loader());
+        instance_b.register_task_loader("resumableTask", resumable_task_loader());
+
+        let task_id = instance_a
+            .schedule_task(
+                ResumableTask {
+                    info: "shared".to_string(),
+                }
+                .boxed(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        instance_b.claim_queued_tasks().await.unwrap();
+
+        let status = wait_for_status(&instance_a, task_id, TaskStatus::is_finished).await;
+
+        let status_json = serde_json::to_value(&status).unwrap();
+        assert_eq!(status_json["status"], json!("completed"));
+        assert_eq!(status_json["taskType"], json!("resumableTask"));
+        assert_eq!(status_json["info"], json!("shared"));
+
+        let conn = instance_a.pool.get().await.unwrap();
+        let queued = conn
+            .query_one("SELECT count(*) FROM task_queue", &[])
+            .await
+            .unwrap();
+        assert_eq!(queued.get::<_, i64>(0), 0);
+    }
+
+    #[ge_context::test]
+    async fn it_resumes_tasks_of_stopped_instances(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        let task_manager = task_manager(&pg_config, Duration::from_secs(60), 1).await;
+        task_manager.register_task_loader("resumableTask", resumable_task_loader());
+
+        // simulate a resumable task of an instance that stopped without finishing it
+        let task_id = TaskId::new();
+        let running_status = TaskStatus::Running(RunningTaskStatusInfo::new(
+            "resumableTask",
+            Some("A resumable task".to_string()),
+            0.5,
+            ().boxed(),
+        ));
+
+        let conn = task_manager.pool.get().await.unwrap();
+        conn.execute(
+            "
+            INSERT INTO tasks (
+                id,
+                task_type,
+                description,
+                status,
+                finished,
+                status_json,
+                time_created,
+                time_updated,
+                lease_owner,
+                lease_expires,
+                payload
+            ) VALUES (
+                $1, 'resumableTask', 'A resumable task', 'Running', FALSE, $2, now(), now(), $3, now() - interval '1 second', $4
+            )",
+            &[
+                &task_id,
+                &serde_json::to_value(&running_status).unwrap(),
+                &Uuid::new_v4(),
+                &json!({ "info": "resumed" }),
+            ],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+
+        task_manager.expire_tasks().await.unwrap();
+        task_manager.claim_queued_tasks().await.unwrap();
+
+        let status = wait_for_status(&task_manager, task_id, TaskStatus::is_finished).await;
+
+        let status_json = serde_json::to_value(&status).unwrap();
+        assert_eq!(status_json["status"], json!("completed"));
+        assert_eq!(status_json["info"], json!("resumed"));
+    }
+
+    #[ge_context::test]
+    async fn it_aborts_queued_tasks(
+        _app_ctx: PostgresContext<NoTls>,
+        pg_config: tokio_postgres::Config,
+    ) {
+        let task_manager = task_manager(&pg_config, Duration::from_secs(60), 0).await;
+        task_manager.register_task_loader("resumableTask", resumable_task_loader());
+
+        let task_id = task_manager
+            .schedule_task(
+                ResumableTask {
+                    info: "never runs".to_string(),
+                }
+                .boxed(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(task_manager
+            .get_task_status(task_id)
+            .await
+            .unwrap()
+            .is_running());
+
+        task_manager.abort_tasks(task_id, false).await.unwrap();
+
+        let status = task_manager.get_task_status(task_id).await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            json!({
+                "status": "aborted",
+                "cleanUp": {"status": "noCleanUp"}
+            })
+        );
+    }
+}
@@ -20,6 +20,15 @@ impl TimeEstimation {
         }
     }
 
+    /// An estimation for a task that started at `time_started` without any progress information
+    pub fn started_at(time_started: DateTime) -> Self {
+        Self {
+            initial_time: time_started,
+            estimate_seconds_per_pct: NumberStatistics::default(),
+            time_estimate_seconds: (None, None),
+        }
+    }
+
     pub fn update(&mut self, time_stamp: DateTime, pct_complete: f64) {
         let pct_complete = pct_complete.clamp(0., 1.);
 
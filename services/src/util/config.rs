@@ -4,6 +4,7 @@ use crate::error::{self, Result};
 use crate::util::parsing::{deserialize_api_prefix, deserialize_base_url_option};
 use config::{Config, Environment, File};
 use geoengine_datatypes::primitives::TimeInterval;
+use geoengine_operators::processing::ExpressionBackend;
 use geoengine_operators::util::raster_stream_to_geotiff::GdalCompressionNumThreads;
 use serde::Deserialize;
 use snafu::ResultExt;
@@ -228,6 +229,10 @@ impl ConfigElement for LayerService {
 pub struct TaskManager {
     pub list_limit: u32,
     pub list_default_limit: u32,
+    /// The time after which unfinished tasks of an unresponsive instance are queued again or marked as failed
+    pub lease_duration_seconds: u64,
+    /// The maximum number of queued tasks that an instance claims and runs at the same time
+    pub max_claimed_tasks: usize,
 }
 
 impl ConfigElement for TaskManager {
@@ -237,6 +242,8 @@ impl ConfigElement for TaskManager {
 #[derive(Debug, Deserialize)]
 pub struct Upload {
     pub path: PathBuf,
+    /// The maximum size of all uploaded files of a user, unlimited if not set
+    pub user_quota_bytes: Option<u64>,
 }
 
 impl ConfigElement for Upload {
@@ -368,6 +375,15 @@ impl ConfigElement for Gdal {
     const KEY: &'static str = "gdal";
 }
 
+#[derive(Debug, Deserialize)]
+pub struct Expression {
+    pub backend: ExpressionBackend,
+}
+
+impl ConfigElement for Expression {
+    const KEY: &'static str = "expression";
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Session {
     pub anonymous_access: bool,
@@ -1,4 +1,5 @@
 mod postgres_workflow_registry;
+pub mod provenance;
 mod raster_stream;
 pub mod registry;
 mod vector_stream;
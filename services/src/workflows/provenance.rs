@@ -0,0 +1,77 @@
+use crate::contexts::SessionContext;
+use crate::datasets::listing::{DatasetProvider, Provenance, ProvenanceOutput};
+use crate::error::Result;
+use crate::layers::storage::LayerProviderDb;
+use crate::workflows::workflow::Workflow;
+use futures::future::join_all;
+use geoengine_datatypes::dataset::DataId;
+use geoengine_operators::engine::{ExecutionContext, OperatorData};
+use std::collections::HashMap;
+
+/// A provenance of the data that a workflow uses and the data it applies to
+pub struct WorkflowProvenance {
+    pub provenance: Provenance,
+    pub data: Vec<DataId>,
+}
+
+/// Gets the provenance of all data used in the `workflow`, grouped by provenance.
+pub async fn workflow_provenance<C: SessionContext>(
+    workflow: &Workflow,
+    ctx: &C,
+) -> Result<Vec<WorkflowProvenance>> {
+    let db = ctx.db();
+    let execution_ctx = ctx.execution_context()?;
+
+    let data_names = workflow.operator.data_names();
+    let mut datasets = Vec::<DataId>::with_capacity(data_names.len());
+    for data_name in data_names {
+        datasets.push(execution_ctx.resolve_named_data(&data_name).await?);
+    }
+
+    let provenance: Vec<_> = datasets
+        .iter()
+        .map(|id| resolve_provenance::<C>(&db, id))
+        .collect();
+    let provenance: Result<Vec<_>> = join_all(provenance).await.into_iter().collect();
+
+    // Filter duplicate DataIds and missing Provenance
+    let provenance: HashMap<DataId, Vec<Provenance>> = provenance?
+        .into_iter()
+        .filter_map(|p| {
+            if let Some(provenance) = p.provenance {
+                Some((p.data, provenance))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Group DataIds by Provenance
+    let mut result: HashMap<Provenance, Vec<DataId>> = HashMap::new();
+    for (data, provenances) in provenance {
+        for item in provenances {
+            result.entry(item).or_default().push(data.clone());
+        }
+    }
+    let result = result
+        .into_iter()
+        .map(|(provenance, data)| WorkflowProvenance { provenance, data })
+        .collect();
+
+    Ok(result)
+}
+
+async fn resolve_provenance<C: SessionContext>(
+    db: &C::GeoEngineDB,
+    id: &DataId,
+) -> Result<ProvenanceOutput> {
+    match id {
+        DataId::Internal { dataset_id } => db.load_provenance(dataset_id).await,
+        DataId::External(e) => {
+            db.load_layer_provider(e.provider_id)
+                .await?
+                .provenance(id)
+                .await
+        }
+    }
+}